@@ -0,0 +1,82 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::types::AccountId;
+use std::collections::HashMap;
+
+/// Contract mock registry.
+///
+/// Allows to register contract mocks under an account and call them.
+pub struct ContractMockHandler {
+    /// The currently registered contract mocks.
+    registered: HashMap<AccountId, Box<dyn ContractMock>>,
+    /// The output buffer used and reused for contract mock call results.
+    output: Vec<u8>,
+}
+
+/// Types implementing this trait can be called as if they were contracts.
+///
+/// This trait is only useful for testing contract via the off-chain environment.
+pub trait ContractMock {
+    /// Calls the contract mock with the given input.
+    ///
+    /// The `input` starts with the selector of the called message followed by its
+    /// SCALE encoded arguments. The mock may fill the `output` buffer with the SCALE
+    /// encoded result of the call.
+    #[allow(clippy::ptr_arg)]
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>);
+}
+
+impl Default for ContractMockHandler {
+    fn default() -> Self {
+        ContractMockHandler::new()
+    }
+}
+
+impl ContractMockHandler {
+    /// Creates a new contract mock handler.
+    ///
+    /// Initialized with an empty set of contract mocks.
+    pub fn new() -> Self {
+        Self {
+            registered: HashMap::new(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Resets the contract mock handler to uninitialized state.
+    pub fn reset(&mut self) {
+        self.registered.clear();
+        self.output.clear();
+    }
+
+    /// Registers a new contract mock under `account_id`.
+    ///
+    /// Replaces the contract mock previously registered under `account_id`.
+    pub fn register(&mut self, account_id: Vec<u8>, mock: Box<dyn ContractMock>) {
+        self.registered.insert(AccountId::from(account_id), mock);
+    }
+
+    /// Calls the contract mock registered under `account_id` with the given `input`.
+    ///
+    /// Returns `None` if there is no contract mock registered under `account_id`.
+    pub fn eval(&mut self, account_id: &[u8], input: &[u8]) -> Option<&[u8]> {
+        self.output.clear();
+        let mock = self
+            .registered
+            .get_mut(&AccountId::from_bytes(account_id))?;
+        mock.call(input, &mut self.output);
+        Some(&self.output)
+    }
+}
@@ -21,7 +21,7 @@ use super::types::{
 
 /// The context of a contract execution.
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ExecContext {
     /// The caller of the contract execution. Might be user or another contract.
     ///
@@ -43,6 +43,16 @@ pub struct ExecContext {
     pub block_number: BlockNumber,
     /// The current block timestamp.
     pub block_timestamp: BlockTimestamp,
+    /// If the block timestamp is frozen, i.e. not advanced together with blocks.
+    pub time_frozen: bool,
+    /// If the contract execution must not change any state, e.g. because it is
+    /// executed as part of a read-only call.
+    pub read_only: bool,
+    /// The gas charged so far, e.g. for the declared weights of chain extension
+    /// method calls.
+    pub gas_consumed: u64,
+    /// The gas limit of the call, `None` if the gas is unlimited.
+    pub gas_limit: Option<u64>,
 }
 
 impl ExecContext {
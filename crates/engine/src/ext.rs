@@ -19,8 +19,13 @@
 
 use crate::{
     chain_extension::ChainExtensionHandler,
+    contract_mock::ContractMockHandler,
     database::Database,
     exec_context::ExecContext,
+    replay::{
+        CallInput,
+        HostCallRecorder,
+    },
     test_api::{
         DebugInfo,
         EmittedEvent,
@@ -31,8 +36,14 @@ use crate::{
         BlockTimestamp,
     },
 };
-use scale::Encode;
-use std::panic::panic_any;
+use scale::{
+    Decode,
+    Encode,
+};
+use std::{
+    cell::RefCell,
+    panic::panic_any,
+};
 
 type Result = core::result::Result<(), Error>;
 
@@ -45,7 +56,7 @@ macro_rules! define_error_codes {
     ) => {
         /// Every error that can be returned to a contract when it calls any of the host functions.
         #[cfg_attr(test, derive(PartialEq, Eq))]
-        #[derive(Debug)]
+        #[derive(Debug, scale::Encode, scale::Decode)]
         #[repr(u32)]
         pub enum Error {
             $(
@@ -124,9 +135,23 @@ pub struct Engine {
     pub chain_spec: ChainSpec,
     /// Handler for registered chain extensions.
     pub chain_extension_handler: ChainExtensionHandler,
+    /// Handler for registered contract mocks.
+    pub contract_mock_handler: ContractMockHandler,
+    /// Recorder of the host calls for recording and replaying test runs.
+    pub(crate) host_calls: RefCell<HostCallRecorder>,
+    /// The recorded inputs dispatched to contracts.
+    ///
+    /// Is `None` if call inputs are not recorded.
+    pub(crate) call_inputs: Option<Vec<CallInput>>,
+    /// The emitted events which have not been taken for notification yet.
+    ///
+    /// Is `None` if events are not queued for notification.
+    pub(crate) event_queue: Option<Vec<EmittedEvent>>,
 }
 
 /// The chain specification.
+#[derive(Clone)]
+#[non_exhaustive]
 pub struct ChainSpec {
     /// The current gas price.
     pub gas_price: Balance,
@@ -135,6 +160,10 @@ pub struct ChainSpec {
     pub minimum_balance: Balance,
     /// The targeted block time.
     pub block_time: BlockTimestamp,
+    /// The storage deposit held for every storage entry.
+    pub deposit_per_item: Balance,
+    /// The storage deposit held for every byte of a storage entry.
+    pub deposit_per_byte: Balance,
 }
 
 /// The default values for the chain specification are:
@@ -142,6 +171,8 @@ pub struct ChainSpec {
 ///   * `gas_price`: 100
 ///   * `minimum_balance`: 42
 ///   * `block_time`: 6
+///   * `deposit_per_item`: 10
+///   * `deposit_per_byte`: 1
 ///
 /// There is no particular reason behind choosing them this way.
 impl Default for ChainSpec {
@@ -150,6 +181,8 @@ impl Default for ChainSpec {
             gas_price: 100,
             minimum_balance: 1000000,
             block_time: 6,
+            deposit_per_item: 10,
+            deposit_per_byte: 1,
         }
     }
 }
@@ -163,10 +196,82 @@ impl Engine {
             debug_info: DebugInfo::new(),
             chain_spec: ChainSpec::default(),
             chain_extension_handler: ChainExtensionHandler::new(),
+            contract_mock_handler: ContractMockHandler::new(),
+            host_calls: RefCell::new(HostCallRecorder::new()),
+            call_inputs: None,
+            event_queue: None,
+        }
+    }
+
+    /// Executes the host function `f` under the current host call mode.
+    ///
+    /// When recording, the result of `f` is appended to the recording. When
+    /// replaying, the recorded result is returned instead. `f` is still executed
+    /// in this case in order to keep the state of the engine in sync unless
+    /// `replay` is [`Replay::Skip`].
+    fn host_call<R, F>(&mut self, function: &str, input: &[u8], replay: Replay, f: F) -> R
+    where
+        R: Encode + Decode,
+        F: FnOnce(&mut Self) -> R,
+    {
+        if let Some(output) = self.host_calls.get_mut().pop(function, input) {
+            if let Replay::Execute = replay {
+                let _ = f(self);
+            }
+            return decode_recorded(function, &output)
+        }
+        let result = f(self);
+        let host_calls = self.host_calls.get_mut();
+        if host_calls.is_recording() {
+            host_calls.push(function, input, result.encode());
+        }
+        result
+    }
+
+    /// Executes the host function `f` which does not change the state of the engine
+    /// under the current host call mode.
+    ///
+    /// `f` is never executed when replaying.
+    fn host_query<R, F>(&self, function: &str, input: &[u8], f: F) -> R
+    where
+        R: Encode + Decode,
+        F: FnOnce(&Self) -> R,
+    {
+        let replayed = self.host_calls.borrow_mut().pop(function, input);
+        if let Some(output) = replayed {
+            return decode_recorded(function, &output)
         }
+        let result = f(self);
+        let mut host_calls = self.host_calls.borrow_mut();
+        if host_calls.is_recording() {
+            host_calls.push(function, input, result.encode());
+        }
+        result
     }
 }
 
+/// Whether a host function is executed when it is replayed.
+#[derive(Copy, Clone)]
+enum Replay {
+    /// The host function is executed and its result replaced by the recorded one.
+    Execute,
+    /// The host function is not executed.
+    ///
+    /// Used for host functions which call into user provided handlers, such as
+    /// chain extensions and contract mocks, in order to not depend on them.
+    Skip,
+}
+
+/// Decodes the recorded result of a call to the host `function`.
+fn decode_recorded<R: Decode>(function: &str, mut output: &[u8]) -> R {
+    <R as Decode>::decode(&mut output).unwrap_or_else(|error| {
+        panic!(
+            "could not decode the recorded result of `{}`: {:?}",
+            function, error
+        )
+    })
+}
+
 impl Default for Engine {
     fn default() -> Self {
         Self::new()
@@ -175,7 +280,15 @@ impl Default for Engine {
 
 impl Engine {
     /// Transfers value from the contract to the destination account.
-    pub fn transfer(&mut self, account_id: &[u8], mut value: &[u8]) -> Result {
+    pub fn transfer(&mut self, account_id: &[u8], value: &[u8]) -> Result {
+        self.ensure_state_change_allowed("transfer");
+        let input = (account_id, value).encode();
+        self.host_call("transfer", &input, Replay::Execute, |engine| {
+            engine.transfer_value(account_id, value)
+        })
+    }
+
+    fn transfer_value(&mut self, account_id: &[u8], mut value: &[u8]) -> Result {
         // Note that a transfer of `0` is allowed here
         let increment = <u128 as scale::Decode>::decode(&mut value)
             .map_err(|_| Error::TransferFailed)?;
@@ -198,6 +311,14 @@ impl Engine {
 
     /// Deposits an event identified by the supplied topics and data.
     pub fn deposit_event(&mut self, topics: &[u8], data: &[u8]) {
+        self.ensure_state_change_allowed("deposit_event");
+        let input = (topics, data).encode();
+        self.host_call("deposit_event", &input, Replay::Execute, |engine| {
+            engine.record_event(topics, data)
+        })
+    }
+
+    fn record_event(&mut self, topics: &[u8], data: &[u8]) {
         // The first byte contains the number of topics in the slice
         let topics_count: scale::Compact<u32> = scale::Decode::decode(&mut &topics[0..1])
             .unwrap_or_else(|err| panic!("decoding number of topics failed: {}", err));
@@ -217,81 +338,189 @@ impl Engine {
             Vec::new()
         };
 
-        self.debug_info.record_event(EmittedEvent {
+        let emitter = self
+            .exec_context
+            .callee
+            .as_ref()
+            .map(|callee| callee.as_bytes().to_vec());
+        let event = EmittedEvent {
+            emitter,
             topics: topics_vec,
             data: data.to_vec(),
-        });
+        };
+        if let Some(queue) = &mut self.event_queue {
+            queue.push(event.clone());
+        }
+        self.debug_info.record_event(event);
     }
 
     /// Writes the encoded value into the storage at the given key.
     /// Returns the size of the previously stored value at the key if any.
     pub fn set_storage(&mut self, key: &[u8], encoded_value: &[u8]) -> Option<u32> {
-        let callee = self.get_callee();
-        let account_id = AccountId::from_bytes(&callee[..]);
-
-        self.debug_info.inc_writes(account_id.clone());
-        self.debug_info
-            .record_cell_for_account(account_id, key.to_vec());
-
-        self.database
-            .insert_into_contract_storage(&callee, key, encoded_value.to_vec())
-            .map(|v| <u32>::try_from(v.len()).expect("usize to u32 conversion failed"))
+        self.ensure_state_change_allowed("set_storage");
+        let input = (key, encoded_value).encode();
+        self.host_call("set_storage", &input, Replay::Execute, |engine| {
+            let callee = engine.get_callee();
+            let account_id = AccountId::from_bytes(&callee[..]);
+
+            engine.debug_info.inc_writes(account_id.clone());
+            engine
+                .debug_info
+                .record_cell_for_account(account_id, key.to_vec());
+
+            let old_value = engine.database.insert_into_contract_storage(
+                &callee,
+                key,
+                encoded_value.to_vec(),
+            );
+            engine.charge_storage_deposit(
+                old_value.as_ref().map(Vec::len),
+                encoded_value.len(),
+            );
+            old_value.map(|v| {
+                <u32>::try_from(v.len()).expect("usize to u32 conversion failed")
+            })
+        })
     }
 
     /// Returns the decoded contract storage at the key if any.
     pub fn get_storage(&mut self, key: &[u8], output: &mut &mut [u8]) -> Result {
-        let callee = self.get_callee();
-        let account_id = AccountId::from_bytes(&callee[..]);
-
-        self.debug_info.inc_reads(account_id);
-        match self.database.get_from_contract_storage(&callee, key) {
-            Some(val) => {
-                set_output(output, val);
-                Ok(())
-            }
-            None => Err(Error::KeyNotFound),
-        }
+        let value = self.host_call("get_storage", key, Replay::Execute, |engine| {
+            let callee = engine.get_callee();
+            let account_id = AccountId::from_bytes(&callee[..]);
+
+            engine.debug_info.inc_reads(account_id);
+            engine
+                .database
+                .get_from_contract_storage(&callee, key)
+                .cloned()
+                .ok_or(Error::KeyNotFound)
+        })?;
+        set_output(output, &value);
+        Ok(())
     }
 
     /// Removes the storage entries at the given key,
     /// returning previously stored value at the key if any.
     pub fn take_storage(&mut self, key: &[u8], output: &mut &mut [u8]) -> Result {
-        let callee = self.get_callee();
-        let account_id = AccountId::from_bytes(&callee[..]);
-
-        self.debug_info.inc_writes(account_id);
-        match self.database.remove_contract_storage(&callee, key) {
-            Some(val) => {
-                set_output(output, &val);
-                Ok(())
-            }
-            None => Err(Error::KeyNotFound),
-        }
+        self.ensure_state_change_allowed("take_storage");
+        let value = self.host_call("take_storage", key, Replay::Execute, |engine| {
+            let callee = engine.get_callee();
+            let account_id = AccountId::from_bytes(&callee[..]);
+
+            engine.debug_info.inc_writes(account_id.clone());
+            let _ = engine
+                .debug_info
+                .remove_cell_for_account(account_id, key.to_vec());
+            let value = engine
+                .database
+                .remove_contract_storage(&callee, key)
+                .ok_or(Error::KeyNotFound)?;
+            let depositor = engine.exec_context.caller.clone();
+            engine.refund_storage_deposit(depositor, value.len());
+            Ok(value)
+        })?;
+        set_output(output, &value);
+        Ok(())
     }
 
     /// Returns the size of the value stored in the contract storage at the key if any.
     pub fn contains_storage(&mut self, key: &[u8]) -> Option<u32> {
-        let callee = self.get_callee();
-        let account_id = AccountId::from_bytes(&callee[..]);
-
-        self.debug_info.inc_reads(account_id);
-        self.database
-            .get_from_contract_storage(&callee, key)
-            .map(|val| val.len() as u32)
+        self.host_call("contains_storage", key, Replay::Execute, |engine| {
+            let callee = engine.get_callee();
+            let account_id = AccountId::from_bytes(&callee[..]);
+
+            engine.debug_info.inc_reads(account_id);
+            engine
+                .database
+                .get_from_contract_storage(&callee, key)
+                .map(|val| val.len() as u32)
+        })
     }
 
     /// Removes the storage entries at the given key.
     /// Returns the size of the previously stored value at the key if any.
     pub fn clear_storage(&mut self, key: &[u8]) -> Option<u32> {
+        self.ensure_state_change_allowed("clear_storage");
+        self.host_call("clear_storage", key, Replay::Execute, |engine| {
+            let depositor = engine.exec_context.caller.clone();
+            engine.remove_storage_entry(key, depositor)
+        })
+    }
+
+    /// Removes all storage entries whose keys start with `prefix`.
+    ///
+    /// Returns the number of removed entries.
+    pub fn clear_storage_prefix(&mut self, prefix: &[u8]) -> u32 {
+        self.ensure_state_change_allowed("clear_storage_prefix");
+        self.host_call("clear_storage_prefix", prefix, Replay::Execute, |engine| {
+            let callee = engine.get_callee();
+            let account_id = AccountId::from_bytes(&callee[..]);
+            let depositor = engine.exec_context.caller.clone();
+            let keys = engine.debug_info.cells_with_prefix(&account_id, prefix);
+            keys.iter()
+                .filter_map(|key| engine.remove_storage_entry(key, depositor.clone()))
+                .count() as u32
+        })
+    }
+
+    /// Removes the storage entry at the given key of the callee and refunds its
+    /// storage deposit to `depositor` if any.
+    fn remove_storage_entry(
+        &mut self,
+        key: &[u8],
+        depositor: Option<AccountId>,
+    ) -> Option<u32> {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
         self.debug_info.inc_writes(account_id.clone());
         let _ = self
             .debug_info
             .remove_cell_for_account(account_id, key.to_vec());
-        self.database
-            .remove_contract_storage(&callee, key)
-            .map(|val| val.len() as u32)
+        let value = self.database.remove_contract_storage(&callee, key)?;
+        self.refund_storage_deposit(depositor, value.len());
+        Some(value.len() as u32)
+    }
+
+    /// Charges the caller the storage deposit for changing the size of a storage
+    /// entry from `old_len` bytes to `new_len` bytes.
+    ///
+    /// Storage entries which shrink refund the deposit of the freed bytes instead.
+    fn charge_storage_deposit(&mut self, old_len: Option<usize>, new_len: usize) {
+        let depositor = match self.exec_context.caller.clone() {
+            Some(caller) => caller,
+            None => return,
+        };
+        let per_byte = self.chain_spec.deposit_per_byte;
+        match old_len {
+            None => {
+                let deposit = self.storage_deposit_of(new_len);
+                self.debug_info.charge_storage_deposit(depositor, deposit)
+            }
+            Some(old_len) if old_len <= new_len => {
+                let deposit = per_byte * (new_len - old_len) as Balance;
+                self.debug_info.charge_storage_deposit(depositor, deposit)
+            }
+            Some(old_len) => {
+                let deposit = per_byte * (old_len - new_len) as Balance;
+                self.debug_info.refund_storage_deposit(depositor, deposit)
+            }
+        }
+    }
+
+    /// Refunds the storage deposit of a removed storage entry of `len` bytes
+    /// to `depositor` if any.
+    fn refund_storage_deposit(&mut self, depositor: Option<AccountId>, len: usize) {
+        if let Some(depositor) = depositor {
+            let deposit = self.storage_deposit_of(len);
+            self.debug_info.refund_storage_deposit(depositor, deposit)
+        }
+    }
+
+    /// Returns the storage deposit held for a storage entry of `len` bytes.
+    fn storage_deposit_of(&self, len: usize) -> Balance {
+        self.chain_spec.deposit_per_item
+            + self.chain_spec.deposit_per_byte * len as Balance
     }
 
     /// Remove the calling account and transfer remaining balance.
@@ -300,6 +529,7 @@ impl Engine {
     /// execution of the destroyed contract is halted. Or it failed during the
     /// termination which is considered fatal.
     pub fn terminate(&mut self, beneficiary: &[u8]) -> ! {
+        self.ensure_state_change_allowed("terminate");
         // Send the remaining balance to the beneficiary
         let contract = self.get_callee();
         let all = self
@@ -318,53 +548,72 @@ impl Engine {
 
     /// Returns the address of the caller.
     pub fn caller(&self, output: &mut &mut [u8]) {
-        let caller = self
-            .exec_context
-            .caller
-            .as_ref()
-            .expect("no caller has been set")
-            .as_bytes();
-        set_output(output, caller);
+        let caller = self.host_query("caller", &[], |engine| {
+            engine
+                .exec_context
+                .caller
+                .as_ref()
+                .expect("no caller has been set")
+                .as_bytes()
+                .to_vec()
+        });
+        set_output(output, &caller);
     }
 
     /// Returns the balance of the executed contract.
     pub fn balance(&self, output: &mut &mut [u8]) {
-        let contract = self
-            .exec_context
-            .callee
-            .as_ref()
-            .expect("no callee has been set");
-
-        let balance_in_storage = self
-            .database
-            .get_balance(contract.as_bytes())
-            .expect("currently executing contract must exist");
-        let balance = scale::Encode::encode(&balance_in_storage);
+        let balance = self.host_query("balance", &[], |engine| {
+            let contract = engine
+                .exec_context
+                .callee
+                .as_ref()
+                .expect("no callee has been set");
+
+            let balance_in_storage = engine
+                .database
+                .get_balance(contract.as_bytes())
+                .expect("currently executing contract must exist");
+            scale::Encode::encode(&balance_in_storage)
+        });
         set_output(output, &balance[..])
     }
 
     /// Returns the transferred value for the called contract.
     pub fn value_transferred(&self, output: &mut &mut [u8]) {
         let value_transferred: Vec<u8> =
-            scale::Encode::encode(&self.exec_context.value_transferred);
+            self.host_query("value_transferred", &[], |engine| {
+                scale::Encode::encode(&engine.exec_context.value_transferred)
+            });
         set_output(output, &value_transferred[..])
     }
 
     /// Returns the address of the executed contract.
     pub fn address(&self, output: &mut &mut [u8]) {
-        let callee = self
-            .exec_context
-            .callee
-            .as_ref()
-            .expect("no callee has been set")
-            .as_bytes();
-        set_output(output, callee)
+        let callee = self.host_query("address", &[], |engine| {
+            engine
+                .exec_context
+                .callee
+                .as_ref()
+                .expect("no callee has been set")
+                .as_bytes()
+                .to_vec()
+        });
+        set_output(output, &callee)
     }
 
     /// Records the given debug message and appends to stdout.
     pub fn debug_message(&mut self, message: &str) {
-        self.debug_info.record_debug_message(String::from(message));
-        print!("{}", message);
+        self.host_call(
+            "debug_message",
+            message.as_bytes(),
+            Replay::Execute,
+            |engine| {
+                engine
+                    .debug_info
+                    .record_debug_message(String::from(message));
+                print!("{}", message);
+            },
+        )
     }
 
     /// Conduct the BLAKE-2 256-bit hash and place the result into `output`.
@@ -389,27 +638,42 @@ impl Engine {
 
     /// Returns the current block number.
     pub fn block_number(&self, output: &mut &mut [u8]) {
-        let block_number: Vec<u8> =
-            scale::Encode::encode(&self.exec_context.block_number);
+        let block_number: Vec<u8> = self.host_query("block_number", &[], |engine| {
+            scale::Encode::encode(&engine.exec_context.block_number)
+        });
         set_output(output, &block_number[..])
     }
 
     /// Returns the timestamp of the current block.
     pub fn block_timestamp(&self, output: &mut &mut [u8]) {
         let block_timestamp: Vec<u8> =
-            scale::Encode::encode(&self.exec_context.block_timestamp);
+            self.host_query("block_timestamp", &[], |engine| {
+                scale::Encode::encode(&engine.exec_context.block_timestamp)
+            });
         set_output(output, &block_timestamp[..])
     }
 
-    pub fn gas_left(&self, _output: &mut &mut [u8]) {
-        unimplemented!("off-chain environment does not yet support `gas_left`");
+    /// Returns the gas left until the gas limit of the call is reached.
+    ///
+    /// Returns `u64::MAX` if no gas limit is set.
+    pub fn gas_left(&self, output: &mut &mut [u8]) {
+        let gas_left: Vec<u8> = self.host_query("gas_left", &[], |engine| {
+            let exec_context = &engine.exec_context;
+            let gas_left = exec_context.gas_limit.map_or(u64::MAX, |limit| {
+                limit.saturating_sub(exec_context.gas_consumed)
+            });
+            scale::Encode::encode(&gas_left)
+        });
+        set_output(output, &gas_left[..])
     }
 
     /// Returns the minimum balance that is required for creating an account
     /// (i.e. the chain's existential deposit).
     pub fn minimum_balance(&self, output: &mut &mut [u8]) {
         let minimum_balance: Vec<u8> =
-            scale::Encode::encode(&self.chain_spec.minimum_balance);
+            self.host_query("minimum_balance", &[], |engine| {
+                scale::Encode::encode(&engine.chain_spec.minimum_balance)
+            });
         set_output(output, &minimum_balance[..])
     }
 
@@ -427,43 +691,102 @@ impl Engine {
         unimplemented!("off-chain environment does not yet support `instantiate`");
     }
 
+    /// Calls the contract mock registered for `callee` with `input`.
+    ///
+    /// Returns `Error::NotCallable` if there is no contract mock registered
+    /// for `callee`. The contract mock is not called when replaying.
+    ///
+    /// Returns `Error::CalleeTrapped` if value is transferred in a read-only context.
     pub fn call(
         &mut self,
-        _callee: &[u8],
+        callee: &[u8],
         _gas_limit: u64,
-        _value: &[u8],
-        _input: &[u8],
-        _output: &mut &mut [u8],
+        value: &[u8],
+        input: &[u8],
+        output: &mut &mut [u8],
     ) -> Result {
-        unimplemented!("off-chain environment does not yet support `call`");
+        if self.exec_context.read_only && value.iter().any(|byte| *byte != 0) {
+            return Err(Error::CalleeTrapped)
+        }
+        let encoded_input = (callee, value, input).encode();
+        let out = self.host_call("call", &encoded_input, Replay::Skip, |engine| {
+            engine
+                .contract_mock_handler
+                .eval(callee, input)
+                .map(<[u8]>::to_vec)
+                .ok_or(Error::NotCallable)
+        })?;
+        set_output(output, &out);
+        Ok(())
+    }
+
+    /// Calls the contract `callee` without allowing it to change any state.
+    ///
+    /// Read-only calls must not transfer any value. The callee as well as all
+    /// contracts it calls in turn are executed in a read-only context in which
+    /// every attempt to change state panics.
+    pub fn call_read_only(
+        &mut self,
+        callee: &[u8],
+        gas_limit: u64,
+        value: &[u8],
+        input: &[u8],
+        output: &mut &mut [u8],
+    ) -> Result {
+        let read_only = core::mem::replace(&mut self.exec_context.read_only, true);
+        let result = self.call(callee, gas_limit, value, input, output);
+        self.exec_context.read_only = read_only;
+        result
+    }
+
+    /// Panics if the current execution context does not allow to change state.
+    fn ensure_state_change_allowed(&self, function: &str) {
+        if self.exec_context.read_only {
+            panic!(
+                "state change denied: `{}` must not be called in a read-only context",
+                function
+            )
+        }
     }
 
     /// Emulates gas price calculation.
     pub fn weight_to_fee(&self, gas: u64, output: &mut &mut [u8]) {
-        let fee = self.chain_spec.gas_price.saturating_mul(gas.into());
-        let fee: Vec<u8> = scale::Encode::encode(&fee);
+        let fee: Vec<u8> = self.host_query("weight_to_fee", &gas.encode(), |engine| {
+            let fee = engine.chain_spec.gas_price.saturating_mul(gas.into());
+            scale::Encode::encode(&fee)
+        });
         set_output(output, &fee[..])
     }
 
     /// Calls the chain extension method registered at `func_id` with `input`.
+    ///
+    /// The chain extension is not called when replaying.
     pub fn call_chain_extension(
         &mut self,
         func_id: u32,
         input: &[u8],
         output: &mut &mut [u8],
     ) {
-        let encoded_input = input.encode();
-        let (status_code, out) = self
-            .chain_extension_handler
-            .eval(func_id, &encoded_input)
-            .unwrap_or_else(|error| {
-                panic!(
-                    "Encountered unexpected missing chain extension method: {:?}",
-                    error
-                );
-            });
-        let res = (status_code, out);
-        let decoded: Vec<u8> = scale::Encode::encode(&res);
+        let recorded_input = (func_id, input).encode();
+        let decoded: Vec<u8> = self.host_call(
+            "call_chain_extension",
+            &recorded_input,
+            Replay::Skip,
+            |engine| {
+                let encoded_input = input.encode();
+                let (status_code, out) = engine
+                    .chain_extension_handler
+                    .eval(func_id, &encoded_input)
+                    .unwrap_or_else(|error| {
+                        panic!(
+                            "Encountered unexpected missing chain extension method: {:?}",
+                            error
+                        );
+                    });
+                let res = (status_code, out);
+                scale::Encode::encode(&res)
+            },
+        );
         set_output(output, &decoded[..])
     }
 
@@ -515,7 +838,9 @@ impl Engine {
     }
 }
 
-/// Copies the `slice` into `output`.
+/// Copies the `slice` into `output` and truncates `output` to the length of `slice`.
+///
+/// This mirrors the on-chain environment where `output` only covers the written bytes.
 ///
 /// Panics if the slice is too large and does not fit.
 fn set_output(output: &mut &mut [u8], slice: &[u8]) {
@@ -526,5 +851,7 @@ fn set_output(output: &mut &mut [u8], slice: &[u8]) {
         slice.len(),
         output.len(),
     );
-    output[..slice.len()].copy_from_slice(slice);
+    let buffer = core::mem::take(output);
+    buffer[..slice.len()].copy_from_slice(slice);
+    *output = &mut buffer[..slice.len()];
 }
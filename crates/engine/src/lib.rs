@@ -21,15 +21,25 @@ pub mod ext;
 pub mod test_api;
 
 mod chain_extension;
+mod contract_mock;
 mod database;
 mod exec_context;
 mod hashing;
+mod replay;
 mod types;
 
 #[cfg(test)]
 mod tests;
 
 pub use chain_extension::ChainExtension;
+pub use contract_mock::ContractMock;
+pub use replay::{
+    CallInput,
+    CallInputLog,
+    EntryPoint,
+    HostCall,
+    HostCallLog,
+};
 pub use types::AccountError;
 
 use derive_more::From;
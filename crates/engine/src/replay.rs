@@ -0,0 +1,243 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::types::Balance;
+use scale::{
+    Decode,
+    Encode,
+};
+use std::{
+    collections::VecDeque,
+    fs,
+    io,
+    path::Path,
+};
+
+/// A single interaction of a contract with a host function of the engine.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct HostCall {
+    /// The name of the called host function.
+    pub function: String,
+    /// The SCALE encoded inputs of the host function.
+    pub input: Vec<u8>,
+    /// The SCALE encoded result the host function returned to the contract.
+    pub output: Vec<u8>,
+}
+
+/// The host calls of a test run in the order in which they happened.
+///
+/// The log is stored as its SCALE encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct HostCallLog {
+    calls: Vec<HostCall>,
+}
+
+impl HostCallLog {
+    /// Returns the recorded host calls in order.
+    pub fn calls(&self) -> &[HostCall] {
+        &self.calls
+    }
+
+    /// Writes the log to the file at `path`, replacing the file if it exists.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.encode())
+    }
+
+    /// Reads a log previously written by [`HostCallLog::save`] from `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let encoded = fs::read(path)?;
+        <Self as Decode>::decode(&mut &encoded[..])
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl FromIterator<HostCall> for HostCallLog {
+    fn from_iter<I: IntoIterator<Item = HostCall>>(iter: I) -> Self {
+        Self {
+            calls: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// The entry point of a contract through which a call input is dispatched.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum EntryPoint {
+    /// The `deploy` entry point, which dispatches constructors.
+    Deploy,
+    /// The `call` entry point, which dispatches messages.
+    Call,
+}
+
+/// The raw input a contract received, together with the context of the call.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CallInput {
+    /// The entry point through which the input was dispatched.
+    pub entry_point: EntryPoint,
+    /// The encoded account id of the caller, if one was set.
+    pub caller: Option<Vec<u8>>,
+    /// The encoded account id of the callee, if one was set.
+    pub callee: Option<Vec<u8>>,
+    /// The value transferred as part of the call.
+    pub value_transferred: Balance,
+    /// The selector followed by the SCALE encoded arguments.
+    pub input: Vec<u8>,
+}
+
+/// The call inputs of a test run in the order in which they were dispatched.
+///
+/// The log is stored as its SCALE encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct CallInputLog {
+    inputs: Vec<CallInput>,
+}
+
+impl CallInputLog {
+    /// Returns the recorded call inputs in order.
+    pub fn inputs(&self) -> &[CallInput] {
+        &self.inputs
+    }
+
+    /// Writes the log to the file at `path`, replacing the file if it exists.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.encode())
+    }
+
+    /// Reads a log previously written by [`CallInputLog::save`] from `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let encoded = fs::read(path)?;
+        <Self as Decode>::decode(&mut &encoded[..])
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl FromIterator<CallInput> for CallInputLog {
+    fn from_iter<I: IntoIterator<Item = CallInput>>(iter: I) -> Self {
+        Self {
+            inputs: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Whether the host calls are currently recorded or replayed.
+enum Mode {
+    /// Host calls are neither recorded nor replayed.
+    Off,
+    /// Host calls are appended to the log.
+    Record(Vec<HostCall>),
+    /// Host calls are answered from the log.
+    Replay {
+        /// The host calls which have not yet been replayed.
+        remaining: VecDeque<HostCall>,
+        /// The number of host calls replayed so far.
+        replayed: usize,
+    },
+}
+
+/// Records or replays the interactions of contracts with the engine.
+pub struct HostCallRecorder {
+    mode: Mode,
+}
+
+impl Default for HostCallRecorder {
+    fn default() -> Self {
+        HostCallRecorder::new()
+    }
+}
+
+impl HostCallRecorder {
+    /// Creates a new host call recorder which neither records nor replays.
+    pub fn new() -> Self {
+        Self { mode: Mode::Off }
+    }
+
+    /// Starts recording host calls, discarding any previous recording or replay.
+    pub fn record(&mut self) {
+        self.mode = Mode::Record(Vec::new());
+    }
+
+    /// Starts replaying the host calls of `log`, discarding any previous recording
+    /// or replay.
+    pub fn replay(&mut self, log: HostCallLog) {
+        self.mode = Mode::Replay {
+            remaining: log.calls.into(),
+            replayed: 0,
+        };
+    }
+
+    /// Stops recording or replaying.
+    ///
+    /// Returns the recorded host calls, or the host calls which have not been
+    /// replayed yet.
+    pub fn stop(&mut self) -> HostCallLog {
+        match core::mem::replace(&mut self.mode, Mode::Off) {
+            Mode::Off => HostCallLog::default(),
+            Mode::Record(calls) => HostCallLog { calls },
+            Mode::Replay { remaining, .. } => remaining.into_iter().collect(),
+        }
+    }
+
+    /// Returns `true` if host calls are currently recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.mode, Mode::Record(_))
+    }
+
+    /// Appends a call to `function` with `input` and `output` to the recording.
+    ///
+    /// Does nothing if host calls are not currently recorded.
+    pub fn push(&mut self, function: &str, input: &[u8], output: Vec<u8>) {
+        if let Mode::Record(calls) = &mut self.mode {
+            calls.push(HostCall {
+                function: function.to_owned(),
+                input: input.to_vec(),
+                output,
+            })
+        }
+    }
+
+    /// Returns the recorded output of the next host call when replaying.
+    ///
+    /// Returns `None` if host calls are not currently replayed.
+    ///
+    /// # Panics
+    ///
+    /// If the call to `function` with `input` diverges from the next recorded
+    /// host call or if there is no recorded host call left to replay.
+    pub fn pop(&mut self, function: &str, input: &[u8]) -> Option<Vec<u8>> {
+        let (remaining, replayed) = match &mut self.mode {
+            Mode::Replay {
+                remaining,
+                replayed,
+            } => (remaining, replayed),
+            _ => return None,
+        };
+        let call = remaining.pop_front().unwrap_or_else(|| {
+            panic!(
+                "host call #{} to `{}` has not been recorded: the recording is exhausted",
+                replayed, function,
+            )
+        });
+        assert!(
+            call.function == function && call.input == input,
+            "host call #{} diverged from the recording: expected `{}` with input {:?}, \
+            got `{}` with input {:?}",
+            replayed,
+            call.function,
+            call.input,
+            function,
+            input,
+        );
+        *replayed += 1;
+        Some(call.output)
+    }
+}
@@ -13,10 +13,22 @@
 // limitations under the License.
 
 use crate::{
-    ext::Engine,
+    database::Database,
+    exec_context::ExecContext,
+    ext::{
+        ChainSpec,
+        Engine,
+    },
+    replay::{
+        CallInput,
+        CallInputLog,
+        EntryPoint,
+        HostCallLog,
+    },
     types::{
         AccountId,
         Balance,
+        BlockTimestamp,
     },
     AccountError,
     Error,
@@ -26,6 +38,10 @@ use std::collections::HashMap;
 /// Record for an emitted event.
 #[derive(Clone)]
 pub struct EmittedEvent {
+    /// The encoded account id of the contract which emitted the event.
+    ///
+    /// Is `None` if no callee has been set when the event was emitted.
+    pub emitter: Option<Vec<u8>>,
     /// Recorded topics of the emitted event.
     pub topics: Vec<Vec<u8>>,
     /// Recorded encoding of the emitted event.
@@ -71,18 +87,34 @@ impl IntoIterator for RecordedDebugMessages {
     }
 }
 
+/// The storage deposit charged from and refunded to an account.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StorageDeposit {
+    /// The total storage deposit charged for storage entries created or grown.
+    pub charged: Balance,
+    /// The total storage deposit refunded for storage entries removed or shrunk.
+    pub refunded: Balance,
+}
+
 /// Recorder for relevant interactions with this crate.
+#[derive(Clone)]
 pub struct DebugInfo {
     /// Emitted events recorder.
     emitted_events: Vec<EmittedEvent>,
+    /// The number of events which had been emitted when the last call started.
+    last_call_events_offset: usize,
     /// Emitted print messages recorder.
     emitted_debug_messages: RecordedDebugMessages,
     /// The total number of reads to the storage.
     count_reads: HashMap<AccountId, usize>,
     /// The total number of writes to the storage.
     count_writes: HashMap<AccountId, usize>,
+    /// The total number of hash calls made to compute event topics.
+    count_topic_hashes: usize,
     /// The number of storage cells used by each account id.
     cells_per_account: HashMap<AccountId, HashMap<Vec<u8>, bool>>,
+    /// The storage deposit charged from and refunded to each account id.
+    storage_deposits: HashMap<AccountId, StorageDeposit>,
 }
 
 impl Default for DebugInfo {
@@ -96,10 +128,13 @@ impl DebugInfo {
     pub fn new() -> Self {
         Self {
             emitted_events: Vec::new(),
+            last_call_events_offset: 0,
             emitted_debug_messages: RecordedDebugMessages::new(),
             count_reads: HashMap::new(),
             count_writes: HashMap::new(),
+            count_topic_hashes: 0,
             cells_per_account: HashMap::new(),
+            storage_deposits: HashMap::new(),
         }
     }
 
@@ -107,9 +142,12 @@ impl DebugInfo {
     pub fn reset(&mut self) {
         self.count_reads.clear();
         self.count_writes.clear();
+        self.count_topic_hashes = 0;
         self.emitted_events.clear();
+        self.last_call_events_offset = 0;
         self.emitted_debug_messages.clear();
         self.cells_per_account.clear();
+        self.storage_deposits.clear();
     }
 
     /// Increases the number of storage writes for the supplied account by one.
@@ -159,6 +197,34 @@ impl DebugInfo {
             .unwrap_or(None)
     }
 
+    /// Returns the keys of all cells of the supplied account which start with `prefix`.
+    pub fn cells_with_prefix(
+        &self,
+        account_id: &AccountId,
+        prefix: &[u8],
+    ) -> Vec<Vec<u8>> {
+        self.cells_per_account
+            .get(account_id)
+            .into_iter()
+            .flat_map(|cells| cells.keys())
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    /// Records that the storage deposit `amount` was charged from the account.
+    pub fn charge_storage_deposit(&mut self, account_id: AccountId, amount: Balance) {
+        self.storage_deposits.entry(account_id).or_default().charged += amount;
+    }
+
+    /// Records that the storage deposit `amount` was refunded to the account.
+    pub fn refund_storage_deposit(&mut self, account_id: AccountId, amount: Balance) {
+        self.storage_deposits
+            .entry(account_id)
+            .or_default()
+            .refunded += amount;
+    }
+
     /// Records a debug message.
     pub fn record_debug_message(&mut self, message: String) {
         self.emitted_debug_messages.record(message);
@@ -168,14 +234,67 @@ impl DebugInfo {
     pub fn record_event(&mut self, event: EmittedEvent) {
         self.emitted_events.push(event);
     }
+
+    /// Marks the start of a new call.
+    ///
+    /// Events recorded afterwards are attributed to this call.
+    pub fn start_call(&mut self) {
+        self.last_call_events_offset = self.emitted_events.len();
+    }
+}
+
+/// A copy of the state of the engine taken by [`Engine::snapshot`].
+///
+/// Registered chain extensions and contract mocks are not part of a snapshot.
+#[derive(Clone)]
+pub struct Snapshot {
+    database: Database,
+    exec_context: ExecContext,
+    debug_info: DebugInfo,
+    chain_spec: ChainSpec,
 }
 
 impl Engine {
+    /// Returns a copy of the current state of the engine.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            database: self.database.clone(),
+            exec_context: self.exec_context.clone(),
+            debug_info: self.debug_info.clone(),
+            chain_spec: self.chain_spec.clone(),
+        }
+    }
+
+    /// Restores the state of the engine to the one of `snapshot`.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.database = snapshot.database.clone();
+        self.exec_context = snapshot.exec_context.clone();
+        self.debug_info = snapshot.debug_info.clone();
+        self.chain_spec = snapshot.chain_spec.clone();
+    }
+
     /// Resets the environment.
     pub fn initialize_or_reset(&mut self) {
         self.exec_context.reset();
         self.database.clear();
         self.debug_info.reset();
+        self.event_queue = None;
+    }
+
+    /// Enables or disables queueing emitted events for notification.
+    ///
+    /// While enabled, every emitted event is additionally queued until it is taken
+    /// with [`Engine::take_queued_events`]. Disabling drops all queued events.
+    pub fn set_event_queueing(&mut self, enabled: bool) {
+        self.event_queue = enabled.then(Vec::new);
+    }
+
+    /// Returns the queued events in order and empties the queue.
+    pub fn take_queued_events(&mut self) -> Vec<EmittedEvent> {
+        self.event_queue
+            .as_mut()
+            .map(core::mem::take)
+            .unwrap_or_default()
     }
 
     /// Returns the total number of reads and writes of the contract's storage.
@@ -196,6 +315,16 @@ impl Engine {
         self.debug_info.count_writes.iter().map(|(_, v)| v).sum()
     }
 
+    /// Records that `amount` hash calls were made to compute the topics of an event.
+    pub fn record_topic_hashes(&mut self, amount: usize) {
+        self.debug_info.count_topic_hashes += amount;
+    }
+
+    /// Returns the total number of hash calls made to compute event topics.
+    pub fn count_topic_hashes(&self) -> usize {
+        self.debug_info.count_topic_hashes
+    }
+
     /// Sets a caller for the next call.
     pub fn set_caller(&mut self, caller: Vec<u8>) {
         self.exec_context.caller = Some(caller.into());
@@ -220,10 +349,104 @@ impl Engine {
         Ok(cells.len())
     }
 
+    /// Returns all storage entries of the contract `account_id` ordered by their keys.
+    ///
+    /// Each entry is a pair of the storage key and the encoded value stored under it.
+    /// Returns no entries if the `account_id` is non-existent.
+    pub fn contract_storage_entries(&self, account_id: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut entries = self
+            .debug_info
+            .cells_per_account
+            .get(&account_id.to_owned().into())
+            .into_iter()
+            .flat_map(|cells| cells.keys())
+            .filter_map(|key| {
+                self.database
+                    .get_from_contract_storage(account_id, key)
+                    .map(|value| (key.clone(), value.clone()))
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    /// Writes `encoded_value` into the contract storage of `account_id` at `key`.
+    ///
+    /// In contrast to [`Engine::set_storage`] this neither requires the account to be
+    /// the callee nor charges gas or a storage deposit, so that fixtures can seed the
+    /// storage of any contract.
+    pub fn set_contract_storage_of(
+        &mut self,
+        account_id: &[u8],
+        key: &[u8],
+        encoded_value: Vec<u8>,
+    ) {
+        self.debug_info
+            .record_cell_for_account(account_id.to_vec().into(), key.to_vec());
+        self.database
+            .insert_into_contract_storage(account_id, key, encoded_value);
+    }
+
+    /// Returns the encoded value in the contract storage of `account_id` at `key`.
+    ///
+    /// In contrast to [`Engine::get_storage`] this neither requires the account to be
+    /// the callee nor counts as a read of the contract.
+    pub fn get_contract_storage_of(
+        &self,
+        account_id: &[u8],
+        key: &[u8],
+    ) -> Option<Vec<u8>> {
+        self.database
+            .get_from_contract_storage(account_id, key)
+            .cloned()
+    }
+
+    /// Returns the storage deposit charged from and refunded to the account `account_id`.
+    ///
+    /// The caller of a contract is charged for the storage entries it creates and is
+    /// refunded for the storage entries it removes. Since the engine does not support
+    /// cross-contract calls, the caller is the origin of the call, which is charged
+    /// and refunded on-chain.
+    pub fn storage_deposit(&self, account_id: &[u8]) -> StorageDeposit {
+        self.debug_info
+            .storage_deposits
+            .get(&account_id.to_owned().into())
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Advances the chain by a single block.
+    ///
+    /// The block timestamp is advanced by the block time unless time is frozen.
     pub fn advance_block(&mut self) {
         self.exec_context.block_number += 1;
-        self.exec_context.block_timestamp += self.chain_spec.block_time;
+        if !self.exec_context.time_frozen {
+            self.exec_context.block_timestamp += self.chain_spec.block_time;
+        }
+    }
+
+    /// Sets the timestamp of the current block to `timestamp`.
+    ///
+    /// The block number is left unchanged.
+    pub fn set_block_timestamp(&mut self, timestamp: BlockTimestamp) {
+        self.exec_context.block_timestamp = timestamp;
+    }
+
+    /// Freezes the block timestamp so that advancing blocks does not advance it.
+    pub fn freeze_time(&mut self) {
+        self.exec_context.time_frozen = true;
+    }
+
+    /// Unfreezes the block timestamp so that it is advanced together with blocks again.
+    pub fn unfreeze_time(&mut self) {
+        self.exec_context.time_frozen = false;
+    }
+
+    /// Sets whether the current execution context is read-only.
+    ///
+    /// Every attempt to change state in a read-only context panics.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.exec_context.read_only = read_only;
     }
 
     /// Returns the callee, i.e. the currently executing contract.
@@ -241,6 +464,20 @@ impl Engine {
         self.debug_info.emitted_events.clone().into_iter()
     }
 
+    /// Marks the start of a new call for the purpose of event recording.
+    pub fn start_call(&mut self) {
+        self.debug_info.start_call();
+    }
+
+    /// Returns the events emitted since the last call started in order.
+    pub fn get_emitted_events_of_last_call(&self) -> impl Iterator<Item = EmittedEvent> {
+        self.debug_info
+            .emitted_events
+            .clone()
+            .into_iter()
+            .skip(self.debug_info.last_call_events_offset)
+    }
+
     /// Returns the current balance of `account_id`.
     pub fn get_balance(&self, account_id: Vec<u8>) -> Result<Balance, Error> {
         self.database
@@ -253,10 +490,106 @@ impl Engine {
         self.database.set_balance(&account_id, new_balance);
     }
 
+    /// Returns the code hash of the contract at `account_id`, if one was set.
+    pub fn get_code_hash(&self, account_id: Vec<u8>) -> Option<Vec<u8>> {
+        self.database.get_code_hash(&account_id).cloned()
+    }
+
+    /// Sets the code hash of the contract at `account_id` to `code_hash`.
+    pub fn set_code_hash(&mut self, account_id: Vec<u8>, code_hash: &[u8]) {
+        self.database.set_code_hash(&account_id, code_hash);
+    }
+
     /// Sets the value transferred from the caller to the callee as part of the call.
     pub fn set_value_transferred(&mut self, value: Balance) {
         self.exec_context.value_transferred = value;
     }
+
+    /// Charges `weight` to the gas consumed so far.
+    pub fn charge_gas(&mut self, weight: u64) {
+        self.exec_context.gas_consumed =
+            self.exec_context.gas_consumed.saturating_add(weight);
+    }
+
+    /// Returns the gas consumed so far.
+    pub fn gas_consumed(&self) -> u64 {
+        self.exec_context.gas_consumed
+    }
+
+    /// Sets the gas limit of the call, `None` for unlimited gas.
+    pub fn set_gas_limit(&mut self, limit: Option<u64>) {
+        self.exec_context.gas_limit = limit;
+    }
+
+    /// Starts recording all host calls of contracts together with their results.
+    ///
+    /// Discards any previous recording and stops replaying.
+    pub fn record_host_calls(&mut self) {
+        self.host_calls.get_mut().record();
+    }
+
+    /// Starts replaying the host calls of `log`.
+    ///
+    /// From now on every host call returns the result recorded in `log` instead of
+    /// the result computed by the engine. Host calls which change the state of the
+    /// engine are still executed, whereas chain extensions and contract mocks are
+    /// not called anymore.
+    ///
+    /// # Panics
+    ///
+    /// Any subsequent host call panics if it diverges from its counterpart in `log`
+    /// or if all host calls of `log` have already been replayed.
+    pub fn replay_host_calls(&mut self, log: HostCallLog) {
+        self.host_calls.get_mut().replay(log);
+    }
+
+    /// Stops recording or replaying host calls.
+    ///
+    /// Returns the recorded host calls, or the host calls of the replayed log which
+    /// have not been replayed yet.
+    pub fn take_host_call_log(&mut self) -> HostCallLog {
+        self.host_calls.get_mut().stop()
+    }
+
+    /// Starts recording the raw inputs dispatched to contracts.
+    ///
+    /// Discards any previous recording.
+    pub fn record_call_inputs(&mut self) {
+        self.call_inputs = Some(Vec::new());
+    }
+
+    /// Records that `input` is dispatched through `entry_point` in the current
+    /// execution context.
+    ///
+    /// Does nothing if call inputs are not recorded.
+    pub fn record_call_input(&mut self, entry_point: EntryPoint, input: &[u8]) {
+        if let Some(inputs) = &mut self.call_inputs {
+            inputs.push(CallInput {
+                entry_point,
+                caller: self
+                    .exec_context
+                    .caller
+                    .as_ref()
+                    .map(|c| c.as_bytes().into()),
+                callee: self
+                    .exec_context
+                    .callee
+                    .as_ref()
+                    .map(|c| c.as_bytes().into()),
+                value_transferred: self.exec_context.value_transferred,
+                input: input.to_vec(),
+            })
+        }
+    }
+
+    /// Stops recording call inputs and returns the recorded ones.
+    pub fn take_call_input_log(&mut self) -> CallInputLog {
+        self.call_inputs
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .collect()
+    }
 }
 
 #[cfg(test)]
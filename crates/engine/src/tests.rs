@@ -12,9 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::ext::{
-    Engine,
-    Error,
+use crate::{
+    ext::{
+        Engine,
+        Error,
+    },
+    test_api::StorageDeposit,
+    CallInput,
+    CallInputLog,
+    ChainExtension,
+    ContractMock,
+    EntryPoint,
+    HostCallLog,
 };
 use secp256k1::{
     ecdsa::RecoverableSignature,
@@ -54,6 +63,67 @@ fn store_load_clear() {
     assert_eq!(res, Err(Error::KeyNotFound));
 }
 
+#[test]
+fn storage_deposits_are_emulated() {
+    // given
+    let mut engine = Engine::new();
+    let depositor = vec![2; 32];
+    engine.set_callee(vec![1; 32]);
+    engine.set_caller(depositor.clone());
+    let key: &[u8; 32] = &[0x42; 32];
+
+    // when
+    engine.set_storage(key, &[0x05_u8; 5]);
+    engine.set_storage(key, &[0x05_u8; 8]);
+    engine.set_storage(key, &[0x05_u8; 6]);
+
+    // then
+    assert_eq!(
+        engine.storage_deposit(&depositor),
+        StorageDeposit {
+            charged: 10 + 5 + 3,
+            refunded: 2,
+        }
+    );
+
+    // when
+    engine.set_caller(vec![3; 32]);
+    assert_eq!(engine.clear_storage(key), Some(6));
+    assert_eq!(engine.clear_storage(key), None);
+
+    // then
+    assert_eq!(
+        engine.storage_deposit(&[3; 32]),
+        StorageDeposit {
+            charged: 0,
+            refunded: 10 + 6,
+        }
+    );
+    assert_eq!(engine.storage_deposit(&depositor).refunded, 2);
+}
+
+#[test]
+fn clear_storage_prefix_works() {
+    // given
+    let mut engine = Engine::new();
+    engine.set_callee(vec![1; 32]);
+    engine.set_storage(&[0x01, 0x01], &[0x11]);
+    engine.set_storage(&[0x01, 0x02], &[0x12]);
+    engine.set_storage(&[0x01], &[0x10]);
+    engine.set_storage(&[0x02, 0x01], &[0x21]);
+
+    // when
+    let removed = engine.clear_storage_prefix(&[0x01]);
+
+    // then
+    assert_eq!(removed, 3);
+    assert_eq!(
+        engine.contract_storage_entries(&[1; 32]),
+        vec![(vec![0x02, 0x01], vec![0x21])]
+    );
+    assert_eq!(engine.clear_storage_prefix(&[0x01]), 0);
+}
+
 #[test]
 fn setting_getting_balance() {
     // given
@@ -163,6 +233,60 @@ fn events() {
     assert!(events.next().is_none());
 }
 
+#[test]
+fn events_of_last_call() {
+    // given
+    let mut engine = Engine::new();
+    let enc_topics_count = scale::Encode::encode(&scale::Compact(0u32));
+    engine.set_callee(vec![1; 32]);
+    engine.deposit_event(&enc_topics_count, &[1]);
+
+    // when
+    engine.start_call();
+    engine.set_callee(vec![2; 32]);
+    engine.deposit_event(&enc_topics_count, &[2]);
+
+    // then
+    assert_eq!(engine.get_emitted_events().count(), 2);
+    let mut events = engine.get_emitted_events_of_last_call();
+    let event = events.next().expect("event must exist");
+    assert_eq!(event.emitter, Some(vec![2; 32]));
+    assert_eq!(event.data, vec![2]);
+    assert!(events.next().is_none());
+}
+
+#[test]
+fn queued_events() {
+    // given
+    let mut engine = Engine::new();
+    let enc_topics_count = scale::Encode::encode(&scale::Compact(0u32));
+    engine.deposit_event(&enc_topics_count, &[1]);
+
+    // when
+    engine.set_event_queueing(true);
+    engine.deposit_event(&enc_topics_count, &[2]);
+    engine.deposit_event(&enc_topics_count, &[3]);
+
+    // then
+    let queued = engine.take_queued_events();
+    assert_eq!(
+        queued
+            .iter()
+            .map(|event| event.data.clone())
+            .collect::<Vec<_>>(),
+        vec![vec![2], vec![3]]
+    );
+    assert!(engine.take_queued_events().is_empty());
+    assert_eq!(engine.get_emitted_events().count(), 3);
+
+    // when
+    engine.set_event_queueing(false);
+    engine.deposit_event(&enc_topics_count, &[4]);
+
+    // then
+    assert!(engine.take_queued_events().is_empty());
+}
+
 #[test]
 fn value_transferred() {
     // given
@@ -272,3 +396,313 @@ fn ecdsa_recovery_with_secp256k1_crate() {
     // then
     assert_eq!(output, pubkey.serialize());
 }
+
+#[test]
+fn call_contract_mock() {
+    struct EchoMock;
+
+    impl ContractMock for EchoMock {
+        fn call(&mut self, input: &[u8], output: &mut Vec<u8>) {
+            output.extend(input.iter().rev());
+        }
+    }
+
+    // given
+    let mut engine = Engine::new();
+    let callee = vec![2; 32];
+    engine
+        .contract_mock_handler
+        .register(callee.clone(), Box::new(EchoMock));
+
+    // when
+    let output = &mut &mut get_buffer()[..];
+    let res = engine.call(&callee, 0, &[], &[1, 2, 3], output);
+
+    // then
+    assert_eq!(res, Ok(()));
+    assert_eq!(output, &[3, 2, 1]);
+
+    // calling an account without a registered contract mock fails
+    let output = &mut &mut get_buffer()[..];
+    let res = engine.call(&[3; 32], 0, &[], &[1, 2, 3], output);
+    assert_eq!(res, Err(Error::NotCallable));
+}
+
+#[test]
+fn read_only_call_denies_value_transfer() {
+    struct EchoMock;
+
+    impl ContractMock for EchoMock {
+        fn call(&mut self, input: &[u8], output: &mut Vec<u8>) {
+            output.extend(input.iter().rev());
+        }
+    }
+
+    // given
+    let mut engine = Engine::new();
+    let callee = vec![2; 32];
+    engine
+        .contract_mock_handler
+        .register(callee.clone(), Box::new(EchoMock));
+    let no_value = scale::Encode::encode(&0u128);
+    let value = scale::Encode::encode(&1u128);
+
+    // when
+    let output = &mut &mut get_buffer()[..];
+    let res = engine.call_read_only(&callee, 0, &no_value, &[1, 2, 3], output);
+
+    // then
+    assert_eq!(res, Ok(()));
+    assert_eq!(output[..3], [3, 2, 1]);
+    let output = &mut &mut get_buffer()[..];
+    let res = engine.call_read_only(&callee, 0, &value, &[1, 2, 3], output);
+    assert_eq!(res, Err(Error::CalleeTrapped));
+
+    // the read-only context ends together with the call
+    engine.set_callee(vec![1; 32]);
+    engine.set_storage(&[0x42; 32], &[0x05]);
+}
+
+#[test]
+#[should_panic(
+    expected = "state change denied: `set_storage` must not be called in a read-only context"
+)]
+fn read_only_context_denies_state_changes() {
+    let mut engine = Engine::new();
+    engine.set_callee(vec![1; 32]);
+    engine.set_read_only(true);
+    engine.set_storage(&[0x42; 32], &[0x05]);
+}
+
+#[test]
+fn replay_host_calls_reproduces_recording() {
+    /// Returns a different value on every call.
+    struct CounterExtension(u8);
+
+    impl ChainExtension for CounterExtension {
+        fn func_id(&self) -> u32 {
+            42
+        }
+
+        fn call(&mut self, _input: &[u8], output: &mut Vec<u8>) -> u32 {
+            self.0 += 1;
+            output.push(self.0);
+            0
+        }
+    }
+
+    let run = |engine: &mut Engine| {
+        engine.set_callee(vec![1; 32]);
+        let previous = engine.set_storage(&[0x42; 32], &[0x05; 5]);
+        let timestamp = &mut &mut get_buffer()[..];
+        engine.block_timestamp(timestamp);
+        let extension = &mut &mut get_buffer()[..];
+        engine.call_chain_extension(42, &[], extension);
+        (previous, timestamp.to_vec(), extension.to_vec())
+    };
+
+    // given
+    let mut engine = Engine::new();
+    engine
+        .chain_extension_handler
+        .register(Box::new(CounterExtension(7)));
+    engine.record_host_calls();
+    let recorded = run(&mut engine);
+    let log = engine.take_host_call_log();
+    assert_eq!(log.calls().len(), 3);
+
+    // when
+    let mut engine = Engine::new();
+    engine.advance_block();
+    engine.replay_host_calls(log);
+    let replayed = run(&mut engine);
+
+    // then
+    // the chain extension was not called and the timestamp before advancing
+    // the block was returned
+    assert_eq!(replayed, recorded);
+    assert_eq!(engine.take_host_call_log(), HostCallLog::default());
+    // the storage was written nonetheless
+    assert_eq!(engine.contains_storage(&[0x42; 32]), Some(5));
+}
+
+#[test]
+#[should_panic(expected = "host call #0 diverged from the recording")]
+fn replay_host_calls_panics_on_divergence() {
+    // given
+    let mut engine = Engine::new();
+    engine.set_callee(vec![1; 32]);
+    engine.record_host_calls();
+    engine.set_storage(&[0x42; 32], &[0x05; 5]);
+    let log = engine.take_host_call_log();
+
+    // when
+    engine.replay_host_calls(log);
+    engine.set_storage(&[0x43; 32], &[0x05; 5]);
+}
+
+#[test]
+fn host_call_log_save_and_load_works() {
+    // given
+    let mut engine = Engine::new();
+    engine.set_callee(vec![1; 32]);
+    engine.record_host_calls();
+    engine.set_storage(&[0x42; 32], &[0x05; 5]);
+    engine.debug_message("recorded");
+    let log = engine.take_host_call_log();
+    let path = std::env::temp_dir()
+        .join(format!("ink_engine_host_calls_{}", std::process::id()));
+
+    // when
+    log.save(&path).expect("saving the log failed");
+    let loaded = HostCallLog::load(&path).expect("loading the log failed");
+    let _ = std::fs::remove_file(&path);
+
+    // then
+    assert_eq!(loaded, log);
+    assert_eq!(loaded.calls()[1].function, "debug_message");
+}
+
+#[test]
+fn record_call_inputs_works() {
+    // given
+    let mut engine = Engine::new();
+    engine.set_caller(vec![2; 32]);
+    engine.set_callee(vec![1; 32]);
+    engine.record_call_input(EntryPoint::Deploy, &[0xFF]);
+
+    // when
+    engine.record_call_inputs();
+    engine.record_call_input(EntryPoint::Deploy, &[0x9B, 0xAE, 0x9D, 0x5E]);
+    engine.set_value_transferred(10);
+    engine.record_call_input(EntryPoint::Call, &[0x63, 0x3A, 0xA5, 0x51, 0x01]);
+    let log = engine.take_call_input_log();
+    engine.record_call_input(EntryPoint::Call, &[0xFF]);
+
+    // then
+    // only the inputs dispatched while recording are logged
+    assert_eq!(
+        log.inputs(),
+        &[
+            CallInput {
+                entry_point: EntryPoint::Deploy,
+                caller: Some(vec![2; 32]),
+                callee: Some(vec![1; 32]),
+                value_transferred: 0,
+                input: vec![0x9B, 0xAE, 0x9D, 0x5E],
+            },
+            CallInput {
+                entry_point: EntryPoint::Call,
+                caller: Some(vec![2; 32]),
+                callee: Some(vec![1; 32]),
+                value_transferred: 10,
+                input: vec![0x63, 0x3A, 0xA5, 0x51, 0x01],
+            },
+        ]
+    );
+    assert_eq!(engine.take_call_input_log(), CallInputLog::default());
+
+    // the log survives a round trip through a file
+    let path = std::env::temp_dir()
+        .join(format!("ink_engine_call_inputs_{}", std::process::id()));
+    log.save(&path).expect("saving the log failed");
+    let loaded = CallInputLog::load(&path).expect("loading the log failed");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded, log);
+}
+
+#[test]
+fn time_travel_works() {
+    // given
+    let mut engine = Engine::new();
+    let timestamp = |engine: &Engine| {
+        let output = &mut &mut get_buffer()[..];
+        engine.block_timestamp(output);
+        <u64 as scale::Decode>::decode(&mut &output[..]).unwrap()
+    };
+    let block_number = |engine: &Engine| {
+        let output = &mut &mut get_buffer()[..];
+        engine.block_number(output);
+        <u32 as scale::Decode>::decode(&mut &output[..]).unwrap()
+    };
+
+    // when
+    engine.set_block_timestamp(1_000);
+    engine.freeze_time();
+    engine.advance_block();
+    engine.advance_block();
+
+    // then
+    assert_eq!(timestamp(&engine), 1_000);
+    assert_eq!(block_number(&engine), 2);
+
+    // when
+    engine.set_block_timestamp(999);
+    engine.unfreeze_time();
+    engine.advance_block();
+
+    // then
+    assert_eq!(timestamp(&engine), 999 + 6);
+    assert_eq!(block_number(&engine), 3);
+}
+
+#[test]
+fn contract_storage_entries_works() {
+    // given
+    let mut engine = Engine::new();
+    let alice = vec![1; 32];
+    let bob = vec![2; 32];
+    engine.set_callee(alice.clone());
+    engine.set_storage(&[0x02; 4], &[0x22]);
+    engine.set_storage(&[0x01; 4], &[0x11]);
+    engine.set_storage(&[0x03; 4], &[0x33]);
+    engine.clear_storage(&[0x03; 4]);
+
+    // when
+    engine.set_callee(bob.clone());
+    engine.set_storage(&[0x01; 4], &[0x99]);
+
+    // then
+    assert_eq!(
+        engine.contract_storage_entries(&alice),
+        vec![(vec![0x01; 4], vec![0x11]), (vec![0x02; 4], vec![0x22]),]
+    );
+    assert_eq!(
+        engine.contract_storage_entries(&bob),
+        vec![(vec![0x01; 4], vec![0x99])]
+    );
+    assert!(engine.contract_storage_entries(&[3; 32]).is_empty());
+}
+
+#[test]
+fn snapshot_and_restore_works() {
+    // given
+    let mut engine = Engine::new();
+    let alice = vec![1; 32];
+    engine.set_callee(alice.clone());
+    engine.set_balance(alice.clone(), 1_000);
+    engine.set_storage(&[0x01; 4], &[0x11]);
+    engine.advance_block();
+    let snapshot = engine.snapshot();
+
+    // when
+    engine.set_balance(alice.clone(), 0);
+    engine.set_storage(&[0x01; 4], &[0x99]);
+    engine.set_storage(&[0x02; 4], &[0x22]);
+    engine.advance_block();
+    engine.restore(&snapshot);
+
+    // then
+    assert_eq!(engine.get_balance(alice.clone()), Ok(1_000));
+    assert_eq!(
+        engine.contract_storage_entries(&alice),
+        vec![(vec![0x01; 4], vec![0x11])]
+    );
+    assert_eq!(engine.exec_context.block_number, 1);
+    assert_eq!(engine.count_used_storage_cells(&alice), Ok(1));
+
+    // restoring does not consume the snapshot
+    engine.initialize_or_reset();
+    engine.restore(&snapshot);
+    assert_eq!(engine.get_balance(alice), Ok(1_000));
+}
@@ -253,6 +253,29 @@ where
     })
 }
 
+/// Clears all entries of the contract's storage whose keys start with the encoded
+/// `prefix`, e.g. all values of a `Mapping` under its root key.
+///
+/// Returns the number of removed entries.
+///
+/// # Errors
+///
+/// - If the chain doesn't support clearing the storage by prefix, which is the case
+///   for the `contracts` pallet. Only the off-chain environment supports it so far.
+///
+/// # Note
+///
+/// The cost of the call grows with the number of removed entries. Clearing a prefix
+/// with many entries may exceed the gas limit of a single call.
+pub fn clear_storage_prefix<K>(prefix: &K) -> Result<u32>
+where
+    K: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::clear_contract_storage_prefix::<K>(instance, prefix)
+    })
+}
+
 /// Invokes a contract message and returns its result.
 ///
 /// # Note
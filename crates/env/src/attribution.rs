@@ -0,0 +1,137 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The attribution of the transferred value to the calls of an ink! multicall
+//! message.
+
+use crate::Environment;
+
+cfg_if::cfg_if! {
+    if #[cfg(all(not(feature = "std"), target_arch = "wasm32"))] {
+        fn with_attribution<F, R>(f: F) -> R
+        where
+            F: FnOnce(&mut Option<u128>) -> R,
+        {
+            use crate::engine::CallLocal;
+            static ATTRIBUTION: CallLocal<Option<u128>> = CallLocal::new(None);
+            ATTRIBUTION.with(f)
+        }
+    } else {
+        fn with_attribution<F, R>(f: F) -> R
+        where
+            F: FnOnce(&mut Option<u128>) -> R,
+        {
+            use core::cell::RefCell;
+            thread_local!(
+                static ATTRIBUTION: RefCell<Option<u128>> = const { RefCell::new(None) }
+            );
+            ATTRIBUTION.with(|attribution| f(&mut attribution.borrow_mut()))
+        }
+
+        /// Removes the attribution once a new test starts in the off-chain environment.
+        pub(crate) fn reset_attributed_value() {
+            with_attribution(|attribution| *attribution = None)
+        }
+    }
+}
+
+/// Returns the part of the transferred value attributed to the executed ink! message.
+///
+/// Within a call of the batch of an ink! multicall message taking
+/// `ink::MulticallPaidCall`s this is the value the caller attributed to the call,
+/// so that payable messages can check the payment meant for them. Otherwise this
+/// is the [`transferred_value`](crate::transferred_value).
+pub fn attributed_value<E>() -> E::Balance
+where
+    E: Environment,
+{
+    match with_attribution(|attribution| *attribution) {
+        Some(value) => {
+            <E::Balance as TryFrom<u128>>::try_from(value).unwrap_or_else(|_| {
+                unreachable!("the attributed value was converted from a balance")
+            })
+        }
+        None => crate::transferred_value::<E>(),
+    }
+}
+
+/// Restores the previous attribution once dropped.
+struct RestoreAttribution(Option<u128>);
+
+impl Drop for RestoreAttribution {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        with_attribution(|attribution| *attribution = previous)
+    }
+}
+
+/// Executes `f` with `value` attributed to it, see [`attributed_value`].
+///
+/// Restores the previous attribution afterwards, even if `f` unwinds.
+///
+/// # Panics
+///
+/// If `value` does not fit into a `u128`.
+#[doc(hidden)]
+pub fn with_attributed_value<E, F, R>(value: E::Balance, f: F) -> R
+where
+    E: Environment,
+    F: FnOnce() -> R,
+{
+    let value = <E::Balance as TryInto<u128>>::try_into(value)
+        .unwrap_or_else(|_| panic!("the attributed value exceeds `u128`"));
+    let _restore =
+        RestoreAttribution(with_attribution(|attribution| attribution.replace(value)));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultEnvironment;
+
+    #[test]
+    fn attributed_value_works() {
+        crate::test::run_test::<DefaultEnvironment, _>(|_| {
+            crate::test::set_value_transferred::<DefaultEnvironment>(100);
+            assert_eq!(attributed_value::<DefaultEnvironment>(), 100);
+            with_attributed_value::<DefaultEnvironment, _, _>(30, || {
+                assert_eq!(attributed_value::<DefaultEnvironment>(), 30);
+                with_attributed_value::<DefaultEnvironment, _, _>(0, || {
+                    assert_eq!(attributed_value::<DefaultEnvironment>(), 0);
+                });
+                assert_eq!(attributed_value::<DefaultEnvironment>(), 30);
+            });
+            assert_eq!(attributed_value::<DefaultEnvironment>(), 100);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn attribution_is_restored_on_unwinding() {
+        crate::test::run_test::<DefaultEnvironment, _>(|_| {
+            crate::test::set_value_transferred::<DefaultEnvironment>(100);
+            let result = std::panic::catch_unwind(|| {
+                with_attributed_value::<DefaultEnvironment, _, _>(30, || {
+                    panic!("the attributed call failed")
+                })
+            });
+            assert!(result.is_err());
+            assert_eq!(attributed_value::<DefaultEnvironment>(), 100);
+            Ok(())
+        })
+        .unwrap()
+    }
+}
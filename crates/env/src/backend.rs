@@ -56,6 +56,12 @@ impl ReturnFlags {
     pub(crate) fn into_u32(self) -> u32 {
         self.value
     }
+
+    /// Returns `true` if the bit to indicate a reverted execution is set.
+    #[cfg(feature = "std")]
+    pub(crate) fn is_reverted(&self) -> bool {
+        self.value & 1 == 1
+    }
 }
 
 /// The flags used to change the behavior of a contract call.
@@ -66,6 +72,7 @@ pub struct CallFlags {
     clone_input: bool,
     tail_call: bool,
     allow_reentry: bool,
+    read_only: bool,
 }
 
 impl CallFlags {
@@ -117,6 +124,20 @@ impl CallFlags {
         self
     }
 
+    /// Deny the callee (or any of its callees) to change any state.
+    ///
+    /// Read-only calls must not transfer any value. This enables safe queries, e.g.
+    /// of a price oracle, from within messages which must not change state.
+    ///
+    /// # Note
+    ///
+    /// Runtimes which do not support read-only calls reject calls with this flag set
+    /// instead of executing them without the restriction.
+    pub const fn set_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
     /// Returns the underlying `u32` representation of the call flags.
     ///
     /// This value is used to forward the call flag information to the
@@ -126,6 +147,7 @@ impl CallFlags {
             | ((self.clone_input as u32) << 1)
             | ((self.tail_call as u32) << 2)
             | ((self.allow_reentry as u32) << 3)
+            | ((self.read_only as u32) << 4)
     }
 
     /// Returns `true` if input forwarding is set.
@@ -163,6 +185,15 @@ impl CallFlags {
     pub const fn allow_reentry(&self) -> bool {
         self.allow_reentry
     }
+
+    /// Returns `true` if the call is read-only.
+    ///
+    /// # Note
+    ///
+    /// See [`Self::set_read_only`] for more information.
+    pub const fn read_only(&self) -> bool {
+        self.read_only
+    }
 }
 
 /// Environmental contract functionality that does not require `Environment`.
@@ -208,6 +239,14 @@ pub trait EnvBackend {
     where
         K: scale::Encode;
 
+    /// Clears all entries of the contract's storage whose keys start with the
+    /// encoded `prefix` if the environment supports it.
+    ///
+    /// Returns the number of removed entries.
+    fn clear_contract_storage_prefix<K>(&mut self, prefix: &K) -> Result<u32>
+    where
+        K: scale::Encode;
+
     /// Returns the execution input to the executed contract and decodes it as `T`.
     ///
     /// # Note
@@ -306,9 +345,13 @@ pub trait EnvBackend {
     /// successful call to the chain extension method is the resulting
     /// output buffer passed to the `decode_to_result` closure, in order to
     /// drive the decoding and error management process from the outside.
+    ///
+    /// The `weight` declared for the chain extension method is only charged
+    /// by the off-chain environment.
     fn call_chain_extension<I, T, E, ErrorCode, F, D>(
         &mut self,
         func_id: u32,
+        weight: Option<u64>,
         input: &I,
         status_to_result: F,
         decode_to_result: D,
@@ -181,7 +181,7 @@ where
 /// The below example shows calling of a message of another contract that does
 /// return a `i32` value back to its caller. The called function:
 ///
-/// - has a selector equal to `0xDEADBEEF`
+/// - is the ink! message `get_value`, its selector is computed from its label
 /// - is provided with 5000 units of gas for its execution
 /// - is provided with 10 units of transferred value for the contract instance
 /// - receives the following arguments in order
@@ -202,7 +202,7 @@ where
 ///                 .gas_limit(5000))
 ///     .transferred_value(10)
 ///     .exec_input(
-///         ExecutionInput::new(Selector::new([0xDE, 0xAD, 0xBE, 0xEF]))
+///         ExecutionInput::new(Selector::from_label("get_value"))
 ///             .push_arg(42u8)
 ///             .push_arg(true)
 ///             .push_arg(&[0x10u8; 32])
@@ -629,6 +629,21 @@ where
     pub fn fire(self) -> Result<R, Error> {
         self.params().invoke()
     }
+
+    /// Invokes the cross-chain function call without allowing the callee to change
+    /// any state and returns the result.
+    ///
+    /// This is meant for queries, e.g. of a price oracle, from within messages that
+    /// must not change state themselves. The callee (or any of its callees) trying to
+    /// change state, e.g. by writing to its storage or emitting an event, traps.
+    ///
+    /// # Note
+    ///
+    /// See [`CallFlags::set_read_only`] for more information.
+    pub fn invoke_read_only(self) -> Result<R, Error> {
+        let call_flags = self.call_flags.set_read_only(true);
+        self.call_flags(call_flags).fire()
+    }
 }
 
 impl<E, Args, R>
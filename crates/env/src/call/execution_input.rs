@@ -13,9 +13,41 @@
 // limitations under the License.
 
 use crate::call::Selector;
+use ink_prelude::vec::Vec;
 
 /// The input data for a smart contract execution.
-#[derive(Default, Debug)]
+///
+/// Consists of the selector of the called message or constructor followed by its
+/// SCALE encoded arguments.
+///
+/// # Example
+///
+/// Arguments are pushed in the order in which the called message expects them.
+/// Borrowed values can be pushed without cloning them since they are encoded
+/// only once the call is fired.
+///
+/// ```
+/// # use ink_env::call::{ExecutionInput, Selector};
+/// let recipients = vec![[0x01; 32], [0x02; 32]];
+/// let input = ExecutionInput::new(Selector::from_label("batch_transfer"))
+///     .push_arg(&recipients)
+///     .push_arg(100u128);
+/// assert_eq!(input.selector(), Selector::from_label("batch_transfer"));
+/// ```
+///
+/// Routers and multicall contracts which only know the call data at runtime
+/// can use already encoded arguments instead:
+///
+/// ```
+/// # use ink_env::call::{ExecutionInput, Selector};
+/// let encoded_args = scale::Encode::encode(&(42u32, true));
+/// let input = ExecutionInput::from_encoded(Selector::from_label("set"), encoded_args);
+/// assert_eq!(
+///     scale::Encode::encode(&input),
+///     scale::Encode::encode(&(Selector::from_label("set"), 42u32, true)),
+/// );
+/// ```
+#[derive(Default, Debug, Clone)]
 pub struct ExecutionInput<Args> {
     /// The selector for the smart contract execution.
     selector: Selector,
@@ -23,6 +55,20 @@ pub struct ExecutionInput<Args> {
     args: Args,
 }
 
+impl<Args> ExecutionInput<Args> {
+    /// Returns the selector of the execution input.
+    #[inline]
+    pub fn selector(&self) -> Selector {
+        self.selector
+    }
+
+    /// Returns the arguments of the execution input.
+    #[inline]
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+}
+
 impl ExecutionInput<EmptyArgumentList> {
     /// Creates a new execution input with the given selector.
     #[inline]
@@ -49,6 +95,24 @@ impl ExecutionInput<EmptyArgumentList> {
     }
 }
 
+impl ExecutionInput<EncodedArgs> {
+    /// Creates a new execution input with the given selector and already SCALE
+    /// encoded arguments.
+    ///
+    /// # Note
+    ///
+    /// The `encoded_args` are appended to the selector as they are. They must be
+    /// the concatenated SCALE encodings of the arguments of the called message or
+    /// constructor, i.e. without a length prefix.
+    #[inline]
+    pub fn from_encoded(selector: Selector, encoded_args: Vec<u8>) -> Self {
+        Self {
+            selector,
+            args: EncodedArgs::from_encoded(encoded_args),
+        }
+    }
+}
+
 impl<Head, Rest> ExecutionInput<ArgumentList<Argument<Head>, Rest>> {
     /// Pushes an argument to the execution input.
     #[inline]
@@ -70,7 +134,7 @@ impl<Head, Rest> ExecutionInput<ArgumentList<Argument<Head>, Rest>> {
 /// arguments. The potentially heap allocating encoding is done right at the end
 /// where we can leverage the static environmental buffer instead of allocating
 /// heap memory.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct ArgumentList<Head, Rest> {
     /// The first argument of the argument list.
     head: Head,
@@ -82,7 +146,7 @@ pub struct ArgumentList<Head, Rest> {
 pub type ArgsList<Head, Rest> = ArgumentList<Argument<Head>, Rest>;
 
 /// A single argument and its reference to a known value.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Argument<T> {
     /// The reference to the known value.
     ///
@@ -93,13 +157,19 @@ pub struct Argument<T> {
 impl<T> Argument<T> {
     /// Creates a new argument.
     #[inline]
-    fn new(arg: T) -> Self {
+    pub fn new(arg: T) -> Self {
         Self { arg }
     }
+
+    /// Returns a shared reference to the value of the argument.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.arg
+    }
 }
 
 /// The end of an argument list.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Copy, Clone)]
 pub struct ArgumentListEnd;
 
 /// An empty argument list.
@@ -188,6 +258,42 @@ where
     }
 }
 
+/// Arguments which have already been SCALE encoded.
+///
+/// Used for calls whose arguments are only known at runtime, e.g. when forwarding
+/// call data received from another contract. See [`ExecutionInput::from_encoded`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct EncodedArgs {
+    /// The concatenated SCALE encodings of the arguments.
+    encoded: Vec<u8>,
+}
+
+impl EncodedArgs {
+    /// Creates the arguments from their concatenated SCALE encodings.
+    #[inline]
+    pub fn from_encoded(encoded: Vec<u8>) -> Self {
+        Self { encoded }
+    }
+
+    /// Returns the concatenated SCALE encodings of the arguments.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.encoded
+    }
+}
+
+impl scale::Encode for EncodedArgs {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.encoded.len()
+    }
+
+    #[inline]
+    fn encode_to<O: scale::Output + ?Sized>(&self, output: &mut O) {
+        output.write(&self.encoded)
+    }
+}
+
 impl<Args> scale::Encode for ExecutionInput<Args>
 where
     Args: scale::Encode,
@@ -246,4 +352,33 @@ mod tests {
             <(i32, bool, [u8; 4]) as scale::Decode>::decode(&mut &encoded[..]).unwrap();
         assert_eq!(decoded, (42i32, true, [0x66; 4]));
     }
+
+    #[test]
+    fn borrowed_arguments_work() {
+        let values = vec![1u8, 2, 3];
+        let exec_input = ExecutionInput::new(Selector::new([0x01, 0x02, 0x03, 0x04]))
+            .push_arg(&values)
+            .push_arg(&42i32);
+        let encoded = scale::Encode::encode(&exec_input);
+        let decoded =
+            <(Selector, Vec<u8>, i32) as scale::Decode>::decode(&mut &encoded[..])
+                .unwrap();
+        assert_eq!(decoded, (exec_input.selector(), values, 42i32));
+    }
+
+    #[test]
+    fn encoded_arguments_work() {
+        let selector = Selector::new([0x01, 0x02, 0x03, 0x04]);
+        let typed = ExecutionInput::new(selector).push_arg(42i32).push_arg(true);
+        let encoded =
+            ExecutionInput::from_encoded(selector, scale::Encode::encode(&(42i32, true)));
+        assert_eq!(
+            scale::Encode::encode(&encoded),
+            scale::Encode::encode(&typed)
+        );
+        assert_eq!(
+            scale::Encode::size_hint(&encoded),
+            scale::Encode::size_hint(&typed)
+        );
+    }
 }
@@ -18,9 +18,15 @@ mod call_builder;
 mod common;
 mod create_builder;
 mod execution_input;
-mod selector;
 
 /// Utility types for the cross-contract calling API.
+///
+/// The argument list types are the building blocks of [`ExecutionInput`] and allow
+/// to name the type of an execution input, e.g. in order to store or return it.
+/// Arguments which are only known at runtime can be provided as [`EncodedArgs`]
+/// via [`ExecutionInput::from_encoded`].
+///
+/// [`EncodedArgs`]: crate::call::utils::EncodedArgs
 pub mod utils {
     pub use super::{
         common::{
@@ -35,6 +41,7 @@ pub mod utils {
             ArgumentList,
             ArgumentListEnd,
             EmptyArgumentList,
+            EncodedArgs,
         },
     };
 }
@@ -55,5 +62,5 @@ pub use self::{
         FromAccountId,
     },
     execution_input::ExecutionInput,
-    selector::Selector,
 };
+pub use ink_primitives::Selector;
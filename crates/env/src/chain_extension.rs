@@ -25,6 +25,7 @@ use crate::{
     },
 };
 use core::marker::PhantomData;
+use ink_primitives::FunctionId;
 
 /// Implemented by error codes in order to construct them from status codes.
 ///
@@ -76,7 +77,8 @@ pub trait FromStatusCode: Sized {
 ///     - **B:** The chain extension method returns a type `T` that is not a `Result` type: `NoResult<T>`
 #[derive(Debug)]
 pub struct ChainExtensionMethod<I, O, ErrorCode> {
-    func_id: u32,
+    func_id: FunctionId,
+    weight: Option<u64>,
     #[allow(clippy::type_complexity)]
     state: PhantomData<fn() -> (I, O, ErrorCode)>,
 }
@@ -86,12 +88,30 @@ impl ChainExtensionMethod<(), (), ()> {
     #[inline]
     pub fn build(func_id: u32) -> Self {
         Self {
-            func_id,
+            func_id: FunctionId::new(func_id),
+            weight: None,
             state: Default::default(),
         }
     }
 }
 
+impl<I, O, ErrorCode> ChainExtensionMethod<I, O, ErrorCode> {
+    /// Declares the weight the chain extension method is expected to charge per call.
+    ///
+    /// # Note
+    ///
+    /// The weight is charged by the off-chain environment in order to estimate the
+    /// costs of chain extension method calls in tests. On-chain the chain extension
+    /// charges its weight on its own.
+    #[inline]
+    pub fn weight(self, weight: u64) -> Self {
+        Self {
+            weight: Some(weight),
+            ..self
+        }
+    }
+}
+
 impl<O, ErrorCode> ChainExtensionMethod<(), O, ErrorCode> {
     /// Sets the input types of the chain extension method call to `I`.
     ///
@@ -107,6 +127,7 @@ impl<O, ErrorCode> ChainExtensionMethod<(), O, ErrorCode> {
     {
         ChainExtensionMethod {
             func_id: self.func_id,
+            weight: self.weight,
             state: Default::default(),
         }
     }
@@ -126,6 +147,7 @@ impl<I, ErrorCode> ChainExtensionMethod<I, (), ErrorCode> {
     {
         ChainExtensionMethod {
             func_id: self.func_id,
+            weight: self.weight,
             state: Default::default(),
         }
     }
@@ -145,6 +167,7 @@ impl<I, ErrorCode> ChainExtensionMethod<I, (), ErrorCode> {
     {
         ChainExtensionMethod {
             func_id: self.func_id,
+            weight: self.weight,
             state: Default::default(),
         }
     }
@@ -164,6 +187,7 @@ impl<I, O> ChainExtensionMethod<I, O, ()> {
     pub fn ignore_error_code(self) -> ChainExtensionMethod<I, O, state::IgnoreErrorCode> {
         ChainExtensionMethod {
             func_id: self.func_id,
+            weight: self.weight,
             state: Default::default(),
         }
     }
@@ -183,6 +207,7 @@ impl<I, O> ChainExtensionMethod<I, O, ()> {
     {
         ChainExtensionMethod {
             func_id: self.func_id,
+            weight: self.weight,
             state: Default::default(),
         }
     }
@@ -266,7 +291,8 @@ where
         <EnvInstance as OnInstance>::on_instance(|instance| {
             EnvBackend::call_chain_extension::<I, T, E, ErrorCode, _, _>(
                 instance,
-                self.func_id,
+                self.func_id.into_u32(),
+                self.weight,
                 input,
                 ErrorCode::from_status_code,
                 |mut output| scale::Decode::decode(&mut output).map_err(Into::into),
@@ -320,7 +346,8 @@ where
         <EnvInstance as OnInstance>::on_instance(|instance| {
             EnvBackend::call_chain_extension::<I, T, E, E, _, _>(
                 instance,
-                self.func_id,
+                self.func_id.into_u32(),
+                self.weight,
                 input,
                 |_status_code| Ok(()),
                 |mut output| scale::Decode::decode(&mut output).map_err(Into::into),
@@ -377,7 +404,8 @@ where
         <EnvInstance as OnInstance>::on_instance(|instance| {
             EnvBackend::call_chain_extension::<I, O, ErrorCode, ErrorCode, _, _>(
                 instance,
-                self.func_id,
+                self.func_id.into_u32(),
+                self.weight,
                 input,
                 ErrorCode::from_status_code,
                 |mut output| {
@@ -426,7 +454,8 @@ where
         <EnvInstance as OnInstance>::on_instance(|instance| {
             EnvBackend::call_chain_extension::<I, O, (), (), _, _>(
                 instance,
-                self.func_id,
+                self.func_id.into_u32(),
+                self.weight,
                 input,
                 |_status_code| Ok(()),
                 |mut output| {
@@ -0,0 +1,181 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for contracts under development.
+//!
+//! Only available with the `ink-debug` feature or in the off-chain environment,
+//! so contracts built for production do not pay for the bookkeeping.
+
+/// Storage access statistics of the current call.
+///
+/// # Usage
+///
+/// ```
+/// let stats = ink_env::debug::io_stats();
+/// ink_env::debug_println!(
+///     "reads: {}, writes: {}, bytes read: {}, bytes written: {}",
+///     stats.reads,
+///     stats.writes,
+///     stats.bytes_read,
+///     stats.bytes_written,
+/// );
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct IoStats {
+    /// The number of storage reads, including checks whether a key exists.
+    pub reads: usize,
+    /// The number of storage writes, including removals.
+    pub writes: usize,
+    /// The number of bytes read from the storage.
+    pub bytes_read: usize,
+    /// The number of bytes written to the storage.
+    pub bytes_written: usize,
+}
+
+impl IoStats {
+    /// Creates statistics without any recorded storage access.
+    pub const fn new() -> Self {
+        Self {
+            reads: 0,
+            writes: 0,
+            bytes_read: 0,
+            bytes_written: 0,
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(all(not(feature = "std"), target_arch = "wasm32"))] {
+        fn with_io_stats<F, R>(f: F) -> R
+        where
+            F: FnOnce(&mut IoStats) -> R,
+        {
+            use crate::engine::CallLocal;
+            static IO_STATS: CallLocal<IoStats> = CallLocal::new(IoStats::new());
+            IO_STATS.with(f)
+        }
+    } else {
+        fn with_io_stats<F, R>(f: F) -> R
+        where
+            F: FnOnce(&mut IoStats) -> R,
+        {
+            use core::cell::RefCell;
+            thread_local!(
+                static IO_STATS: RefCell<IoStats> = const { RefCell::new(IoStats::new()) }
+            );
+            IO_STATS.with(|stats| f(&mut stats.borrow_mut()))
+        }
+
+        /// Resets the statistics once a new call starts in the off-chain environment.
+        pub(crate) fn reset_io_stats() {
+            with_io_stats(|stats| *stats = IoStats::new())
+        }
+    }
+}
+
+/// Records a read of `bytes` bytes from the storage.
+pub(crate) fn record_read(bytes: usize) {
+    with_io_stats(|stats| {
+        stats.reads += 1;
+        stats.bytes_read += bytes;
+    })
+}
+
+/// Records a write of `bytes` bytes to the storage.
+pub(crate) fn record_write(bytes: usize) {
+    with_io_stats(|stats| {
+        stats.writes += 1;
+        stats.bytes_written += bytes;
+    })
+}
+
+/// Returns the storage reads and writes performed during the current call.
+///
+/// This allows contracts under development to report the numbers via
+/// [`debug_println!`](crate::debug_println) in order to catch unexpectedly
+/// expensive storage access, e.g. iterating over all entries of a collection.
+///
+/// In the off-chain environment a new call starts with [`test::start_call`] or
+/// [`test::deploy`].
+///
+/// [`test::start_call`]: crate::test::start_call
+/// [`test::deploy`]: crate::test::deploy
+pub fn io_stats() -> IoStats {
+    with_io_stats(|stats| *stats)
+}
+
+/// Reports that a value of type `T` could not be decoded via [`debug_message`].
+///
+/// The `context` describes what was being decoded, e.g. the arguments of an
+/// ink! message, and `offset` is the number of bytes of the input consumed
+/// before decoding failed, if known.
+///
+/// # Usage
+///
+/// ```
+/// let bytes = [1u8, 2];
+/// let input = &mut &bytes[..];
+/// if let Err(error) = <u32 as scale::Decode>::decode(input) {
+///     let offset = bytes.len() - input.len();
+///     ink_env::debug::report_decode_error::<u32>("my value", Some(offset), &error);
+/// }
+/// ```
+///
+/// [`debug_message`]: crate::debug_message
+pub fn report_decode_error<T>(context: &str, offset: Option<usize>, error: &scale::Error)
+where
+    T: ?Sized,
+{
+    let message = decode_error_message::<T>(context, offset, error);
+    cfg_if::cfg_if! {
+        if #[cfg(all(not(feature = "std"), target_arch = "wasm32"))] {
+            // Decoding might happen while the environment is in use,
+            // e.g. when decoding the input of the contract.
+            crate::engine::debug_message(&message)
+        } else {
+            crate::debug_message(&message)
+        }
+    }
+}
+
+/// Returns the debug message reporting that a value of type `T` could not be decoded.
+pub(crate) fn decode_error_message<T>(
+    context: &str,
+    offset: Option<usize>,
+    error: &scale::Error,
+) -> ink_prelude::string::String
+where
+    T: ?Sized,
+{
+    let type_name = core::any::type_name::<T>();
+    match offset {
+        Some(offset) => {
+            ink_prelude::format!(
+                "{}: failed to decode `{}` at byte offset {}: {}\n",
+                context,
+                type_name,
+                offset,
+                error
+            )
+        }
+        None => {
+            ink_prelude::format!(
+                "{}: failed to decode `{}`: {}\n",
+                context,
+                type_name,
+                error
+            )
+        }
+    }
+}
@@ -28,6 +28,9 @@ cfg_if! {
     if #[cfg(all(not(feature = "std"), target_arch = "wasm32"))] {
         mod on_chain;
         pub use self::on_chain::EnvInstance;
+        pub(crate) use self::on_chain::CallLocal;
+        #[cfg(feature = "ink-debug")]
+        pub(crate) use self::on_chain::debug_message;
     } else if #[cfg(feature = "std")] {
         pub mod off_chain;
         pub use self::off_chain::EnvInstance;
@@ -37,3 +40,36 @@ cfg_if! {
         }
     }
 }
+
+/// Decodes the storage entry under the encoded `key` from `bytes`.
+///
+/// If debug messages are enabled, a failure is passed to `report` together with the
+/// expected type and the byte offset at which decoding failed.
+pub(crate) fn decode_storage<R, F>(
+    key: &[u8],
+    bytes: &[u8],
+    report: F,
+) -> Result<R, scale::Error>
+where
+    R: ink_storage_traits::Storable,
+    F: FnOnce(&str),
+{
+    let input = &mut &bytes[..];
+    <R as ink_storage_traits::Storable>::decode(input).map_err(|error| {
+        #[cfg(any(feature = "ink-debug", feature = "std"))]
+        {
+            let key = key
+                .iter()
+                .map(|byte| ink_prelude::format!("{:02x}", byte))
+                .collect::<ink_prelude::string::String>();
+            report(&crate::debug::decode_error_message::<R>(
+                &ink_prelude::format!("storage entry under key 0x{}", key),
+                Some(bytes.len() - input.len()),
+                &error,
+            ));
+        }
+        #[cfg(not(any(feature = "ink-debug", feature = "std")))]
+        let _ = (key, report);
+        error
+    })
+}
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::EnvInstance;
+use super::{
+    test_api::ReturnValue,
+    EnvInstance,
+};
 use crate::{
     call::{
         Call,
@@ -49,7 +52,7 @@ use ink_storage_traits::Storable;
 /// The capacity of the static buffer.
 /// This is the same size as the ink! on-chain environment. We chose to use the same size
 /// to be as close to the on-chain behavior as possible.
-const BUFFER_SIZE: usize = 1 << 14; // 16 kB
+const BUFFER_SIZE: usize = crate::BUFFER_SIZE;
 
 impl CryptoHash for Blake2x128 {
     fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
@@ -117,16 +120,62 @@ impl From<ext::Error> for crate::Error {
     }
 }
 
+/// Returns the topic under which `topic_value` is recorded.
+///
+/// Values whose encoding fits into `E::Hash` are stored as is, larger ones are hashed.
+pub(crate) fn topic_hash<E, T>(topic_value: &T) -> <E as Environment>::Hash
+where
+    E: Environment,
+    T: scale::Encode,
+{
+    encoded_topic_hash::<E>(&topic_value.encode()[..])
+}
+
+/// Returns the topic under which the value with the `encoded` bytes is recorded.
+fn encoded_topic_hash<E>(encoded: &[u8]) -> <E as Environment>::Hash
+where
+    E: Environment,
+{
+    let len_encoded = encoded.len();
+    let mut result = <E as Environment>::Hash::clear();
+    let len_result = result.as_ref().len();
+    if len_encoded <= len_result {
+        result.as_mut()[..len_encoded].copy_from_slice(encoded);
+    } else {
+        let mut hash_output = <Blake2x256 as HashOutput>::Type::default();
+        <Blake2x256 as CryptoHash>::hash(encoded, &mut hash_output);
+        let copy_len = core::cmp::min(hash_output.len(), len_result);
+        result.as_mut()[0..copy_len].copy_from_slice(&hash_output[0..copy_len]);
+    }
+    result
+}
+
 #[derive(Default)]
 pub struct TopicsBuilder {
     pub topics: Vec<Vec<u8>>,
+    /// The number of topics which had to be hashed.
+    pub hash_calls: usize,
+    /// Scratch buffer shared by the encodings of all pushed topics.
+    scratch: Vec<u8>,
+}
+
+impl TopicsBuilder {
+    /// Records the serialized `topic`.
+    fn record(&mut self, topic: Vec<u8>) {
+        debug_assert!(
+            !self.topics.contains(&topic),
+            "duplicate topic hash discovered!"
+        );
+        self.topics.push(topic);
+    }
 }
 
 impl<E> TopicsBuilderBackend<E> for TopicsBuilder
 where
     E: Environment,
 {
-    type Output = Vec<u8>;
+    /// The serialized topics together with the number of topics which had to be hashed.
+    type Output = (Vec<u8>, usize);
 
     fn expect(&mut self, _expected_topics: usize) {}
 
@@ -134,25 +183,26 @@ where
     where
         T: scale::Encode,
     {
-        let encoded = topic_value.encode();
-        let len_encoded = encoded.len();
-        let mut result = <E as Environment>::Hash::clear();
-        let len_result = result.as_ref().len();
-        if len_encoded <= len_result {
-            result.as_mut()[..len_encoded].copy_from_slice(&encoded[..]);
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.clear();
+        topic_value.encode_to(&mut scratch);
+        let result = encoded_topic_hash::<E>(&scratch[..]);
+        if scratch.len() > result.as_ref().len() {
+            self.hash_calls += 1;
+        }
+        self.scratch = scratch;
+        self.record(result.as_ref().to_vec());
+    }
+
+    fn push_precomputed_topic<T>(&mut self, topic: &[u8; 32], topic_value: &T)
+    where
+        T: scale::Encode,
+    {
+        if <E as Environment>::Hash::clear().as_ref().len() == topic.len() {
+            self.record(topic.to_vec());
         } else {
-            let mut hash_output = <Blake2x256 as HashOutput>::Type::default();
-            <Blake2x256 as CryptoHash>::hash(&encoded[..], &mut hash_output);
-            let copy_len = core::cmp::min(hash_output.len(), len_result);
-            result.as_mut()[0..copy_len].copy_from_slice(&hash_output[0..copy_len]);
+            <Self as TopicsBuilderBackend<E>>::push_topic(self, topic_value);
         }
-        let off_hash = result.as_ref();
-        let off_hash = off_hash.to_vec();
-        debug_assert!(
-            !self.topics.contains(&off_hash),
-            "duplicate topic hash discovered!"
-        );
-        self.topics.push(off_hash);
     }
 
     fn output(self) -> Self::Output {
@@ -163,7 +213,7 @@ where
         all.append(&mut topics_encoded.to_vec());
 
         self.topics.into_iter().for_each(|mut v| all.append(&mut v));
-        all
+        (all, self.hash_calls)
     }
 }
 
@@ -191,6 +241,7 @@ impl EnvBackend for EnvInstance {
     {
         let mut v = vec![];
         Storable::encode(value, &mut v);
+        crate::debug::record_write(v.len());
         self.engine.set_storage(&key.encode(), &v[..])
     }
 
@@ -200,12 +251,18 @@ impl EnvBackend for EnvInstance {
         R: Storable,
     {
         let mut output: [u8; 9600] = [0; 9600];
-        match self.engine.get_storage(&key.encode(), &mut &mut output[..]) {
+        let output = &mut &mut output[..];
+        let key = key.encode();
+        let result = self.engine.get_storage(&key, output);
+        crate::debug::record_read(if result.is_ok() { output.len() } else { 0 });
+        match result {
             Ok(_) => (),
             Err(ext::Error::KeyNotFound) => return Ok(None),
             Err(_) => panic!("encountered unexpected error"),
         }
-        let decoded = Storable::decode(&mut &output[..])?;
+        let decoded = crate::engine::decode_storage(&key, &output[..], |message| {
+            self.engine.debug_message(message)
+        })?;
         Ok(Some(decoded))
     }
 
@@ -215,15 +272,19 @@ impl EnvBackend for EnvInstance {
         R: Storable,
     {
         let mut output: [u8; 9600] = [0; 9600];
-        match self
-            .engine
-            .take_storage(&key.encode(), &mut &mut output[..])
-        {
+        let output = &mut &mut output[..];
+        let key = key.encode();
+        let result = self.engine.take_storage(&key, output);
+        crate::debug::record_read(if result.is_ok() { output.len() } else { 0 });
+        crate::debug::record_write(0);
+        match result {
             Ok(_) => (),
             Err(ext::Error::KeyNotFound) => return Ok(None),
             Err(_) => panic!("encountered unexpected error"),
         }
-        let decoded = Storable::decode(&mut &output[..])?;
+        let decoded = crate::engine::decode_storage(&key, &output[..], |message| {
+            self.engine.debug_message(message)
+        })?;
         Ok(Some(decoded))
     }
 
@@ -231,6 +292,7 @@ impl EnvBackend for EnvInstance {
     where
         K: scale::Encode,
     {
+        crate::debug::record_read(0);
         self.engine.contains_storage(&key.encode())
     }
 
@@ -238,9 +300,18 @@ impl EnvBackend for EnvInstance {
     where
         K: scale::Encode,
     {
+        crate::debug::record_write(0);
         self.engine.clear_storage(&key.encode())
     }
 
+    fn clear_contract_storage_prefix<K>(&mut self, prefix: &K) -> Result<u32>
+    where
+        K: scale::Encode,
+    {
+        crate::debug::record_write(0);
+        Ok(self.engine.clear_storage_prefix(&prefix.encode()))
+    }
+
     fn decode_input<T>(&mut self) -> Result<T>
     where
         T: scale::Decode,
@@ -248,11 +319,16 @@ impl EnvBackend for EnvInstance {
         unimplemented!("the off-chain env does not implement `input`")
     }
 
-    fn return_value<R>(&mut self, _flags: ReturnFlags, _return_value: &R) -> !
+    fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
     where
         R: scale::Encode,
     {
-        unimplemented!("the off-chain env does not implement `return_value`")
+        // Unwind with the encoded output so that off-chain tests such as
+        // `test::deploy` are able to inspect how the execution ended.
+        std::panic::panic_any(ReturnValue {
+            reverted: flags.is_reverted(),
+            data: scale::Encode::encode(return_value),
+        })
     }
 
     fn debug_message(&mut self, message: &str) {
@@ -335,6 +411,7 @@ impl EnvBackend for EnvInstance {
     fn call_chain_extension<I, T, E, ErrorCode, F, D>(
         &mut self,
         func_id: u32,
+        weight: Option<u64>,
         input: &I,
         status_to_result: F,
         decode_to_result: D,
@@ -349,6 +426,9 @@ impl EnvBackend for EnvInstance {
         let enc_input = &scale::Encode::encode(input)[..];
         let mut output: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
 
+        if let Some(weight) = weight {
+            self.engine.charge_gas(weight);
+        }
         self.engine
             .call_chain_extension(func_id, enc_input, &mut &mut output[..]);
         let (status, out): (u32, Vec<u8>) = scale::Decode::decode(&mut &output[..])
@@ -432,8 +512,9 @@ impl TypedEnvBackend for EnvInstance {
         Event: Topics + scale::Encode,
     {
         let builder = TopicsBuilder::default();
-        let enc_topics = event.topics::<E, _>(builder.into());
+        let (enc_topics, hash_calls) = event.topics::<E, _>(builder.into());
         let enc_data = &scale::Encode::encode(&event)[..];
+        self.engine.record_topic_hashes(hash_calls);
         self.engine.deposit_event(&enc_topics[..], enc_data);
     }
 
@@ -446,12 +527,33 @@ impl TypedEnvBackend for EnvInstance {
         Args: scale::Encode,
         R: scale::Decode,
     {
-        let _gas_limit = params.gas_limit();
-        let _callee = params.callee();
+        let gas_limit = params.gas_limit();
+        let enc_callee = &scale::Encode::encode(&params.callee())[..];
         let _call_flags = params.call_flags().into_u32();
-        let _transferred_value = params.transferred_value();
-        let _input = params.exec_input();
-        unimplemented!("off-chain environment does not support contract invocation")
+        let enc_transferred_value =
+            &scale::Encode::encode(&params.transferred_value())[..];
+        let enc_input = &scale::Encode::encode(params.exec_input())[..];
+        let mut output: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+        let output = &mut &mut output[..];
+        if params.call_flags().read_only() {
+            self.engine.call_read_only(
+                enc_callee,
+                gas_limit,
+                enc_transferred_value,
+                enc_input,
+                output,
+            )?;
+        } else {
+            self.engine.call(
+                enc_callee,
+                gas_limit,
+                enc_transferred_value,
+                enc_input,
+                output,
+            )?;
+        }
+        let decoded = scale::Decode::decode(&mut &output[..])?;
+        Ok(decoded)
     }
 
     fn invoke_contract_delegate<E, Args, R>(
@@ -527,17 +629,25 @@ impl TypedEnvBackend for EnvInstance {
         unimplemented!("off-chain environment does not support cross-contract calls")
     }
 
-    fn code_hash<E>(&mut self, _account: &E::AccountId) -> Result<E::Hash>
+    fn code_hash<E>(&mut self, account: &E::AccountId) -> Result<E::Hash>
     where
         E: Environment,
     {
-        unimplemented!("off-chain environment does not support `code_hash`")
+        let code_hash = self
+            .engine
+            .get_code_hash(scale::Encode::encode(account))
+            .ok_or(Error::CodeNotFound)?;
+        scale::Decode::decode(&mut &code_hash[..]).map_err(Into::into)
     }
 
     fn own_code_hash<E>(&mut self) -> Result<E::Hash>
     where
         E: Environment,
     {
-        unimplemented!("off-chain environment does not support `own_code_hash`")
+        let code_hash = self
+            .engine
+            .get_code_hash(self.engine.get_callee())
+            .ok_or(Error::CodeNotFound)?;
+        scale::Decode::decode(&mut &code_hash[..]).map_err(Into::into)
     }
 }
@@ -0,0 +1,120 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    call_data::CallData,
+    EnvInstance,
+    OnInstance,
+};
+use crate::{
+    call::Selector,
+    Environment,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
+
+/// Handles the SCALE encoded arguments of a message and returns its SCALE encoded output.
+type MessageHandler = Box<dyn FnMut(&[u8]) -> Vec<u8>>;
+
+/// A contract mock for the off-chain environment.
+///
+/// Once registered under an account all calls to this account are dispatched to the
+/// handler set for the selector of the called message. All calls are recorded in
+/// order and calling a message without a handler panics.
+///
+/// Clones of a contract mock share their handlers and recorded calls.
+///
+/// # Note
+///
+/// Handlers are invoked while the off-chain environment is in use and therefore
+/// must not call into the environment themselves.
+#[derive(Clone, Default)]
+pub struct MockContract {
+    state: Rc<RefCell<MockContractState>>,
+}
+
+/// The handlers and recorded calls shared by all clones of a [`MockContract`].
+#[derive(Default)]
+struct MockContractState {
+    /// The handlers of the mocked messages by selector.
+    handlers: HashMap<[u8; 4], MessageHandler>,
+    /// The calls to the contract mock in order.
+    calls: Vec<CallData>,
+}
+
+impl MockContract {
+    /// Creates a new contract mock without any handlers.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the handler of the message with the given `selector`.
+    ///
+    /// The handler receives the SCALE encoded arguments of the message and returns
+    /// its SCALE encoded output. Replaces any handler previously set for `selector`.
+    pub fn on<F>(&self, selector: Selector, handler: F) -> &Self
+    where
+        F: FnMut(&[u8]) -> Vec<u8> + 'static,
+    {
+        self.state
+            .borrow_mut()
+            .handlers
+            .insert(selector.to_bytes(), Box::new(handler));
+        self
+    }
+
+    /// Returns all recorded calls to the contract mock in order.
+    pub fn recorded_calls(&self) -> Vec<CallData> {
+        self.state.borrow().calls.clone()
+    }
+
+    /// Registers the contract mock under `account_id`.
+    ///
+    /// Replaces any contract mock previously registered under `account_id`.
+    pub fn register<T>(&self, account_id: T::AccountId)
+    where
+        T: Environment,
+    {
+        let mock = self.clone();
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance
+                .engine
+                .contract_mock_handler
+                .register(scale::Encode::encode(&account_id), Box::new(mock));
+        })
+    }
+}
+
+impl ink_engine::ContractMock for MockContract {
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) {
+        let call_data = <CallData as scale::Decode>::decode(&mut &input[..])
+            .unwrap_or_else(|err| panic!("decoding call data failed: {}", err));
+        let selector = call_data.selector();
+        let mut state = self.state.borrow_mut();
+        state.calls.push(call_data.clone());
+        let handler = state
+            .handlers
+            .get_mut(&selector.to_bytes())
+            .unwrap_or_else(|| {
+                panic!(
+                    "encountered unexpected call to contract mock with selector {:?}",
+                    selector.to_bytes()
+                )
+            });
+        output.extend(handler(call_data.params()));
+    }
+}
@@ -14,6 +14,8 @@
 
 mod call_data;
 mod impls;
+mod mock_contract;
+mod oracle;
 pub mod test_api;
 mod types;
 
@@ -46,7 +48,19 @@ impl OnInstance for EnvInstance {
                 }
             )
         );
-        INSTANCE.with(|instance| f(&mut instance.borrow_mut()))
+        INSTANCE.with(|instance| {
+            let (result, events) = {
+                let mut instance = instance.borrow_mut();
+                let result = f(&mut instance);
+                (result, instance.engine.take_queued_events())
+            };
+            // The listeners are notified once the instance is no longer in use,
+            // so that they can call into the environment themselves.
+            if !events.is_empty() {
+                test_api::notify_event_listeners(events);
+            }
+            result
+        })
     }
 }
 
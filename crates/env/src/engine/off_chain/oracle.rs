@@ -0,0 +1,212 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    EnvInstance,
+    OnInstance,
+};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{
+        Context,
+        Poll,
+        Waker,
+    },
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::Arc,
+    task::Wake,
+    thread::{
+        self,
+        Thread,
+    },
+};
+
+/// The future of an oracle request resolving to the SCALE encoded response or a
+/// non-zero status code.
+type OracleFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>, u32>>>>;
+
+/// Handles the SCALE encoded input of an oracle request.
+type OracleHandler = Box<dyn FnMut(&[u8]) -> OracleFuture>;
+
+/// Drives the future of an oracle request to completion.
+type OracleExecutor = Box<dyn FnMut(OracleFuture) -> Result<Vec<u8>, u32>>;
+
+/// An oracle stub for the off-chain environment.
+///
+/// Once registered all calls to the chain extension method with the oracle's
+/// function ID are answered by its asynchronous handler, which may for example
+/// load fixtures or query an HTTP endpoint. This allows to test contracts consuming
+/// an oracle chain extension against realistic responses. All requests are recorded
+/// in order.
+///
+/// Clones of an oracle stub share their handler and recorded requests.
+///
+/// # Note
+///
+/// By default the future returned by the handler is polled to completion on the
+/// current thread. Futures which rely on the reactor of an async runtime, e.g. the
+/// HTTP clients of `tokio`, must be driven by the runtime instead, see
+/// [`Oracle::with_executor`].
+///
+/// Handlers are invoked while the off-chain environment is in use and therefore
+/// must not call into the environment themselves.
+#[derive(Clone)]
+pub struct Oracle {
+    func_id: u32,
+    state: Rc<RefCell<OracleState>>,
+}
+
+/// The handler and recorded requests shared by all clones of an [`Oracle`].
+struct OracleState {
+    /// The handler of the oracle requests.
+    handler: OracleHandler,
+    /// The executor driving the futures returned by the handler.
+    executor: OracleExecutor,
+    /// The SCALE encoded inputs of the oracle requests in order.
+    requests: Vec<Vec<u8>>,
+}
+
+impl Oracle {
+    /// The status code returned for requests whose input cannot be decoded.
+    pub const INVALID_REQUEST: u32 = u32::MAX;
+
+    /// Creates a new oracle stub for the chain extension method `func_id`.
+    ///
+    /// The `handler` receives the decoded input of a request and resolves to its
+    /// response, which is SCALE encoded into the output of the chain extension
+    /// method, or to a non-zero status code in case of an error.
+    ///
+    /// Requests whose input cannot be decoded into `I` are answered with
+    /// [`Oracle::INVALID_REQUEST`] without calling the `handler`.
+    pub fn new<I, O, F, Fut>(func_id: u32, mut handler: F) -> Self
+    where
+        I: scale::Decode,
+        O: scale::Encode,
+        F: FnMut(I) -> Fut + 'static,
+        Fut: Future<Output = Result<O, u32>> + 'static,
+    {
+        let handler = move |mut input: &[u8]| -> OracleFuture {
+            let input = match <I as scale::Decode>::decode(&mut input) {
+                Ok(input) => input,
+                Err(_) => {
+                    return Box::pin(core::future::ready(Err(Self::INVALID_REQUEST)))
+                }
+            };
+            let response = handler(input);
+            Box::pin(async move {
+                response.await.map(|output| scale::Encode::encode(&output))
+            })
+        };
+        Self {
+            func_id,
+            state: Rc::new(RefCell::new(OracleState {
+                handler: Box::new(handler),
+                executor: Box::new(block_on),
+                requests: Vec::new(),
+            })),
+        }
+    }
+
+    /// Drives the futures returned by the handler with the `executor` instead of
+    /// polling them on the current thread.
+    ///
+    /// This allows handlers to await futures which rely on the reactor of an async
+    /// runtime.
+    ///
+    /// # Usage
+    ///
+    /// ```no_compile
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let oracle = Oracle::new(0x0101, fetch_price)
+    ///     .with_executor(move |future| runtime.block_on(future));
+    /// ```
+    pub fn with_executor<E>(self, executor: E) -> Self
+    where
+        E: FnMut(OracleFuture) -> Result<Vec<u8>, u32> + 'static,
+    {
+        self.state.borrow_mut().executor = Box::new(executor);
+        self
+    }
+
+    /// Returns the SCALE encoded inputs of all recorded requests in order.
+    pub fn recorded_requests(&self) -> Vec<Vec<u8>> {
+        self.state.borrow().requests.clone()
+    }
+
+    /// Registers the oracle stub as the chain extension method with its function ID.
+    ///
+    /// Replaces any chain extension previously registered for the function ID.
+    pub fn register(&self) {
+        let oracle = self.clone();
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance
+                .engine
+                .chain_extension_handler
+                .register(Box::new(oracle));
+        })
+    }
+}
+
+impl ink_engine::ChainExtension for Oracle {
+    fn func_id(&self) -> u32 {
+        self.func_id
+    }
+
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+        // The engine passes the SCALE encoded input of the contract as a byte vector.
+        let input = match <Vec<u8> as scale::Decode>::decode(&mut &input[..]) {
+            Ok(input) => input,
+            Err(_) => return Self::INVALID_REQUEST,
+        };
+        let mut state = self.state.borrow_mut();
+        state.requests.push(input.clone());
+        let response = (state.handler)(&input);
+        match (state.executor)(response) {
+            Ok(encoded) => {
+                output.extend(encoded);
+                0
+            }
+            Err(status_code) => status_code,
+        }
+    }
+}
+
+/// Wakes the thread which polls a future in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark()
+    }
+}
+
+/// Polls the `future` to completion on the current thread.
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -19,25 +19,117 @@ use super::{
     OnInstance,
 };
 use crate::{
+    call::ExecutionInput,
     Environment,
     Result,
 };
 use core::fmt::Debug;
 use ink_engine::test_api::RecordedDebugMessages;
-use std::panic::UnwindSafe;
+use ink_metadata::layout::{
+    Layout,
+    LayoutKey,
+    RootLayout,
+};
+use ink_primitives::{
+    Key,
+    LangError,
+};
+use ink_storage_traits::{
+    Storable,
+    StorageKey,
+    StorageLayout,
+};
+use std::{
+    cell::RefCell,
+    panic::UnwindSafe,
+    sync::{
+        Arc,
+        Mutex,
+        PoisonError,
+    },
+};
 
-pub use super::call_data::CallData;
-pub use ink_engine::ChainExtension;
+pub use super::{
+    call_data::CallData,
+    mock_contract::MockContract,
+    oracle::Oracle,
+};
+pub use ink_engine::{
+    test_api::{
+        Snapshot,
+        StorageDeposit,
+    },
+    CallInput,
+    CallInputLog,
+    ChainExtension,
+    EntryPoint,
+    HostCall,
+    HostCallLog,
+};
 
 /// Record for an emitted event.
 #[derive(Clone)]
 pub struct EmittedEvent {
+    /// The encoded account id of the contract which emitted the event.
+    ///
+    /// Is `None` if no callee has been set when the event was emitted.
+    pub emitter: Option<Vec<u8>>,
     /// Recorded topics of the emitted event.
     pub topics: Vec<Vec<u8>>,
     /// Recorded encoding of the emitted event.
     pub data: Vec<u8>,
 }
 
+impl EmittedEvent {
+    /// Returns the decoded account id of the contract which emitted the event.
+    pub fn decode_emitter<T>(&self) -> Option<T::AccountId>
+    where
+        T: Environment,
+    {
+        self.emitter.as_ref().map(|emitter| {
+            scale::Decode::decode(&mut &emitter[..])
+                .unwrap_or_else(|err| panic!("decoding emitter failed: {}", err))
+        })
+    }
+
+    /// Returns the decoded topics of the event in order.
+    ///
+    /// Use [`topic()`] to compute the expected topic of a value.
+    pub fn decode_topics<T>(&self) -> Vec<T::Hash>
+    where
+        T: Environment,
+    {
+        self.topics
+            .iter()
+            .map(|topic| {
+                scale::Decode::decode(&mut &topic[..])
+                    .unwrap_or_else(|err| panic!("decoding topic failed: {}", err))
+            })
+            .collect()
+    }
+
+    /// Returns the data of the event decoded as an `E` and rendered as pretty-printed
+    /// JSON, e.g. for readable assertion messages.
+    ///
+    /// Falls back to the hex encoding of the data together with the reason if the data
+    /// can't be rendered as an `E`.
+    pub fn data_json<E>(&self) -> String
+    where
+        E: scale_info::TypeInfo + 'static,
+    {
+        match ink_metadata::json::to_json::<E>(&self.data) {
+            Ok(value) => format!("{:#}", value),
+            Err(error) => format!("{} ({})", to_hex(&self.data), error),
+        }
+    }
+}
+
+/// Returns the `0x` prefixed hex representation of `bytes`.
+fn to_hex(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("0x{}", hex)
+}
+
 /// Sets the balance of the account to the given balance.
 ///
 /// # Note
@@ -85,6 +177,24 @@ where
     })
 }
 
+/// Sets the code hash of the contract at `account_id` to `code_hash`.
+///
+/// The code hash is returned by [`code_hash`](crate::code_hash) and, for the
+/// callee, by [`own_code_hash`](crate::own_code_hash), which fail with
+/// [`Error::CodeNotFound`](crate::Error::CodeNotFound) as long as no code hash was
+/// set for the account.
+pub fn set_contract_code_hash<T>(account_id: T::AccountId, code_hash: T::Hash)
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_code_hash(
+            scale::Encode::encode(&account_id),
+            scale::Encode::encode(&code_hash).as_ref(),
+        );
+    })
+}
+
 /// Registers a new chain extension.
 pub fn register_chain_extension<E>(extension: E)
 where
@@ -105,6 +215,258 @@ pub fn recorded_debug_messages() -> RecordedDebugMessages {
     })
 }
 
+/// Returns the gas consumed during the test so far.
+///
+/// Calls to chain extension methods declared with `#[ink(weight = N)]` consume
+/// `N` gas each, which allows to estimate the costs of extension heavy contracts.
+pub fn gas_consumed() -> u64 {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.engine.gas_consumed())
+}
+
+/// Charges `weight` to the gas consumed during the test, e.g. to emulate the costs
+/// of the iterations of a loop.
+pub fn charge_gas(weight: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.charge_gas(weight)
+    })
+}
+
+/// Sets the gas limit of the call, `None` for unlimited gas.
+///
+/// The gas left returned by [`gas_left`](crate::gas_left) is the gas limit minus the
+/// gas consumed so far, see [`gas_consumed`]. The gas is unlimited by default.
+pub fn set_gas_limit(limit: Option<u64>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_gas_limit(limit)
+    })
+}
+
+/// Starts recording all host calls of the contract together with their results.
+///
+/// Use [`take_host_call_log()`] to retrieve the recording and [`HostCallLog::save`]
+/// to persist it, e.g. when a flaky test fails.
+pub fn record_host_calls() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.record_host_calls();
+    })
+}
+
+/// Starts replaying the host calls of `log`.
+///
+/// Every subsequent host call returns the result recorded in `log`, which makes the
+/// recorded test run reproducible bit-for-bit. Chain extensions and contract mocks
+/// are not called while replaying.
+///
+/// # Panics
+///
+/// Any subsequent host call panics if it diverges from the recorded one.
+pub fn replay_host_calls(log: HostCallLog) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.replay_host_calls(log);
+    })
+}
+
+/// Stops recording or replaying host calls.
+///
+/// Returns the recorded host calls, or the host calls of the replayed log which
+/// have not been replayed yet.
+pub fn take_host_call_log() -> HostCallLog {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.take_host_call_log()
+    })
+}
+
+/// Starts recording the raw input of every constructor and message dispatched with
+/// [`deploy()`], [`dispatch()`] or their replaying counterparts.
+///
+/// Use [`take_call_input_log()`] to retrieve the recording and
+/// [`CallInputLog::save`] to persist it, e.g. when a fuzz case fails. The recorded
+/// inputs can be replayed byte-for-byte with [`replay_deploy()`] and
+/// [`replay_dispatch()`].
+pub fn record_call_inputs() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.record_call_inputs();
+    })
+}
+
+/// Stops recording call inputs and returns the recorded ones.
+pub fn take_call_input_log() -> CallInputLog {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.take_call_input_log()
+    })
+}
+
+/// Returns the input of the recorded `call` rendered as pretty-printed JSON of its
+/// selector and its arguments decoded as an `Args`, e.g. the tuple of the argument
+/// types of the called message, for readable assertion messages.
+///
+/// Falls back to the hex encoding of the arguments together with the reason if they
+/// can't be rendered as an `Args`.
+pub fn call_input_json<Args>(call: &CallInput) -> String
+where
+    Args: scale_info::TypeInfo + 'static,
+{
+    let (selector, args) = call.input.split_at(call.input.len().min(4));
+    let args = match ink_metadata::json::to_json::<Args>(args) {
+        Ok(value) => value,
+        Err(error) => format!("{} ({})", to_hex(args), error).into(),
+    };
+    let input = ink_metadata::json::Value::from_iter([
+        ("selector", to_hex(selector).into()),
+        ("args", args),
+    ]);
+    format!("{:#}", input)
+}
+
+/// Returns a copy of the current state of the off-chain environment.
+///
+/// The state comprises the storage and balances of all accounts, the execution
+/// context, the chain specification and the recorded events and statistics.
+/// Registered chain extensions and contract mocks are not part of a snapshot.
+///
+/// Use [`restore_snapshot()`] to return to the state, e.g. to undo the effects of
+/// a call.
+pub fn snapshot() -> Snapshot {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.engine.snapshot())
+}
+
+/// Restores the state of the off-chain environment to `snapshot`.
+pub fn restore_snapshot(snapshot: &Snapshot) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.restore(snapshot);
+    })
+}
+
+/// A chain state shared by the tests of a test binary.
+///
+/// The `genesis` function of the fixture sets up the state, e.g. by deploying and
+/// initializing contracts. It runs only once, in the first test calling
+/// [`Fixture::setup`]. Every other test restores a [`snapshot()`] of the resulting
+/// state instead, which is much cheaper for contracts with an expensive setup.
+///
+/// # Note
+///
+/// Chain extensions and contract mocks registered by `genesis` are only available
+/// in the test which ran it. Register them in every test instead.
+///
+/// # Usage
+///
+/// ```no_compile
+/// static FIXTURE: ink_env::test::Fixture = ink_env::test::Fixture::new(|| {
+///     ink_env::test::deploy::<Exchange, _>(
+///         ExecutionInput::new(Selector::from_label("new")).push_arg(1_000),
+///     )
+///     .expect("constructor must not revert");
+///     // further expensive setup, e.g. filling the order book
+/// });
+///
+/// #[ink::test]
+/// fn trading_works() {
+///     FIXTURE.setup();
+///     let mut exchange = ink_env::get_contract_storage::<Key, Exchange>(&Exchange::KEY)
+///         .expect("could not decode contract")
+///         .expect("contract was deployed by the fixture");
+///     // test code comes here as usual
+/// }
+/// ```
+pub struct Fixture<R: 'static = ()> {
+    genesis: fn() -> R,
+    state: Mutex<Option<(Snapshot, R)>>,
+}
+
+impl<R> Fixture<R> {
+    /// Creates a fixture whose state is set up by `genesis`.
+    pub const fn new(genesis: fn() -> R) -> Self {
+        Self {
+            genesis,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Sets up the state of the fixture in the off-chain environment.
+    ///
+    /// Returns the output of the `genesis` function, e.g. the account ids of the
+    /// deployed contracts.
+    ///
+    /// # Note
+    ///
+    /// This must be called at the start of a test, i.e. on a freshly reset
+    /// off-chain environment, since `genesis` may run as part of it.
+    pub fn setup(&self) -> R
+    where
+        R: Clone,
+    {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let (snapshot, output) = state.get_or_insert_with(|| {
+            let output = (self.genesis)();
+            (snapshot(), output)
+        });
+        restore_snapshot(snapshot);
+        output.clone()
+    }
+}
+
+/// A state of the off-chain environment shared between threads.
+///
+/// Every thread has its own off-chain environment, so that tests running in
+/// parallel threads do not interfere with each other. A test spawning threads
+/// which have to operate on the same chain state opts into sharing it with a
+/// `SharedEnv`. Each thread runs its code within [`SharedEnv::enter`], which
+/// serializes the accesses to the shared state.
+///
+/// # Note
+///
+/// Like a [`Snapshot`], the shared state does not comprise registered chain
+/// extensions and contract mocks. Register them in every thread instead.
+///
+/// # Usage
+///
+/// ```no_compile
+/// let shared = ink_env::test::SharedEnv::from_current();
+/// let workers = (0..4)
+///     .map(|_| {
+///         let shared = shared.clone();
+///         std::thread::spawn(move || shared.enter(|| flipper.flip()))
+///     })
+///     .collect::<Vec<_>>();
+/// workers.into_iter().for_each(|worker| worker.join().unwrap());
+/// // adopt the state resulting from all workers
+/// shared.enter(|| ());
+/// ```
+#[derive(Clone)]
+pub struct SharedEnv {
+    state: Arc<Mutex<Snapshot>>,
+}
+
+impl SharedEnv {
+    /// Shares the current state of the off-chain environment of this thread.
+    pub fn from_current() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(snapshot())),
+        }
+    }
+
+    /// Runs `f` on the shared state in the off-chain environment of this thread.
+    ///
+    /// Sets the environment of this thread to the shared state before and updates
+    /// the shared state with the resulting state of the environment afterwards.
+    /// Other threads entering the shared state wait until `f` returned.
+    ///
+    /// # Note
+    ///
+    /// If `f` panics, the shared state is left unchanged.
+    pub fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        restore_snapshot(&state);
+        let result = f();
+        *state = snapshot();
+        result
+    }
+}
+
 /// Set to true to disable clearing storage
 ///
 /// # Note
@@ -127,6 +489,52 @@ where
     })
 }
 
+/// Sets the timestamp of the current block to `timestamp`.
+///
+/// The block number is left unchanged. This allows to test timestamp based logic,
+/// e.g. deadlines, precisely at boundary values.
+pub fn set_block_timestamp<T>(timestamp: T::Timestamp)
+where
+    T: Environment<Timestamp = u64>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_block_timestamp(timestamp);
+    })
+}
+
+/// Freezes the block timestamp.
+///
+/// Advancing blocks with [`advance_block()`] leaves the block timestamp unchanged
+/// until [`unfreeze_time()`] is called. The timestamp can still be changed with
+/// [`set_block_timestamp()`].
+pub fn freeze_time() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.freeze_time();
+    })
+}
+
+/// Unfreezes the block timestamp.
+///
+/// Advancing blocks with [`advance_block()`] advances the block timestamp by the
+/// block time again.
+pub fn unfreeze_time() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.unfreeze_time();
+    })
+}
+
+/// Sets whether the current call is read-only.
+///
+/// Every attempt to change state in a read-only call panics. This allows to test
+/// that a message can be called via
+/// [`invoke_read_only`](crate::call::CallBuilder::invoke_read_only), e.g. by price
+/// or oracle queries of other contracts.
+pub fn set_read_only(read_only: bool) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_read_only(read_only);
+    })
+}
+
 /// Sets a caller for the next call.
 pub fn set_caller<T>(caller: T::AccountId)
 where
@@ -175,6 +583,17 @@ where
     })
 }
 
+/// Returns the total number of hash calls made to compute the topics of emitted events.
+///
+/// Each of them corresponds to a host hash call when the contract runs on-chain. Topics
+/// whose encoding fits into the environment's `Hash` type and precomputed event signature
+/// topics are not hashed.
+pub fn count_topic_hashes() -> usize {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.count_topic_hashes()
+    })
+}
+
 /// Sets the balance of `account_id` to `new_balance`.
 pub fn set_balance<T>(account_id: T::AccountId, new_balance: T::Balance)
 where
@@ -253,8 +672,205 @@ where
     })
 }
 
+/// Returns an iterator over all storage entries of the contract `account_id`.
+///
+/// Each entry is a pair of the storage key and the encoded value stored under it,
+/// ordered by the storage keys. This works for any contract and not just the current
+/// callee, e.g. to assert on the state of the callee of a cross-contract call.
+pub fn iter_contract_storage<T>(
+    account_id: &T::AccountId,
+) -> impl Iterator<Item = (Vec<u8>, Vec<u8>)>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .contract_storage_entries(&scale::Encode::encode(&account_id))
+            .into_iter()
+    })
+}
+
+/// Writes `value` into the storage of the contract `account_id` at the storage `key`.
+///
+/// The key and the type of the value are checked against the storage layout of the
+/// contract `C`, so that a fixture pre-seeding the storage can't silently write to a
+/// key the contract never reads or in an encoding it can't decode. The write neither
+/// requires `account_id` to be the callee nor charges gas or a storage deposit.
+///
+/// # Panics
+///
+/// If `key` doesn't start with the root key of a storage item of `C`, or the item
+/// doesn't store values laid out like `V` there.
+///
+/// # Usage
+///
+/// ```no_compile
+/// // Seed the balance of Alice in `balances: Mapping<AccountId, Balance, ManualKey<2>>`.
+/// let key = (2u32, accounts.alice);
+/// ink_env::test::set_typed_storage::<DefaultEnvironment, Token, _, Balance>(
+///     &contract, &key, &100,
+/// );
+/// ```
+pub fn set_typed_storage<T, C, K, V>(account_id: &T::AccountId, key: &K, value: &V)
+where
+    T: Environment,
+    C: StorageLayout + StorageKey,
+    K: scale::Encode,
+    V: Storable + StorageLayout,
+{
+    let key = scale::Encode::encode(key);
+    ensure_storage_layout::<C, V>(&key);
+    let mut encoded_value = Vec::new();
+    Storable::encode(value, &mut encoded_value);
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_contract_storage_of(
+            &scale::Encode::encode(&account_id),
+            &key,
+            encoded_value,
+        )
+    })
+}
+
+/// Returns the value in the storage of the contract `account_id` at the storage `key`.
+///
+/// Returns `None` if there is no value at `key`. The key and the type of the value
+/// are checked against the storage layout of the contract `C` like in
+/// [`set_typed_storage()`]. The read doesn't require `account_id` to be the callee.
+///
+/// # Panics
+///
+/// If `key` doesn't start with the root key of a storage item of `C`, the item
+/// doesn't store values laid out like `V` there, or the stored value can't be
+/// decoded.
+pub fn get_typed_storage<T, C, K, V>(account_id: &T::AccountId, key: &K) -> Option<V>
+where
+    T: Environment,
+    C: StorageLayout + StorageKey,
+    K: scale::Encode,
+    V: Storable + StorageLayout,
+{
+    let key = scale::Encode::encode(key);
+    ensure_storage_layout::<C, V>(&key);
+    let encoded_value = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .get_contract_storage_of(&scale::Encode::encode(&account_id), &key)
+    })?;
+    let value =
+        <V as Storable>::decode(&mut &encoded_value[..]).unwrap_or_else(|error| {
+            panic!(
+                "failed to decode `{}` from the storage: {:?}",
+                core::any::type_name::<V>(),
+                error
+            )
+        });
+    Some(value)
+}
+
+/// Ensures that the storage layout of the contract `C` stores values laid out like
+/// `V` at the encoded storage `key`.
+///
+/// A key consists of the root key of a storage item, optionally followed by the
+/// key of an entry of the item, e.g. of a `Mapping`. Items laid out as structs may
+/// store each of their fields under a separate key.
+fn ensure_storage_layout<C, V>(key: &[u8])
+where
+    C: StorageLayout + StorageKey,
+    V: StorageLayout,
+{
+    let root_key = <Key as scale::Decode>::decode(&mut &key[..]).unwrap_or_else(|_| {
+        panic!("the storage key {:?} doesn't start with a root key", key)
+    });
+    let contract = Layout::Root(RootLayout::new(
+        LayoutKey::from(&C::KEY),
+        <C as StorageLayout>::layout(&C::KEY),
+    ));
+    let mut roots = Vec::new();
+    collect_roots(&contract, root_key, &mut roots);
+    if roots.is_empty() {
+        panic!(
+            "`{}` has no storage item at the root key {:?}",
+            core::any::type_name::<C>(),
+            root_key
+        )
+    }
+    let expected = <V as StorageLayout>::layout(&root_key);
+    let matches = |layout: &Layout| {
+        match layout {
+            Layout::Hash(hash) => hash.layout() == &expected,
+            layout => layout == &expected,
+        }
+    };
+    let found = roots.iter().any(|root| {
+        matches(root.layout())
+            || matches!(root.layout(), Layout::Struct(layout)
+                if layout.fields().iter().any(|field| matches(field.layout())))
+    });
+    if !found {
+        panic!(
+            "the storage item of `{}` at the root key {:?} doesn't store `{}`",
+            core::any::type_name::<C>(),
+            root_key,
+            core::any::type_name::<V>()
+        )
+    }
+}
+
+/// Collects all root layouts with the `root_key` within `layout`.
+fn collect_roots<'a>(layout: &'a Layout, root_key: Key, roots: &mut Vec<&'a RootLayout>) {
+    match layout {
+        Layout::Leaf(_) => {}
+        Layout::Root(root) => {
+            if root.root_key().key() == &root_key {
+                roots.push(root);
+            }
+            collect_roots(root.layout(), root_key, roots)
+        }
+        Layout::Hash(hash) => collect_roots(hash.layout(), root_key, roots),
+        Layout::Array(array) => collect_roots(array.layout(), root_key, roots),
+        Layout::Struct(layout) => {
+            for field in layout.fields() {
+                collect_roots(field.layout(), root_key, roots)
+            }
+        }
+        Layout::Enum(layout) => {
+            for variant in layout.variants().values() {
+                for field in variant.fields() {
+                    collect_roots(field.layout(), root_key, roots)
+                }
+            }
+        }
+    }
+}
+
+/// Returns the storage deposit charged from and refunded to the account `account_id`.
+///
+/// The off-chain environment emulates storage deposits according to the chain
+/// specification of the engine: the caller is charged for every storage entry it
+/// creates and refunded for every storage entry it removes. Since the off-chain
+/// environment does not support cross-contract calls, the caller is the origin of
+/// the call, which is charged and refunded on-chain.
+pub fn storage_deposit<T>(account_id: &T::AccountId) -> StorageDeposit
+where
+    T: Environment<Balance = u128>, // Just temporary for the MVP!
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .storage_deposit(&scale::Encode::encode(&account_id))
+    })
+}
+
 /// Runs the given closure test function with the default configuration
 /// for the off-chain environment.
+///
+/// # Note
+///
+/// Every thread has its own off-chain environment, which is reset by this function.
+/// Tests running in parallel threads therefore do not interfere with each other.
+/// Use a [`SharedEnv`] to share the state of the environment with threads spawned
+/// by a test.
 pub fn run_test<T, F>(f: F) -> Result<()>
 where
     T: Environment,
@@ -262,6 +878,9 @@ where
     <T as Environment>::AccountId: From<[u8; 32]>,
 {
     let default_accounts = default_accounts::<T>();
+    crate::debug::reset_io_stats();
+    crate::attribution::reset_attributed_value();
+    EVENT_LISTENERS.with(|listeners| listeners.borrow_mut().clear());
     <EnvInstance as OnInstance>::on_instance(|instance| {
         instance.engine.initialize_or_reset();
 
@@ -339,6 +958,115 @@ pub fn recorded_events() -> impl Iterator<Item = EmittedEvent> {
     })
 }
 
+/// Handles an event emitted in the off-chain environment.
+type EventListener = Box<dyn FnMut(&EmittedEvent)>;
+
+thread_local!(
+    static EVENT_LISTENERS: RefCell<Vec<EventListener>> = RefCell::new(Vec::new())
+);
+
+/// Registers the `listener` to be called with every event emitted afterwards.
+///
+/// The listener is called synchronously right after the event has been emitted,
+/// i.e. before the emitting contract continues its execution. This allows to check
+/// invariants which only hold at the time of the emission, like the order of events
+/// or the state of the storage when an event is emitted. Listeners are called in
+/// the order in which they have been registered.
+///
+/// # Note
+///
+/// Listeners may call into the environment, e.g. to read the contract storage.
+/// Events emitted by a listener itself are not passed to the listeners though.
+/// All listeners are removed at the start of [`run_test()`] and by
+/// [`clear_event_listeners()`].
+///
+/// # Usage
+///
+/// ```no_compile
+/// let emitted = Rc::new(RefCell::new(Vec::new()));
+/// let recorder = emitted.clone();
+/// ink_env::test::on_event(move |event| {
+///     // the storage already contains the new balance when `Transfer` is emitted
+///     recorder.borrow_mut().push(event.data.clone());
+/// });
+/// ```
+pub fn on_event<F>(listener: F)
+where
+    F: FnMut(&EmittedEvent) + 'static,
+{
+    EVENT_LISTENERS.with(|listeners| listeners.borrow_mut().push(Box::new(listener)));
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_event_queueing(true);
+    })
+}
+
+/// Removes all listeners registered with [`on_event()`].
+pub fn clear_event_listeners() {
+    EVENT_LISTENERS.with(|listeners| listeners.borrow_mut().clear());
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_event_queueing(false);
+    })
+}
+
+/// Calls all registered listeners with the `events` in order.
+pub(super) fn notify_event_listeners(events: Vec<ink_engine::test_api::EmittedEvent>) {
+    // The listeners are taken out while they are called, so that they can call
+    // into the environment and register further listeners.
+    let mut listeners =
+        EVENT_LISTENERS.with(|listeners| core::mem::take(&mut *listeners.borrow_mut()));
+    for event in events {
+        let event = EmittedEvent::from(event);
+        for listener in &mut listeners {
+            listener(&event);
+        }
+    }
+    EVENT_LISTENERS.with(|registered| {
+        let mut registered = registered.borrow_mut();
+        let added = core::mem::replace(&mut *registered, listeners);
+        registered.extend(added);
+    });
+}
+
+/// Marks the start of a new call.
+///
+/// Only events emitted afterwards are returned by [`events_of_last_call()`] and
+/// the statistics returned by [`crate::debug::io_stats()`] are reset.
+/// [`deploy()`] starts a new call on its own.
+pub fn start_call() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.start_call();
+    });
+    crate::debug::reset_io_stats();
+}
+
+/// Returns the events emitted since the last call started in order.
+///
+/// A call starts with [`start_call()`] or [`deploy()`]. If no call has been
+/// started yet, all events emitted during the test are returned.
+pub fn events_of_last_call() -> impl Iterator<Item = EmittedEvent> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .get_emitted_events_of_last_call()
+            .map(|evt: ink_engine::test_api::EmittedEvent| evt.into())
+    })
+}
+
+/// Returns the topic under which `value` is recorded if it is an event topic.
+///
+/// This can be compared to the topics returned by [`EmittedEvent::decode_topics`].
+/// Topic fields of ink! events are recorded as a [`PrefixedValue`] with the
+/// `"Contract::Event::field"` prefix.
+///
+/// [`PrefixedValue`]: crate::topics::PrefixedValue
+pub fn topic<T, V>(value: &V) -> T::Hash
+where
+    T: Environment,
+    V: scale::Encode,
+{
+    super::impls::topic_hash::<T, V>(value)
+}
+
 /// Tests if a contract terminates successfully after `self.env().terminate()`
 /// has been called.
 ///
@@ -386,6 +1114,336 @@ pub fn assert_contract_termination<T, F>(
     assert_eq!(beneficiary, expected_beneficiary);
 }
 
+/// The payload with which the off-chain environment unwinds once `return_value` is called.
+pub(crate) struct ReturnValue {
+    /// `true` if the execution has been reverted.
+    pub reverted: bool,
+    /// The encoded value returned to the caller.
+    pub data: Vec<u8>,
+}
+
+/// Implemented by `#[ink::contract]` for the storage struct of the contract in order to
+/// run its generated constructor dispatch in the off-chain environment.
+///
+/// # Note
+///
+/// This is an implementation detail of [`deploy()`] and not meant to be implemented manually.
+pub trait DispatchConstructor: Storable + StorageKey {
+    /// Decodes the constructor from the given call data and executes it the same way
+    /// the `deploy` entry point of the contract does.
+    fn dispatch_constructor(input: &[u8]);
+}
+
+/// The output of a constructor that reverted during [`deploy()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reverted {
+    /// The encoded `ink::ConstructorResult` returned by the constructor.
+    pub data: Vec<u8>,
+}
+
+/// Deploys the contract `C` by running its generated constructor dispatch with the given input.
+///
+/// In contrast to calling the constructor directly this exercises the same code path as
+/// an on-chain instantiation: the selector is decoded, payments are checked, and the
+/// contract storage is written and read back afterwards.
+///
+/// Please note that the acting accounts should be set with [`set_caller()`] and
+/// [`set_callee()`] beforehand. Events emitted by a reverted constructor are not
+/// rolled back.
+///
+/// # Errors
+///
+/// Returns [`Reverted`] with the encoded constructor output if the constructor reverted.
+/// Like on-chain, this includes inputs that are not entirely consumed by the
+/// constructor arguments, which revert with `LangError::CouldNotReadInput`.
+///
+/// # Panics
+///
+/// If the constructor panics, the panic is propagated to the caller.
+///
+/// # Usage
+///
+/// ```no_compile
+/// let contract = ink_env::test::deploy::<Flipper, _>(
+///     ExecutionInput::new(Selector::from_label("new")).push_arg(true),
+/// )
+/// .expect("constructor must not revert");
+/// ```
+pub fn deploy<C, Args>(input: ExecutionInput<Args>) -> core::result::Result<C, Reverted>
+where
+    C: DispatchConstructor,
+    Args: scale::Encode,
+{
+    deploy_raw::<C>(&scale::Encode::encode(&input))
+}
+
+/// Deploys the contract `C` with the raw `input` recorded in `call`, i.e. with the
+/// selector and arguments exactly as they were recorded.
+///
+/// The caller, callee and transferred value are set to the recorded ones
+/// beforehand, if they were set when recording. This allows replaying inputs
+/// captured with [`record_call_inputs()`] or taken from a live extrinsic.
+///
+/// # Errors
+///
+/// Returns [`Reverted`] with the encoded constructor output if the constructor reverted.
+///
+/// # Panics
+///
+/// If `call` was not dispatched through [`EntryPoint::Deploy`] or the constructor
+/// panics.
+pub fn replay_deploy<C>(call: &CallInput) -> core::result::Result<C, Reverted>
+where
+    C: DispatchConstructor,
+{
+    assert_eq!(
+        call.entry_point,
+        EntryPoint::Deploy,
+        "can only deploy a constructor input"
+    );
+    set_call_context(call);
+    deploy_raw::<C>(&call.input)
+}
+
+/// Runs the constructor dispatch of `C` with the encoded `input`.
+fn deploy_raw<C>(input: &[u8]) -> core::result::Result<C, Reverted>
+where
+    C: DispatchConstructor,
+{
+    start_call();
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.record_call_input(EntryPoint::Deploy, input);
+    });
+    let payload = std::panic::catch_unwind(|| C::dispatch_constructor(input))
+        .expect_err("constructor dispatch must end with `return_value`");
+    match payload.downcast::<ReturnValue>() {
+        Ok(output) if output.reverted => Err(Reverted { data: output.data }),
+        Ok(_) => {
+            let contract = crate::get_contract_storage::<Key, C>(&C::KEY)
+                .unwrap_or_else(|err| panic!("could not decode contract: {:?}", err))
+                .expect("constructor did not initialize the contract storage");
+            Ok(contract)
+        }
+        Err(payload) => std::panic::resume_unwind(payload),
+    }
+}
+
+/// Implemented by `#[ink::contract]` for the storage struct of the contract in order to
+/// run its generated message dispatch in the off-chain environment.
+///
+/// # Note
+///
+/// This is an implementation detail of [`dispatch()`] and not meant to be implemented manually.
+pub trait DispatchMessage: Storable + StorageKey {
+    /// Decodes the message from the given call data and executes it the same way
+    /// the `call` entry point of the contract does.
+    fn dispatch_message(input: &[u8]);
+}
+
+/// The reason why a message dispatched with [`dispatch()`] did not succeed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DispatchError {
+    /// The dispatcher could not dispatch the input and reverted with the `LangError`.
+    ///
+    /// Like on-chain, an unknown selector yields [`LangError::CouldNotReadInput`].
+    Lang(LangError),
+    /// Value was transferred to a message which is not payable.
+    PaidUnpayable,
+    /// The message reverted, e.g. by returning an `Err`, with the encoded output.
+    Reverted(Vec<u8>),
+}
+
+impl DispatchError {
+    /// Decodes the error with which the dispatch reverted as `E`.
+    ///
+    /// This is the `LangError` for [`DispatchError::Lang`] and the error of the
+    /// `Result` returned by the message for [`DispatchError::Reverted`].
+    ///
+    /// Returns `None` if there is no such error or it is not a valid `E`.
+    pub fn decode_revert<E>(&self) -> Option<E>
+    where
+        E: scale::Decode,
+    {
+        match self {
+            Self::Lang(error) => {
+                scale::Decode::decode(&mut &scale::Encode::encode(error)[..]).ok()
+            }
+            Self::Reverted(output) => {
+                match output.split_first() {
+                    // The encoded `Err` variant of the returned `Result`.
+                    Some((1, error)) => {
+                        scale::DecodeAll::decode_all(&mut &error[..]).ok()
+                    }
+                    _ => None,
+                }
+            }
+            Self::PaidUnpayable => None,
+        }
+    }
+}
+
+/// Dispatches a message to the contract `C` with the given input through its
+/// generated message dispatch.
+///
+/// In contrast to calling the message directly this exercises the same code path as
+/// an on-chain call: the selector is decoded, payments are checked, and the contract
+/// storage is read before and written after the message is executed. The contract
+/// must have been deployed beforehand, e.g. with [`deploy()`].
+///
+/// Returns the encoded output of the message.
+///
+/// # Errors
+///
+/// Returns the [`DispatchError`] if the message was not dispatched or reverted.
+///
+/// # Panics
+///
+/// If the message panics, the panic is propagated to the caller.
+///
+/// # Usage
+///
+/// ```no_compile
+/// ink_env::test::transfer_in::<DefaultEnvironment>(10);
+/// assert_eq!(
+///     ink_env::test::dispatch::<Flipper, _>(ExecutionInput::new(Selector::from_label("flip"))),
+///     Err(ink_env::test::DispatchError::PaidUnpayable),
+/// );
+/// ```
+pub fn dispatch<C, Args>(
+    input: ExecutionInput<Args>,
+) -> core::result::Result<Vec<u8>, DispatchError>
+where
+    C: DispatchMessage,
+    Args: scale::Encode,
+{
+    dispatch_raw::<C>(&scale::Encode::encode(&input))
+}
+
+/// Dispatches the raw `input` recorded in `call` to the contract `C`, i.e. with
+/// the selector and arguments exactly as they were recorded.
+///
+/// The caller, callee and transferred value are set to the recorded ones
+/// beforehand, if they were set when recording. This allows replaying inputs
+/// captured with [`record_call_inputs()`] or taken from a live extrinsic.
+///
+/// # Errors
+///
+/// Returns the [`DispatchError`] if the message was not dispatched or reverted.
+///
+/// # Panics
+///
+/// If `call` was not dispatched through [`EntryPoint::Call`] or the message panics.
+///
+/// # Usage
+///
+/// ```no_compile
+/// let log = ink_env::test::CallInputLog::load("failing-case.scale")?;
+/// for call in log.inputs() {
+///     if call.entry_point == ink_env::test::EntryPoint::Call {
+///         let _ = ink_env::test::replay_dispatch::<Flipper>(call);
+///     }
+/// }
+/// ```
+pub fn replay_dispatch<C>(
+    call: &CallInput,
+) -> core::result::Result<Vec<u8>, DispatchError>
+where
+    C: DispatchMessage,
+{
+    assert_eq!(
+        call.entry_point,
+        EntryPoint::Call,
+        "can only dispatch a message input"
+    );
+    set_call_context(call);
+    dispatch_raw::<C>(&call.input)
+}
+
+/// Sets the caller, callee and transferred value recorded in `call`.
+fn set_call_context(call: &CallInput) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        if let Some(caller) = &call.caller {
+            instance.engine.set_caller(caller.clone());
+        }
+        if let Some(callee) = &call.callee {
+            instance.engine.set_callee(callee.clone());
+        }
+        instance
+            .engine
+            .set_value_transferred(call.value_transferred);
+    })
+}
+
+/// Runs the message dispatch of `C` with the encoded `input`.
+fn dispatch_raw<C>(input: &[u8]) -> core::result::Result<Vec<u8>, DispatchError>
+where
+    C: DispatchMessage,
+{
+    start_call();
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.record_call_input(EntryPoint::Call, input);
+    });
+    let payload = std::panic::catch_unwind(|| C::dispatch_message(input))
+        .expect_err("message dispatch must end with `return_value`");
+    let payload = match payload.downcast::<DispatchError>() {
+        Ok(error) => return Err(*error),
+        Err(payload) => payload,
+    };
+    let output = match payload.downcast::<ReturnValue>() {
+        Ok(output) => output,
+        Err(payload) => std::panic::resume_unwind(payload),
+    };
+    // The output is an encoded `ink::MessageResult`.
+    match output.data.split_first() {
+        Some((0, data)) if output.reverted => Err(DispatchError::Reverted(data.to_vec())),
+        Some((0, data)) => Ok(data.to_vec()),
+        Some((1, mut error)) => {
+            let error = <LangError as scale::Decode>::decode(&mut error)
+                .unwrap_or_else(|err| panic!("could not decode `LangError`: {}", err));
+            Err(DispatchError::Lang(error))
+        }
+        _ => panic!("message dispatch returned an invalid `MessageResult`"),
+    }
+}
+
+/// Asserts that `dispatch` reverted with an error of type `E` and returns it.
+///
+/// The error is decoded with [`DispatchError::decode_revert`], so `E` can either be
+/// `LangError` to assert that the dispatcher rejected the input or the error type
+/// of the message.
+///
+/// # Panics
+///
+/// If `dispatch` succeeded or did not revert with an `E`.
+///
+/// # Usage
+///
+/// ```no_compile
+/// let error = ink_env::test::assert_reverts_with::<LangError, _, _>(|| {
+///     ink_env::test::dispatch::<Flipper, _>(ExecutionInput::new(Selector::new([0; 4])))
+/// });
+/// assert_eq!(error, LangError::CouldNotReadInput);
+/// ```
+pub fn assert_reverts_with<E, T, F>(dispatch: F) -> E
+where
+    E: scale::Decode,
+    T: Debug,
+    F: FnOnce() -> core::result::Result<T, DispatchError>,
+{
+    match dispatch() {
+        Ok(output) => panic!("dispatch did not revert but returned {:?}", output),
+        Err(error) => {
+            error.decode_revert::<E>().unwrap_or_else(|| {
+                panic!(
+                    "dispatch did not revert with a `{}` but failed with {:?}",
+                    core::any::type_name::<E>(),
+                    error
+                )
+            })
+        }
+    }
+}
+
 /// Prepend contract message call with value transfer. Used for tests in off-chain environment.
 #[macro_export]
 macro_rules! pay_with_call {
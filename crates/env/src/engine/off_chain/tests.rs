@@ -33,11 +33,639 @@ fn topics_builder() -> Result<()> {
 
         let topics_len_compact = &scale::Compact(2u32);
         let topics_len_encoded = scale::Encode::encode(&topics_len_compact);
-        let output = TopicsBuilderBackend::<crate::DefaultEnvironment>::output(builder);
+        let (output, hash_calls) =
+            TopicsBuilderBackend::<crate::DefaultEnvironment>::output(builder);
         #[rustfmt::skip]
         let expected = vec![topics_len_encoded[0], 13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 17, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
         assert_eq!(output, expected);
+        assert_eq!(hash_calls, 0);
 
         Ok(())
     })
 }
+
+#[test]
+fn topics_builder_counts_hash_calls() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        let mut builder = TopicsBuilder::default();
+        let long_value = [42u8; 40];
+        let precomputed = [7u8; 32];
+
+        // when
+        TopicsBuilderBackend::<crate::DefaultEnvironment>::push_topic(&mut builder, &13);
+        TopicsBuilderBackend::<crate::DefaultEnvironment>::push_topic(
+            &mut builder,
+            &long_value,
+        );
+        TopicsBuilderBackend::<crate::DefaultEnvironment>::push_precomputed_topic(
+            &mut builder,
+            &precomputed,
+            &long_value,
+        );
+
+        // then
+        assert_eq!(
+            builder.topics[1],
+            crate::test::topic::<crate::DefaultEnvironment, _>(&long_value).as_ref()
+        );
+        assert_eq!(builder.topics[2], precomputed);
+        let (_, hash_calls) =
+            TopicsBuilderBackend::<crate::DefaultEnvironment>::output(builder);
+        assert_eq!(hash_calls, 1);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn io_stats() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::set_contract_storage(&1u32, &[7u8; 10]);
+        crate::test::start_call();
+
+        // when
+        let value = crate::get_contract_storage::<u32, [u8; 10]>(&1)?;
+        let missing = crate::get_contract_storage::<u32, u8>(&2)?;
+        crate::set_contract_storage(&3u32, &42u32);
+        crate::clear_contract_storage(&1u32);
+
+        // then
+        assert_eq!(value, Some([7u8; 10]));
+        assert_eq!(missing, None);
+        assert_eq!(
+            crate::debug::io_stats(),
+            crate::debug::IoStats {
+                reads: 2,
+                writes: 2,
+                bytes_read: 10,
+                bytes_written: 4,
+            }
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_decode_error_is_reported() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::set_contract_storage(&1u32, &(7u8, 8u8));
+
+        // when
+        let result = crate::get_contract_storage::<u32, (u8, u32)>(&1);
+
+        // then
+        assert!(matches!(result, Err(crate::Error::Decode(_))));
+        let messages = crate::test::recorded_debug_messages()
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with(
+            "storage entry under key 0x01000000: \
+             failed to decode `(u8, u32)` at byte offset 1: "
+        ));
+
+        Ok(())
+    })
+}
+
+#[test]
+fn mock_contract() -> Result<()> {
+    use crate::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        test::MockContract,
+        DefaultEnvironment,
+    };
+
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        let selector = Selector::new([0xCA, 0xFE, 0xBA, 0xBE]);
+        let mock = MockContract::new();
+        mock.on(selector, |mut input| {
+            let value = <u32 as scale::Decode>::decode(&mut input).unwrap();
+            scale::Encode::encode(&(value * 2))
+        });
+        mock.register::<DefaultEnvironment>(accounts.charlie);
+
+        // when
+        let output = build_call::<DefaultEnvironment>()
+            .call_type(Call::new().callee(accounts.charlie))
+            .exec_input(ExecutionInput::new(selector).push_arg(21u32))
+            .returns::<u32>()
+            .fire()?;
+
+        // then
+        assert_eq!(output, 42);
+        let calls = mock.recorded_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].selector(), selector);
+        assert_eq!(calls[0].params(), &scale::Encode::encode(&21u32)[..]);
+
+        // calling an account without a contract mock fails
+        let result = build_call::<DefaultEnvironment>()
+            .call_type(Call::new().callee(accounts.django))
+            .exec_input(ExecutionInput::new(selector).push_arg(21u32))
+            .returns::<u32>()
+            .fire();
+        assert_eq!(result, Err(crate::Error::NotCallable));
+
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(
+    expected = "encountered unexpected call to contract mock with selector [222, 173, 190, 239]"
+)]
+fn mock_contract_panics_on_unexpected_call() {
+    use crate::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        test::MockContract,
+        DefaultEnvironment,
+    };
+
+    let accounts = crate::test::default_accounts::<DefaultEnvironment>();
+    MockContract::new().register::<DefaultEnvironment>(accounts.charlie);
+    let _ = build_call::<DefaultEnvironment>()
+        .call_type(Call::new().callee(accounts.charlie))
+        .exec_input(ExecutionInput::new(Selector::new([0xDE, 0xAD, 0xBE, 0xEF])))
+        .returns::<()>()
+        .fire();
+}
+
+#[test]
+fn invoke_read_only() -> Result<()> {
+    use crate::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        test::MockContract,
+        DefaultEnvironment,
+    };
+
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        let selector = Selector::new([0xCA, 0xFE, 0xBA, 0xBE]);
+        let mock = MockContract::new();
+        mock.on(selector, |_| scale::Encode::encode(&42u32));
+        mock.register::<DefaultEnvironment>(accounts.charlie);
+        let price = |value| {
+            build_call::<DefaultEnvironment>()
+                .call_type(
+                    Call::new()
+                        .callee(accounts.charlie)
+                        .transferred_value(value),
+                )
+                .exec_input(ExecutionInput::new(selector))
+                .returns::<u32>()
+                .invoke_read_only()
+        };
+
+        // when
+        let output = price(0)?;
+
+        // then
+        assert_eq!(output, 42);
+        assert_eq!(price(1), Err(crate::Error::CalleeTrapped));
+        crate::set_contract_storage(&1u32, &true);
+
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(
+    expected = "state change denied: `set_storage` must not be called in a read-only context"
+)]
+fn read_only_call_denies_state_changes() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        crate::test::set_read_only(true);
+        // reading state is fine
+        let _ = crate::get_contract_storage::<u32, bool>(&1u32);
+        crate::set_contract_storage(&1u32, &true);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn replay_host_calls_reproduces_recording() -> Result<()> {
+    use crate::DefaultEnvironment;
+
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::record_host_calls();
+        let recorded = crate::block_timestamp::<DefaultEnvironment>();
+        let log = crate::test::take_host_call_log();
+
+        // when
+        crate::test::advance_block::<DefaultEnvironment>();
+        crate::test::replay_host_calls(log);
+        let replayed = crate::block_timestamp::<DefaultEnvironment>();
+
+        // then
+        assert_eq!(replayed, recorded);
+        assert!(crate::test::take_host_call_log().calls().is_empty());
+        assert_ne!(crate::block_timestamp::<DefaultEnvironment>(), recorded);
+        Ok(())
+    })
+}
+
+#[test]
+fn time_travel_works() -> Result<()> {
+    use crate::DefaultEnvironment;
+
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        // given
+        let deadline = 1_000;
+        crate::test::set_block_timestamp::<DefaultEnvironment>(deadline - 1);
+        crate::test::freeze_time();
+
+        // when
+        crate::test::advance_block::<DefaultEnvironment>();
+
+        // then
+        assert_eq!(crate::block_timestamp::<DefaultEnvironment>(), deadline - 1);
+        assert_eq!(crate::block_number::<DefaultEnvironment>(), 1);
+
+        // when
+        crate::test::set_block_timestamp::<DefaultEnvironment>(deadline);
+        crate::test::unfreeze_time();
+        crate::test::advance_block::<DefaultEnvironment>();
+
+        // then
+        assert_eq!(crate::block_timestamp::<DefaultEnvironment>(), deadline + 6);
+        assert_eq!(crate::block_number::<DefaultEnvironment>(), 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_contract_storage_works() -> Result<()> {
+    use crate::DefaultEnvironment;
+
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::set_callee::<DefaultEnvironment>(accounts.bob);
+        crate::set_contract_storage(&1u32, &42u8);
+
+        // when
+        crate::test::set_callee::<DefaultEnvironment>(accounts.alice);
+        crate::set_contract_storage(&2u32, &true);
+
+        // then
+        let bob_storage =
+            crate::test::iter_contract_storage::<DefaultEnvironment>(&accounts.bob)
+                .collect::<Vec<_>>();
+        assert_eq!(bob_storage, vec![(scale::Encode::encode(&1u32), vec![42])]);
+        assert_eq!(
+            crate::test::iter_contract_storage::<DefaultEnvironment>(&accounts.charlie)
+                .count(),
+            0
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn snapshot_and_restore_works() -> Result<()> {
+    use crate::DefaultEnvironment;
+
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::set_contract_storage(&1u32, &42u8);
+        crate::test::set_account_balance::<DefaultEnvironment>(accounts.bob, 100);
+        let snapshot = crate::test::snapshot();
+
+        // when
+        crate::set_contract_storage(&1u32, &43u8);
+        crate::set_contract_storage(&2u32, &true);
+        crate::test::set_account_balance::<DefaultEnvironment>(accounts.bob, 0);
+        crate::test::advance_block::<DefaultEnvironment>();
+        crate::test::restore_snapshot(&snapshot);
+
+        // then
+        assert_eq!(crate::get_contract_storage::<u32, u8>(&1u32)?, Some(42));
+        assert_eq!(crate::get_contract_storage::<u32, bool>(&2u32)?, None);
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(accounts.bob),
+            Ok(100)
+        );
+        assert_eq!(crate::block_number::<DefaultEnvironment>(), 0);
+        Ok(())
+    })
+}
+
+static GENESIS_RUNS: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+static FIXTURE: crate::test::Fixture<u8> = crate::test::Fixture::new(|| {
+    GENESIS_RUNS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    crate::set_contract_storage(&1u32, &42u8);
+    42
+});
+
+fn fixture_test() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // when
+        let output = FIXTURE.setup();
+
+        // then
+        assert_eq!(output, 42);
+        assert_eq!(crate::get_contract_storage::<u32, u8>(&1u32)?, Some(42));
+
+        // changes of a test are not visible to others
+        crate::set_contract_storage(&1u32, &43u8);
+        Ok(())
+    })
+}
+
+#[test]
+fn fixture_runs_genesis_once() -> Result<()> {
+    let threads = (0..4)
+        .map(|_| std::thread::spawn(fixture_test))
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().expect("fixture test must not panic")?;
+    }
+    fixture_test()?;
+    assert_eq!(GENESIS_RUNS.load(std::sync::atomic::Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[test]
+fn threads_have_separate_environments() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::set_contract_storage(&1u32, &42u8);
+
+        // when
+        let other = std::thread::spawn(|| {
+            crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+                assert_eq!(crate::get_contract_storage::<u32, u8>(&1u32)?, None);
+                crate::set_contract_storage(&1u32, &43u8);
+                Ok(())
+            })
+        });
+        other.join().expect("other test must not panic")?;
+
+        // then
+        assert_eq!(crate::get_contract_storage::<u32, u8>(&1u32)?, Some(42));
+        Ok(())
+    })
+}
+
+#[test]
+fn shared_env_works() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::set_contract_storage(&1u32, &0u32);
+        let shared = crate::test::SharedEnv::from_current();
+
+        // when
+        let threads = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                std::thread::spawn(move || {
+                    shared.enter(|| {
+                        let value = crate::get_contract_storage::<u32, u32>(&1u32)?
+                            .expect("value must be shared");
+                        crate::set_contract_storage(&1u32, &(value + 1));
+                        Ok::<_, crate::Error>(())
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().expect("thread must not panic")?;
+        }
+
+        // then
+        // the environment of this thread is not changed until it enters
+        assert_eq!(crate::get_contract_storage::<u32, u32>(&1u32)?, Some(0));
+        shared.enter(|| ());
+        assert_eq!(crate::get_contract_storage::<u32, u32>(&1u32)?, Some(4));
+        Ok(())
+    })
+}
+
+#[test]
+fn oracle() -> Result<()> {
+    use crate::{
+        chain_extension::{
+            ChainExtensionMethod,
+            FromStatusCode,
+        },
+        test::Oracle,
+        DefaultEnvironment,
+    };
+    use core::task::Poll;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct UnknownPair;
+
+    impl FromStatusCode for UnknownPair {
+        fn from_status_code(status_code: u32) -> core::result::Result<(), Self> {
+            match status_code {
+                0 => Ok(()),
+                _ => Err(UnknownPair),
+            }
+        }
+    }
+
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        // given
+        let oracle = Oracle::new(0x0101, |pair: String| {
+            async move {
+                // resolves only after being woken up once, like a pending HTTP request
+                let mut requested = false;
+                core::future::poll_fn(|cx| {
+                    if requested {
+                        return Poll::Ready(())
+                    }
+                    requested = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                })
+                .await;
+                match pair.as_str() {
+                    "DOT/USD" => Ok(650u128),
+                    _ => Err(1),
+                }
+            }
+        });
+        oracle.register();
+        let price = |pair: &str| {
+            ChainExtensionMethod::build(0x0101)
+                .input::<String>()
+                .output::<u128>()
+                .handle_error_code::<UnknownPair>()
+                .call(&String::from(pair))
+        };
+
+        // when
+        let known = price("DOT/USD");
+        let unknown = price("FOO/BAR");
+
+        // then
+        assert_eq!(known, Ok(650));
+        assert_eq!(unknown, Err(UnknownPair));
+        assert_eq!(
+            oracle.recorded_requests(),
+            vec![
+                scale::Encode::encode("DOT/USD"),
+                scale::Encode::encode("FOO/BAR"),
+            ]
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn oracle_with_executor() -> Result<()> {
+    use crate::{
+        chain_extension::{
+            ChainExtensionMethod,
+            FromStatusCode,
+        },
+        test::Oracle,
+        DefaultEnvironment,
+    };
+    use core::{
+        cell::Cell,
+        task::{
+            Context,
+            Poll,
+            Waker,
+        },
+    };
+    use std::{
+        rc::Rc,
+        sync::Arc,
+        task::Wake,
+    };
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct StatusCode(u32);
+
+    impl FromStatusCode for StatusCode {
+        fn from_status_code(status_code: u32) -> core::result::Result<(), Self> {
+            match status_code {
+                0 => Ok(()),
+                _ => Err(StatusCode(status_code)),
+            }
+        }
+    }
+
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        // given
+        let executed = Rc::new(Cell::new(0));
+        let oracle = Oracle::new(0x0102, |value: u32| async move { Ok(value * 2) })
+            .with_executor({
+                let executed = executed.clone();
+                move |mut future| {
+                    executed.set(executed.get() + 1);
+                    let waker = Waker::from(Arc::new(NoopWaker));
+                    let mut context = Context::from_waker(&waker);
+                    loop {
+                        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                            return output
+                        }
+                    }
+                }
+            });
+        oracle.register();
+
+        // when
+        let doubled = ChainExtensionMethod::build(0x0102)
+            .input::<u32>()
+            .output::<u32>()
+            .handle_error_code::<StatusCode>()
+            .call(&21);
+        let invalid = ChainExtensionMethod::build(0x0102)
+            .input::<u8>()
+            .output::<u32>()
+            .handle_error_code::<StatusCode>()
+            .call(&21);
+
+        // then
+        assert_eq!(doubled, Ok(42));
+        assert_eq!(invalid, Err(StatusCode(Oracle::INVALID_REQUEST)));
+        assert_eq!(executed.get(), 2);
+        assert_eq!(
+            oracle.recorded_requests(),
+            vec![scale::Encode::encode(&21u32), scale::Encode::encode(&21u8)]
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn event_data_renders_as_json() {
+    #[derive(scale::Encode, scale_info::TypeInfo)]
+    struct Transferred {
+        value: u128,
+        memo: Vec<u8>,
+    }
+
+    let event = crate::test::EmittedEvent {
+        emitter: None,
+        topics: Vec::new(),
+        data: scale::Encode::encode(&Transferred {
+            value: 10,
+            memo: vec![0xAB],
+        }),
+    };
+    assert_eq!(
+        event.data_json::<Transferred>(),
+        "{\n  \"memo\": \"0xab\",\n  \"value\": 10\n}"
+    );
+    assert_eq!(
+        event.data_json::<u8>(),
+        "0x0a00000000000000000000000000000004ab (17 bytes are left after the value)"
+    );
+}
+
+#[test]
+fn call_input_renders_as_json() {
+    let call = crate::test::CallInput {
+        entry_point: crate::test::EntryPoint::Call,
+        caller: None,
+        callee: None,
+        value_transferred: 0,
+        input: vec![0x63, 0x3A, 0xA5, 0x51, 0x01, 0x02, 0x00],
+    };
+    assert_eq!(
+        crate::test::call_input_json::<(bool, u16)>(&call),
+        "{\n  \"args\": [\n    true,\n    2\n  ],\n  \"selector\": \"0x633aa551\"\n}"
+    );
+    assert_eq!(
+        crate::test::call_input_json::<bool>(&call),
+        "{\n  \"args\": \"0x010200 (2 bytes are left after the value)\",\n  \
+        \"selector\": \"0x633aa551\"\n}"
+    );
+}
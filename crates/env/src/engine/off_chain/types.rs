@@ -25,6 +25,7 @@ use super::{
 impl From<ink_engine::test_api::EmittedEvent> for EmittedEvent {
     fn from(evt: ink_engine::test_api::EmittedEvent) -> Self {
         EmittedEvent {
+            emitter: evt.emitter,
             topics: evt.topics,
             data: evt.data,
         }
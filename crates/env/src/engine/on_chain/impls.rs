@@ -167,6 +167,17 @@ where
         self.scoped_buffer.append_encoded(&result);
     }
 
+    fn push_precomputed_topic<T>(&mut self, topic: &[u8; 32], topic_value: &T)
+    where
+        T: scale::Encode,
+    {
+        if <E as Environment>::Hash::clear().as_ref().len() == topic.len() {
+            self.scoped_buffer.append_encoded(topic);
+        } else {
+            self.push_topic(topic_value);
+        }
+    }
+
     fn output(mut self) -> Self::Output {
         let encoded_topics = self.scoped_buffer.take_appended();
         (self.scoped_buffer, encoded_topics)
@@ -231,6 +242,8 @@ impl EnvBackend for EnvInstance {
         let mut buffer = self.scoped_buffer();
         let key = buffer.take_encoded(key);
         let value = buffer.take_storable_encoded(value);
+        #[cfg(feature = "ink-debug")]
+        crate::debug::record_write(value.len());
         ext::set_storage(key, value)
     }
 
@@ -242,12 +255,16 @@ impl EnvBackend for EnvInstance {
         let mut buffer = self.scoped_buffer();
         let key = buffer.take_encoded(key);
         let output = &mut buffer.take_rest();
-        match ext::get_storage(key, output) {
+        let result = ext::get_storage(key, output);
+        #[cfg(feature = "ink-debug")]
+        crate::debug::record_read(if result.is_ok() { output.len() } else { 0 });
+        match result {
             Ok(_) => (),
             Err(ExtError::KeyNotFound) => return Ok(None),
             Err(_) => panic!("encountered unexpected error"),
         }
-        let decoded = Storable::decode(&mut &output[..])?;
+        let decoded =
+            crate::engine::decode_storage(key, &output[..], ext::debug_message)?;
         Ok(Some(decoded))
     }
 
@@ -259,12 +276,18 @@ impl EnvBackend for EnvInstance {
         let mut buffer = self.scoped_buffer();
         let key = buffer.take_encoded(key);
         let output = &mut buffer.take_rest();
-        match ext::take_storage(key, output) {
+        let result = ext::take_storage(key, output);
+        #[cfg(feature = "ink-debug")]
+        crate::debug::record_read(if result.is_ok() { output.len() } else { 0 });
+        #[cfg(feature = "ink-debug")]
+        crate::debug::record_write(0);
+        match result {
             Ok(_) => (),
             Err(ExtError::KeyNotFound) => return Ok(None),
             Err(_) => panic!("encountered unexpected error"),
         }
-        let decoded = Storable::decode(&mut &output[..])?;
+        let decoded =
+            crate::engine::decode_storage(key, &output[..], ext::debug_message)?;
         Ok(Some(decoded))
     }
 
@@ -274,6 +297,8 @@ impl EnvBackend for EnvInstance {
     {
         let mut buffer = self.scoped_buffer();
         let key = buffer.take_encoded(key);
+        #[cfg(feature = "ink-debug")]
+        crate::debug::record_read(0);
         ext::storage_contains(key)
     }
 
@@ -283,9 +308,19 @@ impl EnvBackend for EnvInstance {
     {
         let mut buffer = self.scoped_buffer();
         let key = buffer.take_encoded(key);
+        #[cfg(feature = "ink-debug")]
+        crate::debug::record_write(0);
         ext::clear_storage(key)
     }
 
+    fn clear_contract_storage_prefix<K>(&mut self, _prefix: &K) -> Result<u32>
+    where
+        K: scale::Encode,
+    {
+        // The `contracts` pallet has no host function to iterate the storage.
+        Err(Error::Unsupported)
+    }
+
     fn decode_input<T>(&mut self) -> Result<T>
     where
         T: scale::Decode,
@@ -344,6 +379,7 @@ impl EnvBackend for EnvInstance {
     fn call_chain_extension<I, T, E, ErrorCode, F, D>(
         &mut self,
         func_id: u32,
+        _weight: Option<u64>,
         input: &I,
         status_to_result: F,
         decode_to_result: D,
@@ -25,6 +25,10 @@ use self::{
     ext::Error,
 };
 use super::OnInstance;
+use core::cell::RefCell;
+
+#[cfg(feature = "ink-debug")]
+pub(crate) use self::ext::debug_message;
 
 /// The on-chain environment.
 pub struct EnvInstance {
@@ -51,3 +55,28 @@ impl OnInstance for EnvInstance {
         f(unsafe { &mut INSTANCE })
     }
 }
+
+/// A value of the current call which is only accessed by the thread executing it.
+///
+/// Every call to a contract is executed single threaded in a fresh instance, so the
+/// value always refers to the current call.
+pub(crate) struct CallLocal<T>(RefCell<T>);
+
+// SAFETY: Contracts are executed single threaded, so the value is never accessed
+//         by more than one thread.
+unsafe impl<T> Sync for CallLocal<T> {}
+
+impl<T> CallLocal<T> {
+    /// Creates the value of the call.
+    pub(crate) const fn new(value: T) -> Self {
+        Self(RefCell::new(value))
+    }
+
+    /// Executes `f` with mutable access to the value.
+    pub(crate) fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.0.borrow_mut())
+    }
+}
@@ -49,6 +49,8 @@ pub enum Error {
     LoggingDisabled,
     /// ECDSA pubkey recovery failed. Most probably wrong recovery id or signature.
     EcdsaRecoveryFailed,
+    /// The called host function is not supported by the chain the contract runs on.
+    Unsupported,
 }
 
 /// A result of environmental operations.
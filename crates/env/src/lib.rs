@@ -71,9 +71,12 @@ extern crate ink_allocator;
 
 mod api;
 mod arithmetic;
+mod attribution;
 mod backend;
 pub mod call;
 pub mod chain_extension;
+#[cfg(any(feature = "ink-debug", feature = "std"))]
+pub mod debug;
 mod engine;
 mod error;
 pub mod hash;
@@ -94,6 +97,10 @@ use self::backend::{
 };
 pub use self::{
     api::*,
+    attribution::{
+        attributed_value,
+        with_attributed_value,
+    },
     backend::{
         CallFlags,
         ReturnFlags,
@@ -113,6 +120,12 @@ pub use self::{
 };
 use ink_primitives::Clear;
 
+/// The size of the static buffer in bytes into which the contract encodes storage
+/// values, call inputs and return values, 16 kB.
+///
+/// Encoding a larger value into the buffer traps the contract.
+pub const BUFFER_SIZE: usize = 1 << 14; // 16 kB
+
 cfg_if::cfg_if! {
     if #[cfg(any(feature = "ink-debug", feature = "std"))] {
         /// Required by the `debug_print*` macros below, because there is no guarantee that
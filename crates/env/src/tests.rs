@@ -84,7 +84,15 @@ fn test_call_flags() {
     assert!(flags.allow_reentry());
     assert_eq!(flags.into_u32(), 0b0000_1111);
 
+    let flags = flags.set_read_only(true);
+    assert!(flags.read_only());
+    assert_eq!(flags.into_u32(), 0b0001_1111);
+
     // disable each flag one after the other
+    let flags = flags.set_read_only(false);
+    assert!(!flags.read_only());
+    assert_eq!(flags.into_u32(), 0b0000_1111);
+
     let flags = flags.set_allow_reentry(false);
     assert!(!flags.allow_reentry());
     assert_eq!(flags.into_u32(), 0b0000_0111);
@@ -101,3 +109,33 @@ fn test_call_flags() {
     assert!(!flags.forward_input());
     assert_eq!(flags.into_u32(), 0b0000_0000);
 }
+
+#[test]
+fn precomputed_topics_fall_back_to_push_topic() {
+    use crate::topics::TopicsBuilderBackend;
+
+    /// A backend only implementing the required methods.
+    #[derive(Default)]
+    struct EncodingBackend(Vec<Vec<u8>>);
+
+    impl TopicsBuilderBackend<crate::DefaultEnvironment> for EncodingBackend {
+        type Output = Vec<Vec<u8>>;
+
+        fn expect(&mut self, _expected_topics: usize) {}
+
+        fn push_topic<T>(&mut self, topic_value: &T)
+        where
+            T: scale::Encode,
+        {
+            self.0.push(topic_value.encode())
+        }
+
+        fn output(self) -> Self::Output {
+            self.0
+        }
+    }
+
+    let mut backend = EncodingBackend::default();
+    backend.push_precomputed_topic(&[0xFF; 32], &42u8);
+    assert_eq!(backend.output(), vec![vec![42u8]]);
+}
@@ -35,6 +35,18 @@ where
     where
         T: scale::Encode;
 
+    /// Pushes another topic whose hash has been computed ahead of time.
+    ///
+    /// `topic` must be the topic of `topic_value` in an environment with a 32 bytes
+    /// `Hash` type. Backends of other environments fall back to computing the topic
+    /// of `topic_value`, which is what the default implementation does.
+    fn push_precomputed_topic<T>(&mut self, _topic: &[u8; 32], topic_value: &T)
+    where
+        T: scale::Encode,
+    {
+        self.push_topic(topic_value)
+    }
+
     /// Extracts the serialized topics.
     fn output(self) -> Self::Output;
 }
@@ -116,6 +128,28 @@ where
             state: Default::default(),
         }
     }
+
+    /// Pushes another event topic whose hash has been computed ahead of time.
+    ///
+    /// This avoids hashing `value` again whenever the environment's `Hash` type
+    /// has 32 bytes.
+    ///
+    /// Returns a topics builder that expects one less event topic for serialization
+    /// than before the call.
+    pub fn push_precomputed_topic<T>(
+        mut self,
+        topic: &[u8; 32],
+        value: &T,
+    ) -> TopicsBuilder<<S as SomeRemainingTopics>::Next, E, B>
+    where
+        T: scale::Encode,
+    {
+        self.backend.push_precomputed_topic(topic, value);
+        TopicsBuilder {
+            backend: self.backend,
+            state: Default::default(),
+        }
+    }
 }
 
 impl<E, B> TopicsBuilder<state::NoRemainingTopics, E, B>
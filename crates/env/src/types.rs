@@ -159,6 +159,10 @@ pub trait Environment {
     /// This is a type that is defined through the `#[ink::chain_extension]` procedural macro.
     /// For more information about usage and definition click [this][chain_extension] link.
     ///
+    /// Contracts access the chain extension via `self.env().extension()`. Declaring a
+    /// different environment, e.g. for tests, swaps the chain extension of a contract
+    /// without changing its code.
+    ///
     /// [chain_extension]: https://paritytech.github.io/ink/ink/attr.chain_extension.html
     type ChainExtension;
 }
@@ -0,0 +1,80 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    generator,
+    GenerateCode,
+};
+use derive_more::From;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::spanned::Spanned as _;
+
+/// Generates code for the `#[ink::chain_config]` macro.
+#[derive(From)]
+pub struct ChainConfig<'a> {
+    /// The chain configuration to generate code for.
+    config: &'a ir::ChainConfig,
+}
+
+impl ChainConfig<'_> {
+    /// Generates the metadata of the chain configuration.
+    ///
+    /// The value of every constant is SCALE encoded at the time the metadata
+    /// is generated so that off-chain tooling does not need to evaluate it.
+    fn generate_metadata(&self) -> TokenStream2 {
+        let item = self.config.item();
+        let span = item.span();
+        let self_ty = &item.self_ty;
+        let constants = self.config.constants().map(|constant| {
+            let span = constant.span();
+            let ident = &constant.ident;
+            let label = ident.to_string();
+            let type_spec = generator::Metadata::generate_type_spec(&constant.ty);
+            quote_spanned!(span=>
+                ::ink::metadata::ConstantSpec::new(
+                    #label,
+                    #type_spec,
+                    ::scale::Encode::encode(&<#self_ty>::#ident),
+                )
+            )
+        });
+        let label = self.config.ident().to_string();
+        quote_spanned!(span=>
+            #[cfg(feature = "std")]
+            impl ::ink::ChainConfigMetadata for #self_ty {
+                fn chain_config_spec() -> ::ink::metadata::ChainConfigSpec {
+                    ::ink::metadata::ChainConfigSpec::new(#label)
+                        .constants([
+                            #( #constants ),*
+                        ])
+                        .done()
+                }
+            }
+        )
+    }
+}
+
+impl GenerateCode for ChainConfig<'_> {
+    /// Generates the chain configuration alongside its metadata.
+    fn generate_code(&self) -> TokenStream2 {
+        let item = self.config.item();
+        let span = item.span();
+        let metadata = self.generate_metadata();
+        quote_spanned!(span=>
+            #item
+            #metadata
+        )
+    }
+}
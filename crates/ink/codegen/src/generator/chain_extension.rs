@@ -72,6 +72,12 @@ impl ChainExtension<'_> {
             }
         };
 
+        let weight = method.weight().map(|weight| {
+            quote_spanned!(span=>
+                .weight(#weight)
+            )
+        });
+
         let handle_status = method.handle_status();
         let returns_result = method.returns_result();
 
@@ -129,6 +135,7 @@ impl ChainExtension<'_> {
                 #where_output_impls_from_error_code
             {
                 ::ink::env::chain_extension::ChainExtensionMethod::build(#func_id)
+                    #weight
                     .input::<#compound_input_type>()
                     #result_handling
                     #error_code_handling
@@ -136,6 +143,39 @@ impl ChainExtension<'_> {
             }
         )
     }
+
+    /// Generates the metadata of the chain extension.
+    fn generate_metadata(&self) -> TokenStream2 {
+        let span = self.extension.span();
+        let ident = self.extension.ident();
+        let methods = self.extension.iter_methods().map(|method| {
+            let span = method.span();
+            let label = method.ident().to_string();
+            let func_id = method.id().into_u32();
+            let weight = match method.weight() {
+                Some(weight) => {
+                    quote_spanned!(span=> ::core::option::Option::Some(#weight))
+                }
+                None => quote_spanned!(span=> ::core::option::Option::None),
+            };
+            quote_spanned!(span=>
+                ::ink::metadata::ChainExtensionMethodSpec::new(#label, #func_id, #weight)
+            )
+        });
+        let label = ident.to_string();
+        quote_spanned!(span=>
+            #[cfg(feature = "std")]
+            impl ::ink::ChainExtensionMetadata for #ident {
+                fn chain_extension_spec() -> ::ink::metadata::ChainExtensionSpec {
+                    ::ink::metadata::ChainExtensionSpec::new(#label)
+                        .methods([
+                            #( #methods ),*
+                        ])
+                        .done()
+                }
+            }
+        )
+    }
 }
 
 impl GenerateCode for ChainExtension<'_> {
@@ -149,6 +189,7 @@ impl GenerateCode for ChainExtension<'_> {
             .iter_methods()
             .map(|method| Self::generate_for_instance_method(method, error_code));
         let instance_ident = format_ident!("__ink_{}Instance", ident);
+        let metadata = self.generate_metadata();
         quote_spanned!(span =>
             #(#attrs)*
             pub enum #ident {}
@@ -172,6 +213,8 @@ impl GenerateCode for ChainExtension<'_> {
                         Self::Instance { __ink_private: __ink_Private }
                     }
                 }
+
+                #metadata
             };
         )
     }
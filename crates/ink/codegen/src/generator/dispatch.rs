@@ -71,6 +71,11 @@ impl GenerateCode for Dispatch<'_> {
             self.generate_constructor_decoder_type(&constructor_spans);
         let message_decoder_type = self.generate_message_decoder_type(&message_spans);
         let entry_points = self.generate_entry_points(&constructor_spans, &message_spans);
+        let off_chain_constructor_dispatch =
+            self.generate_off_chain_constructor_dispatch(&constructor_spans);
+        let off_chain_message_dispatch =
+            self.generate_off_chain_message_dispatch(&message_spans);
+        let dispatch_table = self.generate_dispatch_table(&message_spans);
         quote! {
             #amount_dispatchables
             #contract_dispatchable_messages
@@ -79,12 +84,20 @@ impl GenerateCode for Dispatch<'_> {
             #contract_dispatchable_messages_infos
             #constructor_decoder_type
             #message_decoder_type
+            #dispatch_table
 
             #[cfg(not(test))]
             #[cfg(not(feature = "ink-as-dependency"))]
             const _: () = {
                 #entry_points
             };
+
+            #[cfg(feature = "std")]
+            #[cfg(not(feature = "ink-as-dependency"))]
+            const _: () = {
+                #off_chain_constructor_dispatch
+                #off_chain_message_dispatch
+            };
         }
     }
 }
@@ -119,6 +132,15 @@ impl Dispatch<'_> {
             .position(|item| item.has_wildcard_selector())
     }
 
+    /// Returns the index of the ink! message flagged with `#[ink(multicall)]`, if existent.
+    fn query_multicall_message(&self) -> Option<usize> {
+        self.contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .position(|item| item.callable().is_multicall())
+    }
+
     /// Returns the index of the ink! constructor which has a wildcard selector, if existent.
     fn query_wildcard_constructor(&self) -> Option<usize> {
         self.contract
@@ -395,6 +417,179 @@ impl Dispatch<'_> {
         )
     }
 
+    /// Generates code to decode and execute an ink! constructor.
+    ///
+    /// The `decode_input` expression must evaluate to a `Result` of the constructor
+    /// decoder type. This is shared between the `deploy` entry point and the
+    /// off-chain [`ink::env::test::DispatchConstructor`] implementation so that
+    /// both dispatch constructors the same way.
+    fn generate_constructor_dispatch(
+        &self,
+        constructor_spans: &[proc_macro2::Span],
+        decode_input: TokenStream2,
+    ) -> TokenStream2 {
+        let span = self.contract.module().storage().span();
+        let storage_ident = self.contract.module().storage().ident();
+        let any_constructor_accept_payment =
+            self.any_constructor_accepts_payment_expr(constructor_spans);
+        quote_spanned!(span=>
+            if !#any_constructor_accept_payment {
+                ::ink::codegen::deny_payment::<<#storage_ident as ::ink::reflect::ContractEnv>::Env>()
+                    .unwrap_or_else(|error| ::core::panic!("{}", error))
+            }
+
+            let dispatchable = match #decode_input {
+                ::core::result::Result::Ok(decoded_dispatchable) => {
+                    decoded_dispatchable
+                }
+                ::core::result::Result::Err(_decoding_error) => {
+                    let error = ::ink::ConstructorResult::Err(::ink::LangError::CouldNotReadInput);
+
+                    // At this point we're unable to set the `Ok` variant to be the any "real"
+                    // constructor output since we were unable to figure out what the caller wanted
+                    // to dispatch in the first place, so we set it to `()`.
+                    //
+                    // This is okay since we're going to only be encoding the `Err` variant
+                    // into the output buffer anyways.
+                    ::ink::env::return_value::<::ink::ConstructorResult<()>>(
+                        ::ink::env::ReturnFlags::new_with_reverted(true),
+                        &error,
+                    );
+                }
+            };
+
+            <<#storage_ident as ::ink::reflect::ContractConstructorDecoder>::Type
+                as ::ink::reflect::ExecuteDispatchable>::execute_dispatchable(dispatchable)
+            .unwrap_or_else(|error| {
+                ::core::panic!("dispatching ink! message failed: {}", error)
+            })
+        )
+    }
+
+    /// Generates the off-chain [`ink::env::test::DispatchConstructor`] implementation.
+    ///
+    /// This allows `ink::env::test::deploy` to run the real constructor dispatch in
+    /// unit tests.
+    fn generate_off_chain_constructor_dispatch(
+        &self,
+        constructor_spans: &[proc_macro2::Span],
+    ) -> TokenStream2 {
+        let span = self.contract.module().storage().span();
+        let storage_ident = self.contract.module().storage().ident();
+        let dispatch = self.generate_constructor_dispatch(
+            constructor_spans,
+            quote_spanned!(span=>
+                <<#storage_ident as ::ink::reflect::ContractConstructorDecoder>::Type
+                    as ::scale::Decode>::decode(&mut &input[..])
+            ),
+        );
+        quote_spanned!(span=>
+            impl ::ink::env::test::DispatchConstructor for #storage_ident {
+                #[allow(clippy::nonminimal_bool)]
+                fn dispatch_constructor(input: &[::core::primitive::u8]) {
+                    #dispatch
+                }
+            }
+        )
+    }
+
+    /// Generates code to decode and execute an ink! message.
+    ///
+    /// The `decode_input` expression must evaluate to a `Result` of the decoded
+    /// message dispatch. The `on_dispatch_error` closure handles the
+    /// `ink::reflect::DispatchError` of a payment to a non-payable message or of a
+    /// failed execution and must diverge. This is shared between the `call` entry
+    /// point and the off-chain [`ink::env::test::DispatchMessage`] implementation so
+    /// that both dispatch messages the same way.
+    fn generate_message_dispatch(
+        &self,
+        message_spans: &[proc_macro2::Span],
+        decode_input: TokenStream2,
+        on_dispatch_error: TokenStream2,
+    ) -> TokenStream2 {
+        let span = self.contract.module().storage().span();
+        let storage_ident = self.contract.module().storage().ident();
+        let any_message_accept_payment =
+            self.any_message_accepts_payment_expr(message_spans);
+        quote_spanned!(span=>
+            if !#any_message_accept_payment {
+                ::ink::codegen::deny_payment::<<#storage_ident as ::ink::reflect::ContractEnv>::Env>()
+                    .unwrap_or_else(#on_dispatch_error)
+            }
+
+            let dispatchable = match #decode_input {
+                ::core::result::Result::Ok(::ink::codegen::DecodedDispatch(
+                    ::core::result::Result::Ok(decoded_dispatchable),
+                )) => {
+                    decoded_dispatchable
+                }
+                decoding_error => {
+                    let lang_error = match decoding_error {
+                        ::core::result::Result::Ok(::ink::codegen::DecodedDispatch(
+                            ::core::result::Result::Err(
+                                ::ink::reflect::DispatchError::InputTooLarge,
+                            ),
+                        )) => ::ink::LangError::InputTooLarge,
+                        _ => ::ink::LangError::CouldNotReadInput,
+                    };
+                    let error = ::ink::MessageResult::Err(lang_error);
+
+                    // At this point we're unable to set the `Ok` variant to be the any "real"
+                    // message output since we were unable to figure out what the caller wanted
+                    // to dispatch in the first place, so we set it to `()`.
+                    //
+                    // This is okay since we're going to only be encoding the `Err` variant
+                    // into the output buffer anyways.
+                    ::ink::env::return_value::<::ink::MessageResult<()>>(
+                        ::ink::env::ReturnFlags::new_with_reverted(true),
+                        &error,
+                    );
+                }
+            };
+
+            <<#storage_ident as ::ink::reflect::ContractMessageDecoder>::Type
+                as ::ink::reflect::ExecuteDispatchable>::execute_dispatchable(dispatchable)
+            .unwrap_or_else(#on_dispatch_error)
+        )
+    }
+
+    /// Generates the off-chain [`ink::env::test::DispatchMessage`] implementation.
+    ///
+    /// This allows `ink::env::test::dispatch` to run the real message dispatch in
+    /// unit tests. Payments to non-payable messages unwind with a typed
+    /// `ink::env::test::DispatchError` instead of a panic message.
+    fn generate_off_chain_message_dispatch(
+        &self,
+        message_spans: &[proc_macro2::Span],
+    ) -> TokenStream2 {
+        let span = self.contract.module().storage().span();
+        let storage_ident = self.contract.module().storage().ident();
+        let dispatch = self.generate_message_dispatch(
+            message_spans,
+            quote_spanned!(span=>
+                <::ink::codegen::DecodedDispatch<
+                    <#storage_ident as ::ink::reflect::ContractMessageDecoder>::Type,
+                > as ::scale::Decode>::decode(&mut &input[..])
+            ),
+            quote_spanned!(span=>
+                |error| match error {
+                    ::ink::reflect::DispatchError::PaidUnpayableMessage => {
+                        ::std::panic::panic_any(::ink::env::test::DispatchError::PaidUnpayable)
+                    }
+                    error => ::core::panic!("dispatching ink! message failed: {}", error),
+                }
+            ),
+        );
+        quote_spanned!(span=>
+            impl ::ink::env::test::DispatchMessage for #storage_ident {
+                #[allow(clippy::nonminimal_bool)]
+                fn dispatch_message(input: &[::core::primitive::u8]) {
+                    #dispatch
+                }
+            }
+        )
+    }
+
     /// Generates code for the entry points of the root ink! smart contract.
     ///
     /// This generates the `deploy` and `call` functions with which the smart
@@ -406,89 +601,90 @@ impl Dispatch<'_> {
     ) -> TokenStream2 {
         let span = self.contract.module().storage().span();
         let storage_ident = self.contract.module().storage().ident();
-        let any_constructor_accept_payment =
-            self.any_constructor_accepts_payment_expr(constructor_spans);
-        let any_message_accept_payment =
-            self.any_message_accepts_payment_expr(message_spans);
+        let deploy_body = self.generate_constructor_dispatch(
+            constructor_spans,
+            quote_spanned!(span=>
+                ::ink::env::decode_input::<
+                    <#storage_ident as ::ink::reflect::ContractConstructorDecoder>::Type,
+                >()
+            ),
+        );
+        let call_body = self.generate_message_dispatch(
+            message_spans,
+            quote_spanned!(span=>
+                ::ink::env::decode_input::<
+                    ::ink::codegen::DecodedDispatch<
+                        <#storage_ident as ::ink::reflect::ContractMessageDecoder>::Type,
+                    >,
+                >()
+            ),
+            quote_spanned!(span=>
+                |error| ::core::panic!("dispatching ink! message failed: {}", error)
+            ),
+        );
         quote_spanned!(span=>
             #[cfg(not(test))]
             #[no_mangle]
             #[allow(clippy::nonminimal_bool)]
             fn deploy() {
-                if !#any_constructor_accept_payment {
-                    ::ink::codegen::deny_payment::<<#storage_ident as ::ink::reflect::ContractEnv>::Env>()
-                        .unwrap_or_else(|error| ::core::panic!("{}", error))
-                }
-
-                let dispatchable = match ::ink::env::decode_input::<
-                    <#storage_ident as ::ink::reflect::ContractConstructorDecoder>::Type,
-                >() {
-                    ::core::result::Result::Ok(decoded_dispatchable) => {
-                        decoded_dispatchable
-                    }
-                    ::core::result::Result::Err(_decoding_error) => {
-                        let error = ::ink::ConstructorResult::Err(::ink::LangError::CouldNotReadInput);
-
-                        // At this point we're unable to set the `Ok` variant to be the any "real"
-                        // constructor output since we were unable to figure out what the caller wanted
-                        // to dispatch in the first place, so we set it to `()`.
-                        //
-                        // This is okay since we're going to only be encoding the `Err` variant
-                        // into the output buffer anyways.
-                        ::ink::env::return_value::<::ink::ConstructorResult<()>>(
-                            ::ink::env::ReturnFlags::new_with_reverted(true),
-                            &error,
-                        );
-                    }
-                };
-
-                <<#storage_ident as ::ink::reflect::ContractConstructorDecoder>::Type
-                    as ::ink::reflect::ExecuteDispatchable>::execute_dispatchable(dispatchable)
-                .unwrap_or_else(|error| {
-                    ::core::panic!("dispatching ink! message failed: {}", error)
-                })
+                #deploy_body
             }
 
             #[cfg(not(test))]
             #[no_mangle]
             #[allow(clippy::nonminimal_bool)]
             fn call() {
-                if !#any_message_accept_payment {
-                    ::ink::codegen::deny_payment::<<#storage_ident as ::ink::reflect::ContractEnv>::Env>()
-                        .unwrap_or_else(|error| ::core::panic!("{}", error))
-                }
-
-                let dispatchable = match ::ink::env::decode_input::<
-                    <#storage_ident as ::ink::reflect::ContractMessageDecoder>::Type,
-                >() {
-                    ::core::result::Result::Ok(decoded_dispatchable) => {
-                        decoded_dispatchable
-                    }
-                    ::core::result::Result::Err(_decoding_error) => {
-                        let error = ::ink::MessageResult::Err(::ink::LangError::CouldNotReadInput);
-
-                        // At this point we're unable to set the `Ok` variant to be the any "real"
-                        // message output since we were unable to figure out what the caller wanted
-                        // to dispatch in the first place, so we set it to `()`.
-                        //
-                        // This is okay since we're going to only be encoding the `Err` variant
-                        // into the output buffer anyways.
-                        ::ink::env::return_value::<::ink::MessageResult<()>>(
-                            ::ink::env::ReturnFlags::new_with_reverted(true),
-                            &error,
-                        );
-                    }
-                };
-
-                <<#storage_ident as ::ink::reflect::ContractMessageDecoder>::Type
-                    as ::ink::reflect::ExecuteDispatchable>::execute_dispatchable(dispatchable)
-                .unwrap_or_else(|error| {
-                    ::core::panic!("dispatching ink! message failed: {}", error)
-                })
+                #call_body
             }
         )
     }
 
+    /// Generates the `DISPATCH_TABLE` constant if enabled in the ink! configuration.
+    ///
+    /// The table lists the selector, label, mutability and payability of every
+    /// dispatchable ink! message so that external tooling can enumerate them.
+    fn generate_dispatch_table(
+        &self,
+        message_spans: &[proc_macro2::Span],
+    ) -> TokenStream2 {
+        if !self.contract.config().dispatch_table() {
+            return quote! {}
+        }
+        let span = self.contract.module().storage().span();
+        let storage_ident = self.contract.module().storage().ident();
+        let entries = message_spans.iter().enumerate().map(|(index, message_span)| {
+            let message_info = quote_spanned!(*message_span=>
+                <#storage_ident as ::ink::reflect::DispatchableMessageInfo<{
+                    <#storage_ident as ::ink::reflect::ContractDispatchableMessages<{
+                        <#storage_ident as ::ink::reflect::ContractAmountDispatchables>::MESSAGES
+                    }>>::IDS[#index]
+                }>>
+            );
+            quote_spanned!(*message_span=>
+                (
+                    #message_info::SELECTOR,
+                    #message_info::LABEL,
+                    #message_info::MUTATES,
+                    #message_info::PAYABLE,
+                )
+            )
+        });
+        quote_spanned!(span=>
+            /// The dispatchable ink! messages of the contract.
+            ///
+            /// Each entry holds the selector, label, whether the message mutates
+            /// the storage and whether it is payable, in this order.
+            pub const DISPATCH_TABLE: &[(
+                [::core::primitive::u8; 4usize],
+                &::core::primitive::str,
+                ::core::primitive::bool,
+                ::core::primitive::bool,
+            )] = &[
+                #( #entries ),*
+            ];
+        )
+    }
+
     /// Generates code for the ink! constructor decoder type of the ink! smart contract.
     ///
     /// This type can be used in order to decode the input bytes received by a call to `deploy`
@@ -515,6 +711,22 @@ impl Dispatch<'_> {
             )
         }
 
+        /// Expands into the token sequence to represent the
+        /// label of the ink! constructor at the given index.
+        fn expand_constructor_label(
+            span: proc_macro2::Span,
+            storage_ident: &syn::Ident,
+            constructor_index: usize,
+        ) -> TokenStream2 {
+            quote_spanned!(span=>
+                <#storage_ident as ::ink::reflect::DispatchableConstructorInfo<{
+                    <#storage_ident as ::ink::reflect::ContractDispatchableConstructors<{
+                        <#storage_ident as ::ink::reflect::ContractAmountDispatchables>::CONSTRUCTORS
+                    }>>::IDS[#constructor_index]
+                }>>::LABEL
+            )
+        }
+
         /// Returns the n-th ink! constructor identifier for the decoder type.
         fn constructor_variant_ident(n: usize) -> syn::Ident {
             quote::format_ident!("Constructor{}", n)
@@ -549,11 +761,11 @@ impl Dispatch<'_> {
             let constructor_ident = constructor_variant_ident(index);
             let const_ident = format_ident!("CONSTRUCTOR_{}", index);
             let constructor_input = expand_constructor_input(constructor_span, storage_ident, index);
+            let constructor_label = expand_constructor_label(constructor_span, storage_ident, index);
             quote_spanned!(constructor_span=>
                 #const_ident => {
                     ::core::result::Result::Ok(Self::#constructor_ident(
-                        <#constructor_input as ::scale::Decode>::decode(input)
-                            .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
+                        ::ink::codegen::decode_dispatch_input::<#constructor_input, _>(#constructor_label, input)?
                     ))
                 }
             )
@@ -569,10 +781,14 @@ impl Dispatch<'_> {
                     storage_ident,
                     wildcard_index,
                 );
+                let constructor_label = expand_constructor_label(
+                    constructor_span,
+                    storage_ident,
+                    wildcard_index,
+                );
                 quote! {
                     ::core::result::Result::Ok(Self::#constructor_ident(
-                        <#constructor_input as ::scale::Decode>::decode(input)
-                            .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
+                        ::ink::codegen::decode_dispatch_input::<#constructor_input, _>(#constructor_label, input)?
                     ))
                 }
             }
@@ -661,12 +877,14 @@ impl Dispatch<'_> {
                         #(
                             #constructor_selector
                         )*
-                        match <[::core::primitive::u8; 4usize] as ::scale::Decode>::decode(input)
+                        let dispatchable = match <[::core::primitive::u8; 4usize] as ::scale::Decode>::decode(input)
                             .map_err(|_| ::ink::reflect::DispatchError::InvalidSelector)?
                         {
                             #( #constructor_match , )*
                             _invalid => #possibly_wildcard_selector_constructor
-                        }
+                        }?;
+                        ::ink::codegen::ensure_input_consumed(input)?;
+                        ::core::result::Result::Ok(dispatchable)
                     }
                 }
 
@@ -722,6 +940,22 @@ impl Dispatch<'_> {
             )
         }
 
+        /// Expands into the token sequence to represent the
+        /// label of the ink! message at the given index.
+        fn expand_message_label(
+            span: proc_macro2::Span,
+            storage_ident: &syn::Ident,
+            message_index: usize,
+        ) -> TokenStream2 {
+            quote_spanned!(span=>
+                <#storage_ident as ::ink::reflect::DispatchableMessageInfo<{
+                    <#storage_ident as ::ink::reflect::ContractDispatchableMessages<{
+                        <#storage_ident as ::ink::reflect::ContractAmountDispatchables>::MESSAGES
+                    }>>::IDS[#message_index]
+                }>>::LABEL
+            )
+        }
+
         /// Returns the n-th ink! message identifier for the decoder type.
         fn message_variant_ident(n: usize) -> syn::Ident {
             quote::format_ident!("Message{}", n)
@@ -750,17 +984,73 @@ impl Dispatch<'_> {
             )
         });
 
+        /// Expands into the decoding of the inputs of the ink! message, which rejects
+        /// inputs exceeding the maximum input size of the message if any.
+        fn expand_decode_message_input(
+            span: proc_macro2::Span,
+            message_input: TokenStream2,
+            message_label: TokenStream2,
+            max_input: Option<u32>,
+        ) -> TokenStream2 {
+            match max_input {
+                Some(max_input) => {
+                    let max_input = max_input as usize;
+                    quote_spanned!(span=>
+                        ::ink::codegen::decode_bounded_dispatch_input::<#message_input, _>(#message_label, input, #max_input)?
+                    )
+                }
+                None => {
+                    quote_spanned!(span=>
+                        ::ink::codegen::decode_dispatch_input::<#message_input, _>(#message_label, input)?
+                    )
+                }
+            }
+        }
+
+        let max_inputs = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .map(|message| message.callable().max_input())
+            .collect::<Vec<_>>();
+        let alias_selectors = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .map(|message| message.callable().alias_selector())
+            .collect::<Vec<_>>();
+        let message_alias_selector = alias_selectors.iter().enumerate().filter_map(
+            |(index, alias_selector)| {
+                alias_selector.map(|alias_selector| {
+                    let const_ident = format_ident!("MESSAGE_{}_ALIAS", index);
+                    let selector_bytes = alias_selector.hex_lits();
+                    quote_spanned!(span=>
+                        const #const_ident: [::core::primitive::u8; 4usize] = [ #( #selector_bytes ),* ];
+                    )
+                })
+            },
+        );
         let message_match = (0..count_messages).map(|index| {
             let message_span = message_spans[index];
             let message_ident = message_variant_ident(index);
             let const_ident = format_ident!("MESSAGE_{}", index);
+            let alias_const_ident = alias_selectors[index].map(|_| {
+                let alias_const_ident = format_ident!("MESSAGE_{}_ALIAS", index);
+                quote_spanned!(message_span=> | #alias_const_ident)
+            });
             let message_input = expand_message_input(message_span, storage_ident, index);
+            let message_label = expand_message_label(message_span, storage_ident, index);
+            let decode_message_input = expand_decode_message_input(
+                message_span,
+                message_input,
+                message_label,
+                max_inputs[index],
+            );
             quote_spanned!(message_span=>
-                #const_ident => {
-                    ::core::result::Result::Ok(Self::#message_ident(
-                        <#message_input as ::scale::Decode>::decode(input)
-                            .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
-                    ))
+                #const_ident #alias_const_ident => {
+                    ::core::result::Result::Ok(Self::#message_ident(#decode_message_input))
                 }
             )
         });
@@ -770,11 +1060,16 @@ impl Dispatch<'_> {
                 let message_ident = message_variant_ident(wildcard_index);
                 let message_input =
                     expand_message_input(message_span, storage_ident, wildcard_index);
+                let message_label =
+                    expand_message_label(message_span, storage_ident, wildcard_index);
+                let decode_message_input = expand_decode_message_input(
+                    message_span,
+                    message_input,
+                    message_label,
+                    max_inputs[wildcard_index],
+                );
                 quote! {
-                    ::core::result::Result::Ok(Self::#message_ident(
-                        <#message_input as ::scale::Decode>::decode(input)
-                            .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
-                    ))
+                    ::core::result::Result::Ok(Self::#message_ident(#decode_message_input))
                 }
             }
             None => {
@@ -785,6 +1080,11 @@ impl Dispatch<'_> {
         };
         let any_message_accept_payment =
             self.any_message_accepts_payment_expr(message_spans);
+        let has_invariants = self
+            .contract
+            .module()
+            .impls()
+            .any(|item_impl| item_impl.iter_invariants().next().is_some());
 
         let message_execute = (0..count_messages).map(|index| {
             let message_span = message_spans[index];
@@ -817,6 +1117,15 @@ impl Dispatch<'_> {
                     }>>::IDS[#index]
                 }>>::MUTATES
             );
+            // Checks the ink! invariants of the contract in debug and test builds.
+            let invariant_checks = has_invariants.then(|| {
+                let message_label =
+                    expand_message_label(message_span, storage_ident, index);
+                quote_spanned!(message_span=>
+                    #[cfg(any(test, debug_assertions))]
+                    contract.__ink_check_invariants(#message_label);
+                )
+            });
 
             quote_spanned!(message_span=>
                 Self::#message_ident(input) => {
@@ -831,6 +1140,7 @@ impl Dispatch<'_> {
 
                     // no need to push back results: transaction gets reverted anyways
                     if !is_reverted {
+                        #invariant_checks
                         push_contract(contract, #mutates_storage);
                     }
 
@@ -844,6 +1154,62 @@ impl Dispatch<'_> {
             )
         });
 
+        let multicall_execute = self.query_multicall_message().map(|multicall_index| {
+            let message_execute = (0..count_messages).map(|index| {
+                let message_span = message_spans[index];
+                let message_ident = message_variant_ident(index);
+                if index == multicall_index {
+                    return quote_spanned!(message_span=>
+                        Self::#message_ident(_) => {
+                            ::core::result::Result::Err(::ink::MulticallErrorKind::Recursive)
+                        }
+                    )
+                }
+                let message_info = quote_spanned!(message_span=>
+                    <#storage_ident as ::ink::reflect::DispatchableMessageInfo<{
+                        <#storage_ident as ::ink::reflect::ContractDispatchableMessages<{
+                            <#storage_ident as ::ink::reflect::ContractAmountDispatchables>::MESSAGES
+                        }>>::IDS[#index]
+                    }>>
+                );
+                quote_spanned!(message_span=>
+                    Self::#message_ident(input) => {
+                        if !#message_info::PAYABLE {
+                            ::ink::codegen::deny_payment::<
+                                <#storage_ident as ::ink::reflect::ContractEnv>::Env>()?;
+                        }
+                        let result: #message_info::Output = #message_info::CALLABLE(storage, input);
+                        if ::ink::is_result_type!(#message_info::Output)
+                            && ::ink::is_result_err!(result)
+                        {
+                            return ::core::result::Result::Err(
+                                ::ink::MulticallErrorKind::Reverted(::scale::Encode::encode(&result))
+                            )
+                        }
+                        ::core::result::Result::Ok(::scale::Encode::encode(&result))
+                    }
+                )
+            });
+            quote_spanned!(span=>
+                impl ::ink::reflect::ExecuteMulticall for __ink_MessageDecoder {
+                    type Storage = #storage_ident;
+
+                    #[allow(clippy::let_unit_value)]
+                    fn execute_multicall(
+                        self,
+                        storage: &mut #storage_ident,
+                    ) -> ::core::result::Result<
+                        ::ink::prelude::vec::Vec<::core::primitive::u8>,
+                        ::ink::MulticallErrorKind,
+                    > {
+                        match self {
+                            #( #message_execute ),*
+                        }
+                    }
+                }
+            )
+        });
+
         quote_spanned!(span=>
             const _: () = {
                 #[allow(non_camel_case_types)]
@@ -860,6 +1226,9 @@ impl Dispatch<'_> {
                         #(
                             #message_selector
                         )*
+                        #(
+                            #message_alias_selector
+                        )*
                         match <[::core::primitive::u8; 4usize] as ::scale::Decode>::decode(input)
                             .map_err(|_| ::ink::reflect::DispatchError::InvalidSelector)?
                         {
@@ -913,6 +1282,8 @@ impl Dispatch<'_> {
                     }
                 }
 
+                #multicall_execute
+
                 impl ::ink::reflect::ContractMessageDecoder for #storage_ident {
                     type Type = __ink_MessageDecoder;
                 }
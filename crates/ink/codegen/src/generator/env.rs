@@ -27,6 +27,11 @@ impl GenerateCode for Env<'_> {
     fn generate_code(&self) -> TokenStream2 {
         let env = self.contract.config().env();
         let storage_ident = self.contract.module().storage().ident();
+        let chain_config = self.contract.config().chain_config().map(|path| {
+            quote! {
+                type ChainConfig = #path;
+            }
+        });
         quote! {
             impl ::ink::reflect::ContractEnv for #storage_ident {
                 type Env = #env;
@@ -39,6 +44,8 @@ impl GenerateCode for Env<'_> {
             type Hash = <<#storage_ident as ::ink::reflect::ContractEnv>::Env as ::ink::env::Environment>::Hash;
             type Timestamp = <<#storage_ident as ::ink::reflect::ContractEnv>::Env as ::ink::env::Environment>::Timestamp;
             type BlockNumber = <<#storage_ident as ::ink::reflect::ContractEnv>::Env as ::ink::env::Environment>::BlockNumber;
+
+            #chain_config
         }
     }
 }
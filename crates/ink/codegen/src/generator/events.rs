@@ -21,6 +21,7 @@ use proc_macro2::{
 use quote::{
     quote,
     quote_spanned,
+    ToTokens as _,
 };
 use syn::spanned::Spanned as _;
 
@@ -42,12 +43,14 @@ impl GenerateCode for Events<'_> {
         let topic_guards = self.generate_topic_guards();
         let topics_impls = self.generate_topics_impls();
         let event_structs = self.generate_event_structs();
+        let signature_impls = self.generate_signature_impls();
         quote! {
             #emit_event_trait_impl
             #event_base
             #( #topic_guards )*
             #( #event_structs )*
             #( #topics_impls )*
+            #( #signature_impls )*
         }
     }
 }
@@ -214,13 +217,23 @@ impl<'a> Events<'a> {
                     )
                 });
             // Only include topic for event signature in case of non-anonymous event.
+            //
+            // The topic of the event signature is known at compile time and therefore
+            // never hashed when emitting the event.
             let event_signature_topic = match event.anonymous {
                 true => None,
-                false => Some(quote_spanned!(span=>
-                    .push_topic::<::ink::env::topics::PrefixedValue<[u8; #len_event_signature]>>(
-                        &::ink::env::topics::PrefixedValue { value: #event_signature, prefix: b"" }
-                    )
-                ))
+                false => {
+                    let topic = Self::signature_topic(&format!(
+                        "{}::{}",
+                        contract_ident, event_ident
+                    ));
+                    Some(quote_spanned!(span=>
+                        .push_precomputed_topic::<::ink::env::topics::PrefixedValue<[u8; #len_event_signature]>>(
+                            &[ #( #topic ),* ],
+                            &::ink::env::topics::PrefixedValue { value: #event_signature, prefix: b"" }
+                        )
+                    ))
+                }
             };
             // Anonymous events require 1 fewer topics since they do not include their signature.
             let anonymous_topics_offset = if event.anonymous { 0 } else { 1 };
@@ -255,6 +268,84 @@ impl<'a> Events<'a> {
         })
     }
 
+    /// Generates the `SIGNATURE` and `SIGNATURE_TOPIC` constants of the user defined events.
+    ///
+    /// The signature lists all fields of the event in their declaration order and thus
+    /// changes whenever the schema of the event changes. The signature topic is the
+    /// first topic of non-anonymous events and is computed here once so that off-chain
+    /// code can filter events without recomputing it.
+    fn generate_signature_impls(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
+        let contract_ident = self.contract.module().storage().ident();
+        self.contract.module().events().map(move |event| {
+            let span = event.span();
+            let event_ident = event.ident();
+            let fields = event
+                .fields()
+                .enumerate()
+                .map(|(n, field)| {
+                    let field_ident = field
+                        .ident()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| n.to_string());
+                    let field_ty = field.ty().to_token_stream().to_string().replace(' ', "");
+                    let topic = if field.is_topic { "#[topic]" } else { "" };
+                    format!("{}{}:{}", topic, field_ident, field_ty)
+                })
+                .collect::<Vec<_>>();
+            let signature = format!(
+                "{}::{}({})",
+                contract_ident,
+                event_ident,
+                fields.join(",")
+            );
+            let signature_topic = match event.anonymous {
+                true => quote_spanned!(span=> ::core::option::Option::None),
+                false => {
+                    let topic = Self::signature_topic(&format!(
+                        "{}::{}",
+                        contract_ident, event_ident
+                    ));
+                    quote_spanned!(span=> ::core::option::Option::Some([ #( #topic ),* ]))
+                }
+            };
+            quote_spanned!(span =>
+                const _: () = {
+                    impl #event_ident {
+                        /// The canonical signature of the event.
+                        ///
+                        /// Lists all fields of the event together with their types in
+                        /// declaration order. Fields prefixed with `#[topic]` are topics.
+                        pub const SIGNATURE: &'static ::core::primitive::str = #signature;
+
+                        /// The first topic of the event if it is not anonymous.
+                        ///
+                        /// Assumes an environment with a 32 bytes `Hash` type.
+                        pub const SIGNATURE_TOPIC: ::core::option::Option<[::core::primitive::u8; 32]> =
+                            #signature_topic;
+                    }
+                };
+            )
+        })
+    }
+
+    /// Returns the topic of the given event signature.
+    ///
+    /// This mirrors how `ink_env` computes the topic of the event signature which is
+    /// pushed as a `PrefixedValue` with an empty prefix.
+    fn signature_topic(signature: &str) -> [u8; 32] {
+        // The empty prefix is encoded as its compact length followed by the raw bytes
+        // of the signature.
+        let mut encoded = vec![0x00];
+        encoded.extend_from_slice(signature.as_bytes());
+        let mut topic = [0x00; 32];
+        if encoded.len() <= topic.len() {
+            topic[..encoded.len()].copy_from_slice(&encoded);
+        } else {
+            ir::blake2b_256(&encoded, &mut topic);
+        }
+        topic
+    }
+
     /// Generates all the user defined event struct definitions.
     fn generate_event_structs(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
         self.contract.module().events().map(move |event| {
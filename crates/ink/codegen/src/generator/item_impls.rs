@@ -46,6 +46,7 @@ impl GenerateCode for ItemImpls<'_> {
             .map(|item_impl| self.generate_item_impl(item_impl));
         let inout_guards = self.generate_input_output_guards();
         let trait_message_property_guards = self.generate_trait_message_property_guards();
+        let invariant_checks = self.generate_invariant_checks();
         let use_emit_event =
             self.contract.module().events().next().is_some().then(|| {
                 // Required to make `self.env().emit_event(...)` syntax available.
@@ -60,6 +61,7 @@ impl GenerateCode for ItemImpls<'_> {
                 #( #item_impls )*
                 #inout_guards
                 #trait_message_property_guards
+                #invariant_checks
             };
         }
     }
@@ -119,6 +121,164 @@ impl ItemImpls<'_> {
         )
     }
 
+    /// Returns `true` if the contract declares any ink! invariants.
+    fn has_invariants(&self) -> bool {
+        self.contract
+            .module()
+            .impls()
+            .any(|item_impl| item_impl.iter_invariants().next().is_some())
+    }
+
+    /// Generates the function checking all ink! invariants of the contract.
+    ///
+    /// The function is only generated for debug and test builds and panics
+    /// if any of the invariants does not hold. The message dispatch calls it after
+    /// every ink! message that didn't revert.
+    fn generate_invariant_checks(&self) -> Option<TokenStream2> {
+        if !self.has_invariants() {
+            return None
+        }
+        let storage_span = self.contract.module().storage().span();
+        let storage_ident = self.contract.module().storage().ident();
+        let checks = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_invariants())
+            .map(|invariant| {
+                let span = invariant.span();
+                let ident = invariant.ident();
+                let name = ident.to_string();
+                quote_spanned!(span=>
+                    if !Self::#ident(self) {
+                        ::core::panic!(
+                            "ink! invariant `{}` violated after message `{}`",
+                            #name,
+                            message,
+                        )
+                    }
+                )
+            });
+        Some(quote_spanned!(storage_span=>
+            #[cfg(any(test, debug_assertions))]
+            impl #storage_ident {
+                fn __ink_check_invariants(&self, message: &::core::primitive::str) {
+                    #( #checks )*
+                }
+            }
+        ))
+    }
+
+    /// Prepends a debug message to the `statements` of the ink! message that warns
+    /// about calling it in debug and test builds if it is deprecated.
+    fn expand_deprecation_warning(
+        message: &ir::Message,
+        statements: TokenStream2,
+    ) -> TokenStream2 {
+        let note = match message.deprecated() {
+            Some(note) => note,
+            None => return statements,
+        };
+        let span = message.span();
+        let name = message.ident().to_string();
+        quote_spanned!(span=>
+            #[cfg(any(test, debug_assertions))]
+            ::ink::env::debug_println!(
+                "ink! message `{}` is deprecated: {}",
+                #name,
+                #note,
+            );
+            #statements
+        )
+    }
+
+    /// Prepends a guard to the `statements` of the ink! message that allows to call
+    /// it only once per code hash if it is flagged with `#[ink(migration)]`.
+    fn expand_migration_guard(
+        message: &ir::Message,
+        statements: TokenStream2,
+    ) -> TokenStream2 {
+        if !message.is_migration() {
+            return statements
+        }
+        let span = message.span();
+        quote_spanned!(span=>
+            ::ink::storage::migration::ensure_first_run::<
+                <Self as ::ink::reflect::ContractEnv>::Env,
+            >();
+            #statements
+        )
+    }
+
+    /// Prepends entering the reentrancy guard to the `statements` of an ink! message
+    /// flagged with `#[ink(non_reentrant)]`.
+    ///
+    /// The guard is exited when it goes out of scope, also if the message returns early.
+    fn expand_reentrancy_guard(
+        message: &ir::Message,
+        statements: TokenStream2,
+    ) -> TokenStream2 {
+        if !message.is_non_reentrant() {
+            return statements
+        }
+        let span = message.span();
+        quote_spanned!(span=>
+            let __ink_non_reentrant = ::ink::storage::security::reentrancy::NonReentrant::enter();
+            #statements
+        )
+    }
+
+    /// Prepends the check that the `pausable` field of the storage struct is not
+    /// paused to the `statements` of an ink! message flagged with
+    /// `#[ink(when_not_paused)]`.
+    fn expand_pause_check(
+        message: &ir::Message,
+        pausable: Option<&syn::Member>,
+        statements: TokenStream2,
+    ) -> TokenStream2 {
+        if !message.is_when_not_paused() {
+            return statements
+        }
+        let span = message.span();
+        let pausable = pausable
+            .expect("encountered `when_not_paused` ink! message without `Pausable`");
+        quote_spanned!(span=>
+            ::ink::storage::security::pausable::ensure_not_paused(&self.#pausable);
+            #statements
+        )
+    }
+
+    /// Generates code to assert that an ink! message flagged with `#[ink(chunked)]`
+    /// takes a `u32` offset as its first input and returns a chunk.
+    fn generate_chunked_guards(message: &ir::Message) -> Option<TokenStream2> {
+        if !message.is_chunked() {
+            return None
+        }
+        let offset = message
+            .inputs()
+            .next()
+            .expect("ink! chunked messages have an offset input");
+        let offset_span = offset.span();
+        let offset_type = &*offset.ty;
+        let output_type = message
+            .output()
+            .expect("ink! chunked messages have an output");
+        let output_span = output_type.span();
+        let offset_guard = quote_spanned!(offset_span=>
+            let _: ::ink::codegen::utils::IsSameType<::core::primitive::u32> =
+                ::ink::codegen::utils::IsSameType::<#offset_type>::new();
+        );
+        let output_guard = quote_spanned!(output_span=>
+            ::ink::codegen::utils::consume_type::<
+                ::ink::codegen::ChunkedOutput<#output_type>
+            >();
+        );
+        Some(quote! {
+            #offset_guard
+            #output_guard
+        })
+    }
+
     /// Generates code to assert that ink! input and output types meet certain properties.
     fn generate_input_output_guards(&self) -> TokenStream2 {
         let storage_span = self.contract.module().storage().span();
@@ -166,9 +326,11 @@ impl ItemImpls<'_> {
                         >();
                     )
                 });
+                let chunked_guards = Self::generate_chunked_guards(message.callable());
                 quote_spanned!(message_span=>
                     #( #message_inputs )*
                     #message_output
+                    #chunked_guards
                 )
             });
         quote_spanned!(storage_span=>
@@ -180,9 +342,14 @@ impl ItemImpls<'_> {
     }
 
     /// Generates the code for the given ink! message within a trait implementation block.
-    fn generate_trait_message(message: &ir::Message) -> TokenStream2 {
+    fn generate_trait_message(
+        message: &ir::Message,
+        size_report: bool,
+        pausable: Option<&syn::Member>,
+    ) -> TokenStream2 {
         let span = message.span();
         let attrs = message.attrs();
+        let inline = Self::generate_size_report_inline(size_report, attrs);
         let vis = message.visibility();
         let receiver = message.receiver();
         let ident = message.ident();
@@ -194,23 +361,33 @@ impl ItemImpls<'_> {
             .cloned()
             .unwrap_or_else(|| syn::parse_quote! { () });
         let statements = message.statements();
+        let mut statements = quote! { #( #statements )* };
+        statements = Self::expand_migration_guard(message, statements);
+        statements = Self::expand_reentrancy_guard(message, statements);
+        statements = Self::expand_pause_check(message, pausable, statements);
+        statements = Self::expand_deprecation_warning(message, statements);
         quote_spanned!(span =>
             type #output_ident = #output;
 
             #( #attrs )*
+            #inline
             #vis fn #ident(#receiver #( , #inputs )* ) -> Self::#output_ident {
-                #( #statements )*
+                #statements
             }
         )
     }
 
-    fn generate_trait_item_impl(item_impl: &ir::ItemImpl) -> TokenStream2 {
+    fn generate_trait_item_impl(
+        item_impl: &ir::ItemImpl,
+        size_report: bool,
+        pausable: Option<&syn::Member>,
+    ) -> TokenStream2 {
         assert!(item_impl.trait_path().is_some());
         let span = item_impl.span();
         let attrs = item_impl.attrs();
-        let messages = item_impl
-            .iter_messages()
-            .map(|cws| Self::generate_trait_message(cws.callable()));
+        let messages = item_impl.iter_messages().map(|cws| {
+            Self::generate_trait_message(cws.callable(), size_report, pausable)
+        });
         let trait_path = item_impl
             .trait_path()
             .expect("encountered missing trait path for trait impl block");
@@ -232,9 +409,13 @@ impl ItemImpls<'_> {
     ///
     /// The `__ink_dylint_Constructor` config attribute is used here to convey the
     /// information that the generated function is an ink! constructor to `dylint`.
-    fn generate_inherent_constructor(constructor: &ir::Constructor) -> TokenStream2 {
+    fn generate_inherent_constructor(
+        constructor: &ir::Constructor,
+        size_report: bool,
+    ) -> TokenStream2 {
         let span = constructor.span();
         let attrs = constructor.attrs();
+        let inline = Self::generate_size_report_inline(size_report, attrs);
         let vis = constructor.visibility();
         let ident = constructor.ident();
         let inputs = constructor.inputs();
@@ -242,6 +423,7 @@ impl ItemImpls<'_> {
         let output = constructor.output();
         quote_spanned!(span =>
             #( #attrs )*
+            #inline
             #[cfg(not(feature = "__ink_dylint_Constructor"))]
             #vis fn #ident( #( #inputs ),* ) -> #output {
                 #( #statements )*
@@ -250,34 +432,70 @@ impl ItemImpls<'_> {
     }
 
     /// Generates the code for the given ink! message within an inherent implementation block.
-    fn generate_inherent_message(message: &ir::Message) -> TokenStream2 {
+    fn generate_inherent_message(
+        message: &ir::Message,
+        size_report: bool,
+        pausable: Option<&syn::Member>,
+    ) -> TokenStream2 {
         let span = message.span();
         let attrs = message.attrs();
+        let inline = Self::generate_size_report_inline(size_report, attrs);
         let vis = message.visibility();
         let receiver = message.receiver();
         let ident = message.ident();
         let inputs = message.inputs();
         let output_arrow = message.output().map(|_| quote! { -> });
         let output = message.output();
-        let statements = message.statements();
+        let mut statements = match message.multicall_input() {
+            Some(calls) => {
+                quote_spanned!(span=>
+                    ::ink::codegen::execute_multicall::<
+                        <Self as ::ink::reflect::ContractMessageDecoder>::Type,
+                        _,
+                    >(self, #calls)
+                )
+            }
+            None => {
+                let statements = message.statements();
+                quote! { #( #statements )* }
+            }
+        };
+        statements = Self::expand_migration_guard(message, statements);
+        statements = Self::expand_reentrancy_guard(message, statements);
+        statements = Self::expand_pause_check(message, pausable, statements);
+        statements = Self::expand_deprecation_warning(message, statements);
         quote_spanned!(span =>
             #( #attrs )*
+            #inline
             #vis fn #ident(#receiver #( , #inputs )* ) #output_arrow #output {
-                #( #statements )*
+                #statements
             }
         )
     }
 
-    fn generate_inherent_item_impl(item_impl: &ir::ItemImpl) -> TokenStream2 {
+    fn generate_inherent_item_impl(
+        item_impl: &ir::ItemImpl,
+        size_report: bool,
+        pausable: Option<&syn::Member>,
+    ) -> TokenStream2 {
         assert!(item_impl.trait_path().is_none());
         let span = item_impl.span();
         let attrs = item_impl.attrs();
-        let messages = item_impl
-            .iter_messages()
-            .map(|cws| Self::generate_inherent_message(cws.callable()));
+        let messages = item_impl.iter_messages().map(|cws| {
+            Self::generate_inherent_message(cws.callable(), size_report, pausable)
+        });
         let constructors = item_impl
             .iter_constructors()
-            .map(|cws| Self::generate_inherent_constructor(cws.callable()));
+            .map(|cws| Self::generate_inherent_constructor(cws.callable(), size_report));
+        let invariants = item_impl.iter_invariants().map(|invariant| {
+            let span = invariant.span();
+            let item = invariant.item();
+            quote_spanned!(span=>
+                // Invariants are only checked in debug and test builds.
+                #[cfg_attr(not(any(test, debug_assertions)), allow(dead_code))]
+                #item
+            )
+        });
         let other_items = item_impl
             .items()
             .iter()
@@ -289,11 +507,25 @@ impl ItemImpls<'_> {
             impl #self_type {
                 #( #constructors )*
                 #( #messages )*
+                #( #invariants )*
                 #( #other_items )*
             }
         )
     }
 
+    /// Generates `#[inline(never)]` for an ink! constructor or message if the size
+    /// report is enabled and the callable has no `inline` attribute of its own.
+    ///
+    /// This keeps the callable a function of its own in the Wasm blob, so that its
+    /// code size can be attributed to it by the size report of the metadata.
+    fn generate_size_report_inline(
+        size_report: bool,
+        attrs: &[syn::Attribute],
+    ) -> Option<TokenStream2> {
+        let has_inline = attrs.iter().any(|attr| attr.path.is_ident("inline"));
+        (size_report && !has_inline).then(|| quote! { #[inline(never)] })
+    }
+
     /// Generates code to guard against ink! implementations that have not been implemented
     /// for the ink! storage struct.
     fn generate_item_impl_self_ty_guard(&self, item_impl: &ir::ItemImpl) -> TokenStream2 {
@@ -309,9 +541,12 @@ impl ItemImpls<'_> {
     /// Generates code for the given ink! implementation block.
     fn generate_item_impl(&self, item_impl: &ir::ItemImpl) -> TokenStream2 {
         let self_ty_guard = self.generate_item_impl_self_ty_guard(item_impl);
+        let size_report = self.contract.config().size_report();
+        let pausable = self.contract.module().storage().pausable_fields().next();
+        let pausable = pausable.as_ref();
         let impl_block = match item_impl.trait_path() {
-            Some(_) => Self::generate_trait_item_impl(item_impl),
-            None => Self::generate_inherent_item_impl(item_impl),
+            Some(_) => Self::generate_trait_item_impl(item_impl, size_report, pausable),
+            None => Self::generate_inherent_item_impl(item_impl, size_report, pausable),
         };
         quote! {
             #self_ty_guard
@@ -96,14 +96,27 @@ impl Metadata<'_> {
             ::ink::LangError
         };
         let error = Self::generate_type_spec(&error_ty);
+        let env = self.contract.config().env();
+        let chain_config = self.contract.config().chain_config().map(|path| {
+            quote! {
+                .chain_config(::core::option::Option::Some(
+                    <#path as ::ink::ChainConfigMetadata>::chain_config_spec()
+                ))
+            }
+        });
+        let errors = self.generate_errors();
         quote! {
             ::ink::metadata::ContractSpec::new()
                 .constructors([
                     #( #constructors ),*
                 ])
-                .messages([
-                    #( #messages ),*
-                ])
+                .messages({
+                    #[allow(unused_mut)]
+                    let mut messages: ::ink::prelude::vec::Vec<::ink::metadata::MessageSpec> =
+                        ::ink::prelude::vec::Vec::new();
+                    #( #messages )*
+                    messages
+                })
                 .events([
                     #( #events ),*
                 ])
@@ -113,10 +126,52 @@ impl Metadata<'_> {
                 .lang_error(
                      #error
                 )
+                .chain_extension({
+                    use ::ink::codegen::ChainExtensionSpecFallback as _;
+                    ::ink::codegen::ChainExtensionSpecOf::<
+                        <#env as ::ink::env::Environment>::ChainExtension,
+                    >::new()
+                    .spec()
+                })
+                #chain_config
+                .errors({
+                    use ::ink::codegen::ErrorSpecsFallback as _;
+                    #[allow(unused_mut)]
+                    let mut errors: ::ink::prelude::vec::Vec<::ink::metadata::ErrorSpec> =
+                        ::ink::prelude::vec::Vec::new();
+                    #( #errors )*
+                    errors
+                })
                 .done()
         }
     }
 
+    /// Generates the metadata of the error types with stable error codes returned by
+    /// the ink! messages.
+    ///
+    /// Message outputs without stable error codes yield no metadata.
+    fn generate_errors(&self) -> Vec<TokenStream2> {
+        self.contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .filter_map(|message| {
+                let metadata_filter =
+                    Self::generate_message_metadata_filter(message.callable());
+                message.callable().output().map(|output| {
+                    quote_spanned!(output.span()=>
+                        #metadata_filter {
+                            ::core::iter::Extend::extend(
+                                &mut errors,
+                                ::ink::codegen::ErrorSpecsOf::<#output>::new().error_specs(),
+                            );
+                        }
+                    )
+                })
+            })
+            .collect()
+    }
+
     /// Generates ink! metadata for all ink! smart contract constructors.
     #[allow(clippy::redundant_closure)] // We are getting arcane lifetime errors otherwise.
     fn generate_constructors(&self) -> impl Iterator<Item = TokenStream2> + '_ {
@@ -141,8 +196,9 @@ impl Metadata<'_> {
         let selector_id = constructor.composed_selector().into_be_u32();
         let is_payable = constructor.is_payable();
         let constructor = constructor.callable();
+        let display_name = Self::generate_display_name(constructor);
         let ident = constructor.ident();
-        let args = constructor.inputs().map(Self::generate_dispatch_argument);
+        let args = Self::generate_dispatch_arguments(constructor);
         let storage_ident = self.contract.module().storage().ident();
         let ret_ty = Self::generate_constructor_return_type(storage_ident, selector_id);
         quote_spanned!(span=>
@@ -158,26 +214,72 @@ impl Metadata<'_> {
                 .docs([
                     #( #docs ),*
                 ])
+                #display_name
                 .done()
         )
     }
 
+    /// Generates the ink! metadata for all parameters of the given ink! callable.
+    fn generate_dispatch_arguments<'b, C>(
+        callable: &'b C,
+    ) -> impl Iterator<Item = TokenStream2> + 'b
+    where
+        C: ir::Callable,
+    {
+        callable
+            .inputs()
+            .zip(callable.input_defaults())
+            .zip(callable.input_labels())
+            .map(|((pat_type, default), label)| {
+                Self::generate_dispatch_argument(
+                    pat_type,
+                    default.as_ref(),
+                    label.as_deref(),
+                )
+            })
+    }
+
     /// Generates the ink! metadata for the given parameter and parameter type.
-    fn generate_dispatch_argument(pat_type: &syn::PatType) -> TokenStream2 {
+    ///
+    /// The optional default value of the parameter is type checked against the
+    /// parameter type and stored SCALE encoded.
+    fn generate_dispatch_argument(
+        pat_type: &syn::PatType,
+        default: Option<&ir::ArgDefault>,
+        label: Option<&str>,
+    ) -> TokenStream2 {
         let ident = match &*pat_type.pat {
             syn::Pat::Ident(ident) => &ident.ident,
             _ => unreachable!("encountered ink! dispatch input with missing identifier"),
         };
-        let type_spec = Self::generate_type_spec(&pat_type.ty);
+        let ty = &*pat_type.ty;
+        let type_spec = Self::generate_type_spec(ty);
+        let default = default.map(|default| {
+            let expr = default.expr();
+            quote_spanned!(expr.span()=>
+                .default_value(::scale::Encode::encode(&{
+                    let default: #ty = #expr;
+                    default
+                }))
+            )
+        });
+        let display_name = label.map(|label| {
+            quote! {
+                .display_name(#label)
+            }
+        });
         quote! {
             ::ink::metadata::MessageParamSpec::new(::core::stringify!(#ident))
                 .of_type(#type_spec)
+                .display_hints(::ink::metadata::DisplayHint::of_type::<#ty>())
+                #default
+                #display_name
                 .done()
         }
     }
 
     /// Generates the ink! metadata for the given type.
-    fn generate_type_spec(ty: &syn::Type) -> TokenStream2 {
+    pub(crate) fn generate_type_spec(ty: &syn::Type) -> TokenStream2 {
         fn without_display_name(ty: &syn::Type) -> TokenStream2 {
             quote! { ::ink::metadata::TypeSpec::of_type::<#ty>() }
         }
@@ -234,25 +336,42 @@ impl Metadata<'_> {
                 let selector_bytes = message.composed_selector().hex_lits();
                 let is_payable = message.is_payable();
                 let message = message.callable();
+                let metadata_filter = Self::generate_message_metadata_filter(message);
+                let deprecated = Self::generate_message_deprecation(message);
+                let class = Self::generate_message_class(message);
+                let chunked = Self::generate_message_chunked(message);
+                let when_not_paused = Self::generate_message_when_not_paused(message);
+                let display_name = Self::generate_display_name(message);
+                let alias_selector = Self::generate_message_alias_selector(message);
                 let mutates = message.receiver().is_ref_mut();
                 let ident = message.ident();
-                let args = message.inputs().map(Self::generate_dispatch_argument);
+                let args = Self::generate_dispatch_arguments(message);
                 let ret_ty = Self::generate_return_type(Some(&message.wrapped_output()));
                 quote_spanned!(span =>
-                    ::ink::metadata::MessageSpec::from_label(::core::stringify!(#ident))
-                        .selector([
-                            #( #selector_bytes ),*
-                        ])
-                        .args([
-                            #( #args ),*
-                        ])
-                        .returns(#ret_ty)
-                        .mutates(#mutates)
-                        .payable(#is_payable)
-                        .docs([
-                            #( #docs ),*
-                        ])
-                        .done()
+                    #metadata_filter {
+                        messages.push(
+                            ::ink::metadata::MessageSpec::from_label(::core::stringify!(#ident))
+                                .selector([
+                                    #( #selector_bytes ),*
+                                ])
+                                .args([
+                                    #( #args ),*
+                                ])
+                                .returns(#ret_ty)
+                                .mutates(#mutates)
+                                .payable(#is_payable)
+                                .docs([
+                                    #( #docs ),*
+                                ])
+                                #deprecated
+                                #class
+                                #chunked
+                                #when_not_paused
+                                #alias_selector
+                                #display_name
+                                .done()
+                        );
+                    }
                 )
             })
             .collect()
@@ -278,13 +397,21 @@ impl Metadata<'_> {
             .map(|((trait_ident, trait_path), message)| {
                 let message_span = message.span();
                 let message_ident = message.ident();
+                let metadata_filter =
+                    Self::generate_message_metadata_filter(message.callable());
+                let deprecated = Self::generate_message_deprecation(message.callable());
+                let class = Self::generate_message_class(message.callable());
+                let chunked = Self::generate_message_chunked(message.callable());
+                let when_not_paused =
+                    Self::generate_message_when_not_paused(message.callable());
+                let display_name = Self::generate_display_name(message.callable());
+                let alias_selector =
+                    Self::generate_message_alias_selector(message.callable());
                 let message_docs = message
                     .attrs()
                     .iter()
                     .filter_map(|attr| attr.extract_docs());
-                let message_args = message
-                    .inputs()
-                    .map(Self::generate_dispatch_argument);
+                let message_args = Self::generate_dispatch_arguments(message.callable());
                 let mutates = message.receiver().is_ref_mut();
                 let local_id = message.local_id().hex_padded_suffixed();
                 let is_payable = quote! {{
@@ -300,23 +427,107 @@ impl Metadata<'_> {
                 let ret_ty = Self::generate_return_type(message.output());
                 let label = [trait_ident.to_string(), message_ident.to_string()].join("::");
                 quote_spanned!(message_span=>
-                    ::ink::metadata::MessageSpec::from_label(#label)
-                        .selector(#selector)
-                        .args([
-                            #( #message_args ),*
-                        ])
-                        .returns(#ret_ty)
-                        .mutates(#mutates)
-                        .payable(#is_payable)
-                        .docs([
-                            #( #message_docs ),*
-                        ])
-                        .done()
+                    #metadata_filter {
+                        messages.push(
+                            ::ink::metadata::MessageSpec::from_label(#label)
+                                .selector(#selector)
+                                .args([
+                                    #( #message_args ),*
+                                ])
+                                .returns(#ret_ty)
+                                .mutates(#mutates)
+                                .payable(#is_payable)
+                                .docs([
+                                    #( #message_docs ),*
+                                ])
+                                #deprecated
+                                #class
+                                #chunked
+                                #when_not_paused
+                                #alias_selector
+                                #display_name
+                                .done()
+                        );
+                    }
                 )
             })
             .collect()
     }
 
+    /// Generates the condition for the metadata of the given ink! message.
+    ///
+    /// Messages flagged with `#[ink(metadata = false)]` are only included in the
+    /// contract metadata if the `full-metadata` crate feature of ink! is enabled.
+    fn generate_message_metadata_filter(message: &ir::Message) -> Option<TokenStream2> {
+        (!message.has_metadata()).then(|| {
+            quote! {
+                if ::ink::codegen::FULL_METADATA
+            }
+        })
+    }
+
+    /// Generates the deprecation note of the given ink! message if it is deprecated.
+    fn generate_message_deprecation(message: &ir::Message) -> Option<TokenStream2> {
+        message.deprecated().map(|note| {
+            quote! {
+                .deprecated(#note)
+            }
+        })
+    }
+
+    /// Generates the alias selector of the given ink! message if any.
+    fn generate_message_alias_selector(message: &ir::Message) -> Option<TokenStream2> {
+        message.alias_selector().map(|selector| {
+            let selector_bytes = selector.hex_lits();
+            quote! {
+                .alias_selector([
+                    #( #selector_bytes ),*
+                ])
+            }
+        })
+    }
+
+    /// Generates the class of the given ink! message if it is classified.
+    fn generate_message_class(message: &ir::Message) -> Option<TokenStream2> {
+        message.class().map(|class| {
+            let class = class.as_str();
+            quote! {
+                .class(#class)
+            }
+        })
+    }
+
+    /// Generates the chunked flag of the given ink! message if it is chunked.
+    fn generate_message_chunked(message: &ir::Message) -> Option<TokenStream2> {
+        message.is_chunked().then(|| {
+            quote! {
+                .chunked(true)
+            }
+        })
+    }
+
+    /// Generates the paused flag of the given ink! message if it reverts while the
+    /// contract is paused.
+    fn generate_message_when_not_paused(message: &ir::Message) -> Option<TokenStream2> {
+        message.is_when_not_paused().then(|| {
+            quote! {
+                .when_not_paused(true)
+            }
+        })
+    }
+
+    /// Generates the human-readable name of the given ink! callable if it is labeled.
+    fn generate_display_name<C>(callable: &C) -> Option<TokenStream2>
+    where
+        C: ir::Callable,
+    {
+        callable.label().map(|label| {
+            quote! {
+                .display_name(#label)
+            }
+        })
+    }
+
     /// Generates ink! metadata for the given return type.
     fn generate_return_type(ret_ty: Option<&syn::Type>) -> TokenStream2 {
         match ret_ty {
@@ -366,6 +577,8 @@ impl Metadata<'_> {
             let args = Self::generate_event_args(event);
             quote_spanned!(span =>
                 ::ink::metadata::EventSpec::new(::core::stringify!(#ident))
+                    .signature(#ident::SIGNATURE)
+                    .signature_topic(#ident::SIGNATURE_TOPIC)
                     .args([
                         #( #args ),*
                     ])
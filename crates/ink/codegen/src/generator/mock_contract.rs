@@ -0,0 +1,40 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::GenerateCode;
+use derive_more::From;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::spanned::Spanned as _;
+
+/// Generates code for the `mock_contract!` macro.
+#[derive(From)]
+pub struct MockContract<'a> {
+    /// The macro input to generate code for.
+    macro_input: &'a ir::MockContractMacro,
+}
+
+impl GenerateCode for MockContract<'_> {
+    /// Generates `mock_contract!` macro code.
+    fn generate_code(&self) -> TokenStream2 {
+        let trait_path = self.macro_input.trait_path();
+        let span = trait_path.span();
+        let env = self.macro_input.env();
+        quote_spanned!(span=>
+            <<<::ink::reflect::TraitDefinitionRegistry<#env> as #trait_path>::__ink_TraitInfo
+                as ::ink::codegen::TraitMock>::Mock
+                as ::core::default::Default>::default()
+        )
+    }
+}
@@ -29,6 +29,7 @@ macro_rules! impl_as_ref_for_generator {
 mod arg_list;
 mod as_dependency;
 mod blake2b;
+mod chain_config;
 mod chain_extension;
 mod contract;
 mod dispatch;
@@ -37,10 +38,13 @@ mod events;
 mod ink_test;
 mod item_impls;
 mod metadata;
+mod mock_contract;
 mod selector;
+mod shared_item;
 mod storage;
 mod storage_item;
 mod trait_def;
+mod upgrade_guard;
 
 pub use self::{
     arg_list::{
@@ -54,6 +58,7 @@ pub use self::{
     },
     as_dependency::ContractReference,
     blake2b::Blake2x256,
+    chain_config::ChainConfig,
     chain_extension::ChainExtension,
     contract::Contract,
     dispatch::Dispatch,
@@ -62,11 +67,14 @@ pub use self::{
     ink_test::InkTest,
     item_impls::ItemImpls,
     metadata::Metadata,
+    mock_contract::MockContract,
     selector::{
         SelectorBytes,
         SelectorId,
     },
+    shared_item::SharedItem,
     storage::Storage,
     storage_item::StorageItem,
     trait_def::TraitDefinition,
+    upgrade_guard::UpgradeGuard,
 };
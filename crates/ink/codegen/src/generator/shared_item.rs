@@ -0,0 +1,44 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::GenerateCode;
+use derive_more::From;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+
+/// Generates code for an ink! shared item.
+#[derive(From, Copy, Clone)]
+pub struct SharedItem<'a> {
+    /// The shared item to generate code for.
+    item: &'a ir::SharedItem,
+}
+
+impl GenerateCode for SharedItem<'_> {
+    /// Generates ink! shared item code.
+    ///
+    /// The item only derives the traits required to be used as message argument,
+    /// return value or event field. Its type information is derived in the defining
+    /// crate, so the metadata of every contract using it reports the same path and
+    /// lists it under the same stable name in its `customTypes`.
+    fn generate_code(&self) -> TokenStream2 {
+        let ast = self.item.ast();
+        let span = ast.span();
+        quote_spanned!(span=>
+            #[derive(::scale::Encode, ::scale::Decode)]
+            #[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
+            #ast
+        )
+    }
+}
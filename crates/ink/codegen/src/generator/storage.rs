@@ -18,6 +18,7 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::{
     quote,
     quote_spanned,
+    ToTokens as _,
 };
 use syn::spanned::Spanned as _;
 
@@ -96,6 +97,7 @@ impl Storage<'_> {
         let generics = storage.generics();
         let attrs = storage.attrs();
         let fields = storage.fields();
+        let fingerprint = self.storage_fingerprint();
         quote_spanned!( span =>
             #(#attrs)*
             #[::ink::storage_item]
@@ -109,7 +111,88 @@ impl Storage<'_> {
                 impl ::ink::reflect::ContractName for #ident {
                     const NAME: &'static str = ::core::stringify!(#ident);
                 }
+
+                impl ::ink::upgrade::StorageFingerprint for #ident {
+                    const FINGERPRINT: [::core::primitive::u8; 32] = [ #( #fingerprint ),* ];
+                }
             };
         )
     }
+
+    /// Returns the fingerprint of the storage struct definition.
+    ///
+    /// This is the BLAKE2b 256-bit hash of the canonical signature of the storage
+    /// struct, e.g. `Flipper{value:bool}`. It changes whenever the name of the
+    /// storage struct or the name, type or order of one of its fields changes since
+    /// all of them determine how the storage of the contract is laid out.
+    fn storage_fingerprint(&self) -> [u8; 32] {
+        let storage = self.contract.module().storage();
+        let fields = storage
+            .fields()
+            .enumerate()
+            .map(|(n, field)| {
+                let field_ident = field
+                    .ident
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| n.to_string());
+                let field_ty = field.ty.to_token_stream().to_string().replace(' ', "");
+                format!("{}:{}", field_ident, field_ty)
+            })
+            .collect::<Vec<_>>();
+        let signature = format!("{}{{{}}}", storage.ident(), fields.join(","));
+        let mut fingerprint = [0x00; 32];
+        ir::blake2b_256(signature.as_bytes(), &mut fingerprint);
+        fingerprint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the storage fingerprint of a contract with the given storage fields.
+    fn fingerprint_of(fields: TokenStream2) -> [u8; 32] {
+        let contract = ir::Contract::new(
+            quote! {},
+            quote! {
+                mod flipper {
+                    #[ink(storage)]
+                    pub struct Flipper { #fields }
+
+                    impl Flipper {
+                        #[ink(constructor)]
+                        pub fn new() -> Self { todo!() }
+
+                        #[ink(message)]
+                        pub fn get(&self) {}
+                    }
+                }
+            },
+        )
+        .unwrap();
+        Storage::from(&contract).storage_fingerprint()
+    }
+
+    #[test]
+    fn storage_fingerprint_works() {
+        let fingerprint = fingerprint_of(quote! { value: bool, flips: u32 });
+        let mut expected = [0x00; 32];
+        ir::blake2b_256(b"Flipper{value:bool,flips:u32}", &mut expected);
+        assert_eq!(fingerprint, expected);
+        // Documentation does not affect the fingerprint.
+        assert_eq!(
+            fingerprint_of(quote! { /// The value.
+            value: bool, flips: u32 }),
+            fingerprint
+        );
+        // The name, type and order of the fields do.
+        for fields in [
+            quote! { flag: bool, flips: u32 },
+            quote! { value: bool, flips: u64 },
+            quote! { flips: u32, value: bool },
+        ] {
+            assert_ne!(fingerprint_of(fields), fingerprint);
+        }
+    }
 }
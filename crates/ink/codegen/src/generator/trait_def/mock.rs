@@ -0,0 +1,231 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TraitDefinition;
+use crate::{
+    generator,
+    traits::GenerateCode,
+};
+use derive_more::From;
+use proc_macro2::{
+    Span,
+    TokenStream as TokenStream2,
+};
+use quote::{
+    format_ident,
+    quote,
+    quote_spanned,
+};
+
+impl<'a> TraitDefinition<'a> {
+    /// Generates code for the global trait mock for an ink! trait.
+    ///
+    /// # Note
+    ///
+    /// The generated mock wraps an off-chain `MockContract` and allows to set
+    /// typed handlers for the ink! trait messages and to inspect the decoded
+    /// arguments of recorded calls. It is only available in `std` builds.
+    pub fn generate_mock(&self) -> TokenStream2 {
+        Mock::from(*self).generate_code()
+    }
+
+    /// The identifier of the ink! trait mock.
+    pub fn mock_ident(&self) -> syn::Ident {
+        self.append_trait_suffix(Mock::SUFFIX)
+    }
+}
+
+/// Generates code for the global ink! trait mock.
+#[derive(From)]
+struct Mock<'a> {
+    trait_def: TraitDefinition<'a>,
+}
+
+impl GenerateCode for Mock<'_> {
+    fn generate_code(&self) -> TokenStream2 {
+        let struct_definition = self.generate_struct_definition();
+        let trait_mock_impl = self.generate_trait_mock_impl();
+        let inherent_impl = self.generate_inherent_impl();
+        quote! {
+            #[cfg(feature = "std")]
+            const _: () = {
+                #struct_definition
+                #trait_mock_impl
+                #inherent_impl
+            };
+        }
+    }
+}
+
+impl Mock<'_> {
+    /// The name suffix for ink! trait mock.
+    const SUFFIX: &'static str = "TraitMock";
+
+    /// Returns the span of the ink! trait definition.
+    fn span(&self) -> Span {
+        self.trait_def.span()
+    }
+
+    /// Generates the struct type definition for the ink! trait mock.
+    fn generate_struct_definition(&self) -> TokenStream2 {
+        let span = self.span();
+        let mock_ident = self.trait_def.mock_ident();
+        quote_spanned!(span =>
+            /// The contract mock of the ink! trait definition.
+            ///
+            /// Use `ink::mock_contract!` in order to create an instance of it.
+            #[allow(non_camel_case_types)]
+            pub struct #mock_ident<E> {
+                contract: ::ink::env::test::MockContract,
+                marker: ::core::marker::PhantomData<fn() -> E>,
+            }
+
+            impl<E> ::core::default::Default for #mock_ident<E> {
+                fn default() -> Self {
+                    Self {
+                        contract: ::core::default::Default::default(),
+                        marker: ::core::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl<E> ::ink::reflect::ContractEnv for #mock_ident<E>
+            where
+                E: ::ink::env::Environment,
+            {
+                type Env = E;
+            }
+        )
+    }
+
+    /// Registers the ink! trait mock as the mock of the ink! trait definition.
+    fn generate_trait_mock_impl(&self) -> TokenStream2 {
+        let span = self.span();
+        let trait_info_ident = self.trait_def.trait_info_ident();
+        let mock_ident = self.trait_def.mock_ident();
+        quote_spanned!(span =>
+            impl<E> ::ink::codegen::TraitMock for #trait_info_ident<E>
+            where
+                E: ::ink::env::Environment,
+            {
+                type Mock = #mock_ident<E>;
+            }
+        )
+    }
+
+    /// Generates the inherent methods of the ink! trait mock.
+    fn generate_inherent_impl(&self) -> TokenStream2 {
+        let span = self.span();
+        let mock_ident = self.trait_def.mock_ident();
+        let messages = self.trait_def.trait_def.item().iter_items().filter_map(
+            |(item, selector)| {
+                item.filter_map_message()
+                    .map(|message| Self::generate_message_methods(&message, selector))
+            },
+        );
+        quote_spanned!(span =>
+            impl<E> #mock_ident<E>
+            where
+                E: ::ink::env::Environment,
+            {
+                /// Registers the contract mock under `account_id`.
+                ///
+                /// All calls to `account_id` are then dispatched to the contract mock.
+                pub fn register(&self, account_id: E::AccountId) -> &Self {
+                    self.contract.register::<E>(account_id);
+                    self
+                }
+
+                /// Returns all recorded calls to the contract mock in order.
+                pub fn recorded_calls(
+                    &self,
+                ) -> ::ink::prelude::vec::Vec<::ink::env::test::CallData> {
+                    self.contract.recorded_calls()
+                }
+
+                #( #messages )*
+            }
+        )
+    }
+
+    /// Generates the methods to set the handler of and inspect the recorded calls
+    /// to a single ink! trait message.
+    fn generate_message_methods(
+        message: &ir::InkTraitMessage,
+        selector: ir::Selector,
+    ) -> TokenStream2 {
+        let span = message.span();
+        let message_ident = message.ident();
+        let message_name = message_ident.to_string();
+        let on_ident = format_ident!("on_{}", message_ident);
+        let calls_ident = format_ident!("calls_to_{}", message_ident);
+        let on_doc = format!(
+            " Sets the handler of the `{}` ink! trait message.",
+            message_name
+        );
+        let calls_doc = format!(
+            " Returns the decoded arguments of all recorded calls to the `{}` ink! trait message.",
+            message_name
+        );
+        let output_type = message
+            .output()
+            .map_or_else(|| quote! { () }, |output| quote! { #output });
+        let selector_bytes = selector.hex_lits();
+        let input_bindings = generator::input_bindings(message.inputs());
+        let input_types = generator::input_types(message.inputs());
+        let input_bindings_tuple = generator::input_bindings_tuple(message.inputs());
+        let input_types_tuple = generator::input_types_tuple(message.inputs());
+        quote_spanned!(span =>
+            #[doc = #on_doc]
+            ///
+            /// The handler receives the decoded arguments of the message and returns
+            /// its output.
+            pub fn #on_ident<F>(&self, mut handler: F) -> &Self
+            where
+                F: ::core::ops::FnMut( #( #input_types ),* ) -> #output_type + 'static,
+            {
+                self.contract.on(
+                    ::ink::env::call::Selector::new([ #( #selector_bytes ),* ]),
+                    move |mut input: &[::core::primitive::u8]| {
+                        let #input_bindings_tuple =
+                            <#input_types_tuple as ::scale::Decode>::decode(&mut input)
+                                .unwrap_or_else(|error| ::core::panic!(
+                                    "decoding the arguments of `{}` failed: {}",
+                                    #message_name,
+                                    error,
+                                ));
+                        ::scale::Encode::encode(&handler( #( #input_bindings ),* ))
+                    },
+                );
+                self
+            }
+
+            #[doc = #calls_doc]
+            pub fn #calls_ident(&self) -> ::ink::prelude::vec::Vec<#input_types_tuple> {
+                let selector = ::ink::env::call::Selector::new([ #( #selector_bytes ),* ]);
+                let calls = ::core::iter::IntoIterator::into_iter(self.contract.recorded_calls());
+                let calls = ::core::iter::Iterator::filter(calls, |call| call.selector() == selector);
+                let calls = ::core::iter::Iterator::map(calls, |call| {
+                    <#input_types_tuple as ::scale::Decode>::decode(&mut call.params())
+                        .unwrap_or_else(|error| ::core::panic!(
+                            "decoding the arguments of `{}` failed: {}",
+                            #message_name,
+                            error,
+                        ))
+                });
+                ::core::iter::Iterator::collect(calls)
+            }
+        )
+    }
+}
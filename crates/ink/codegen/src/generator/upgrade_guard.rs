@@ -0,0 +1,59 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::GenerateCode;
+use derive_more::From;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+use syn::spanned::Spanned as _;
+
+/// Generates code for the `#[ink::upgrade_guard]` macro.
+#[derive(From)]
+pub struct UpgradeGuard<'a> {
+    /// The upgrade guard to generate code for.
+    guard: &'a ir::UpgradeGuard,
+}
+
+impl GenerateCode for UpgradeGuard<'_> {
+    /// Generates the guarded ink! message.
+    ///
+    /// The statements of the message are executed first and may reject the upgrade
+    /// by returning an `Err`, e.g. if the caller is not authorized. Afterwards the
+    /// code hash is set if the contract is paused and the storage fingerprint matches.
+    fn generate_code(&self) -> TokenStream2 {
+        let item = self.guard.item();
+        let span = item.span();
+        let attrs = &item.attrs;
+        let vis = &item.vis;
+        let sig = &item.sig;
+        let output = match &sig.output {
+            syn::ReturnType::Type(_, ty) => ty,
+            syn::ReturnType::Default => {
+                unreachable!("encountered ink! upgrade guard without output")
+            }
+        };
+        let statements = &item.block.stmts;
+        let code_hash = self.guard.code_hash();
+        let fingerprint = self.guard.fingerprint();
+        quote_spanned!(span=>
+            #( #attrs )*
+            #vis #sig {
+                let __ink_checks: #output = { #( #statements )* };
+                __ink_checks?;
+                ::ink::upgrade::set_code_hash_guarded(self, &#code_hash, &#fingerprint)?;
+                ::core::result::Result::Ok(())
+            }
+        )
+    }
+}
@@ -61,6 +61,10 @@ impl<'a> CodeGenerator for &'a ir::StorageItem {
     type Generator = generator::StorageItem<'a>;
 }
 
+impl<'a> CodeGenerator for &'a ir::SharedItem {
+    type Generator = generator::SharedItem<'a>;
+}
+
 impl<'a> CodeGenerator for &'a ir::InkTraitDefinition {
     type Generator = generator::TraitDefinition<'a>;
 }
@@ -69,10 +73,18 @@ impl<'a> CodeGenerator for &'a ir::InkTest {
     type Generator = generator::InkTest<'a>;
 }
 
+impl<'a> CodeGenerator for &'a ir::UpgradeGuard {
+    type Generator = generator::UpgradeGuard<'a>;
+}
+
 impl<'a> CodeGenerator for &'a ir::ChainExtension {
     type Generator = generator::ChainExtension<'a>;
 }
 
+impl<'a> CodeGenerator for &'a ir::ChainConfig {
+    type Generator = generator::ChainConfig<'a>;
+}
+
 impl<'a> CodeGenerator for &'a ir::SelectorMacro<ir::marker::SelectorId> {
     type Generator = generator::SelectorId<'a>;
 }
@@ -85,6 +97,10 @@ impl<'a> CodeGenerator for &'a ir::Blake2x256Macro {
     type Generator = generator::Blake2x256<'a>;
 }
 
+impl<'a> CodeGenerator for &'a ir::MockContractMacro {
+    type Generator = generator::MockContract<'a>;
+}
+
 /// Generates the entire code for the given ink! contract.
 pub fn generate_code<T>(entity: T) -> TokenStream2
 where
@@ -273,12 +273,122 @@ impl InkAttribute {
         })
     }
 
+    /// Returns the maximum input size of the ink! attribute if any.
+    pub fn max_input(&self) -> Option<u32> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::MaxInput(max_input) = arg.kind() {
+                return Some(*max_input)
+            }
+            None
+        })
+    }
+
+    /// Returns the weight of the ink! attribute if any.
+    pub fn weight(&self) -> Option<u64> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::Weight(weight) = arg.kind() {
+                return Some(*weight)
+            }
+            None
+        })
+    }
+
+    /// Returns the `metadata` flag of the ink! attribute if any.
+    pub fn metadata(&self) -> Option<bool> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::Metadata(metadata) = arg.kind() {
+                return Some(*metadata)
+            }
+            None
+        })
+    }
+
+    /// Returns the deprecation note of the ink! attribute if any.
+    pub fn deprecated(&self) -> Option<String> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::Deprecated(note) = arg.kind() {
+                return Some(note.clone())
+            }
+            None
+        })
+    }
+
+    /// Returns the alias selector of the ink! attribute if any.
+    pub fn alias_selector(&self) -> Option<Selector> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::AliasSelector(selector) = arg.kind() {
+                return Some(*selector)
+            }
+            None
+        })
+    }
+
+    /// Returns the class of the ink! attribute if any.
+    pub fn class(&self) -> Option<MessageClass> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::Class(class) = arg.kind() {
+                return Some(*class)
+            }
+            None
+        })
+    }
+
+    /// Returns the human-readable label of the ink! attribute if any.
+    pub fn label(&self) -> Option<String> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::Label(label) = arg.kind() {
+                return Some(label.clone())
+            }
+            None
+        })
+    }
+
+    /// Returns the default value of the `arg(default = value)` argument if any.
+    pub fn arg_default(&self) -> Option<ArgDefault> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::Arg(default) = arg.kind() {
+                return Some(default.clone())
+            }
+            None
+        })
+    }
+
     /// Returns `true` if the ink! attribute contains the `payable` argument.
     pub fn is_payable(&self) -> bool {
         self.args()
             .any(|arg| matches!(arg.kind(), AttributeArg::Payable))
     }
 
+    /// Returns `true` if the ink! attribute contains the `multicall` argument.
+    pub fn is_multicall(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::Multicall))
+    }
+
+    /// Returns `true` if the ink! attribute contains the `migration` argument.
+    pub fn is_migration(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::Migration))
+    }
+
+    /// Returns `true` if the ink! attribute contains the `non_reentrant` argument.
+    pub fn is_non_reentrant(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::NonReentrant))
+    }
+
+    /// Returns `true` if the ink! attribute contains the `when_not_paused` argument.
+    pub fn is_when_not_paused(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::WhenNotPaused))
+    }
+
+    /// Returns `true` if the ink! attribute contains the `chunked` argument.
+    pub fn is_chunked(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::Chunked))
+    }
+
     /// Returns `true` if the ink! attribute contains the wildcard selector.
     pub fn has_wildcard_selector(&self) -> bool {
         self.args().any(|arg| {
@@ -351,11 +461,37 @@ pub enum AttributeArgKind {
     Constructor,
     /// `#[ink(payable)]`
     Payable,
+    /// `#[ink(multicall)]`
+    Multicall,
+    /// `#[ink(migration)]`
+    Migration,
+    /// `#[ink(chunked)]`
+    Chunked,
+    /// `#[ink(non_reentrant)]`
+    NonReentrant,
+    /// `#[ink(when_not_paused)]`
+    WhenNotPaused,
+    /// `#[ink(invariant)]`
+    Invariant,
     /// `#[ink(selector = _)]`
     /// `#[ink(selector = 0xDEADBEEF)]`
     Selector,
+    /// `#[ink(alias_selector = 0xDEADBEEF)]`
+    AliasSelector,
+    /// `#[ink(max_input = N: u32)]`
+    MaxInput,
+    /// `#[ink(metadata = flag: bool)]`
+    Metadata,
+    /// `#[ink(deprecated = "note")]`
+    Deprecated,
+    /// `#[ink(class = "class")]`
+    Class,
+    /// `#[ink(label = "label")]`
+    Label,
     /// `#[ink(extension = N: u32)]`
     Extension,
+    /// `#[ink(weight = N: u64)]`
+    Weight,
     /// `#[ink(namespace = "my_namespace")]`
     Namespace,
     /// `#[ink(impl)]`
@@ -364,6 +500,8 @@ pub enum AttributeArgKind {
     HandleStatus,
     /// `#[ink(returns_result = flag: bool)]`
     ReturnsResult,
+    /// `#[ink(arg(default = value))]`
+    Arg,
 }
 
 /// An ink! specific attribute flag.
@@ -405,6 +543,37 @@ pub enum AttributeArg {
     /// Applied on ink! constructors or messages in order to specify that they
     /// can receive funds from callers.
     Payable,
+    /// `#[ink(multicall)]`
+    ///
+    /// Applied on ink! messages in order to generate their implementation which
+    /// dispatches a batch of calls to the other ink! messages of the contract.
+    Multicall,
+    /// `#[ink(migration)]`
+    ///
+    /// Applied on ink! messages in order to allow calling them only once per code
+    /// hash of the contract, e.g. to migrate the storage after an upgrade.
+    Migration,
+    /// `#[ink(chunked)]`
+    ///
+    /// Applied on ink! messages in order to return a large sequence of bytes in
+    /// chunks which fit into the static buffer, one chunk per call.
+    Chunked,
+    /// `#[ink(non_reentrant)]`
+    ///
+    /// Applied on ink! messages in order to revert calls to them while another
+    /// non-reentrant ink! message of the contract is in progress.
+    NonReentrant,
+    /// `#[ink(when_not_paused)]`
+    ///
+    /// Applied on ink! messages in order to revert calls to them while the
+    /// contract is paused.
+    WhenNotPaused,
+    /// `#[ink(invariant)]`
+    ///
+    /// Applied on `&self` methods returning `bool` to flag them for being
+    /// invariants of the contract that are checked after every ink! message
+    /// in debug and test builds.
+    Invariant,
     /// Can be either one of:
     ///
     /// - `#[ink(selector = 0xDEADBEEF)]`
@@ -414,6 +583,41 @@ pub enum AttributeArg {
     ///   Applied on ink! messages to define a fallback messages that is invoked
     ///   if no other ink! message matches a given selector.
     Selector(SelectorOrWildcard),
+    /// `#[ink(alias_selector = 0xDEADBEEF)]`
+    ///
+    /// Applied on ink! messages in order to dispatch calls with the given
+    /// selector to them in addition to calls with their own selector, e.g. to
+    /// stay call compatible with a legacy interface.
+    AliasSelector(Selector),
+    /// `#[ink(max_input = N: u32)]`
+    ///
+    /// Applied on ink! messages to reject calls whose encoded arguments exceed
+    /// `N` bytes before they are decoded.
+    MaxInput(u32),
+    /// `#[ink(metadata = flag: bool)]`
+    ///
+    /// Applied on ink! messages in order to omit them from the contract metadata
+    /// if `flag` is `false`. The messages are still dispatchable.
+    ///
+    /// Default value: `true`
+    Metadata(bool),
+    /// `#[ink(deprecated = "note")]`
+    ///
+    /// Applied on ink! messages in order to flag them as deprecated in the
+    /// contract metadata. Deprecated messages remain callable, but calling them
+    /// emits a debug message in debug builds.
+    Deprecated(String),
+    /// `#[ink(class = "class")]`
+    ///
+    /// Applied on ink! messages in order to classify them in the contract
+    /// metadata, e.g. to allow user interfaces to warn about privileged messages.
+    Class(MessageClass),
+    /// `#[ink(label = "label")]`
+    ///
+    /// Applied on ink! messages, constructors or their inputs in order to give
+    /// user interfaces a human-readable name for them via the contract metadata.
+    /// The label must not be empty and has at most 64 characters.
+    Label(String),
     /// `#[ink(namespace = "my_namespace")]`
     ///
     /// Applied on ink! trait implementation blocks to disambiguate other trait
@@ -438,6 +642,13 @@ pub enum AttributeArg {
     ///
     /// Used by the `#[ink::chain_extension]` procedural macro.
     Extension(ExtensionId),
+    /// `#[ink(weight = N: u64)]`
+    ///
+    /// Applies on ink! chain extension methods to declare the weight the chain
+    /// extension method is expected to charge per call.
+    ///
+    /// Used by the `#[ink::chain_extension]` procedural macro.
+    Weight(u64),
     /// `#[ink(handle_status = flag: bool)]`
     ///
     /// Used by the `#[ink::chain_extension]` procedural macro.
@@ -450,6 +661,11 @@ pub enum AttributeArg {
     ///
     /// Default value: `true`
     ReturnsResult(bool),
+    /// `#[ink(arg(default = value))]`
+    ///
+    /// Applied on inputs of ink! messages or constructors in order to provide
+    /// user interfaces with a default value for them via the contract metadata.
+    Arg(ArgDefault),
 }
 
 impl core::fmt::Display for AttributeArgKind {
@@ -462,18 +678,32 @@ impl core::fmt::Display for AttributeArgKind {
             Self::Message => write!(f, "message"),
             Self::Constructor => write!(f, "constructor"),
             Self::Payable => write!(f, "payable"),
+            Self::Multicall => write!(f, "multicall"),
+            Self::Migration => write!(f, "migration"),
+            Self::Chunked => write!(f, "chunked"),
+            Self::NonReentrant => write!(f, "non_reentrant"),
+            Self::WhenNotPaused => write!(f, "when_not_paused"),
+            Self::Invariant => write!(f, "invariant"),
             Self::Selector => {
                 write!(f, "selector = S:[u8; 4] || _")
             }
+            Self::AliasSelector => write!(f, "alias_selector = S:[u8; 4]"),
+            Self::MaxInput => write!(f, "max_input = N:u32"),
+            Self::Metadata => write!(f, "metadata"),
+            Self::Deprecated => write!(f, "deprecated = N:string"),
+            Self::Class => write!(f, "class = N:string"),
+            Self::Label => write!(f, "label = N:string"),
             Self::Extension => {
                 write!(f, "extension = N:u32)")
             }
+            Self::Weight => write!(f, "weight = N:u64"),
             Self::Namespace => {
                 write!(f, "namespace = N:string")
             }
             Self::Implementation => write!(f, "impl"),
             Self::HandleStatus => write!(f, "handle_status"),
             Self::ReturnsResult => write!(f, "returns_result"),
+            Self::Arg => write!(f, "arg(default = value)"),
         }
     }
 }
@@ -489,12 +719,26 @@ impl AttributeArg {
             Self::Message => AttributeArgKind::Message,
             Self::Constructor => AttributeArgKind::Constructor,
             Self::Payable => AttributeArgKind::Payable,
+            Self::Multicall => AttributeArgKind::Multicall,
+            Self::Migration => AttributeArgKind::Migration,
+            Self::Chunked => AttributeArgKind::Chunked,
+            Self::NonReentrant => AttributeArgKind::NonReentrant,
+            Self::WhenNotPaused => AttributeArgKind::WhenNotPaused,
+            Self::Invariant => AttributeArgKind::Invariant,
             Self::Selector(_) => AttributeArgKind::Selector,
+            Self::AliasSelector(_) => AttributeArgKind::AliasSelector,
+            Self::MaxInput(_) => AttributeArgKind::MaxInput,
+            Self::Metadata(_) => AttributeArgKind::Metadata,
+            Self::Deprecated(_) => AttributeArgKind::Deprecated,
+            Self::Class(_) => AttributeArgKind::Class,
+            Self::Label(_) => AttributeArgKind::Label,
             Self::Extension(_) => AttributeArgKind::Extension,
+            Self::Weight(_) => AttributeArgKind::Weight,
             Self::Namespace(_) => AttributeArgKind::Namespace,
             Self::Implementation => AttributeArgKind::Implementation,
             Self::HandleStatus(_) => AttributeArgKind::HandleStatus,
             Self::ReturnsResult(_) => AttributeArgKind::ReturnsResult,
+            Self::Arg(_) => AttributeArgKind::Arg,
         }
     }
 }
@@ -509,16 +753,32 @@ impl core::fmt::Display for AttributeArg {
             Self::Message => write!(f, "message"),
             Self::Constructor => write!(f, "constructor"),
             Self::Payable => write!(f, "payable"),
+            Self::Multicall => write!(f, "multicall"),
+            Self::Migration => write!(f, "migration"),
+            Self::Chunked => write!(f, "chunked"),
+            Self::NonReentrant => write!(f, "non_reentrant"),
+            Self::WhenNotPaused => write!(f, "when_not_paused"),
+            Self::Invariant => write!(f, "invariant"),
             Self::Selector(selector) => core::fmt::Display::fmt(&selector, f),
+            Self::AliasSelector(selector) => {
+                write!(f, "alias_selector = {:?}", selector.to_bytes())
+            }
+            Self::MaxInput(max_input) => write!(f, "max_input = {:?}", max_input),
+            Self::Metadata(value) => write!(f, "metadata = {:?}", value),
+            Self::Deprecated(note) => write!(f, "deprecated = {:?}", note),
+            Self::Class(class) => write!(f, "class = {:?}", class.as_str()),
+            Self::Label(label) => write!(f, "label = {:?}", label),
             Self::Extension(extension) => {
                 write!(f, "extension = {:?}", extension.into_u32())
             }
+            Self::Weight(weight) => write!(f, "weight = {:?}", weight),
             Self::Namespace(namespace) => {
                 write!(f, "namespace = {:?}", namespace.as_bytes())
             }
             Self::Implementation => write!(f, "impl"),
             Self::HandleStatus(value) => write!(f, "handle_status = {:?}", value),
             Self::ReturnsResult(value) => write!(f, "returns_result = {:?}", value),
+            Self::Arg(default) => write!(f, "arg(default = {})", default),
         }
     }
 }
@@ -569,6 +829,137 @@ impl Namespace {
     }
 }
 
+/// The default value of an ink! message or constructor input.
+///
+/// Given via `#[ink(arg(default = value))]` where `value` is either a literal of
+/// the input type or a string literal containing a Rust expression of the input
+/// type, e.g. `#[ink(arg(default = "None"))]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArgDefault {
+    /// The expression evaluating to the default value.
+    expr: Box<syn::Expr>,
+}
+
+impl ArgDefault {
+    /// Returns the expression evaluating to the default value.
+    pub fn expr(&self) -> &syn::Expr {
+        &self.expr
+    }
+}
+
+impl TryFrom<syn::Lit> for ArgDefault {
+    type Error = syn::Error;
+
+    fn try_from(lit: syn::Lit) -> Result<Self, Self::Error> {
+        let expr = match lit {
+            syn::Lit::Str(lit_str) => {
+                lit_str.parse::<syn::Expr>().map_err(|_| {
+                    format_err!(
+                        lit_str,
+                        "expected a Rust expression for `value` in #[ink(arg(default = \"value\"))]",
+                    )
+                })?
+            }
+            lit => {
+                syn::Expr::Lit(syn::ExprLit {
+                    attrs: Vec::new(),
+                    lit,
+                })
+            }
+        };
+        Ok(Self {
+            expr: Box::new(expr),
+        })
+    }
+}
+
+impl core::fmt::Display for ArgDefault {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        use quote::ToTokens as _;
+        write!(f, "{}", self.expr.to_token_stream())
+    }
+}
+
+impl PartialOrd for ArgDefault {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArgDefault {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.to_string().cmp(&other.to_string())
+    }
+}
+
+/// The class of an ink! message given via `#[ink(class = "class")]`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessageClass {
+    /// The message only reads the contract state.
+    View,
+    /// The message is a regular state mutating transaction.
+    Transaction,
+    /// The message is a privileged entry point, e.g. restricted to the owner.
+    Admin,
+}
+
+impl MessageClass {
+    /// All valid message classes.
+    pub const ALL: [Self; 3] = [Self::View, Self::Transaction, Self::Admin];
+
+    /// Returns the name of the message class as used in the contract metadata.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::View => "view",
+            Self::Transaction => "transaction",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl TryFrom<syn::LitStr> for MessageClass {
+    type Error = syn::Error;
+
+    fn try_from(lit_str: syn::LitStr) -> Result<Self, Self::Error> {
+        let class = lit_str.value();
+        Self::ALL
+            .into_iter()
+            .find(|valid| valid.as_str() == class)
+            .ok_or_else(|| {
+                format_err!(
+                    lit_str,
+                    "encountered unknown ink! message class {:?}, expected one of \"view\", \"transaction\" or \"admin\"",
+                    class,
+                )
+            })
+    }
+}
+
+/// The maximum number of characters of a label given via `#[ink(label = "label")]`.
+const MAX_LABEL_LEN: usize = 64;
+
+/// Returns the label given via `#[ink(label = "label")]`.
+///
+/// # Errors
+///
+/// If the label is empty or longer than [`MAX_LABEL_LEN`] characters.
+fn parse_label(lit_str: &syn::LitStr) -> Result<String, syn::Error> {
+    let label = lit_str.value();
+    if label.trim().is_empty() {
+        return Err(format_err!(lit_str, "encountered empty ink! label"))
+    }
+    let len = label.chars().count();
+    if len > MAX_LABEL_LEN {
+        return Err(format_err!(
+            lit_str,
+            "encountered ink! label with {} characters, labels must have at most {} characters",
+            len,
+            MAX_LABEL_LEN,
+        ))
+    }
+    Ok(label)
+}
+
 /// Returns `true` if the given iterator yields at least one attribute of the form
 /// `#[ink(...)]` or `#[ink]`.
 ///
@@ -934,6 +1325,24 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                             }
                             return Err(format_err!(name_value, "expected 4-digit hexcode for `selector` argument, e.g. #[ink(selector = 0xC0FEBABE]"))
                         }
+                        if name_value.path.is_ident("alias_selector") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let selector_u32 = lit_int.base10_parse::<u32>()
+                                    .map_err(|error| {
+                                        format_err_spanned!(
+                                            lit_int,
+                                            "alias selector value out of range. alias selector must be a valid `u32` integer: {}",
+                                            error
+                                        )
+                                    })?;
+                                let selector = Selector::from(selector_u32.to_be_bytes());
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::AliasSelector(selector),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected 4-digit hexcode for `alias_selector` argument, e.g. #[ink(alias_selector = 0xC0FEBABE]"))
+                        }
                         if name_value.path.is_ident("namespace") {
                             if let syn::Lit::Str(lit_str) = &name_value.lit {
                                 let argument = lit_str.value();
@@ -968,6 +1377,76 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                             }
                             return Err(format_err!(name_value, "expected `u32` integer type for `N` in #[ink(extension = N)]"))
                         }
+                        if name_value.path.is_ident("weight") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let weight = lit_int.base10_parse::<u64>().map_err(|parse_err| {
+                                    format_err!(
+                                        name_value,
+                                        "could not parse `N` in `#[ink(weight = N)]` into a `u64` integer",
+                                    ).into_combine(parse_err)
+                                })?;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Weight(weight),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `u64` integer type for `N` in #[ink(weight = N)]"))
+                        }
+                        if name_value.path.is_ident("max_input") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let max_input = lit_int.base10_parse::<u32>().map_err(|parse_err| {
+                                    format_err!(
+                                        name_value,
+                                        "could not parse `N` in `#[ink(max_input = N)]` into a `u32` integer",
+                                    ).into_combine(parse_err)
+                                })?;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::MaxInput(max_input),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `u32` integer type for `N` in #[ink(max_input = N)]"))
+                        }
+                        if name_value.path.is_ident("metadata") {
+                            if let syn::Lit::Bool(lit_bool) = &name_value.lit {
+                                let value = lit_bool.value;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Metadata(value),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `bool` value type for `flag` in #[ink(metadata = flag)]"))
+                        }
+                        if name_value.path.is_ident("deprecated") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let note = lit_str.value();
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Deprecated(note),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected string type for `note` in #[ink(deprecated = \"note\")]"))
+                        }
+                        if name_value.path.is_ident("class") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let class = MessageClass::try_from(lit_str.clone())?;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Class(class),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected string type for `class` in #[ink(class = \"class\")]"))
+                        }
+                        if name_value.path.is_ident("label") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let label = parse_label(lit_str)?;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Label(label),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected string type for `label` in #[ink(label = \"label\")]"))
+                        }
                         if name_value.path.is_ident("handle_status") {
                             if let syn::Lit::Bool(lit_bool) = &name_value.lit {
                                 let value = lit_bool.value;
@@ -1006,12 +1485,23 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                                 "anonymous" => Ok(AttributeArg::Anonymous),
                                 "topic" => Ok(AttributeArg::Topic),
                                 "payable" => Ok(AttributeArg::Payable),
+                                "multicall" => Ok(AttributeArg::Multicall),
+                                "migration" => Ok(AttributeArg::Migration),
+                                "chunked" => Ok(AttributeArg::Chunked),
+                                "non_reentrant" => Ok(AttributeArg::NonReentrant),
+                                "when_not_paused" => Ok(AttributeArg::WhenNotPaused),
+                                "invariant" => Ok(AttributeArg::Invariant),
                                 "impl" => Ok(AttributeArg::Implementation),
                                 "selector" => Err(format_err!(
                                     meta,
                                     "encountered #[ink(selector)] that is missing its u32 parameter. \
                                     Did you mean #[ink(selector = value: u32)] ?"
                                 )),
+                                "alias_selector" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(alias_selector)] that is missing its u32 parameter. \
+                                    Did you mean #[ink(alias_selector = value: u32)] ?"
+                                )),
                                 "namespace" => Err(format_err!(
                                     meta,
                                     "encountered #[ink(namespace)] that is missing its string parameter. \
@@ -1022,6 +1512,36 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                                     "encountered #[ink(extension)] that is missing its `id` parameter. \
                                     Did you mean #[ink(extension = id: u32)] ?"
                                 )),
+                                "weight" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(weight)] that is missing its `N` parameter. \
+                                    Did you mean #[ink(weight = N: u64)] ?"
+                                )),
+                                "max_input" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(max_input)] that is missing its `N` parameter. \
+                                    Did you mean #[ink(max_input = N: u32)] ?"
+                                )),
+                                "metadata" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(metadata)] that is missing its `flag: bool` parameter. \
+                                    Did you mean #[ink(metadata = flag: bool)] ?"
+                                )),
+                                "deprecated" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(deprecated)] that is missing its `note` parameter. \
+                                    Did you mean #[ink(deprecated = \"note\")] ?"
+                                )),
+                                "class" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(class)] that is missing its `class` parameter. \
+                                    Did you mean #[ink(class = \"class\")] ?"
+                                )),
+                                "label" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(label)] that is missing its `label` parameter. \
+                                    Did you mean #[ink(label = \"label\")] ?"
+                                )),
                                 "handle_status" => Err(format_err!(
                                     meta,
                                     "encountered #[ink(handle_status)] that is missing its `flag: bool` parameter. \
@@ -1032,12 +1552,35 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                                     "encountered #[ink(returns_result)] that is missing its `flag: bool` parameter. \
                                     Did you mean #[ink(returns_result = flag: bool)] ?"
                                 )),
+                                "arg" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(arg)] that is missing its `default` parameter. \
+                                    Did you mean #[ink(arg(default = value))] ?"
+                                )),
                                 _ => Err(format_err_spanned!(
                                     meta, "unknown ink! attribute (path)"
                                 ))
                             })
                             .map(|kind| AttributeFrag { ast: meta, arg: kind, })
                     }
+                    syn::Meta::List(meta_list) if meta_list.path.is_ident("arg") => {
+                        let mut nested = meta_list.nested.iter();
+                        match (nested.next(), nested.next()) {
+                            (Some(syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))), None)
+                                if name_value.path.is_ident("default") =>
+                            {
+                                let default = ArgDefault::try_from(name_value.lit.clone())?;
+                                Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Arg(default),
+                                })
+                            }
+                            _ => Err(format_err_spanned!(
+                                meta,
+                                "expected exactly one `default = value` argument in #[ink(arg(default = value))]"
+                            )),
+                        }
+                    }
                     syn::Meta::List(_) => {
                         Err(format_err_spanned!(
                             meta,
@@ -1266,6 +1809,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn alias_selector_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(alias_selector = 0xDEADBEEF)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::AliasSelector(
+                Selector::from([0xDE, 0xAD, 0xBE, 0xEF]),
+            )])),
+        );
+    }
+
+    #[test]
+    fn alias_selector_invalid_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(alias_selector = "_")]
+            },
+            Err("expected 4-digit hexcode for `alias_selector` argument, e.g. #[ink(alias_selector = 0xC0FEBABE]"),
+        );
+    }
+
+    #[test]
+    fn alias_selector_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(alias_selector)]
+            },
+            Err(
+                "encountered #[ink(alias_selector)] that is missing its u32 parameter. \
+                Did you mean #[ink(alias_selector = value: u32)] ?",
+            ),
+        );
+    }
+
     #[test]
     fn namespace_works() {
         assert_attribute_try_from(
@@ -1367,6 +1945,234 @@ mod tests {
         );
     }
 
+    #[test]
+    fn weight_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(weight = 10_000)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Weight(10_000)])),
+        );
+    }
+
+    #[test]
+    fn weight_invalid_value_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(weight = "10000")]
+            },
+            Err("expected `u64` integer type for `N` in #[ink(weight = N)]"),
+        );
+    }
+
+    #[test]
+    fn weight_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(weight)]
+            },
+            Err(
+                "encountered #[ink(weight)] that is missing its `N` parameter. \
+                Did you mean #[ink(weight = N: u64)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn max_input_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(max_input = 4096)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::MaxInput(4096)])),
+        );
+    }
+
+    #[test]
+    fn max_input_invalid_value_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(max_input = "4096")]
+            },
+            Err("expected `u32` integer type for `N` in #[ink(max_input = N)]"),
+        );
+    }
+
+    #[test]
+    fn max_input_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(max_input)]
+            },
+            Err(
+                "encountered #[ink(max_input)] that is missing its `N` parameter. \
+                Did you mean #[ink(max_input = N: u32)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn metadata_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(metadata = false)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Metadata(false)])),
+        );
+    }
+
+    #[test]
+    fn metadata_invalid_value_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(metadata = "false")]
+            },
+            Err("expected `bool` value type for `flag` in #[ink(metadata = flag)]"),
+        );
+    }
+
+    #[test]
+    fn metadata_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(metadata)]
+            },
+            Err(
+                "encountered #[ink(metadata)] that is missing its `flag: bool` parameter. \
+                Did you mean #[ink(metadata = flag: bool)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn deprecated_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(deprecated = "use transfer_v2")]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Deprecated(
+                "use transfer_v2".to_string(),
+            )])),
+        );
+    }
+
+    #[test]
+    fn deprecated_invalid_value_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(deprecated = true)]
+            },
+            Err("expected string type for `note` in #[ink(deprecated = \"note\")]"),
+        );
+    }
+
+    #[test]
+    fn deprecated_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(deprecated)]
+            },
+            Err(
+                "encountered #[ink(deprecated)] that is missing its `note` parameter. \
+                Did you mean #[ink(deprecated = \"note\")] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn label_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(label = "Transfer tokens")]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Label(
+                "Transfer tokens".to_string(),
+            )])),
+        );
+    }
+
+    #[test]
+    fn label_invalid_value_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(label = 42)]
+            },
+            Err("expected string type for `label` in #[ink(label = \"label\")]"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(label = " ")]
+            },
+            Err("encountered empty ink! label"),
+        );
+        let label = "a".repeat(65);
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(label = #label)]
+            },
+            Err("encountered ink! label with 65 characters, labels must have at most 64 characters"),
+        );
+    }
+
+    #[test]
+    fn label_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(label)]
+            },
+            Err(
+                "encountered #[ink(label)] that is missing its `label` parameter. \
+                Did you mean #[ink(label = \"label\")] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn class_works() {
+        for class in MessageClass::ALL {
+            let name = class.as_str();
+            assert_attribute_try_from(
+                syn::parse_quote! {
+                    #[ink(class = #name)]
+                },
+                Ok(test::Attribute::Ink(vec![AttributeArg::Class(class)])),
+            );
+        }
+    }
+
+    #[test]
+    fn class_unknown_value() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(class = "owner")]
+            },
+            Err("encountered unknown ink! message class \"owner\", expected one of \"view\", \"transaction\" or \"admin\""),
+        );
+    }
+
+    #[test]
+    fn class_invalid_value_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(class = 1)]
+            },
+            Err("expected string type for `class` in #[ink(class = \"class\")]"),
+        );
+    }
+
+    #[test]
+    fn class_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(class)]
+            },
+            Err(
+                "encountered #[ink(class)] that is missing its `class` parameter. \
+                Did you mean #[ink(class = \"class\")] ?",
+            ),
+        );
+    }
+
     #[test]
     fn handle_status_works() {
         fn expected_ok(value: bool) -> Result<test::Attribute, &'static str> {
@@ -1481,6 +2287,12 @@ mod tests {
                     event,
                     topic,
                     payable,
+                    multicall,
+                    migration,
+                    chunked,
+                    non_reentrant,
+                    when_not_paused,
+                    invariant,
                     impl,
                 )]
             },
@@ -1491,6 +2303,12 @@ mod tests {
                 AttributeArg::Event,
                 AttributeArg::Topic,
                 AttributeArg::Payable,
+                AttributeArg::Multicall,
+                AttributeArg::Migration,
+                AttributeArg::Chunked,
+                AttributeArg::NonReentrant,
+                AttributeArg::WhenNotPaused,
+                AttributeArg::Invariant,
                 AttributeArg::Implementation,
             ])),
         );
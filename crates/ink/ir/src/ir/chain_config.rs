@@ -0,0 +1,169 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::{
+    Ident,
+    TokenStream as TokenStream2,
+};
+
+/// A checked ink! chain configuration.
+///
+/// A chain configuration is an inherent implementation block whose associated
+/// constants are provided to ink! smart contracts built with
+/// `#[ink::contract(config = ..)]` and recorded in their metadata.
+pub struct ChainConfig {
+    /// The inherent implementation block of the chain configuration.
+    item: syn::ItemImpl,
+    /// The identifier of the type implementing the chain configuration.
+    ident: Ident,
+}
+
+impl ChainConfig {
+    /// Returns `Ok` if the input matches all requirements for an ink! chain configuration.
+    pub fn new(config: TokenStream2, item: TokenStream2) -> Result<Self, syn::Error> {
+        if !config.is_empty() {
+            return Err(format_err_spanned!(
+                config,
+                "unexpected attribute input for ink! chain configuration"
+            ))
+        }
+        let item = syn::parse2::<syn::ItemImpl>(item)?;
+        if let Some((_, trait_path, _)) = &item.trait_ {
+            return Err(format_err_spanned!(
+                trait_path,
+                "ink! chain configurations must be inherent implementation blocks"
+            ))
+        }
+        if !item.generics.params.is_empty() {
+            return Err(format_err_spanned!(
+                item.generics.params,
+                "ink! chain configurations must not be generic"
+            ))
+        }
+        let ident = match &*item.self_ty {
+            syn::Type::Path(type_path) if type_path.qself.is_none() => {
+                type_path
+                    .path
+                    .segments
+                    .last()
+                    .filter(|segment| segment.arguments.is_empty())
+                    .map(|segment| segment.ident.clone())
+            }
+            _ => None,
+        }
+        .ok_or_else(|| {
+            format_err_spanned!(
+                item.self_ty,
+                "ink! chain configurations must be implemented for a named type"
+            )
+        })?;
+        Ok(Self { item, ident })
+    }
+
+    /// Returns the inherent implementation block of the chain configuration.
+    pub fn item(&self) -> &syn::ItemImpl {
+        &self.item
+    }
+
+    /// Returns the identifier of the type implementing the chain configuration.
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    /// Returns an iterator over the constants of the chain configuration.
+    pub fn constants(&self) -> impl Iterator<Item = &syn::ImplItemConst> {
+        self.item.items.iter().filter_map(|item| {
+            match item {
+                syn::ImplItem::Const(constant) => Some(constant),
+                _ => None,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_try_from_fails(config: TokenStream2, item: TokenStream2, expected: &str) {
+        assert_eq!(
+            ChainConfig::new(config, item)
+                .map(|_| ())
+                .map_err(|err| err.to_string()),
+            Err(expected.to_string()),
+        )
+    }
+
+    #[test]
+    fn new_works() {
+        let config = ChainConfig::new(
+            quote::quote! {},
+            quote::quote! {
+                impl chains::MyChainConfig {
+                    pub const FEE_DENOMINATOR: u128 = 1_000;
+                    pub const DECIMALS: u8 = 12;
+
+                    pub fn fee(amount: u128) -> u128 {
+                        amount / Self::FEE_DENOMINATOR
+                    }
+                }
+            },
+        )
+        .unwrap();
+        assert_eq!(config.ident(), "MyChainConfig");
+        assert_eq!(
+            config
+                .constants()
+                .map(|constant| constant.ident.to_string())
+                .collect::<Vec<_>>(),
+            vec!["FEE_DENOMINATOR", "DECIMALS"],
+        );
+    }
+
+    #[test]
+    fn config_input_fails() {
+        assert_try_from_fails(
+            quote::quote! { env = MyEnv },
+            quote::quote! { impl MyChainConfig {} },
+            "unexpected attribute input for ink! chain configuration",
+        )
+    }
+
+    #[test]
+    fn trait_impl_fails() {
+        assert_try_from_fails(
+            quote::quote! {},
+            quote::quote! { impl MyTrait for MyChainConfig {} },
+            "ink! chain configurations must be inherent implementation blocks",
+        )
+    }
+
+    #[test]
+    fn generic_fails() {
+        assert_try_from_fails(
+            quote::quote! {},
+            quote::quote! { impl<T> MyChainConfig<T> {} },
+            "ink! chain configurations must not be generic",
+        )
+    }
+
+    #[test]
+    fn unnamed_type_fails() {
+        assert_try_from_fails(
+            quote::quote! {},
+            quote::quote! { impl [u8; 32] {} },
+            "ink! chain configurations must be implemented for a named type",
+        )
+    }
+}
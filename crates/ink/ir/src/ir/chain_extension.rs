@@ -95,6 +95,11 @@ pub struct ChainExtensionMethod {
     ///
     /// The default for this flag is `true`.
     returns_result: bool,
+    /// The weight the chain extension method is expected to charge per call.
+    ///
+    /// Declared via `#[ink(weight = N)]`. It is included in the metadata and
+    /// charged by the off-chain environment when the method is called.
+    weight: Option<u64>,
 }
 
 impl ChainExtensionMethod {
@@ -143,6 +148,11 @@ impl ChainExtensionMethod {
     pub fn returns_result(&self) -> bool {
         self.returns_result
     }
+
+    /// Returns the weight declared via `#[ink(weight = N)]` if any.
+    pub fn weight(&self) -> Option<u64> {
+        self.weight
+    }
 }
 
 pub struct ChainExtensionMethodInputs<'a> {
@@ -487,7 +497,8 @@ impl ChainExtension {
                 match arg.kind() {
                     ir::AttributeArg::Extension(_)
                     | ir::AttributeArg::HandleStatus(_)
-                    | ir::AttributeArg::ReturnsResult(_) => Ok(()),
+                    | ir::AttributeArg::ReturnsResult(_)
+                    | ir::AttributeArg::Weight(_) => Ok(()),
                     _ => Err(None),
                 }
             },
@@ -503,6 +514,7 @@ impl ChainExtension {
             item: item_method.clone(),
             handle_status: ink_attrs.is_handle_status(),
             returns_result: ink_attrs.is_returns_result(),
+            weight: ink_attrs.weight(),
         };
         Ok(result)
     }
@@ -980,4 +992,29 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    #[test]
+    fn chain_extension_with_weight_is_ok() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                pub trait MyChainExtension {
+                    type ErrorCode = ();
+
+                    #[ink(extension = 1, weight = 10_000)]
+                    fn extension_a();
+                    #[ink(extension = 2)]
+                    #[ink(weight = 0)]
+                    fn extension_b();
+                    #[ink(extension = 3)]
+                    fn extension_c();
+                }
+            })
+            .unwrap();
+        let weights = chain_extension
+            .methods
+            .iter()
+            .map(ChainExtensionMethod::weight)
+            .collect::<Vec<_>>();
+        assert_eq!(weights, vec![Some(10_000), Some(0), None]);
+    }
 }
@@ -31,6 +31,19 @@ pub struct Config {
     env: Option<Environment>,
     /// The set of attributes that can be passed to call builder in the codegen.
     whitelisted_attributes: WhitelistedAttributes,
+    /// If set to `true` a `DISPATCH_TABLE` constant listing all dispatchable
+    /// ink! messages is generated.
+    dispatch_table: bool,
+    /// If set to `true` the ink! constructors and messages are never inlined, so that
+    /// the size report can attribute their code size in the Wasm blob.
+    size_report: bool,
+    /// If set to `true` the `ink::info::ContractInfo` trait is implemented for the
+    /// storage struct, which adds its introspection messages to the contract.
+    contract_info: bool,
+    /// The chain configuration providing compile-time constants.
+    ///
+    /// This must be a type annotated with `#[ink::chain_config]`.
+    chain_config: Option<syn::Path>,
 }
 
 impl TryFrom<ast::AttributeArgs> for Config {
@@ -39,6 +52,10 @@ impl TryFrom<ast::AttributeArgs> for Config {
     fn try_from(args: ast::AttributeArgs) -> Result<Self, Self::Error> {
         let mut env: Option<(Environment, ast::MetaNameValue)> = None;
         let mut whitelisted_attributes = WhitelistedAttributes::default();
+        let mut dispatch_table: Option<(bool, ast::MetaNameValue)> = None;
+        let mut size_report: Option<(bool, ast::MetaNameValue)> = None;
+        let mut contract_info: Option<(bool, ast::MetaNameValue)> = None;
+        let mut chain_config: Option<(syn::Path, ast::MetaNameValue)> = None;
 
         for arg in args.into_iter() {
             if arg.name.is_ident("env") {
@@ -53,8 +70,66 @@ impl TryFrom<ast::AttributeArgs> for Config {
                         "expected a path for `env` ink! configuration argument",
                     ))
                 }
+            } else if arg.name.is_ident("config") {
+                if let Some((_, ast)) = chain_config {
+                    return Err(duplicate_config_err(ast, arg, "config", "contract"))
+                }
+                if let ast::PathOrLit::Path(path) = &arg.value {
+                    chain_config = Some((path.clone(), arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a path for `config` ink! configuration argument",
+                    ))
+                }
             } else if arg.name.is_ident("keep_attr") {
                 whitelisted_attributes.parse_arg_value(&arg)?;
+            } else if arg.name.is_ident("dispatch_table") {
+                if let Some((_, ast)) = dispatch_table {
+                    return Err(duplicate_config_err(
+                        ast,
+                        arg,
+                        "dispatch_table",
+                        "contract",
+                    ))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    dispatch_table = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `dispatch_table` ink! configuration argument",
+                    ))
+                }
+            } else if arg.name.is_ident("size_report") {
+                if let Some((_, ast)) = size_report {
+                    return Err(duplicate_config_err(ast, arg, "size_report", "contract"))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    size_report = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `size_report` ink! configuration argument",
+                    ))
+                }
+            } else if arg.name.is_ident("contract_info") {
+                if let Some((_, ast)) = contract_info {
+                    return Err(duplicate_config_err(
+                        ast,
+                        arg,
+                        "contract_info",
+                        "contract",
+                    ))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    contract_info = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `contract_info` ink! configuration argument",
+                    ))
+                }
             } else {
                 return Err(format_err_spanned!(
                     arg,
@@ -65,6 +140,10 @@ impl TryFrom<ast::AttributeArgs> for Config {
         Ok(Config {
             env: env.map(|(value, _)| value),
             whitelisted_attributes,
+            dispatch_table: dispatch_table.map(|(value, _)| value).unwrap_or(false),
+            size_report: size_report.map(|(value, _)| value).unwrap_or(false),
+            contract_info: contract_info.map(|(value, _)| value).unwrap_or(false),
+            chain_config: chain_config.map(|(value, _)| value),
         })
     }
 }
@@ -85,6 +164,27 @@ impl Config {
     pub fn whitelisted_attributes(&self) -> &WhitelistedAttributes {
         &self.whitelisted_attributes
     }
+
+    /// Returns `true` if a `DISPATCH_TABLE` constant shall be generated.
+    pub fn dispatch_table(&self) -> bool {
+        self.dispatch_table
+    }
+
+    /// Returns `true` if the ink! constructors and messages shall never be inlined
+    /// for the size report.
+    pub fn size_report(&self) -> bool {
+        self.size_report
+    }
+
+    /// Returns `true` if the `ink::info::ContractInfo` trait shall be implemented.
+    pub fn contract_info(&self) -> bool {
+        self.contract_info
+    }
+
+    /// Returns the chain configuration providing compile-time constants if specified.
+    pub fn chain_config(&self) -> Option<&syn::Path> {
+        self.chain_config.as_ref()
+    }
 }
 
 /// The environmental types definition.
@@ -135,6 +235,10 @@ mod tests {
                     path: syn::parse_quote! { ::my::env::Types },
                 }),
                 whitelisted_attributes: Default::default(),
+                dispatch_table: false,
+                size_report: false,
+                contract_info: false,
+                chain_config: None,
             }),
         )
     }
@@ -178,10 +282,127 @@ mod tests {
             Ok(Config {
                 env: None,
                 whitelisted_attributes: attrs,
+                dispatch_table: false,
+                size_report: false,
+                contract_info: false,
+                chain_config: None,
             }),
         )
     }
 
+    #[test]
+    fn dispatch_table_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                dispatch_table = true
+            },
+            Ok(Config {
+                env: None,
+                whitelisted_attributes: Default::default(),
+                dispatch_table: true,
+                size_report: false,
+                contract_info: false,
+                chain_config: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn dispatch_table_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { dispatch_table = "yes" },
+            Err("expected a bool literal for `dispatch_table` ink! configuration argument"),
+        );
+    }
+
+    #[test]
+    fn size_report_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                size_report = true
+            },
+            Ok(Config {
+                env: None,
+                whitelisted_attributes: Default::default(),
+                dispatch_table: false,
+                size_report: true,
+                contract_info: false,
+                chain_config: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn size_report_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { size_report = 1 },
+            Err("expected a bool literal for `size_report` ink! configuration argument"),
+        );
+    }
+
+    #[test]
+    fn contract_info_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                contract_info = true
+            },
+            Ok(Config {
+                env: None,
+                whitelisted_attributes: Default::default(),
+                dispatch_table: false,
+                size_report: false,
+                contract_info: true,
+                chain_config: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn contract_info_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { contract_info = "yes" },
+            Err(
+                "expected a bool literal for `contract_info` ink! configuration argument",
+            ),
+        );
+    }
+
+    #[test]
+    fn config_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                config = crate::chains::MyChainConfig
+            },
+            Ok(Config {
+                env: None,
+                whitelisted_attributes: Default::default(),
+                dispatch_table: false,
+                size_report: false,
+                contract_info: false,
+                chain_config: Some(syn::parse_quote! { crate::chains::MyChainConfig }),
+            }),
+        )
+    }
+
+    #[test]
+    fn config_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { config = "MyChainConfig" },
+            Err("expected a path for `config` ink! configuration argument"),
+        );
+    }
+
+    #[test]
+    fn duplicate_config_fails() {
+        assert_try_from(
+            syn::parse_quote! {
+                config = MyChainConfig,
+                config = MyOtherChainConfig,
+            },
+            Err("encountered duplicate ink! contract `config` configuration argument"),
+        );
+    }
+
     #[test]
     fn keep_attr_invalid_value_fails() {
         assert_try_from(
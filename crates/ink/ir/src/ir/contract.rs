@@ -65,7 +65,10 @@ impl Contract {
         let config = syn::parse2::<ast::AttributeArgs>(ink_config)?;
         let module = syn::parse2::<syn::ItemMod>(ink_module)?;
         let ink_config = ir::Config::try_from(config)?;
-        let ink_module = ir::ItemMod::try_from(module)?;
+        let mut ink_module = ir::ItemMod::try_from(module)?;
+        if ink_config.contract_info() {
+            ink_module.add_contract_info()?;
+        }
         Ok(Self {
             item: ink_module,
             config: ink_config,
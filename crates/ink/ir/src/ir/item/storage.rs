@@ -121,6 +121,35 @@ impl Storage {
     pub fn fields(&self) -> syn::punctuated::Iter<syn::Field> {
         self.ast.fields.iter()
     }
+
+    /// Returns an iterator yielding the members of all fields of the storage struct
+    /// whose type is a `Pausable`.
+    ///
+    /// # Note
+    ///
+    /// A field is a `Pausable` if the last segment of the path of its type is
+    /// `Pausable`, e.g. `ink::storage::security::pausable::Pausable<Environment>`.
+    pub fn pausable_fields(&self) -> impl Iterator<Item = syn::Member> + '_ {
+        self.fields()
+            .enumerate()
+            .filter(|(_, field)| {
+                match &field.ty {
+                    syn::Type::Path(path) => {
+                        matches!(
+                            path.path.segments.last(),
+                            Some(segment) if segment.ident == "Pausable"
+                        )
+                    }
+                    _ => false,
+                }
+            })
+            .map(|(index, field)| {
+                match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(index.into()),
+                }
+            })
+    }
 }
 
 #[cfg(test)]
@@ -132,6 +132,18 @@ where
         <C as Callable>::inputs_span(self.callable)
     }
 
+    fn input_defaults(&self) -> &[Option<ir::ArgDefault>] {
+        <C as Callable>::input_defaults(self.callable)
+    }
+
+    fn label(&self) -> Option<&str> {
+        <C as Callable>::label(self.callable)
+    }
+
+    fn input_labels(&self) -> &[Option<String>] {
+        <C as Callable>::input_labels(self.callable)
+    }
+
     fn statements(&self) -> &[syn::Stmt] {
         <C as Callable>::statements(self.callable)
     }
@@ -178,6 +190,24 @@ pub trait Callable {
     /// Returns the span of the inputs of the ink! callable.
     fn inputs_span(&self) -> Span;
 
+    /// Returns the default values of the input parameters of the ink! callable.
+    ///
+    /// Yields one element per input parameter in the order of [`Callable::inputs`]
+    /// which is `Some` if the parameter is flagged with `#[ink(arg(default = value))]`.
+    fn input_defaults(&self) -> &[Option<ir::ArgDefault>];
+
+    /// Returns the human-readable label of the ink! callable if any.
+    ///
+    /// Given via `#[ink(label = "label")]` and shown by user interfaces instead of
+    /// the identifier of the callable.
+    fn label(&self) -> Option<&str>;
+
+    /// Returns the human-readable labels of the input parameters of the ink! callable.
+    ///
+    /// Yields one element per input parameter in the order of [`Callable::inputs`]
+    /// which is `Some` if the parameter is flagged with `#[ink(label = "label")]`.
+    fn input_labels(&self) -> &[Option<String>];
+
     /// Returns a slice over shared references to the statements of the callable.
     fn statements(&self) -> &[syn::Stmt];
 }
@@ -441,6 +471,70 @@ pub(super) fn ensure_callable_invariants(
     Ok(())
 }
 
+/// The ink! attributes of the inputs of an ink! message or constructor.
+#[derive(Debug, Default)]
+pub(super) struct InputAttrs {
+    /// The default values given via `#[ink(arg(default = value))]`.
+    pub defaults: Vec<Option<ir::ArgDefault>>,
+    /// The human-readable labels given via `#[ink(label = "label")]`.
+    pub labels: Vec<Option<String>>,
+}
+
+/// Extracts the `#[ink(arg(..))]` and `#[ink(label = "label")]` attributes from the
+/// inputs of an ink! message or constructor.
+///
+/// Returns the default values and labels of the inputs in the order of the inputs
+/// and leaves only the non-ink! attributes on the inputs.
+///
+/// # Errors
+///
+/// - If an input has ink! attributes other than `#[ink(arg(default = value))]` or
+///   `#[ink(label = "label")]`.
+/// - If two inputs have the same label.
+pub(super) fn extract_input_attrs(
+    method_item: &mut syn::ImplItemMethod,
+) -> Result<InputAttrs, syn::Error> {
+    let mut input_attrs = InputAttrs::default();
+    for pat_type in method_item.sig.inputs.iter_mut().filter_map(|input| {
+        match input {
+            syn::FnArg::Typed(pat_type) => Some(pat_type),
+            syn::FnArg::Receiver(_) => None,
+        }
+    }) {
+        let (ink_attrs, other_attrs) = ir::sanitize_optional_attributes(
+            pat_type.span(),
+            pat_type.attrs.clone(),
+            |arg| {
+                match arg.kind() {
+                    ir::AttributeArg::Arg(_) | ir::AttributeArg::Label(_) => Ok(()),
+                    _ => Err(None),
+                }
+            },
+        )?;
+        let label = ink_attrs.as_ref().and_then(ir::InkAttribute::label);
+        if let Some(label) = &label {
+            if input_attrs
+                .labels
+                .iter()
+                .flatten()
+                .any(|other| other == label)
+            {
+                return Err(format_err_spanned!(
+                    pat_type,
+                    "encountered duplicate ink! input label {:?}",
+                    label,
+                ))
+            }
+        }
+        input_attrs
+            .defaults
+            .push(ink_attrs.and_then(|ink_attrs| ink_attrs.arg_default()));
+        input_attrs.labels.push(label);
+        pat_type.attrs = other_attrs;
+    }
+    Ok(input_attrs)
+}
+
 /// The visibility of an ink! message or constructor.
 #[derive(Debug, Clone)]
 pub enum Visibility {
@@ -14,6 +14,7 @@
 
 use super::{
     ensure_callable_invariants,
+    extract_input_attrs,
     Callable,
     CallableKind,
     InputsIter,
@@ -73,6 +74,12 @@ pub struct Constructor {
     /// This overrides the computed selector, even when using a manual namespace
     /// for the parent implementation block.
     selector: Option<SelectorOrWildcard>,
+    /// An optional human-readable label given via `#[ink(label = "label")]`.
+    label: Option<String>,
+    /// The default values of the inputs given via `#[ink(arg(default = value))]`.
+    input_defaults: Vec<Option<ir::ArgDefault>>,
+    /// The human-readable labels of the inputs given via `#[ink(label = "label")]`.
+    input_labels: Vec<Option<String>>,
 }
 
 impl quote::ToTokens for Constructor {
@@ -137,7 +144,8 @@ impl Constructor {
                 match arg.kind() {
                     ir::AttributeArg::Constructor
                     | ir::AttributeArg::Payable
-                    | ir::AttributeArg::Selector(_) => Ok(()),
+                    | ir::AttributeArg::Selector(_)
+                    | ir::AttributeArg::Label(_) => Ok(()),
                     _ => Err(None),
                 }
             },
@@ -148,16 +156,21 @@ impl Constructor {
 impl TryFrom<syn::ImplItemMethod> for Constructor {
     type Error = syn::Error;
 
-    fn try_from(method_item: syn::ImplItemMethod) -> Result<Self, Self::Error> {
+    fn try_from(mut method_item: syn::ImplItemMethod) -> Result<Self, Self::Error> {
         ensure_callable_invariants(&method_item, CallableKind::Constructor)?;
         Self::ensure_return(&method_item)?;
         Self::ensure_no_self_receiver(&method_item)?;
         let (ink_attrs, other_attrs) = Self::sanitize_attributes(&method_item)?;
         let is_payable = ink_attrs.is_payable();
         let selector = ink_attrs.selector();
+        let label = ink_attrs.label();
+        let input_attrs = extract_input_attrs(&mut method_item)?;
         Ok(Constructor {
             selector,
             is_payable,
+            label,
+            input_defaults: input_attrs.defaults,
+            input_labels: input_attrs.labels,
             item: syn::ImplItemMethod {
                 attrs: other_attrs,
                 ..method_item
@@ -209,6 +222,18 @@ impl Callable for Constructor {
         self.item.sig.inputs.span()
     }
 
+    fn input_defaults(&self) -> &[Option<ir::ArgDefault>] {
+        &self.input_defaults
+    }
+
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    fn input_labels(&self) -> &[Option<String>] {
+        &self.input_labels
+    }
+
     fn statements(&self) -> &[syn::Stmt] {
         &self.item.block.stmts
     }
@@ -601,4 +626,18 @@ mod tests {
         };
         assert!(<ir::Constructor as TryFrom<_>>::try_from(item).is_ok());
     }
+
+    #[test]
+    fn labels_work() {
+        let item: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(constructor, label = "Create token")]
+            pub fn new(#[ink(label = "Initial supply")] supply: Balance) -> Self {}
+        };
+        let constructor = <ir::Constructor as TryFrom<_>>::try_from(item).unwrap();
+        assert_eq!(constructor.label(), Some("Create token"));
+        assert_eq!(
+            constructor.input_labels(),
+            &[Some("Initial supply".to_string())]
+        );
+    }
 }
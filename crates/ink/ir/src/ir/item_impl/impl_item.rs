@@ -14,6 +14,7 @@
 
 use super::{
     Constructor,
+    Invariant,
     Message,
 };
 use crate::{
@@ -28,6 +29,7 @@ use syn::spanned::Spanned as _;
 /// Can be either
 /// - an ink! [`ir::Constructor`](`crate::ir::Constructor`)
 /// - an ink! [`ir::Message`](`crate::ir::Message`)
+/// - an ink! [`ir::Invariant`](`crate::ir::Invariant`)
 /// - or any other non-ink! item.
 ///
 /// # Note
@@ -40,6 +42,8 @@ pub enum ImplItem {
     Constructor(Constructor),
     /// A `#[ink(message)]` marked method.
     Message(Message),
+    /// A `#[ink(invariant)]` marked method.
+    Invariant(Invariant),
     /// Any other implementation block item.
     Other(syn::ImplItem),
 }
@@ -51,6 +55,7 @@ impl quote::ToTokens for ImplItem {
         match self {
             Self::Constructor(constructor) => constructor.to_tokens(tokens),
             Self::Message(message) => message.to_tokens(tokens),
+            Self::Invariant(invariant) => invariant.to_tokens(tokens),
             Self::Other(other) => other.to_tokens(tokens),
         }
     }
@@ -78,10 +83,14 @@ impl TryFrom<syn::ImplItem> for ImplItem {
                             .map(Into::into)
                             .map(Self::Constructor)
                     }
+                    ir::AttributeArg::Invariant => {
+                        <Invariant as TryFrom<_>>::try_from(method_item)
+                            .map(Self::Invariant)
+                    }
                     _ => Err(format_err_spanned!(
                         method_item,
                         "encountered invalid ink! attribute at this point, expected either \
-                        #[ink(message)] or #[ink(constructor) attributes"
+                        #[ink(message)], #[ink(constructor)] or #[ink(invariant)] attributes"
                     )),
                 }
             }
@@ -137,6 +146,21 @@ impl ImplItem {
         }
     }
 
+    /// Returns `true` if the `impl` block item is an ink! invariant.
+    pub fn is_invariant(&self) -> bool {
+        self.filter_map_invariant().is_some()
+    }
+
+    /// Returns `Some` if `self` is an ink! invariant.
+    ///
+    /// Otherwise, returns `None`.
+    pub fn filter_map_invariant(&self) -> Option<&Invariant> {
+        match self {
+            ImplItem::Invariant(invariant) => Some(invariant),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if the `impl` block item is a non ink! specific item.
     pub fn is_other_item(&self) -> bool {
         self.filter_map_other_item().is_some()
@@ -0,0 +1,266 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ir;
+use proc_macro2::Ident;
+use syn::spanned::Spanned as _;
+
+/// An ink! invariant definition.
+///
+/// Invariants are checked after every ink! message in debug and test builds and
+/// are skipped in release builds.
+///
+/// # Example
+///
+/// ```
+/// # <ink_ir::ItemImpl as TryFrom<syn::ItemImpl>>::try_from(syn::parse_quote! {
+/// impl MyStorage {
+///     #[ink(invariant)]
+///     fn supply_is_covered(&self) -> bool {
+///         /* invariant implementation goes here */
+/// #       unimplemented!()
+///     }
+/// }
+/// # }).unwrap();
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct Invariant {
+    /// The underlying Rust method item.
+    pub(super) item: syn::ImplItemMethod,
+}
+
+impl quote::ToTokens for Invariant {
+    /// We mainly implement this trait for this ink! type to have a derived
+    /// [`Spanned`](`syn::spanned::Spanned`) implementation for it.
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.item.to_tokens(tokens)
+    }
+}
+
+impl Invariant {
+    /// Ensures that the signature of the ink! invariant is `fn(&self) -> bool`.
+    ///
+    /// # Errors
+    ///
+    /// - If the method does not have a `&self` receiver.
+    /// - If the method has inputs other than its receiver.
+    /// - If the method does not return `bool`.
+    /// - If the method is generic, const, async or unsafe.
+    fn ensure_valid_signature(
+        method_item: &syn::ImplItemMethod,
+    ) -> Result<(), syn::Error> {
+        let sig = &method_item.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(format_err_spanned!(
+                sig.generics.params,
+                "ink! invariants must not be generic",
+            ))
+        }
+        if sig.constness.is_some()
+            || sig.asyncness.is_some()
+            || sig.unsafety.is_some()
+            || sig.abi.is_some()
+        {
+            return Err(format_err_spanned!(
+                sig,
+                "ink! invariants must not be const, async, unsafe or have an explicit ABI",
+            ))
+        }
+        let mut inputs = sig.inputs.iter();
+        match (inputs.next(), inputs.next()) {
+            (Some(syn::FnArg::Receiver(receiver)), None)
+                if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+            _ => {
+                return Err(format_err_spanned!(
+                    sig,
+                    "ink! invariants must have a `&self` receiver and no other inputs",
+                ))
+            }
+        }
+        match &sig.output {
+            syn::ReturnType::Type(_, ty) if Self::is_bool(ty) => Ok(()),
+            _ => {
+                Err(format_err_spanned!(
+                    sig.output,
+                    "ink! invariants must return `bool`",
+                ))
+            }
+        }
+    }
+
+    /// Returns `true` if the given type is `bool`.
+    fn is_bool(ty: &syn::Type) -> bool {
+        match ty {
+            syn::Type::Path(type_path) => {
+                type_path.qself.is_none() && type_path.path.is_ident("bool")
+            }
+            _ => false,
+        }
+    }
+
+    /// Sanitizes the attributes for the ink! invariant.
+    ///
+    /// Returns a tuple of ink! attributes and non-ink! attributes.
+    fn sanitize_attributes(
+        method_item: &syn::ImplItemMethod,
+    ) -> Result<(ir::InkAttribute, Vec<syn::Attribute>), syn::Error> {
+        ir::sanitize_attributes(
+            method_item.span(),
+            method_item.attrs.clone(),
+            &ir::AttributeArgKind::Invariant,
+            |arg| {
+                match arg.kind() {
+                    ir::AttributeArg::Invariant => Ok(()),
+                    _ => Err(None),
+                }
+            },
+        )
+    }
+}
+
+impl TryFrom<syn::ImplItemMethod> for Invariant {
+    type Error = syn::Error;
+
+    fn try_from(method_item: syn::ImplItemMethod) -> Result<Self, Self::Error> {
+        Self::ensure_valid_signature(&method_item)?;
+        let (_ink_attrs, other_attrs) = Self::sanitize_attributes(&method_item)?;
+        Ok(Self {
+            item: syn::ImplItemMethod {
+                attrs: other_attrs,
+                ..method_item
+            },
+        })
+    }
+}
+
+impl Invariant {
+    /// Returns the Rust method item of the ink! invariant without its ink! attributes.
+    pub fn item(&self) -> &syn::ImplItemMethod {
+        &self.item
+    }
+
+    /// Returns the identifier of the ink! invariant.
+    pub fn ident(&self) -> &Ident {
+        &self.item.sig.ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_works() {
+        let item_methods: Vec<syn::ImplItemMethod> = vec![
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant(&self) -> bool {}
+            },
+            syn::parse_quote! {
+                #[ink(invariant)]
+                pub fn my_invariant(&self) -> bool {}
+            },
+            syn::parse_quote! {
+                #[ink(invariant)]
+                #[doc = "a documented invariant"]
+                fn my_invariant(&self) -> bool {}
+            },
+        ];
+        for item_method in item_methods {
+            assert!(<Invariant as TryFrom<_>>::try_from(item_method).is_ok());
+        }
+    }
+
+    fn assert_try_from_fails(item_method: syn::ImplItemMethod, expected_err: &str) {
+        assert_eq!(
+            <Invariant as TryFrom<_>>::try_from(item_method)
+                .map_err(|err| err.to_string()),
+            Err(expected_err.to_string()),
+        );
+    }
+
+    #[test]
+    fn try_from_invalid_receiver_fails() {
+        let item_methods: Vec<syn::ImplItemMethod> = vec![
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant() -> bool {}
+            },
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant(&mut self) -> bool {}
+            },
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant(self) -> bool {}
+            },
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant(&self, value: i32) -> bool {}
+            },
+        ];
+        for item_method in item_methods {
+            assert_try_from_fails(
+                item_method,
+                "ink! invariants must have a `&self` receiver and no other inputs",
+            )
+        }
+    }
+
+    #[test]
+    fn try_from_invalid_output_fails() {
+        let item_methods: Vec<syn::ImplItemMethod> = vec![
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant(&self) {}
+            },
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant(&self) -> Result<(), ()> {}
+            },
+        ];
+        for item_method in item_methods {
+            assert_try_from_fails(item_method, "ink! invariants must return `bool`")
+        }
+    }
+
+    #[test]
+    fn try_from_invalid_signature_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(invariant)]
+                fn my_invariant<T>(&self) -> bool {}
+            },
+            "ink! invariants must not be generic",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(invariant)]
+                async fn my_invariant(&self) -> bool {}
+            },
+            "ink! invariants must not be const, async, unsafe or have an explicit ABI",
+        );
+    }
+
+    #[test]
+    fn try_from_conflicting_attributes_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(invariant, payable)]
+                fn my_invariant(&self) -> bool {}
+            },
+            "encountered conflicting ink! attribute argument",
+        );
+    }
+}
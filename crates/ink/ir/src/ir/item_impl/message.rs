@@ -14,6 +14,7 @@
 
 use super::{
     ensure_callable_invariants,
+    extract_input_attrs,
     Callable,
     CallableKind,
     InputsIter,
@@ -105,6 +106,34 @@ pub struct Message {
     /// This overrides the computed selector, even when using a manual namespace
     /// for the parent implementation block.
     selector: Option<SelectorOrWildcard>,
+    /// An optional selector given via `#[ink(alias_selector = S:u32)]` which is
+    /// dispatched to the ink! message in addition to its own selector.
+    alias_selector: Option<ir::Selector>,
+    /// An optional maximum size of the encoded message arguments in bytes.
+    max_input: Option<u32>,
+    /// If the ink! message shall be included in the contract metadata.
+    metadata: bool,
+    /// An optional deprecation note given via `#[ink(deprecated = "note")]`.
+    deprecated: Option<String>,
+    /// An optional class given via `#[ink(class = "class")]`.
+    class: Option<ir::MessageClass>,
+    /// If the ink! message dispatches a batch of calls to the other ink! messages.
+    is_multicall: bool,
+    /// If the ink! message can only be called once per code hash of the contract.
+    is_migration: bool,
+    /// If the ink! message returns a large sequence of bytes in chunks.
+    is_chunked: bool,
+    /// If the ink! message reverts while another non-reentrant ink! message is in
+    /// progress.
+    is_non_reentrant: bool,
+    /// If the ink! message reverts while the contract is paused.
+    is_when_not_paused: bool,
+    /// An optional human-readable label given via `#[ink(label = "label")]`.
+    label: Option<String>,
+    /// The default values of the inputs given via `#[ink(arg(default = value))]`.
+    input_defaults: Vec<Option<ir::ArgDefault>>,
+    /// The human-readable labels of the inputs given via `#[ink(label = "label")]`.
+    input_labels: Vec<Option<String>>,
 }
 
 impl quote::ToTokens for Message {
@@ -171,6 +200,188 @@ impl Message {
         Ok(())
     }
 
+    /// Ensures that an ink! multicall message has a generatable signature.
+    ///
+    /// # Errors
+    ///
+    /// - If the ink! multicall message does not have a `&mut self` receiver.
+    /// - If the ink! multicall message does not have exactly one input.
+    /// - If the ink! multicall message has a wildcard selector.
+    /// - If the ink! multicall message has a non-empty body.
+    fn ensure_valid_multicall(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if !ink_attrs.is_multicall() {
+            return Ok(())
+        }
+        match method_item.sig.inputs.first() {
+            Some(syn::FnArg::Receiver(receiver)) if receiver.mutability.is_some() => (),
+            _ => {
+                return Err(format_err_spanned!(
+                    method_item.sig,
+                    "ink! multicall messages must have a `&mut self` receiver",
+                ))
+            }
+        }
+        let mut inputs = method_item.sig.inputs.iter().skip(1);
+        match (inputs.next(), inputs.next()) {
+            (Some(syn::FnArg::Typed(_)), None) => (),
+            _ => {
+                return Err(format_err_spanned!(
+                    method_item.sig.inputs,
+                    "ink! multicall messages must have exactly one input \
+                    of type `Vec<ink::MulticallCall>` or \
+                    `Vec<ink::MulticallPaidCall<Balance>>`",
+                ))
+            }
+        }
+        if ink_attrs.has_wildcard_selector() {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "ink! multicall messages must not have a wildcard selector",
+            ))
+        }
+        if !method_item.block.stmts.is_empty() {
+            return Err(format_err_spanned!(
+                method_item.block,
+                "ink! multicall messages must have an empty body since their \
+                implementation is generated",
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that an ink! migration message can record that it ran.
+    ///
+    /// # Errors
+    ///
+    /// - If the ink! migration message does not have a `&mut self` receiver.
+    /// - If the ink! migration message is also a multicall message.
+    fn ensure_valid_migration(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if !ink_attrs.is_migration() {
+            return Ok(())
+        }
+        match method_item.sig.inputs.first() {
+            Some(syn::FnArg::Receiver(receiver)) if receiver.mutability.is_some() => (),
+            _ => {
+                return Err(format_err_spanned!(
+                    method_item.sig,
+                    "ink! migration messages must have a `&mut self` receiver",
+                ))
+            }
+        }
+        if ink_attrs.is_multicall() {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "ink! migration messages must not be multicall messages",
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that an ink! chunked message follows the chunked return protocol.
+    ///
+    /// # Errors
+    ///
+    /// - If the ink! chunked message does not have an input for the offset.
+    /// - If the ink! chunked message does not have an output.
+    /// - If the ink! chunked message is also a multicall message.
+    fn ensure_valid_chunked(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if !ink_attrs.is_chunked() {
+            return Ok(())
+        }
+        if !matches!(
+            method_item.sig.inputs.iter().nth(1),
+            Some(syn::FnArg::Typed(_))
+        ) {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "ink! chunked messages must take the `u32` offset of the chunk \
+                as their first input",
+            ))
+        }
+        if let syn::ReturnType::Default = method_item.sig.output {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "ink! chunked messages must return an `ink::chunk::Chunk`",
+            ))
+        }
+        if ink_attrs.is_multicall() {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "ink! chunked messages must not be multicall messages",
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that an ink! non-reentrant message can enter its guard.
+    ///
+    /// # Errors
+    ///
+    /// If the ink! non-reentrant message is also a multicall message, since the
+    /// calls of its batch could not enter the guard.
+    fn ensure_valid_non_reentrant(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if ink_attrs.is_non_reentrant() && ink_attrs.is_multicall() {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "ink! non-reentrant messages must not be multicall messages",
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that the class of an ink! message matches its receiver.
+    ///
+    /// # Errors
+    ///
+    /// If the ink! message is classified as `"view"` but has a `&mut self` receiver.
+    fn ensure_valid_class(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if ink_attrs.class() != Some(ir::MessageClass::View) {
+            return Ok(())
+        }
+        match method_item.sig.inputs.first() {
+            Some(syn::FnArg::Receiver(receiver)) if receiver.mutability.is_some() => {
+                Err(format_err_spanned!(
+                    method_item.sig,
+                    "ink! messages of class \"view\" must have a `&self` receiver",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Ensures that an ink! message with an alias selector is dispatchable by it.
+    ///
+    /// # Errors
+    ///
+    /// If the ink! message has both an alias selector and a wildcard selector.
+    fn ensure_valid_alias_selector(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if ink_attrs.alias_selector().is_some() && ink_attrs.has_wildcard_selector() {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "ink! messages with a wildcard selector must not have an alias selector",
+            ))
+        }
+        Ok(())
+    }
+
     /// Sanitizes the attributes for the ink! message.
     ///
     /// Returns a tuple of ink! attributes and non-ink! attributes.
@@ -185,7 +396,18 @@ impl Message {
                 match arg.kind() {
                     ir::AttributeArg::Message
                     | ir::AttributeArg::Payable
-                    | ir::AttributeArg::Selector(_) => Ok(()),
+                    | ir::AttributeArg::Multicall
+                    | ir::AttributeArg::Migration
+                    | ir::AttributeArg::Chunked
+                    | ir::AttributeArg::NonReentrant
+                    | ir::AttributeArg::WhenNotPaused
+                    | ir::AttributeArg::Selector(_)
+                    | ir::AttributeArg::AliasSelector(_)
+                    | ir::AttributeArg::MaxInput(_)
+                    | ir::AttributeArg::Metadata(_)
+                    | ir::AttributeArg::Deprecated(_)
+                    | ir::AttributeArg::Class(_)
+                    | ir::AttributeArg::Label(_) => Ok(()),
                     _ => Err(None),
                 }
             },
@@ -196,16 +418,47 @@ impl Message {
 impl TryFrom<syn::ImplItemMethod> for Message {
     type Error = syn::Error;
 
-    fn try_from(method_item: syn::ImplItemMethod) -> Result<Self, Self::Error> {
+    fn try_from(mut method_item: syn::ImplItemMethod) -> Result<Self, Self::Error> {
         ensure_callable_invariants(&method_item, CallableKind::Message)?;
         Self::ensure_receiver_is_self_ref(&method_item)?;
         Self::ensure_not_return_self(&method_item)?;
         let (ink_attrs, other_attrs) = Self::sanitize_attributes(&method_item)?;
+        Self::ensure_valid_multicall(&method_item, &ink_attrs)?;
+        Self::ensure_valid_migration(&method_item, &ink_attrs)?;
+        Self::ensure_valid_chunked(&method_item, &ink_attrs)?;
+        Self::ensure_valid_non_reentrant(&method_item, &ink_attrs)?;
+        Self::ensure_valid_class(&method_item, &ink_attrs)?;
+        Self::ensure_valid_alias_selector(&method_item, &ink_attrs)?;
         let is_payable = ink_attrs.is_payable();
         let selector = ink_attrs.selector();
+        let alias_selector = ink_attrs.alias_selector();
+        let max_input = ink_attrs.max_input();
+        let metadata = ink_attrs.metadata().unwrap_or(true);
+        let deprecated = ink_attrs.deprecated();
+        let class = ink_attrs.class();
+        let is_multicall = ink_attrs.is_multicall();
+        let is_migration = ink_attrs.is_migration();
+        let is_chunked = ink_attrs.is_chunked();
+        let is_non_reentrant = ink_attrs.is_non_reentrant();
+        let is_when_not_paused = ink_attrs.is_when_not_paused();
+        let label = ink_attrs.label();
+        let input_attrs = extract_input_attrs(&mut method_item)?;
         Ok(Self {
             is_payable,
             selector,
+            alias_selector,
+            max_input,
+            metadata,
+            deprecated,
+            class,
+            is_multicall,
+            is_migration,
+            is_chunked,
+            is_non_reentrant,
+            is_when_not_paused,
+            label,
+            input_defaults: input_attrs.defaults,
+            input_labels: input_attrs.labels,
             item: syn::ImplItemMethod {
                 attrs: other_attrs,
                 ..method_item
@@ -257,6 +510,18 @@ impl Callable for Message {
         self.item.sig.inputs.span()
     }
 
+    fn input_defaults(&self) -> &[Option<ir::ArgDefault>] {
+        &self.input_defaults
+    }
+
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    fn input_labels(&self) -> &[Option<String>] {
+        &self.input_labels
+    }
+
     fn statements(&self) -> &[syn::Stmt] {
         &self.item.block.stmts
     }
@@ -268,6 +533,98 @@ impl Message {
         &self.item.attrs
     }
 
+    /// Returns the maximum size of the encoded message arguments in bytes if any.
+    pub fn max_input(&self) -> Option<u32> {
+        self.max_input
+    }
+
+    /// Returns `true` if the ink! message shall be included in the contract metadata.
+    ///
+    /// Messages flagged with `#[ink(metadata = false)]` are only included if the
+    /// contract is compiled with the `full-metadata` crate feature of `ink` enabled.
+    pub fn has_metadata(&self) -> bool {
+        self.metadata
+    }
+
+    /// Returns the alias selector of the ink! message if any.
+    ///
+    /// Calls with the alias selector are dispatched to the ink! message just
+    /// like calls with its own selector.
+    pub fn alias_selector(&self) -> Option<ir::Selector> {
+        self.alias_selector
+    }
+
+    /// Returns the deprecation note of the ink! message if it is deprecated.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Returns the class of the ink! message if any.
+    pub fn class(&self) -> Option<ir::MessageClass> {
+        self.class
+    }
+
+    /// Returns `true` if the ink! message is flagged with `#[ink(multicall)]`.
+    ///
+    /// The implementation of an ink! multicall message is generated. It dispatches
+    /// a batch of calls to the other ink! messages of the contract.
+    pub fn is_multicall(&self) -> bool {
+        self.is_multicall
+    }
+
+    /// Returns `true` if the ink! message is flagged with `#[ink(migration)]`.
+    ///
+    /// An ink! migration message can only be called once per code hash of the
+    /// contract, e.g. to migrate the storage once after an upgrade of the code.
+    pub fn is_migration(&self) -> bool {
+        self.is_migration
+    }
+
+    /// Returns `true` if the ink! message is flagged with `#[ink(chunked)]`.
+    ///
+    /// An ink! chunked message takes the `u32` offset of a chunk as its first input
+    /// and returns the chunk of a large sequence of bytes at that offset.
+    pub fn is_chunked(&self) -> bool {
+        self.is_chunked
+    }
+
+    /// Returns `true` if the ink! message is flagged with `#[ink(non_reentrant)]`.
+    ///
+    /// An ink! non-reentrant message reverts if it is called while another
+    /// non-reentrant ink! message of the contract is in progress, e.g. by a contract
+    /// it called with `ALLOW_REENTRY`.
+    pub fn is_non_reentrant(&self) -> bool {
+        self.is_non_reentrant
+    }
+
+    /// Returns `true` if the ink! message is flagged with `#[ink(when_not_paused)]`.
+    ///
+    /// An ink! message flagged with `when_not_paused` reverts while the
+    /// `ink::storage::security::pausable::Pausable` field of the storage struct is paused.
+    pub fn is_when_not_paused(&self) -> bool {
+        self.is_when_not_paused
+    }
+
+    /// Returns the name of the input of an ink! multicall message.
+    ///
+    /// Returns `None` if the ink! message is not flagged with `#[ink(multicall)]`.
+    pub fn multicall_input(&self) -> Option<&Ident> {
+        if !self.is_multicall {
+            return None
+        }
+        self.item.sig.inputs.iter().find_map(|input| {
+            match input {
+                syn::FnArg::Typed(pat_type) => {
+                    match &*pat_type.pat {
+                        syn::Pat::Ident(pat_ident) => Some(&pat_ident.ident),
+                        _ => None,
+                    }
+                }
+                syn::FnArg::Receiver(_) => None,
+            }
+        })
+    }
+
     /// Returns the `self` receiver of the ink! message.
     pub fn receiver(&self) -> Receiver {
         match self.item.sig.inputs.iter().next() {
@@ -460,6 +817,209 @@ mod tests {
         }
     }
 
+    #[test]
+    fn max_input_works() {
+        let test_inputs: Vec<(Option<u32>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                Some(4096),
+                syn::parse_quote! {
+                    #[ink(message, max_input = 4096)]
+                    fn my_message(&self, input: Vec<u8>) {}
+                },
+            ),
+            (
+                Some(32),
+                syn::parse_quote! {
+                    #[ink(message)]
+                    #[ink(payable, max_input = 32)]
+                    pub fn my_message(&self, input: Vec<u8>) {}
+                },
+            ),
+        ];
+        for (expected_max_input, item_method) in test_inputs {
+            let max_input = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .max_input();
+            assert_eq!(max_input, expected_max_input);
+        }
+    }
+
+    #[test]
+    fn deprecated_works() {
+        let test_inputs: Vec<(Option<&str>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn transfer(&mut self, value: u128) {}
+                },
+            ),
+            (
+                Some("use transfer_v2"),
+                syn::parse_quote! {
+                    #[ink(message, deprecated = "use transfer_v2")]
+                    fn transfer(&mut self, value: u128) {}
+                },
+            ),
+            (
+                Some("use transfer_v2"),
+                syn::parse_quote! {
+                    #[ink(message, payable)]
+                    #[ink(deprecated = "use transfer_v2")]
+                    pub fn transfer(&mut self, value: u128) {}
+                },
+            ),
+        ];
+        for (expected_deprecated, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.deprecated(), expected_deprecated);
+        }
+    }
+
+    #[test]
+    fn class_works() {
+        let test_inputs: Vec<(Option<ir::MessageClass>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn get(&self) -> u32 {}
+                },
+            ),
+            (
+                Some(ir::MessageClass::View),
+                syn::parse_quote! {
+                    #[ink(message, class = "view")]
+                    fn get(&self) -> u32 {}
+                },
+            ),
+            (
+                Some(ir::MessageClass::Transaction),
+                syn::parse_quote! {
+                    #[ink(message, class = "transaction")]
+                    fn transfer(&mut self, value: u128) {}
+                },
+            ),
+            (
+                Some(ir::MessageClass::Admin),
+                syn::parse_quote! {
+                    #[ink(message)]
+                    #[ink(class = "admin")]
+                    pub fn set_owner(&mut self, owner: AccountId) {}
+                },
+            ),
+        ];
+        for (expected_class, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.class(), expected_class);
+        }
+    }
+
+    #[test]
+    fn alias_selector_works() {
+        let test_inputs: Vec<(Option<[u8; 4]>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn transfer(&mut self, value: u128) {}
+                },
+            ),
+            (
+                Some([0xDE, 0xAD, 0xBE, 0xEF]),
+                syn::parse_quote! {
+                    #[ink(message, alias_selector = 0xDEADBEEF)]
+                    fn transfer(&mut self, value: u128) {}
+                },
+            ),
+            (
+                Some([0x00, 0x00, 0x00, 0x01]),
+                syn::parse_quote! {
+                    #[ink(message, selector = 0xC0DECAFE)]
+                    #[ink(alias_selector = 1)]
+                    pub fn transfer(&mut self, value: u128) {}
+                },
+            ),
+        ];
+        for (expected_alias_selector, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(
+                message.alias_selector().map(|selector| selector.to_bytes()),
+                expected_alias_selector
+            );
+        }
+    }
+
+    #[test]
+    fn try_from_wildcard_alias_selector_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, selector = _, alias_selector = 0xDEADBEEF)]
+                fn fallback(&mut self) {}
+            },
+            "ink! messages with a wildcard selector must not have an alias selector",
+        )
+    }
+
+    #[test]
+    fn try_from_mutable_view_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, class = "view")]
+                fn get(&mut self) -> u32 {}
+            },
+            "ink! messages of class \"view\" must have a `&self` receiver",
+        )
+    }
+
+    #[test]
+    fn has_metadata_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, metadata = true)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message, metadata = false)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    #[ink(payable, metadata = false)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expected_metadata, item_method) in test_inputs {
+            let metadata = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .has_metadata();
+            assert_eq!(metadata, expected_metadata);
+        }
+    }
+
     #[test]
     fn receiver_works() {
         let test_inputs: Vec<(Receiver, syn::ImplItemMethod)> = vec![
@@ -799,4 +1359,342 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn multicall_works() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message, payable, multicall)]
+            pub fn batch(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(message.is_multicall());
+        assert!(message.is_payable());
+        assert_eq!(
+            message.multicall_input(),
+            Some(&quote::format_ident!("calls"))
+        );
+
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message)]
+            pub fn message(&mut self, calls: Vec<ink::MulticallCall>) {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(!message.is_multicall());
+        assert_eq!(message.multicall_input(), None);
+    }
+
+    #[test]
+    fn migration_works() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message, migration)]
+            pub fn migrate(&mut self) {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(message.is_migration());
+
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message)]
+            pub fn migrate(&mut self) {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(!message.is_migration());
+    }
+
+    #[test]
+    fn try_from_invalid_migration_fails() {
+        let test_inputs: Vec<(syn::ImplItemMethod, &str)> = vec![
+            (
+                syn::parse_quote! {
+                    #[ink(message, migration)]
+                    pub fn migrate(&self) {}
+                },
+                "ink! migration messages must have a `&mut self` receiver",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message, migration, multicall)]
+                    pub fn batch(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+                },
+                "ink! migration messages must not be multicall messages",
+            ),
+        ];
+        for (item_method, expected_error) in test_inputs {
+            assert_try_from_fails(item_method, expected_error)
+        }
+    }
+
+    #[test]
+    fn chunked_works() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message, chunked)]
+            pub fn blob_chunk(&self, offset: u32) -> ink::chunk::Chunk {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(message.is_chunked());
+
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message)]
+            pub fn blob_chunk(&self, offset: u32) -> ink::chunk::Chunk {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(!message.is_chunked());
+    }
+
+    #[test]
+    fn try_from_invalid_chunked_fails() {
+        let test_inputs: Vec<(syn::ImplItemMethod, &str)> = vec![
+            (
+                syn::parse_quote! {
+                    #[ink(message, chunked)]
+                    pub fn blob_chunk(&self) -> ink::chunk::Chunk {}
+                },
+                "ink! chunked messages must take the `u32` offset of the chunk \
+                as their first input",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message, chunked)]
+                    pub fn blob_chunk(&self, offset: u32) {}
+                },
+                "ink! chunked messages must return an `ink::chunk::Chunk`",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message, chunked, multicall)]
+                    pub fn batch(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+                },
+                "ink! chunked messages must not be multicall messages",
+            ),
+        ];
+        for (item_method, expected_error) in test_inputs {
+            assert_try_from_fails(item_method, expected_error)
+        }
+    }
+
+    #[test]
+    fn non_reentrant_works() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message, non_reentrant)]
+            pub fn withdraw(&mut self, value: u128) {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(message.is_non_reentrant());
+
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message)]
+            pub fn withdraw(&mut self, value: u128) {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(!message.is_non_reentrant());
+    }
+
+    #[test]
+    fn when_not_paused_works() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message, when_not_paused)]
+            pub fn withdraw(&mut self, value: u128) {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(message.is_when_not_paused());
+
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message)]
+            pub fn withdraw(&mut self, value: u128) {}
+        };
+        let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+        assert!(!message.is_when_not_paused());
+    }
+
+    #[test]
+    fn try_from_invalid_non_reentrant_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, non_reentrant, multicall)]
+                pub fn batch(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+            },
+            "ink! non-reentrant messages must not be multicall messages",
+        )
+    }
+
+    #[test]
+    fn try_from_invalid_multicall_fails() {
+        let test_inputs: Vec<(syn::ImplItemMethod, &str)> = vec![
+            (
+                syn::parse_quote! {
+                    #[ink(message, multicall)]
+                    pub fn batch(&self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+                },
+                "ink! multicall messages must have a `&mut self` receiver",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message, multicall)]
+                    pub fn batch(&mut self) -> ink::MulticallResult {}
+                },
+                "ink! multicall messages must have exactly one input \
+                of type `Vec<ink::MulticallCall>` or \
+                `Vec<ink::MulticallPaidCall<Balance>>`",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message, multicall)]
+                    pub fn batch(&mut self, a: Vec<ink::MulticallCall>, b: bool) -> ink::MulticallResult {}
+                },
+                "ink! multicall messages must have exactly one input \
+                of type `Vec<ink::MulticallCall>` or \
+                `Vec<ink::MulticallPaidCall<Balance>>`",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message, multicall, selector = _)]
+                    pub fn batch(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+                },
+                "ink! multicall messages must not have a wildcard selector",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message, multicall)]
+                    pub fn batch(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {
+                        Ok(Vec::new())
+                    }
+                },
+                "ink! multicall messages must have an empty body since their \
+                implementation is generated",
+            ),
+        ];
+        for (item_method, expected_err) in test_inputs {
+            assert_try_from_fails(item_method, expected_err)
+        }
+    }
+
+    #[test]
+    fn input_defaults_works() {
+        let message =
+            <ir::Message as TryFrom<syn::ImplItemMethod>>::try_from(syn::parse_quote! {
+                #[ink(message)]
+                pub fn my_message(
+                    &self,
+                    #[ink(arg(default = 10))] a: u32,
+                    b: bool,
+                    #[ink(arg(default = "Some(Ok(1))"))]
+                    #[allow(unused)]
+                    c: Option<Result<u8, ()>>,
+                ) {}
+            })
+            .unwrap();
+        let defaults = message
+            .input_defaults()
+            .iter()
+            .map(|default| default.as_ref().map(ToString::to_string))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            defaults,
+            vec![
+                Some("10".to_string()),
+                None,
+                Some("Some (Ok (1))".to_string())
+            ]
+        );
+        // The ink! attributes are removed from the inputs, other attributes are kept.
+        let attrs = message
+            .inputs()
+            .map(|input| input.attrs.len())
+            .collect::<Vec<_>>();
+        assert_eq!(attrs, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn try_from_invalid_input_defaults_fails() {
+        let test_inputs: Vec<(syn::ImplItemMethod, &str)> = vec![
+            (
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&self, #[ink(payable)] a: u32) {}
+                },
+                "encountered conflicting ink! attribute argument",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&self, #[ink(arg)] a: u32) {}
+                },
+                "encountered #[ink(arg)] that is missing its `default` parameter. \
+                Did you mean #[ink(arg(default = value))] ?",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&self, #[ink(arg(value = 1))] a: u32) {}
+                },
+                "expected exactly one `default = value` argument in #[ink(arg(default = value))]",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&self, #[ink(arg(default = "1 +"))] a: u32) {}
+                },
+                "expected a Rust expression for `value` in #[ink(arg(default = \"value\"))]",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(
+                        &self,
+                        #[ink(arg(default = 1))]
+                        #[ink(arg(default = 2))]
+                        a: u32,
+                    ) {}
+                },
+                "encountered ink! attribute arguments with equal kinds",
+            ),
+            (
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(
+                        &self,
+                        #[ink(label = "Amount")] a: u32,
+                        #[ink(label = "Amount")] b: u32,
+                    ) {}
+                },
+                "encountered duplicate ink! input label \"Amount\"",
+            ),
+        ];
+        for (item_method, expected_err) in test_inputs {
+            assert_try_from_fails(item_method, expected_err)
+        }
+    }
+
+    #[test]
+    fn labels_work() {
+        let message =
+            <ir::Message as TryFrom<syn::ImplItemMethod>>::try_from(syn::parse_quote! {
+                #[ink(message, label = "Transfer tokens")]
+                pub fn transfer(
+                    &mut self,
+                    #[ink(label = "Recipient")] to: AccountId,
+                    #[ink(label = "Amount", arg(default = 0))] value: Balance,
+                    data: Vec<u8>,
+                ) {}
+            })
+            .unwrap();
+        assert_eq!(message.label(), Some("Transfer tokens"));
+        assert_eq!(
+            message.input_labels(),
+            &[
+                Some("Recipient".to_string()),
+                Some("Amount".to_string()),
+                None
+            ]
+        );
+        assert!(message.input_defaults()[1].is_some());
+
+        let message =
+            <ir::Message as TryFrom<syn::ImplItemMethod>>::try_from(syn::parse_quote! {
+                #[ink(message)]
+                pub fn transfer(&mut self, to: AccountId) {}
+            })
+            .unwrap();
+        assert_eq!(message.label(), None);
+        assert_eq!(message.input_labels(), &[None]);
+    }
 }
@@ -25,13 +25,17 @@ use proc_macro2::{
 mod callable;
 mod constructor;
 mod impl_item;
+mod invariant;
 mod iter;
 mod message;
 
 #[cfg(test)]
 mod tests;
 
-use self::callable::ensure_callable_invariants;
+use self::callable::{
+    ensure_callable_invariants,
+    extract_input_attrs,
+};
 pub use self::{
     callable::{
         Callable,
@@ -42,6 +46,7 @@ pub use self::{
     },
     constructor::Constructor,
     impl_item::ImplItem,
+    invariant::Invariant,
     iter::{
         IterConstructors,
         IterMessages,
@@ -184,7 +189,7 @@ impl ItemImpl {
             }
         }
         // Check if any of the implementation block's methods either resembles
-        // an ink! constructor or an ink! message:
+        // an ink! constructor, an ink! message or an ink! invariant:
         'repeat: for item in &item_impl.items {
             match item {
                 syn::ImplItem::Method(method_item) => {
@@ -194,9 +199,9 @@ impl ItemImpl {
                     let attr = ir::first_ink_attribute(&method_item.attrs)?
                         .expect("missing expected ink! attribute for struct");
                     match attr.first().kind() {
-                        ir::AttributeArg::Constructor | ir::AttributeArg::Message => {
-                            return Ok(true)
-                        }
+                        ir::AttributeArg::Constructor
+                        | ir::AttributeArg::Message
+                        | ir::AttributeArg::Invariant => return Ok(true),
                         _ => continue 'repeat,
                     }
                 }
@@ -276,6 +281,12 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
                         "message",
                         is_trait_impl,
                     )?;
+                    if is_trait_impl && message.is_multicall() {
+                        return Err(format_err!(
+                            message.item.span(),
+                            "ink! multicall messages are not allowed in trait impl blocks",
+                        ))
+                    }
                 }
                 ir::ImplItem::Constructor(constructor) => {
                     ensure_valid_visibility(
@@ -285,6 +296,12 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
                         is_trait_impl,
                     )?;
                 }
+                ir::ImplItem::Invariant(invariant) if is_trait_impl => {
+                    return Err(format_err!(
+                        invariant.item.span(),
+                        "ink! invariants are not allowed in trait impl blocks",
+                    ))
+                }
                 _ => (),
             }
         }
@@ -368,6 +385,11 @@ impl ItemImpl {
         IterConstructors::new(self)
     }
 
+    /// Returns an iterator yielding the ink! invariants of the implementation block.
+    pub fn iter_invariants(&self) -> impl Iterator<Item = &ir::Invariant> {
+        self.items.iter().filter_map(ImplItem::filter_map_invariant)
+    }
+
     /// Returns a slice over the shared references of the items of the `impl`.
     pub fn items(&self) -> &[ir::ImplItem] {
         &self.items
@@ -51,6 +51,12 @@ fn is_ink_impl_block_eval_true_works() {
                 fn my_message(&self) {}
             }
         },
+        syn::parse_quote! {
+            impl MyStorage {
+                #[ink(invariant)]
+                fn my_invariant(&self) -> bool {}
+            }
+        },
         syn::parse_quote! {
             #[ink(impl)]
             impl MyTrait for MyStorage {}
@@ -206,6 +212,19 @@ fn visibility_fails() {
     );
 }
 
+#[test]
+fn invariant_in_trait_impl_fails() {
+    assert_try_from_item_impl_fails(
+        syn::parse_quote! {
+            impl MyTrait for MyStorage {
+                #[ink(invariant)]
+                fn my_invariant(&self) -> bool {}
+            }
+        },
+        "ink! invariants are not allowed in trait impl blocks",
+    );
+}
+
 #[test]
 fn try_from_works() {
     let item_impls: Vec<syn::ItemImpl> = vec![
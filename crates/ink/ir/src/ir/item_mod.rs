@@ -22,7 +22,10 @@ use proc_macro2::{
     Span,
 };
 use quote::TokenStreamExt as _;
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    iter,
+};
 use syn::{
     spanned::Spanned,
     token,
@@ -204,18 +207,21 @@ impl ItemMod {
                 ))
             }
             for message in item_impl.iter_messages() {
-                let selector = message.composed_selector();
-                match messages.entry(selector) {
-                    Entry::Occupied(overlap) => {
-                        return Err(compose_error(
-                            overlap.get().span(),
-                            message.callable().span(),
-                            selector,
-                            "message",
-                        ))
-                    }
-                    Entry::Vacant(vacant) => {
-                        vacant.insert(message.callable());
+                let selectors = iter::once(message.composed_selector())
+                    .chain(message.callable().alias_selector());
+                for selector in selectors {
+                    match messages.entry(selector) {
+                        Entry::Occupied(overlap) => {
+                            return Err(compose_error(
+                                overlap.get().span(),
+                                message.callable().span(),
+                                selector,
+                                "message",
+                            ))
+                        }
+                        Entry::Vacant(vacant) => {
+                            vacant.insert(message.callable());
+                        }
                     }
                 }
             }
@@ -290,6 +296,129 @@ impl ItemMod {
         }
         Ok(())
     }
+
+    /// Ensures that at most one ink! message is flagged with `#[ink(multicall)]`.
+    fn ensure_only_one_multicall_message(items: &[ir::Item]) -> Result<(), syn::Error> {
+        let mut multicall: Option<&ir::Message> = None;
+        for message in items
+            .iter()
+            .filter_map(ir::Item::map_ink_item)
+            .filter_map(ir::InkItem::filter_map_impl_block)
+            .flat_map(ir::ItemImpl::iter_messages)
+        {
+            if !message.callable().is_multicall() {
+                continue
+            }
+            match multicall {
+                None => multicall = Some(message.callable()),
+                Some(first) => {
+                    use crate::error::ExtError as _;
+                    return Err(format_err!(
+                        message.callable().span(),
+                        "encountered more than one ink! multicall message",
+                    )
+                    .into_combine(format_err!(
+                        first.span(),
+                        "first ink! multicall message here",
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures that the ink! storage struct has exactly one `Pausable` field if an
+    /// ink! message is flagged with `#[ink(when_not_paused)]`.
+    ///
+    /// # Note
+    ///
+    /// The flagged messages check the `Pausable` field of the storage struct, so it
+    /// must be unambiguous which `Pausable` that is.
+    fn ensure_pausable_for_when_not_paused(items: &[ir::Item]) -> Result<(), syn::Error> {
+        let storage = items
+            .iter()
+            .filter_map(ir::Item::map_ink_item)
+            .find_map(ir::InkItem::filter_map_storage_item)
+            .expect("encountered ink! module without a storage struct");
+        if storage.pausable_fields().count() == 1 {
+            return Ok(())
+        }
+        let when_not_paused = items
+            .iter()
+            .filter_map(ir::Item::map_ink_item)
+            .filter_map(ir::InkItem::filter_map_impl_block)
+            .flat_map(ir::ItemImpl::iter_messages)
+            .find(|message| message.callable().is_when_not_paused());
+        if let Some(message) = when_not_paused {
+            return Err(format_err!(
+                message.callable().span(),
+                "ink! messages flagged with `when_not_paused` require exactly one \
+                `Pausable` field in the ink! storage struct",
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that no two ink! messages and no two ink! constructors have the same
+    /// label given via `#[ink(label = "label")]`.
+    ///
+    /// # Note
+    ///
+    /// Labels of messages and constructors may coincide since user interfaces show
+    /// them separately from each other.
+    fn ensure_unique_labels(items: &[ir::Item]) -> Result<(), syn::Error> {
+        /// Kind is either `"message"` or `"constructor"`.
+        fn ensure_unique<'a>(
+            callables: impl Iterator<Item = (Option<&'a str>, Span)>,
+            kind: &str,
+        ) -> Result<(), syn::Error> {
+            let mut labels = <HashMap<&str, Span>>::new();
+            for (label, span) in callables {
+                let label = match label {
+                    Some(label) => label,
+                    None => continue,
+                };
+                if let Some(first) = labels.insert(label, span) {
+                    use crate::error::ExtError as _;
+                    return Err(format_err!(
+                        span,
+                        "encountered ink! {}s with duplicate label {:?}",
+                        kind,
+                        label,
+                    )
+                    .into_combine(format_err!(
+                        first,
+                        "first ink! {} with this label here",
+                        kind,
+                    )))
+                }
+            }
+            Ok(())
+        }
+        let item_impls = || {
+            items
+                .iter()
+                .filter_map(ir::Item::map_ink_item)
+                .filter_map(ir::InkItem::filter_map_impl_block)
+        };
+        ensure_unique(
+            item_impls()
+                .flat_map(ir::ItemImpl::iter_messages)
+                .map(|message| (message.callable().label(), message.callable().span())),
+            "message",
+        )?;
+        ensure_unique(
+            item_impls()
+                .flat_map(ir::ItemImpl::iter_constructors)
+                .map(|constructor| {
+                    (
+                        constructor.callable().label(),
+                        constructor.callable().span(),
+                    )
+                }),
+            "constructor",
+        )
+    }
 }
 
 impl TryFrom<syn::ItemMod> for ItemMod {
@@ -330,6 +459,9 @@ impl TryFrom<syn::ItemMod> for ItemMod {
         Self::ensure_contains_constructor(module_span, &items)?;
         Self::ensure_no_overlapping_selectors(&items)?;
         Self::ensure_only_one_wildcard_selector(&items)?;
+        Self::ensure_only_one_multicall_message(&items)?;
+        Self::ensure_unique_labels(&items)?;
+        Self::ensure_pausable_for_when_not_paused(&items)?;
         Ok(Self {
             attrs: other_attrs,
             vis: module.vis,
@@ -397,6 +529,44 @@ impl ItemMod {
         storage
     }
 
+    /// Adds the implementation of `ink::info::ContractInfo` for the storage struct.
+    ///
+    /// # Errors
+    ///
+    /// If one of its messages overlaps with the selector of another message.
+    pub(crate) fn add_contract_info(&mut self) -> Result<(), syn::Error> {
+        let storage = self.storage();
+        let span = storage.span();
+        let ident = storage.ident();
+        let item_impl: syn::Item = syn::parse_quote_spanned!(span=>
+            impl ::ink::info::ContractInfo for #ident {
+                #[ink(message)]
+                fn code_version(&self) -> ::ink::prelude::string::String {
+                    ::ink::prelude::string::String::from(::core::env!("CARGO_PKG_VERSION"))
+                }
+
+                #[ink(message)]
+                fn storage_fingerprint(&self) -> [::core::primitive::u8; 32] {
+                    <Self as ::ink::upgrade::StorageFingerprint>::FINGERPRINT
+                }
+
+                #[ink(message)]
+                fn is_paused(&self) -> ::core::option::Option<::core::primitive::bool> {
+                    // Only one of the traits applies to the storage struct.
+                    #[allow(unused_imports)]
+                    use ::ink::info::{
+                        IsNotPausable as _,
+                        IsPausable as _,
+                    };
+                    (&::ink::info::PausedStatus(self)).get()
+                }
+            }
+        );
+        self.items
+            .push(<ir::Item as TryFrom<syn::Item>>::try_from(item_impl)?);
+        Self::ensure_no_overlapping_selectors(&self.items)
+    }
+
     /// Returns all (ink! and non-ink! specific) item definitions of the ink! inline module.
     pub fn items(&self) -> &[ir::Item] {
         self.items.as_slice()
@@ -778,6 +948,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn overlapping_alias_selectors_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, selector = 0xDEADBEEF)]
+                        pub fn my_message_1(&self) {}
+
+                        #[ink(message, alias_selector = 0xDEADBEEF)]
+                        pub fn my_message_2(&self) {}
+                    }
+                }
+            },
+            "encountered ink! messages with overlapping selectors (= [DE, AD, BE, EF])\n\
+            hint: use #[ink(selector = S:u32)] on the callable or #[ink(namespace = N:string)] \
+            on the implementation block to disambiguate overlapping selectors.",
+        );
+    }
+
     #[test]
     fn overlapping_constructors_fails() {
         assert_fail(
@@ -879,6 +1075,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multiple_multicall_messages_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, multicall)]
+                        pub fn my_batch1(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+
+                        #[ink(message, multicall)]
+                        pub fn my_batch2(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+                    }
+                }
+            },
+            "encountered more than one ink! multicall message",
+        );
+    }
+
+    #[test]
+    fn duplicate_labels_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor, label = "Create")]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, label = "Transfer")]
+                        pub fn my_message1(&self) {}
+
+                        #[ink(message, label = "Transfer")]
+                        pub fn my_message2(&self) {}
+                    }
+                }
+            },
+            "encountered ink! messages with duplicate label \"Transfer\"",
+        );
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor, label = "Create")]
+                        pub fn my_constructor1() -> Self {}
+
+                        #[ink(constructor, label = "Create")]
+                        pub fn my_constructor2() -> Self {}
+
+                        #[ink(message, label = "Create")]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            },
+            "encountered ink! constructors with duplicate label \"Create\"",
+        );
+    }
+
+    #[test]
+    fn multicall_message_in_trait_impl_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+                    }
+
+                    impl MyTrait for MyStorage {
+                        #[ink(message, multicall)]
+                        fn my_batch(&mut self, calls: Vec<ink::MulticallCall>) -> ink::MulticallResult {}
+                    }
+                }
+            },
+            "ink! multicall messages are not allowed in trait impl blocks",
+        );
+    }
+
     #[test]
     fn wildcard_selector_on_constructor_works() {
         assert!(
@@ -920,4 +1207,51 @@ mod tests {
             "encountered ink! attribute arguments with equal kinds",
         );
     }
+
+    #[test]
+    fn when_not_paused_without_pausable_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, when_not_paused)]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            },
+            "ink! messages flagged with `when_not_paused` require exactly one \
+            `Pausable` field in the ink! storage struct",
+        );
+    }
+
+    #[test]
+    fn when_not_paused_with_multiple_pausables_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {
+                        deposits: Pausable<Environment>,
+                        withdrawals: Pausable<Environment, ManualKey<123>>,
+                    }
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, when_not_paused)]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            },
+            "ink! messages flagged with `when_not_paused` require exactly one \
+            `Pausable` field in the ink! storage struct",
+        );
+    }
 }
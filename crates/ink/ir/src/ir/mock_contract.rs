@@ -0,0 +1,133 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    ast,
+    ir::config::Environment,
+    utils::duplicate_config_err,
+};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::parse::{
+    Parse,
+    ParseStream,
+};
+
+/// The input of the `mock_contract!` macro.
+///
+/// # Note
+///
+/// This is mainly used for analysis and codegen of the `mock_contract!` macro.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MockContractMacro {
+    /// The path to the mocked ink! trait definition.
+    trait_path: syn::Path,
+    /// The environmental types definition of the contract mock.
+    env: Environment,
+}
+
+impl MockContractMacro {
+    /// Returns the path to the mocked ink! trait definition.
+    pub fn trait_path(&self) -> &syn::Path {
+        &self.trait_path
+    }
+
+    /// Returns the environmental types definition of the contract mock.
+    pub fn env(&self) -> &syn::Path {
+        &self.env.path
+    }
+}
+
+impl TryFrom<TokenStream2> for MockContractMacro {
+    type Error = syn::Error;
+
+    fn try_from(input: TokenStream2) -> Result<Self, Self::Error> {
+        syn::parse2::<Self>(input)
+    }
+}
+
+impl Parse for MockContractMacro {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let trait_path = input.parse::<syn::Path>()?;
+        let mut env: Option<(Environment, ast::MetaNameValue)> = None;
+        if input.parse::<Option<syn::Token![,]>>()?.is_some() {
+            for arg in input.parse::<ast::AttributeArgs>()? {
+                if arg.name.is_ident("env") {
+                    if let Some((_, ast)) = env {
+                        return Err(duplicate_config_err(ast, arg, "env", "mock_contract"))
+                    }
+                    if let ast::PathOrLit::Path(path) = &arg.value {
+                        env = Some((Environment { path: path.clone() }, arg))
+                    } else {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected a path for `env` ink! configuration argument",
+                        ))
+                    }
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "encountered unknown or unsupported ink! configuration argument",
+                    ))
+                }
+            }
+        }
+        Ok(Self {
+            trait_path,
+            env: env.map(|(value, _)| value).unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trait_path_works() {
+        let mock = MockContractMacro::try_from(quote::quote! { erc20::Erc20 }).unwrap();
+        assert_eq!(mock.trait_path(), &syn::parse_quote! { erc20::Erc20 });
+        assert_eq!(mock.env(), &Environment::default().path);
+    }
+
+    #[test]
+    fn env_works() {
+        let mock =
+            MockContractMacro::try_from(quote::quote! { Erc20, env = MyEnvironment })
+                .unwrap();
+        assert_eq!(mock.trait_path(), &syn::parse_quote! { Erc20 });
+        assert_eq!(mock.env(), &syn::parse_quote! { MyEnvironment });
+    }
+
+    #[test]
+    fn duplicate_env_fails() {
+        let error = MockContractMacro::try_from(
+            quote::quote! { Erc20, env = MyEnvironment, env = MyEnvironment },
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "encountered duplicate ink! mock_contract `env` configuration argument"
+        );
+    }
+
+    #[test]
+    fn unknown_argument_fails() {
+        let error = MockContractMacro::try_from(quote::quote! { Erc20, unknown = true })
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "encountered unknown or unsupported ink! configuration argument"
+        );
+    }
+}
@@ -16,6 +16,7 @@
 
 mod attrs;
 mod blake2;
+mod chain_config;
 mod chain_extension;
 mod config;
 mod contract;
@@ -24,9 +25,12 @@ mod ink_test;
 mod item;
 mod item_impl;
 mod item_mod;
+mod mock_contract;
 mod selector;
+mod shared_item;
 mod storage_item;
 mod trait_def;
+mod upgrade_guard;
 pub mod utils;
 
 /// Marker types and definitions.
@@ -53,13 +57,16 @@ use self::attrs::{
 };
 pub use self::{
     attrs::{
+        ArgDefault,
         IsDocAttribute,
+        MessageClass,
         Namespace,
     },
     blake2::{
         blake2b_256,
         Blake2x256Macro,
     },
+    chain_config::ChainConfig,
     chain_extension::{
         ChainExtension,
         ChainExtensionMethod,
@@ -81,6 +88,7 @@ pub use self::{
         Constructor,
         ImplItem,
         InputsIter,
+        Invariant,
         ItemImpl,
         IterConstructors,
         IterMessages,
@@ -93,11 +101,13 @@ pub use self::{
         IterEvents,
         IterItemImpls,
     },
+    mock_contract::MockContractMacro,
     selector::{
         Selector,
         SelectorMacro,
         TraitPrefix,
     },
+    shared_item::SharedItem,
     storage_item::StorageItem,
     trait_def::{
         InkItemTrait,
@@ -106,4 +116,5 @@ pub use self::{
         InkTraitMessage,
         IterInkTraitItems,
     },
+    upgrade_guard::UpgradeGuard,
 };
@@ -0,0 +1,164 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::TokenStream as TokenStream2;
+
+/// A checked ink! shared item.
+///
+/// Shared items are types such as errors or data structs which are defined once in a
+/// types-only crate and used by several ink! smart contracts.
+pub struct SharedItem {
+    ast: syn::DeriveInput,
+}
+
+impl SharedItem {
+    /// Returns `Ok` if the input matches all requirements for an ink! shared item.
+    pub fn new(config: TokenStream2, item: TokenStream2) -> Result<Self, syn::Error> {
+        if !config.is_empty() {
+            return Err(format_err_spanned!(
+                config,
+                "unexpected attribute input for ink! shared item"
+            ))
+        }
+        let ast = syn::parse2::<syn::DeriveInput>(item)?;
+        if let syn::Data::Union(union_item) = &ast.data {
+            return Err(format_err_spanned!(
+                union_item.union_token,
+                "ink! shared items must be structs or enums"
+            ))
+        }
+        for attr in &ast.attrs {
+            if is_shared_attribute(&attr.path) {
+                return Err(format_err_spanned!(
+                    attr,
+                    "only one `ink::shared` is allowed",
+                ))
+            }
+        }
+        Ok(Self { ast })
+    }
+
+    /// Returns AST.
+    pub fn ast(&self) -> &syn::DeriveInput {
+        &self.ast
+    }
+
+    /// Returns the identifier of the shared item.
+    pub fn ident(&self) -> &syn::Ident {
+        &self.ast.ident
+    }
+}
+
+/// Returns `true` if `path` is the path of the `#[ink::shared]` attribute, i.e.
+/// `shared` or `ink::shared`.
+fn is_shared_attribute(path: &syn::Path) -> bool {
+    let segments = &path.segments;
+    path.is_ident("shared")
+        || (segments.len() == 2
+            && segments[0].ident == "ink"
+            && segments[1].ident == "shared")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_try_from_fails(config: TokenStream2, item: TokenStream2, expected: &str) {
+        assert_eq!(
+            SharedItem::new(config, item)
+                .map(|_| ())
+                .map_err(|err| err.to_string()),
+            Err(expected.to_string()),
+        )
+    }
+
+    #[test]
+    fn struct_and_enum_work() {
+        assert!(SharedItem::new(
+            quote::quote! {},
+            quote::quote! {
+                pub struct Data {
+                    value: u32,
+                }
+            }
+        )
+        .is_ok());
+        assert!(SharedItem::new(
+            quote::quote! {},
+            quote::quote! {
+                pub enum Error {
+                    InsufficientBalance,
+                }
+            }
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn union_fails() {
+        assert_try_from_fails(
+            quote::quote! {},
+            quote::quote! {
+                pub union Data {
+                    value: u32,
+                }
+            },
+            "ink! shared items must be structs or enums",
+        )
+    }
+
+    #[test]
+    fn config_fails() {
+        assert_try_from_fails(
+            quote::quote! { derive = true },
+            quote::quote! {
+                pub struct Data;
+            },
+            "unexpected attribute input for ink! shared item",
+        )
+    }
+
+    #[test]
+    fn duplicate_attribute_fails() {
+        assert_try_from_fails(
+            quote::quote! {},
+            quote::quote! {
+                #[ink::shared]
+                pub struct Data;
+            },
+            "only one `ink::shared` is allowed",
+        );
+        assert_try_from_fails(
+            quote::quote! {},
+            quote::quote! {
+                #[shared]
+                pub struct Data;
+            },
+            "only one `ink::shared` is allowed",
+        )
+    }
+
+    #[test]
+    fn other_attributes_containing_shared_work() {
+        assert!(SharedItem::new(
+            quote::quote! {},
+            quote::quote! {
+                #[shared_with(other)]
+                #[cfg_attr(feature = "std", unshared)]
+                pub struct Data;
+            }
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::{
+    Ident,
+    TokenStream as TokenStream2,
+};
+use syn::spanned::Spanned as _;
+
+/// A checked ink! upgrade guard.
+///
+/// An upgrade guard is an ink! message which sets the code hash of the contract
+/// to its first input after checking that the contract is paused and that its
+/// second input matches the storage fingerprint of the contract.
+pub struct UpgradeGuard {
+    /// The guarded ink! message.
+    item: syn::ImplItemMethod,
+    /// The identifier of the input holding the new code hash.
+    code_hash: Ident,
+    /// The identifier of the input holding the expected storage fingerprint.
+    fingerprint: Ident,
+}
+
+impl UpgradeGuard {
+    /// Returns `Ok` if the input matches all requirements for an ink! upgrade guard.
+    pub fn new(config: TokenStream2, item: TokenStream2) -> Result<Self, syn::Error> {
+        if !config.is_empty() {
+            return Err(format_err_spanned!(
+                config,
+                "unexpected attribute input for ink! upgrade guard"
+            ))
+        }
+        let item = syn::parse2::<syn::ImplItemMethod>(item)?;
+        let mut inputs = item.sig.inputs.iter();
+        match inputs.next() {
+            Some(syn::FnArg::Receiver(receiver))
+                if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+            _ => {
+                return Err(format_err_spanned!(
+                    item.sig,
+                    "ink! upgrade guards must have a `&mut self` receiver"
+                ))
+            }
+        }
+        let (code_hash, fingerprint) = match (
+            inputs.next().and_then(Self::input_ident),
+            inputs.next().and_then(Self::input_ident),
+            inputs.next(),
+        ) {
+            (Some(code_hash), Some(fingerprint), None) => (code_hash, fingerprint),
+            _ => {
+                return Err(format_err!(
+                    item.sig.inputs.span(),
+                    "ink! upgrade guards must have exactly two inputs: \
+                    the new code hash and the expected storage fingerprint"
+                ))
+            }
+        };
+        if let syn::ReturnType::Default = item.sig.output {
+            return Err(format_err_spanned!(
+                item.sig,
+                "ink! upgrade guards must return a `Result<(), E>` where \
+                `E: From<ink::upgrade::UpgradeError>`"
+            ))
+        }
+        Ok(Self {
+            item,
+            code_hash,
+            fingerprint,
+        })
+    }
+
+    /// Returns the identifier of the given input if it is a plain identifier pattern.
+    fn input_ident(input: &syn::FnArg) -> Option<Ident> {
+        match input {
+            syn::FnArg::Typed(pat_type) => {
+                match &*pat_type.pat {
+                    syn::Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+                    _ => None,
+                }
+            }
+            syn::FnArg::Receiver(_) => None,
+        }
+    }
+
+    /// Returns the guarded ink! message.
+    pub fn item(&self) -> &syn::ImplItemMethod {
+        &self.item
+    }
+
+    /// Returns the identifier of the input holding the new code hash.
+    pub fn code_hash(&self) -> &Ident {
+        &self.code_hash
+    }
+
+    /// Returns the identifier of the input holding the expected storage fingerprint.
+    pub fn fingerprint(&self) -> &Ident {
+        &self.fingerprint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_try_from_fails(config: TokenStream2, item: TokenStream2, expected: &str) {
+        assert_eq!(
+            UpgradeGuard::new(config, item)
+                .map(|_| ())
+                .map_err(|err| err.to_string()),
+            Err(expected.to_string()),
+        )
+    }
+
+    #[test]
+    fn new_works() {
+        let guard = UpgradeGuard::new(
+            quote::quote! {},
+            quote::quote! {
+                pub fn upgrade(
+                    &mut self,
+                    code_hash: Hash,
+                    fingerprint: [u8; 32],
+                ) -> Result<(), Error> {
+                    Ok(())
+                }
+            },
+        )
+        .unwrap();
+        assert_eq!(guard.code_hash(), "code_hash");
+        assert_eq!(guard.fingerprint(), "fingerprint");
+    }
+
+    #[test]
+    fn config_fails() {
+        assert_try_from_fails(
+            quote::quote! { paused = true },
+            quote::quote! {
+                pub fn upgrade(&mut self, a: Hash, b: [u8; 32]) -> Result<(), Error> {}
+            },
+            "unexpected attribute input for ink! upgrade guard",
+        )
+    }
+
+    #[test]
+    fn invalid_receiver_fails() {
+        for item in [
+            quote::quote! {
+                pub fn upgrade(&self, a: Hash, b: [u8; 32]) -> Result<(), Error> {}
+            },
+            quote::quote! {
+                pub fn upgrade(a: Hash, b: [u8; 32]) -> Result<(), Error> {}
+            },
+        ] {
+            assert_try_from_fails(
+                quote::quote! {},
+                item,
+                "ink! upgrade guards must have a `&mut self` receiver",
+            )
+        }
+    }
+
+    #[test]
+    fn invalid_inputs_fails() {
+        for item in [
+            quote::quote! {
+                pub fn upgrade(&mut self, a: Hash) -> Result<(), Error> {}
+            },
+            quote::quote! {
+                pub fn upgrade(&mut self, a: Hash, b: [u8; 32], c: u8) -> Result<(), Error> {}
+            },
+            quote::quote! {
+                pub fn upgrade(&mut self, a: Hash, (b, c): ([u8; 32], u8)) -> Result<(), Error> {}
+            },
+        ] {
+            assert_try_from_fails(
+                quote::quote! {},
+                item,
+                "ink! upgrade guards must have exactly two inputs: \
+                the new code hash and the expected storage fingerprint",
+            )
+        }
+    }
+
+    #[test]
+    fn missing_output_fails() {
+        assert_try_from_fails(
+            quote::quote! {},
+            quote::quote! {
+                pub fn upgrade(&mut self, a: Hash, b: [u8; 32]) {}
+            },
+            "ink! upgrade guards must return a `Result<(), E>` where \
+            `E: From<ink::upgrade::UpgradeError>`",
+        )
+    }
+}
@@ -26,6 +26,9 @@
 //! a so-called Rust inline module (`mod my_module { ... items ... }`).
 //! Therefore all ink! definition are found and accessed using the
 //! [`ItemMod`](`crate::ir::ItemMod`) data structure.
+//!
+//! Third-party tools which only need to inspect the structure of an ink! smart
+//! contract should prefer the stable [`model`](`crate::model`) over the ink! IR.
 
 #![doc(
     html_logo_url = "https://use.ink/img/crate-docs/logo.png",
@@ -38,16 +41,19 @@ mod error;
 pub mod ast;
 mod ir;
 mod literal;
+pub mod model;
 
 pub use self::{
     ir::{
         blake2b_256,
         marker,
         utils,
+        ArgDefault,
         Blake2x256Macro,
         Callable,
         CallableKind,
         CallableWithSelector,
+        ChainConfig,
         ChainExtension,
         ChainExtensionMethod,
         Config,
@@ -63,6 +69,7 @@ pub use self::{
         InkTraitItem,
         InkTraitMessage,
         InputsIter,
+        Invariant,
         IsDocAttribute,
         Item,
         ItemImpl,
@@ -73,12 +80,16 @@ pub use self::{
         IterItemImpls,
         IterMessages,
         Message,
+        MessageClass,
+        MockContractMacro,
         Namespace,
         Receiver,
         Selector,
         SelectorMacro,
+        SharedItem,
         Storage,
         StorageItem,
+        UpgradeGuard,
         Visibility,
     },
     literal::HexLiteral,
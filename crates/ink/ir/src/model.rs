@@ -0,0 +1,710 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A stable model of parsed ink! smart contracts for third-party tools.
+//!
+//! In contrast to the ink! IR this model does not expose any `syn` data structures
+//! and already applies the semantics of the ink! attributes, e.g. it resolves the
+//! selectors, payability and topics of the ink! definitions. This allows tools like
+//! analyzers or documentation generators to inspect the structure of an ink! smart
+//! contract without interpreting its Rust syntax themselves.
+//!
+//! All types only expose getters, so that new information can be added to the
+//! model without breaking its users.
+//!
+//! # Example
+//!
+//! ```
+//! use ink_ir::model::Contract;
+//! use quote::quote;
+//!
+//! let contract = Contract::parse(
+//!     quote! {},
+//!     quote! {
+//!         mod flipper {
+//!             #[ink(storage)]
+//!             pub struct Flipper {
+//!                 value: bool,
+//!             }
+//!
+//!             impl Flipper {
+//!                 #[ink(constructor)]
+//!                 pub fn new() -> Self {
+//!                     Self { value: false }
+//!                 }
+//!
+//!                 /// Flips the current value.
+//!                 #[ink(message, selector = 0xCAFEBABE)]
+//!                 pub fn flip(&mut self) {
+//!                     self.value = !self.value;
+//!                 }
+//!             }
+//!         }
+//!     },
+//! )
+//! .unwrap();
+//! assert_eq!(contract.name(), "flipper");
+//! assert_eq!(contract.storage().fields()[0].ty(), "bool");
+//! let flip = &contract.messages()[0];
+//! assert_eq!(flip.label(), "flip");
+//! assert_eq!(flip.selector(), Some([0xCA, 0xFE, 0xBA, 0xBE]));
+//! assert!(flip.mutates());
+//! assert_eq!(flip.docs(), [" Flips the current value."]);
+//! ```
+
+use crate::{
+    ir,
+    ir::{
+        Callable as _,
+        IsDocAttribute as _,
+    },
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens as _;
+
+/// A parsed ink! smart contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contract {
+    name: String,
+    docs: Vec<String>,
+    storage: Storage,
+    constructors: Vec<Constructor>,
+    messages: Vec<Message>,
+    events: Vec<Event>,
+}
+
+impl Contract {
+    /// Parses the ink! smart contract from the token streams of the configuration
+    /// and the inline module given to `#[ink::contract(config)]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as the `#[ink::contract]` macro if the ink! smart
+    /// contract is invalid.
+    pub fn parse(
+        ink_config: TokenStream2,
+        ink_module: TokenStream2,
+    ) -> Result<Self, syn::Error> {
+        ir::Contract::new(ink_config, ink_module).map(|contract| Self::from(&contract))
+    }
+
+    /// Returns the name of the ink! module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the documentation of the ink! module.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+
+    /// Returns the `#[ink(storage)]` struct.
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    /// Returns all ink! constructors in order of their definition.
+    pub fn constructors(&self) -> &[Constructor] {
+        &self.constructors
+    }
+
+    /// Returns all ink! messages in order of their definition.
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// Returns all ink! events in order of their definition.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+impl From<&ir::Contract> for Contract {
+    fn from(contract: &ir::Contract) -> Self {
+        let module = contract.module();
+        let constructors = module
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_constructors())
+            .map(|constructor| {
+                Constructor {
+                    label: label_of(constructor.item_impl(), constructor.ident()),
+                    selector: selector_of(&constructor),
+                    payable: constructor.is_payable(),
+                    inputs: inputs_of(constructor.callable()),
+                    docs: docs_of(constructor.attrs()),
+                }
+            })
+            .collect();
+        let messages = module
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .map(|message| {
+                let callable = message.callable();
+                Message {
+                    label: label_of(message.item_impl(), message.ident()),
+                    selector: selector_of(&message),
+                    alias_selector: callable
+                        .alias_selector()
+                        .map(|selector| selector.to_bytes()),
+                    mutates: callable.receiver().is_ref_mut(),
+                    payable: callable.is_payable(),
+                    inputs: inputs_of(callable),
+                    output: callable.output().map(type_to_string),
+                    class: callable.class().map(|class| class.as_str().to_string()),
+                    deprecated: callable.deprecated().map(ToString::to_string),
+                    docs: docs_of(callable.attrs()),
+                }
+            })
+            .collect();
+        let events = module
+            .events()
+            .map(|event| {
+                Event {
+                    name: event.ident().to_string(),
+                    anonymous: event.anonymous,
+                    fields: event
+                        .fields()
+                        .map(|field| {
+                            EventField {
+                                name: field.ident().map(ToString::to_string),
+                                ty: type_to_string(field.ty()),
+                                topic: field.is_topic,
+                                docs: docs_of(&field.attrs()),
+                            }
+                        })
+                        .collect(),
+                    docs: docs_of(event.attrs()),
+                }
+            })
+            .collect();
+        let storage = module.storage();
+        Self {
+            name: module.ident().to_string(),
+            docs: docs_of(module.attrs()),
+            storage: Storage {
+                name: storage.ident().to_string(),
+                fields: storage
+                    .fields()
+                    .map(|field| {
+                        Field {
+                            name: field.ident.as_ref().map(ToString::to_string),
+                            ty: type_to_string(&field.ty),
+                            docs: docs_of(&field.attrs),
+                        }
+                    })
+                    .collect(),
+                docs: docs_of(storage.attrs()),
+            },
+            constructors,
+            messages,
+            events,
+        }
+    }
+}
+
+/// The `#[ink(storage)]` struct of an ink! smart contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Storage {
+    name: String,
+    fields: Vec<Field>,
+    docs: Vec<String>,
+}
+
+impl Storage {
+    /// Returns the name of the storage struct.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the fields of the storage struct in order of their definition.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Returns the documentation of the storage struct.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// A field of the `#[ink(storage)]` struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    name: Option<String>,
+    ty: String,
+    docs: Vec<String>,
+}
+
+impl Field {
+    /// Returns the name of the field or `None` for fields of tuple structs.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the type of the field as written in the source without whitespace.
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// Returns the documentation of the field.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// An ink! constructor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constructor {
+    label: String,
+    selector: Option<[u8; 4]>,
+    payable: bool,
+    inputs: Vec<Argument>,
+    docs: Vec<String>,
+}
+
+impl Constructor {
+    /// Returns the label of the constructor.
+    ///
+    /// Constructors of trait implementations are prefixed with the trait name,
+    /// e.g. `MyTrait::new`, just like in the contract metadata.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the selector of the constructor.
+    ///
+    /// Returns `None` for a wildcard selector.
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        self.selector
+    }
+
+    /// Returns `true` if the constructor accepts value transfers.
+    pub fn payable(&self) -> bool {
+        self.payable
+    }
+
+    /// Returns the inputs of the constructor in order.
+    pub fn inputs(&self) -> &[Argument] {
+        &self.inputs
+    }
+
+    /// Returns the documentation of the constructor.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// An ink! message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    label: String,
+    selector: Option<[u8; 4]>,
+    alias_selector: Option<[u8; 4]>,
+    mutates: bool,
+    payable: bool,
+    inputs: Vec<Argument>,
+    output: Option<String>,
+    class: Option<String>,
+    deprecated: Option<String>,
+    docs: Vec<String>,
+}
+
+impl Message {
+    /// Returns the label of the message.
+    ///
+    /// Messages of trait implementations are prefixed with the trait name,
+    /// e.g. `MyTrait::get`, just like in the contract metadata.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the selector of the message.
+    ///
+    /// Returns `None` for a wildcard selector and for messages of trait
+    /// implementations, since their selectors are determined by the ink! trait
+    /// definition.
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        self.selector
+    }
+
+    /// Returns the alias selector given via `#[ink(alias_selector = S:u32)]` if any.
+    pub fn alias_selector(&self) -> Option<[u8; 4]> {
+        self.alias_selector
+    }
+
+    /// Returns `true` if the message may mutate the contract storage.
+    pub fn mutates(&self) -> bool {
+        self.mutates
+    }
+
+    /// Returns `true` if the message accepts value transfers.
+    pub fn payable(&self) -> bool {
+        self.payable
+    }
+
+    /// Returns the inputs of the message in order.
+    pub fn inputs(&self) -> &[Argument] {
+        &self.inputs
+    }
+
+    /// Returns the output type of the message without whitespace if any.
+    pub fn output(&self) -> Option<&str> {
+        self.output.as_deref()
+    }
+
+    /// Returns the class given via `#[ink(class = "class")]` if any.
+    pub fn class(&self) -> Option<&str> {
+        self.class.as_deref()
+    }
+
+    /// Returns the deprecation note given via `#[ink(deprecated = "note")]` if any.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Returns the documentation of the message.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// An input of an ink! constructor or message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Argument {
+    name: String,
+    ty: String,
+}
+
+impl Argument {
+    /// Returns the name of the input.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the input as written in the source without whitespace.
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+}
+
+/// An ink! event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    name: String,
+    anonymous: bool,
+    fields: Vec<EventField>,
+    docs: Vec<String>,
+}
+
+impl Event {
+    /// Returns the name of the event struct.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the event is flagged with `#[ink(anonymous)]`.
+    pub fn anonymous(&self) -> bool {
+        self.anonymous
+    }
+
+    /// Returns the fields of the event in order of their definition.
+    pub fn fields(&self) -> &[EventField] {
+        &self.fields
+    }
+
+    /// Returns the documentation of the event.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// A field of an ink! event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventField {
+    name: Option<String>,
+    ty: String,
+    topic: bool,
+    docs: Vec<String>,
+}
+
+impl EventField {
+    /// Returns the name of the field or `None` for fields of tuple structs.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the type of the field as written in the source without whitespace.
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// Returns `true` if the field is flagged with `#[ink(topic)]`.
+    pub fn topic(&self) -> bool {
+        self.topic
+    }
+
+    /// Returns the documentation of the field.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// Returns the label of an ink! constructor or message as used in the metadata.
+fn label_of(item_impl: &ir::ItemImpl, ident: &syn::Ident) -> String {
+    match item_impl.trait_ident() {
+        Some(trait_ident) => format!("{}::{}", trait_ident, ident),
+        None => ident.to_string(),
+    }
+}
+
+/// Returns the selector of an ink! constructor or message if it is known.
+fn selector_of<C>(callable: &ir::CallableWithSelector<C>) -> Option<[u8; 4]>
+where
+    C: ir::Callable,
+{
+    if callable.has_wildcard_selector() || callable.item_impl().trait_path().is_some() {
+        return None
+    }
+    Some(callable.composed_selector().to_bytes())
+}
+
+/// Returns the inputs of an ink! constructor or message.
+fn inputs_of<C>(callable: &C) -> Vec<Argument>
+where
+    C: ir::Callable,
+{
+    callable
+        .inputs()
+        .map(|input| {
+            Argument {
+                name: input.pat.to_token_stream().to_string(),
+                ty: type_to_string(&input.ty),
+            }
+        })
+        .collect()
+}
+
+/// Returns the documentation of the given attributes.
+fn docs_of(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| attr.extract_docs())
+        .collect()
+}
+
+/// Returns the type as written in the source without whitespace.
+fn type_to_string(ty: &syn::Type) -> String {
+    ty.to_token_stream().to_string().replace(' ', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    fn parse(ink_module: TokenStream2) -> Contract {
+        Contract::parse(quote! {}, ink_module).unwrap()
+    }
+
+    #[test]
+    fn storage_works() {
+        let contract = parse(quote! {
+            /// The ERC-20 contract.
+            mod erc20 {
+                /// The storage.
+                #[ink(storage)]
+                pub struct Erc20 {
+                    /// The total supply.
+                    total_supply: Balance,
+                    balances: ink::storage::Mapping<AccountId, Balance>,
+                }
+
+                impl Erc20 {
+                    #[ink(constructor)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message)]
+                    pub fn total_supply(&self) -> Balance {}
+                }
+            }
+        });
+        assert_eq!(contract.name(), "erc20");
+        assert_eq!(contract.docs(), [" The ERC-20 contract."]);
+        let storage = contract.storage();
+        assert_eq!(storage.name(), "Erc20");
+        assert_eq!(storage.docs(), [" The storage."]);
+        let fields = storage
+            .fields()
+            .iter()
+            .map(|field| (field.name(), field.ty(), field.docs().len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                (Some("total_supply"), "Balance", 1),
+                (
+                    Some("balances"),
+                    "ink::storage::Mapping<AccountId,Balance>",
+                    0
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn callables_work() {
+        let contract = parse(quote! {
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyStorage {}
+
+                impl MyStorage {
+                    #[ink(constructor, payable, selector = 1)]
+                    pub fn new(init_value: u32) -> Self {}
+
+                    #[ink(message, class = "admin", alias_selector = 0xDEADBEEF)]
+                    pub fn set(&mut self, key: u8, value: Option<u32>) {}
+
+                    #[ink(message, deprecated = "use `set`")]
+                    pub fn get(&self, key: u8) -> Option<u32> {}
+
+                    #[ink(message, payable, selector = _)]
+                    pub fn fallback(&mut self) {}
+                }
+
+                impl MyTrait for MyStorage {
+                    #[ink(message)]
+                    fn get(&self) -> bool {}
+                }
+            }
+        });
+        let new = &contract.constructors()[0];
+        assert_eq!(new.label(), "new");
+        assert_eq!(new.selector(), Some([0, 0, 0, 1]));
+        assert!(new.payable());
+        assert_eq!(new.inputs()[0].name(), "init_value");
+        assert_eq!(new.inputs()[0].ty(), "u32");
+
+        let messages = contract.messages();
+        let labels = messages.iter().map(Message::label).collect::<Vec<_>>();
+        assert_eq!(labels, ["set", "get", "fallback", "MyTrait::get"]);
+
+        let set = &messages[0];
+        assert_eq!(
+            set.selector(),
+            Some(ir::Selector::compute(b"set").to_bytes())
+        );
+        assert_eq!(set.alias_selector(), Some([0xDE, 0xAD, 0xBE, 0xEF]));
+        assert!(set.mutates());
+        assert!(!set.payable());
+        let inputs = set
+            .inputs()
+            .iter()
+            .map(|input| (input.name(), input.ty()))
+            .collect::<Vec<_>>();
+        assert_eq!(inputs, [("key", "u8"), ("value", "Option<u32>")]);
+        assert_eq!(set.output(), None);
+        assert_eq!(set.class(), Some("admin"));
+        assert_eq!(set.deprecated(), None);
+
+        let get = &messages[1];
+        assert!(!get.mutates());
+        assert_eq!(get.output(), Some("Option<u32>"));
+        assert_eq!(get.class(), None);
+        assert_eq!(get.deprecated(), Some("use `set`"));
+
+        let fallback = &messages[2];
+        assert_eq!(fallback.selector(), None);
+        assert!(fallback.payable());
+
+        let trait_get = &messages[3];
+        assert_eq!(trait_get.selector(), None);
+        assert_eq!(trait_get.output(), Some("bool"));
+    }
+
+    #[test]
+    fn events_work() {
+        let contract = parse(quote! {
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyStorage {}
+
+                /// Emitted on transfers.
+                #[ink(event)]
+                pub struct Transferred {
+                    #[ink(topic)]
+                    from: Option<AccountId>,
+                    /// The transferred value.
+                    value: Balance,
+                }
+
+                #[ink(event, anonymous)]
+                pub struct Flipped(bool);
+
+                impl MyStorage {
+                    #[ink(constructor)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message)]
+                    pub fn flip(&mut self) {}
+                }
+            }
+        });
+        let events = contract.events();
+        assert_eq!(events.len(), 2);
+
+        let transferred = &events[0];
+        assert_eq!(transferred.name(), "Transferred");
+        assert!(!transferred.anonymous());
+        assert_eq!(transferred.docs(), [" Emitted on transfers."]);
+        let fields = transferred
+            .fields()
+            .iter()
+            .map(|field| (field.name(), field.ty(), field.topic()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                (Some("from"), "Option<AccountId>", true),
+                (Some("value"), "Balance", false),
+            ]
+        );
+        assert_eq!(transferred.fields()[1].docs(), [" The transferred value."]);
+
+        let flipped = &events[1];
+        assert!(flipped.anonymous());
+        assert_eq!(flipped.fields()[0].name(), None);
+        assert_eq!(flipped.fields()[0].ty(), "bool");
+    }
+
+    #[test]
+    fn parse_fails_for_invalid_contract() {
+        let error = Contract::parse(
+            quote! {},
+            quote! {
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(message)]
+                        pub fn flip(&mut self) {}
+                    }
+                }
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "missing ink! constructor");
+    }
+}
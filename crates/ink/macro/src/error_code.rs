@@ -0,0 +1,313 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_ir::IsDocAttribute as _;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+
+/// The `#[error_code(N)]` or `#[error_code(N, from)]` attribute of a variant.
+struct VariantCode {
+    /// The stable error code of the variant.
+    code: u32,
+    /// Whether the variant nests the error type of its only field.
+    from: bool,
+}
+
+/// Returns the parsed `#[error_code(..)]` attribute of the variant.
+///
+/// # Errors
+///
+/// - If the variant has no or more than one `#[error_code(..)]` attribute.
+/// - If the attribute is not of the form `#[error_code(N)]` or
+///   `#[error_code(N, from)]` with an integer literal `N` that fits into a `u32`.
+/// - If a variant flagged with `from` does not have exactly one field.
+fn variant_code(variant: &syn::Variant) -> Result<VariantCode, syn::Error> {
+    let mut attrs = variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("error_code"));
+    let attr = attrs.next().ok_or_else(|| {
+        syn::Error::new(
+            variant.ident.span(),
+            format!(
+                "missing `#[error_code(N)]` attribute for variant `{}`",
+                variant.ident
+            ),
+        )
+    })?;
+    if let Some(duplicate) = attrs.next() {
+        return Err(syn::Error::new(
+            duplicate.span(),
+            "encountered duplicate `#[error_code(..)]` attribute",
+        ))
+    }
+    let invalid = || {
+        syn::Error::new(
+            attr.span(),
+            "expected `#[error_code(N)]` or `#[error_code(N, from)]`",
+        )
+    };
+    let list = match attr.parse_meta()? {
+        syn::Meta::List(list) => list,
+        _ => return Err(invalid()),
+    };
+    let mut nested = list.nested.iter();
+    let code = match nested.next() {
+        Some(syn::NestedMeta::Lit(syn::Lit::Int(lit))) => lit.base10_parse::<u32>()?,
+        _ => return Err(invalid()),
+    };
+    let from = match nested.next() {
+        None => false,
+        Some(syn::NestedMeta::Meta(syn::Meta::Path(path))) if path.is_ident("from") => {
+            true
+        }
+        Some(_) => return Err(invalid()),
+    };
+    if nested.next().is_some() {
+        return Err(invalid())
+    }
+    if from && variant.fields.len() != 1 {
+        return Err(syn::Error::new(
+            variant.ident.span(),
+            "variants flagged with `from` must have exactly one field",
+        ))
+    }
+    Ok(VariantCode { code, from })
+}
+
+/// Returns the `From` implementation nesting the error type of the only field of
+/// `variant` into the derived error type.
+fn from_impl(s: &synstructure::Structure, variant: &syn::Variant) -> TokenStream2 {
+    let variant_ident = &variant.ident;
+    let field = variant
+        .fields
+        .iter()
+        .next()
+        .expect("variants flagged with `from` have exactly one field");
+    let field_ty = &field.ty;
+    let construct = match &field.ident {
+        Some(field_ident) => quote! { Self::#variant_ident { #field_ident: error } },
+        None => quote! { Self::#variant_ident(error) },
+    };
+    s.gen_impl(quote! {
+        gen impl ::core::convert::From<#field_ty> for @Self {
+            fn from(error: #field_ty) -> Self {
+                #construct
+            }
+        }
+    })
+}
+
+pub fn error_code_derive(mut s: synstructure::Structure) -> TokenStream2 {
+    s.bind_with(|_| synstructure::BindStyle::Ref)
+        .add_bounds(synstructure::AddBounds::None)
+        .underscore_const(true);
+    let variants = match &s.ast().data {
+        syn::Data::Enum(data) => data.variants.iter().cloned().collect::<Vec<_>>(),
+        _ => {
+            return syn::Error::new(
+                s.ast().span(),
+                "`ErrorCode` can only be derived for enums",
+            )
+            .to_compile_error()
+        }
+    };
+
+    let mut codes: Vec<VariantCode> = Vec::with_capacity(variants.len());
+    for variant in &variants {
+        let code = match variant_code(variant) {
+            Ok(code) => code,
+            Err(error) => return error.to_compile_error(),
+        };
+        if codes.iter().any(|other| other.code == code.code) {
+            return syn::Error::new(
+                variant.ident.span(),
+                format!(
+                    "encountered duplicate error code `{}` for variant `{}`",
+                    code.code, variant.ident
+                ),
+            )
+            .to_compile_error()
+        }
+        codes.push(code);
+    }
+
+    let mut index = 0;
+    let error_code = s.each_variant(|_| {
+        let code = codes[index].code;
+        index += 1;
+        quote! { #code }
+    });
+    let code_specs = variants.iter().zip(&codes).map(|(variant, code)| {
+        let label = variant.ident.to_string();
+        let value = code.code;
+        let docs = variant.attrs.iter().filter_map(|attr| attr.extract_docs());
+        let nested = match variant.fields.iter().next() {
+            Some(field) if code.from => {
+                let field_ty = &field.ty;
+                quote! {
+                    ::core::option::Option::Some(
+                        ::ink::metadata::TypeSpec::of_type::<#field_ty>()
+                    )
+                }
+            }
+            _ => quote! { ::core::option::Option::None },
+        };
+        quote! {
+            ::ink::metadata::ErrorCodeSpec::new(
+                #label,
+                #value,
+                #nested,
+                [ #( #docs ),* ],
+            )
+        }
+    });
+    let nested_specs = variants
+        .iter()
+        .zip(&codes)
+        .filter(|(_, code)| code.from)
+        .filter_map(|(variant, _)| variant.fields.iter().next())
+        .map(|field| {
+            let field_ty = &field.ty;
+            quote! {
+                __specs.extend(<#field_ty as ::ink::ErrorCodeMetadata>::error_specs());
+            }
+        });
+    let from_impls = variants
+        .iter()
+        .zip(&codes)
+        .filter(|(_, code)| code.from)
+        .map(|(variant, _)| from_impl(&s, variant));
+    let ident = &s.ast().ident;
+    let label = ident.to_string();
+
+    let error_code_impl = s.gen_impl(quote! {
+        gen impl ::ink::ErrorCode for @Self {
+            fn error_code(&self) -> ::core::primitive::u32 {
+                match self {
+                    #error_code
+                }
+            }
+        }
+    });
+    let metadata_impl = s.gen_impl(quote! {
+        gen impl ::ink::ErrorCodeMetadata for @Self {
+            fn error_specs() -> ::ink::prelude::vec::Vec<::ink::metadata::ErrorSpec> {
+                #[allow(unused_mut)]
+                let mut __specs = ::ink::prelude::vec![
+                    ::ink::metadata::ErrorSpec::new(
+                        #label,
+                        ::ink::metadata::TypeSpec::with_name_str::<Self>(#label),
+                    )
+                    .codes([ #( #code_specs ),* ])
+                    .done()
+                ];
+                #( #nested_specs )*
+                __specs
+            }
+        }
+    });
+    quote! {
+        #error_code_impl
+        #[cfg(feature = "std")]
+        #metadata_impl
+        #( #from_impls )*
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::error_code_derive;
+
+    fn derive_error(input: syn::DeriveInput) -> String {
+        let output = error_code_derive(synstructure::Structure::new(&input)).to_string();
+        assert!(
+            output.contains("compile_error"),
+            "expected an error: {}",
+            output
+        );
+        output
+    }
+
+    #[test]
+    fn missing_code_fails() {
+        let output = derive_error(syn::parse_quote! {
+            enum Error {
+                #[error_code(1)]
+                A,
+                B,
+            }
+        });
+        assert!(output.contains("missing `#[error_code(N)]` attribute for variant `B`"));
+    }
+
+    #[test]
+    fn duplicate_code_fails() {
+        let output = derive_error(syn::parse_quote! {
+            enum Error {
+                #[error_code(1)]
+                A,
+                #[error_code(1)]
+                B,
+            }
+        });
+        assert!(output.contains("duplicate error code `1` for variant `B`"));
+    }
+
+    #[test]
+    fn invalid_attribute_fails() {
+        for input in [
+            syn::parse_quote! {
+                enum Error {
+                    #[error_code = 1]
+                    A,
+                }
+            },
+            syn::parse_quote! {
+                enum Error {
+                    #[error_code(1, into)]
+                    A,
+                }
+            },
+            syn::parse_quote! {
+                enum Error {
+                    #[error_code(-1)]
+                    A,
+                }
+            },
+        ] {
+            derive_error(input);
+        }
+    }
+
+    #[test]
+    fn from_requires_single_field() {
+        let output = derive_error(syn::parse_quote! {
+            enum Error {
+                #[error_code(1, from)]
+                A(u8, u8),
+            }
+        });
+        assert!(output.contains("must have exactly one field"));
+    }
+
+    #[test]
+    fn struct_fails() {
+        let output = derive_error(syn::parse_quote! {
+            struct Error;
+        });
+        assert!(output.contains("can only be derived for enums"));
+    }
+}
@@ -20,13 +20,18 @@
 extern crate proc_macro;
 
 mod blake2b;
+mod chain_config;
 mod chain_extension;
 mod contract;
+mod error_code;
 mod ink_test;
+mod mock_contract;
 mod selector;
+mod shared;
 mod storage;
 mod storage_item;
 mod trait_def;
+mod upgrade_guard;
 
 use proc_macro::TokenStream;
 
@@ -91,6 +96,28 @@ pub fn selector_id(input: TokenStream) -> TokenStream {
 ///     [50, 77, 207, 2],
 /// );
 /// ```
+///
+/// The selector of a message defined in an ink! trait definition is computed from
+/// `"TraitName::message_name"`, or `"namespace::TraitName::message_name"` if the
+/// trait definition has a `namespace`. Since the macro expands into a literal it
+/// can be used in constants and in match arms, e.g. to route calls by selector:
+///
+/// ```
+/// # use ink_macro::selector_bytes;
+/// const TRANSFER: [u8; 4] = selector_bytes!("PSP22::transfer");
+///
+/// fn route(selector: [u8; 4]) -> &'static str {
+///     match selector {
+///         TRANSFER => "transfer",
+///         selector_bytes!("PSP22::approve") => "approve",
+///         _ => "unknown",
+///     }
+/// }
+///
+/// assert_eq!(route(TRANSFER), "transfer");
+/// assert_eq!(route(selector_bytes!("PSP22::approve")), "approve");
+/// assert_eq!(route(selector_bytes!("approve")), "unknown");
+/// ```
 #[proc_macro]
 pub fn selector_bytes(input: TokenStream) -> TokenStream {
     selector::generate_selector_bytes(input.into()).into()
@@ -206,6 +233,130 @@ pub fn selector_bytes(input: TokenStream) -> TokenStream {
 ///
 ///     **Default value:** `DefaultEnvironment` defined in `ink_env` crate.
 ///
+/// - `dispatch_table: bool`
+///
+///     Tells the ink! code generator to emit a `DISPATCH_TABLE` constant into the
+///     contract module. It lists the selector, label, mutability and payability of
+///     every ink! message so that audit tooling and fuzzers can enumerate the
+///     callable surface of the contract without parsing its metadata.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(dispatch_table = true)]
+///     mod my_contract {
+///         # #[ink(storage)]
+///         # pub struct MyStorage;
+///         # impl MyStorage {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { MyStorage {} }
+///         #     #[ink(message, payable)]
+///         #     pub fn message(&mut self) {}
+///         # }
+///         // ...
+///     }
+///
+///     let (selector, label, mutates, payable) = my_contract::DISPATCH_TABLE[0];
+///     assert_eq!(selector, ink::selector_bytes!("message"));
+///     assert_eq!(label, "message");
+///     assert!(mutates);
+///     assert!(payable);
+///     ```
+///
+///     **Default value:** `false`.
+///
+/// - `size_report: bool`
+///
+///     Tells the ink! code generator to mark all ink! constructors and messages
+///     without an `inline` attribute of their own as `#[inline(never)]`. They stay
+///     functions of their own in the Wasm blob, so that
+///     `ink_metadata::size_report::SizeReport::from_wasm` can attribute the code
+///     size of the built contract to them. Nothing is added to the contract itself.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(size_report = true)]
+///     mod my_contract {
+///         # #[ink(storage)]
+///         # pub struct MyStorage;
+///         # impl MyStorage {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { MyStorage {} }
+///         #     #[ink(message)]
+///         #     pub fn message(&self) {}
+///         # }
+///         // ...
+///     }
+///     ```
+///
+///     **Default value:** `false`.
+///
+/// - `contract_info: bool`
+///
+///     Tells the ink! code generator to implement the `ink::info::ContractInfo`
+///     trait for the storage struct. Its messages return the version of the contract
+///     crate, the storage fingerprint and, if the contract implements
+///     `ink::upgrade::Pausable`, whether it is paused. Since the selectors of these
+///     messages are the same for all contracts, monitoring tooling can query any
+///     contract which enables them uniformly.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(contract_info = true)]
+///     mod my_contract {
+///         # #[ink(storage)]
+///         # pub struct MyStorage;
+///         # impl MyStorage {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { MyStorage {} }
+///         #     #[ink(message)]
+///         #     pub fn message(&self) {}
+///         # }
+///         // ...
+///     }
+///
+///     use ink::info::ContractInfo as _;
+///     let contract = my_contract::MyStorage::construct();
+///     assert_eq!(contract.is_paused(), None);
+///     ```
+///
+///     **Default value:** `false`.
+///
+/// - `config: Path`
+///
+///     Tells the ink! code generator which chain configuration to use for the ink!
+///     smart contract. The chain configuration must be defined via `#[ink::chain_config]`
+///     and provides chain specific compile-time constants, such as fee denominators or
+///     token decimals, that would otherwise be hard-coded in every contract.
+///
+///     The chain configuration is made available as `ChainConfig` within the contract
+///     module and all of its constants together with their SCALE encoded values are
+///     recorded in the contract metadata.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::chain_config]
+///     impl MyChainConfig {
+///         pub const DECIMALS: u8 = 12;
+///     }
+///     # pub struct MyChainConfig;
+///
+///     #[ink::contract(config = crate::MyChainConfig)]
+///     mod my_contract {
+///         # #[ink(storage)]
+///         # pub struct MyStorage;
+///         # impl MyStorage {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { MyStorage {} }
+///         #     #[ink(message)]
+///         #     pub fn decimals(&self) -> u8 { ChainConfig::DECIMALS }
+///         # }
+///         // ...
+///     }
+///     # fn main() {}
+///     ```
+///
+///     **Default value:** No chain configuration.
+///
 /// ## Analysis
 ///
 /// The `#[ink::contract]` macro fully analyses its input smart contract
@@ -389,6 +540,381 @@ pub fn selector_bytes(input: TokenStream) -> TokenStream {
 ///     # }
 ///     ```
 ///
+///     **Limiting the size of message inputs:**
+///
+///     An ink! message may be flagged with `max_input = N` in order to reject calls
+///     whose encoded input exceeds `N` bytes. The check happens in the dispatch before
+///     any of the message's arguments are decoded and makes the call fail with
+///     `LangError::InputTooLarge`. If the length of the input is unknown, decoding the
+///     arguments fails with the same error as soon as more than `N` bytes are read.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         #
+///         /// Stores the first byte of `data` as the current value.
+///         #[ink(message, max_input = 4096)]
+///         pub fn set(&mut self, data: Vec<u8>) {
+///             self.value = data.first().copied().unwrap_or_default() != 0;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Omitting messages from the contract metadata:**
+///
+///     An ink! message flagged with `metadata = false` is still dispatchable but is
+///     not part of the contract's public metadata. This is useful for internal or
+///     maintenance messages. Compiling the contract with the `full-metadata` crate
+///     feature of `ink` enabled includes all messages in the metadata, e.g. for audits.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         #
+///         /// Resets the current value.
+///         #[ink(message, metadata = false)]
+///         pub fn reset(&mut self) {
+///             self.value = false;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Deprecating messages:**
+///
+///     An ink! message flagged with `deprecated = "note"` keeps its selector and remains
+///     callable, but is flagged as deprecated together with the note in the contract
+///     metadata. Calling it in debug and test builds emits a debug message that
+///     contains the note. This allows to evolve the interface of a contract while
+///     signalling clients to move off old messages.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         #
+///         /// Sets the current value.
+///         #[ink(message, deprecated = "use `set_v2` instead")]
+///         pub fn set(&mut self, value: bool) {
+///             self.set_v2(value);
+///         }
+///
+///         /// Sets the current value.
+///         #[ink(message)]
+///         pub fn set_v2(&mut self, value: bool) {
+///             self.value = value;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Batching calls with a multicall message:**
+///
+///     An ink! message flagged with `multicall` dispatches a batch of calls to the
+///     other ink! messages of the contract within a single transaction. Its body
+///     must be empty since it is generated. Each call consists of the selector of
+///     an ink! message and its SCALE encoded arguments. The message returns the
+///     SCALE encoded outputs of all calls in order.
+///
+///     If one of the calls fails, e.g. because it returned an `Err`, the multicall
+///     message returns a `MulticallError` with the index of the failed call and the
+///     whole batch is reverted. Calls to non-payable ink! messages fail if value was
+///     transferred to a `payable` multicall message.
+///
+///     A multicall message taking `Vec<ink::MulticallPaidCall<Balance>>` instead
+///     attributes a part of the transferred value to each call. The attributed values
+///     must add up to the transferred value. Payable messages read the value meant for
+///     them with `self.env().attributed_value()`, and calls to non-payable messages
+///     fail only if value was attributed to them.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         #
+///         /// Flips the current value.
+///         #[ink(message)]
+///         pub fn flip(&mut self) {
+///             self.value = !self.value;
+///         }
+///
+///         /// Dispatches the `calls` to the other messages of the contract.
+///         #[ink(message, multicall)]
+///         pub fn multicall(
+///             &mut self,
+///             calls: Vec<ink::MulticallCall>,
+///         ) -> ink::MulticallResult {}
+///     }
+///     # }
+///     ```
+///
+///     **Migrating the storage after a code upgrade:**
+///
+///     An ink! message flagged with `migration` can run only once per code hash of
+///     the contract. It takes `&mut self` and is meant to convert the storage left
+///     behind by the old code with the helpers of `ink::storage::migration`, e.g.
+///     after an upgrade via `set_code_hash`. Calling it a second time before the
+///     next upgrade panics.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod counter {
+///         # #[ink(storage)]
+///         # pub struct Counter {
+///         #     value: ink::storage::Lazy<u64, ink::storage::traits::ManualKey<8>>,
+///         # }
+///     impl Counter {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Counter { value: Default::default() }
+///         # }
+///         #
+///         /// Moves the `u32` counter of the old code to the new `u64` counter.
+///         #[ink(message, migration)]
+///         pub fn migrate(&mut self) {
+///             ink::storage::migration::migrate(&7u32, &8u32, |old: u32| u64::from(old))
+///                 .expect("the old counter is a `u32`");
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Returning large sequences of bytes in chunks:**
+///
+///     The return value of an ink! message must fit into the static buffer of the
+///     contract. An ink! message flagged with `chunked` returns a large sequence of
+///     bytes in chunks instead: it takes the `u32` offset of an `ink::chunk::Chunk`
+///     as its first input and returns the chunk at that offset, optionally wrapped
+///     into an `Option` or a `Result`. The helpers of `ink::chunk` compute the chunk
+///     boundaries from the size of the static buffer and the message is flagged in
+///     the contract metadata, so that clients fetch chunks until the last one.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod archive {
+///         # #[ink(storage)]
+///         # pub struct Archive {
+///         #     data: Vec<u8>,
+///         # }
+///     impl Archive {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Archive { data: Vec::new() }
+///         # }
+///         #
+///         /// Returns the chunk of the data at `offset`.
+///         #[ink(message, chunked)]
+///         pub fn data_chunk(&self, offset: u32) -> ink::chunk::Chunk {
+///             ink::chunk::slice(&self.data, offset)
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Protecting messages against reentrant calls:**
+///
+///     A contract calling another contract with `ALLOW_REENTRY` may be called back
+///     before the call returned. An ink! message flagged with `non_reentrant` enters
+///     a guard in the storage before its body and exits it afterwards, also if it
+///     returns early. It reverts if it is called while another `non_reentrant`
+///     message of the contract is in progress. Contracts guarding only parts of a
+///     message use an `ink::storage::security::reentrancy::ReentrancyGuard` instead.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod bank {
+///         # #[ink(storage)]
+///         # pub struct Bank {
+///         #     balance: Balance,
+///         # }
+///     impl Bank {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Bank { balance: 0 }
+///         # }
+///         #
+///         /// Withdraws `value` from the balance.
+///         #[ink(message, non_reentrant)]
+///         pub fn withdraw(&mut self, value: Balance) {
+///             self.balance -= value;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Pausing messages in an emergency:**
+///
+///     An ink! message flagged with `when_not_paused` reverts while the contract is
+///     paused, i.e. while the `ink::storage::security::pausable::Pausable` field of the storage
+///     struct is paused. The storage struct must have exactly one such field. The
+///     message is flagged in the contract metadata, so that user interfaces can
+///     disable it. Who may pause the contract is up to the contract, e.g. its owner.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod vault {
+///         # use ink::storage::security::pausable::Pausable;
+///         # #[ink(storage)]
+///         # pub struct Vault {
+///         #     pausable: Pausable<Environment>,
+///         #     withdrawn: Balance,
+///         # }
+///     impl Vault {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Vault { pausable: Default::default(), withdrawn: 0 }
+///         # }
+///         #
+///         /// Withdraws `value`, unless the vault is paused.
+///         #[ink(message, when_not_paused)]
+///         pub fn withdraw(&mut self, value: Balance) {
+///             self.withdrawn += value;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Checking invariants of the contract:**
+///
+///     Methods flagged with `#[ink(invariant)]` must have the signature
+///     `fn(&self) -> bool` and are not exported as ink! messages. In debug and test
+///     builds the message dispatch checks all invariants of the contract after every
+///     ink! message that didn't revert and panics if any of them does not hold. This
+///     catches bugs that corrupt the state of the contract, e.g. in off-chain tests
+///     using `ink::env::test::dispatch`, without having to assert the invariants in
+///     every test. Calling a message directly as a method doesn't check the
+///     invariants and release builds skip the checks.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         #     flips: u32,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value, flips: initial_value as u32 }
+///         # }
+///         #
+///         /// Flips the current value.
+///         #[ink(message)]
+///         pub fn flip(&mut self) {
+///             self.value = !self.value;
+///             self.flips += 1;
+///         }
+///
+///         /// An odd number of flips must have been performed if the value is `true`.
+///         #[ink(invariant)]
+///         fn value_matches_flips(&self) -> bool {
+///             self.value == (self.flips % 2 == 1)
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Default values of message arguments:**
+///
+///     Inputs of ink! messages and constructors can be flagged with
+///     `#[ink(arg(default = value))]` to include a default value for them in the
+///     contract metadata, so that user interfaces can prefill the argument instead
+///     of relying on sentinel values. The `value` is either a literal or a string
+///     literal containing a Rust expression, e.g. `default = "None"`. It is type
+///     checked against the input type and stored SCALE encoded. Callers still have
+///     to provide every argument.
+///
+///     The metadata of every argument additionally carries display hints for the
+///     `Option`s and `Result`s its type is wrapped in.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         #
+///         /// Sets the current value or resets it if `value` is `None`.
+///         #[ink(message)]
+///         pub fn set(&mut self, #[ink(arg(default = "None"))] value: Option<bool>) {
+///             self.value = value.unwrap_or_default();
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Human-readable labels:**
+///
+///     ink! messages, constructors and their inputs can be flagged with
+///     `#[ink(label = "label")]` to include a human-readable name for them as their
+///     `displayName` in the contract metadata, so that user interfaces show e.g.
+///     "Transfer tokens" instead of `transfer`. Labels must not be empty, have at
+///     most 64 characters and must be unique among the messages, the constructors
+///     and the inputs of a single message or constructor respectively.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         #[ink(constructor, label = "Create flipper")]
+///         pub fn new(#[ink(label = "Initial value")] initial_value: bool) -> Self {
+///             Flipper { value: initial_value }
+///         }
+///
+///         #[ink(message, label = "Flip the value")]
+///         pub fn flip(&mut self) {
+///             self.value = !self.value;
+///         }
+///     }
+///     # }
+///     ```
+///
 /// ## Interacting with the Contract Executor
 ///
 /// The `ink_env` crate provides facilities to interact with the contract executor that
@@ -478,6 +1004,18 @@ pub fn selector_bytes(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// Every ink! event provides a `SIGNATURE` constant that lists its fields and their
+/// types in declaration order, e.g. `Erc20::Transferred(from:Option<AccountId>,...)`,
+/// as well as a `SIGNATURE_TOPIC` constant holding the first topic under which the
+/// event is emitted, or `None` for anonymous events. Both are also part of the
+/// contract metadata so that off-chain code can filter events without recomputing
+/// their topics and notice whenever the schema of an event changes.
+///
+/// The signature topic only depends on the names of the contract and the event, so
+/// equally named contracts of different crates emit their equally named events under
+/// the same topic. Call `ink::metadata::topics::assert_unique_topics` from a test of
+/// the workspace with the directory of the built metadata to detect such collisions.
+///
 /// ## Example: Flipper
 ///
 /// The below code shows the complete implementation of the so-called Flipper
@@ -650,6 +1188,67 @@ pub fn trait_definition(attr: TokenStream, item: TokenStream) -> TokenStream {
     trait_def::analyze(attr.into(), item.into()).into()
 }
 
+/// Creates a contract mock for an ink! trait definition to be used in off-chain tests.
+///
+/// The contract mock can be registered under an account of the off-chain environment.
+/// All calls to this account are then dispatched to the contract mock which decodes
+/// their arguments and encodes their outputs according to the ink! trait definition.
+///
+/// For every ink! trait message `msg` the contract mock provides:
+///
+/// - `on_msg`: Sets the handler computing the output of `msg` from its arguments.
+/// - `calls_to_msg`: Returns the decoded arguments of all recorded calls to `msg`.
+///
+/// Calling a message without a handler panics.
+///
+/// # Note
+///
+/// - The contract mock is only available in `std` builds of the crate that defines
+///   the ink! trait definition.
+/// - The environment defaults to `DefaultEnvironment` and can be changed using
+///   `mock_contract!(MyTrait, env = MyEnvironment)`.
+/// - The handlers must not call into the off-chain environment themselves.
+///
+/// # Example
+///
+/// ```
+/// #[ink::trait_definition]
+/// pub trait Erc20 {
+///     #[ink(message)]
+///     fn balance_of(&self, owner: ink::primitives::AccountId) -> u128;
+/// }
+///
+/// use ink::env::{
+///     call::{
+///         build_call,
+///         Call,
+///         ExecutionInput,
+///         Selector,
+///     },
+///     DefaultEnvironment,
+/// };
+///
+/// let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+/// let mock = ink::mock_contract!(Erc20);
+/// mock.on_balance_of(|_owner| 100).register(accounts.charlie);
+///
+/// let balance = build_call::<DefaultEnvironment>()
+///     .call_type(Call::new().callee(accounts.charlie))
+///     .exec_input(
+///         ExecutionInput::new(Selector::new(ink::selector_bytes!("Erc20::balance_of")))
+///             .push_arg(accounts.alice),
+///     )
+///     .returns::<u128>()
+///     .fire()
+///     .unwrap();
+/// assert_eq!(balance, 100);
+/// assert_eq!(mock.calls_to_balance_of(), [accounts.alice]);
+/// ```
+#[proc_macro]
+pub fn mock_contract(input: TokenStream) -> TokenStream {
+    mock_contract::generate_mock_contract(input.into()).into()
+}
+
 /// Prepares the type to be fully compatible and usable with the storage.
 /// It implements all necessary traits and calculates the storage key for types.
 /// `Packed` types don't have a storage key, but non-packed types (like `Mapping`, `Lazy` etc.)
@@ -763,6 +1362,36 @@ pub fn trait_definition(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// ## Composite Non-Packed Types
+///
+/// `Option`, `Result`, tuples and arrays are storage entities only if all of their
+/// elements are packed. The storage traits are implemented for every packed type by
+/// a blanket implementation, which rules out forwarding implementations for these
+/// types with non-packed elements such as `Option<Lazy<u128>>`.
+///
+/// Instead, define the composite type as a non-packed storage item:
+///
+/// ```
+/// use ink::storage::Lazy;
+///
+/// // The non-packed counterpart of `Option<Lazy<u128>>`.
+/// #[ink::storage_item]
+/// enum MaybeLazy {
+///     None,
+///     Some(Lazy<u128>),
+/// }
+///
+/// // The non-packed counterpart of `(Lazy<u128>, Lazy<bool>)`.
+/// #[ink::storage_item]
+/// struct LazyPair(Lazy<u128>, Lazy<bool>);
+///
+/// #[ink::storage_item]
+/// struct Vault {
+///     deposit: MaybeLazy,
+///     limits: LazyPair,
+/// }
+/// ```
+///
 /// ## Header Arguments
 ///
 /// The `#[ink::storage_item]` macro can be provided with an additional comma-separated
@@ -796,6 +1425,177 @@ pub fn storage_item(attr: TokenStream, item: TokenStream) -> TokenStream {
     storage_item::generate(attr.into(), item.into()).into()
 }
 
+/// Marks a struct or enum as a type shared by several ink! smart contracts.
+///
+/// Types such as errors or data structs that are used by multiple contracts of a
+/// workspace should be defined once in a types-only crate instead of being
+/// duplicated into each contract. The `#[ink::shared]` attribute derives the
+/// traits required to use the type as message argument, return value or event field:
+///
+/// - `scale::Encode` and `scale::Decode`
+/// - `scale_info::TypeInfo` if the `std` feature of the defining crate is enabled
+///
+/// Since the type information is derived in the defining crate, all contracts
+/// report the same path for the type in their metadata. While the id of the type
+/// in the type registry differs between contracts, the `customTypes` section of the
+/// metadata of every contract maps the same stable name of the type, i.e. its path
+/// and type arguments such as `shared_types::Order<u128>`, to its id.
+///
+/// # Example
+///
+/// ```
+/// #[ink::shared]
+/// #[derive(Debug, PartialEq, Eq)]
+/// pub enum Error {
+///     InsufficientBalance,
+///     Unauthorized,
+/// }
+///
+/// #[ink::shared]
+/// pub struct Order {
+///     pub amount: u128,
+///     pub price: u128,
+/// }
+/// ```
+///
+/// The attribute can only be applied to structs and enums and takes no arguments.
+#[proc_macro_attribute]
+pub fn shared(attr: TokenStream, item: TokenStream) -> TokenStream {
+    shared::generate(attr.into(), item.into()).into()
+}
+
+/// Guards an ink! message that upgrades the code of the contract.
+///
+/// The guarded message must have a `&mut self` receiver, take the new code hash
+/// and the expected storage fingerprint as its two inputs and return a
+/// `Result<(), E>` where `E: From<ink::upgrade::UpgradeError>`. Its body is
+/// executed first and can reject the upgrade by returning an `Err`, e.g. if the
+/// caller is not authorized. Afterwards the code hash of the contract is set via
+/// `ink::upgrade::set_code_hash_guarded` which fails unless
+///
+/// - the contract is paused according to its `ink::upgrade::Pausable` implementation,
+///   so that no other message can observe the storage during the migration, and
+/// - the given fingerprint matches the `ink::upgrade::StorageFingerprint` of the
+///   contract, which the `#[ink::contract]` macro derives from the definition of the
+///   storage struct. Passing the fingerprint of the new code ensures that both
+///   versions lay out the storage in the same way.
+///
+/// # Example
+///
+/// ```
+/// #[ink::contract]
+/// mod upgradeable {
+///     use ink::upgrade::UpgradeError;
+///
+///     #[ink(storage)]
+///     pub struct Upgradeable {
+///         owner: AccountId,
+///         paused: bool,
+///     }
+///
+///     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+///     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+///     pub enum Error {
+///         NotOwner,
+///         Upgrade(UpgradeError),
+///     }
+///
+///     impl From<UpgradeError> for Error {
+///         fn from(error: UpgradeError) -> Self {
+///             Error::Upgrade(error)
+///         }
+///     }
+///
+///     impl ink::upgrade::Pausable for Upgradeable {
+///         fn paused(&self) -> bool {
+///             self.paused
+///         }
+///     }
+///
+///     impl Upgradeable {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self { owner: Self::env().caller(), paused: false }
+///         }
+///
+///         #[ink(message)]
+///         pub fn set_paused(&mut self, paused: bool) {
+///             self.paused = paused;
+///         }
+///
+///         /// Upgrades the code of the contract to `code_hash`.
+///         #[ink(message)]
+///         #[ink::upgrade_guard]
+///         pub fn upgrade(
+///             &mut self,
+///             code_hash: Hash,
+///             fingerprint: [u8; 32],
+///         ) -> Result<(), Error> {
+///             if self.env().caller() != self.owner {
+///                 return Err(Error::NotOwner)
+///             }
+///             Ok(())
+///         }
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn upgrade_guard(attr: TokenStream, item: TokenStream) -> TokenStream {
+    upgrade_guard::generate(attr.into(), item.into()).into()
+}
+
+/// Defines a chain configuration that can be used by ink! smart contracts.
+///
+/// A chain configuration is an inherent implementation block whose associated
+/// constants describe properties of the chain a contract is deployed to, e.g. its
+/// fee denominator or the decimals of its native token. Contracts opt into a chain
+/// configuration via `#[ink::contract(config = ..)]` which makes it available as
+/// `ChainConfig` within the contract module and records its constants in the
+/// contract metadata.
+///
+/// The types of all constants must implement `scale::Encode` and `scale_info::TypeInfo`
+/// since their values are SCALE encoded into the metadata.
+///
+/// # Example
+///
+/// ```
+/// pub struct MyChainConfig;
+///
+/// #[ink::chain_config]
+/// impl MyChainConfig {
+///     /// The denominator of all fees charged by the chain.
+///     pub const FEE_DENOMINATOR: u128 = 1_000;
+///     /// The decimals of the native token of the chain.
+///     pub const DECIMALS: u8 = 12;
+/// }
+///
+/// #[ink::contract(config = crate::MyChainConfig)]
+/// mod exchange {
+///     #[ink(storage)]
+///     pub struct Exchange {
+///         fee: Balance,
+///     }
+///
+///     impl Exchange {
+///         #[ink(constructor)]
+///         pub fn new(fee: Balance) -> Self {
+///             Self { fee }
+///         }
+///
+///         /// Returns the fee charged for trading `amount`.
+///         #[ink(message)]
+///         pub fn fee_of(&self, amount: Balance) -> Balance {
+///             amount * self.fee / ChainConfig::FEE_DENOMINATOR
+///         }
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_attribute]
+pub fn chain_config(attr: TokenStream, item: TokenStream) -> TokenStream {
+    chain_config::generate(attr.into(), item.into()).into()
+}
+
 /// Defines a unit test that makes use of ink!'s off-chain testing capabilities.
 ///
 /// If your unit test does not require the existence of an off-chain environment
@@ -804,6 +1604,10 @@ pub fn storage_item(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Note that this macro is not required to run unit tests that require ink!'s
 /// off-chain testing capabilities but merely improves code readability.
 ///
+/// Every test runs on a freshly reset off-chain environment of its own thread, so
+/// tests can run in parallel threads without interfering with each other. Threads
+/// spawned by a test opt into the state of the test via `ink::env::test::SharedEnv`.
+///
 /// ## How do you find out if your test requires the off-chain environment?
 ///
 /// Normally if the test recursively uses or invokes some contract methods that
@@ -865,7 +1669,7 @@ pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// # Attributes
 ///
-/// There are three different attributes with which the chain extension methods
+/// There are four different attributes with which the chain extension methods
 /// can be flagged:
 ///
 /// | Attribute | Required | Default Value | Description |
@@ -873,6 +1677,7 @@ pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// | `ink(extension = N: u32)` | Yes | - | Determines the unique function ID of the chain extension method. |
 /// | `ink(handle_status = flag: bool)` | Optional | `true` | Assumes that the returned status code of the chain extension method always indicates success and therefore always loads and decodes the output buffer of the call. |
 /// | `ink(returns_result = flag: bool)` | Optional | `true` | By default chain extension methods are assumed to return a `Result<T, E>` in the output buffer. Using `returns_result = false` this check is disabled and the chain extension method may return any other type. |
+/// | `ink(weight = N: u64)` | Optional | - | Declares the weight the chain extension method is expected to charge per call. |
 ///
 /// As with all ink! attributes multiple of them can either appear in a contiguous list:
 /// ```
@@ -926,6 +1731,26 @@ pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Note that if a chain extension method is attributed with `returns_result = false`
 /// and with `handle_status = true` it will still return a value of type `Result<T, Self::ErrorCode>`.
 ///
+/// ## Details: `weight`
+///
+/// Declares the weight `N` that the chain extension method is expected to charge per call.
+/// The weight is included in the contract metadata of contracts using the chain extension
+/// in their environment.
+///
+/// The off-chain environment charges the declared weight for every call of the chain
+/// extension method, so that the costs of contracts heavily using chain extensions can be
+/// estimated in tests via `ink::env::test::gas_consumed()`. On-chain the chain extension
+/// charges its weight on its own.
+///
+/// ```
+/// # #[ink::chain_extension]
+/// # pub trait MyChainExtension {
+/// #     type ErrorCode = i32;
+/// #[ink(extension = 1101, handle_status = false, returns_result = false, weight = 10_000)]
+/// fn fetch_random(subject: [u8; 32]) -> [u8; 32];
+/// # }
+/// ```
+///
 /// ## Usage: `handle_status` and `returns_result`
 ///
 /// Use both `handle_status = false` and `returns_result = false` for the same chain extension method
@@ -1265,9 +2090,61 @@ pub fn chain_extension(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 synstructure::decl_derive!(
-    [Storable] =>
+    [ErrorCode, attributes(error_code)] =>
+    /// Derives `ink`'s `ErrorCode` trait for the given `enum`, which assigns stable
+    /// numeric codes to the variants of an error type.
+    ///
+    /// Every variant must be annotated with its code via `#[error_code(N)]`. The codes
+    /// must be unique and are independent of the order of the variants, so client
+    /// SDKs can map failures to them without parsing strings. The codes and the docs
+    /// of the variants are embedded in the contract metadata for all error types
+    /// returned by ink! messages as `Result<_, E>`.
+    ///
+    /// A variant with a single field of another error type deriving `ErrorCode`, e.g.
+    /// the error of a module of the contract, can be annotated with
+    /// `#[error_code(N, from)]` instead. This generates a `From` implementation for
+    /// the nested error type, so it can be propagated with `?`, and embeds the codes
+    /// of the nested error type in the metadata as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ink::ErrorCode;
+    ///
+    /// #[derive(ErrorCode, scale::Encode, scale::Decode)]
+    /// #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    /// pub enum TokenError {
+    ///     /// The balance is too low.
+    ///     #[error_code(1)]
+    ///     InsufficientBalance,
+    /// }
+    ///
+    /// #[derive(ErrorCode, scale::Encode, scale::Decode)]
+    /// #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    /// pub enum Error {
+    ///     #[error_code(100)]
+    ///     NotOwner,
+    ///     #[error_code(200, from)]
+    ///     Token(TokenError),
+    /// }
+    ///
+    /// fn transfer() -> Result<(), Error> {
+    ///     Err(TokenError::InsufficientBalance)?
+    /// }
+    ///
+    /// assert_eq!(transfer().unwrap_err().error_code(), 200);
+    /// assert_eq!(Error::NotOwner.error_code(), 100);
+    /// ```
+    error_code::error_code_derive
+);
+synstructure::decl_derive!(
+    [Storable, attributes(codec)] =>
     /// Derives `ink::storage`'s `Storable` trait for the given `struct`, `enum` or `union`.
     ///
+    /// Enum variants are encoded with the same discriminant as `scale` would use: the
+    /// `#[codec(index = N)]` attribute, the explicit `Variant = N` discriminant or the
+    /// position of the variant, in that order of precedence.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1348,9 +2225,13 @@ synstructure::decl_derive!(
     storage::storage_key_derive
 );
 synstructure::decl_derive!(
-    [StorageLayout] =>
+    [StorageLayout, attributes(codec)] =>
     /// Derives `ink::storage`'s `StorageLayout` trait for the given `struct` or `enum`.
     ///
+    /// The discriminants of the emitted enum layout are the same as the ones used by
+    /// the `Storable` derive, so the layout stays valid when explicitly numbered variants
+    /// are reordered.
+    ///
     /// # Examples
     ///
     /// ```
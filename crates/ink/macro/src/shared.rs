@@ -12,25 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use derive_more::From;
+use ink_codegen::generate_code;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::Result;
 
-/// The function selector.
-#[derive(
-    Default, Debug, Copy, Clone, PartialEq, Eq, From, scale::Decode, scale::Encode,
-)]
-pub struct Selector {
-    /// The 4 underlying bytes.
-    bytes: [u8; 4],
-}
-
-impl Selector {
-    /// Creates a selector directly from 4 bytes.
-    pub const fn new(bytes: [u8; 4]) -> Self {
-        Self { bytes }
+pub fn generate(config: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    match generate_or_err(config, input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
     }
+}
 
-    /// Returns the underlying bytes of the selector.
-    pub const fn to_bytes(self) -> [u8; 4] {
-        self.bytes
-    }
+pub fn generate_or_err(
+    config: TokenStream2,
+    input: TokenStream2,
+) -> Result<TokenStream2> {
+    let shared_item = ink_ir::SharedItem::new(config, input)?;
+    Ok(generate_code(&shared_item))
 }
@@ -0,0 +1,107 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use syn::spanned::Spanned;
+
+/// Returns the discriminants of the enum variants in their order of declaration.
+///
+/// The discriminants follow the same rules as the `scale` codec, so that packed and
+/// non-packed enums are encoded the same way:
+///
+/// 1. If the variant has a `#[codec(index = N)]` attribute, `N` is the discriminant.
+/// 1. Otherwise, if the variant has an explicit discriminant `Variant = N`, `N` is used.
+/// 1. Otherwise, the position of the variant in the enum is used.
+///
+/// This keeps the encoding of variants stable when they are reordered as long as they
+/// have explicit discriminants.
+///
+/// # Errors
+///
+/// - If an explicit discriminant is not an integer literal.
+/// - If a discriminant does not fit into a `u8`.
+/// - If two variants share the same discriminant.
+pub fn variant_discriminants(s: &synstructure::Structure) -> Result<Vec<u8>, syn::Error> {
+    let mut discriminants: Vec<u8> = Vec::with_capacity(s.variants().len());
+    for (position, variant) in s.variants().iter().enumerate() {
+        let ast = variant.ast();
+        let discriminant = match codec_index(ast.attrs)? {
+            Some(index) => index,
+            None => match ast.discriminant {
+                Some((_, expr)) => explicit_discriminant(expr)?,
+                None => u8::try_from(position).map_err(|_| {
+                    syn::Error::new(
+                        ast.ident.span(),
+                        "Currently only enums with at most 256 variants are supported.",
+                    )
+                })?,
+            },
+        };
+        if discriminants.contains(&discriminant) {
+            return Err(syn::Error::new(
+                ast.ident.span(),
+                format!(
+                    "encountered duplicate discriminant `{}` for variant `{}`",
+                    discriminant, ast.ident
+                ),
+            ))
+        }
+        discriminants.push(discriminant);
+    }
+    Ok(discriminants)
+}
+
+/// Returns the `N` of a `#[codec(index = N)]` attribute if any.
+fn codec_index(attrs: &[syn::Attribute]) -> Result<Option<u8>, syn::Error> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("codec")) {
+        if let syn::Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested.iter() {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) = nested {
+                    if name_value.path.is_ident("index") {
+                        return match &name_value.lit {
+                            syn::Lit::Int(lit) => lit.base10_parse::<u8>().map(Some),
+                            lit => Err(syn::Error::new(
+                                lit.span(),
+                                "expected an integer literal for `#[codec(index = N)]`",
+                            )),
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the value of an explicit `Variant = N` discriminant.
+fn explicit_discriminant(expr: &syn::Expr) -> Result<u8, syn::Error> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => {
+            lit.base10_parse::<u8>().map_err(|_| {
+                syn::Error::new(
+                    lit.span(),
+                    "enum discriminants must fit into a `u8` to be stored",
+                )
+            })
+        }
+        expr => {
+            Err(syn::Error::new(
+                expr.span(),
+                "only integer literals are supported as enum discriminants",
+            ))
+        }
+    }
+}
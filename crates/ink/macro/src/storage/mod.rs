@@ -17,6 +17,7 @@
 //! This crate provides helpers to define your very own custom storage data
 //! structures that work along the `ink_storage` data structures.
 
+mod discriminant;
 mod storable;
 mod storable_hint;
 mod storage_key;
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::discriminant::variant_discriminants;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{
     quote,
@@ -55,6 +56,8 @@ fn storable_struct_derive(s: &synstructure::Structure) -> TokenStream2 {
 }
 
 /// `Storable` derive implementation for `enum` types.
+///
+/// The variants are encoded with the discriminants returned by [`variant_discriminants`].
 fn storable_enum_derive(s: &synstructure::Structure) -> TokenStream2 {
     assert!(
         !s.variants().is_empty(),
@@ -69,6 +72,11 @@ fn storable_enum_derive(s: &synstructure::Structure) -> TokenStream2 {
         .to_compile_error()
     }
 
+    let discriminants = match variant_discriminants(s) {
+        Ok(discriminants) => discriminants,
+        Err(error) => return error.to_compile_error(),
+    };
+
     let decode_body = s
         .variants()
         .iter()
@@ -81,18 +89,16 @@ fn storable_enum_derive(s: &synstructure::Structure) -> TokenStream2 {
                 )
             })
         })
-        .enumerate()
-        .fold(quote! {}, |acc, (index, variant)| {
-            let index = index as u8;
+        .zip(discriminants.iter())
+        .fold(quote! {}, |acc, (variant, index)| {
             quote! {
                 #acc
                 #index => #variant,
             }
         });
 
-    let encode_body = s.variants().iter().enumerate().map(|(index, variant)| {
+    let encode_body = s.variants().iter().zip(discriminants.iter()).map(|(variant, index)| {
         let pat = variant.pat();
-        let index = index as u8;
         let fields = variant.bindings().iter().map(|field| {
             let span = field.ast().ty.span();
             quote_spanned!(span =>
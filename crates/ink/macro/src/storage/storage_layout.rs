@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::discriminant::variant_discriminants;
+use ink_ir::IsDocAttribute as _;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::spanned::Spanned;
@@ -31,15 +33,53 @@ fn field_layout<'a>(
             }
         };
         let ty = &field.ty;
+        let type_name = type_name(ty);
+        let docs = field
+            .attrs
+            .iter()
+            .filter_map(|attr| attr.extract_docs())
+            .collect::<Vec<_>>();
+        let with_docs = (!docs.is_empty()).then(|| {
+            quote! {
+                .with_docs([ #( #docs ),* ])
+            }
+        });
         quote! {
             ::ink::metadata::layout::FieldLayout::new(
                 #ident,
                 <#ty as ::ink::storage::traits::StorageLayout>::layout(__key),
             )
+            .with_type_name(#type_name)
+            #with_docs
         }
     })
 }
 
+/// Returns the Rust type as written in the source, e.g. `Mapping<AccountId, u128>`.
+///
+/// The stringified tokens of a type are separated by whitespace which is removed
+/// wherever it would not appear in idiomatically formatted Rust code.
+fn type_name(ty: &syn::Type) -> String {
+    let mut type_name = quote!(#ty).to_string();
+    for (spaced, compact) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" ;", ";"),
+        ("& ", "&"),
+        ("[ ", "["),
+        (" ]", "]"),
+        ("( ", "("),
+        (" )", ")"),
+    ] {
+        type_name = type_name.replace(spaced, compact);
+    }
+    type_name
+}
+
 fn storage_layout_struct(s: &synstructure::Structure) -> TokenStream2 {
     assert!(
         matches!(s.ast().data, syn::Data::Struct(_)),
@@ -82,29 +122,33 @@ fn storage_layout_enum(s: &synstructure::Structure) -> TokenStream2 {
         .to_compile_error()
     }
 
-    let variant_layouts = s.variants().iter().enumerate().map(|(n, variant)| {
-        let variant_ident = variant.ast().ident;
-        let discriminant = variant
-            .ast()
-            .discriminant
-            .as_ref()
-            .map(|(_, expr)| quote! { #expr })
-            .unwrap_or_else(|| quote! { #n });
-        let field_layouts = field_layout(variant);
-        quote! {
-            {
-                (
-                    ::ink::metadata::layout::Discriminant::from(#discriminant),
-                    ::ink::metadata::layout::StructLayout::new(
-                        ::core::stringify!(#variant_ident),
-                        [
-                            #(#field_layouts ,)*
-                        ]
-                    ),
-                )
-            }
-        }
-    });
+    let discriminants = match variant_discriminants(s) {
+        Ok(discriminants) => discriminants,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let variant_layouts =
+        s.variants()
+            .iter()
+            .zip(discriminants)
+            .map(|(variant, discriminant)| {
+                let variant_ident = variant.ast().ident;
+                let discriminant = discriminant as usize;
+                let field_layouts = field_layout(variant);
+                quote! {
+                    {
+                        (
+                            ::ink::metadata::layout::Discriminant::from(#discriminant),
+                            ::ink::metadata::layout::StructLayout::new(
+                                ::core::stringify!(#variant_ident),
+                                [
+                                    #(#field_layouts ,)*
+                                ]
+                            ),
+                        )
+                    }
+                }
+            });
     let enum_ident = s.ast().ident.clone();
     s.gen_impl(quote! {
         gen impl ::ink::storage::traits::StorageLayout for @Self {
@@ -399,3 +399,64 @@ fn generic_enum_works() {
         }
     }
 }
+
+#[test]
+fn enum_with_explicit_discriminants_works() {
+    crate::test_derive! {
+        storable_derive {
+            enum ExplicitEnum {
+                B = 7,
+                #[codec(index = 42)]
+                A,
+            }
+        }
+        expands to {
+            const _: () = {
+                impl ::ink::storage::traits::Storable for ExplicitEnum {
+                    #[inline(always)]
+                    #[allow(non_camel_case_types)]
+                    fn decode<__ink_I: ::scale::Input>(__input: &mut __ink_I) -> ::core::result::Result<Self, ::scale::Error> {
+                        ::core::result::Result::Ok(
+                            match <::core::primitive::u8 as ::ink::storage::traits::Storable>::decode(__input)?
+                            {
+                                7u8 => ExplicitEnum::B,
+                                42u8 => ExplicitEnum::A,
+                                _ => unreachable!("encountered invalid enum discriminant"),
+                            }
+                        )
+                    }
+
+                    #[inline(always)]
+                    #[allow(non_camel_case_types)]
+                    fn encode<__ink_O: ::scale::Output + ?::core::marker::Sized>(&self, __dest: &mut __ink_O) {
+                        match self {
+                            ExplicitEnum::B => {
+                                {
+                                    <::core::primitive::u8 as ::ink::storage::traits::Storable>::encode(&7u8, __dest);
+                                }
+                            }
+                            ExplicitEnum::A => {
+                                {
+                                    <::core::primitive::u8 as ::ink::storage::traits::Storable>::encode(&42u8, __dest);
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+        } no_build
+    }
+}
+
+#[test]
+fn enum_with_duplicate_discriminants_fails() {
+    let input = syn::parse_quote! {
+        enum DuplicateEnum {
+            A = 1,
+            B,
+        }
+    };
+    let output = storable_derive(synstructure::Structure::new(&input)).to_string();
+    assert!(output.contains("compile_error"));
+    assert!(output.contains("encountered duplicate discriminant `1` for variant `B`"));
+}
@@ -51,15 +51,18 @@ fn tuple_struct_works() {
                                     ::ink::metadata::layout::FieldLayout::new(
                                         "0",
                                         <bool as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                    ),
+                                    )
+                                    .with_type_name("bool"),
                                     ::ink::metadata::layout::FieldLayout::new(
                                         "1",
                                         <u32 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                    ),
+                                    )
+                                    .with_type_name("u32"),
                                     ::ink::metadata::layout::FieldLayout::new(
                                         "2",
                                         <i64 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                    ),
+                                    )
+                                    .with_type_name("i64"),
                                 ]
                             )
                         )
@@ -91,15 +94,18 @@ fn named_fields_struct_works() {
                                     ::ink::metadata::layout::FieldLayout::new(
                                         "a",
                                         <bool as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                    ),
+                                    )
+                                    .with_type_name("bool"),
                                     ::ink::metadata::layout::FieldLayout::new(
                                         "b",
                                         <u32 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                    ),
+                                    )
+                                    .with_type_name("u32"),
                                     ::ink::metadata::layout::FieldLayout::new(
                                         "c",
                                         <i64 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                    ),
+                                    )
+                                    .with_type_name("i64"),
                                 ]
                             )
                         )
@@ -199,15 +205,18 @@ fn mixed_enum_works() {
                                                     ::ink::metadata::layout::FieldLayout::new(
                                                         "0",
                                                         <bool as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                                    ),
+                                                    )
+                                                    .with_type_name("bool"),
                                                     ::ink::metadata::layout::FieldLayout::new(
                                                         "1",
                                                         <u32 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                                    ),
+                                                    )
+                                                    .with_type_name("u32"),
                                                     ::ink::metadata::layout::FieldLayout::new(
                                                         "2",
                                                         <i64 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                                    ),
+                                                    )
+                                                    .with_type_name("i64"),
                                                 ]
                                             ),
                                         )
@@ -221,15 +230,18 @@ fn mixed_enum_works() {
                                                     ::ink::metadata::layout::FieldLayout::new(
                                                         "a",
                                                         <bool as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                                    ),
+                                                    )
+                                                    .with_type_name("bool"),
                                                     ::ink::metadata::layout::FieldLayout::new(
                                                         "b",
                                                         <u32 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                                    ),
+                                                    )
+                                                    .with_type_name("u32"),
                                                     ::ink::metadata::layout::FieldLayout::new(
                                                         "c",
                                                         <i64 as ::ink::storage::traits::StorageLayout>::layout(__key),
-                                                    ),
+                                                    )
+                                                    .with_type_name("i64"),
                                                 ]
                                             ),
                                         )
@@ -243,3 +255,113 @@ fn mixed_enum_works() {
         }
     }
 }
+
+#[test]
+fn enum_with_explicit_discriminants_works() {
+    crate::test_derive! {
+        storage_layout_derive {
+            #[repr(u8)]
+            enum ExplicitEnum {
+                B = 7,
+                A(bool) = 5,
+                #[codec(index = 42)]
+                C,
+                D,
+            }
+        }
+        expands to {
+            const _: () = {
+                impl ::ink::storage::traits::StorageLayout for ExplicitEnum {
+                    fn layout(__key: &::ink::primitives::Key) -> ::ink::metadata::layout::Layout {
+                        ::ink::metadata::layout::Layout::Enum(
+                            ::ink::metadata::layout::EnumLayout::new(
+                                ::core::stringify!(ExplicitEnum),
+                                ::ink::metadata::layout::LayoutKey::from(__key),
+                                [
+                                    {
+                                        (
+                                            ::ink::metadata::layout::Discriminant::from(7usize),
+                                            ::ink::metadata::layout::StructLayout::new(
+                                                ::core::stringify!(B), []
+                                            ),
+                                        )
+                                    },
+                                    {
+                                        (
+                                            ::ink::metadata::layout::Discriminant::from(5usize),
+                                            ::ink::metadata::layout::StructLayout::new(
+                                                ::core::stringify!(A),
+                                                [
+                                                    ::ink::metadata::layout::FieldLayout::new(
+                                                        "0",
+                                                        <bool as ::ink::storage::traits::StorageLayout>::layout(__key),
+                                                    )
+                                                    .with_type_name("bool"),
+                                                ]
+                                            ),
+                                        )
+                                    },
+                                    {
+                                        (
+                                            ::ink::metadata::layout::Discriminant::from(42usize),
+                                            ::ink::metadata::layout::StructLayout::new(
+                                                ::core::stringify!(C), []
+                                            ),
+                                        )
+                                    },
+                                    {
+                                        (
+                                            ::ink::metadata::layout::Discriminant::from(3usize),
+                                            ::ink::metadata::layout::StructLayout::new(
+                                                ::core::stringify!(D), []
+                                            ),
+                                        )
+                                    },
+                                ]
+                            )
+                        )
+                    }
+                }
+            };
+        } no_build
+    }
+}
+
+#[test]
+fn field_docs_and_type_names_work() {
+    crate::test_derive! {
+        storage_layout_derive {
+            struct DocumentedStruct {
+                /// The balances of all accounts.
+                balances: Mapping<AccountId, u128>,
+                owner: Option<[u8; 32]>,
+            }
+        }
+        expands to {
+            const _: () = {
+                impl ::ink::storage::traits::StorageLayout for DocumentedStruct {
+                    fn layout(__key: &::ink::primitives::Key) -> ::ink::metadata::layout::Layout {
+                        ::ink::metadata::layout::Layout::Struct(
+                            ::ink::metadata::layout::StructLayout::new(
+                                ::core::stringify!(DocumentedStruct),
+                                [
+                                    ::ink::metadata::layout::FieldLayout::new(
+                                        "balances",
+                                        <Mapping<AccountId, u128> as ::ink::storage::traits::StorageLayout>::layout(__key),
+                                    )
+                                    .with_type_name("Mapping<AccountId, u128>")
+                                    .with_docs([" The balances of all accounts."]),
+                                    ::ink::metadata::layout::FieldLayout::new(
+                                        "owner",
+                                        <Option<[u8; 32]> as ::ink::storage::traits::StorageLayout>::layout(__key),
+                                    )
+                                    .with_type_name("Option<[u8; 32]>"),
+                                ]
+                            )
+                        )
+                    }
+                }
+            };
+        } no_build
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities to process large amounts of data across several calls.
+//!
+//! A message looping over a large collection may run out of gas, which reverts all
+//! of its changes. A [`Budget`] stops the loop gracefully instead once the gas left
+//! falls below a reserve, so the message can persist its progress and return the
+//! position to resume from in a subsequent call.
+
+use core::marker::PhantomData;
+use ink_env::{
+    DefaultEnvironment,
+    Environment,
+    Gas,
+};
+use ink_storage::{
+    collections::{
+        BatchCursor,
+        IndexOrder,
+        IterableMapping,
+    },
+    traits::{
+        Packed,
+        StorageKey,
+    },
+};
+
+pub use ink_primitives::OutOfBudget;
+
+/// The gas budget of a call, which keeps a reserve of gas for the work after the
+/// last checkpoint.
+///
+/// # Example
+///
+/// ```
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::{
+///     budget::{
+///         Budget,
+///         OutOfBudget,
+///     },
+///     storage::{
+///         collections::StorageVec,
+///         Lazy,
+///     },
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Payroll {
+///     employees: StorageVec<AccountId>,
+///     paid: Lazy<u32>,
+/// }
+///
+/// impl Payroll {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     /// Pays the employees from the last interrupted position on.
+///     #[ink(message)]
+///     pub fn pay(&mut self, salary: Balance) -> Result<(), OutOfBudget<u32>> {
+///         let budget = Budget::<Environment>::new(1_000_000_000);
+///         let mut index = self.paid.get_or_default();
+///         let result = loop {
+///             if let Err(error) = budget.checkpoint_at(index) {
+///                 break Err(error)
+///             }
+///             match self.employees.get(index) {
+///                 Some(employee) => {
+///                     let _ = self.env().transfer(employee, salary);
+///                     index += 1;
+///                 }
+///                 None => break Ok(()),
+///             }
+///         };
+///         self.paid.set(&if result.is_ok() { 0 } else { index });
+///         result
+///     }
+/// }
+/// # }
+/// ```
+pub struct Budget<E: Environment = DefaultEnvironment> {
+    reserve: Gas,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E> Budget<E>
+where
+    E: Environment,
+{
+    /// Creates a budget which is exhausted once no more than `reserve` gas is left.
+    ///
+    /// The reserve must cover the work after the last checkpoint, e.g. persisting
+    /// the progress and returning from the message.
+    pub fn new(reserve: Gas) -> Self {
+        Self {
+            reserve,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns the gas kept in reserve.
+    pub fn reserve(&self) -> Gas {
+        self.reserve
+    }
+
+    /// Returns the gas left above the reserve.
+    pub fn remaining(&self) -> Gas {
+        ink_env::gas_left::<E>().saturating_sub(self.reserve)
+    }
+
+    /// Returns `true` if no more than the reserve is left.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Checks that the budget is not exhausted yet.
+    ///
+    /// # Errors
+    ///
+    /// If no more than the reserve is left.
+    pub fn checkpoint(&self) -> Result<(), OutOfBudget> {
+        self.checkpoint_at(())
+    }
+
+    /// Checks that the budget is not exhausted yet, before the processing of the
+    /// position `resume_at`.
+    ///
+    /// # Errors
+    ///
+    /// If no more than the reserve is left, with `resume_at` as the position to
+    /// resume the processing from.
+    pub fn checkpoint_at<P>(&self, resume_at: P) -> Result<(), OutOfBudget<P>> {
+        if self.is_exhausted() {
+            return Err(OutOfBudget { resume_at })
+        }
+        Ok(())
+    }
+
+    /// Processes the indices of a collection with `len` elements with `f`, from the
+    /// index saved in `cursor` on, until the budget is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// If the budget is exhausted before all indices are processed, with the index
+    /// to resume from, which is saved in `cursor` for the next call.
+    pub fn run_batch<K, F>(
+        &self,
+        cursor: &mut BatchCursor<u32, K>,
+        len: u32,
+        f: F,
+    ) -> Result<(), OutOfBudget<u32>>
+    where
+        K: StorageKey,
+        F: FnMut(u32),
+    {
+        cursor
+            .run(len, || !self.is_exhausted(), f)
+            .map_err(|resume_at| OutOfBudget { resume_at })
+    }
+
+    /// Processes the entries of `mapping` with `f`, from the position saved in
+    /// `cursor` on, until the budget is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// If the budget is exhausted before all entries are processed, with the
+    /// position to resume from, which is saved in `cursor` for the next call.
+    pub fn run_batch_over<K, V, CursorKey, MapKey, Order, F>(
+        &self,
+        cursor: &mut BatchCursor<u32, CursorKey>,
+        mapping: &IterableMapping<K, V, MapKey, Order>,
+        f: F,
+    ) -> Result<(), OutOfBudget<u32>>
+    where
+        K: Packed,
+        V: Packed,
+        CursorKey: StorageKey,
+        MapKey: StorageKey,
+        Order: IndexOrder<K>,
+        F: FnMut(K, V),
+    {
+        cursor
+            .run_over(mapping, || !self.is_exhausted(), f)
+            .map_err(|resume_at| OutOfBudget { resume_at })
+    }
+}
+
+impl<E> Clone for Budget<E>
+where
+    E: Environment,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for Budget<E> where E: Environment {}
+
+impl<E> ::core::fmt::Debug for Budget<E>
+where
+    E: Environment,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Budget")
+            .field("reserve", &self.reserve)
+            .finish()
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Provides the metadata of a chain configuration.
+///
+/// # Note
+///
+/// This trait is automatically implemented when using `#[ink::chain_config]` procedural macro.
+pub trait ChainConfigMetadata {
+    /// Returns the metadata of the chain configuration.
+    fn chain_config_spec() -> ink_metadata::ChainConfigSpec;
+}
@@ -19,6 +19,57 @@
 /// # Note
 ///
 /// This trait is automatically implemented when using `#[ink::chain_extension]` procedural macro.
+///
+/// # Swapping the chain extension
+///
+/// The chain extension of a contract is the `ChainExtension` type of its
+/// [`Environment`](ink_env::Environment). Since `self.env().extension()` only requires
+/// this trait, the chain extension can be swapped per environment by implementing it
+/// by hand, e.g. in order to use a no-op chain extension in tests. The instance must
+/// provide the chain extension methods the contract calls with the same signatures.
+///
+/// ```
+/// # use ink::env::{DefaultEnvironment, Environment};
+/// /// Answers every random value request with zeros.
+/// pub enum NoopRandom {}
+///
+/// pub struct NoopRandomInstance;
+///
+/// impl NoopRandomInstance {
+///     pub fn fetch_random(self, _subject: [u8; 32]) -> [u8; 32] {
+///         [0; 32]
+///     }
+/// }
+///
+/// impl ink::ChainExtensionInstance for NoopRandom {
+///     type Instance = NoopRandomInstance;
+///
+///     fn instantiate() -> Self::Instance {
+///         NoopRandomInstance
+///     }
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Eq)]
+/// #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// pub enum TestEnvironment {}
+///
+/// impl Environment for TestEnvironment {
+///     const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+///
+///     type AccountId = <DefaultEnvironment as Environment>::AccountId;
+///     type Balance = <DefaultEnvironment as Environment>::Balance;
+///     type Hash = <DefaultEnvironment as Environment>::Hash;
+///     type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+///     type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+///
+///     type ChainExtension = NoopRandom;
+/// }
+///
+/// let random = ink::EnvAccess::<TestEnvironment>::default()
+///     .extension()
+///     .fetch_random([1; 32]);
+/// assert_eq!(random, [0; 32]);
+/// ```
 pub trait ChainExtensionInstance {
     /// The type of the chain extension instance.
     type Instance;
@@ -36,6 +87,17 @@ pub trait ChainExtension {
     type ErrorCode: ink_env::chain_extension::FromStatusCode;
 }
 
+/// Provides the metadata of a chain extension.
+///
+/// # Note
+///
+/// This trait is automatically implemented when using `#[ink::chain_extension]` procedural macro.
+#[cfg(feature = "std")]
+pub trait ChainExtensionMetadata {
+    /// Returns the metadata of the chain extension.
+    fn chain_extension_spec() -> ink_metadata::ChainExtensionSpec;
+}
+
 /// Only implemented for `Result<T, E>`.
 ///
 /// Used to check at compile time if the chain extension method return type
@@ -0,0 +1,152 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities to return large sequences of bytes in chunks.
+//!
+//! The return value of a message is encoded into a static buffer of
+//! [`BUFFER_SIZE`](ink_env::BUFFER_SIZE) bytes, and returning a larger value traps
+//! the contract. An ink! message flagged with `#[ink(message, chunked)]` instead
+//! takes the offset of a [`Chunk`] as its first input and returns at most
+//! [`MAX_LEN`] bytes per call, so that clients fetch large blobs chunk by chunk.
+//!
+//! # Example
+//!
+//! ```
+//! # #[ink::contract]
+//! # mod my_module {
+//! use ink::{
+//!     chunk::{
+//!         self,
+//!         Chunk,
+//!     },
+//!     storage::collections::StorageBlob,
+//! };
+//!
+//! #[ink(storage)]
+//! #[derive(Default)]
+//! pub struct Archive {
+//!     blob: StorageBlob,
+//! }
+//!
+//! impl Archive {
+//!     #[ink(constructor)]
+//!     pub fn new() -> Self {
+//!         Self::default()
+//!     }
+//!
+//!     #[ink(message)]
+//!     pub fn upload(&mut self, data: Vec<u8>) {
+//!         self.blob.append(&data);
+//!     }
+//!
+//!     /// Returns the chunk of the blob at `offset`.
+//!     #[ink(message, chunked)]
+//!     pub fn blob_chunk(&self, offset: u32) -> Chunk {
+//!         chunk::read(self.blob.len(), offset, |range| {
+//!             self.blob
+//!                 .read_range(range.start, range.end - range.start)
+//!                 .unwrap_or_default()
+//!         })
+//!     }
+//! }
+//! # }
+//! ```
+
+use core::ops::Range;
+use ink_prelude::vec::Vec;
+
+pub use ink_primitives::Chunk;
+
+/// The bytes encoded around the data of a chunk returned by an ink! message.
+///
+/// Consists of the `Ok` of the message result, an optional `Some` or `Ok` of the
+/// message's own output, the offset and total length and the compact encoded length
+/// of the data.
+const OVERHEAD: usize = 1 + 1 + 4 + 4 + 4;
+
+/// The maximum number of bytes in a chunk, so that a returned chunk fits into the
+/// static buffer.
+pub const MAX_LEN: u32 = (ink_env::BUFFER_SIZE - OVERHEAD) as u32;
+
+/// Returns the range of the chunk at `offset` of a sequence of `total_len` bytes.
+///
+/// The range is empty if `offset` is beyond the sequence.
+pub fn range(total_len: u32, offset: u32) -> Range<u32> {
+    let start = offset.min(total_len);
+    let end = start.saturating_add(MAX_LEN).min(total_len);
+    start..end
+}
+
+/// Returns the chunk of `bytes` at `offset`.
+///
+/// # Panics
+///
+/// If `bytes` is longer than `u32::MAX` bytes.
+pub fn slice(bytes: &[u8], offset: u32) -> Chunk {
+    let total_len =
+        u32::try_from(bytes.len()).expect("the sequence is longer than u32::MAX bytes");
+    let range = range(total_len, offset);
+    let data = bytes[range.start as usize..range.end as usize].to_vec();
+    Chunk::new(offset, total_len, data)
+}
+
+/// Returns the chunk at `offset` of a sequence of `total_len` bytes, reading the
+/// bytes of the chunk's range with `read`, e.g. from a
+/// [`StorageBlob`](crate::storage::collections::StorageBlob).
+///
+/// # Panics
+///
+/// If `read` returns more bytes than the range contains.
+pub fn read<F>(total_len: u32, offset: u32, read: F) -> Chunk
+where
+    F: FnOnce(Range<u32>) -> Vec<u8>,
+{
+    let range = range(total_len, offset);
+    let len = range.end - range.start;
+    let data = read(range);
+    assert!(
+        data.len() <= len as usize,
+        "read more bytes than the chunk contains"
+    );
+    Chunk::new(offset, total_len, data)
+}
+
+/// Reassembles a sequence of bytes from its chunks, fetching the chunk at a given
+/// offset with `fetch`, e.g. by calling an ink! message flagged with `chunked`.
+///
+/// # Panics
+///
+/// If a chunk which is not the last one is empty, since the sequence could not
+/// be reassembled.
+pub fn collect<F>(mut fetch: F) -> Vec<u8>
+where
+    F: FnMut(u32) -> Chunk,
+{
+    let mut bytes = Vec::new();
+    let mut offset = 0;
+    loop {
+        let chunk = fetch(offset);
+        match chunk.next_offset() {
+            Some(next) => {
+                assert!(next > offset, "encountered an empty chunk at {}", offset);
+                offset = next;
+                bytes.extend(chunk.into_data());
+            }
+            None => {
+                bytes.extend(chunk.into_data());
+                return bytes
+            }
+        }
+    }
+}
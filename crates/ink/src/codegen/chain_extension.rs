@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ChainExtensionMetadata;
+use core::marker::PhantomData;
+use ink_metadata::ChainExtensionSpec;
+
+/// Queries the metadata of the chain extension `T` if it provides any.
+///
+/// # Note
+///
+/// The chain extension of an environment is not required to be defined via
+/// `#[ink::chain_extension]`, e.g. `type ChainExtension = ()`. The inherent
+/// [`ChainExtensionSpecOf::spec`] is only applicable if `T` implements
+/// [`ChainExtensionMetadata`]. Otherwise the method resolution falls back to
+/// [`ChainExtensionSpecFallback::spec`] which yields no metadata.
+///
+/// Used by the metadata generated by the `#[ink::contract]` procedural macro.
+pub struct ChainExtensionSpecOf<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ChainExtensionSpecOf<T> {
+    /// Creates a new chain extension metadata query for `T`.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> ChainExtensionSpecOf<T>
+where
+    T: ChainExtensionMetadata,
+{
+    /// Returns the metadata of the chain extension `T`.
+    pub fn spec(&self) -> Option<ChainExtensionSpec> {
+        Some(<T as ChainExtensionMetadata>::chain_extension_spec())
+    }
+}
+
+/// Yields no metadata for chain extensions that do not provide any.
+///
+/// See [`ChainExtensionSpecOf`] for more information.
+pub trait ChainExtensionSpecFallback {
+    /// Returns `None` since the chain extension provides no metadata.
+    fn spec(&self) -> Option<ChainExtensionSpec> {
+        None
+    }
+}
+
+impl<T> ChainExtensionSpecFallback for ChainExtensionSpecOf<T> {}
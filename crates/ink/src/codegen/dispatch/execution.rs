@@ -12,11 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::reflect::DispatchError;
+use crate::reflect::{
+    ContractEnv,
+    DecodeDispatch,
+    DispatchError,
+    ExecuteMulticall,
+};
 use ink_env::Environment;
+use ink_prelude::vec::Vec;
+use ink_primitives::{
+    MulticallCall,
+    MulticallError,
+    MulticallErrorKind,
+    MulticallPaidCall,
+    MulticallResult,
+    Selector,
+};
 
 /// Returns `Ok` if the caller did not transfer additional value to the callee.
 ///
+/// Within the batch of an ink! multicall message only the value attributed to the
+/// call is considered, see [`ink_env::attributed_value`].
+///
 /// # Errors
 ///
 /// If the caller did send some amount of transferred value to the callee.
@@ -25,9 +42,280 @@ pub fn deny_payment<E>() -> Result<(), DispatchError>
 where
     E: Environment,
 {
-    let transferred = ink_env::transferred_value::<E>();
+    let transferred = ink_env::attributed_value::<E>();
     if transferred != <E as Environment>::Balance::from(0_u32) {
         return Err(DispatchError::PaidUnpayableMessage)
     }
     Ok(())
 }
+
+/// Returns `Ok` if no bytes remain in the `input`.
+///
+/// # Errors
+///
+/// If the `input` has not been consumed entirely.
+#[inline]
+pub fn ensure_input_consumed<I>(input: &mut I) -> Result<(), DispatchError>
+where
+    I: scale::Input,
+{
+    let remaining = match input.remaining_len() {
+        Ok(Some(len)) => len > 0,
+        _ => input.read_byte().is_ok(),
+    };
+    if remaining {
+        return Err(DispatchError::InvalidParameters)
+    }
+    Ok(())
+}
+
+/// Decodes the arguments of the ink! message `label` from `input`, which must not
+/// exceed `max_input` bytes.
+///
+/// If the length of the remaining `input` is unknown, at most `max_input` bytes are
+/// read while decoding the arguments.
+///
+/// # Errors
+///
+/// - If the input is larger than `max_input`.
+/// - If the arguments could not be decoded.
+#[inline]
+pub fn decode_bounded_dispatch_input<T, I>(
+    label: &str,
+    input: &mut I,
+    max_input: usize,
+) -> Result<T, DispatchError>
+where
+    T: scale::Decode,
+    I: scale::Input,
+{
+    match input.remaining_len() {
+        Ok(Some(len)) if len > max_input => Err(DispatchError::InputTooLarge),
+        Ok(Some(_)) => decode_dispatch_input(label, input),
+        _ => {
+            let mut input = BoundedInput {
+                input,
+                remaining: max_input,
+                exceeded: false,
+            };
+            decode_dispatch_input(label, &mut input).map_err(|error| {
+                if input.exceeded {
+                    DispatchError::InputTooLarge
+                } else {
+                    error
+                }
+            })
+        }
+    }
+}
+
+/// An input of unknown length that fails once more than a maximum number of bytes
+/// is read from it.
+struct BoundedInput<'a, I> {
+    /// The wrapped input.
+    input: &'a mut I,
+    /// The number of bytes that may still be read.
+    remaining: usize,
+    /// Whether a read exceeded the maximum number of bytes.
+    exceeded: bool,
+}
+
+impl<I> scale::Input for BoundedInput<'_, I>
+where
+    I: scale::Input,
+{
+    fn remaining_len(&mut self) -> Result<Option<usize>, scale::Error> {
+        self.input.remaining_len()
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> Result<(), scale::Error> {
+        if into.len() > self.remaining {
+            self.exceeded = true;
+            return Err("input exceeds the maximum input size".into())
+        }
+        self.remaining -= into.len();
+        self.input.read(into)
+    }
+
+    fn descend_ref(&mut self) -> Result<(), scale::Error> {
+        self.input.descend_ref()
+    }
+
+    fn ascend_ref(&mut self) {
+        self.input.ascend_ref()
+    }
+}
+
+/// Decodes the arguments of the ink! message or constructor `label` from `input`.
+///
+/// If debug messages are enabled, a failure is reported together with the expected
+/// type and the byte offset within the arguments at which decoding failed.
+///
+/// # Errors
+///
+/// If the arguments could not be decoded.
+#[inline]
+pub fn decode_dispatch_input<T, I>(label: &str, input: &mut I) -> Result<T, DispatchError>
+where
+    T: scale::Decode,
+    I: scale::Input,
+{
+    #[cfg(any(feature = "ink-debug", feature = "std"))]
+    let len = input.remaining_len().ok().flatten();
+    <T as scale::Decode>::decode(input).map_err(|_error| {
+        #[cfg(any(feature = "ink-debug", feature = "std"))]
+        {
+            let offset = len
+                .zip(input.remaining_len().ok().flatten())
+                .map(|(len, remaining)| len - remaining);
+            ink_env::debug::report_decode_error::<T>(
+                &ink_prelude::format!("arguments of `{}`", label),
+                offset,
+                &_error,
+            );
+        }
+        #[cfg(not(any(feature = "ink-debug", feature = "std")))]
+        let _ = label;
+        DispatchError::InvalidParameters
+    })
+}
+
+/// Decodes a dispatchable while preserving the [`DispatchError`] upon failure.
+///
+/// Decoding via `scale::Decode` discards the kind of the error, which is required
+/// in order to report the appropriate [`LangError`](ink_primitives::LangError).
+pub struct DecodedDispatch<T>(pub Result<T, DispatchError>);
+
+impl<T> scale::Decode for DecodedDispatch<T>
+where
+    T: DecodeDispatch,
+{
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Self(T::decode_dispatch(input)))
+    }
+}
+
+/// A call of the batch of an ink! multicall message.
+///
+/// Implemented for [`MulticallCall`] and [`MulticallPaidCall`].
+pub trait MulticallInput<Balance> {
+    /// Splits the call into the selector, the attributed value, if any, and the
+    /// SCALE encoded arguments of the called ink! message.
+    fn into_parts(self) -> (Selector, Option<Balance>, Vec<u8>);
+}
+
+impl<Balance> MulticallInput<Balance> for MulticallCall {
+    #[inline]
+    fn into_parts(self) -> (Selector, Option<Balance>, Vec<u8>) {
+        let (selector, input) = self;
+        (selector, None, input)
+    }
+}
+
+impl<Balance> MulticallInput<Balance> for MulticallPaidCall<Balance> {
+    #[inline]
+    fn into_parts(self) -> (Selector, Option<Balance>, Vec<u8>) {
+        let (selector, value, input) = self;
+        (selector, Some(value), input)
+    }
+}
+
+/// The environment of the ink! multicall message decoder `D`.
+type EnvOf<D> = <<D as ExecuteMulticall>::Storage as ContractEnv>::Env;
+
+/// The balance type of the environment of the ink! multicall message decoder `D`.
+type BalanceOf<D> = <EnvOf<D> as Environment>::Balance;
+
+/// Executes the `calls` of an ink! multicall message one after another on `storage`.
+///
+/// Returns the SCALE encoded outputs of all calls in order.
+///
+/// Calls that attribute a value to themselves are executed with that value as
+/// their [`ink_env::attributed_value`], so that payable ink! messages check the
+/// payment meant for them instead of the value transferred to the whole batch.
+///
+/// # Errors
+///
+/// - If the attributed values do not add up to the transferred value. No call is
+///   executed in this case.
+/// - If one of the calls failed. The error holds the index of the failed call, the
+///   remaining calls are not executed.
+pub fn execute_multicall<D, C>(
+    storage: &mut <D as ExecuteMulticall>::Storage,
+    calls: Vec<C>,
+) -> MulticallResult
+where
+    D: DecodeDispatch + ExecuteMulticall,
+    <D as ExecuteMulticall>::Storage: ContractEnv,
+    C: MulticallInput<BalanceOf<D>>,
+{
+    let calls = calls
+        .into_iter()
+        .map(MulticallInput::into_parts)
+        .collect::<Vec<_>>();
+    ensure_valid_attribution::<EnvOf<D>>(
+        calls.iter().map(|(_, value, _)| value.as_ref()),
+    )?;
+    let mut outputs = Vec::with_capacity(calls.len());
+    for (index, (selector, value, input)) in calls.into_iter().enumerate() {
+        let error = |kind| {
+            MulticallError {
+                index: index as u32,
+                kind,
+            }
+        };
+        let mut call_data = Vec::with_capacity(4 + input.len());
+        call_data.extend_from_slice(&selector.to_bytes());
+        call_data.extend_from_slice(&input);
+        let dispatchable = D::decode_dispatch(&mut &call_data[..])
+            .map_err(|dispatch_error| error(dispatch_error.into()))?;
+        let output = match value {
+            Some(value) => {
+                ink_env::with_attributed_value::<EnvOf<D>, _, _>(value, || {
+                    dispatchable.execute_multicall(storage)
+                })
+            }
+            None => dispatchable.execute_multicall(storage),
+        }
+        .map_err(error)?;
+        outputs.push(output);
+    }
+    Ok(outputs)
+}
+
+/// Returns `Ok` if the attributed `values` of the calls of a batch add up to the
+/// transferred value.
+///
+/// Either all or none of the calls of a batch attribute a value. Batches that do
+/// not attribute any value to their calls are always valid.
+fn ensure_valid_attribution<'a, E>(
+    values: impl ExactSizeIterator<Item = Option<&'a E::Balance>>,
+) -> Result<(), MulticallError>
+where
+    E: Environment,
+{
+    let error = |index: usize| {
+        MulticallError {
+            index: index as u32,
+            kind: MulticallErrorKind::InvalidValueAttribution,
+        }
+    };
+    let len = values.len();
+    let mut values = values.enumerate().peekable();
+    if !matches!(values.peek(), Some((_, Some(_)))) {
+        return Ok(())
+    }
+    let transferred = ink_env::transferred_value::<E>();
+    let mut total = E::Balance::from(0_u32);
+    for (index, value) in values {
+        // `total` never exceeds `transferred`, so neither of the operations overflows.
+        match value {
+            Some(value) if *value <= transferred - total => total += *value,
+            _ => return Err(error(index)),
+        }
+    }
+    if total != transferred {
+        return Err(error(len))
+    }
+    Ok(())
+}
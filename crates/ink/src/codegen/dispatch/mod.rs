@@ -17,10 +17,20 @@ mod info;
 mod type_check;
 
 pub use self::{
-    execution::deny_payment,
+    execution::{
+        decode_bounded_dispatch_input,
+        decode_dispatch_input,
+        deny_payment,
+        ensure_input_consumed,
+        execute_multicall,
+        DecodedDispatch,
+        MulticallInput,
+    },
     info::ContractCallBuilder,
     type_check::{
+        ChunkedOutput,
         DispatchInput,
         DispatchOutput,
+        ReturnsChunk,
     },
 };
@@ -67,3 +67,36 @@ where
 pub struct DispatchOutput<T>(T)
 where
     T: scale::Encode + 'static;
+
+/// Used to check if `T` is allowed as output type of an ink! chunked message.
+///
+/// # Note
+///
+/// An ink! chunked message returns a [`Chunk`](crate::chunk::Chunk), optionally
+/// wrapped into an `Option` or a `Result`.
+///
+/// # Example
+///
+/// This compiles since a `Result` of a chunk is a chunked output.
+///
+/// ```
+/// # use ink::{chunk::Chunk, codegen::ChunkedOutput};
+/// const _: () = ink::codegen::utils::consume_type::<ChunkedOutput<Result<Chunk, u8>>>();
+/// ```
+///
+/// This fails to compile since `Vec<u8>` is no chunk.
+///
+/// ```compile_fail
+/// # use ink::codegen::ChunkedOutput;
+/// const _: () = ink::codegen::utils::consume_type::<ChunkedOutput<Vec<u8>>>();
+/// ```
+pub struct ChunkedOutput<T>(T)
+where
+    T: ReturnsChunk;
+
+/// Implemented by the output types allowed for ink! chunked messages.
+pub trait ReturnsChunk {}
+
+impl ReturnsChunk for crate::chunk::Chunk {}
+impl ReturnsChunk for Option<crate::chunk::Chunk> {}
+impl<E> ReturnsChunk for Result<crate::chunk::Chunk, E> {}
@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ErrorCodeMetadata;
+use core::marker::PhantomData;
+use ink_metadata::ErrorSpec;
+
+/// Queries the metadata of the error type of the message output `T` if it has
+/// stable error codes.
+///
+/// # Note
+///
+/// The inherent [`ErrorSpecsOf::error_specs`] is only applicable if `T` is a
+/// `Result<_, E>` and `E` implements [`ErrorCodeMetadata`]. Otherwise the method
+/// resolution falls back to [`ErrorSpecsFallback::error_specs`] which yields no
+/// metadata.
+///
+/// Used by the metadata generated by the `#[ink::contract]` procedural macro.
+pub struct ErrorSpecsOf<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ErrorSpecsOf<T> {
+    /// Creates a new error metadata query for the message output `T`.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, E> ErrorSpecsOf<Result<T, E>>
+where
+    E: ErrorCodeMetadata,
+{
+    /// Returns the metadata of the error type `E` and the error types nested in it.
+    pub fn error_specs(&self) -> Vec<ErrorSpec> {
+        <E as ErrorCodeMetadata>::error_specs()
+    }
+}
+
+/// Yields no metadata for message outputs without stable error codes.
+///
+/// See [`ErrorSpecsOf`] for more information.
+pub trait ErrorSpecsFallback {
+    /// Returns no metadata since the message output has no stable error codes.
+    fn error_specs(&self) -> Vec<ErrorSpec> {
+        Vec::new()
+    }
+}
+
+impl<T> ErrorSpecsFallback for ErrorSpecsOf<T> {}
@@ -14,19 +14,45 @@
 
 //! Definitions and facilities mainly used by the ink! codegen.
 
+#[cfg(feature = "std")]
+mod chain_extension;
 mod dispatch;
 mod env;
+#[cfg(feature = "std")]
+mod error_code;
 mod event;
 mod implies_return;
 mod trait_def;
 pub mod utils;
 
+/// Whether the ink! messages flagged with `#[ink(metadata = false)]` are included in
+/// the contract metadata, i.e. if the `full-metadata` crate feature is enabled.
+pub const FULL_METADATA: bool = cfg!(feature = "full-metadata");
+
+#[cfg(feature = "std")]
+pub use self::chain_extension::{
+    ChainExtensionSpecFallback,
+    ChainExtensionSpecOf,
+};
+#[cfg(feature = "std")]
+pub use self::error_code::{
+    ErrorSpecsFallback,
+    ErrorSpecsOf,
+};
 pub use self::{
     dispatch::{
+        decode_bounded_dispatch_input,
+        decode_dispatch_input,
         deny_payment,
+        ensure_input_consumed,
+        execute_multicall,
+        ChunkedOutput,
         ContractCallBuilder,
+        DecodedDispatch,
         DispatchInput,
         DispatchOutput,
+        MulticallInput,
+        ReturnsChunk,
     },
     env::{
         Env,
@@ -46,5 +72,6 @@ pub use self::{
         TraitCallForwarderFor,
         TraitMessagePayable,
         TraitMessageSelector,
+        TraitMock,
     },
 };
@@ -0,0 +1,26 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Implemented by the global trait info provider.
+///
+/// It is used to query the global trait mock which can be registered
+/// as a contract mock with the off-chain environment.
+///
+/// # Note
+///
+/// The trait mock is only generated for `std` builds.
+pub trait TraitMock {
+    /// The trait mock type.
+    type Mock: Default;
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod call_builder;
+mod mock;
 mod trait_message;
 
 pub use self::{
@@ -21,6 +22,7 @@ pub use self::{
         TraitCallForwarder,
         TraitCallForwarderFor,
     },
+    mock::TraitMock,
     trait_message::{
         TraitMessagePayable,
         TraitMessageSelector,
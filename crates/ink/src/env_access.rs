@@ -34,21 +34,79 @@ use ink_env::{
 ///
 /// This allows ink! messages to make use of the environment efficiently
 /// and user friendly while also maintaining access invariants.
-#[derive(Copy, Clone)]
+///
+/// # Library code
+///
+/// The accessor is `Copy` and zero-sized, so it can be passed to modules outside
+/// of the `#[ink::contract]` instead of passing every required environment value,
+/// e.g. the caller, the block timestamp and the transferred value, separately.
+/// Outside of a contract, e.g. in off-chain tests, it is created with [`EnvAccess::new`].
+///
+/// ```
+/// use ink::{
+///     env::{
+///         DefaultEnvironment,
+///         Environment,
+///     },
+///     EnvAccess,
+/// };
+///
+/// /// Returns the fee to charge for a call, which is waived for the `owner`.
+/// pub fn fee<E: Environment>(env: EnvAccess<'_, E>, owner: &E::AccountId) -> E::Balance {
+///     if env.caller() == *owner {
+///         return 0u32.into()
+///     }
+///     env.transferred_value() / 100u32.into()
+/// }
+///
+/// ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+///     let env = EnvAccess::<DefaultEnvironment>::new();
+///     ink::env::test::set_value_transferred::<DefaultEnvironment>(1_000);
+///     assert_eq!(fee(env, &accounts.alice), 0);
+///     assert_eq!(fee(env, &accounts.bob), 10);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+///
+/// Within a contract the accessor returned by `self.env()` or `Self::env()` is
+/// passed instead, e.g. `fee(self.env(), &self.owner)`.
 pub struct EnvAccess<'a, E> {
     /// Tricks the Rust compiler into thinking that we use `E`.
     marker: PhantomData<fn() -> &'a E>,
 }
 
-impl<'a, E> Default for EnvAccess<'a, E> {
+impl<'a, E> EnvAccess<'a, E> {
+    /// Creates an accessor to the environment `E`.
+    ///
+    /// This is useful to call library code which takes the accessor from outside
+    /// of a contract, e.g. in tests. Within a contract, use `self.env()` or
+    /// `Self::env()` instead.
     #[inline]
-    fn default() -> Self {
+    pub const fn new() -> Self {
         Self {
-            marker: Default::default(),
+            marker: PhantomData,
         }
     }
 }
 
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<'a, E> Clone for EnvAccess<'a, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, E> Copy for EnvAccess<'a, E> {}
+
+impl<'a, E> Default for EnvAccess<'a, E> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a, E> core::fmt::Debug for EnvAccess<'a, E> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.debug_struct("EnvAccess").finish()
@@ -140,6 +198,43 @@ where
         ink_env::transferred_value::<E>()
     }
 
+    /// Returns the part of the transferred value attributed to the executed message.
+    ///
+    /// Within the batch of an ink! multicall message taking `ink::MulticallPaidCall`s
+    /// this is the value the caller attributed to the current call. Otherwise it is
+    /// the same as [`transferred_value`](Self::transferred_value).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract { deposited: Balance }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self { deposited: 0 }
+    /// #         }
+    /// #
+    /// /// Records the deposit, also when called as part of a batch.
+    /// #[ink(message, payable)]
+    /// pub fn deposit(&mut self) {
+    ///     self.deposited += self.env().attributed_value();
+    /// }
+    /// #
+    /// #     }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::attributed_value`]
+    pub fn attributed_value(self) -> E::Balance {
+        ink_env::attributed_value::<E>()
+    }
+
     /// Returns the price for the specified amount of gas.
     ///
     /// # Example
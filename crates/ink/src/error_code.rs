@@ -0,0 +1,39 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// An error type whose variants have stable numeric codes.
+///
+/// The codes are part of the ABI of a contract: client SDKs map failures to them
+/// without parsing strings, so a code must not change once it was released, even if
+/// variants are renamed or reordered.
+///
+/// # Note
+///
+/// This trait is automatically implemented when using `#[derive(ink::ErrorCode)]`.
+pub trait ErrorCode {
+    /// Returns the stable numeric code of the error.
+    fn error_code(&self) -> u32;
+}
+
+/// Provides the metadata of an error type with stable numeric codes.
+///
+/// # Note
+///
+/// This trait is automatically implemented when using `#[derive(ink::ErrorCode)]`.
+#[cfg(feature = "std")]
+pub trait ErrorCodeMetadata {
+    /// Returns the metadata of the error type followed by the metadata of the error
+    /// types nested in its variants.
+    fn error_specs() -> ink_prelude::vec::Vec<ink_metadata::ErrorSpec>;
+}
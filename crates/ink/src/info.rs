@@ -0,0 +1,82 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Uniform introspection of ink! smart contracts, e.g. for monitoring tooling.
+//!
+//! Contracts enable the [`ContractInfo`] messages with
+//! `#[ink::contract(contract_info = true)]`. Since the messages of an ink! trait
+//! definition have the same selectors in all contracts, tooling can query any
+//! such contract without knowing its metadata.
+
+use crate::{
+    prelude::string::String,
+    upgrade::Pausable,
+};
+
+/// Introspection messages implemented by `#[ink::contract(contract_info = true)]`.
+#[crate::trait_definition]
+pub trait ContractInfo {
+    /// Returns the version of the contract code, i.e. the version of its crate.
+    #[ink(message)]
+    fn code_version(&self) -> String;
+
+    /// Returns the fingerprint of the storage layout of the contract.
+    ///
+    /// See [`StorageFingerprint`](crate::upgrade::StorageFingerprint).
+    #[ink(message)]
+    fn storage_fingerprint(&self) -> [u8; 32];
+
+    /// Returns whether the contract is paused.
+    ///
+    /// Returns `None` if the contract does not implement
+    /// [`Pausable`](crate::upgrade::Pausable).
+    #[ink(message)]
+    fn is_paused(&self) -> Option<bool>;
+}
+
+/// Queries the paused status of a contract which might not be [`Pausable`].
+///
+/// # Note
+///
+/// This is an implementation detail of the generated [`ContractInfo::is_paused`].
+/// With both [`IsPausable`] and [`IsNotPausable`] in scope,
+/// `(&PausedStatus(storage)).get()` resolves to the former if the storage is
+/// [`Pausable`] and to the latter otherwise.
+#[doc(hidden)]
+pub struct PausedStatus<'a, S>(pub &'a S);
+
+#[doc(hidden)]
+pub trait IsPausable {
+    fn get(&self) -> Option<bool>;
+}
+
+impl<S> IsPausable for PausedStatus<'_, S>
+where
+    S: Pausable,
+{
+    fn get(&self) -> Option<bool> {
+        Some(self.0.paused())
+    }
+}
+
+#[doc(hidden)]
+pub trait IsNotPausable {
+    fn get(&self) -> Option<bool>;
+}
+
+impl<S> IsNotPausable for &PausedStatus<'_, S> {
+    fn get(&self) -> Option<bool> {
+        None
+    }
+}
@@ -18,6 +18,9 @@
 )]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+// Allows the ink! macros used within this crate to refer to it as `::ink`.
+extern crate self as ink;
+
 #[macro_use]
 #[doc(hidden)]
 pub mod result_info;
@@ -25,17 +28,26 @@ pub mod result_info;
 #[cfg_attr(not(feature = "show-codegen-docs"), doc(hidden))]
 pub mod codegen;
 
+pub mod budget;
+pub mod chunk;
+pub mod info;
 pub mod reflect;
+pub mod upgrade;
 
+#[cfg(feature = "std")]
+mod chain_config;
 mod chain_extension;
 mod contract_ref;
 mod env_access;
+mod error_code;
 
 pub use ink_env as env;
 #[cfg(feature = "std")]
 pub use ink_metadata as metadata;
 pub use ink_prelude as prelude;
 pub use ink_primitives as primitives;
+#[cfg(feature = "zk")]
+pub use ink_zk as zk;
 
 pub mod storage {
     pub mod traits {
@@ -48,11 +60,26 @@ pub mod storage {
         pub use ink_storage::traits::*;
     }
     pub use ink_storage::{
+        collections,
+        ensure_role,
+        key_encoder,
+        migration,
+        security,
+        ArithmeticError,
+        CachedMapping,
+        CheckedArithmetic,
         Lazy,
         Mapping,
+        NestedMapping,
     };
 }
 
+#[cfg(feature = "std")]
+pub use self::{
+    chain_config::ChainConfigMetadata,
+    chain_extension::ChainExtensionMetadata,
+    error_code::ErrorCodeMetadata,
+};
 pub use self::{
     chain_extension::{
         ChainExtensionInstance,
@@ -60,19 +87,31 @@ pub use self::{
     },
     contract_ref::ToAccountId,
     env_access::EnvAccess,
+    error_code::ErrorCode,
 };
 pub use ink_macro::{
     blake2x256,
+    chain_config,
     chain_extension,
     contract,
+    mock_contract,
     selector_bytes,
     selector_id,
+    shared,
     storage_item,
     test,
     trait_definition,
+    upgrade_guard,
+    ErrorCode,
 };
 pub use ink_primitives::{
     ConstructorResult,
     LangError,
+    MaybeOwned,
     MessageResult,
+    MulticallCall,
+    MulticallError,
+    MulticallErrorKind,
+    MulticallPaidCall,
+    MulticallResult,
 };
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use core::fmt::Display;
+use ink_prelude::vec::Vec;
+use ink_primitives::MulticallErrorKind;
 
 /// Reflects the number of dispatchable ink! messages and constructors respectively.
 ///
@@ -594,6 +596,31 @@ pub trait ExecuteDispatchable {
     fn execute_dispatchable(self) -> Result<(), DispatchError>;
 }
 
+/// Executes a decoded ink! message as part of the batch of an ink! multicall message.
+///
+/// # Note
+///
+/// Implemented by the ink! smart contract message decoder if the contract defines
+/// an ink! message flagged with `#[ink(multicall)]`.
+pub trait ExecuteMulticall {
+    /// The ink! smart contract storage the messages operate on.
+    type Storage;
+
+    /// Executes the ink! message on the already loaded `storage`.
+    ///
+    /// Returns the SCALE encoded output of the ink! message.
+    ///
+    /// # Errors
+    ///
+    /// - If the ink! message is the multicall message itself.
+    /// - If value was transferred but the ink! message is not payable.
+    /// - If the ink! message returned an `Err`.
+    fn execute_multicall(
+        self,
+        storage: &mut Self::Storage,
+    ) -> Result<Vec<u8>, MulticallErrorKind>;
+}
+
 /// An error that can occur during dispatch of ink! dispatchables.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum DispatchError {
@@ -607,6 +634,8 @@ pub enum DispatchError {
     CouldNotReadInput,
     /// Invalidly paid an unpayable dispatchable.
     PaidUnpayableMessage,
+    /// The parameters exceed the maximum input size of the selected dispatchable.
+    InputTooLarge,
 }
 
 impl Display for DispatchError {
@@ -625,6 +654,23 @@ impl DispatchError {
             Self::InvalidParameters => "unable to decode input",
             Self::CouldNotReadInput => "could not read input",
             Self::PaidUnpayableMessage => "paid an unpayable message",
+            Self::InputTooLarge => "input exceeds the maximum size",
+        }
+    }
+}
+
+impl From<DispatchError> for MulticallErrorKind {
+    #[inline]
+    fn from(error: DispatchError) -> Self {
+        match error {
+            DispatchError::InvalidSelector | DispatchError::UnknownSelector => {
+                Self::UnknownSelector
+            }
+            DispatchError::InvalidParameters | DispatchError::CouldNotReadInput => {
+                Self::InvalidParameters
+            }
+            DispatchError::PaidUnpayableMessage => Self::PaidUnpayableMessage,
+            DispatchError::InputTooLarge => Self::InputTooLarge,
         }
     }
 }
@@ -649,6 +695,7 @@ impl From<DispatchError> for scale::Error {
 /// - `InvalidSelector`: The first four bytes could not properly decoded into the selector.
 /// - `UnknownSelector`: The decoded selector did not match any of the expected ones.
 /// - `InvalidParameters`: Failed to decoded the parameters for the selected dispatchable.
+/// - `InputTooLarge`: The parameters exceed the maximum input size of the selected dispatchable.
 ///
 /// The other dispatch errors are handled by other structures usually.
 ///
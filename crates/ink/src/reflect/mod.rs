@@ -47,6 +47,7 @@ pub use self::{
         DispatchableConstructorInfo,
         DispatchableMessageInfo,
         ExecuteDispatchable,
+        ExecuteMulticall,
     },
     event::ContractEventBase,
     trait_def::{
@@ -0,0 +1,82 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities to safely upgrade the code of an ink! smart contract.
+//!
+//! Upgrading the code of a contract via `set_code_hash` keeps its storage, so the
+//! new code must lay out the storage in the same way and no message must observe
+//! the storage while it is migrated. Use [`set_code_hash_guarded`] or flag the
+//! upgrade message with `#[ink::upgrade_guard]` to enforce both in code.
+
+pub use ink_primitives::UpgradeError;
+
+/// Implemented by contracts that can be paused.
+///
+/// Contracts pausing via an [`ink::storage::security::pausable::Pausable`] field implement it
+/// by forwarding to the field.
+///
+/// [`ink::storage::security::pausable::Pausable`]: ink_storage::security::pausable::Pausable
+pub trait Pausable {
+    /// Returns `true` if the contract is currently paused.
+    fn paused(&self) -> bool;
+}
+
+impl<E, KeyType> Pausable for ink_storage::security::pausable::Pausable<E, KeyType>
+where
+    E: ink_env::Environment,
+    KeyType: ink_storage::traits::StorageKey,
+{
+    fn paused(&self) -> bool {
+        self.is_paused()
+    }
+}
+
+/// Implemented by the `#[ink::contract]` macro for the storage struct of a contract.
+///
+/// # Note
+///
+/// The fingerprint is the BLAKE2b 256-bit hash of the definition of the storage
+/// struct. It changes whenever the name of the storage struct or the name, type or
+/// order of one of its fields changes.
+pub trait StorageFingerprint {
+    /// The fingerprint of the storage struct definition.
+    const FINGERPRINT: [u8; 32];
+}
+
+/// Sets the code hash of the contract if it is safe to upgrade it.
+///
+/// # Errors
+///
+/// - If the contract is not paused.
+/// - If `fingerprint` does not match the storage fingerprint of the contract.
+/// - If setting the code hash failed, e.g. because no code is stored under it.
+pub fn set_code_hash_guarded<S, H>(
+    storage: &S,
+    code_hash: &H,
+    fingerprint: &[u8; 32],
+) -> Result<(), UpgradeError>
+where
+    S: Pausable + StorageFingerprint,
+    H: AsRef<[u8]>,
+{
+    if !storage.paused() {
+        return Err(UpgradeError::NotPaused)
+    }
+    if fingerprint != &<S as StorageFingerprint>::FINGERPRINT {
+        return Err(UpgradeError::FingerprintMismatch)
+    }
+    let code_hash = <[u8; 32]>::try_from(code_hash.as_ref())
+        .map_err(|_| UpgradeError::SetCodeHashFailed)?;
+    ink_env::set_code_hash(&code_hash).map_err(|_| UpgradeError::SetCodeHashFailed)
+}
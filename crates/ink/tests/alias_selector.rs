@@ -0,0 +1,120 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod counter {
+    #[ink(storage)]
+    pub struct Counter {
+        value: u32,
+    }
+
+    impl Counter {
+        /// Creates a new `Counter` starting at `0`.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: 0 }
+        }
+
+        /// Increases the counter by `by`.
+        ///
+        /// Also callable with the selector `0xDEADBEEF` of a legacy interface.
+        #[ink(message, alias_selector = 0xDEADBEEF)]
+        pub fn increment(&mut self, by: u32) {
+            self.value += by;
+        }
+
+        /// Returns the current value.
+        #[ink(message)]
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+
+        /// Dispatches the given calls in order.
+        #[ink(message, multicall)]
+        pub fn multicall(
+            &mut self,
+            calls: Vec<ink::MulticallCall>,
+        ) -> ink::MulticallResult {
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::{
+            env::call::Selector,
+            reflect::ContractMessageDecoder,
+        };
+        use scale::{
+            Decode,
+            Encode,
+        };
+
+        #[ink::test]
+        fn alias_selector_dispatches_to_message() {
+            // given
+            let mut counter = Counter::new();
+
+            // when
+            let outputs = counter
+                .multicall(vec![
+                    (Selector::from_label("increment"), 1u32.encode()),
+                    (Selector::new([0xDE, 0xAD, 0xBE, 0xEF]), 2u32.encode()),
+                    (Selector::from_label("get"), Vec::new()),
+                ])
+                .expect("multicall must not fail");
+
+            // then
+            assert_eq!(outputs[2], 3u32.encode());
+            assert_eq!(counter.get(), 3);
+        }
+
+        #[ink::test]
+        fn alias_selector_decodes_message_input() {
+            let decode = |input: Vec<u8>| {
+                <<Counter as ContractMessageDecoder>::Type as Decode>::decode(
+                    &mut &input[..],
+                )
+                .is_ok()
+            };
+            assert!(decode((0xDEADBEEFu32.to_be_bytes(), 1u32).encode()));
+            assert!(!decode(0xDEADBEEFu32.to_be_bytes().encode()));
+            assert!(!decode((0xDEADBEEEu32.to_be_bytes(), 1u32).encode()));
+        }
+    }
+}
+
+#[test]
+fn alias_selector_is_recorded_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let messages = project.spec().messages();
+    let alias_selector = |label: &str| {
+        messages
+            .iter()
+            .find(|message| message.label() == label)
+            .unwrap()
+            .alias_selector()
+            .map(|selector| selector.to_bytes().to_vec())
+    };
+    assert_eq!(
+        alias_selector("increment"),
+        Some(vec![0xDE, 0xAD, 0xBE, 0xEF])
+    );
+    assert_eq!(alias_selector("get"), None);
+}
@@ -0,0 +1,97 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod orders {
+    #[ink(storage)]
+    pub struct Orders {
+        limit: u32,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        Rejected,
+    }
+
+    impl Orders {
+        #[ink(constructor)]
+        pub fn new(#[ink(arg(default = 100))] limit: u32) -> Self {
+            Self { limit }
+        }
+
+        /// Places an order, optionally with a price limit.
+        #[ink(message)]
+        pub fn place(
+            &mut self,
+            amount: u32,
+            #[ink(arg(default = "None"))] price_limit: Option<Balance>,
+            #[ink(arg(default = "Some(Ok(true))"))] flag: Option<Result<bool, Error>>,
+        ) -> bool {
+            let _ = (price_limit, flag);
+            amount <= self.limit
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn defaults_do_not_affect_calls() {
+            let mut orders = Orders::new(10);
+            assert!(orders.place(10, Some(1), None));
+            assert!(!orders.place(11, None, Some(Err(Error::Rejected))));
+        }
+    }
+}
+
+#[test]
+fn arg_defaults_and_display_hints_are_in_metadata() {
+    use ink::metadata::DisplayHint;
+    use scale::Encode as _;
+
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let constructor_args = project.spec().constructors()[0].args();
+    assert_eq!(
+        constructor_args[0]
+            .default()
+            .map(|default| default.to_bytes()),
+        Some(&100u32.encode()[..])
+    );
+    assert!(constructor_args[0].display_hints().is_empty());
+
+    let args = project.spec().messages()[0].args();
+    assert_eq!(args.len(), 3);
+    assert_eq!(args[0].default(), None);
+    assert!(args[0].display_hints().is_empty());
+    assert_eq!(
+        args[1].default().map(|default| default.to_bytes()),
+        Some(&None::<u128>.encode()[..])
+    );
+    assert_eq!(args[1].display_hints(), [DisplayHint::Optional]);
+    assert_eq!(
+        args[2].default().map(|default| default.to_bytes()),
+        Some(&Some(Ok::<bool, ()>(true)).encode()[..])
+    );
+    assert_eq!(
+        args[2].display_hints(),
+        [DisplayHint::Optional, DisplayHint::Result]
+    );
+}
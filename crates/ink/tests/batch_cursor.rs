@@ -0,0 +1,117 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod rewards {
+    use ink::{
+        budget::{
+            Budget,
+            OutOfBudget,
+        },
+        storage::{
+            collections::{
+                BatchCursor,
+                IterableMapping,
+            },
+            Mapping,
+        },
+    };
+
+    /// The gas charged for every reward in the tests.
+    pub const GAS_PER_REWARD: u64 = 10;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Rewards {
+        stakes: IterableMapping<AccountId, Balance>,
+        rewards: Mapping<AccountId, Balance>,
+        distribution: BatchCursor,
+    }
+
+    impl Rewards {
+        #[ink(constructor)]
+        pub fn new(stakers: u8) -> Self {
+            let mut instance = Self::default();
+            for staker in 1..=stakers {
+                instance
+                    .stakes
+                    .insert(AccountId::from([staker; 32]), &Balance::from(staker));
+            }
+            instance
+        }
+
+        /// Distributes `rate` per staked unit, resuming an interrupted distribution.
+        #[ink(message)]
+        pub fn distribute(&mut self, rate: Balance) -> Result<(), OutOfBudget<u32>> {
+            let budget = Budget::<Environment>::new(25);
+            let rewards = &mut self.rewards;
+            budget.run_batch_over(
+                &mut self.distribution,
+                &self.stakes,
+                |account, stake| {
+                    ink::env::test::charge_gas(GAS_PER_REWARD);
+                    let reward = rewards.get(account).unwrap_or_default();
+                    rewards.insert(account, &(reward + stake * rate));
+                },
+            )
+        }
+
+        #[ink(message)]
+        pub fn is_distributing(&self) -> bool {
+            self.distribution.is_running()
+        }
+
+        #[ink(message)]
+        pub fn reward_of(&self, account: AccountId) -> Balance {
+            self.rewards.get(account).unwrap_or_default()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+
+        fn rewards(contract: &Rewards, stakers: u8) -> Vec<Balance> {
+            (1..=stakers)
+                .map(|staker| contract.reward_of(AccountId::from([staker; 32])))
+                .collect()
+        }
+
+        #[ink::test]
+        fn distribution_resumes_after_exhausted_budget() {
+            let mut contract = Rewards::new(8);
+
+            test::set_gas_limit(Some(75));
+            assert_eq!(contract.distribute(2), Err(OutOfBudget { resume_at: 5 }));
+            assert!(contract.is_distributing());
+            assert_eq!(rewards(&contract, 8), [2, 4, 6, 8, 10, 0, 0, 0]);
+
+            test::set_gas_limit(None);
+            assert_eq!(contract.distribute(2), Ok(()));
+            assert!(!contract.is_distributing());
+            assert_eq!(rewards(&contract, 8), [2, 4, 6, 8, 10, 12, 14, 16]);
+        }
+
+        #[ink::test]
+        fn finished_distribution_starts_over() {
+            let mut contract = Rewards::new(3);
+            assert_eq!(contract.distribute(1), Ok(()));
+            assert_eq!(contract.distribute(1), Ok(()));
+            assert_eq!(rewards(&contract, 3), [2, 4, 6]);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod order_book {
+    use ink::primitives::{
+        BoundedString,
+        BoundedVec,
+        CapacityError,
+    };
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct OrderBook {
+        name: BoundedString<16>,
+        orders: BoundedVec<u64, 2>,
+    }
+
+    impl OrderBook {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        #[ink(message)]
+        pub fn place(&mut self, order_id: u64) -> Result<(), CapacityError> {
+            self.orders.try_push(order_id)
+        }
+
+        #[ink(message)]
+        pub fn orders(&self) -> BoundedVec<u64, 2> {
+            self.orders.clone()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn inserts_beyond_capacity_fail() {
+            let mut contract = OrderBook::new();
+            assert_eq!(contract.place(1), Ok(()));
+            assert_eq!(contract.place(2), Ok(()));
+            assert_eq!(contract.place(3), Err(CapacityError));
+            assert_eq!(&contract.orders()[..], &[1, 2]);
+        }
+    }
+}
+
+#[test]
+fn capacities_are_included_in_layout() {
+    use ink::metadata::layout::Layout;
+
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let Layout::Root(root) = project.layout() else {
+        panic!("expected a root layout")
+    };
+    let Layout::Struct(storage) = root.layout() else {
+        panic!("expected a struct layout")
+    };
+    let capacity = |name: &str| {
+        let field = storage
+            .fields()
+            .iter()
+            .find(|field| field.name() == name)
+            .unwrap();
+        match field.layout() {
+            Layout::Leaf(leaf) => leaf.capacity(),
+            _ => panic!("expected a leaf layout"),
+        }
+    };
+    assert_eq!(capacity("name"), Some(16));
+    assert_eq!(capacity("orders"), Some(2));
+}
@@ -0,0 +1,112 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod summer {
+    use ink::{
+        budget::{
+            Budget,
+            OutOfBudget,
+        },
+        storage::{
+            collections::StorageVec,
+            Lazy,
+        },
+    };
+
+    /// The gas charged for every summand in the tests.
+    pub const GAS_PER_SUMMAND: u64 = 10;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Summer {
+        summands: StorageVec<u32>,
+        sum: Lazy<u64>,
+    }
+
+    impl Summer {
+        #[ink(constructor)]
+        pub fn new(summands: Vec<u32>) -> Self {
+            let mut instance = Self::default();
+            for summand in summands {
+                instance.summands.push(&summand);
+            }
+            instance
+        }
+
+        /// Adds the summands from `offset` on to the sum.
+        #[ink(message)]
+        pub fn sum_from(&mut self, offset: u32) -> Result<u64, OutOfBudget<u32>> {
+            let budget = Budget::<Environment>::new(25);
+            let mut sum = self.sum.get_or_default();
+            let mut index = offset;
+            let result = loop {
+                if let Err(error) = budget.checkpoint_at(index) {
+                    break Err(error)
+                }
+                match self.summands.get(index) {
+                    Some(summand) => {
+                        ink::env::test::charge_gas(GAS_PER_SUMMAND);
+                        sum += u64::from(summand);
+                        index += 1;
+                    }
+                    None => break Ok(sum),
+                }
+            };
+            self.sum.set(&sum);
+            result
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+
+        #[ink::test]
+        fn unlimited_budget_is_never_exhausted() {
+            let budget = Budget::<Environment>::new(u64::MAX - 1);
+            assert!(!budget.is_exhausted());
+            assert_eq!(budget.checkpoint(), Ok(()));
+        }
+
+        #[ink::test]
+        fn processing_resumes_after_exhausted_budget() {
+            let mut contract = Summer::new((1..=10).collect());
+
+            test::set_gas_limit(Some(75));
+            assert_eq!(contract.sum_from(0), Err(OutOfBudget { resume_at: 5 }));
+            assert_eq!(test::gas_consumed(), 50);
+
+            test::set_gas_limit(None);
+            assert_eq!(contract.sum_from(5), Ok(55));
+        }
+
+        #[ink::test]
+        fn checkpoint_fails_at_reserve() {
+            let budget = Budget::<Environment>::new(10);
+            test::set_gas_limit(Some(11));
+            assert_eq!(budget.remaining(), 1);
+            assert_eq!(budget.checkpoint_at("next"), Ok(()));
+            test::charge_gas(1);
+            assert!(budget.is_exhausted());
+            assert_eq!(
+                budget.checkpoint_at("next"),
+                Err(OutOfBudget { resume_at: "next" })
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub struct MyChainConfig;
+
+#[ink::chain_config]
+impl MyChainConfig {
+    /// The denominator of all fees charged by the chain.
+    pub const FEE_DENOMINATOR: u128 = 1_000;
+    /// The decimals of the native token of the chain.
+    pub const DECIMALS: u8 = 12;
+}
+
+#[ink::contract(config = crate::MyChainConfig)]
+mod exchange {
+    #[ink(storage)]
+    pub struct Exchange {
+        fee: Balance,
+    }
+
+    impl Exchange {
+        /// Creates a new `Exchange` charging `fee` per mille.
+        #[ink(constructor)]
+        pub fn new(fee: Balance) -> Self {
+            Self { fee }
+        }
+
+        /// Returns the fee charged for trading `amount`.
+        #[ink(message)]
+        pub fn fee_of(&self, amount: Balance) -> Balance {
+            amount * self.fee / ChainConfig::FEE_DENOMINATOR
+        }
+
+        /// Returns the decimals of the native token.
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            ChainConfig::DECIMALS
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn chain_config_constants_are_available() {
+            let exchange = Exchange::new(3);
+            assert_eq!(exchange.fee_of(10_000), 30);
+            assert_eq!(exchange.decimals(), 12);
+        }
+    }
+}
+
+#[test]
+fn chain_config_is_recorded_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let chain_config = project.spec().chain_config().unwrap();
+    assert_eq!(chain_config.label(), "MyChainConfig");
+    let constants = chain_config
+        .constants()
+        .iter()
+        .map(|constant| {
+            (
+                constant.label().as_str(),
+                constant.ty().display_name().segments.clone(),
+                constant.value().to_vec(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        constants,
+        vec![
+            (
+                "FEE_DENOMINATOR",
+                vec![String::from("u128")],
+                scale::Encode::encode(&1_000u128),
+            ),
+            ("DECIMALS", vec![String::from("u8")], vec![12]),
+        ]
+    );
+}
@@ -0,0 +1,158 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink::{
+    env::{
+        DefaultEnvironment,
+        Environment,
+    },
+    metadata::InkProject,
+    ChainExtensionMetadata,
+};
+
+#[ink::chain_extension]
+pub trait FetchRandom {
+    type ErrorCode = RandomReadErr;
+
+    #[ink(extension = 1101, returns_result = false, weight = 10_000)]
+    fn fetch_random(subject: [u8; 32]) -> [u8; 32];
+
+    #[ink(extension = 1102, returns_result = false)]
+    fn random_seed() -> u32;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RandomReadErr {
+    FailGetRandomSource,
+}
+
+impl ink::env::chain_extension::FromStatusCode for RandomReadErr {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            1 => Err(Self::FailGetRandomSource),
+            _ => panic!("encountered unknown status code"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CustomEnvironment {}
+
+impl Environment for CustomEnvironment {
+    const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Balance = <DefaultEnvironment as Environment>::Balance;
+    type Hash = <DefaultEnvironment as Environment>::Hash;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+    type ChainExtension = FetchRandom;
+}
+
+#[ink::contract(env = crate::CustomEnvironment)]
+mod rand_extension {
+    use super::RandomReadErr;
+
+    #[ink(storage)]
+    pub struct RandExtension {
+        value: [u8; 32],
+    }
+
+    impl RandExtension {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: [0; 32] }
+        }
+
+        /// Updates the stored value with a random value fetched from the chain.
+        #[ink(message)]
+        pub fn update(&mut self, subject: [u8; 32]) -> Result<(), RandomReadErr> {
+            self.value = self.env().extension().fetch_random(subject)?;
+            Ok(())
+        }
+
+        /// Returns the stored value.
+        #[ink(message)]
+        pub fn get(&self) -> [u8; 32] {
+            self.value
+        }
+    }
+}
+
+/// Returns the random value `[1; 32]` for every subject.
+struct MockedExtension;
+
+impl ink::env::test::ChainExtension for MockedExtension {
+    fn func_id(&self) -> u32 {
+        1101
+    }
+
+    fn call(&mut self, _input: &[u8], output: &mut Vec<u8>) -> u32 {
+        scale::Encode::encode_to(&[1u8; 32], output);
+        0
+    }
+}
+
+#[test]
+fn declared_weight_is_charged_off_chain() {
+    ink::env::test::register_chain_extension(MockedExtension);
+    let mut contract = rand_extension::RandExtension::new();
+    assert_eq!(ink::env::test::gas_consumed(), 0);
+
+    contract
+        .update([0; 32])
+        .expect("fetching the random value failed");
+    contract
+        .update([0; 32])
+        .expect("fetching the random value failed");
+
+    assert_eq!(contract.get(), [1; 32]);
+    assert_eq!(ink::env::test::gas_consumed(), 20_000);
+}
+
+#[test]
+fn chain_extension_metadata_works() {
+    let spec = <FetchRandom as ChainExtensionMetadata>::chain_extension_spec();
+    assert_eq!(*spec.label(), "FetchRandom");
+    let methods = spec
+        .methods()
+        .iter()
+        .map(|method| (*method.label(), method.id(), method.weight()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        methods,
+        vec![
+            ("fetch_random", 1101, Some(10_000)),
+            ("random_seed", 1102, None),
+        ]
+    );
+}
+
+#[test]
+fn contract_metadata_contains_chain_extension() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let chain_extension = project
+        .spec()
+        .chain_extension()
+        .expect("missing chain extension metadata");
+    assert_eq!(chain_extension.label(), "FetchRandom");
+    assert_eq!(chain_extension.methods()[0].weight(), Some(10_000));
+}
@@ -0,0 +1,119 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink::env::{
+    DefaultEnvironment,
+    Environment,
+};
+
+#[ink::chain_extension]
+pub trait FetchRandom {
+    type ErrorCode = RandomReadErr;
+
+    #[ink(extension = 1101, returns_result = false)]
+    fn fetch_random(subject: [u8; 32]) -> [u8; 32];
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RandomReadErr {
+    FailGetRandomSource,
+}
+
+impl ink::env::chain_extension::FromStatusCode for RandomReadErr {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            1 => Err(Self::FailGetRandomSource),
+            _ => panic!("encountered unknown status code"),
+        }
+    }
+}
+
+/// A hand-written replacement of `FetchRandom` answering every request with `[7; 32]`.
+pub enum NoopFetchRandom {}
+
+pub struct NoopFetchRandomInstance;
+
+impl NoopFetchRandomInstance {
+    pub fn fetch_random(self, _subject: [u8; 32]) -> Result<[u8; 32], RandomReadErr> {
+        Ok([7; 32])
+    }
+}
+
+impl ink::ChainExtensionInstance for NoopFetchRandom {
+    type Instance = NoopFetchRandomInstance;
+
+    fn instantiate() -> Self::Instance {
+        NoopFetchRandomInstance
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CustomEnvironment {}
+
+impl Environment for CustomEnvironment {
+    const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Balance = <DefaultEnvironment as Environment>::Balance;
+    type Hash = <DefaultEnvironment as Environment>::Hash;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+    #[cfg(not(test))]
+    type ChainExtension = FetchRandom;
+    #[cfg(test)]
+    type ChainExtension = NoopFetchRandom;
+}
+
+#[ink::contract(env = crate::CustomEnvironment)]
+mod rand_extension {
+    use super::RandomReadErr;
+
+    #[ink(storage)]
+    pub struct RandExtension {
+        value: [u8; 32],
+    }
+
+    impl RandExtension {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: [0; 32] }
+        }
+
+        /// Updates the stored value with a random value fetched from the chain.
+        #[ink(message)]
+        pub fn update(&mut self, subject: [u8; 32]) -> Result<(), RandomReadErr> {
+            self.value = self.env().extension().fetch_random(subject)?;
+            Ok(())
+        }
+
+        /// Returns the stored value.
+        #[ink(message)]
+        pub fn get(&self) -> [u8; 32] {
+            self.value
+        }
+    }
+}
+
+#[test]
+fn chain_extension_can_be_swapped_per_environment() {
+    let mut contract = rand_extension::RandExtension::new();
+    contract
+        .update([0; 32])
+        .expect("fetching the random value failed");
+    assert_eq!(contract.get(), [7; 32]);
+}
@@ -0,0 +1,135 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod archive {
+    use ink::{
+        chunk::{
+            self,
+            Chunk,
+        },
+        storage::collections::StorageBlob,
+    };
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Archive {
+        blob: StorageBlob,
+        notes: Vec<u8>,
+    }
+
+    impl Archive {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        #[ink(message)]
+        pub fn upload(&mut self, data: Vec<u8>) {
+            self.blob.append(&data);
+        }
+
+        #[ink(message)]
+        pub fn set_notes(&mut self, notes: Vec<u8>) {
+            self.notes = notes;
+        }
+
+        /// Returns the chunk of the blob at `offset`.
+        #[ink(message, chunked)]
+        pub fn blob_chunk(&self, offset: u32) -> Chunk {
+            chunk::read(self.blob.len(), offset, |range| {
+                self.blob
+                    .read_range(range.start, range.end - range.start)
+                    .unwrap_or_default()
+            })
+        }
+
+        /// Returns the chunk of the notes at `offset`, if there are any notes.
+        #[ink(message, chunked)]
+        pub fn notes_chunk(&self, offset: u32) -> Option<Chunk> {
+            (!self.notes.is_empty()).then(|| chunk::slice(&self.notes, offset))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn bytes(len: u32) -> Vec<u8> {
+            (0..len).map(|byte| byte as u8).collect()
+        }
+
+        #[ink::test]
+        fn large_blob_is_returned_in_chunks() {
+            let mut archive = Archive::new();
+            let blob = bytes(2 * chunk::MAX_LEN + 10);
+            archive.upload(blob.clone());
+
+            let first = archive.blob_chunk(0);
+            assert_eq!(first.data().len(), chunk::MAX_LEN as usize);
+            assert_eq!(first.next_offset(), Some(chunk::MAX_LEN));
+            let last = archive.blob_chunk(2 * chunk::MAX_LEN);
+            assert_eq!(last.data().len(), 10);
+            assert!(last.is_last());
+
+            let mut calls = 0;
+            let collected = chunk::collect(|offset| {
+                calls += 1;
+                archive.blob_chunk(offset)
+            });
+            assert_eq!(collected, blob);
+            assert_eq!(calls, 3);
+        }
+
+        #[ink::test]
+        fn chunks_fit_into_the_static_buffer() {
+            let mut archive = Archive::new();
+            archive.set_notes(bytes(chunk::MAX_LEN + 1));
+            let chunk = archive.notes_chunk(0);
+            let encoded = scale::Encode::encode(&ink::MessageResult::Ok(chunk));
+            assert!(encoded.len() <= ink::env::BUFFER_SIZE);
+        }
+
+        #[ink::test]
+        fn offsets_beyond_the_blob_return_empty_chunk() {
+            let mut archive = Archive::new();
+            archive.upload(bytes(5));
+            let chunk = archive.blob_chunk(7);
+            assert!(chunk.data().is_empty());
+            assert!(chunk.is_last());
+            assert_eq!(
+                chunk::collect(|offset| archive.blob_chunk(offset)),
+                bytes(5)
+            );
+        }
+    }
+}
+
+#[test]
+fn chunked_messages_are_flagged_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let chunked = project
+        .spec()
+        .messages()
+        .iter()
+        .filter(|message| message.chunked())
+        .map(|message| message.label().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(chunked, vec!["blob_chunk", "notes_chunk"]);
+}
@@ -28,6 +28,8 @@ fn ui_tests() {
     t.pass("tests/ui/contract/pass/*.rs");
     t.compile_fail("tests/ui/contract/fail/*.rs");
 
+    t.pass("tests/ui/shared/pass/*.rs");
+
     t.pass("tests/ui/storage_item/pass/*.rs");
     t.compile_fail("tests/ui/storage_item/fail/*.rs");
 
@@ -0,0 +1,84 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract(contract_info = true)]
+mod pausable {
+    #[ink(storage)]
+    pub struct Pausable {
+        paused: bool,
+    }
+
+    impl ink::upgrade::Pausable for Pausable {
+        fn paused(&self) -> bool {
+            self.paused
+        }
+    }
+
+    impl Pausable {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { paused: false }
+        }
+
+        #[ink(message)]
+        pub fn set_paused(&mut self, paused: bool) {
+            self.paused = paused;
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::{
+            env::{
+                call::{
+                    ExecutionInput,
+                    Selector,
+                },
+                test,
+            },
+            info::ContractInfo,
+            upgrade::StorageFingerprint,
+        };
+        use scale::Decode as _;
+
+        #[ink::test]
+        fn contract_info_works() {
+            let mut contract = Pausable::new();
+            assert_eq!(contract.code_version(), env!("CARGO_PKG_VERSION"));
+            assert_eq!(
+                contract.storage_fingerprint(),
+                <Pausable as StorageFingerprint>::FINGERPRINT
+            );
+            assert_eq!(contract.is_paused(), Some(false));
+            contract.set_paused(true);
+            assert_eq!(contract.is_paused(), Some(true));
+        }
+
+        #[ink::test]
+        fn contract_info_is_dispatched_by_trait_selector() {
+            test::deploy::<Pausable, _>(ExecutionInput::new(Selector::new(
+                ink::selector_bytes!("new"),
+            )))
+            .expect("constructor must not revert");
+            let output = test::dispatch::<Pausable, _>(ExecutionInput::new(
+                Selector::new(ink::selector_bytes!("ContractInfo::is_paused")),
+            ))
+            .unwrap();
+            assert_eq!(<Option<bool>>::decode(&mut &output[..]), Ok(Some(false)));
+        }
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod my_contract {
+    #[ink(storage)]
+    pub struct MyContract {
+        value: u32,
+    }
+
+    /// Emitted once the contract has been instantiated.
+    #[ink(event)]
+    pub struct Instantiated {
+        #[ink(topic)]
+        value: u32,
+    }
+
+    /// Emitted if the contract has been instantiated with a transferred value.
+    #[ink(event)]
+    pub struct InstantiatedWithTransferredValue {
+        value: u32,
+    }
+
+    /// Returned by the fallible constructor.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ZeroValue;
+
+    impl MyContract {
+        /// Creates a new `MyContract` instance.
+        #[ink(constructor)]
+        pub fn new(value: u32) -> Self {
+            Self::env().emit_event(Instantiated { value });
+            Self { value }
+        }
+
+        /// Creates a new `MyContract` instance if `value` is not zero.
+        #[ink(constructor)]
+        pub fn try_new(value: u32) -> Result<Self, ZeroValue> {
+            if value == 0 {
+                return Err(ZeroValue)
+            }
+            Ok(Self { value })
+        }
+
+        /// Creates a new `MyContract` instance with the transferred value.
+        #[ink(constructor, payable)]
+        pub fn with_transferred_value() -> Self {
+            let value = Self::env().transferred_value() as u32;
+            Self::env().emit_event(InstantiatedWithTransferredValue { value });
+            Self { value }
+        }
+
+        /// Returns the stored value.
+        #[ink(message)]
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            call::{
+                ExecutionInput,
+                Selector,
+            },
+            DefaultEnvironment,
+        };
+
+        fn input(
+            selector: [u8; 4],
+        ) -> ExecutionInput<ink::env::call::utils::EmptyArgumentList> {
+            ExecutionInput::new(Selector::new(selector))
+        }
+
+        static FIXTURE: ink::env::test::Fixture = ink::env::test::Fixture::new(|| {
+            ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new")).push_arg(7u32),
+            )
+            .expect("constructor must not revert");
+        });
+
+        #[ink::test]
+        fn fixture_restores_deployed_contract() {
+            FIXTURE.setup();
+
+            let contract =
+                ink::env::get_contract_storage::<ink::primitives::Key, MyContract>(
+                    &<MyContract as ink::storage::traits::StorageKey>::KEY,
+                )
+                .expect("could not decode contract")
+                .expect("contract was deployed by the fixture");
+            assert_eq!(contract.get(), 7);
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn deploy_runs_constructor() {
+            let contract = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new")).push_arg(42u32),
+            )
+            .expect("constructor must not revert");
+
+            assert_eq!(contract.get(), 42);
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn events_of_last_call_works() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            ink::env::test::set_callee::<DefaultEnvironment>(accounts.bob);
+            let _ = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new")).push_arg(1u32),
+            );
+            ink::env::test::set_callee::<DefaultEnvironment>(accounts.charlie);
+            let _ = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new")).push_arg(2u32),
+            );
+
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+            let events = ink::env::test::events_of_last_call().collect::<Vec<_>>();
+            assert_eq!(events.len(), 1);
+            assert_eq!(
+                events[0].decode_emitter::<DefaultEnvironment>(),
+                Some(accounts.charlie)
+            );
+            let expected_topic = ink::env::test::topic::<DefaultEnvironment, _>(
+                &ink::env::topics::PrefixedValue {
+                    prefix: b"MyContract::Instantiated::value",
+                    value: &2u32,
+                },
+            );
+            assert_eq!(
+                events[0].decode_topics::<DefaultEnvironment>()[1],
+                expected_topic
+            );
+        }
+
+        #[ink::test]
+        fn event_signature_works() {
+            assert_eq!(
+                Instantiated::SIGNATURE,
+                "MyContract::Instantiated(#[topic]value:u32)"
+            );
+            let _ = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new")).push_arg(1u32),
+            );
+            let events = ink::env::test::events_of_last_call().collect::<Vec<_>>();
+            let signature_topic = Instantiated::SIGNATURE_TOPIC
+                .expect("non-anonymous events must have a signature topic");
+            assert_eq!(
+                events[0].decode_topics::<DefaultEnvironment>()[0],
+                signature_topic.into()
+            );
+        }
+
+        #[ink::test]
+        fn deploy_returns_reverted_output() {
+            let reverted = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("try_new")).push_arg(0u32),
+            )
+            .expect_err("constructor must revert");
+
+            let output: ink::ConstructorResult<Result<(), ZeroValue>> =
+                scale::Decode::decode(&mut &reverted.data[..])
+                    .expect("output must be decodable");
+            assert_eq!(output, Ok(Err(ZeroValue)));
+        }
+
+        #[ink::test]
+        fn deploy_rejects_unknown_selector() {
+            let reverted = ink::env::test::deploy::<MyContract, _>(input([0xFF; 4]))
+                .expect_err("unknown selector must revert");
+
+            let output: ink::ConstructorResult<()> =
+                scale::Decode::decode(&mut &reverted.data[..])
+                    .expect("output must be decodable");
+            assert_eq!(output, Err(ink::LangError::CouldNotReadInput));
+        }
+
+        #[ink::test]
+        fn deploy_rejects_trailing_input() {
+            let reverted = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new"))
+                    .push_arg(42u32)
+                    .push_arg(0u8),
+            )
+            .expect_err("input remaining after the arguments must revert");
+
+            let output: ink::ConstructorResult<()> =
+                scale::Decode::decode(&mut &reverted.data[..])
+                    .expect("output must be decodable");
+            assert_eq!(output, Err(ink::LangError::CouldNotReadInput));
+        }
+
+        #[test]
+        fn constructor_decoder_rejects_trailing_input() {
+            use ink::reflect::{
+                ContractConstructorDecoder,
+                DecodeDispatch,
+                DispatchError,
+            };
+
+            let mut input_bytes = ink::selector_bytes!("new").to_vec();
+            input_bytes.extend(scale::Encode::encode(&42u32));
+            assert!(
+                <<MyContract as ContractConstructorDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                    &mut &input_bytes[..]
+                )
+                .is_ok()
+            );
+
+            input_bytes.push(0);
+            assert_eq!(
+                <<MyContract as ContractConstructorDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                    &mut &input_bytes[..]
+                )
+                .map(|_| ())
+                .unwrap_err(),
+                DispatchError::InvalidParameters,
+            );
+        }
+
+        #[ink::test]
+        fn deploy_reports_invalid_arguments() {
+            let reverted = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new")).push_arg(42u16),
+            )
+            .expect_err("invalid arguments must revert");
+
+            let output: ink::ConstructorResult<()> =
+                scale::Decode::decode(&mut &reverted.data[..])
+                    .expect("output must be decodable");
+            assert_eq!(output, Err(ink::LangError::CouldNotReadInput));
+            let messages = ink::env::test::recorded_debug_messages()
+                .into_iter()
+                .collect::<Vec<_>>();
+            assert_eq!(messages.len(), 1);
+            assert!(messages[0].starts_with(
+                "arguments of `new`: failed to decode `u32` at byte offset 0: "
+            ));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "paid an unpayable message")]
+        fn deploy_denies_payment() {
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(10);
+            let _ = ink::env::test::deploy::<MyContract, _>(
+                input(ink::selector_bytes!("new")).push_arg(42u32),
+            );
+        }
+
+        #[ink::test]
+        fn deploy_accepts_payment() {
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(10);
+            let contract = ink::env::test::deploy::<MyContract, _>(input(
+                ink::selector_bytes!("with_transferred_value"),
+            ))
+            .expect("constructor must not revert");
+
+            assert_eq!(contract.get(), 10);
+        }
+
+        #[ink::test]
+        fn hashed_event_signature_topic_works() {
+            assert_eq!(
+                InstantiatedWithTransferredValue::SIGNATURE,
+                "MyContract::InstantiatedWithTransferredValue(value:u32)"
+            );
+            let _ = ink::env::test::deploy::<MyContract, _>(input(ink::selector_bytes!(
+                "with_transferred_value"
+            )));
+            let events = ink::env::test::events_of_last_call().collect::<Vec<_>>();
+            let expected_topic = ink::env::test::topic::<DefaultEnvironment, _>(
+                &ink::env::topics::PrefixedValue {
+                    prefix: b"",
+                    value: b"MyContract::InstantiatedWithTransferredValue",
+                },
+            );
+            assert_eq!(
+                InstantiatedWithTransferredValue::SIGNATURE_TOPIC.map(Into::into),
+                Some(expected_topic)
+            );
+            assert_eq!(
+                events[0].decode_topics::<DefaultEnvironment>()[0],
+                expected_topic
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod flipper {
+    #[ink(storage)]
+    pub struct Flipper {
+        value: bool,
+    }
+
+    impl Flipper {
+        /// Creates a new `Flipper` instance.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: false }
+        }
+
+        /// Sets the current value.
+        #[ink(message, deprecated = "use `set_v2` instead")]
+        pub fn set(&mut self, value: bool) {
+            self.set_v2(value);
+        }
+
+        /// Sets the current value.
+        #[ink(message)]
+        pub fn set_v2(&mut self, value: bool) {
+            self.value = value;
+        }
+
+        /// Returns the current value.
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn deprecated_message_is_callable_and_warns() {
+            // given
+            let mut flipper = Flipper::new();
+
+            // when
+            flipper.set(true);
+            flipper.set_v2(false);
+
+            // then
+            assert!(!flipper.get());
+            let messages = ink::env::test::recorded_debug_messages()
+                .into_iter()
+                .collect::<Vec<_>>();
+            assert_eq!(
+                messages,
+                vec!["ink! message `set` is deprecated: use `set_v2` instead\n"]
+            );
+        }
+    }
+}
+
+#[test]
+fn deprecated_message_is_flagged_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let messages = project.spec().messages();
+    let deprecated = |label: &str| {
+        messages
+            .iter()
+            .find(|message| message.label() == label)
+            .unwrap()
+            .deprecated()
+            .cloned()
+    };
+    assert_eq!(
+        deprecated("set"),
+        Some(String::from("use `set_v2` instead"))
+    );
+    assert_eq!(deprecated("set_v2"), None);
+    assert_eq!(deprecated("get"), None);
+}
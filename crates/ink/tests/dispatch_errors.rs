@@ -0,0 +1,165 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod vault {
+    #[ink(storage)]
+    pub struct Vault {
+        deposits: Balance,
+    }
+
+    /// Returned if more than the deposits is withdrawn.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct InsufficientDeposits;
+
+    impl Vault {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { deposits: 0 }
+        }
+
+        /// Deposits the transferred value.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {
+            self.deposits += self.env().transferred_value();
+        }
+
+        /// Withdraws the given amount.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<(), InsufficientDeposits> {
+            self.deposits = self
+                .deposits
+                .checked_sub(amount)
+                .ok_or(InsufficientDeposits)?;
+            Ok(())
+        }
+
+        /// Returns the deposits.
+        #[ink(message)]
+        pub fn deposits(&self) -> Balance {
+            self.deposits
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            call::{
+                utils::EmptyArgumentList,
+                ExecutionInput,
+                Selector,
+            },
+            test::{
+                self,
+                DispatchError,
+            },
+            DefaultEnvironment,
+        };
+        use scale::Decode as _;
+
+        fn input(selector: [u8; 4]) -> ExecutionInput<EmptyArgumentList> {
+            ExecutionInput::new(Selector::new(selector))
+        }
+
+        fn deploy() {
+            test::deploy::<Vault, _>(input(ink::selector_bytes!("new")))
+                .expect("constructor must not revert");
+        }
+
+        #[ink::test]
+        fn dispatch_works() {
+            deploy();
+            test::transfer_in::<DefaultEnvironment>(10);
+            assert_eq!(
+                test::dispatch::<Vault, _>(input(ink::selector_bytes!("deposit"))),
+                Ok(Vec::new())
+            );
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            let output =
+                test::dispatch::<Vault, _>(input(ink::selector_bytes!("deposits")))
+                    .unwrap();
+            assert_eq!(Balance::decode(&mut &output[..]), Ok(10));
+        }
+
+        #[ink::test]
+        fn paying_unpayable_message_fails() {
+            deploy();
+            test::transfer_in::<DefaultEnvironment>(10);
+            assert_eq!(
+                test::dispatch::<Vault, _>(input(ink::selector_bytes!("deposits"))),
+                Err(DispatchError::PaidUnpayable)
+            );
+        }
+
+        #[ink::test]
+        fn unknown_selector_reverts_with_lang_error() {
+            deploy();
+            let error = test::assert_reverts_with::<ink::LangError, _, _>(|| {
+                test::dispatch::<Vault, _>(input([0xDE, 0xAD, 0xBE, 0xEF]))
+            });
+            assert_eq!(error, ink::LangError::CouldNotReadInput);
+        }
+
+        #[ink::test]
+        fn message_error_is_decoded() {
+            deploy();
+            let error = test::assert_reverts_with::<InsufficientDeposits, _, _>(|| {
+                test::dispatch::<Vault, _>(
+                    input(ink::selector_bytes!("withdraw")).push_arg(1 as Balance),
+                )
+            });
+            assert_eq!(error, InsufficientDeposits);
+        }
+
+        #[ink::test]
+        fn recorded_inputs_replay_byte_for_byte() {
+            let initial = test::snapshot();
+            test::record_call_inputs();
+            deploy();
+            test::transfer_in::<DefaultEnvironment>(10);
+            test::dispatch::<Vault, _>(input(ink::selector_bytes!("deposit"))).unwrap();
+            let log = test::take_call_input_log();
+            assert_eq!(log.inputs().len(), 2);
+            assert_eq!(log.inputs()[1].value_transferred, 10);
+
+            test::restore_snapshot(&initial);
+            let (deploy_input, deposit_input) = (&log.inputs()[0], &log.inputs()[1]);
+            test::replay_deploy::<Vault>(deploy_input)
+                .expect("constructor must not revert");
+            assert_eq!(
+                test::replay_dispatch::<Vault>(deposit_input),
+                Ok(Vec::new())
+            );
+
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            let output =
+                test::dispatch::<Vault, _>(input(ink::selector_bytes!("deposits")))
+                    .unwrap();
+            assert_eq!(Balance::decode(&mut &output[..]), Ok(10));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "dispatch did not revert")]
+        fn assert_reverts_with_fails_on_success() {
+            deploy();
+            test::assert_reverts_with::<ink::LangError, _, _>(|| {
+                test::dispatch::<Vault, _>(input(ink::selector_bytes!("deposits")))
+            });
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Library code which is shared by contracts and takes the environment accessor.
+mod fees {
+    use ink::{
+        env::Environment,
+        EnvAccess,
+    };
+
+    /// Returns the fee to charge for a call, which is waived for the `owner`.
+    pub fn fee<E: Environment>(
+        env: EnvAccess<'_, E>,
+        owner: &E::AccountId,
+    ) -> E::Balance {
+        if env.caller() == *owner {
+            return 0u32.into()
+        }
+        env.transferred_value() / 100u32.into()
+    }
+}
+
+#[ink::contract]
+mod my_contract {
+    use super::fees;
+
+    #[ink(storage)]
+    pub struct MyContract {
+        owner: AccountId,
+    }
+
+    impl MyContract {
+        /// Creates a new `MyContract` owned by the caller.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+            }
+        }
+
+        /// Returns the fee charged for the call.
+        #[ink(message, payable)]
+        pub fn pay(&self) -> Balance {
+            fees::fee(self.env(), &self.owner)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::DefaultEnvironment;
+
+        #[ink::test]
+        fn env_access_can_be_passed_to_library_code() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            let contract = MyContract::new();
+
+            ink::env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(500);
+            assert_eq!(contract.pay(), 5);
+
+            ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.pay(), 0);
+        }
+
+        #[ink::test]
+        fn env_access_can_be_created_in_tests() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            let env = ink::EnvAccess::<DefaultEnvironment>::new();
+
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(200);
+            assert_eq!(fees::fee(env, &accounts.bob), 2);
+            assert_eq!(env.caller(), accounts.alice);
+        }
+    }
+}
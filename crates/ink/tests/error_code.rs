@@ -0,0 +1,158 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// The errors of the token module of the contract.
+#[derive(Debug, PartialEq, Eq, ink::ErrorCode, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TokenError {
+    /// The balance of the sender is too low.
+    #[error_code(2)]
+    InsufficientBalance,
+    #[error_code(1)]
+    InsufficientAllowance,
+}
+
+#[ink::contract]
+mod vault {
+    use super::TokenError;
+    use ink::ErrorCode;
+
+    #[derive(Debug, PartialEq, Eq, ErrorCode, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The caller is not the owner of the vault.
+        #[error_code(100)]
+        NotOwner,
+        #[error_code(200, from)]
+        Token(TokenError),
+        #[error_code(300)]
+        Locked { until: BlockNumber },
+    }
+
+    #[ink(storage)]
+    pub struct Vault {
+        balance: Balance,
+    }
+
+    impl Vault {
+        #[ink(constructor)]
+        pub fn new(balance: Balance) -> Self {
+            Self { balance }
+        }
+
+        fn debit(&mut self, amount: Balance) -> Result<(), TokenError> {
+            self.balance = self
+                .balance
+                .checked_sub(amount)
+                .ok_or(TokenError::InsufficientBalance)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance) -> Result<(), Error> {
+            self.debit(amount)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn lock(&mut self) -> Result<(), Error> {
+            Err(Error::Locked { until: 10 })
+        }
+
+        #[ink(message)]
+        pub fn balance(&self) -> Balance {
+            self.balance
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn error_codes_are_stable() {
+            let mut vault = Vault::new(5);
+            assert_eq!(vault.withdraw(3), Ok(()));
+            let error = vault.withdraw(3).unwrap_err();
+            assert_eq!(error, Error::Token(TokenError::InsufficientBalance));
+            assert_eq!(error.error_code(), 200);
+            assert_eq!(vault.lock().unwrap_err().error_code(), 300);
+            assert_eq!(Error::NotOwner.error_code(), 100);
+            assert_eq!(TokenError::InsufficientBalance.error_code(), 2);
+            assert_eq!(TokenError::InsufficientAllowance.error_code(), 1);
+        }
+    }
+}
+
+#[test]
+fn error_codes_are_recorded_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let errors = project
+        .spec()
+        .errors()
+        .iter()
+        .map(|error| {
+            let codes = error
+                .codes()
+                .iter()
+                .map(|code| {
+                    (
+                        code.label().as_str(),
+                        code.code(),
+                        code.nested().is_some(),
+                        code.docs().to_vec(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            (error.label().as_str(), codes)
+        })
+        .collect::<Vec<_>>();
+    // The error type returned by two messages is only listed once, followed by the
+    // nested error type.
+    assert_eq!(
+        errors,
+        vec![
+            (
+                "Error",
+                vec![
+                    (
+                        "NotOwner",
+                        100,
+                        false,
+                        vec![String::from("The caller is not the owner of the vault.")],
+                    ),
+                    ("Token", 200, true, vec![]),
+                    ("Locked", 300, false, vec![]),
+                ]
+            ),
+            (
+                "TokenError",
+                vec![
+                    (
+                        "InsufficientBalance",
+                        2,
+                        false,
+                        vec![String::from("The balance of the sender is too low.")],
+                    ),
+                    ("InsufficientAllowance", 1, false, vec![]),
+                ]
+            ),
+        ]
+    );
+}
@@ -0,0 +1,127 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Benchmarks the number of host hash calls needed to compute event topics.
+//!
+//! Emitting `TransferWithMetadata` requires one hash call per `#[ink(topic)]` field
+//! whose encoding does not fit into a `Hash`. Its non-topic fields are never hashed
+//! and the topic of its signature is computed at compile time, whereas hashing all
+//! fields and the signature at runtime would take 13 hash calls per event.
+
+#[ink::contract]
+mod many_fields_benchmark {
+    #[ink(storage)]
+    pub struct ManyFieldsBenchmark {}
+
+    /// Emitted for every transfer.
+    #[ink(event)]
+    pub struct TransferWithMetadata {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        fee: Balance,
+        nonce: u64,
+        block: BlockNumber,
+        timestamp: Timestamp,
+        memo: [u8; 32],
+        reference: Hash,
+        approved: bool,
+        operator: Option<AccountId>,
+        tags: [u32; 8],
+        note: [u8; 64],
+    }
+
+    impl ManyFieldsBenchmark {
+        /// Creates a new `ManyFieldsBenchmark` instance.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        /// Emits `count` transfer events.
+        #[ink(message)]
+        pub fn emit_transfers(&self, count: u32) {
+            let caller = self.env().caller();
+            for nonce in 0..count {
+                self.env().emit_event(TransferWithMetadata {
+                    from: caller,
+                    to: AccountId::from([0x02; 32]),
+                    value: 100,
+                    fee: 1,
+                    nonce: nonce.into(),
+                    block: self.env().block_number(),
+                    timestamp: self.env().block_timestamp(),
+                    memo: [0x03; 32],
+                    reference: Hash::from([0x04; 32]),
+                    approved: true,
+                    operator: None,
+                    tags: [5; 8],
+                    note: [0x06; 64],
+                });
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EVENTS: u32 = 100;
+
+        #[ink::test]
+        fn only_topic_fields_are_hashed() {
+            // given
+            let contract = ManyFieldsBenchmark::new();
+            assert_eq!(ink::env::test::count_topic_hashes(), 0);
+
+            // when
+            contract.emit_transfers(EVENTS);
+
+            // then
+            assert_eq!(ink::env::test::recorded_events().count(), EVENTS as usize);
+            assert_eq!(ink::env::test::count_topic_hashes(), 2 * EVENTS as usize);
+        }
+
+        #[ink::test]
+        fn precomputed_signature_topic_matches() {
+            // given
+            let contract = ManyFieldsBenchmark::new();
+            let signature = b"ManyFieldsBenchmark::TransferWithMetadata";
+
+            // when
+            contract.emit_transfers(1);
+
+            // then
+            let event = ink::env::test::recorded_events().next().unwrap();
+            let topics = event.decode_topics::<Environment>();
+            let expected = ink::env::test::topic::<Environment, _>(
+                &ink::env::topics::PrefixedValue {
+                    prefix: b"",
+                    value: signature,
+                },
+            );
+            assert_eq!(topics[0], expected);
+            assert_eq!(
+                Some(topics[0].as_ref()),
+                TransferWithMetadata::SIGNATURE_TOPIC
+                    .as_ref()
+                    .map(|t| &t[..])
+            );
+        }
+    }
+}
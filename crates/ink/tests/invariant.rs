@@ -0,0 +1,128 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod my_token {
+    #[ink(storage)]
+    pub struct MyToken {
+        total_supply: Balance,
+        alice: Balance,
+        bob: Balance,
+    }
+
+    /// Returned if a balance does not cover a transfer.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct InsufficientBalance;
+
+    impl MyToken {
+        /// Creates a new `MyToken` instance with the whole supply owned by Alice.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            Self {
+                total_supply,
+                alice: total_supply,
+                bob: 0,
+            }
+        }
+
+        /// Transfers `value` from Alice to Bob.
+        #[ink(message)]
+        pub fn transfer(&mut self, value: Balance) -> Result<(), InsufficientBalance> {
+            if value > self.alice {
+                return Err(InsufficientBalance)
+            }
+            self.alice -= value;
+            self.bob += value;
+            Ok(())
+        }
+
+        /// Mints `value` to Bob, but forgets to increase the total supply.
+        #[ink(message)]
+        pub fn mint(&mut self, value: Balance) {
+            self.bob += value;
+        }
+
+        /// Returns the total supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// The balances must always sum up to the total supply.
+        #[ink(invariant)]
+        fn balances_match_supply(&self) -> bool {
+            self.alice + self.bob == self.total_supply
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            call::{
+                ExecutionInput,
+                Selector,
+            },
+            test::{
+                self,
+                DispatchError,
+            },
+        };
+        use scale::Encode as _;
+
+        fn deploy() {
+            test::deploy::<MyToken, _>(
+                ExecutionInput::new(Selector::new(ink::selector_bytes!("new")))
+                    .push_arg(100 as Balance),
+            )
+            .expect("constructor must not revert");
+        }
+
+        fn call(selector: [u8; 4], value: Balance) -> Result<Vec<u8>, DispatchError> {
+            test::dispatch::<MyToken, _>(
+                ExecutionInput::new(Selector::new(selector)).push_arg(value),
+            )
+        }
+
+        #[ink::test]
+        fn invariants_hold() {
+            deploy();
+            assert_eq!(
+                call(ink::selector_bytes!("transfer"), 30),
+                Ok(Ok::<(), InsufficientBalance>(()).encode())
+            );
+            // Reverted messages are not checked.
+            assert!(call(ink::selector_bytes!("transfer"), 100).is_err());
+        }
+
+        #[ink::test]
+        #[should_panic(
+            expected = "ink! invariant `balances_match_supply` violated after message `mint`"
+        )]
+        fn violated_invariant_panics() {
+            deploy();
+            let _ = call(ink::selector_bytes!("mint"), 1);
+        }
+
+        #[ink::test]
+        fn direct_calls_are_not_checked() {
+            let mut token = MyToken::new(100);
+            token.mint(1);
+            assert_eq!((token.alice, token.bob), (100, 1));
+        }
+    }
+}
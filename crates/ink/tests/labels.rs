@@ -0,0 +1,90 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod token {
+    #[ink(storage)]
+    pub struct Token {
+        total_supply: Balance,
+    }
+
+    impl Token {
+        #[ink(constructor, label = "Create token")]
+        pub fn new(#[ink(label = "Initial supply")] total_supply: Balance) -> Self {
+            Self { total_supply }
+        }
+
+        /// Transfers tokens to the recipient.
+        #[ink(message, label = "Transfer tokens")]
+        pub fn transfer(
+            &mut self,
+            #[ink(label = "Recipient")] to: AccountId,
+            #[ink(label = "Amount", arg(default = 0))] value: Balance,
+        ) -> bool {
+            let _ = to;
+            value <= self.total_supply
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn labels_do_not_affect_calls() {
+            let mut token = Token::new(10);
+            assert!(token.transfer(AccountId::from([0x01; 32]), 10));
+            assert!(!token.transfer(AccountId::from([0x01; 32]), 11));
+            assert_eq!(token.total_supply(), 10);
+        }
+    }
+}
+
+#[test]
+fn labels_are_display_names_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let constructor = &project.spec().constructors()[0];
+    assert_eq!(
+        constructor.display_name().map(String::as_str),
+        Some("Create token")
+    );
+    assert_eq!(
+        constructor.args()[0].display_name().map(String::as_str),
+        Some("Initial supply")
+    );
+
+    let messages = project.spec().messages();
+    assert_eq!(
+        messages[0].display_name().map(String::as_str),
+        Some("Transfer tokens")
+    );
+    let labels = messages[0]
+        .args()
+        .iter()
+        .map(|arg| arg.display_name().map(String::as_str))
+        .collect::<Vec<_>>();
+    assert_eq!(labels, [Some("Recipient"), Some("Amount")]);
+    assert!(messages[0].args()[1].default().is_some());
+    assert_eq!(messages[1].display_name(), None);
+}
@@ -0,0 +1,98 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod relay {
+    use ink::{
+        storage::Mapping,
+        MaybeOwned,
+    };
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Relay {
+        payloads: Mapping<u32, Vec<u8>>,
+    }
+
+    impl Relay {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Stores the `payload` without decoding it if it is still encoded.
+        #[ink(message)]
+        pub fn store(&mut self, id: u32, payload: MaybeOwned<'static, Vec<u8>>) {
+            self.payloads.insert(id, &payload);
+        }
+
+        #[ink(message)]
+        pub fn load(&self, id: u32) -> Option<Vec<u8>> {
+            self.payloads.get(id)
+        }
+    }
+}
+
+use ink::{
+    env::call::{
+        ExecutionInput,
+        Selector,
+    },
+    MaybeOwned,
+};
+use relay::Relay;
+use scale::Encode;
+
+#[ink::test]
+fn encoded_payload_is_stored_as_is() {
+    // The SCALE encoding of `vec![1u8, 2, 3]`.
+    static ENCODED: [u8; 4] = [12, 1, 2, 3];
+    let mut relay = Relay::new();
+    relay.store(1, MaybeOwned::Encoded(&ENCODED));
+    relay.store(2, MaybeOwned::Owned(vec![4, 5]));
+    assert_eq!(relay.load(1), Some(vec![1, 2, 3]));
+    assert_eq!(relay.load(2), Some(vec![4, 5]));
+}
+
+#[test]
+fn encoded_argument_is_forwarded_as_is() {
+    let payload = vec![1u8, 2, 3];
+    let encoded = payload.encode();
+    let selector = Selector::new(ink::selector_bytes!("store"));
+    assert_eq!(
+        ExecutionInput::new(selector)
+            .push_arg(1u32)
+            .push_arg(MaybeOwned::<Vec<u8>>::Encoded(&encoded))
+            .encode(),
+        ExecutionInput::new(selector)
+            .push_arg(1u32)
+            .push_arg(payload)
+            .encode()
+    );
+}
+
+#[test]
+fn metadata_describes_the_inner_type() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    assert!(project
+        .registry()
+        .types
+        .iter()
+        .all(|ty| !ty.ty.path.segments.iter().any(|s| s == "MaybeOwned")));
+}
@@ -0,0 +1,81 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod owned_flipper {
+    #[ink(storage)]
+    pub struct OwnedFlipper {
+        owner: AccountId,
+        value: bool,
+    }
+
+    impl OwnedFlipper {
+        /// Creates a new `OwnedFlipper` owned by the caller.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+                value: false,
+            }
+        }
+
+        /// Flips the current value.
+        #[ink(message, class = "transaction")]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+
+        /// Transfers the ownership to `new_owner`.
+        #[ink(message, class = "admin")]
+        pub fn set_owner(&mut self, new_owner: AccountId) {
+            assert_eq!(self.env().caller(), self.owner, "caller is not the owner");
+            self.owner = new_owner;
+        }
+
+        /// Returns the current value.
+        #[ink(message, class = "view")]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+
+        /// Returns the current owner.
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+    }
+}
+
+#[test]
+fn message_classes_are_included_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let messages = project.spec().messages();
+    let class = |label: &str| {
+        messages
+            .iter()
+            .find(|message| message.label() == label)
+            .unwrap()
+            .class()
+            .cloned()
+    };
+    assert_eq!(class("flip"), Some(String::from("transaction")));
+    assert_eq!(class("set_owner"), Some(String::from("admin")));
+    assert_eq!(class("get"), Some(String::from("view")));
+    assert_eq!(class("owner"), None);
+}
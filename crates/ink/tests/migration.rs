@@ -0,0 +1,98 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod counter {
+    use ink::storage::{
+        migration,
+        traits::ManualKey,
+        Lazy,
+    };
+
+    /// The storage key of the `u32` counter of the old code.
+    pub const OLD_COUNTER_KEY: u32 = 7;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Counter {
+        value: Lazy<u64, ManualKey<8>>,
+    }
+
+    impl Counter {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> u64 {
+            self.value.get_or_default()
+        }
+
+        /// Moves the `u32` counter of the old code to the new `u64` counter.
+        #[ink(message, migration)]
+        pub fn migrate(&mut self) -> bool {
+            migration::migrate(&OLD_COUNTER_KEY, &8u32, |old: u32| u64::from(old))
+                .expect("the old counter is a `u32`")
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test,
+            DefaultEnvironment,
+        };
+
+        fn set_own_code_hash(code_hash: [u8; 32]) {
+            test::set_contract_code_hash::<DefaultEnvironment>(
+                test::callee::<DefaultEnvironment>(),
+                Hash::from(code_hash),
+            );
+        }
+
+        #[ink::test]
+        fn migration_moves_old_counter() {
+            set_own_code_hash([0x01; 32]);
+            ink::env::set_contract_storage(&OLD_COUNTER_KEY, &42u32);
+            let mut contract = Counter::new();
+            assert!(contract.migrate());
+            assert_eq!(contract.get(), 42);
+            assert_eq!(migration::read_raw(&OLD_COUNTER_KEY), None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "migration already ran for the current code hash")]
+        fn migration_runs_once_per_code_hash() {
+            set_own_code_hash([0x01; 32]);
+            let mut contract = Counter::new();
+            assert!(!contract.migrate());
+            contract.migrate();
+        }
+
+        #[ink::test]
+        fn migration_runs_again_after_upgrade() {
+            set_own_code_hash([0x01; 32]);
+            let mut contract = Counter::new();
+            assert!(!contract.migrate());
+            set_own_code_hash([0x02; 32]);
+            ink::env::set_contract_storage(&OLD_COUNTER_KEY, &5u32);
+            assert!(contract.migrate());
+            assert_eq!(contract.get(), 5);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink::{
+    codegen::TraitCallForwarder,
+    env::{
+        call::FromAccountId,
+        DefaultEnvironment,
+    },
+    primitives::AccountId,
+    reflect::TraitDefinitionRegistry,
+};
+
+#[ink::trait_definition]
+pub trait Token {
+    /// Returns the total token supply.
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    /// Transfers `value` tokens to `to`.
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128) -> bool;
+}
+
+/// The call forwarder of the `Token` trait definition.
+type TokenRef = <<TraitDefinitionRegistry<DefaultEnvironment> as Token>::__ink_TraitInfo
+    as TraitCallForwarder>::Forwarder;
+
+#[test]
+fn mock_contract_works() {
+    let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+    let mock = ink::mock_contract!(Token);
+    mock.on_total_supply(|| 1_000)
+        .on_transfer(|_to, value| value <= 1_000)
+        .register(accounts.charlie);
+
+    let mut token = <TokenRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+        accounts.charlie,
+    );
+    assert_eq!(token.total_supply(), 1_000);
+    assert!(token.transfer(accounts.bob, 10));
+    assert!(!token.transfer(accounts.django, 10_000));
+
+    assert_eq!(mock.recorded_calls().len(), 3);
+    assert_eq!(mock.calls_to_total_supply(), [()]);
+    assert_eq!(
+        mock.calls_to_transfer(),
+        [(accounts.bob, 10), (accounts.django, 10_000)]
+    );
+}
+
+#[test]
+fn mock_contract_with_env_works() {
+    let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+    let mock = ink::mock_contract!(Token, env = DefaultEnvironment);
+    mock.on_total_supply(|| 42).register(accounts.django);
+
+    let token =
+        <TokenRef as FromAccountId<DefaultEnvironment>>::from_account_id(accounts.django);
+    assert_eq!(token.total_supply(), 42);
+}
+
+#[test]
+#[should_panic(expected = "encountered unexpected call to contract mock")]
+fn mock_contract_panics_on_unexpected_call() {
+    let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+    let mock = ink::mock_contract!(Token);
+    mock.on_total_supply(|| 1_000).register(accounts.charlie);
+
+    let mut token = <TokenRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+        accounts.charlie,
+    );
+    token.transfer(accounts.bob, 10);
+}
@@ -0,0 +1,188 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod my_token {
+    #[ink(storage)]
+    pub struct MyToken {
+        balance: Balance,
+        allowance: Balance,
+        deposited: Balance,
+    }
+
+    /// Returned if the allowance does not cover a transfer.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct InsufficientAllowance;
+
+    impl MyToken {
+        /// Creates a new `MyToken` instance with the given `balance`.
+        #[ink(constructor)]
+        pub fn new(balance: Balance) -> Self {
+            Self {
+                balance,
+                allowance: 0,
+                deposited: 0,
+            }
+        }
+
+        /// Sets the allowance to `value`.
+        #[ink(message)]
+        pub fn approve(&mut self, value: Balance) {
+            self.allowance = value;
+        }
+
+        /// Transfers `value` out of the allowance and returns the new balance.
+        #[ink(message)]
+        pub fn transfer(
+            &mut self,
+            value: Balance,
+        ) -> Result<Balance, InsufficientAllowance> {
+            if value > self.allowance {
+                return Err(InsufficientAllowance)
+            }
+            self.allowance -= value;
+            self.balance -= value;
+            Ok(self.balance)
+        }
+
+        /// Records the transferred value as deposited.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {
+            self.deposited += self.env().transferred_value();
+        }
+
+        /// Returns the current balance.
+        #[ink(message)]
+        pub fn balance(&self) -> Balance {
+            self.balance
+        }
+
+        /// Dispatches the `calls` to the other messages of the contract.
+        #[ink(message, payable, multicall)]
+        pub fn multicall(
+            &mut self,
+            calls: Vec<ink::MulticallCall>,
+        ) -> ink::MulticallResult {
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::{
+            env::{
+                call::Selector,
+                DefaultEnvironment,
+            },
+            MulticallError,
+            MulticallErrorKind,
+        };
+        use scale::Encode;
+
+        fn call(label: &str, input: impl Encode) -> ink::MulticallCall {
+            (Selector::from_label(label), input.encode())
+        }
+
+        #[ink::test]
+        fn multicall_works() {
+            let mut token = MyToken::new(100);
+            let outputs = token
+                .multicall(vec![
+                    call("approve", 50 as Balance),
+                    call("transfer", 20 as Balance),
+                    call("transfer", 10 as Balance),
+                    call("balance", ()),
+                ])
+                .expect("multicall must not fail");
+
+            assert_eq!(
+                outputs,
+                vec![
+                    ().encode(),
+                    Ok::<Balance, InsufficientAllowance>(80).encode(),
+                    Ok::<Balance, InsufficientAllowance>(70).encode(),
+                    (70 as Balance).encode(),
+                ]
+            );
+            assert_eq!(token.allowance, 20);
+        }
+
+        #[ink::test]
+        fn multicall_reports_failed_call() {
+            let mut token = MyToken::new(100);
+            assert_eq!(
+                token.multicall(vec![
+                    call("approve", 10 as Balance),
+                    call("transfer", 20 as Balance),
+                    call("balance", ()),
+                ]),
+                Err(MulticallError {
+                    index: 1,
+                    kind: MulticallErrorKind::Reverted(
+                        Err::<Balance, _>(InsufficientAllowance).encode()
+                    ),
+                })
+            );
+        }
+
+        #[ink::test]
+        fn multicall_rejects_invalid_calls() {
+            let mut token = MyToken::new(100);
+            assert_eq!(
+                token.multicall(vec![call("burn", 10 as Balance)]),
+                Err(MulticallError {
+                    index: 0,
+                    kind: MulticallErrorKind::UnknownSelector,
+                })
+            );
+            assert_eq!(
+                token.multicall(vec![call("balance", ()), call("approve", ())]),
+                Err(MulticallError {
+                    index: 1,
+                    kind: MulticallErrorKind::InvalidParameters,
+                })
+            );
+            assert_eq!(
+                token
+                    .multicall(vec![call("multicall", Vec::<ink::MulticallCall>::new())]),
+                Err(MulticallError {
+                    index: 0,
+                    kind: MulticallErrorKind::Recursive,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn multicall_respects_payable() {
+            let mut token = MyToken::new(100);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(5);
+
+            assert_eq!(
+                token.multicall(vec![call("deposit", ()), call("deposit", ())]),
+                Ok(vec![().encode(), ().encode()])
+            );
+            assert_eq!(token.deposited, 10);
+            assert_eq!(
+                token.multicall(vec![call("deposit", ()), call("approve", 1 as Balance)]),
+                Err(MulticallError {
+                    index: 1,
+                    kind: MulticallErrorKind::PaidUnpayableMessage,
+                })
+            );
+        }
+    }
+}
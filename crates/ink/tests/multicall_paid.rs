@@ -0,0 +1,140 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod vault {
+    #[ink(storage)]
+    pub struct Vault {
+        deposited: Balance,
+        donated: Balance,
+        counter: u32,
+    }
+
+    impl Vault {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                deposited: 0,
+                donated: 0,
+                counter: 0,
+            }
+        }
+
+        /// Records the value attributed to the call as deposited.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {
+            self.deposited += self.env().attributed_value();
+        }
+
+        /// Records the value attributed to the call as donated.
+        #[ink(message, payable)]
+        pub fn donate(&mut self) {
+            self.donated += self.env().attributed_value();
+        }
+
+        /// Increments the counter.
+        #[ink(message)]
+        pub fn increment(&mut self) {
+            self.counter += 1;
+        }
+
+        /// Dispatches the `calls` to the other messages of the contract.
+        #[ink(message, payable, multicall)]
+        pub fn multicall(
+            &mut self,
+            calls: Vec<ink::MulticallPaidCall<Balance>>,
+        ) -> ink::MulticallResult {
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::{
+            env::{
+                call::Selector,
+                DefaultEnvironment,
+            },
+            MulticallError,
+            MulticallErrorKind,
+        };
+        use scale::Encode;
+
+        fn call(label: &str, value: Balance) -> ink::MulticallPaidCall<Balance> {
+            (Selector::from_label(label), value, ().encode())
+        }
+
+        #[ink::test]
+        fn attributed_values_are_seen_by_calls() {
+            let mut vault = Vault::new();
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(10);
+
+            assert_eq!(
+                vault.multicall(vec![
+                    call("deposit", 3),
+                    call("donate", 7),
+                    call("increment", 0),
+                ]),
+                Ok(vec![().encode(), ().encode(), ().encode()])
+            );
+            assert_eq!(vault.deposited, 3);
+            assert_eq!(vault.donated, 7);
+            assert_eq!(vault.counter, 1);
+            assert_eq!(ink::env::attributed_value::<DefaultEnvironment>(), 10);
+        }
+
+        #[ink::test]
+        fn unpayable_messages_reject_attributed_value() {
+            let mut vault = Vault::new();
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(10);
+
+            assert_eq!(
+                vault.multicall(vec![call("deposit", 9), call("increment", 1)]),
+                Err(MulticallError {
+                    index: 1,
+                    kind: MulticallErrorKind::PaidUnpayableMessage,
+                })
+            );
+        }
+
+        #[ink::test]
+        fn attributed_values_must_add_up() {
+            let mut vault = Vault::new();
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(10);
+
+            assert_eq!(
+                vault.multicall(vec![
+                    call("deposit", 6),
+                    call("donate", 5),
+                    call("increment", 0),
+                ]),
+                Err(MulticallError {
+                    index: 1,
+                    kind: MulticallErrorKind::InvalidValueAttribution,
+                })
+            );
+            assert_eq!(
+                vault.multicall(vec![call("deposit", 6), call("donate", 3)]),
+                Err(MulticallError {
+                    index: 2,
+                    kind: MulticallErrorKind::InvalidValueAttribution,
+                })
+            );
+            assert_eq!(vault.deposited, 0);
+            assert_eq!(vault.donated, 0);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod bank {
+    #[ink(storage)]
+    pub struct Bank {
+        balance: Balance,
+    }
+
+    /// Returned if the balance does not cover a withdrawal.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct InsufficientBalance;
+
+    impl Bank {
+        #[ink(constructor)]
+        pub fn new(balance: Balance) -> Self {
+            Self { balance }
+        }
+
+        /// Withdraws `value` from the balance.
+        #[ink(message, non_reentrant)]
+        pub fn withdraw(&mut self, value: Balance) -> Result<(), InsufficientBalance> {
+            if value > self.balance {
+                return Err(InsufficientBalance)
+            }
+            self.balance -= value;
+            Ok(())
+        }
+
+        /// Withdraws `value` twice, re-entering `withdraw`.
+        #[ink(message, non_reentrant)]
+        pub fn withdraw_twice(
+            &mut self,
+            value: Balance,
+        ) -> Result<(), InsufficientBalance> {
+            self.withdraw(value)?;
+            self.withdraw(value)
+        }
+
+        /// Returns the balance.
+        #[ink(message)]
+        pub fn balance(&self) -> Balance {
+            self.balance
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn sequential_calls_work() {
+            let mut bank = Bank::new(10);
+            assert_eq!(bank.withdraw(3), Ok(()));
+            assert_eq!(bank.withdraw(8), Err(InsufficientBalance));
+            // The guard is exited after the early return.
+            assert_eq!(bank.withdraw(7), Ok(()));
+            assert_eq!(bank.balance(), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(
+            expected = "encountered reentrant call of a non-reentrant ink! message"
+        )]
+        fn reentrant_call_reverts() {
+            let mut bank = Bank::new(10);
+            let _ = bank.withdraw_twice(1);
+        }
+    }
+}
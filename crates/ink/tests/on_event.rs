@@ -0,0 +1,135 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod my_token {
+    use ink::storage::{
+        traits::ManualKey,
+        Mapping,
+    };
+
+    /// The storage key of the balances.
+    pub type BalancesKey = ManualKey<0xBA1A>;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MyToken {
+        balances: Mapping<AccountId, Balance, BalancesKey>,
+    }
+
+    #[ink(event)]
+    pub struct Minted {
+        #[ink(topic)]
+        pub to: AccountId,
+        pub value: Balance,
+    }
+
+    impl MyToken {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Mints `value` to `to` and emits a `Minted` event afterwards.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, value: Balance) {
+            let balance = self.balances.get(to).unwrap_or(0);
+            self.balances.insert(to, &(balance + value));
+            self.env().emit_event(Minted { to, value });
+        }
+
+        /// Emits `count` `Minted` events with increasing values.
+        #[ink(message)]
+        pub fn mint_batch(&mut self, to: AccountId, count: Balance) {
+            for value in 1..=count {
+                self.mint(to, value);
+            }
+        }
+    }
+}
+
+use ink::{
+    env::DefaultEnvironment,
+    storage::Mapping,
+};
+use my_token::{
+    BalancesKey,
+    MyToken,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+
+type AccountId = <DefaultEnvironment as ink::env::Environment>::AccountId;
+
+/// Returns the balance of `account` as stored when it is called.
+fn stored_balance(account: AccountId) -> u128 {
+    Mapping::<AccountId, u128, BalancesKey>::new()
+        .get(account)
+        .unwrap_or(0)
+}
+
+#[ink::test]
+fn listener_sees_storage_at_emission() {
+    // given
+    let bob = ink::env::test::default_accounts::<DefaultEnvironment>().bob;
+    let balances = Rc::new(RefCell::new(Vec::new()));
+    let recorder = balances.clone();
+    ink::env::test::on_event(move |_| {
+        recorder.borrow_mut().push(stored_balance(bob));
+    });
+    let mut token = MyToken::new();
+
+    // when
+    token.mint_batch(bob, 3);
+
+    // then
+    // the listener observes every intermediate balance instead of the final one
+    assert_eq!(*balances.borrow(), vec![1, 3, 6]);
+    assert_eq!(stored_balance(bob), 6);
+}
+
+#[ink::test]
+fn listeners_are_notified_in_order() {
+    // given
+    let bob = ink::env::test::default_accounts::<DefaultEnvironment>().bob;
+    let notifications = Rc::new(RefCell::new(Vec::new()));
+    for listener in ["first", "second"] {
+        let recorder = notifications.clone();
+        ink::env::test::on_event(move |event| {
+            // the event is encoded as the variant of the contract's event enum
+            let (_variant, _to, value) =
+                <(u8, AccountId, u128) as scale::Decode>::decode(&mut &event.data[..])
+                    .expect("event must decode");
+            recorder.borrow_mut().push((listener, value));
+        });
+    }
+    let mut token = MyToken::new();
+
+    // when
+    token.mint(bob, 1);
+    token.mint(bob, 2);
+    ink::env::test::clear_event_listeners();
+    token.mint(bob, 3);
+
+    // then
+    assert_eq!(
+        *notifications.borrow(),
+        vec![("first", 1), ("second", 1), ("first", 2), ("second", 2)]
+    );
+    assert_eq!(ink::env::test::recorded_events().count(), 3);
+}
@@ -0,0 +1,117 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod vault {
+    use ink::storage::{
+        security::pausable::{
+            Pausable,
+            PausableError,
+        },
+        traits::ManualKey,
+    };
+
+    #[ink(storage)]
+    pub struct Vault {
+        // Messages flagged with `when_not_paused` check this field, whatever its key.
+        pausable: Pausable<Environment, ManualKey<0xCAFE>>,
+        withdrawn: Balance,
+    }
+
+    impl Vault {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                pausable: Default::default(),
+                withdrawn: 0,
+            }
+        }
+
+        /// Withdraws `value`, unless the vault is paused.
+        #[ink(message, when_not_paused)]
+        pub fn withdraw(&mut self, value: Balance) {
+            self.withdrawn += value;
+        }
+
+        /// Returns the withdrawn value.
+        #[ink(message)]
+        pub fn withdrawn(&self) -> Balance {
+            self.withdrawn
+        }
+
+        /// Pauses the vault.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), PausableError> {
+            self.pausable.pause()
+        }
+
+        /// Unpauses the vault.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), PausableError> {
+            self.pausable.unpause()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn unpaused_calls_work() {
+            let mut vault = Vault::new();
+            vault.withdraw(3);
+            assert_eq!(vault.pause(), Ok(()));
+            assert_eq!(vault.unpause(), Ok(()));
+            vault.withdraw(4);
+            assert_eq!(vault.withdrawn(), 7);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "while the contract is paused")]
+        fn paused_calls_revert() {
+            let mut vault = Vault::new();
+            vault.pause().unwrap();
+            // Messages without the flag remain callable.
+            assert_eq!(vault.withdrawn(), 0);
+            vault.withdraw(1);
+        }
+
+        #[ink::test]
+        fn pausable_reports_the_pause_for_upgrades() {
+            let mut vault = Vault::new();
+            let paused = |vault: &Vault| ink::upgrade::Pausable::paused(&vault.pausable);
+            assert!(!paused(&vault));
+            vault.pause().unwrap();
+            assert!(paused(&vault));
+        }
+    }
+}
+
+#[test]
+fn when_not_paused_messages_are_flagged_in_metadata() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let flagged = project
+        .spec()
+        .messages()
+        .iter()
+        .filter(|message| message.when_not_paused())
+        .map(|message| message.label().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(flagged, vec!["withdraw"]);
+}
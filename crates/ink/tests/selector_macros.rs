@@ -0,0 +1,143 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::trait_definition]
+pub trait Psp22 {
+    #[ink(message)]
+    fn transfer(&mut self, value: u32);
+}
+
+#[ink::trait_definition(namespace = "psp34")]
+pub trait Psp34 {
+    #[ink(message)]
+    fn transfer(&mut self, id: u32);
+}
+
+#[ink::contract]
+mod my_contract {
+    #[ink(storage)]
+    pub struct MyContract {}
+
+    impl MyContract {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> u32 {
+            0
+        }
+
+        #[ink(message, selector = 0xCAFEBABE)]
+        pub fn custom(&self) {}
+    }
+
+    impl super::Psp22 for MyContract {
+        #[ink(message)]
+        fn transfer(&mut self, _value: u32) {}
+    }
+
+    impl super::Psp34 for MyContract {
+        #[ink(message)]
+        fn transfer(&mut self, _id: u32) {}
+    }
+}
+
+use ink::reflect::{
+    DispatchableConstructorInfo,
+    DispatchableMessageInfo,
+};
+use my_contract::MyContract;
+
+/// Returns the label of the message with the given selector.
+///
+/// The selectors are matched symbolically in the way a router or fallback
+/// handler would do it.
+fn route(selector: [u8; 4]) -> &'static str {
+    const TOTAL_SUPPLY: [u8; 4] = ink::selector_bytes!("total_supply");
+    match selector {
+        TOTAL_SUPPLY => "total_supply",
+        ink::selector_bytes!("Psp22::transfer") => "Psp22::transfer",
+        ink::selector_bytes!("psp34::Psp34::transfer") => "psp34::Psp34::transfer",
+        _ => "unknown",
+    }
+}
+
+#[test]
+fn selector_macros_match_generated_selectors() {
+    // The `ID` of the dispatchable info only exists if the macros are consistent
+    // with the selectors derived by the codegen.
+    const TRANSFER_ID: u32 = ink::selector_id!("Psp22::transfer");
+    assert_eq!(
+        <MyContract as DispatchableMessageInfo<TRANSFER_ID>>::SELECTOR,
+        ink::selector_bytes!("Psp22::transfer")
+    );
+    assert_eq!(
+        <MyContract as DispatchableMessageInfo<
+            { ink::selector_id!("psp34::Psp34::transfer") },
+        >>::SELECTOR,
+        ink::selector_bytes!("psp34::Psp34::transfer")
+    );
+    assert_eq!(
+        <MyContract as DispatchableMessageInfo<{ ink::selector_id!("total_supply") }>>::SELECTOR,
+        ink::selector_bytes!("total_supply")
+    );
+    assert_eq!(
+        <MyContract as DispatchableConstructorInfo<{ ink::selector_id!("new") }>>::SELECTOR,
+        ink::selector_bytes!("new")
+    );
+    assert_eq!(
+        ink::selector_bytes!("total_supply"),
+        ink::primitives::Selector::from_label("total_supply").to_bytes()
+    );
+    assert_eq!(
+        ink::selector_id!("total_supply"),
+        u32::from_be_bytes(ink::selector_bytes!("total_supply"))
+    );
+}
+
+#[test]
+fn selector_macros_work_in_match_arms() {
+    assert_eq!(
+        route(
+            <MyContract as DispatchableMessageInfo<
+                { ink::selector_id!("total_supply") },
+            >>::SELECTOR
+        ),
+        "total_supply"
+    );
+    assert_eq!(
+        route(
+            <MyContract as DispatchableMessageInfo<
+                { ink::selector_id!("Psp22::transfer") },
+            >>::SELECTOR
+        ),
+        "Psp22::transfer"
+    );
+    assert_eq!(
+        route(
+            <MyContract as DispatchableMessageInfo<
+                { ink::selector_id!("psp34::Psp34::transfer") },
+            >>::SELECTOR
+        ),
+        "psp34::Psp34::transfer"
+    );
+    assert_eq!(
+        route(<MyContract as DispatchableMessageInfo<0xCAFEBABE>>::SELECTOR),
+        "unknown"
+    );
+}
@@ -0,0 +1,95 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod my_token {
+    use ink::storage::Mapping;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MyToken {
+        balances: Mapping<AccountId, Balance>,
+    }
+
+    #[ink(event)]
+    pub struct Transferred {
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        InsufficientBalance,
+    }
+
+    impl MyToken {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), Error> {
+            self.balances.insert(to, &value);
+            Ok(())
+        }
+
+        #[ink(message, multicall)]
+        pub fn multicall(
+            &mut self,
+            calls: Vec<ink::MulticallCall>,
+        ) -> ink::MulticallResult {
+        }
+    }
+}
+
+#[test]
+fn metadata_type_paths_are_stable() {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> ink::metadata::InkProject;
+    }
+    let project = unsafe { __ink_generate_metadata() };
+    let paths = project
+        .registry()
+        .types
+        .iter()
+        .map(|ty| ty.ty.path.segments.join("::"))
+        .filter(|path| !path.is_empty())
+        .collect::<Vec<_>>();
+    for path in &paths {
+        assert!(
+            !path.split("::").any(|segment| segment.starts_with("__")),
+            "type path `{}` leaks an ink! internal module",
+            path,
+        );
+    }
+    for expected in [
+        "ink_primitives::LangError",
+        "ink_primitives::Selector",
+        "ink_primitives::MulticallError",
+        "ink_primitives::MulticallErrorKind",
+        "type_paths::my_token::Error",
+    ] {
+        assert!(
+            paths.iter().any(|path| path == expected),
+            "missing `{}` in {:?}",
+            expected,
+            paths,
+        );
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod token {
+    use ink::storage::{
+        traits::ManualKey,
+        Lazy,
+        Mapping,
+    };
+
+    #[ink(storage)]
+    pub struct Token {
+        supply: Lazy<Balance, ManualKey<1>>,
+        balances: Mapping<AccountId, Balance, ManualKey<2>>,
+    }
+
+    impl Token {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                supply: Default::default(),
+                balances: Default::default(),
+            }
+        }
+
+        /// Returns the total supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.supply.get_or_default()
+        }
+
+        /// Returns the balance of `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test,
+            DefaultEnvironment,
+        };
+
+        #[ink::test]
+        fn seeded_storage_is_read_by_the_contract() {
+            let token = Token::new();
+            let contract = test::callee::<DefaultEnvironment>();
+            let alice = test::default_accounts::<DefaultEnvironment>().alice;
+
+            test::set_typed_storage::<DefaultEnvironment, Token, _, Balance>(
+                &contract, &1u32, &100,
+            );
+            test::set_typed_storage::<DefaultEnvironment, Token, _, Balance>(
+                &contract,
+                &(2u32, alice),
+                &40,
+            );
+            assert_eq!(token.total_supply(), 100);
+            assert_eq!(token.balance_of(alice), 40);
+            assert_eq!(
+                test::get_typed_storage::<DefaultEnvironment, Token, _, Balance>(
+                    &contract,
+                    &(2u32, alice),
+                ),
+                Some(40)
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "has no storage item at the root key 3")]
+        fn unknown_keys_are_rejected() {
+            let contract = test::callee::<DefaultEnvironment>();
+            test::set_typed_storage::<DefaultEnvironment, Token, _, Balance>(
+                &contract, &3u32, &100,
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "at the root key 2 doesn't store `u32`")]
+        fn mismatching_types_are_rejected() {
+            let contract = test::callee::<DefaultEnvironment>();
+            let alice = test::default_accounts::<DefaultEnvironment>().alice;
+            test::set_typed_storage::<DefaultEnvironment, Token, _, u32>(
+                &contract,
+                &(2u32, alice),
+                &100,
+            );
+        }
+    }
+}
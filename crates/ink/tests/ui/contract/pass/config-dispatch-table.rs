@@ -0,0 +1,28 @@
+#[ink::contract(dispatch_table = true)]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message_1(&self) {}
+
+        #[ink(message, payable, selector = 0xCAFEBABE)]
+        pub fn message_2(&mut self) {}
+    }
+}
+
+fn main() {
+    assert_eq!(
+        contract::DISPATCH_TABLE,
+        &[
+            (ink::selector_bytes!("message_1"), "message_1", false, false),
+            ([0xCA, 0xFE, 0xBA, 0xBE], "message_2", true, true),
+        ]
+    );
+}
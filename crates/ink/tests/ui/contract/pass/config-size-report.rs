@@ -0,0 +1,21 @@
+#[ink::contract(size_report = true)]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+
+        #[ink(message)]
+        #[inline]
+        pub fn inlined_message(&self) {}
+    }
+}
+
+fn main() {}
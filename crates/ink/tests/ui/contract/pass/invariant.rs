@@ -0,0 +1,52 @@
+#[ink::trait_definition]
+pub trait Decrement {
+    #[ink(message)]
+    fn decrement(&mut self);
+}
+
+#[ink::contract]
+mod contract {
+    use super::Decrement;
+
+    #[ink(storage)]
+    pub struct Contract {
+        value: u32,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { value: 1 }
+        }
+
+        #[ink(message)]
+        pub fn increment(&mut self) -> Result<(), ()> {
+            self.value = self.value.checked_add(1).ok_or(())?;
+            Ok(())
+        }
+
+        #[ink(invariant)]
+        fn value_is_positive(&self) -> bool {
+            self.value > 0
+        }
+    }
+
+    impl Decrement for Contract {
+        #[ink(message)]
+        fn decrement(&mut self) {
+            if self.value == 1 {
+                return
+            }
+            self.value -= 1;
+        }
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    let mut contract = Contract::constructor();
+    assert_eq!(contract.increment(), Ok(()));
+    contract.decrement();
+    contract.decrement();
+}
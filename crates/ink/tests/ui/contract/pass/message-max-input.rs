@@ -0,0 +1,87 @@
+use ink::{
+    reflect::{
+        ContractMessageDecoder,
+        DecodeDispatch,
+        DispatchError,
+    },
+    selector_bytes,
+};
+use scale::Encode;
+
+#[ink::contract]
+pub mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, max_input = 16)]
+        pub fn message(&self, _input: Vec<u8>) {}
+    }
+}
+
+use contract::Contract;
+
+/// An input which does not know its remaining length.
+struct UnknownLength<'a>(&'a [u8]);
+
+impl scale::Input for UnknownLength<'_> {
+    fn remaining_len(&mut self) -> Result<Option<usize>, scale::Error> {
+        Ok(None)
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> Result<(), scale::Error> {
+        self.0.read(into)
+    }
+}
+
+fn main() {
+    // Valid call to `message` within the maximum input size:
+    {
+        let mut input_bytes = Vec::new();
+        input_bytes.extend(selector_bytes!("message"));
+        input_bytes.extend(vec![1u8; 8].encode());
+        assert!(
+            <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                &mut &input_bytes[..]).is_ok()
+        );
+    }
+    // Invalid call to `message` exceeding the maximum input size:
+    {
+        let mut input_bytes = Vec::new();
+        input_bytes.extend(selector_bytes!("message"));
+        input_bytes.extend(vec![1u8; 32].encode());
+        assert_eq!(
+            <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                &mut &input_bytes[..])
+                .map(|_| ())
+                .unwrap_err(),
+            DispatchError::InputTooLarge,
+        );
+    }
+    // Call to `message` from an input of unknown length within and exceeding the
+    // maximum input size:
+    {
+        let mut input_bytes = Vec::new();
+        input_bytes.extend(selector_bytes!("message"));
+        input_bytes.extend(vec![1u8; 8].encode());
+        assert!(
+            <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                &mut UnknownLength(&input_bytes[..])).is_ok()
+        );
+        let mut input_bytes = Vec::new();
+        input_bytes.extend(selector_bytes!("message"));
+        input_bytes.extend(vec![1u8; 32].encode());
+        assert_eq!(
+            <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                &mut UnknownLength(&input_bytes[..]))
+                .map(|_| ())
+                .unwrap_err(),
+            DispatchError::InputTooLarge,
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use ink::{
+    metadata::InkProject,
+    reflect::{
+        ContractMessageDecoder,
+        DecodeDispatch,
+    },
+    selector_bytes,
+};
+
+#[ink::contract]
+pub mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn public_message(&self) {}
+
+        #[ink(message, metadata = false)]
+        pub fn maintenance_message(&mut self) {}
+    }
+}
+
+fn generate_metadata() -> InkProject {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> InkProject;
+    }
+
+    unsafe { __ink_generate_metadata() }
+}
+
+fn main() {
+    use contract::Contract;
+
+    let metadata = generate_metadata();
+    let labels = metadata
+        .spec()
+        .messages()
+        .iter()
+        .map(|message| message.label().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(labels, ["public_message"]);
+
+    // The message omitted from the metadata is still dispatchable.
+    let input_bytes = selector_bytes!("maintenance_message");
+    assert!(
+        <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+            &mut &input_bytes[..]
+        )
+        .is_ok()
+    );
+}
@@ -0,0 +1,45 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {
+        value: bool,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { value: false }
+        }
+
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+
+        #[ink(message, multicall)]
+        pub fn multicall(
+            &mut self,
+            calls: Vec<ink::MulticallCall>,
+        ) -> ink::MulticallResult {
+        }
+    }
+}
+
+use contract::Contract;
+use ink::env::call::Selector;
+
+fn main() {
+    let mut contract = Contract::constructor();
+    let outputs = contract
+        .multicall(vec![
+            (Selector::from_label("flip"), Vec::new()),
+            (Selector::from_label("get"), Vec::new()),
+        ])
+        .unwrap();
+    assert_eq!(outputs, vec![Vec::new(), vec![1]]);
+}
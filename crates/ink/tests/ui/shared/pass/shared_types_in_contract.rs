@@ -0,0 +1,41 @@
+#[ink::shared]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InsufficientBalance,
+    Unauthorized,
+}
+
+#[ink::shared]
+#[derive(Debug, Clone)]
+pub struct Order {
+    pub amount: u128,
+    pub price: u128,
+}
+
+#[ink::contract]
+mod contract {
+    use super::{
+        Error,
+        Order,
+    };
+
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn place(&self, order: Order) -> Result<Order, Error> {
+            if order.amount == 0 {
+                return Err(Error::InsufficientBalance)
+            }
+            Ok(order)
+        }
+    }
+}
+
+fn main() {}
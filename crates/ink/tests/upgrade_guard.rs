@@ -0,0 +1,122 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod upgradeable {
+    use ink::upgrade::UpgradeError;
+
+    #[ink(storage)]
+    pub struct Upgradeable {
+        owner: AccountId,
+        paused: bool,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        NotOwner,
+        Upgrade(UpgradeError),
+    }
+
+    impl From<UpgradeError> for Error {
+        fn from(error: UpgradeError) -> Self {
+            Error::Upgrade(error)
+        }
+    }
+
+    impl ink::upgrade::Pausable for Upgradeable {
+        fn paused(&self) -> bool {
+            self.paused
+        }
+    }
+
+    impl Upgradeable {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+                paused: false,
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_paused(&mut self, paused: bool) {
+            self.paused = paused;
+        }
+
+        #[ink(message)]
+        #[ink::upgrade_guard]
+        pub fn upgrade(
+            &mut self,
+            code_hash: Hash,
+            fingerprint: [u8; 32],
+        ) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner)
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::upgrade::StorageFingerprint;
+
+        const FINGERPRINT: [u8; 32] = <Upgradeable as StorageFingerprint>::FINGERPRINT;
+
+        #[ink::test]
+        fn upgrade_requires_pause() {
+            let mut contract = Upgradeable::new();
+            assert_eq!(
+                contract.upgrade(Hash::from([0x01; 32]), FINGERPRINT),
+                Err(Error::Upgrade(UpgradeError::NotPaused))
+            );
+        }
+
+        #[ink::test]
+        fn upgrade_requires_matching_fingerprint() {
+            let mut contract = Upgradeable::new();
+            contract.set_paused(true);
+            assert_eq!(
+                contract.upgrade(Hash::from([0x01; 32]), [0x00; 32]),
+                Err(Error::Upgrade(UpgradeError::FingerprintMismatch))
+            );
+        }
+
+        #[ink::test]
+        fn upgrade_runs_message_checks_first() {
+            let mut contract = Upgradeable::new();
+            let accounts =
+                ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.upgrade(Hash::from([0x01; 32]), FINGERPRINT),
+                Err(Error::NotOwner)
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(
+            expected = "off-chain environment does not support `set_code_hash`"
+        )]
+        fn upgrade_sets_code_hash_if_safe() {
+            let mut contract = Upgradeable::new();
+            contract.set_paused(true);
+            let _ = contract.upgrade(Hash::from([0x01; 32]), FINGERPRINT);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stable names of the custom types of a contract.
+//!
+//! The ids of the types in the registry are assigned in the order in which the types
+//! are registered, so the same type gets a different id in the metadata of every
+//! contract. Custom types, i.e. structs and enums, are therefore additionally
+//! identified by their path and type arguments, e.g. `shared_types::Order<u128>`,
+//! which is the same for all contracts using a type of a shared crate.
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use scale_info::{
+    PortableRegistry,
+    TypeDef,
+    TypeDefPrimitive,
+};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Returns the ids of all structs and enums in the `registry` by their stable name.
+pub fn custom_types(registry: &PortableRegistry) -> BTreeMap<String, u32> {
+    registry
+        .types
+        .iter()
+        .filter(|ty| {
+            !ty.ty.path.segments.is_empty()
+                && matches!(ty.ty.type_def, TypeDef::Composite(_) | TypeDef::Variant(_))
+        })
+        .map(|ty| (stable_name(registry, ty.id), ty.id))
+        .collect()
+}
+
+/// Returns the name of the type with the given `id` as written in Rust.
+///
+/// Types with a path are named by their full path, followed by their type
+/// arguments if any.
+fn stable_name(registry: &PortableRegistry, id: u32) -> String {
+    let ty = match registry.resolve(id) {
+        Some(ty) => ty,
+        None => return "_".to_string(),
+    };
+    if !ty.path.segments.is_empty() {
+        let path = ty.path.segments.join("::");
+        if ty.type_params.is_empty() {
+            return path
+        }
+        let args = ty
+            .type_params
+            .iter()
+            .map(|param| {
+                match param.ty {
+                    Some(arg) => stable_name(registry, arg.id),
+                    None => param.name.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        return format!("{}<{}>", path, args.join(", "))
+    }
+    match &ty.type_def {
+        TypeDef::Primitive(primitive) => primitive_name(primitive).to_string(),
+        TypeDef::Sequence(sequence) => {
+            format!("[{}]", stable_name(registry, sequence.type_param.id))
+        }
+        TypeDef::Array(array) => {
+            format!(
+                "[{}; {}]",
+                stable_name(registry, array.type_param.id),
+                array.len
+            )
+        }
+        TypeDef::Tuple(tuple) => {
+            let fields = tuple
+                .fields
+                .iter()
+                .map(|field| stable_name(registry, field.id))
+                .collect::<Vec<_>>();
+            format!("({})", fields.join(", "))
+        }
+        TypeDef::Compact(compact) => {
+            format!("Compact<{}>", stable_name(registry, compact.type_param.id))
+        }
+        TypeDef::BitSequence(bits) => {
+            format!(
+                "BitVec<{}, {}>",
+                stable_name(registry, bits.bit_store_type.id),
+                stable_name(registry, bits.bit_order_type.id),
+            )
+        }
+        TypeDef::Composite(_) | TypeDef::Variant(_) => "_".to_string(),
+    }
+}
+
+/// Returns the name of the `primitive` as written in Rust.
+fn primitive_name(primitive: &TypeDefPrimitive) -> &'static str {
+    match primitive {
+        TypeDefPrimitive::Bool => "bool",
+        TypeDefPrimitive::Char => "char",
+        TypeDefPrimitive::Str => "str",
+        TypeDefPrimitive::U8 => "u8",
+        TypeDefPrimitive::U16 => "u16",
+        TypeDefPrimitive::U32 => "u32",
+        TypeDefPrimitive::U64 => "u64",
+        TypeDefPrimitive::U128 => "u128",
+        TypeDefPrimitive::U256 => "u256",
+        TypeDefPrimitive::I8 => "i8",
+        TypeDefPrimitive::I16 => "i16",
+        TypeDefPrimitive::I32 => "i32",
+        TypeDefPrimitive::I64 => "i64",
+        TypeDefPrimitive::I128 => "i128",
+        TypeDefPrimitive::I256 => "i256",
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The host functions imported by a built contract.
+//!
+//! The host functions are read from the import section of the Wasm blob of the
+//! contract. They therefore include the host functions imported on behalf of the
+//! dependencies of the contract, e.g. storage collections, and nothing else. Build
+//! tools record them in the metadata with [`InkProject::with_host_functions`] once
+//! the contract has been built.
+
+use crate::InkProject;
+use wasmparser::{
+    BinaryReaderError,
+    Parser,
+    Payload,
+    TypeRef,
+};
+
+/// Returns the host functions imported by the `wasm` blob, sorted by name.
+///
+/// Every host function is given as `module::name` of its import, e.g.
+/// `seal0::caller`.
+///
+/// # Errors
+///
+/// If the Wasm blob is malformed.
+pub fn imported_host_functions(wasm: &[u8]) -> Result<Vec<String>, BinaryReaderError> {
+    let mut host_functions = Vec::new();
+    for payload in Parser::new(0).parse_all(wasm) {
+        if let Payload::ImportSection(reader) = payload? {
+            for import in reader.into_imports() {
+                let import = import?;
+                if let TypeRef::Func(_) = import.ty {
+                    host_functions.push(format!("{}::{}", import.module, import.name));
+                }
+            }
+        }
+    }
+    host_functions.sort();
+    host_functions.dedup();
+    Ok(host_functions)
+}
+
+impl InkProject {
+    /// Records the host functions imported by the built `wasm` blob of the contract
+    /// in its metadata.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm blob is malformed.
+    pub fn with_host_functions(mut self, wasm: &[u8]) -> Result<Self, BinaryReaderError> {
+        self.spec.set_host_functions(imported_host_functions(wasm)?);
+        Ok(self)
+    }
+}
@@ -0,0 +1,406 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rendering of SCALE encoded values as JSON for debugging and test output.
+//!
+//! The types of the values are resolved from a [`PortableRegistry`], e.g. the one of
+//! the contract metadata, so that events, call inputs and storage values can be
+//! printed in a human readable form without decoding them into their Rust types.
+//!
+//! The values are rendered as follows:
+//!
+//! - Structs with named fields as objects with the fields in alphabetical order,
+//!   tuple structs with a single field as the value of the field and other tuple
+//!   structs as well as tuples as arrays.
+//! - Enum variants without fields as their name and other variants as an object
+//!   mapping the name of the variant to its fields, e.g. `{"Some": 42}`.
+//! - Sequences and arrays of bytes as `0x` prefixed hex strings, other sequences
+//!   and arrays as arrays.
+//! - Integers as numbers if they fit into 64 bits and as strings otherwise, 256 bit
+//!   integers as hex strings of their encoding.
+
+use core::fmt;
+use scale_info::{
+    form::PortableForm,
+    scale::{
+        Compact,
+        Decode,
+    },
+    Field,
+    PortableRegistry,
+    TypeDef,
+    TypeDefPrimitive,
+};
+pub use serde_json::Value;
+use serde_json::{
+    Map,
+    Number,
+};
+
+/// Returned if a value can't be rendered as JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The type is not part of the registry.
+    UnknownType(u32),
+    /// The bytes don't encode a value of the type.
+    Decode(scale_info::scale::Error),
+    /// The discriminant of an enum doesn't belong to any of its variants.
+    UnknownVariant {
+        /// The ID of the enum type.
+        type_id: u32,
+        /// The decoded discriminant.
+        index: u8,
+    },
+    /// The type can't be rendered, e.g. a bit sequence.
+    UnsupportedType(u32),
+    /// There are bytes left after the value.
+    TrailingBytes(usize),
+}
+
+impl From<scale_info::scale::Error> for Error {
+    fn from(error: scale_info::scale::Error) -> Self {
+        Self::Decode(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownType(id) => write!(f, "type {} is not in the registry", id),
+            Self::Decode(error) => write!(f, "failed to decode value: {}", error),
+            Self::UnknownVariant { type_id, index } => {
+                write!(f, "type {} has no variant with index {}", type_id, index)
+            }
+            Self::UnsupportedType(id) => {
+                write!(f, "type {} can't be rendered as JSON", id)
+            }
+            Self::TrailingBytes(len) => {
+                write!(f, "{} bytes are left after the value", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Renders the SCALE encoded `bytes` of the type `type_id` of the `registry` as JSON.
+///
+/// # Errors
+///
+/// If the `bytes` don't encode exactly one value of the type, or the type can't be
+/// rendered.
+pub fn scale_to_json(
+    registry: &PortableRegistry,
+    type_id: u32,
+    mut bytes: &[u8],
+) -> Result<Value, Error> {
+    let value = render_value(registry, type_id, &mut bytes)?;
+    if !bytes.is_empty() {
+        return Err(Error::TrailingBytes(bytes.len()))
+    }
+    Ok(value)
+}
+
+/// Renders the value of the type `type_id` of the `registry` at the start of `input`
+/// as JSON and advances `input` past the value.
+///
+/// # Errors
+///
+/// If the start of `input` doesn't encode a value of the type, or the type can't be
+/// rendered.
+pub fn render_value(
+    registry: &PortableRegistry,
+    type_id: u32,
+    input: &mut &[u8],
+) -> Result<Value, Error> {
+    Renderer { registry }.render(type_id, input)
+}
+
+/// Renders the SCALE encoded `bytes` of a `T` as JSON.
+///
+/// # Errors
+///
+/// If the `bytes` don't encode exactly one `T`, or `T` can't be rendered.
+pub fn to_json<T>(bytes: &[u8]) -> Result<Value, Error>
+where
+    T: scale_info::TypeInfo + 'static,
+{
+    let mut registry = scale_info::Registry::new();
+    let type_id = registry.register_type(&scale_info::meta_type::<T>()).id;
+    scale_to_json(&registry.into(), type_id, bytes)
+}
+
+/// Renders values of the types of a registry.
+struct Renderer<'a> {
+    registry: &'a PortableRegistry,
+}
+
+impl Renderer<'_> {
+    /// Renders the value of the type `type_id` at the start of `input`.
+    fn render(&self, type_id: u32, input: &mut &[u8]) -> Result<Value, Error> {
+        let ty = self
+            .registry
+            .resolve(type_id)
+            .ok_or(Error::UnknownType(type_id))?;
+        match &ty.type_def {
+            TypeDef::Composite(composite) => self.render_fields(&composite.fields, input),
+            TypeDef::Variant(variant) => {
+                let index = u8::decode(input)?;
+                let variant = variant
+                    .variants
+                    .iter()
+                    .find(|variant| variant.index == index)
+                    .ok_or(Error::UnknownVariant { type_id, index })?;
+                if variant.fields.is_empty() {
+                    return Ok(Value::String(variant.name.clone()))
+                }
+                let fields = self.render_fields(&variant.fields, input)?;
+                Ok(Value::Object(Map::from_iter([(
+                    variant.name.clone(),
+                    fields,
+                )])))
+            }
+            TypeDef::Sequence(sequence) => {
+                let len = Compact::<u32>::decode(input)?.0;
+                self.render_elements(sequence.type_param.id, len, input)
+            }
+            TypeDef::Array(array) => {
+                self.render_elements(array.type_param.id, array.len, input)
+            }
+            TypeDef::Tuple(tuple) => {
+                if tuple.fields.is_empty() {
+                    return Ok(Value::Null)
+                }
+                tuple
+                    .fields
+                    .iter()
+                    .map(|field| self.render(field.id, input))
+                    .collect()
+            }
+            TypeDef::Primitive(primitive) => render_primitive(primitive, input),
+            TypeDef::Compact(compact) => {
+                self.render_compact(compact.type_param.id, input)
+            }
+            TypeDef::BitSequence(_) => Err(Error::UnsupportedType(type_id)),
+        }
+    }
+
+    /// Renders the `fields` of a struct or an enum variant.
+    fn render_fields(
+        &self,
+        fields: &[Field<PortableForm>],
+        input: &mut &[u8],
+    ) -> Result<Value, Error> {
+        match fields {
+            [] => Ok(Value::Null),
+            [field] if field.name.is_none() => self.render(field.ty.id, input),
+            fields if fields.iter().all(|field| field.name.is_some()) => {
+                fields
+                    .iter()
+                    .map(|field| {
+                        let name = field.name.clone().expect("checked above; qed");
+                        Ok((name, self.render(field.ty.id, input)?))
+                    })
+                    .collect::<Result<Map<_, _>, _>>()
+                    .map(Value::Object)
+            }
+            fields => {
+                fields
+                    .iter()
+                    .map(|field| self.render(field.ty.id, input))
+                    .collect()
+            }
+        }
+    }
+
+    /// Renders `len` elements of the type `type_id`, as a hex string for bytes.
+    fn render_elements(
+        &self,
+        type_id: u32,
+        len: u32,
+        input: &mut &[u8],
+    ) -> Result<Value, Error> {
+        let is_byte = matches!(
+            self.registry.resolve(type_id).map(|ty| &ty.type_def),
+            Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+        );
+        if is_byte {
+            // Reject the length before allocating, it is read from untrusted input.
+            let bytes = input.get(..len as usize).ok_or_else(|| {
+                scale_info::scale::Error::from("Not enough data to fill buffer")
+            })?;
+            *input = &input[len as usize..];
+            return Ok(hex(bytes))
+        }
+        (0..len).map(|_| self.render(type_id, input)).collect()
+    }
+
+    /// Renders the compact encoding of the type `type_id`.
+    fn render_compact(&self, type_id: u32, input: &mut &[u8]) -> Result<Value, Error> {
+        let ty = self
+            .registry
+            .resolve(type_id)
+            .ok_or(Error::UnknownType(type_id))?;
+        match &ty.type_def {
+            TypeDef::Primitive(
+                TypeDefPrimitive::U8
+                | TypeDefPrimitive::U16
+                | TypeDefPrimitive::U32
+                | TypeDefPrimitive::U64
+                | TypeDefPrimitive::U128,
+            ) => Ok(unsigned(Compact::<u128>::decode(input)?.0)),
+            // A struct wrapping a single integer has the compact encoding of it.
+            TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+                let field = &composite.fields[0];
+                let value = self.render_compact(field.ty.id, input)?;
+                Ok(match &field.name {
+                    Some(name) => Value::Object(Map::from_iter([(name.clone(), value)])),
+                    None => value,
+                })
+            }
+            _ => Err(Error::UnsupportedType(type_id)),
+        }
+    }
+}
+
+/// Renders the primitive value at the start of `input`.
+fn render_primitive(
+    primitive: &TypeDefPrimitive,
+    input: &mut &[u8],
+) -> Result<Value, Error> {
+    Ok(match primitive {
+        TypeDefPrimitive::Bool => Value::Bool(bool::decode(input)?),
+        TypeDefPrimitive::Char => {
+            let code = u32::decode(input)?;
+            let char = char::from_u32(code)
+                .ok_or_else(|| scale_info::scale::Error::from("invalid char"))?;
+            Value::String(char.into())
+        }
+        TypeDefPrimitive::Str => Value::String(String::decode(input)?),
+        TypeDefPrimitive::U8 => Value::from(u8::decode(input)?),
+        TypeDefPrimitive::U16 => Value::from(u16::decode(input)?),
+        TypeDefPrimitive::U32 => Value::from(u32::decode(input)?),
+        TypeDefPrimitive::U64 => Value::from(u64::decode(input)?),
+        TypeDefPrimitive::U128 => unsigned(u128::decode(input)?),
+        TypeDefPrimitive::I8 => Value::from(i8::decode(input)?),
+        TypeDefPrimitive::I16 => Value::from(i16::decode(input)?),
+        TypeDefPrimitive::I32 => Value::from(i32::decode(input)?),
+        TypeDefPrimitive::I64 => Value::from(i64::decode(input)?),
+        TypeDefPrimitive::I128 => {
+            let value = i128::decode(input)?;
+            match i64::try_from(value) {
+                Ok(value) => Value::from(value),
+                Err(_) => Value::String(value.to_string()),
+            }
+        }
+        TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => {
+            hex(&<[u8; 32]>::decode(input)?)
+        }
+    })
+}
+
+/// Renders `value` as a number if it fits into 64 bits and as a string otherwise.
+fn unsigned(value: u128) -> Value {
+    match u64::try_from(value) {
+        Ok(value) => Value::Number(Number::from(value)),
+        Err(_) => Value::String(value.to_string()),
+    }
+}
+
+/// Renders `bytes` as a `0x` prefixed hex string.
+fn hex(bytes: &[u8]) -> Value {
+    Value::String(impl_serde::serialize::to_hex(bytes, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode;
+    use scale_info::TypeInfo;
+    use serde_json::json;
+
+    #[derive(Encode, TypeInfo)]
+    struct Transfer {
+        from: Option<[u8; 4]>,
+        value: u128,
+        memo: Vec<u8>,
+        #[codec(compact)]
+        nonce: u64,
+    }
+
+    #[derive(Encode, TypeInfo)]
+    enum Event {
+        Paused,
+        Transferred(Transfer),
+        Moved(u8, (bool, String)),
+    }
+
+    #[test]
+    fn renders_composites_and_variants() {
+        let event = Event::Transferred(Transfer {
+            from: Some([1, 2, 3, 4]),
+            value: u128::MAX,
+            memo: b"hi".to_vec(),
+            nonce: 300,
+        });
+
+        assert_eq!(
+            to_json::<Event>(&event.encode()).unwrap(),
+            json!({
+                "Transferred": {
+                    "from": { "Some": "0x01020304" },
+                    "value": u128::MAX.to_string(),
+                    "memo": "0x6869",
+                    "nonce": 300,
+                }
+            })
+        );
+        assert_eq!(
+            to_json::<Event>(&Event::Paused.encode()).unwrap(),
+            json!("Paused")
+        );
+        assert_eq!(
+            to_json::<Event>(&Event::Moved(7, (true, "x".into())).encode()).unwrap(),
+            json!({ "Moved": [7, [true, "x"]] })
+        );
+        assert_eq!(
+            to_json::<Vec<(String, i128)>>(&vec![("a".to_string(), -1i128)].encode())
+                .unwrap(),
+            json!([["a", -1]])
+        );
+    }
+
+    #[test]
+    fn invalid_encodings_are_rejected() {
+        assert!(matches!(
+            to_json::<Event>(&[9]),
+            Err(Error::UnknownVariant { index: 9, .. })
+        ));
+        assert!(matches!(to_json::<u32>(&[1, 2]), Err(Error::Decode(_))));
+        assert!(matches!(
+            to_json::<u8>(&[1, 2]),
+            Err(Error::TrailingBytes(1))
+        ));
+        // The length of the bytes exceeds the input.
+        assert!(matches!(
+            to_json::<Vec<u8>>(&scale::Compact(u32::MAX).encode()),
+            Err(Error::Decode(_))
+        ));
+        let registry = PortableRegistry::from(scale_info::Registry::new());
+        assert!(matches!(
+            scale_to_json(&registry, 0, &[]),
+            Err(Error::UnknownType(0))
+        ));
+    }
+}
@@ -24,11 +24,16 @@ use crate::{
     utils::{
         deserialize_from_byte_str,
         serialize_as_byte_str,
+        trim_extra_whitespace,
     },
 };
 use derive_more::From;
 use ink_prelude::collections::btree_map::BTreeMap;
-use ink_primitives::Key;
+use ink_primitives::{
+    HexKey,
+    Key,
+    KeyComposer,
+};
 use scale_info::{
     form::{
         Form,
@@ -73,6 +78,19 @@ pub enum Layout<F: Form = MetaForm> {
     Enum(EnumLayout<F>),
 }
 
+/// The version of the storage layout format.
+///
+/// It is recorded next to the storage layout in the metadata, so that tools can
+/// reject layouts written in a format they do not understand instead of
+/// misinterpreting them. Metadata without a version is read as [`LayoutVersion::V1`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutVersion {
+    /// Keys are `0x` prefixed 4 byte big endian hex strings.
+    #[default]
+    #[serde(rename = "1")]
+    V1,
+}
+
 /// A pointer into some storage region.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, From)]
 pub struct LayoutKey {
@@ -89,13 +107,30 @@ impl serde::Serialize for LayoutKey {
 }
 
 impl<'de> serde::Deserialize<'de> for LayoutKey {
+    /// Accepts `0x` prefixed big endian hex of at most 4 bytes.
+    ///
+    /// Shorter representations such as `"0x159"` are zero-extended, so that keys
+    /// written by hand or by other tools are read the same as the normalized
+    /// 8 digit form emitted by the serializer.
     fn deserialize<D>(d: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let mut arr = [0; 4];
-        serde_hex::deserialize_check_len(d, serde_hex::ExpectedLen::Exact(&mut arr[..]))?;
-        Ok(Key::from_be_bytes(arr).into())
+        let hex = String::deserialize(d)?;
+        let digits = hex.strip_prefix("0x").ok_or_else(|| {
+            serde::de::Error::custom(format!("layout key `{}` is missing 0x prefix", hex))
+        })?;
+        if digits.is_empty() || digits.len() > 2 * core::mem::size_of::<Key>() {
+            return Err(serde::de::Error::custom(format!(
+                "layout key `{}` must have between 1 and 8 hex digits",
+                hex
+            )))
+        }
+        Key::from_str_radix(digits, 16)
+            .map(Into::into)
+            .map_err(|_| {
+                serde::de::Error::custom(format!("layout key `{}` is not valid hex", hex))
+            })
     }
 }
 
@@ -105,6 +140,13 @@ impl<'a> From<&'a Key> for LayoutKey {
     }
 }
 
+impl Display for LayoutKey {
+    /// Formats the key as in the metadata, i.e. as `0x` prefixed big endian hex.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", HexKey(self.key))
+    }
+}
+
 impl LayoutKey {
     /// Construct a custom layout key.
     pub fn new<T>(key: T) -> Self
@@ -118,6 +160,41 @@ impl LayoutKey {
     pub fn key(&self) -> &Key {
         &self.key
     }
+
+    /// Returns the raw bytes used by the contracts pallet to identify the storage cell.
+    ///
+    /// # Note
+    ///
+    /// The bytes are the SCALE encoding of the key, i.e. in little endian order,
+    /// whereas the metadata represents the key in big endian order.
+    pub fn to_storage_bytes(&self) -> [u8; 4] {
+        KeyComposer::to_storage_bytes(self.key)
+    }
+
+    /// Returns the key of a storage item with the `key` nested below this key.
+    ///
+    /// This derives the key the same way the runtime does for a non-packed
+    /// storage item, e.g. `ManualKey<KEY, ParentKey>`, so that the result matches
+    /// the key under which the item is actually stored.
+    pub fn concat(&self, key: Key) -> Self {
+        KeyComposer::concat(key, self.key).into()
+    }
+
+    /// Returns the key of the `field_name` field of a non-packed struct or enum
+    /// variant stored below this key.
+    ///
+    /// The `variant_name` is empty for structs. Returns `None` if `struct_name`
+    /// or `field_name` is empty.
+    pub fn field(
+        &self,
+        struct_name: &str,
+        variant_name: &str,
+        field_name: &str,
+    ) -> Option<Self> {
+        KeyComposer::compute_key(struct_name, variant_name, field_name)
+            .ok()
+            .map(|key| self.concat(key))
+    }
 }
 
 /// Sub-tree root.
@@ -181,6 +258,9 @@ pub struct LeafLayout<F: Form = MetaForm> {
     key: LayoutKey,
     /// The type of the encoded entity.
     ty: <F as Form>::Type,
+    /// The maximum number of elements of a bounded collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    capacity: Option<u32>,
 }
 
 impl LeafLayout {
@@ -192,6 +272,18 @@ impl LeafLayout {
         Self {
             key,
             ty: meta_type::<T>(),
+            capacity: None,
+        }
+    }
+
+    /// Creates a new cell layout of a collection holding at most `capacity` elements.
+    pub fn from_key_bounded<T>(key: LayoutKey, capacity: u32) -> Self
+    where
+        T: TypeInfo + 'static,
+    {
+        Self {
+            capacity: Some(capacity),
+            ..Self::from_key::<T>(key)
         }
     }
 }
@@ -203,6 +295,7 @@ impl IntoPortable for LeafLayout {
         LeafLayout {
             key: self.key,
             ty: registry.register_type(&self.ty),
+            capacity: self.capacity,
         }
     }
 }
@@ -248,8 +341,17 @@ where
         &self.ty
     }
 
+    /// Returns the maximum number of elements if the entity is a bounded collection.
+    pub fn capacity(&self) -> Option<u32> {
+        self.capacity
+    }
+
     pub fn new(key: LayoutKey, ty: <F as Form>::Type) -> Self {
-        Self { key, ty }
+        Self {
+            key,
+            ty,
+            capacity: None,
+        }
     }
 }
 
@@ -375,6 +477,9 @@ pub enum CryptoHasher {
     Sha2x256,
     /// The KECCAK crypto hasher with an output of 256 bits.
     Keccak256,
+    /// The BLAKE-2 crypto hasher with an output of 128 bits, followed by the
+    /// encoded key itself.
+    Blake2x128Concat,
 }
 
 /// A layout for an array of associated cells with the same encoding.
@@ -513,6 +618,12 @@ pub struct FieldLayout<F: Form = MetaForm> {
     /// This is either a direct layout bound
     /// or another recursive layout sub-struct.
     layout: Layout<F>,
+    /// The Rust type of the field as written in its definition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    type_name: Option<F::String>,
+    /// The documentation of the field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    docs: Vec<F::String>,
 }
 
 impl<F> FieldLayout<F>
@@ -528,6 +639,35 @@ where
         Self {
             name: name.into(),
             layout: layout.into(),
+            type_name: None,
+            docs: Vec::new(),
+        }
+    }
+
+    /// Sets the Rust type of the field as written in its definition,
+    /// e.g. `Mapping<AccountId, u128>`.
+    pub fn with_type_name<N>(self, type_name: N) -> Self
+    where
+        N: Into<F::String>,
+    {
+        Self {
+            type_name: Some(type_name.into()),
+            ..self
+        }
+    }
+
+    /// Sets the documentation of the field.
+    pub fn with_docs<'a, D>(self, docs: D) -> Self
+    where
+        D: IntoIterator<Item = &'a str>,
+        F::String: From<&'a str>,
+    {
+        Self {
+            docs: docs
+                .into_iter()
+                .map(|doc| trim_extra_whitespace(doc).into())
+                .collect(),
+            ..self
         }
     }
 
@@ -543,6 +683,16 @@ where
     pub fn layout(&self) -> &Layout<F> {
         &self.layout
     }
+
+    /// Returns the Rust type of the field as written in its definition if any.
+    pub fn type_name(&self) -> Option<&F::String> {
+        self.type_name.as_ref()
+    }
+
+    /// Returns the documentation of the field.
+    pub fn docs(&self) -> &[F::String] {
+        &self.docs
+    }
 }
 
 impl IntoPortable for FieldLayout {
@@ -552,6 +702,8 @@ impl IntoPortable for FieldLayout {
         FieldLayout {
             name: self.name.to_string(),
             layout: self.layout.into_portable(registry),
+            type_name: self.type_name.map(|type_name| type_name.to_string()),
+            docs: self.docs.into_iter().map(|doc| doc.to_string()).collect(),
         }
     }
 }
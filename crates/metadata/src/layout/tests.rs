@@ -13,7 +13,10 @@
 // limitations under the License.
 
 use super::*;
-use ink_primitives::Key;
+use ink_primitives::{
+    Key,
+    KeyComposer,
+};
 use scale_info::Path;
 
 #[test]
@@ -23,6 +26,69 @@ fn layout_key_works() {
     assert_eq!(json, "\"0x00000001\"",);
 }
 
+#[test]
+fn layout_key_display_and_storage_bytes_work() {
+    let layout_key = LayoutKey::from(0x12345678);
+    assert_eq!(layout_key.to_string(), "0x12345678");
+    assert_eq!(
+        serde_json::to_string(&layout_key).unwrap(),
+        format!("\"{}\"", layout_key)
+    );
+    assert_eq!(layout_key.to_storage_bytes(), [0x78, 0x56, 0x34, 0x12]);
+}
+
+#[test]
+fn layout_key_deserialize_normalizes_width() {
+    let short: LayoutKey = serde_json::from_str("\"0x159\"").unwrap();
+    let full: LayoutKey = serde_json::from_str("\"0x00000159\"").unwrap();
+    assert_eq!(short, LayoutKey::from(&345));
+    assert_eq!(short, full);
+    assert_eq!(serde_json::to_string(&short).unwrap(), "\"0x00000159\"");
+}
+
+#[test]
+fn layout_key_deserialize_rejects_invalid_keys() {
+    for invalid in ["\"159\"", "\"0x\"", "\"0x000000159\"", "\"0xzz\""] {
+        assert!(
+            serde_json::from_str::<LayoutKey>(invalid).is_err(),
+            "{} must be rejected",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn layout_key_derivation_matches_runtime() {
+    let root = LayoutKey::from(KeyComposer::from_str("Contract::a"));
+    assert_eq!(
+        root.concat(123),
+        LayoutKey::from(KeyComposer::concat(123, *root.key()))
+    );
+    assert_eq!(LayoutKey::new(0u32).concat(123), LayoutKey::new(123u32));
+    assert_eq!(
+        root.field("NonPacked", "", "b"),
+        Some(LayoutKey::from(KeyComposer::concat(
+            KeyComposer::from_str("NonPacked::b"),
+            KeyComposer::from_str("Contract::a"),
+        )))
+    );
+    assert_eq!(
+        root.field("NonPacked", "A", "0"),
+        Some(root.concat(KeyComposer::from_str("NonPacked::A::0")))
+    );
+    assert_eq!(root.field("", "", "b"), None);
+    assert_eq!(root.field("NonPacked", "", ""), None);
+}
+
+#[test]
+fn layout_version_works() {
+    assert_eq!(
+        serde_json::to_string(&LayoutVersion::default()).unwrap(),
+        "\"1\""
+    );
+    assert!(serde_json::from_str::<LayoutVersion>("\"2\"").is_err());
+}
+
 fn named_fields_struct_layout(key: &Key) -> Layout {
     StructLayout::new(
         "Struct",
@@ -70,6 +136,53 @@ fn named_fields_work() {
     assert_eq!(json, expected);
 }
 
+#[test]
+fn field_type_name_and_docs_work() {
+    let layout: Layout = StructLayout::new(
+        "Struct",
+        vec![FieldLayout::new(
+            "balances",
+            LeafLayout::from_key::<u128>(LayoutKey::from(&345)),
+        )
+        .with_type_name("Mapping<AccountId, u128>")
+        .with_docs([" The balances of all accounts.  "])],
+    )
+    .into();
+    let mut registry = Registry::new();
+    let compacted = layout.into_portable(&mut registry);
+    let json = serde_json::to_value(&compacted).unwrap();
+    let expected = serde_json::json! {
+        {
+            "struct": {
+                "fields": [
+                    {
+                        "docs": ["The balances of all accounts."],
+                        "layout": {
+                            "leaf": {
+                                "key": "0x00000159",
+                                "ty": 0,
+                            }
+                        },
+                        "name": "balances",
+                        "type_name": "Mapping<AccountId, u128>",
+                    }
+                ],
+                "name": "Struct",
+            }
+        }
+    };
+    assert_eq!(json, expected);
+    let field = match &compacted {
+        Layout::Struct(layout) => &layout.fields()[0],
+        _ => unreachable!("encountered unexpected layout"),
+    };
+    assert_eq!(
+        field.type_name().map(String::as_str),
+        Some("Mapping<AccountId, u128>")
+    );
+    assert_eq!(field.docs(), ["The balances of all accounts."]);
+}
+
 fn tuple_struct_layout(key: &Key) -> Layout {
     StructLayout::new(
         "(A, B)",
@@ -320,6 +433,31 @@ fn unbounded_layout_works() {
     assert_eq!(json, expected);
 }
 
+#[test]
+fn bounded_leaf_layout_works() {
+    let layout: Layout =
+        LeafLayout::from_key_bounded::<Vec<u8>>(LayoutKey::from(&1), 32).into();
+    let mut registry = Registry::new();
+    let compacted = layout.into_portable(&mut registry);
+    let json = serde_json::to_value(&compacted).unwrap();
+    let expected = serde_json::json! {
+        {
+            "leaf": {
+                "key": "0x00000001",
+                "ty": 0,
+                "capacity": 32
+            }
+        }
+    };
+    assert_eq!(json, expected);
+    let decoded: Layout<PortableForm> = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, compacted);
+    match decoded {
+        Layout::Leaf(leaf) => assert_eq!(leaf.capacity(), Some(32)),
+        _ => panic!("expected a leaf layout"),
+    }
+}
+
 #[test]
 fn runtime_storage_layout_works() {
     let key = LayoutKey::new(0u32);
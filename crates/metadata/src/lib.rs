@@ -24,18 +24,37 @@ extern crate alloc;
 #[cfg(test)]
 mod tests;
 
+mod custom_types;
+#[cfg(feature = "host-functions")]
+pub mod host_functions;
+#[cfg(feature = "std")]
+pub mod json;
 pub mod layout;
+#[cfg(feature = "size-report")]
+pub mod size_report;
 mod specs;
+#[cfg(feature = "std")]
+pub mod topics;
 mod utils;
 
 pub use ink_primitives::LangError;
 
 pub use self::specs::{
+    ChainConfigSpec,
+    ChainConfigSpecBuilder,
+    ChainExtensionMethodSpec,
+    ChainExtensionSpec,
+    ChainExtensionSpecBuilder,
+    ConstantSpec,
     ConstructorSpec,
     ConstructorSpecBuilder,
     ContractSpec,
     ContractSpecBuilder,
+    DisplayHint,
     DisplayName,
+    ErrorCodeSpec,
+    ErrorSpec,
+    ErrorSpecBuilder,
     EventParamSpec,
     EventParamSpecBuilder,
     EventSpec,
@@ -44,11 +63,18 @@ pub use self::specs::{
     MessageParamSpecBuilder,
     MessageSpec,
     MessageSpecBuilder,
+    ParamDefault,
     ReturnTypeSpec,
     Selector,
+    SignatureTopic,
     TypeSpec,
 };
 
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    string::String,
+};
 use impl_serde::serialize as serde_hex;
 
 #[cfg(feature = "derive")]
@@ -62,6 +88,8 @@ use serde::{
     Deserialize,
     Serialize,
 };
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
 
 /// The metadata version of the generated ink! contract.
 ///
@@ -91,10 +119,20 @@ pub struct InkProject {
     version: MetadataVersion,
     #[serde(flatten)]
     registry: PortableRegistry,
+    /// The version of the storage layout format.
+    #[serde(default, rename = "layoutVersion")]
+    layout_version: layout::LayoutVersion,
     #[serde(rename = "storage")]
     /// The layout of the storage data structure
     layout: layout::Layout<PortableForm>,
     spec: ContractSpec<PortableForm>,
+    /// The ids of the structs and enums in the registry by their stable name.
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        rename = "customTypes"
+    )]
+    custom_types: BTreeMap<String, u32>,
 }
 
 impl InkProject {
@@ -105,13 +143,9 @@ impl InkProject {
         S: Into<ContractSpec>,
     {
         let mut registry = Registry::new();
-
-        Self {
-            version: Default::default(),
-            layout: layout.into().into_portable(&mut registry),
-            spec: spec.into().into_portable(&mut registry),
-            registry: registry.into(),
-        }
+        let layout = layout.into().into_portable(&mut registry);
+        let spec = spec.into().into_portable(&mut registry);
+        Self::new_portable(layout, spec, registry.into())
     }
 
     /// Create a new portable ink! project.
@@ -124,8 +158,10 @@ impl InkProject {
     ) -> Self {
         Self {
             version: Default::default(),
+            layout_version: Default::default(),
             layout,
             spec,
+            custom_types: custom_types::custom_types(&registry),
             registry,
         }
     }
@@ -140,6 +176,11 @@ impl InkProject {
         &self.registry
     }
 
+    /// Returns the version of the storage layout format used by the contract.
+    pub fn layout_version(&self) -> layout::LayoutVersion {
+        self.layout_version
+    }
+
     /// Returns the storage layout of the contract.
     pub fn layout(&self) -> &layout::Layout<PortableForm> {
         &self.layout
@@ -149,4 +190,14 @@ impl InkProject {
     pub fn spec(&self) -> &ContractSpec<PortableForm> {
         &self.spec
     }
+
+    /// Returns the ids of the structs and enums in the registry by their stable name.
+    ///
+    /// The id of a type in the registry differs between contracts, while its stable
+    /// name, i.e. its path and type arguments, is the same for all contracts using
+    /// the type. Tools use it to recognize the types shared by several contracts,
+    /// e.g. the types of an `#[ink::shared]` crate.
+    pub fn custom_types(&self) -> &BTreeMap<String, u32> {
+        &self.custom_types
+    }
 }
@@ -0,0 +1,415 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Attribution of the code size of a built contract to its ink! constructors,
+//! messages and events.
+//!
+//! The sizes are the sizes of the function bodies in the code section of the Wasm
+//! blob. Functions are attributed by their symbol in the name section, hence the
+//! report must be created from the Wasm blob as emitted by the compiler, before
+//! the symbols are stripped, e.g. by `wasm-opt`.
+//!
+//! Code inlined into another function is counted for the function it is inlined
+//! into. The `size_report` option of `#[ink::contract]` therefore keeps the ink!
+//! constructors and messages from being inlined into the dispatch of the contract.
+
+use crate::{
+    layout::Layout,
+    InkProject,
+    Selector,
+};
+use core::fmt;
+use rustc_demangle::demangle;
+use serde::Serialize;
+use std::collections::HashMap;
+use wasmparser::{
+    BinaryReaderError,
+    KnownCustom,
+    Name,
+    Parser,
+    Payload,
+    TypeRef,
+};
+
+/// Returned if the size report can't be created from a Wasm blob.
+#[derive(Debug)]
+pub enum Error {
+    /// The Wasm blob is malformed.
+    Wasm(BinaryReaderError),
+    /// The Wasm blob has no function names, e.g. because it has been optimized.
+    MissingNames,
+    /// The storage layout of the contract is not the layout of a struct.
+    UnknownStorage,
+}
+
+impl From<BinaryReaderError> for Error {
+    fn from(error: BinaryReaderError) -> Self {
+        Self::Wasm(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Wasm(error) => write!(f, "failed to parse Wasm: {}", error),
+            Self::MissingNames => {
+                write!(
+                    f,
+                    "the Wasm has no function names, it must be taken from the \
+                    build before it is optimized"
+                )
+            }
+            Self::UnknownStorage => {
+                write!(f, "the storage layout of the contract is not a struct")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The code size of an ink! constructor, message or event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntrySize {
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selector: Option<Selector>,
+    size: usize,
+}
+
+impl EntrySize {
+    fn new(label: &str, selector: Option<Selector>) -> Self {
+        Self {
+            label: label.to_string(),
+            selector,
+            size: 0,
+        }
+    }
+
+    /// Returns the label of the constructor, message or event.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the selector of the constructor or message.
+    pub fn selector(&self) -> Option<&Selector> {
+        self.selector.as_ref()
+    }
+
+    /// Returns the size of the functions attributed to the entry in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// The code size of a built contract per ink! constructor, message and event.
+///
+/// # Usage
+///
+/// ```no_compile
+/// let wasm = std::fs::read("target/wasm32-unknown-unknown/release/flipper.wasm")?;
+/// let report = SizeReport::from_wasm(&project, &wasm)?;
+/// println!("{}", serde_json::to_string_pretty(&report)?);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SizeReport {
+    code_size: usize,
+    constructors: Vec<EntrySize>,
+    messages: Vec<EntrySize>,
+    events: Vec<EntrySize>,
+    unattributed: usize,
+}
+
+impl SizeReport {
+    /// Creates the size report of the contract of `project` from its `wasm` blob.
+    ///
+    /// A function is attributed to a constructor or message if it is the
+    /// constructor or message itself or a closure in it, and to an event if it
+    /// implements a trait for the event, e.g. its encoding. The size of all other
+    /// functions, e.g. the dispatch and the standard library, is reported as
+    /// unattributed.
+    ///
+    /// # Errors
+    ///
+    /// If the `wasm` blob is malformed or has no function names.
+    pub fn from_wasm(project: &InkProject, wasm: &[u8]) -> Result<Self, Error> {
+        let layout = match project.layout() {
+            Layout::Root(root) => root.layout(),
+            layout => layout,
+        };
+        let storage = match layout {
+            Layout::Struct(layout) => layout.name().as_str(),
+            _ => return Err(Error::UnknownStorage),
+        };
+        let spec = project.spec();
+        let mut report = Self {
+            code_size: 0,
+            constructors: spec
+                .constructors()
+                .iter()
+                .map(|constructor| {
+                    EntrySize::new(
+                        constructor.label(),
+                        Some(constructor.selector().clone()),
+                    )
+                })
+                .collect(),
+            messages: spec
+                .messages()
+                .iter()
+                .map(|message| {
+                    EntrySize::new(message.label(), Some(message.selector().clone()))
+                })
+                .collect(),
+            events: spec
+                .events()
+                .iter()
+                .map(|event| EntrySize::new(event.label(), None))
+                .collect(),
+            unattributed: 0,
+        };
+        for (symbol, size) in function_sizes(wasm)? {
+            report.code_size += size;
+            let path = format!("{:#}", demangle(&symbol));
+            let entry = match attribute(&path, storage) {
+                Some(Owner::Callable(label)) => {
+                    report
+                        .constructors
+                        .iter_mut()
+                        .chain(report.messages.iter_mut())
+                        .find(|entry| entry.label == label)
+                }
+                Some(Owner::Type(label)) => {
+                    report.events.iter_mut().find(|entry| entry.label == label)
+                }
+                None => None,
+            };
+            match entry {
+                Some(entry) => entry.size += size,
+                None => report.unattributed += size,
+            }
+        }
+        Ok(report)
+    }
+
+    /// Returns the size of the code section in bytes.
+    pub fn code_size(&self) -> usize {
+        self.code_size
+    }
+
+    /// Returns the sizes of the constructors.
+    pub fn constructors(&self) -> &[EntrySize] {
+        &self.constructors
+    }
+
+    /// Returns the sizes of the messages.
+    pub fn messages(&self) -> &[EntrySize] {
+        &self.messages
+    }
+
+    /// Returns the sizes of the events.
+    pub fn events(&self) -> &[EntrySize] {
+        &self.events
+    }
+
+    /// Returns the size of the functions not attributed to any entry in bytes.
+    pub fn unattributed(&self) -> usize {
+        self.unattributed
+    }
+}
+
+/// Returns the symbols and body sizes of the functions defined by the `wasm` blob.
+fn function_sizes(wasm: &[u8]) -> Result<Vec<(String, usize)>, Error> {
+    let mut imported = 0;
+    let mut sizes = Vec::new();
+    let mut names = HashMap::new();
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_imports() {
+                    if let TypeRef::Func(_) = import?.ty {
+                        imported += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => sizes.push(body.range().len()),
+            Payload::CustomSection(reader) => {
+                if let KnownCustom::Name(reader) = reader.as_known() {
+                    for name in reader {
+                        if let Name::Function(map) = name? {
+                            for naming in map {
+                                let naming = naming?;
+                                names.insert(naming.index, naming.name.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if names.is_empty() && !sizes.is_empty() {
+        return Err(Error::MissingNames)
+    }
+    Ok(sizes
+        .into_iter()
+        .enumerate()
+        .map(|(index, size)| {
+            let symbol = names.remove(&(imported + index as u32)).unwrap_or_default();
+            (symbol, size)
+        })
+        .collect())
+}
+
+/// The entry a function is attributed to.
+#[derive(Debug, PartialEq, Eq)]
+enum Owner {
+    /// A constructor or message with the label.
+    Callable(String),
+    /// A trait implementation for the type with the name, e.g. an event.
+    Type(String),
+}
+
+/// Returns the entry the function with the demangled `path` is attributed to.
+///
+/// Inherent functions of the `storage` struct, e.g. `flipper::Flipper::flip` and
+/// `flipper::Flipper::flip::{{closure}}`, are attributed to the callable with
+/// the name of the function. Trait implementations for the `storage` struct, e.g.
+/// `<flipper::Erc20 as flipper::BaseErc20>::total_supply`, are attributed to the
+/// callable labeled with the trait and function name. Trait implementations for
+/// other types are attributed to the type.
+fn attribute(path: &str, storage: &str) -> Option<Owner> {
+    let segments = split_path(path);
+    if let Some((self_ty, trait_path)) = segments.first().and_then(|s| qualified(s)) {
+        let self_ty = last_ident(self_ty);
+        if self_ty != storage {
+            return Some(Owner::Type(self_ty.to_string()))
+        }
+        let function = segments.get(1)?;
+        return Some(Owner::Callable(format!(
+            "{}::{}",
+            last_ident(trait_path),
+            function
+        )))
+    }
+    segments
+        .windows(2)
+        .find(|window| window[0] == storage)
+        .map(|window| Owner::Callable(window[1].to_string()))
+}
+
+/// Splits the `path` at the `::` separators outside of angle brackets.
+fn split_path(path: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut previous = None;
+    for (index, char) in path.char_indices() {
+        match char {
+            '<' => depth += 1,
+            // The arrow of function pointer types doesn't close a bracket.
+            '>' if previous != Some('-') => depth = depth.saturating_sub(1),
+            ':' if depth == 0 && path[index..].starts_with("::") && index >= start => {
+                segments.push(&path[start..index]);
+                start = index + 2;
+            }
+            _ => {}
+        }
+        previous = Some(char);
+    }
+    segments.push(&path[start..]);
+    segments
+}
+
+/// Returns the self type and the trait of a qualified path segment like
+/// `<a::B as c::D>`.
+fn qualified(segment: &str) -> Option<(&str, &str)> {
+    let inner = segment.strip_prefix('<')?.strip_suffix('>')?;
+    let mut depth = 0usize;
+    for (index, char) in inner.char_indices() {
+        match char {
+            '<' => depth += 1,
+            '>' if !inner[..index].ends_with('-') => depth = depth.saturating_sub(1),
+            ' ' if depth == 0 && inner[index..].starts_with(" as ") => {
+                return Some((&inner[..index], &inner[index + 4..]))
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the last identifier of a path without generic arguments, e.g. `C` for
+/// `a::b::C<D>`.
+fn last_ident(path: &str) -> &str {
+    let last = split_path(path).pop().unwrap_or(path);
+    last.split('<').next().unwrap_or(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn callable(label: &str) -> Option<Owner> {
+        Some(Owner::Callable(label.to_string()))
+    }
+
+    #[test]
+    fn inherent_functions_are_attributed_to_callables() {
+        assert_eq!(
+            attribute("flipper::flipper::Flipper::flip", "Flipper"),
+            callable("flip")
+        );
+        assert_eq!(
+            attribute("flipper::flipper::Flipper::flip::{{closure}}", "Flipper"),
+            callable("flip")
+        );
+        assert_eq!(attribute("flipper::flipper::Other::flip", "Flipper"), None);
+        assert_eq!(attribute("call", "Flipper"), None);
+    }
+
+    #[test]
+    fn trait_functions_are_attributed_to_callables_and_types() {
+        assert_eq!(
+            attribute(
+                "<erc20::erc20::Erc20 as erc20::BaseErc20>::total_supply",
+                "Erc20"
+            ),
+            callable("BaseErc20::total_supply")
+        );
+        assert_eq!(
+            attribute(
+                "<erc20::erc20::Transfer as ink_env::topics::Topics>::topics::<ink_env::types::DefaultEnvironment, ()>",
+                "Erc20"
+            ),
+            Some(Owner::Type("Transfer".to_string()))
+        );
+        assert_eq!(
+            attribute(
+                "<alloc::vec::Vec<u8> as core::clone::Clone>::clone",
+                "Erc20"
+            ),
+            Some(Owner::Type("Vec".to_string()))
+        );
+    }
+
+    #[test]
+    fn paths_are_split_outside_of_brackets() {
+        assert_eq!(
+            split_path("<a::B<fn() -> c::D> as e::F>::g::{{closure}}"),
+            ["<a::B<fn() -> c::D> as e::F>", "g", "{{closure}}"]
+        );
+        assert_eq!(last_ident("a::b::C<d::E>"), "C");
+    }
+}
@@ -16,7 +16,11 @@
 
 use crate::{
     serde_hex,
-    utils::trim_extra_whitespace,
+    utils::{
+        deserialize_from_byte_str,
+        serialize_as_byte_str,
+        trim_extra_whitespace,
+    },
 };
 #[cfg(not(feature = "std"))]
 use alloc::{
@@ -59,6 +63,18 @@ pub struct ContractSpec<F: Form = MetaForm> {
     docs: Vec<F::String>,
     /// The language specific error type.
     lang_error: TypeSpec<F>,
+    /// The chain extension used by the contract if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chain_extension: Option<ChainExtensionSpec<F>>,
+    /// The chain configuration the contract was built with if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chain_config: Option<ChainConfigSpec<F>>,
+    /// The host functions imported by the built contract, e.g. `seal0::caller`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    host_functions: Vec<F::String>,
+    /// The error types with stable error codes returned by the messages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<ErrorSpec<F>>,
 }
 
 impl IntoPortable for ContractSpec {
@@ -83,6 +99,18 @@ impl IntoPortable for ContractSpec {
                 .collect::<Vec<_>>(),
             docs: registry.map_into_portable(self.docs),
             lang_error: self.lang_error.into_portable(registry),
+            chain_extension: self
+                .chain_extension
+                .map(|chain_extension| chain_extension.into_portable(registry)),
+            chain_config: self
+                .chain_config
+                .map(|chain_config| chain_config.into_portable(registry)),
+            host_functions: registry.map_into_portable(self.host_functions),
+            errors: self
+                .errors
+                .into_iter()
+                .map(|error| error.into_portable(registry))
+                .collect::<Vec<_>>(),
         }
     }
 }
@@ -115,6 +143,36 @@ where
     pub fn lang_error(&self) -> &TypeSpec<F> {
         &self.lang_error
     }
+
+    /// Returns the chain extension used by the contract if any.
+    pub fn chain_extension(&self) -> Option<&ChainExtensionSpec<F>> {
+        self.chain_extension.as_ref()
+    }
+
+    /// Returns the chain configuration the contract was built with if any.
+    pub fn chain_config(&self) -> Option<&ChainConfigSpec<F>> {
+        self.chain_config.as_ref()
+    }
+
+    /// Returns the host functions imported by the built contract.
+    ///
+    /// Every host function is given as `module::name` of its Wasm import, e.g.
+    /// `seal0::caller`. The list is empty unless it has been read from the built
+    /// Wasm blob of the contract, see `InkProject::with_host_functions`.
+    pub fn host_functions(&self) -> &[F::String] {
+        &self.host_functions
+    }
+
+    /// Sets the host functions imported by the built contract.
+    #[cfg(feature = "host-functions")]
+    pub(crate) fn set_host_functions(&mut self, host_functions: Vec<F::String>) {
+        self.host_functions = host_functions;
+    }
+
+    /// Returns the error types with stable error codes returned by the messages.
+    pub fn errors(&self) -> &[ErrorSpec<F>] {
+        &self.errors
+    }
 }
 
 /// The message builder is ready to finalize construction.
@@ -212,6 +270,69 @@ where
             ..self
         }
     }
+
+    /// Sets the chain extension used by the contract specification.
+    pub fn chain_extension(self, chain_extension: Option<ChainExtensionSpec<F>>) -> Self {
+        debug_assert!(self.spec.chain_extension.is_none());
+        Self {
+            spec: ContractSpec {
+                chain_extension,
+                ..self.spec
+            },
+            ..self
+        }
+    }
+
+    /// Sets the chain configuration the contract specification was built with.
+    pub fn chain_config(self, chain_config: Option<ChainConfigSpec<F>>) -> Self {
+        debug_assert!(self.spec.chain_config.is_none());
+        Self {
+            spec: ContractSpec {
+                chain_config,
+                ..self.spec
+            },
+            ..self
+        }
+    }
+
+    /// Sets the host functions imported by the built contract of the specification.
+    pub fn host_functions<H>(self, host_functions: H) -> Self
+    where
+        H: IntoIterator<Item = <F as Form>::String>,
+    {
+        debug_assert!(self.spec.host_functions.is_empty());
+        Self {
+            spec: ContractSpec {
+                host_functions: host_functions.into_iter().collect::<Vec<_>>(),
+                ..self.spec
+            },
+            ..self
+        }
+    }
+
+    /// Sets the error types with stable error codes of the contract specification.
+    ///
+    /// Error types returned by several messages are only listed once.
+    pub fn errors<E>(self, errors: E) -> Self
+    where
+        E: IntoIterator<Item = ErrorSpec<F>>,
+        ErrorSpec<F>: PartialEq,
+    {
+        debug_assert!(self.spec.errors.is_empty());
+        let mut unique = Vec::new();
+        for error in errors {
+            if !unique.contains(&error) {
+                unique.push(error)
+            }
+        }
+        Self {
+            spec: ContractSpec {
+                errors: unique,
+                ..self.spec
+            },
+            ..self
+        }
+    }
 }
 
 impl<F> ContractSpecBuilder<F, Valid>
@@ -246,6 +367,10 @@ where
                 events: Vec::new(),
                 docs: Vec::new(),
                 lang_error: Default::default(),
+                chain_extension: None,
+                chain_config: None,
+                host_functions: Vec::new(),
+                errors: Vec::new(),
             },
             marker: PhantomData,
         }
@@ -274,6 +399,9 @@ pub struct ConstructorSpec<F: Form = MetaForm> {
     pub return_type: ReturnTypeSpec<F>,
     /// The deployment handler documentation.
     pub docs: Vec<F::String>,
+    /// The human-readable name of the constructor shown by user interfaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<F::String>,
 }
 
 impl IntoPortable for ConstructorSpec {
@@ -291,6 +419,7 @@ impl IntoPortable for ConstructorSpec {
                 .collect::<Vec<_>>(),
             return_type: self.return_type.into_portable(registry),
             docs: self.docs.into_iter().map(|s| s.into()).collect(),
+            display_name: self.display_name.map(|name| name.to_string()),
         }
     }
 }
@@ -330,6 +459,11 @@ where
     pub fn docs(&self) -> &[F::String] {
         &self.docs
     }
+
+    /// Returns the human-readable name of the constructor if any.
+    pub fn display_name(&self) -> Option<&F::String> {
+        self.display_name.as_ref()
+    }
 }
 
 /// A builder for constructors.
@@ -367,6 +501,7 @@ where
                 args: Vec::new(),
                 return_type: ReturnTypeSpec::new(None),
                 docs: Vec::new(),
+                display_name: None,
             },
             marker: PhantomData,
         }
@@ -459,6 +594,14 @@ where
             .collect::<Vec<_>>();
         this
     }
+
+    /// Sets the human-readable name of the constructor shown by user interfaces.
+    pub fn display_name(self, display_name: <F as Form>::String) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.display_name.is_none());
+        this.spec.display_name = Some(display_name);
+        this
+    }
 }
 
 impl<F> ConstructorSpecBuilder<F, state::Selector, state::IsPayable, state::Returns>
@@ -486,6 +629,10 @@ pub struct MessageSpec<F: Form = MetaForm> {
     label: F::String,
     /// The selector hash of the message.
     selector: Selector,
+    /// An additional selector by which the message can be called, e.g. the
+    /// selector of the message in a legacy interface.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alias_selector: Option<Selector>,
     /// If the message is allowed to mutate the contract state.
     mutates: bool,
     /// If the message accepts any `value` from the caller.
@@ -496,6 +643,28 @@ pub struct MessageSpec<F: Form = MetaForm> {
     return_type: ReturnTypeSpec<F>,
     /// The message documentation.
     docs: Vec<F::String>,
+    /// The deprecation note of the message if it is deprecated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated: Option<F::String>,
+    /// The class of the message, e.g. `"view"`, `"transaction"` or `"admin"`.
+    ///
+    /// Allows user interfaces to group messages and to warn about privileged ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    class: Option<F::String>,
+    /// If the message returns a large sequence of bytes in chunks.
+    ///
+    /// Clients call the message with the offset of the next chunk until they
+    /// received the last chunk.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    chunked: bool,
+    /// If the message reverts while the contract is paused.
+    ///
+    /// Allows user interfaces to disable the message during an emergency stop.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    when_not_paused: bool,
+    /// The human-readable name of the message shown by user interfaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_name: Option<F::String>,
 }
 
 /// Type state for builders to tell that some mandatory state has not yet been set
@@ -534,11 +703,17 @@ where
             spec: Self {
                 label,
                 selector: Selector::default(),
+                alias_selector: None,
                 mutates: false,
                 payable: false,
                 args: Vec::new(),
                 return_type: ReturnTypeSpec::new(None),
                 docs: Vec::new(),
+                deprecated: None,
+                class: None,
+                chunked: false,
+                when_not_paused: false,
+                display_name: None,
             },
             marker: PhantomData,
         }
@@ -562,6 +737,11 @@ where
         &self.selector
     }
 
+    /// Returns the alias selector of the message if any.
+    pub fn alias_selector(&self) -> Option<&Selector> {
+        self.alias_selector.as_ref()
+    }
+
     /// Returns true if the message is allowed to mutate the contract state.
     pub fn mutates(&self) -> bool {
         self.mutates
@@ -586,6 +766,31 @@ where
     pub fn docs(&self) -> &[F::String] {
         &self.docs
     }
+
+    /// Returns the deprecation note of the message if it is deprecated.
+    pub fn deprecated(&self) -> Option<&F::String> {
+        self.deprecated.as_ref()
+    }
+
+    /// Returns the class of the message if any.
+    pub fn class(&self) -> Option<&F::String> {
+        self.class.as_ref()
+    }
+
+    /// Returns `true` if the message returns a large sequence of bytes in chunks.
+    pub fn chunked(&self) -> bool {
+        self.chunked
+    }
+
+    /// Returns `true` if the message reverts while the contract is paused.
+    pub fn when_not_paused(&self) -> bool {
+        self.when_not_paused
+    }
+
+    /// Returns the human-readable name of the message if any.
+    pub fn display_name(&self) -> Option<&F::String> {
+        self.display_name.as_ref()
+    }
 }
 
 /// A builder for messages.
@@ -706,6 +911,54 @@ where
         this.spec.docs = docs.into_iter().collect::<Vec<_>>();
         this
     }
+
+    /// Flags the message as deprecated with the given deprecation note.
+    ///
+    /// Deprecated messages remain callable but signal clients to move off them.
+    pub fn deprecated(self, note: <F as Form>::String) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.deprecated.is_none());
+        this.spec.deprecated = Some(note);
+        this
+    }
+
+    /// Sets an additional selector by which the message can be called.
+    pub fn alias_selector(self, selector: [u8; 4]) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.alias_selector.is_none());
+        this.spec.alias_selector = Some(selector.into());
+        this
+    }
+
+    /// Sets the class of the message, e.g. `"view"`, `"transaction"` or `"admin"`.
+    pub fn class(self, class: <F as Form>::String) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.class.is_none());
+        this.spec.class = Some(class);
+        this
+    }
+
+    /// Sets if the message returns a large sequence of bytes in chunks.
+    pub fn chunked(self, chunked: bool) -> Self {
+        let mut this = self;
+        this.spec.chunked = chunked;
+        this
+    }
+
+    /// Sets if the message reverts while the contract is paused.
+    pub fn when_not_paused(self, when_not_paused: bool) -> Self {
+        let mut this = self;
+        this.spec.when_not_paused = when_not_paused;
+        this
+    }
+
+    /// Sets the human-readable name of the message shown by user interfaces.
+    pub fn display_name(self, display_name: <F as Form>::String) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.display_name.is_none());
+        this.spec.display_name = Some(display_name);
+        this
+    }
 }
 
 impl<F>
@@ -732,6 +985,7 @@ impl IntoPortable for MessageSpec {
         MessageSpec {
             label: self.label.to_string(),
             selector: self.selector,
+            alias_selector: self.alias_selector,
             mutates: self.mutates,
             payable: self.payable,
             args: self
@@ -741,6 +995,11 @@ impl IntoPortable for MessageSpec {
                 .collect::<Vec<_>>(),
             return_type: self.return_type.into_portable(registry),
             docs: self.docs.into_iter().map(|s| s.into()).collect(),
+            deprecated: self.deprecated.map(|note| note.to_string()),
+            class: self.class.map(|class| class.to_string()),
+            chunked: self.chunked,
+            when_not_paused: self.when_not_paused,
+            display_name: self.display_name.map(|name| name.to_string()),
         }
     }
 }
@@ -751,9 +1010,16 @@ impl IntoPortable for MessageSpec {
     serialize = "F::Type: Serialize, F::String: Serialize",
     deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
 ))]
+#[serde(rename_all = "camelCase")]
 pub struct EventSpec<F: Form = MetaForm> {
     /// The label of the event.
     label: F::String,
+    /// The canonical signature of the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<F::String>,
+    /// The topic under which non-anonymous events are emitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature_topic: Option<SignatureTopic>,
     /// The event arguments.
     args: Vec<EventParamSpec<F>>,
     /// The event documentation.
@@ -795,6 +1061,27 @@ where
         this
     }
 
+    /// Sets the canonical signature of the event specification.
+    pub fn signature(self, signature: <F as Form>::String) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.signature.is_none());
+        this.spec.signature = Some(signature);
+        this
+    }
+
+    /// Sets the signature topic of the event specification.
+    ///
+    /// Anonymous events do not have a signature topic.
+    pub fn signature_topic<T>(self, signature_topic: Option<T>) -> Self
+    where
+        T: Into<[u8; 32]>,
+    {
+        let mut this = self;
+        debug_assert!(this.spec.signature_topic.is_none());
+        this.spec.signature_topic = signature_topic.map(SignatureTopic::new);
+        this
+    }
+
     /// Finalizes building the event specification.
     pub fn done(self) -> EventSpec<F> {
         self.spec
@@ -807,6 +1094,8 @@ impl IntoPortable for EventSpec {
     fn into_portable(self, registry: &mut Registry) -> Self::Output {
         EventSpec {
             label: self.label.to_string(),
+            signature: self.signature.map(|signature| signature.to_string()),
+            signature_topic: self.signature_topic,
             args: self
                 .args
                 .into_iter()
@@ -826,6 +1115,8 @@ where
         EventSpecBuilder {
             spec: Self {
                 label,
+                signature: None,
+                signature_topic: None,
                 args: Vec::new(),
                 docs: Vec::new(),
             },
@@ -842,6 +1133,16 @@ where
         &self.label
     }
 
+    /// Returns the canonical signature of the event if any.
+    pub fn signature(&self) -> Option<&F::String> {
+        self.signature.as_ref()
+    }
+
+    /// Returns the signature topic of the event if any.
+    pub fn signature_topic(&self) -> Option<&SignatureTopic> {
+        self.signature_topic.as_ref()
+    }
+
     /// The event arguments.
     pub fn args(&self) -> &[EventParamSpec<F>] {
         &self.args
@@ -853,8 +1154,463 @@ where
     }
 }
 
+/// Describes a chain extension.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F::Type: Serialize, F::String: Serialize",
+    deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
+))]
+pub struct ChainExtensionSpec<F: Form = MetaForm> {
+    /// The label of the chain extension.
+    label: F::String,
+    /// The methods of the chain extension.
+    methods: Vec<ChainExtensionMethodSpec<F>>,
+}
+
+/// A chain extension specification builder.
+#[must_use]
+pub struct ChainExtensionSpecBuilder<F>
+where
+    F: Form,
+{
+    spec: ChainExtensionSpec<F>,
+}
+
+impl<F> ChainExtensionSpecBuilder<F>
+where
+    F: Form,
+{
+    /// Sets the methods of the chain extension specification.
+    pub fn methods<M>(self, methods: M) -> Self
+    where
+        M: IntoIterator<Item = ChainExtensionMethodSpec<F>>,
+    {
+        let mut this = self;
+        debug_assert!(this.spec.methods.is_empty());
+        this.spec.methods = methods.into_iter().collect::<Vec<_>>();
+        this
+    }
+
+    /// Finalizes building the chain extension specification.
+    pub fn done(self) -> ChainExtensionSpec<F> {
+        self.spec
+    }
+}
+
+impl IntoPortable for ChainExtensionSpec {
+    type Output = ChainExtensionSpec<PortableForm>;
+
+    fn into_portable(self, registry: &mut Registry) -> Self::Output {
+        ChainExtensionSpec {
+            label: self.label.to_string(),
+            methods: self
+                .methods
+                .into_iter()
+                .map(|method| method.into_portable(registry))
+                .collect::<Vec<_>>(),
+        }
+    }
+}
+
+impl<F> ChainExtensionSpec<F>
+where
+    F: Form,
+{
+    /// Creates a new chain extension specification builder.
+    pub fn new(label: <F as Form>::String) -> ChainExtensionSpecBuilder<F> {
+        ChainExtensionSpecBuilder {
+            spec: Self {
+                label,
+                methods: Vec::new(),
+            },
+        }
+    }
+
+    /// Returns the label of the chain extension.
+    pub fn label(&self) -> &F::String {
+        &self.label
+    }
+
+    /// Returns the methods of the chain extension.
+    pub fn methods(&self) -> &[ChainExtensionMethodSpec<F>] {
+        &self.methods
+    }
+}
+
+/// Describes a chain extension method.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F::Type: Serialize, F::String: Serialize",
+    deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
+))]
+pub struct ChainExtensionMethodSpec<F: Form = MetaForm> {
+    /// The label of the chain extension method.
+    label: F::String,
+    /// The function ID of the chain extension method.
+    id: u32,
+    /// The weight the chain extension method is expected to charge per call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    weight: Option<u64>,
+}
+
+impl IntoPortable for ChainExtensionMethodSpec {
+    type Output = ChainExtensionMethodSpec<PortableForm>;
+
+    fn into_portable(self, _registry: &mut Registry) -> Self::Output {
+        ChainExtensionMethodSpec {
+            label: self.label.to_string(),
+            id: self.id,
+            weight: self.weight,
+        }
+    }
+}
+
+impl<F> ChainExtensionMethodSpec<F>
+where
+    F: Form,
+{
+    /// Creates a new chain extension method specification.
+    ///
+    /// `weight` is the weight the chain extension method is expected to charge per
+    /// call if it has been declared.
+    pub fn new(label: <F as Form>::String, id: u32, weight: Option<u64>) -> Self {
+        Self { label, id, weight }
+    }
+
+    /// Returns the label of the chain extension method.
+    pub fn label(&self) -> &F::String {
+        &self.label
+    }
+
+    /// Returns the function ID of the chain extension method.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the weight the chain extension method is expected to charge if any.
+    pub fn weight(&self) -> Option<u64> {
+        self.weight
+    }
+}
+
+/// Describes the chain configuration a contract was built with.
+///
+/// Given via `#[ink::contract(config = MyChainConfig)]`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F::Type: Serialize, F::String: Serialize",
+    deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
+))]
+pub struct ChainConfigSpec<F: Form = MetaForm> {
+    /// The label of the chain configuration.
+    label: F::String,
+    /// The compile-time constants of the chain configuration.
+    constants: Vec<ConstantSpec<F>>,
+}
+
+/// A chain configuration specification builder.
+#[must_use]
+pub struct ChainConfigSpecBuilder<F>
+where
+    F: Form,
+{
+    spec: ChainConfigSpec<F>,
+}
+
+impl<F> ChainConfigSpecBuilder<F>
+where
+    F: Form,
+{
+    /// Sets the constants of the chain configuration specification.
+    pub fn constants<C>(self, constants: C) -> Self
+    where
+        C: IntoIterator<Item = ConstantSpec<F>>,
+    {
+        let mut this = self;
+        debug_assert!(this.spec.constants.is_empty());
+        this.spec.constants = constants.into_iter().collect::<Vec<_>>();
+        this
+    }
+
+    /// Finalizes building the chain configuration specification.
+    pub fn done(self) -> ChainConfigSpec<F> {
+        self.spec
+    }
+}
+
+impl IntoPortable for ChainConfigSpec {
+    type Output = ChainConfigSpec<PortableForm>;
+
+    fn into_portable(self, registry: &mut Registry) -> Self::Output {
+        ChainConfigSpec {
+            label: self.label.to_string(),
+            constants: self
+                .constants
+                .into_iter()
+                .map(|constant| constant.into_portable(registry))
+                .collect::<Vec<_>>(),
+        }
+    }
+}
+
+impl<F> ChainConfigSpec<F>
+where
+    F: Form,
+{
+    /// Creates a new chain configuration specification builder.
+    pub fn new(label: <F as Form>::String) -> ChainConfigSpecBuilder<F> {
+        ChainConfigSpecBuilder {
+            spec: Self {
+                label,
+                constants: Vec::new(),
+            },
+        }
+    }
+
+    /// Returns the label of the chain configuration.
+    pub fn label(&self) -> &F::String {
+        &self.label
+    }
+
+    /// Returns the constants of the chain configuration.
+    pub fn constants(&self) -> &[ConstantSpec<F>] {
+        &self.constants
+    }
+}
+
+/// Describes a compile-time constant of a chain configuration.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F::Type: Serialize, F::String: Serialize",
+    deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
+))]
+pub struct ConstantSpec<F: Form = MetaForm> {
+    /// The label of the constant.
+    label: F::String,
+    /// The type of the constant.
+    #[serde(rename = "type")]
+    ty: TypeSpec<F>,
+    /// The SCALE encoded value of the constant.
+    #[serde(
+        serialize_with = "serialize_as_byte_str",
+        deserialize_with = "deserialize_from_byte_str"
+    )]
+    value: Vec<u8>,
+}
+
+impl IntoPortable for ConstantSpec {
+    type Output = ConstantSpec<PortableForm>;
+
+    fn into_portable(self, registry: &mut Registry) -> Self::Output {
+        ConstantSpec {
+            label: self.label.to_string(),
+            ty: self.ty.into_portable(registry),
+            value: self.value,
+        }
+    }
+}
+
+impl<F> ConstantSpec<F>
+where
+    F: Form,
+{
+    /// Creates a new constant specification from its SCALE encoded `value`.
+    pub fn new(label: <F as Form>::String, ty: TypeSpec<F>, value: Vec<u8>) -> Self {
+        Self { label, ty, value }
+    }
+
+    /// Returns the label of the constant.
+    pub fn label(&self) -> &F::String {
+        &self.label
+    }
+
+    /// Returns the type of the constant.
+    pub fn ty(&self) -> &TypeSpec<F> {
+        &self.ty
+    }
+
+    /// Returns the SCALE encoded value of the constant.
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+/// Describes an error type with stable error codes.
+///
+/// Given via `#[derive(ink::ErrorCode)]`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F::Type: Serialize, F::String: Serialize",
+    deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
+))]
+pub struct ErrorSpec<F: Form = MetaForm> {
+    /// The label of the error type.
+    label: F::String,
+    /// The error type.
+    #[serde(rename = "type")]
+    ty: TypeSpec<F>,
+    /// The error codes of the variants of the error type.
+    codes: Vec<ErrorCodeSpec<F>>,
+}
+
+/// An error specification builder.
+#[must_use]
+pub struct ErrorSpecBuilder<F>
+where
+    F: Form,
+{
+    spec: ErrorSpec<F>,
+}
+
+impl<F> ErrorSpecBuilder<F>
+where
+    F: Form,
+{
+    /// Sets the error codes of the error specification.
+    pub fn codes<C>(self, codes: C) -> Self
+    where
+        C: IntoIterator<Item = ErrorCodeSpec<F>>,
+    {
+        let mut this = self;
+        debug_assert!(this.spec.codes.is_empty());
+        this.spec.codes = codes.into_iter().collect::<Vec<_>>();
+        this
+    }
+
+    /// Finalizes building the error specification.
+    pub fn done(self) -> ErrorSpec<F> {
+        self.spec
+    }
+}
+
+impl IntoPortable for ErrorSpec {
+    type Output = ErrorSpec<PortableForm>;
+
+    fn into_portable(self, registry: &mut Registry) -> Self::Output {
+        ErrorSpec {
+            label: self.label.to_string(),
+            ty: self.ty.into_portable(registry),
+            codes: self
+                .codes
+                .into_iter()
+                .map(|code| code.into_portable(registry))
+                .collect::<Vec<_>>(),
+        }
+    }
+}
+
+impl<F> ErrorSpec<F>
+where
+    F: Form,
+{
+    /// Creates a new error specification builder.
+    pub fn new(label: <F as Form>::String, ty: TypeSpec<F>) -> ErrorSpecBuilder<F> {
+        ErrorSpecBuilder {
+            spec: Self {
+                label,
+                ty,
+                codes: Vec::new(),
+            },
+        }
+    }
+
+    /// Returns the label of the error type.
+    pub fn label(&self) -> &F::String {
+        &self.label
+    }
+
+    /// Returns the error type.
+    pub fn ty(&self) -> &TypeSpec<F> {
+        &self.ty
+    }
+
+    /// Returns the error codes of the variants of the error type.
+    pub fn codes(&self) -> &[ErrorCodeSpec<F>] {
+        &self.codes
+    }
+}
+
+/// Describes the stable error code of a variant of an error type.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F::Type: Serialize, F::String: Serialize",
+    deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
+))]
+pub struct ErrorCodeSpec<F: Form = MetaForm> {
+    /// The label of the variant.
+    label: F::String,
+    /// The error code of the variant.
+    code: u32,
+    /// The nested error type if the variant wraps another error type with stable
+    /// error codes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nested: Option<TypeSpec<F>>,
+    /// The documentation of the variant.
+    docs: Vec<F::String>,
+}
+
+impl IntoPortable for ErrorCodeSpec {
+    type Output = ErrorCodeSpec<PortableForm>;
+
+    fn into_portable(self, registry: &mut Registry) -> Self::Output {
+        ErrorCodeSpec {
+            label: self.label.to_string(),
+            code: self.code,
+            nested: self.nested.map(|nested| nested.into_portable(registry)),
+            docs: registry.map_into_portable(self.docs),
+        }
+    }
+}
+
+impl<F> ErrorCodeSpec<F>
+where
+    F: Form,
+{
+    /// Creates a new error code specification.
+    pub fn new<'a, D>(
+        label: <F as Form>::String,
+        code: u32,
+        nested: Option<TypeSpec<F>>,
+        docs: D,
+    ) -> Self
+    where
+        D: IntoIterator<Item = &'a str>,
+        F::String: From<&'a str>,
+    {
+        Self {
+            label,
+            code,
+            nested,
+            docs: docs
+                .into_iter()
+                .map(|s| trim_extra_whitespace(s).into())
+                .collect::<Vec<_>>(),
+        }
+    }
+
+    /// Returns the label of the variant.
+    pub fn label(&self) -> &F::String {
+        &self.label
+    }
+
+    /// Returns the error code of the variant.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// Returns the nested error type if the variant wraps one.
+    pub fn nested(&self) -> Option<&TypeSpec<F>> {
+        self.nested.as_ref()
+    }
+
+    /// Returns the documentation of the variant.
+    pub fn docs(&self) -> &[F::String] {
+        &self.docs
+    }
+}
+
 /// The 4 byte selector to identify constructors and messages
-#[derive(Debug, Default, PartialEq, Eq, derive_more::From)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, derive_more::From)]
 pub struct Selector([u8; 4]);
 
 impl serde::Serialize for Selector {
@@ -892,6 +1648,105 @@ impl Selector {
     }
 }
 
+/// A hint for user interfaces on how to render a message parameter.
+///
+/// A parameter has one hint for every `Option` or `Result` its type is wrapped
+/// in, outermost first. For example, a parameter of type
+/// `Option<Result<u32, Error>>` has the hints `[optional, result]`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DisplayHint {
+    /// The parameter is an `Option` that may be left empty.
+    Optional,
+    /// The parameter is a `Result` that is either a success or an error value.
+    Result,
+}
+
+impl DisplayHint {
+    /// Returns the display hints for a parameter of type `T`.
+    ///
+    /// Follows the type of the `Some` and `Ok` value of nested `Option`s and
+    /// `Result`s, so type aliases of them are detected as well.
+    pub fn of_type<T>() -> Vec<DisplayHint>
+    where
+        T: TypeInfo + ?Sized + 'static,
+    {
+        let mut hints = Vec::new();
+        let mut ty = meta_type::<T>().type_info();
+        loop {
+            let hint = match ty.path.segments.as_slice() {
+                ["Option"] => DisplayHint::Optional,
+                ["Result"] => DisplayHint::Result,
+                _ => break,
+            };
+            hints.push(hint);
+            match ty.type_params.first().and_then(|param| param.ty) {
+                Some(inner) => ty = inner.type_info(),
+                None => break,
+            }
+        }
+        hints
+    }
+}
+
+/// The SCALE encoded default value of a message parameter.
+///
+/// Given via `#[ink(arg(default = value))]` on the parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, derive_more::From)]
+pub struct ParamDefault(
+    #[serde(
+        serialize_with = "serialize_as_byte_str",
+        deserialize_with = "deserialize_from_byte_str"
+    )]
+    Vec<u8>,
+);
+
+impl ParamDefault {
+    /// Returns the SCALE encoded default value.
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The 32 bytes topic under which a non-anonymous event is emitted.
+#[derive(Debug, Default, Clone, PartialEq, Eq, derive_more::From)]
+pub struct SignatureTopic([u8; 32]);
+
+impl serde::Serialize for SignatureTopic {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde_hex::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SignatureTopic {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut arr = [0; 32];
+        serde_hex::deserialize_check_len(d, serde_hex::ExpectedLen::Exact(&mut arr[..]))?;
+        Ok(arr.into())
+    }
+}
+
+impl SignatureTopic {
+    /// Create a new signature topic.
+    pub fn new<T>(bytes: T) -> Self
+    where
+        T: Into<[u8; 32]>,
+    {
+        Self(bytes.into())
+    }
+
+    /// Returns the underlying signature topic bytes.
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 /// Describes the syntactical name of a type at a given type position.
 ///
 /// This is important when trying to work with type aliases.
@@ -1226,12 +2081,22 @@ where
     serialize = "F::Type: Serialize, F::String: Serialize",
     deserialize = "F::Type: DeserializeOwned, F::String: DeserializeOwned"
 ))]
+#[serde(rename_all = "camelCase")]
 pub struct MessageParamSpec<F: Form = MetaForm> {
     /// The label of the parameter.
     label: F::String,
     /// The type of the parameter.
     #[serde(rename = "type")]
     ty: TypeSpec<F>,
+    /// Hints for user interfaces on how to render the parameter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    display_hints: Vec<DisplayHint>,
+    /// The SCALE encoded default value of the parameter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<ParamDefault>,
+    /// The human-readable name of the parameter shown by user interfaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_name: Option<F::String>,
 }
 
 impl IntoPortable for MessageParamSpec {
@@ -1241,6 +2106,9 @@ impl IntoPortable for MessageParamSpec {
         MessageParamSpec {
             label: self.label.to_string(),
             ty: self.ty.into_portable(registry),
+            display_hints: self.display_hints,
+            default: self.default,
+            display_name: self.display_name.map(|name| name.to_string()),
         }
     }
 }
@@ -1257,6 +2125,9 @@ where
                 label,
                 // Uses `()` type by default.
                 ty: TypeSpec::default(),
+                display_hints: Vec::new(),
+                default: None,
+                display_name: None,
             },
         }
     }
@@ -1270,6 +2141,21 @@ where
     pub fn ty(&self) -> &TypeSpec<F> {
         &self.ty
     }
+
+    /// Returns the hints for user interfaces on how to render the parameter.
+    pub fn display_hints(&self) -> &[DisplayHint] {
+        &self.display_hints
+    }
+
+    /// Returns the SCALE encoded default value of the parameter if any.
+    pub fn default(&self) -> Option<&ParamDefault> {
+        self.default.as_ref()
+    }
+
+    /// Returns the human-readable name of the parameter if any.
+    pub fn display_name(&self) -> Option<&F::String> {
+        self.display_name.as_ref()
+    }
 }
 
 /// Used to construct a message parameter specification.
@@ -1290,6 +2176,30 @@ where
         this
     }
 
+    /// Sets the hints for user interfaces on how to render the message parameter.
+    pub fn display_hints<H>(self, display_hints: H) -> Self
+    where
+        H: IntoIterator<Item = DisplayHint>,
+    {
+        let mut this = self;
+        this.spec.display_hints = display_hints.into_iter().collect();
+        this
+    }
+
+    /// Sets the SCALE encoded default value of the message parameter.
+    pub fn default_value(self, default: Vec<u8>) -> Self {
+        let mut this = self;
+        this.spec.default = Some(ParamDefault(default));
+        this
+    }
+
+    /// Sets the human-readable name of the message parameter shown by user interfaces.
+    pub fn display_name(self, display_name: <F as Form>::String) -> Self {
+        let mut this = self;
+        this.spec.display_name = Some(display_name);
+        this
+    }
+
     /// Finishes construction of the message parameter.
     pub fn done(self) -> MessageParamSpec<F> {
         self.spec
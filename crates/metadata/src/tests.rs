@@ -53,6 +53,473 @@ fn spec_constructor_selector_must_serialize_to_hex() {
     assert_eq!(deserialized.selector, portable_spec.selector);
 }
 
+#[test]
+fn spec_message_selector_from_label_must_serialize_to_hex() {
+    // given
+    let ms = MessageSpec::from_label("flip")
+        .selector(ink_primitives::Selector::from_label("flip").to_bytes())
+        .mutates(true)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .done();
+    let mut registry = Registry::new();
+    let portable_spec = ms.into_portable(&mut registry);
+
+    // when
+    let json = serde_json::to_value(&portable_spec).unwrap();
+
+    // then
+    assert_eq!(json["selector"], json!("0x633aa551"));
+}
+
+#[test]
+fn spec_message_deprecated_json() {
+    // given
+    let deprecated = MessageSpec::from_label("transfer")
+        .selector([0x84, 0xA1, 0x5D, 0xA1])
+        .mutates(true)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .deprecated("use transfer_v2")
+        .done();
+    let current = MessageSpec::from_label("transfer_v2")
+        .selector([0x13, 0x37, 0x13, 0x37])
+        .mutates(true)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .done();
+    let mut registry = Registry::new();
+    let deprecated = deprecated.into_portable(&mut registry);
+    let current = current.into_portable(&mut registry);
+
+    // when
+    let deprecated_json = serde_json::to_value(&deprecated).unwrap();
+    let current_json = serde_json::to_value(&current).unwrap();
+    let deserialized: MessageSpec<PortableForm> =
+        serde_json::from_value(deprecated_json.clone()).unwrap();
+
+    // then
+    assert_eq!(deprecated_json["deprecated"], json!("use transfer_v2"));
+    assert!(current_json.get("deprecated").is_none());
+    assert_eq!(
+        deserialized.deprecated().map(String::as_str),
+        Some("use transfer_v2")
+    );
+    assert_eq!(deserialized, deprecated);
+}
+
+#[test]
+fn spec_message_alias_selector_json() {
+    // given
+    let aliased = MessageSpec::from_label("transfer")
+        .selector([0x84, 0xA1, 0x5D, 0xA1])
+        .alias_selector([0xDE, 0xAD, 0xBE, 0xEF])
+        .mutates(true)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .done();
+    let mut registry = Registry::new();
+    let aliased = aliased.into_portable(&mut registry);
+
+    // when
+    let json = serde_json::to_value(&aliased).unwrap();
+    let deserialized: MessageSpec<PortableForm> =
+        serde_json::from_value(json.clone()).unwrap();
+
+    // then
+    assert_eq!(json["selector"], json!("0x84a15da1"));
+    assert_eq!(json["aliasSelector"], json!("0xdeadbeef"));
+    assert_eq!(
+        deserialized.alias_selector(),
+        Some(&Selector::from([0xDE, 0xAD, 0xBE, 0xEF]))
+    );
+    assert_eq!(deserialized, aliased);
+}
+
+#[test]
+fn spec_message_class_json() {
+    // given
+    let admin = MessageSpec::from_label("set_owner")
+        .selector([0x12, 0x34, 0x56, 0x78])
+        .mutates(true)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .class("admin")
+        .done();
+    let unclassified = MessageSpec::from_label("get")
+        .selector([0x2F, 0x86, 0x5B, 0xD9])
+        .mutates(false)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .done();
+    let mut registry = Registry::new();
+    let admin = admin.into_portable(&mut registry);
+    let unclassified = unclassified.into_portable(&mut registry);
+
+    // when
+    let admin_json = serde_json::to_value(&admin).unwrap();
+    let unclassified_json = serde_json::to_value(&unclassified).unwrap();
+    let deserialized: MessageSpec<PortableForm> =
+        serde_json::from_value(admin_json.clone()).unwrap();
+
+    // then
+    assert_eq!(admin_json["class"], json!("admin"));
+    assert!(unclassified_json.get("class").is_none());
+    assert_eq!(deserialized.class().map(String::as_str), Some("admin"));
+    assert_eq!(deserialized, admin);
+}
+
+#[test]
+fn spec_message_chunked_json() {
+    // given
+    let chunked = MessageSpec::from_label("blob_chunk")
+        .selector([0x12, 0x34, 0x56, 0x78])
+        .mutates(false)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .chunked(true)
+        .done();
+    let plain = MessageSpec::from_label("get")
+        .selector([0x2F, 0x86, 0x5B, 0xD9])
+        .mutates(false)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .done();
+    let mut registry = Registry::new();
+    let chunked = chunked.into_portable(&mut registry);
+    let plain = plain.into_portable(&mut registry);
+
+    // when
+    let chunked_json = serde_json::to_value(&chunked).unwrap();
+    let plain_json = serde_json::to_value(&plain).unwrap();
+    let deserialized: MessageSpec<PortableForm> =
+        serde_json::from_value(chunked_json.clone()).unwrap();
+
+    // then
+    assert_eq!(chunked_json["chunked"], json!(true));
+    assert!(plain_json.get("chunked").is_none());
+    assert!(deserialized.chunked());
+    assert_eq!(deserialized, chunked);
+}
+
+#[test]
+fn spec_message_when_not_paused_json() {
+    // given
+    let pausable = MessageSpec::from_label("withdraw")
+        .selector([0x12, 0x34, 0x56, 0x78])
+        .mutates(true)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .when_not_paused(true)
+        .done();
+    let plain = MessageSpec::from_label("get")
+        .selector([0x2F, 0x86, 0x5B, 0xD9])
+        .mutates(false)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .done();
+    let mut registry = Registry::new();
+    let pausable = pausable.into_portable(&mut registry);
+    let plain = plain.into_portable(&mut registry);
+
+    // when
+    let pausable_json = serde_json::to_value(&pausable).unwrap();
+    let plain_json = serde_json::to_value(&plain).unwrap();
+    let deserialized: MessageSpec<PortableForm> =
+        serde_json::from_value(pausable_json.clone()).unwrap();
+
+    // then
+    assert_eq!(pausable_json["whenNotPaused"], json!(true));
+    assert!(plain_json.get("whenNotPaused").is_none());
+    assert!(deserialized.when_not_paused());
+    assert_eq!(deserialized, pausable);
+}
+
+#[test]
+fn spec_display_names_json() {
+    // given
+    let constructor = ConstructorSpec::from_label("new")
+        .selector([0x5E, 0xBD, 0x88, 0xD6])
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .display_name("Create token")
+        .done();
+    let message = MessageSpec::from_label("transfer")
+        .selector([0x84, 0xA1, 0x5D, 0xA1])
+        .args([MessageParamSpec::new("to")
+            .of_type(TypeSpec::with_name_str::<u32>("AccountId"))
+            .display_name("Recipient")
+            .done()])
+        .mutates(true)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .display_name("Transfer tokens")
+        .done();
+    let plain = MessageSpec::from_label("get")
+        .selector([0x2F, 0x86, 0x5B, 0xD9])
+        .mutates(false)
+        .payable(false)
+        .returns(ReturnTypeSpec::new(None))
+        .done();
+    let mut registry = Registry::new();
+    let constructor = constructor.into_portable(&mut registry);
+    let message = message.into_portable(&mut registry);
+    let plain = plain.into_portable(&mut registry);
+
+    // when
+    let constructor_json = serde_json::to_value(&constructor).unwrap();
+    let message_json = serde_json::to_value(&message).unwrap();
+    let plain_json = serde_json::to_value(&plain).unwrap();
+    let deserialized: MessageSpec<PortableForm> =
+        serde_json::from_value(message_json.clone()).unwrap();
+
+    // then
+    assert_eq!(constructor_json["displayName"], json!("Create token"));
+    assert_eq!(message_json["displayName"], json!("Transfer tokens"));
+    assert_eq!(message_json["args"][0]["displayName"], json!("Recipient"));
+    assert!(plain_json.get("displayName").is_none());
+    assert_eq!(
+        deserialized.display_name().map(String::as_str),
+        Some("Transfer tokens")
+    );
+    assert_eq!(deserialized, message);
+}
+
+#[test]
+fn spec_message_param_display_hints_and_default_json() {
+    // given
+    let param = MessageParamSpec::new("limit")
+        .of_type(TypeSpec::with_name_str::<Option<u32>>("Option"))
+        .display_hints(DisplayHint::of_type::<Option<u32>>())
+        .default_value(vec![0x01, 0x0A, 0x00, 0x00, 0x00])
+        .done();
+    let mut registry = Registry::new();
+    let portable_param = param.into_portable(&mut registry);
+
+    // when
+    let json = serde_json::to_value(&portable_param).unwrap();
+    let deserialized: MessageParamSpec<PortableForm> =
+        serde_json::from_value(json.clone()).unwrap();
+
+    // then
+    assert_eq!(
+        json,
+        json!({
+            "label": "limit",
+            "type": {
+                "type": 0,
+                "displayName": ["Option"],
+            },
+            "displayHints": ["optional"],
+            "default": "0x010a000000",
+        })
+    );
+    assert_eq!(deserialized, portable_param);
+}
+
+#[test]
+fn display_hints_of_type_works() {
+    type Fallible = Result<Option<u32>, ()>;
+    assert_eq!(DisplayHint::of_type::<u32>(), vec![]);
+    assert_eq!(
+        DisplayHint::of_type::<Option<bool>>(),
+        vec![DisplayHint::Optional]
+    );
+    assert_eq!(
+        DisplayHint::of_type::<Option<Result<u8, ()>>>(),
+        vec![DisplayHint::Optional, DisplayHint::Result]
+    );
+    assert_eq!(
+        DisplayHint::of_type::<Fallible>(),
+        vec![DisplayHint::Result, DisplayHint::Optional]
+    );
+    // Only the `Ok` value of a `Result` is followed.
+    assert_eq!(
+        DisplayHint::of_type::<Result<u8, Option<u8>>>(),
+        vec![DisplayHint::Result]
+    );
+}
+
+#[test]
+fn spec_chain_extension_json() {
+    // given
+    let spec: ChainExtensionSpec = ChainExtensionSpec::new("RandExtension")
+        .methods(vec![
+            ChainExtensionMethodSpec::new("fetch_random", 1101, Some(10_000)),
+            ChainExtensionMethodSpec::new("seed", 1102, None),
+        ])
+        .done();
+    let mut registry = Registry::new();
+    let portable_spec = spec.into_portable(&mut registry);
+
+    // when
+    let json = serde_json::to_value(&portable_spec).unwrap();
+    let deserialized: ChainExtensionSpec<PortableForm> =
+        serde_json::from_value(json.clone()).unwrap();
+
+    // then
+    assert_eq!(
+        json,
+        json!({
+            "label": "RandExtension",
+            "methods": [
+                {
+                    "label": "fetch_random",
+                    "id": 1101,
+                    "weight": 10000
+                },
+                {
+                    "label": "seed",
+                    "id": 1102
+                }
+            ]
+        })
+    );
+    assert_eq!(deserialized, portable_spec);
+}
+
+#[test]
+fn spec_chain_config_json() {
+    // given
+    let spec: ChainConfigSpec = ChainConfigSpec::new("MyChainConfig")
+        .constants(vec![
+            ConstantSpec::new(
+                "FEE_DENOMINATOR",
+                TypeSpec::with_name_str::<u128>("u128"),
+                1_000u128.to_le_bytes().to_vec(),
+            ),
+            ConstantSpec::new("DECIMALS", TypeSpec::with_name_str::<u8>("u8"), vec![12]),
+        ])
+        .done();
+    let mut registry = Registry::new();
+    let portable_spec = spec.into_portable(&mut registry);
+
+    // when
+    let json = serde_json::to_value(&portable_spec).unwrap();
+    let deserialized: ChainConfigSpec<PortableForm> =
+        serde_json::from_value(json.clone()).unwrap();
+
+    // then
+    assert_eq!(
+        json,
+        json!({
+            "label": "MyChainConfig",
+            "constants": [
+                {
+                    "label": "FEE_DENOMINATOR",
+                    "type": {
+                        "type": 0,
+                        "displayName": ["u128"],
+                    },
+                    "value": "0xe8030000000000000000000000000000",
+                },
+                {
+                    "label": "DECIMALS",
+                    "type": {
+                        "type": 1,
+                        "displayName": ["u8"],
+                    },
+                    "value": "0x0c",
+                }
+            ]
+        })
+    );
+    assert_eq!(deserialized, portable_spec);
+}
+
+#[test]
+fn spec_error_json() {
+    // given
+    let spec: ErrorSpec =
+        ErrorSpec::new("Error", TypeSpec::with_name_str::<u32>("Error"))
+            .codes(vec![
+                ErrorCodeSpec::new(
+                    "InsufficientBalance",
+                    1,
+                    None,
+                    vec!["Not enough funds."],
+                ),
+                ErrorCodeSpec::new(
+                    "Token",
+                    100,
+                    Some(TypeSpec::with_name_str::<u8>("TokenError")),
+                    Vec::new(),
+                ),
+            ])
+            .done();
+    let mut registry = Registry::new();
+    let portable_spec = spec.into_portable(&mut registry);
+
+    // when
+    let json = serde_json::to_value(&portable_spec).unwrap();
+    let deserialized: ErrorSpec<PortableForm> =
+        serde_json::from_value(json.clone()).unwrap();
+
+    // then
+    assert_eq!(
+        json,
+        json!({
+            "label": "Error",
+            "type": {
+                "type": 0,
+                "displayName": ["Error"],
+            },
+            "codes": [
+                {
+                    "label": "InsufficientBalance",
+                    "code": 1,
+                    "docs": ["Not enough funds."],
+                },
+                {
+                    "label": "Token",
+                    "code": 100,
+                    "nested": {
+                        "type": 1,
+                        "displayName": ["TokenError"],
+                    },
+                    "docs": [],
+                }
+            ]
+        })
+    );
+    assert_eq!(deserialized, portable_spec);
+}
+
+#[test]
+fn spec_contract_errors_are_unique() {
+    let error = |code| {
+        let path: Path<PortableForm> =
+            Path::from_segments_unchecked(["Error".to_string()]);
+        ErrorSpec::new("Error".to_string(), TypeSpec::new(7.into(), path))
+            .codes([ErrorCodeSpec::new(
+                "Failed".to_string(),
+                code,
+                None,
+                Vec::new(),
+            )])
+            .done()
+    };
+    let spec = ContractSpec::new()
+        .constructors([runtime_constructor_spec()])
+        .messages([runtime_message_spec()])
+        .errors([error(1), error(2), error(1)])
+        .done();
+    assert_eq!(spec.errors(), &[error(1), error(2)]);
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(json["errors"].as_array().map(Vec::len), Some(2));
+    let deserialized: ContractSpec<PortableForm> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+
+    // Metadata without any error types omits the section.
+    let spec = ContractSpec::new()
+        .constructors([runtime_constructor_spec()])
+        .messages([runtime_message_spec()])
+        .done();
+    let json = serde_json::to_value(&spec).unwrap();
+    assert!(json.get("errors").is_none());
+}
+
 #[test]
 fn spec_contract_json() {
     // given
@@ -346,6 +813,8 @@ fn runtime_event_spec() -> EventSpec<PortableForm> {
         .docs(vec![])
         .done()];
     EventSpec::new("foobar".into())
+        .signature("Foo::foobar(#[topic]something:FooBarEvent)".into())
+        .signature_topic(Some([0x42; 32]))
         .args(args)
         .docs(["foobar event".into()])
         .done()
@@ -423,6 +892,8 @@ fn construct_runtime_contract_spec() {
     let expected_event_spec = serde_json::json!(
         {
             "label": "foobar",
+            "signature": "Foo::foobar(#[topic]something:FooBarEvent)",
+            "signatureTopic": "0x4242424242424242424242424242424242424242424242424242424242424242",
             "args": [
                 {
                     "label": "something",
@@ -443,3 +914,303 @@ fn construct_runtime_contract_spec() {
     );
     assert_eq!(event_spec, expected_event_spec);
 }
+
+#[test]
+fn ink_project_storage_version_works() {
+    let spec = ContractSpec::new()
+        .constructors([runtime_constructor_spec()])
+        .messages([runtime_message_spec()])
+        .done();
+    let layout = layout::Layout::Struct(layout::StructLayout::new("Storage", []));
+    let project = InkProject::new_portable(layout, spec, Registry::new().into());
+    assert_eq!(project.layout_version(), layout::LayoutVersion::V1);
+
+    let mut json = serde_json::to_value(&project).unwrap();
+    assert_eq!(json["layoutVersion"], json!("1"));
+
+    // Metadata written before the version was recorded is read as the first version.
+    json.as_object_mut().unwrap().remove("layoutVersion");
+    let project: InkProject = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(project.layout_version(), layout::LayoutVersion::V1);
+
+    json["layoutVersion"] = json!("2");
+    assert!(serde_json::from_value::<InkProject>(json).is_err());
+}
+
+#[test]
+fn spec_host_functions_json() {
+    let spec = ContractSpec::new()
+        .constructors([runtime_constructor_spec()])
+        .messages([runtime_message_spec()])
+        .host_functions(["seal0::caller".into(), "seal0::input".into()])
+        .done();
+    assert_eq!(
+        spec.host_functions(),
+        &["seal0::caller".to_string(), "seal0::input".to_string()]
+    );
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["host_functions"],
+        json!(["seal0::caller", "seal0::input"])
+    );
+    let deserialized: ContractSpec<PortableForm> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+
+    // Metadata without recorded host functions omits the section.
+    let spec = ContractSpec::new()
+        .constructors([runtime_constructor_spec()])
+        .messages([runtime_message_spec()])
+        .done();
+    let json = serde_json::to_value(&spec).unwrap();
+    assert!(json.get("host_functions").is_none());
+}
+
+#[cfg(feature = "host-functions")]
+#[test]
+fn host_functions_are_read_from_wasm_imports() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "seal1" "get_storage" (func (param i32 i32 i32 i32) (result i32)))
+            (import "seal0" "caller" (func (param i32 i32)))
+            (import "env" "memory" (memory 2 16))
+            (func (export "call"))
+            (func (export "deploy"))
+        )
+        "#,
+    )
+    .unwrap();
+    let spec = ContractSpec::new()
+        .constructors([runtime_constructor_spec()])
+        .messages([runtime_message_spec()])
+        .done();
+    let layout = layout::Layout::Struct(layout::StructLayout::new("Storage", []));
+    let project = InkProject::new_portable(layout, spec, Registry::new().into());
+
+    let project = project.with_host_functions(&wasm).unwrap();
+
+    assert_eq!(
+        project.spec().host_functions(),
+        &[
+            "seal0::caller".to_string(),
+            "seal1::get_storage".to_string()
+        ]
+    );
+    assert!(host_functions::imported_host_functions(b"not wasm").is_err());
+}
+
+/// Helper for creating a project with an event of the signature and topic
+fn topic_project(signature: &str, topic: Option<[u8; 32]>) -> InkProject {
+    let spec = ContractSpec::new()
+        .constructors([runtime_constructor_spec()])
+        .messages([runtime_message_spec()])
+        .events([EventSpec::new("Transferred".into())
+            .signature(signature.into())
+            .signature_topic(topic)
+            .done()])
+        .done();
+    let layout = layout::Layout::Struct(layout::StructLayout::new("Storage", []));
+    InkProject::new_portable(layout, spec, Registry::new().into())
+}
+
+#[test]
+fn signature_topic_collisions_are_detected() {
+    let erc20 =
+        topic_project("Erc20::Transferred(#[topic]from:AccountId)", Some([1; 32]));
+    let other = topic_project("Erc20::Transferred(#[topic]to:AccountId)", Some([1; 32]));
+    let anonymous = topic_project("Erc20::Transferred(#[topic]to:AccountId)", None);
+
+    // The same event may be validated more than once.
+    assert_eq!(topics::ValidateTopics::validate([&erc20, &erc20]), Ok(()));
+    assert_eq!(
+        topics::ValidateTopics::validate([&erc20, &anonymous]),
+        Ok(())
+    );
+
+    let error = topics::ValidateTopics::validate([&erc20, &anonymous, &other])
+        .expect_err("the topics of different events must not collide");
+    assert_eq!(error.first(), "Erc20::Transferred(#[topic]from:AccountId)");
+    assert_eq!(error.second(), "Erc20::Transferred(#[topic]to:AccountId)");
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "signature topic collision occurred for \
+            `Erc20::Transferred(#[topic]to:AccountId)`. The same topic 0x{} is used by \
+            `Erc20::Transferred(#[topic]from:AccountId)`.",
+            "01".repeat(32)
+        )
+    );
+}
+
+#[test]
+fn signature_topic_collisions_are_detected_in_metadata_files() {
+    let dir = std::env::temp_dir().join(format!("ink-topics-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let write = |name: &str, signature: &str| {
+        let mut json =
+            serde_json::to_value(topic_project(signature, Some([1; 32]))).unwrap();
+        // Metadata files of built contracts describe their source, too.
+        json["source"] = json!({ "hash": "0x00" });
+        std::fs::write(dir.join(name), json.to_string()).unwrap();
+    };
+    write("erc20.json", "Erc20::Transferred(#[topic]from:AccountId)");
+    std::fs::write(dir.join("notes.txt"), "not metadata").unwrap();
+    assert!(topics::ValidateTopics::validate_dir(&dir).is_ok());
+    topics::assert_unique_topics(&dir);
+
+    write(
+        "governance.json",
+        "Erc20::Transferred(#[topic]to:AccountId)",
+    );
+    let error = topics::ValidateTopics::validate_dir(&dir)
+        .expect_err("the topics of different events must not collide");
+    assert!(matches!(error, topics::Error::Collision(_)));
+    let panic = std::panic::catch_unwind(|| topics::assert_unique_topics(&dir))
+        .expect_err("the assertion must fail on collisions");
+    assert_eq!(panic.downcast_ref::<String>(), Some(&error.to_string()));
+
+    std::fs::write(dir.join("governance.json"), "{}").unwrap();
+    assert!(matches!(
+        topics::ValidateTopics::validate_dir(&dir),
+        Err(topics::Error::Metadata { path, .. }) if path == dir.join("governance.json")
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(
+        topics::ValidateTopics::validate_dir(&dir),
+        Err(topics::Error::Io { .. })
+    ));
+}
+
+#[cfg(feature = "size-report")]
+#[test]
+fn size_report_attributes_functions() {
+    let spec = ContractSpec::new()
+        .constructors([ConstructorSpec::from_label("new".to_string())
+            .selector([1; 4])
+            .payable(false)
+            .args([])
+            .returns(ReturnTypeSpec::new(None))
+            .docs(Vec::<&str>::new())
+            .done()])
+        .messages([
+            MessageSpec::from_label("BaseErc20::total_supply".to_string())
+                .selector([2; 4])
+                .mutates(false)
+                .payable(false)
+                .args([])
+                .returns(ReturnTypeSpec::new(None))
+                .docs(Vec::<String>::new())
+                .done(),
+        ])
+        .events([EventSpec::new("Transfer".into()).done()])
+        .done();
+    let layout = layout::Layout::Root(layout::RootLayout::new(
+        layout::LayoutKey::from(&0),
+        layout::Layout::Struct(layout::StructLayout::new("Erc20", [])),
+    ));
+    let project = InkProject::new_portable(layout, spec, Registry::new().into());
+    // The body of a function has one byte for its locals and one for its end.
+    let wasm = wat::parse_str(
+        r#"(module
+            (import "seal0" "input" (func (param i32 i32)))
+            (func (@name "_ZN5erc205Erc203new17h0123456789abcdefE") nop)
+            (func (@name "_ZN49_$LT$erc20..Erc20$u20$as$u20$erc20..BaseErc20$GT$12total_supply17h0123456789abcdefE")
+                nop nop)
+            (func (@name "<erc20::Erc20 as erc20::BaseErc20>::total_supply::{{closure}}")
+                nop nop nop)
+            (func (@name "<erc20::Transfer as parity_scale_codec::codec::Encode>::encode_to")
+                nop nop nop nop)
+            (func (@name "call") nop nop nop nop nop)
+            (func (@name "<erc20::Erc20 as ink::reflect::DispatchableMessageInfo<_>>::CALLABLE::{{closure}}"))
+        )"#,
+    )
+    .unwrap();
+
+    let report = size_report::SizeReport::from_wasm(&project, &wasm).unwrap();
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        json!({
+            "code_size": 27,
+            "constructors": [{ "label": "new", "selector": "0x01010101", "size": 3 }],
+            "messages": [{
+                "label": "BaseErc20::total_supply",
+                "selector": "0x02020202",
+                "size": 9,
+            }],
+            "events": [{ "label": "Transfer", "size": 6 }],
+            "unattributed": 9,
+        })
+    );
+
+    // The names are required to attribute the functions.
+    let stripped = wat::parse_str("(module (func nop))").unwrap();
+    assert!(matches!(
+        size_report::SizeReport::from_wasm(&project, &stripped),
+        Err(size_report::Error::MissingNames)
+    ));
+}
+
+#[test]
+fn custom_types_have_stable_names() {
+    #[derive(scale_info::TypeInfo)]
+    #[allow(dead_code)]
+    struct Order<T> {
+        amount: T,
+    }
+
+    #[derive(scale_info::TypeInfo)]
+    #[allow(dead_code)]
+    enum Error {
+        Unauthorized,
+    }
+
+    fn project(args: Vec<TypeSpec>) -> InkProject {
+        let spec = ContractSpec::new()
+            .constructors([ConstructorSpec::from_label("new")
+                .selector([0; 4])
+                .payable(false)
+                .returns(ReturnTypeSpec::new(None))
+                .done()])
+            .messages([MessageSpec::from_label("place")
+                .selector([1; 4])
+                .mutates(true)
+                .payable(false)
+                .args(
+                    args.into_iter()
+                        .map(|arg| MessageParamSpec::new("arg").of_type(arg).done()),
+                )
+                .returns(ReturnTypeSpec::new(None))
+                .done()])
+            .done();
+        let layout = layout::Layout::Struct(layout::StructLayout::new("Storage", []));
+        InkProject::new(layout, spec)
+    }
+
+    // given
+    let order = || TypeSpec::of_type::<Result<Order<u128>, Error>>();
+    let first = project(vec![order()]);
+    let second = project(vec![TypeSpec::of_type::<(u64, [u8; 4])>(), order()]);
+
+    // then
+    let names = first.custom_types().keys().collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "Result<ink_metadata::tests::Order<u128>, ink_metadata::tests::Error>",
+            "ink_metadata::tests::Error",
+            "ink_metadata::tests::Order<u128>",
+        ]
+    );
+    assert_eq!(second.custom_types().keys().collect::<Vec<_>>(), names);
+    assert_ne!(first.custom_types(), second.custom_types());
+
+    let json = serde_json::to_value(&second).unwrap();
+    let id = json["customTypes"]["ink_metadata::tests::Error"]
+        .as_u64()
+        .unwrap();
+    assert_eq!(
+        json["types"][id as usize]["type"]["path"],
+        json!(["ink_metadata", "tests", "Error"])
+    );
+}
@@ -0,0 +1,247 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detection of events of different contracts sharing a signature topic.
+//!
+//! The signature topic of an event is derived from the names of the contract and
+//! the event only. Contracts of different crates with the same name therefore emit
+//! their equally named events under the same topic, even if the events have
+//! different fields, so that indexers subscribing by topic can't tell them apart.
+//!
+//! A workspace checks its contracts by calling [`assert_unique_topics`] from a test,
+//! or [`ValidateTopics::validate_dir`] from a build script, with the directory the
+//! metadata of the built contracts is written to.
+
+use crate::{
+    InkProject,
+    SignatureTopic,
+};
+use core::fmt::Display;
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+/// Two events with different signatures are emitted under the same signature topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicCollision {
+    topic: SignatureTopic,
+    first: String,
+    second: String,
+}
+
+impl TopicCollision {
+    /// Returns the signature topic shared by the events.
+    pub fn topic(&self) -> &SignatureTopic {
+        &self.topic
+    }
+
+    /// Returns the signature of the event which was validated first.
+    pub fn first(&self) -> &str {
+        &self.first
+    }
+
+    /// Returns the signature of the event colliding with the first one.
+    pub fn second(&self) -> &str {
+        &self.second
+    }
+}
+
+impl Display for TopicCollision {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let topic: String = self
+            .topic
+            .to_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        write!(
+            f,
+            "signature topic collision occurred for `{}`. \
+            The same topic 0x{} is used by `{}`.",
+            self.second, topic, self.first
+        )
+    }
+}
+
+/// Returned if the metadata files of a directory can't be validated.
+#[derive(Debug)]
+pub enum Error {
+    /// The directory or a metadata file can't be read.
+    Io {
+        /// The path which can't be read.
+        path: PathBuf,
+        /// The error reading the path.
+        error: io::Error,
+    },
+    /// A file doesn't contain ink! metadata.
+    Metadata {
+        /// The path of the file.
+        path: PathBuf,
+        /// The error deserializing the metadata.
+        error: serde_json::Error,
+    },
+    /// Events of the metadata share a signature topic.
+    Collision(TopicCollision),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Io { path, error } => {
+                write!(f, "failed to read `{}`: {}", path.display(), error)
+            }
+            Self::Metadata { path, error } => {
+                write!(
+                    f,
+                    "invalid ink! metadata in `{}`: {}",
+                    path.display(),
+                    error
+                )
+            }
+            Self::Collision(collision) => collision.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// It validates that the events of ink! projects, e.g. the contracts of a workspace,
+/// don't share signature topics. Otherwise an error with a description of the
+/// collision is returned.
+///
+/// Events with equal signatures are considered the same event and may share their
+/// topic, e.g. if the metadata of a contract is validated more than once. Anonymous
+/// events don't have a signature topic and can't collide.
+///
+/// # Usage
+///
+/// ```no_compile
+/// let projects = ["erc20.json", "governance/erc20.json"].map(|path| {
+///     let file = std::fs::File::open(path).unwrap();
+///     serde_json::from_reader::<_, InkProject>(file).unwrap()
+/// });
+/// ValidateTopics::validate(&projects).unwrap_or_else(|error| panic!("{}", error));
+/// ```
+pub struct ValidateTopics<'a> {
+    first_event: HashMap<&'a [u8], &'a str>,
+}
+
+impl<'a> ValidateTopics<'a> {
+    /// Validates the signature topics of the events of all `projects`.
+    pub fn validate<I>(projects: I) -> Result<(), TopicCollision>
+    where
+        I: IntoIterator<Item = &'a InkProject>,
+    {
+        let mut validator = Self {
+            first_event: Default::default(),
+        };
+        for project in projects {
+            validator.check_project(project)?;
+        }
+        Ok(())
+    }
+
+    /// Validates the signature topics of the events of all metadata files in `dir`.
+    ///
+    /// Every file with the `json` extension in `dir` must contain ink! metadata, e.g.
+    /// the metadata of the contracts built by `cargo contract build`. Other files
+    /// and sub-directories are ignored.
+    pub fn validate_dir<P>(dir: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let io_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |error| Error::Io { path, error }
+        };
+        let mut paths = fs::read_dir(dir)
+            .map_err(io_error(dir))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(io_error(dir))?;
+        // Sort the files to report collisions independent of the file system.
+        paths.sort();
+        let projects = paths
+            .into_iter()
+            .filter(|path| path.is_file())
+            .filter(|path| path.extension() == Some("json".as_ref()))
+            .map(|path| {
+                let file = fs::File::open(&path).map_err(io_error(&path))?;
+                serde_json::from_reader(io::BufReader::new(file))
+                    .map_err(|error| Error::Metadata { path, error })
+            })
+            .collect::<Result<Vec<InkProject>, _>>()?;
+        ValidateTopics::validate(&projects).map_err(Error::Collision)
+    }
+
+    fn check_project(&mut self, project: &'a InkProject) -> Result<(), TopicCollision> {
+        for event in project.spec().events() {
+            let topic = match event.signature_topic() {
+                Some(topic) => topic,
+                None => continue,
+            };
+            let signature = event.signature().unwrap_or_else(|| event.label());
+            match self.first_event.get(topic.to_bytes()) {
+                Some(first) if *first != signature => {
+                    return Err(TopicCollision {
+                        topic: topic.clone(),
+                        first: first.to_string(),
+                        second: signature.to_string(),
+                    })
+                }
+                Some(_) => {}
+                None => {
+                    self.first_event.insert(topic.to_bytes(), signature);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Asserts that the events of the metadata files in `dir` don't share signature
+/// topics.
+///
+/// This is meant to be called from a test of a workspace after its contracts have
+/// been built, so that the test fails on unintentional collisions.
+///
+/// # Usage
+///
+/// ```no_compile
+/// #[test]
+/// fn signature_topics_are_unique() {
+///     ink_metadata::topics::assert_unique_topics(
+///         concat!(env!("CARGO_MANIFEST_DIR"), "/target/ink"),
+///     );
+/// }
+/// ```
+///
+/// # Panics
+///
+/// If the metadata files can't be read or their events share a signature topic.
+pub fn assert_unique_topics<P>(dir: P)
+where
+    P: AsRef<Path>,
+{
+    if let Err(error) = ValidateTopics::validate_dir(dir) {
+        panic!("{}", error)
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `const` implementation of the BLAKE2b hash function.
+//!
+//! Allows to compute selectors in `const` contexts. It is not meant to be fast.
+
+/// The BLAKE2b initialization vector.
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// The message word permutations of the BLAKE2b rounds.
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+/// The size of a BLAKE2b block in bytes.
+const BLOCK_LEN: usize = 128;
+
+/// Computes the BLAKE2b 256-bit hash of `input`.
+pub const fn blake2b_256(input: &[u8]) -> [u8; 32] {
+    let mut h = IV;
+    // Parameter block: digest length of 32 bytes, no key, fanout and depth of 1.
+    h[0] ^= 0x0101_0000 ^ 32;
+
+    let mut offset = 0;
+    while input.len() - offset > BLOCK_LEN {
+        h = compress(h, input, offset, (offset + BLOCK_LEN) as u128, false);
+        offset += BLOCK_LEN;
+    }
+    h = compress(h, input, offset, input.len() as u128, true);
+
+    let mut output = [0; 32];
+    let mut i = 0;
+    while i < 32 {
+        output[i] = (h[i / 8] >> (8 * (i % 8))) as u8;
+        i += 1;
+    }
+    output
+}
+
+/// Compresses the block of `input` starting at `offset` into the state `h`.
+///
+/// Bytes beyond the end of `input` are treated as zero padding.
+const fn compress(
+    h: [u64; 8],
+    input: &[u8],
+    offset: usize,
+    counter: u128,
+    last: bool,
+) -> [u64; 8] {
+    let mut m = [0u64; 16];
+    let mut i = 0;
+    while i < BLOCK_LEN && offset + i < input.len() {
+        m[i / 8] |= (input[offset + i] as u64) << (8 * (i % 8));
+        i += 1;
+    }
+
+    let mut v = [0u64; 16];
+    let mut i = 0;
+    while i < 8 {
+        v[i] = h[i];
+        v[i + 8] = IV[i];
+        i += 1;
+    }
+    v[12] ^= counter as u64;
+    v[13] ^= (counter >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    let mut round = 0;
+    while round < 12 {
+        let s = &SIGMA[round];
+        v = mix(v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        v = mix(v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        v = mix(v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        v = mix(v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        v = mix(v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        v = mix(v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        v = mix(v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        v = mix(v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        round += 1;
+    }
+
+    let mut h = h;
+    let mut i = 0;
+    while i < 8 {
+        h[i] ^= v[i] ^ v[i + 8];
+        i += 1;
+    }
+    h
+}
+
+/// The BLAKE2b mixing function `G`.
+#[allow(clippy::too_many_arguments)]
+const fn mix(
+    mut v: [u64; 16],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    x: u64,
+    y: u64,
+) -> [u64; 16] {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::blake2b_256;
+
+    #[test]
+    fn blake2b_256_works() {
+        // Known answers of BLAKE2b with a 256-bit digest.
+        assert_eq!(
+            blake2b_256(b""),
+            [
+                0x0e, 0x57, 0x51, 0xc0, 0x26, 0xe5, 0x43, 0xb2, 0xe8, 0xab, 0x2e, 0xb0,
+                0x60, 0x99, 0xda, 0xa1, 0xd1, 0xe5, 0xdf, 0x47, 0x77, 0x8f, 0x77, 0x87,
+                0xfa, 0xab, 0x45, 0xcd, 0xf1, 0x2f, 0xe3, 0xa8,
+            ]
+        );
+        assert_eq!(
+            blake2b_256(b"abc"),
+            [
+                0xbd, 0xdd, 0x81, 0x3c, 0x63, 0x42, 0x39, 0x72, 0x31, 0x71, 0xef, 0x3f,
+                0xee, 0x98, 0x57, 0x9b, 0x94, 0x96, 0x4e, 0x3b, 0xb1, 0xcb, 0x3e, 0x42,
+                0x72, 0x62, 0xc8, 0xc0, 0x68, 0xd5, 0x23, 0x19,
+            ]
+        );
+    }
+}
@@ -0,0 +1,442 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Collections with a capacity fixed at compile time.
+//!
+//! The capacity bounds the storage footprint of values of these types and is
+//! recorded in the storage layout of the contract metadata.
+
+use core::{
+    fmt,
+    ops::Deref,
+};
+use ink_prelude::{
+    string::String,
+    vec::Vec,
+};
+use scale::{
+    Compact,
+    Decode,
+    Encode,
+    EncodeLike,
+    Input,
+    Output,
+};
+#[cfg(feature = "std")]
+use scale_info::{
+    Type,
+    TypeInfo,
+};
+
+/// Returned if an insertion would exceed the capacity of a bounded collection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the capacity of the bounded collection is exceeded")
+    }
+}
+
+/// Decodes the length prefix of a bounded collection, rejecting lengths above `N`.
+fn decode_len<I: Input, const N: usize>(input: &mut I) -> Result<usize, scale::Error> {
+    let len = <Compact<u32>>::decode(input)?.0 as usize;
+    if len > N {
+        return Err("the length exceeds the capacity of the bounded collection".into())
+    }
+    Ok(len)
+}
+
+/// A vector holding at most `N` elements.
+///
+/// A `BoundedVec<T, N>` has the same encoding and type information as a `Vec<T>`.
+/// Decoding fails for more than `N` elements.
+///
+/// # Example
+///
+/// ```
+/// # use ink_primitives::{BoundedVec, CapacityError};
+/// let mut order_ids = BoundedVec::<u64, 2>::new();
+/// assert_eq!(order_ids.try_push(1), Ok(()));
+/// assert_eq!(order_ids.try_push(2), Ok(()));
+/// assert_eq!(order_ids.try_push(3), Err(CapacityError));
+/// assert_eq!(&order_ids[..], &[1, 2]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedVec<T, const N: usize>(Vec<T>);
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    /// The maximum number of elements.
+    pub const CAPACITY: usize = N;
+
+    /// Creates a new empty `BoundedVec`.
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Appends the `value` to the back of the vector.
+    ///
+    /// # Errors
+    ///
+    /// If the vector already holds `N` elements.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError)
+        }
+        self.0.push(value);
+        Ok(())
+    }
+
+    /// Inserts the `value` at position `index`, shifting all elements after it.
+    ///
+    /// # Errors
+    ///
+    /// If the vector already holds `N` elements.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length of the vector.
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError)
+        }
+        self.0.insert(index, value);
+        Ok(())
+    }
+
+    /// Removes the last element and returns it, or `None` if the vector is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    /// Removes the element at position `index`, shifting all elements after it.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.0.remove(index)
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Returns `true` if no more elements can be inserted.
+    pub fn is_full(&self) -> bool {
+        self.0.len() >= N
+    }
+
+    /// Returns the underlying vector.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for BoundedVec<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, CapacityError> {
+        if vec.len() > N {
+            return Err(CapacityError)
+        }
+        Ok(Self(vec))
+    }
+}
+
+impl<T, const N: usize> From<BoundedVec<T, N>> for Vec<T> {
+    fn from(vec: BoundedVec<T, N>) -> Self {
+        vec.0
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a BoundedVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for BoundedVec<T, N> {
+    type Item = T;
+    type IntoIter = ink_prelude::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T, const N: usize> Encode for BoundedVec<T, N>
+where
+    T: Encode,
+{
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<T, const N: usize> EncodeLike for BoundedVec<T, N> where T: Encode {}
+
+impl<T, const N: usize> EncodeLike<Vec<T>> for BoundedVec<T, N> where T: Encode {}
+
+impl<T, const N: usize> Decode for BoundedVec<T, N>
+where
+    T: Decode,
+{
+    fn decode<I: Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let len = decode_len::<_, N>(input)?;
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..len {
+            vec.push(T::decode(input)?);
+        }
+        Ok(Self(vec))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, const N: usize> TypeInfo for BoundedVec<T, N>
+where
+    T: TypeInfo + 'static,
+{
+    type Identity = <Vec<T> as TypeInfo>::Identity;
+
+    fn type_info() -> Type {
+        <Vec<T>>::type_info()
+    }
+}
+
+/// A string holding at most `N` bytes of UTF-8.
+///
+/// A `BoundedString<N>` has the same encoding and type information as a `String`.
+/// Decoding fails for more than `N` bytes.
+///
+/// # Example
+///
+/// ```
+/// # use ink_primitives::{BoundedString, CapacityError};
+/// let mut name = BoundedString::<8>::try_from("ink!").unwrap();
+/// assert_eq!(name.try_push_str(" 4"), Ok(()));
+/// assert_eq!(name.try_push_str(" rocks"), Err(CapacityError));
+/// assert_eq!(name.as_str(), "ink! 4");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BoundedString<const N: usize>(String);
+
+impl<const N: usize> BoundedString<N> {
+    /// The maximum number of bytes.
+    pub const CAPACITY: usize = N;
+
+    /// Creates a new empty `BoundedString`.
+    pub const fn new() -> Self {
+        Self(String::new())
+    }
+
+    /// Appends the character `ch` to the end of the string.
+    ///
+    /// # Errors
+    ///
+    /// If the UTF-8 encoding of `ch` does not fit into the remaining capacity.
+    pub fn try_push(&mut self, ch: char) -> Result<(), CapacityError> {
+        self.ensure_fits(ch.len_utf8())?;
+        self.0.push(ch);
+        Ok(())
+    }
+
+    /// Appends the string slice `string` to the end of the string.
+    ///
+    /// # Errors
+    ///
+    /// If `string` does not fit into the remaining capacity.
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), CapacityError> {
+        self.ensure_fits(string.len())?;
+        self.0.push_str(string);
+        Ok(())
+    }
+
+    /// Removes the last character and returns it, or `None` if the string is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        self.0.pop()
+    }
+
+    /// Removes all characters.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Returns the string slice of the whole string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the underlying string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    fn ensure_fits(&self, additional: usize) -> Result<(), CapacityError> {
+        if self.0.len() + additional > N {
+            return Err(CapacityError)
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> fmt::Display for BoundedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const N: usize> TryFrom<String> for BoundedString<N> {
+    type Error = CapacityError;
+
+    fn try_from(string: String) -> Result<Self, CapacityError> {
+        if string.len() > N {
+            return Err(CapacityError)
+        }
+        Ok(Self(string))
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for BoundedString<N> {
+    type Error = CapacityError;
+
+    fn try_from(string: &str) -> Result<Self, CapacityError> {
+        let mut bounded = Self::new();
+        bounded.try_push_str(string)?;
+        Ok(bounded)
+    }
+}
+
+impl<const N: usize> From<BoundedString<N>> for String {
+    fn from(string: BoundedString<N>) -> Self {
+        string.0
+    }
+}
+
+impl<const N: usize> Encode for BoundedString<N> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<const N: usize> EncodeLike for BoundedString<N> {}
+
+impl<const N: usize> EncodeLike<String> for BoundedString<N> {}
+
+impl<const N: usize> Decode for BoundedString<N> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let len = decode_len::<_, N>(input)?;
+        let mut bytes = ink_prelude::vec![0; len];
+        input.read(&mut bytes)?;
+        String::from_utf8(bytes)
+            .map(Self)
+            .map_err(|_| "invalid UTF-8 in bounded string".into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> TypeInfo for BoundedString<N> {
+    type Identity = <String as TypeInfo>::Identity;
+
+    fn type_info() -> Type {
+        String::type_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_vec_rejects_elements_beyond_capacity() {
+        let mut vec = BoundedVec::<u8, 2>::new();
+        assert_eq!(vec.try_insert(0, 2), Ok(()));
+        assert_eq!(vec.try_insert(0, 1), Ok(()));
+        assert!(vec.is_full());
+        assert_eq!(vec.try_push(3), Err(CapacityError));
+        assert_eq!(vec.try_insert(0, 0), Err(CapacityError));
+        assert_eq!(vec.pop(), Some(2));
+        assert_eq!(vec.try_push(3), Ok(()));
+        assert_eq!(vec.into_inner(), [1, 3]);
+        assert_eq!(
+            BoundedVec::<u8, 2>::try_from(Vec::from([1, 2, 3])),
+            Err(CapacityError)
+        );
+    }
+
+    #[test]
+    fn bounded_vec_encoding_works() {
+        let vec = BoundedVec::<u16, 3>::try_from(Vec::from([1, 2, 3])).unwrap();
+        let encoded = vec.encode();
+        assert_eq!(encoded, Vec::from([1u16, 2, 3]).encode());
+        assert_eq!(BoundedVec::<u16, 3>::decode(&mut &encoded[..]), Ok(vec));
+        assert!(BoundedVec::<u16, 2>::decode(&mut &encoded[..]).is_err());
+    }
+
+    #[test]
+    fn bounded_string_counts_bytes() {
+        let mut string = BoundedString::<4>::new();
+        assert_eq!(string.try_push('ä'), Ok(()));
+        assert_eq!(string.try_push_str("ab"), Ok(()));
+        assert_eq!(string.try_push('ä'), Err(CapacityError));
+        assert_eq!(string.as_str(), "äab");
+        assert_eq!(BoundedString::<2>::try_from("abc"), Err(CapacityError));
+    }
+
+    #[test]
+    fn bounded_string_encoding_works() {
+        let string = BoundedString::<5>::try_from("hello").unwrap();
+        let encoded = string.encode();
+        assert_eq!(encoded, String::from("hello").encode());
+        assert_eq!(BoundedString::<5>::decode(&mut &encoded[..]), Ok(string));
+        assert!(BoundedString::<4>::decode(&mut &encoded[..]).is_err());
+        let invalid = Vec::from([0xFFu8]).encode();
+        assert!(BoundedString::<4>::decode(&mut &invalid[..]).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_prelude::vec::Vec;
+#[cfg(feature = "std")]
+use scale_info::{
+    build::Fields,
+    Path,
+    Type,
+    TypeInfo,
+};
+
+/// A part of a large sequence of bytes returned by an ink! message flagged with
+/// `chunked`.
+///
+/// The bytes at `offset..offset + data.len()` of a sequence of `total_len` bytes.
+/// Clients fetch the following chunk at [`next_offset`](Self::next_offset) until
+/// the chunk [`is_last`](Self::is_last).
+#[derive(Debug, Clone, PartialEq, Eq, ::scale::Encode, ::scale::Decode)]
+pub struct Chunk {
+    /// The offset of the chunk within the sequence.
+    offset: u32,
+    /// The length of the whole sequence.
+    total_len: u32,
+    /// The bytes of the chunk.
+    data: Vec<u8>,
+}
+
+impl Chunk {
+    /// Creates the chunk of `data` at `offset` of a sequence of `total_len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `data` is not empty and exceeds the sequence.
+    pub fn new(offset: u32, total_len: u32, data: Vec<u8>) -> Self {
+        assert!(
+            data.is_empty()
+                || u64::from(offset) + data.len() as u64 <= u64::from(total_len),
+            "the chunk exceeds the sequence",
+        );
+        Self {
+            offset,
+            total_len,
+            data,
+        }
+    }
+
+    /// Returns the offset of the chunk within the sequence.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the length of the whole sequence.
+    pub fn total_len(&self) -> u32 {
+        self.total_len
+    }
+
+    /// Returns the bytes of the chunk.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the bytes of the chunk.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Returns the offset of the following chunk, `None` if this is the last chunk.
+    pub fn next_offset(&self) -> Option<u32> {
+        let end = self.offset.saturating_add(self.data.len() as u32);
+        (end < self.total_len).then_some(end)
+    }
+
+    /// Returns `true` if the chunk reaches the end of the sequence.
+    pub fn is_last(&self) -> bool {
+        self.next_offset().is_none()
+    }
+}
+
+// The `TypeInfo` implementation below registers the chunk under the crate root
+// instead of the private module it is defined in, so that its path in the contract
+// metadata stays stable.
+
+#[cfg(feature = "std")]
+impl TypeInfo for Chunk {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("Chunk", "ink_primitives"))
+            .composite(
+                Fields::named()
+                    .field(|f| f.ty::<u32>().name("offset").type_name("u32"))
+                    .field(|f| f.ty::<u32>().name("total_len").type_name("u32"))
+                    .field(|f| f.ty::<Vec<u8>>().name("data").type_name("Vec<u8>")),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_offset_works() {
+        let first = Chunk::new(0, 5, vec![1, 2, 3]);
+        assert_eq!(first.next_offset(), Some(3));
+        assert!(!first.is_last());
+
+        let last = Chunk::new(3, 5, vec![4, 5]);
+        assert_eq!(last.next_offset(), None);
+        assert!(last.is_last());
+
+        // Chunks beyond the sequence are empty.
+        assert!(Chunk::new(7, 5, Vec::new()).is_last());
+        assert!(Chunk::new(0, 0, Vec::new()).is_last());
+    }
+
+    #[test]
+    #[should_panic(expected = "the chunk exceeds the sequence")]
+    fn new_fails_for_exceeding_data() {
+        Chunk::new(4, 5, vec![5, 6]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn type_path_is_stable() {
+        assert_eq!(
+            Chunk::type_info().path.segments,
+            vec!["ink_primitives", "Chunk"]
+        );
+    }
+}
@@ -27,8 +27,28 @@ const XXH32_SEED: u32 = 0;
 ///   to indicate the respective cells using this primitive type.
 /// - The `Key` type can be compared to a raw pointer and also allows operations
 ///   similar to pointer arithmetic.
+/// - The key is a `u32` so that it can be used as a const generic parameter, e.g. in
+///   `ManualKey<123>`. The contracts pallet however identifies a storage cell by its
+///   raw bytes, which are the SCALE encoding of the key, i.e. its little endian bytes.
+///   Use [`KeyComposer::from_storage_bytes`] and [`KeyComposer::to_storage_bytes`]
+///   to convert between both representations.
+/// - Being an alias of `u32`, the key is ordered like integers and cannot have
+///   methods or a `Display` of its own. Wider keys, e.g. 32 byte hashes, don't fit
+///   into a const generic parameter on stable Rust and are therefore hashed into a
+///   `Key` with [`KeyComposer::from_hash`]. Use [`HexKey`] to display a key as in
+///   the metadata.
 pub type Key = u32;
 
+/// Formats a [`Key`] as `0x` prefixed big endian hex, as in the metadata.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexKey(pub Key);
+
+impl core::fmt::Display for HexKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
 /// Contains all rules related to storage key creation.
 pub struct KeyComposer;
 
@@ -59,6 +79,25 @@ impl KeyComposer {
         xxh32(bytes, XXH32_SEED)
     }
 
+    /// Returns the storage key of the 32 byte `hash`, e.g. of a variable length key.
+    ///
+    /// This is equal to [`KeyComposer::from_bytes`] applied to the `hash`.
+    pub const fn from_hash(hash: [u8; 32]) -> Key {
+        Self::from_bytes(&hash)
+    }
+
+    /// Returns the storage key identified by the raw `bytes` used by the contracts pallet.
+    pub const fn from_storage_bytes(bytes: [u8; 4]) -> Key {
+        Key::from_le_bytes(bytes)
+    }
+
+    /// Returns the raw bytes used by the contracts pallet to identify the storage `key`.
+    ///
+    /// This is equal to the SCALE encoding of the `key`.
+    pub const fn to_storage_bytes(key: Key) -> [u8; 4] {
+        key.to_le_bytes()
+    }
+
     /// Evaluates the storage key of the field in the structure, variant or union.
     ///
     /// 1. Compute the ASCII byte representation of `struct_name` and call it `S`.
@@ -132,6 +171,45 @@ mod tests {
         assert_eq!(KeyComposer::from_bytes(b"Hello world"), 0x9705d437);
     }
 
+    #[test]
+    fn from_hash_works_correct() {
+        let hash = [0x2a; 32];
+        assert_eq!(KeyComposer::from_hash(hash), KeyComposer::from_bytes(&hash));
+        assert_ne!(
+            KeyComposer::from_hash(hash),
+            KeyComposer::from_hash([0; 32])
+        );
+    }
+
+    #[test]
+    fn hex_key_works_correct() {
+        assert_eq!(HexKey(0x12345678).to_string(), "0x12345678");
+        assert_eq!(HexKey(0xff).to_string(), "0x000000ff");
+        assert!(HexKey(1) < HexKey(0x100));
+    }
+
+    #[test]
+    fn storage_bytes_works_correct() {
+        assert_eq!(
+            KeyComposer::to_storage_bytes(0x12345678),
+            [0x78, 0x56, 0x34, 0x12]
+        );
+        assert_eq!(
+            KeyComposer::from_storage_bytes([0x78, 0x56, 0x34, 0x12]),
+            0x12345678
+        );
+        for key in [0, 1, 0xf820ff02, Key::MAX] {
+            assert_eq!(
+                KeyComposer::to_storage_bytes(key)[..],
+                scale::Encode::encode(&key)[..]
+            );
+            assert_eq!(
+                KeyComposer::from_storage_bytes(KeyComposer::to_storage_bytes(key)),
+                key
+            );
+        }
+    }
+
     #[test]
     fn compute_key_works_correct() {
         assert_eq!(
@@ -27,17 +27,40 @@
 )]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod blake2;
+mod bounded;
+mod chunk;
 mod key;
+mod maybe_owned;
+mod multicall;
+mod selector;
 mod types;
 
 pub use self::{
+    bounded::{
+        BoundedString,
+        BoundedVec,
+        CapacityError,
+    },
+    chunk::Chunk,
     key::{
+        HexKey,
         Key,
         KeyComposer,
     },
+    maybe_owned::MaybeOwned,
+    multicall::{
+        MulticallCall,
+        MulticallError,
+        MulticallErrorKind,
+        MulticallPaidCall,
+        MulticallResult,
+    },
+    selector::Selector,
     types::{
         AccountId,
         Clear,
+        FunctionId,
         Hash,
     },
 };
@@ -54,6 +77,34 @@ pub use self::{
 pub enum LangError {
     /// Failed to read execution input for the dispatchable.
     CouldNotReadInput = 1u32,
+    /// The execution input exceeds the maximum size declared by the dispatchable.
+    InputTooLarge = 2u32,
+}
+
+/// An error emitted by a guarded upgrade of the code of a contract.
+///
+/// Returned if an ink! message flagged with `#[ink::upgrade_guard]` refused to set
+/// the code hash of the contract.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ::scale::Encode, ::scale::Decode)]
+#[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
+pub enum UpgradeError {
+    /// The contract is not paused.
+    NotPaused,
+    /// The given fingerprint does not match the storage fingerprint of the contract.
+    FingerprintMismatch,
+    /// Setting the code hash failed, e.g. because no code is stored under it.
+    SetCodeHashFailed,
+}
+
+/// An error returned once the gas budget of a call is exhausted.
+///
+/// Returned by the checkpoints of an `ink::budget::Budget` together with the position
+/// to resume the interrupted processing from in a subsequent call.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ::scale::Encode, ::scale::Decode)]
+#[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
+pub struct OutOfBudget<P = ()> {
+    /// The position to resume the processing from.
+    pub resume_at: P,
 }
 
 /// The `Result` type for ink! messages.
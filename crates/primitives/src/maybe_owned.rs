@@ -0,0 +1,213 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use scale::{
+    Decode,
+    DecodeAll,
+    Encode,
+    EncodeLike,
+    Input,
+    Output,
+};
+#[cfg(feature = "std")]
+use scale_info::{
+    Type,
+    TypeInfo,
+};
+
+/// A copy-on-write value of type `T` which is either decoded or still SCALE encoded.
+///
+/// The encoded variant borrows the SCALE encoding of a `T`, e.g. a slice of the
+/// input buffer, and is written as is when encoded. This allows to pass large
+/// payloads through a contract, like the call data forwarded by a proxy, without
+/// decoding and re-encoding them.
+///
+/// A `MaybeOwned<T>` has the same encoding and type information as `T`, so it can
+/// be used in place of `T` for message arguments, call builder arguments and values
+/// in storage. Decoding always yields the [`MaybeOwned::Owned`] variant.
+///
+/// # Example
+///
+/// ```
+/// # use ink_primitives::MaybeOwned;
+/// # use scale::Encode;
+/// let encoded = (42u32, true).encode();
+/// let value = MaybeOwned::<(u32, bool)>::Encoded(&encoded);
+/// // The encoded bytes are passed through without decoding them.
+/// assert_eq!(value.encode(), encoded);
+/// // But they can still be decoded on demand.
+/// assert_eq!(value.into_owned(), Ok((42, true)));
+/// ```
+#[derive(Debug, Clone)]
+pub enum MaybeOwned<'a, T> {
+    /// The decoded value.
+    Owned(T),
+    /// The SCALE encoding of the value.
+    Encoded(&'a [u8]),
+}
+
+impl<'a, T> MaybeOwned<'a, T> {
+    /// Returns `true` if the value is decoded.
+    pub fn is_owned(&self) -> bool {
+        matches!(self, Self::Owned(_))
+    }
+
+    /// Returns a reference to the value if it is decoded.
+    pub fn as_owned(&self) -> Option<&T> {
+        match self {
+            Self::Owned(value) => Some(value),
+            Self::Encoded(_) => None,
+        }
+    }
+}
+
+impl<'a, T> MaybeOwned<'a, T>
+where
+    T: Decode,
+{
+    /// Returns the value, decoding it if it is still encoded.
+    ///
+    /// # Errors
+    ///
+    /// If the encoded bytes are not exactly the encoding of a `T`.
+    pub fn into_owned(self) -> Result<T, scale::Error> {
+        match self {
+            Self::Owned(value) => Ok(value),
+            Self::Encoded(mut bytes) => T::decode_all(&mut bytes),
+        }
+    }
+
+    /// Returns a mutable reference to the value, decoding it first if it is still
+    /// encoded.
+    ///
+    /// # Errors
+    ///
+    /// If the encoded bytes are not exactly the encoding of a `T`.
+    pub fn to_mut(&mut self) -> Result<&mut T, scale::Error> {
+        if let Self::Encoded(mut bytes) = *self {
+            *self = Self::Owned(T::decode_all(&mut bytes)?);
+        }
+        match self {
+            Self::Owned(value) => Ok(value),
+            Self::Encoded(_) => unreachable!("the value has just been decoded"),
+        }
+    }
+}
+
+impl<'a, T> From<T> for MaybeOwned<'a, T> {
+    fn from(value: T) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<'a, T> Encode for MaybeOwned<'a, T>
+where
+    T: Encode,
+{
+    fn size_hint(&self) -> usize {
+        match self {
+            Self::Owned(value) => value.size_hint(),
+            Self::Encoded(bytes) => bytes.len(),
+        }
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        match self {
+            Self::Owned(value) => value.encode_to(dest),
+            Self::Encoded(bytes) => dest.write(bytes),
+        }
+    }
+
+    fn encoded_size(&self) -> usize {
+        match self {
+            Self::Owned(value) => value.encoded_size(),
+            Self::Encoded(bytes) => bytes.len(),
+        }
+    }
+}
+
+impl<'a, T> EncodeLike for MaybeOwned<'a, T> where T: Encode {}
+
+impl<'a, T> EncodeLike<T> for MaybeOwned<'a, T> where T: Encode {}
+
+impl<'a, T> Decode for MaybeOwned<'a, T>
+where
+    T: Decode,
+{
+    fn decode<I: Input>(input: &mut I) -> Result<Self, scale::Error> {
+        T::decode(input).map(Self::Owned)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> TypeInfo for MaybeOwned<'a, T>
+where
+    T: TypeInfo + 'static,
+{
+    type Identity = T;
+
+    fn type_info() -> Type {
+        T::type_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_prelude::vec::Vec;
+
+    #[test]
+    fn encoding_is_transparent() {
+        let value = (7u8, Some(1_000u128));
+        let encoded = value.encode();
+        let owned = MaybeOwned::Owned(value);
+        let borrowed = MaybeOwned::<(u8, Option<u128>)>::Encoded(&encoded);
+        assert_eq!(owned.encode(), encoded);
+        assert_eq!(borrowed.encode(), encoded);
+        assert_eq!(borrowed.encoded_size(), encoded.len());
+        assert_eq!(
+            <MaybeOwned<(u8, Option<u128>)>>::decode(&mut &encoded[..])
+                .map(MaybeOwned::into_owned),
+            Ok(Ok(value))
+        );
+    }
+
+    #[test]
+    fn encoded_bytes_are_not_length_prefixed() {
+        let payload = Vec::from([1u8, 2, 3]);
+        let encoded = payload.encode();
+        let forwarded = (0xCAFEBABEu32, MaybeOwned::<Vec<u8>>::Encoded(&encoded));
+        assert_eq!(forwarded.encode(), (0xCAFEBABEu32, payload).encode());
+    }
+
+    #[test]
+    fn decoding_on_demand_works() {
+        let encoded = 42u32.encode();
+        let mut value = MaybeOwned::<u32>::Encoded(&encoded);
+        assert!(!value.is_owned());
+        assert_eq!(value.as_owned(), None);
+        *value.to_mut().unwrap() += 1;
+        assert!(value.is_owned());
+        assert_eq!(value.as_owned(), Some(&43));
+        assert_eq!(value.encode(), 43u32.encode());
+    }
+
+    #[test]
+    fn decoding_invalid_bytes_fails() {
+        let too_short = [1u8, 2];
+        assert!(MaybeOwned::<u32>::Encoded(&too_short).into_owned().is_err());
+        let too_long = [1u8, 2, 3, 4, 5];
+        assert!(MaybeOwned::<u32>::Encoded(&too_long).into_owned().is_err());
+    }
+}
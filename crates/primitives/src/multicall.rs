@@ -0,0 +1,170 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Selector;
+use ink_prelude::vec::Vec;
+#[cfg(feature = "std")]
+use scale_info::{
+    build::{
+        Fields,
+        Variants,
+    },
+    Path,
+    Type,
+    TypeInfo,
+};
+
+/// A single call of a batch dispatched by an ink! multicall message.
+///
+/// Consists of the selector of the called ink! message and its SCALE encoded
+/// arguments.
+pub type MulticallCall = (Selector, Vec<u8>);
+
+/// A single call of a batch dispatched by an ink! multicall message that
+/// attributes a part of the transferred value to the call.
+///
+/// Consists of the selector of the called ink! message, the value attributed to
+/// the call and its SCALE encoded arguments. The attributed values of all calls
+/// of a batch must add up to the value transferred to the multicall message.
+pub type MulticallPaidCall<Balance> = (Selector, Balance, Vec<u8>);
+
+/// The `Result` type of ink! multicall messages.
+///
+/// Contains the SCALE encoded outputs of all calls of the batch in order.
+pub type MulticallResult = ::core::result::Result<Vec<Vec<u8>>, MulticallError>;
+
+/// The error returned by an ink! multicall message if one of its calls failed.
+///
+/// Returning this error reverts the whole batch.
+#[derive(Debug, Clone, PartialEq, Eq, ::scale::Encode, ::scale::Decode)]
+pub struct MulticallError {
+    /// The index of the failed call within the batch.
+    pub index: u32,
+    /// The reason why the call failed.
+    pub kind: MulticallErrorKind,
+}
+
+/// The reason why a call of an ink! multicall message failed.
+#[derive(Debug, Clone, PartialEq, Eq, ::scale::Encode, ::scale::Decode)]
+pub enum MulticallErrorKind {
+    /// The selector does not belong to any ink! message of the contract.
+    UnknownSelector,
+    /// The arguments could not be decoded for the selected ink! message.
+    InvalidParameters,
+    /// The arguments exceed the maximum input size of the selected ink! message.
+    InputTooLarge,
+    /// Value was transferred to the batch or attributed to the call, but the selected
+    /// ink! message is not payable.
+    PaidUnpayableMessage,
+    /// The selected ink! message is the multicall message itself.
+    Recursive,
+    /// The selected ink! message returned an `Err`.
+    ///
+    /// Contains the SCALE encoded `Err` returned by the message.
+    Reverted(Vec<u8>),
+    /// The values attributed to the calls do not add up to the transferred value.
+    ///
+    /// The index of the error is the first call whose attributed value exceeds the
+    /// remaining transferred value, or the number of calls if the attributed values
+    /// fall short of it.
+    InvalidValueAttribution,
+}
+
+// The `TypeInfo` implementations below register the multicall types under the
+// crate root instead of the private module they are defined in, so that their
+// paths in the contract metadata stay stable.
+
+#[cfg(feature = "std")]
+impl TypeInfo for MulticallError {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("MulticallError", "ink_primitives"))
+            .composite(
+                Fields::named()
+                    .field(|f| f.ty::<u32>().name("index").type_name("u32"))
+                    .field(|f| {
+                        f.ty::<MulticallErrorKind>()
+                            .name("kind")
+                            .type_name("MulticallErrorKind")
+                    }),
+            )
+    }
+}
+
+#[cfg(feature = "std")]
+impl TypeInfo for MulticallErrorKind {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("MulticallErrorKind", "ink_primitives"))
+            .variant(
+                Variants::new()
+                    .variant("UnknownSelector", |v| v.index(0))
+                    .variant("InvalidParameters", |v| v.index(1))
+                    .variant("InputTooLarge", |v| v.index(2))
+                    .variant("PaidUnpayableMessage", |v| v.index(3))
+                    .variant("Recursive", |v| v.index(4))
+                    .variant("Reverted", |v| {
+                        v.index(5).fields(
+                            Fields::unnamed()
+                                .field(|f| f.ty::<Vec<u8>>().type_name("Vec<u8>")),
+                        )
+                    })
+                    .variant("InvalidValueAttribution", |v| v.index(6)),
+            )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use scale::Encode as _;
+
+    #[test]
+    fn type_paths_are_stable() {
+        assert_eq!(
+            MulticallError::type_info().path.segments,
+            vec!["ink_primitives", "MulticallError"]
+        );
+        assert_eq!(
+            MulticallErrorKind::type_info().path.segments,
+            vec!["ink_primitives", "MulticallErrorKind"]
+        );
+    }
+
+    #[test]
+    fn variant_indices_match_encoding() {
+        let variants = match MulticallErrorKind::type_info().type_def {
+            scale_info::TypeDef::Variant(variants) => variants.variants,
+            _ => panic!("expected a variant type"),
+        };
+        let kinds = [
+            MulticallErrorKind::UnknownSelector,
+            MulticallErrorKind::InvalidParameters,
+            MulticallErrorKind::InputTooLarge,
+            MulticallErrorKind::PaidUnpayableMessage,
+            MulticallErrorKind::Recursive,
+            MulticallErrorKind::Reverted(Vec::new()),
+            MulticallErrorKind::InvalidValueAttribution,
+        ];
+        assert_eq!(variants.len(), kinds.len());
+        for (variant, kind) in variants.iter().zip(kinds) {
+            assert_eq!(variant.index, kind.encode()[0]);
+            assert!(format!("{:?}", kind).starts_with(variant.name));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::blake2::blake2b_256;
+use core::fmt;
+use derive_more::From;
+use scale::{
+    Decode,
+    Encode,
+};
+#[cfg(feature = "std")]
+use scale_info::{
+    build::Fields,
+    Path,
+    Type,
+    TypeInfo,
+};
+
+/// The 4 byte selector identifying an ink! message or constructor.
+///
+/// The bytes are stored in the order in which they appear in the call data,
+/// i.e. the selector `0x633AA551` starts with the byte `0x63`.
+///
+/// # Example
+///
+/// ```
+/// # use ink_primitives::Selector;
+/// const FLIP: Selector = Selector::from_label("flip");
+/// assert_eq!(FLIP, Selector::new([0x63, 0x3A, 0xA5, 0x51]));
+/// assert_eq!(FLIP.to_string(), "0x633aa551");
+/// ```
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, From, Encode, Decode)]
+pub struct Selector {
+    /// The 4 underlying bytes.
+    bytes: [u8; 4],
+}
+
+impl Selector {
+    /// Creates a selector directly from 4 bytes.
+    pub const fn new(bytes: [u8; 4]) -> Self {
+        Self { bytes }
+    }
+
+    /// Computes the selector of an ink! message or constructor from its label.
+    ///
+    /// The selector consists of the first 4 bytes of the BLAKE2b 256-bit hash of
+    /// the label. This is the same selector the `#[ink(message)]` and
+    /// `#[ink(constructor)]` attributes compute for an inherent method of the
+    /// given name without a custom `selector`.
+    ///
+    /// # Note
+    ///
+    /// Messages defined in an ink! trait definition have their selector computed
+    /// from the trait path and the message name instead. The label in this case
+    /// is `"TraitName::message_name"`.
+    pub const fn from_label(label: &str) -> Self {
+        let hash = blake2b_256(label.as_bytes());
+        Self::new([hash[0], hash[1], hash[2], hash[3]])
+    }
+
+    /// Returns the underlying bytes of the selector.
+    pub const fn to_bytes(self) -> [u8; 4] {
+        self.bytes
+    }
+}
+
+impl From<Selector> for [u8; 4] {
+    fn from(selector: Selector) -> Self {
+        selector.bytes
+    }
+}
+
+impl AsRef<[u8]> for Selector {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..]
+    }
+}
+
+impl fmt::LowerHex for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        for byte in self.bytes {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Registered under `ink_primitives::Selector` instead of the private module it is
+/// defined in, so that the path in the contract metadata stays stable.
+#[cfg(feature = "std")]
+impl TypeInfo for Selector {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("Selector", "ink_primitives"))
+            .composite(
+                Fields::named()
+                    .field(|f| f.ty::<[u8; 4]>().name("bytes").type_name("[u8; 4]")),
+            )
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selector;
+
+    #[test]
+    fn from_label_works() {
+        assert_eq!(
+            Selector::from_label("flip"),
+            Selector::new([0x63, 0x3A, 0xA5, 0x51])
+        );
+        assert_eq!(
+            Selector::from_label("get"),
+            Selector::new([0x2F, 0x86, 0x5B, 0xD9])
+        );
+        assert_eq!(
+            Selector::from_label("new"),
+            Selector::new([0x9B, 0xAE, 0x9D, 0x5E])
+        );
+    }
+
+    #[test]
+    fn from_label_is_const() {
+        const SELECTOR: Selector = Selector::from_label("flip");
+        assert_eq!(SELECTOR.to_bytes(), [0x63, 0x3A, 0xA5, 0x51]);
+    }
+
+    #[test]
+    fn formatting_works() {
+        let selector = Selector::new([0x0A, 0x1B, 0x2C, 0x3D]);
+        assert_eq!(selector.to_string(), "0x0a1b2c3d");
+        assert_eq!(format!("{:x}", selector), "0a1b2c3d");
+        assert_eq!(format!("{:#x}", selector), "0x0a1b2c3d");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn type_path_is_stable() {
+        use scale_info::TypeInfo as _;
+        assert_eq!(
+            Selector::type_info().path.segments,
+            vec!["ink_primitives", "Selector"]
+        );
+    }
+}
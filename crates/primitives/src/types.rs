@@ -13,7 +13,10 @@
 // limitations under the License.
 
 use core::array::TryFromSliceError;
-use derive_more::From;
+use derive_more::{
+    Display,
+    From,
+};
 use scale::{
     Decode,
     Encode,
@@ -155,3 +158,43 @@ impl Clear for Hash {
         Self(<[u8; 32] as Clear>::clear())
     }
 }
+
+/// The identifier of a chain extension method.
+///
+/// This is the `func_id` the contract passes to the `seal_call_chain_extension`
+/// host function in order to select which method of the chain extension to call.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Encode,
+    Decode,
+    From,
+    Default,
+    Display,
+)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct FunctionId(u32);
+
+impl FunctionId {
+    /// Creates a function identifier from its `u32` representation.
+    pub const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    /// Returns the `u32` representation of the function identifier.
+    pub const fn into_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<FunctionId> for u32 {
+    fn from(func_id: FunctionId) -> Self {
+        func_id.0
+    }
+}
@@ -0,0 +1,411 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A persisted cursor of a batch job which runs across several calls.
+//!
+//! # Note
+//!
+//! Like the [`Lazy`](crate::Lazy), the batch cursor doesn't actually "own" any
+//! data. The position to resume the job from is stored under the root key while
+//! the job is in progress.
+
+use super::{
+    Cursor,
+    IndexOrder,
+    IterableMapping,
+};
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The progress of a batch job, e.g. distributing rewards to a large number of
+/// accounts, which processes the positions `P` of a collection across several
+/// calls.
+///
+/// A job which is interrupted, e.g. because the gas left runs low, saves the
+/// position to resume from. The next call continues from there until the job
+/// finishes, which clears the saved position so that the next job starts over.
+///
+/// # Important
+///
+/// The batch cursor requires its own pre-defined storage key where to store the
+/// position. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the batch cursor together with the budget
+/// of a call:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::{
+///     budget::{
+///         Budget,
+///         OutOfBudget,
+///     },
+///     storage::{
+///         collections::{
+///             BatchCursor,
+///             IterableMapping,
+///         },
+///         Mapping,
+///     },
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Rewards {
+///     stakes: IterableMapping<AccountId, Balance>,
+///     rewards: Mapping<AccountId, Balance>,
+///     distribution: BatchCursor,
+/// }
+///
+/// impl Rewards {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     /// Distributes a reward per staked unit, resuming an interrupted
+///     /// distribution.
+///     #[ink(message)]
+///     pub fn distribute(&mut self, rate: Balance) -> Result<(), OutOfBudget<u32>> {
+///         let budget = Budget::<Environment>::new(1_000_000_000);
+///         let rewards = &mut self.rewards;
+///         budget.run_batch_over(&mut self.distribution, &self.stakes, |account, stake| {
+///             let reward = rewards.get(&account).unwrap_or_default();
+///             rewards.insert(account, &(reward + stake * rate));
+///         })
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BatchCursor<P = u32, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (P, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<P, KeyType> Default for BatchCursor<P, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P, KeyType> BatchCursor<P, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new `BatchCursor` without a job in progress.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<P, KeyType> ::core::fmt::Debug for BatchCursor<P, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("BatchCursor")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<P, KeyType> BatchCursor<P, KeyType>
+where
+    P: Packed,
+    KeyType: StorageKey,
+{
+    /// Returns the cursor to resume the job from, [`Cursor::start`] if no job is in
+    /// progress.
+    pub fn cursor(&self) -> Cursor<P> {
+        match self.saved() {
+            Some(position) => Cursor::at(position),
+            None => Cursor::start(),
+        }
+    }
+
+    /// Returns `true` if a job was interrupted and not finished yet.
+    pub fn is_running(&self) -> bool {
+        ink_env::contains_contract_storage(&KeyType::KEY).is_some()
+    }
+
+    /// Saves `position` as the position to resume the job from.
+    pub fn save(&mut self, position: &P) {
+        ink_env::set_contract_storage::<Key, P>(&KeyType::KEY, position);
+    }
+
+    /// Finishes the job, so that the next job starts over.
+    pub fn finish(&mut self) {
+        ink_env::clear_contract_storage(&KeyType::KEY);
+    }
+
+    /// Returns the saved position if any.
+    fn saved(&self) -> Option<P> {
+        ink_env::get_contract_storage::<Key, P>(&KeyType::KEY).unwrap_or_else(|error| {
+            panic!("Failed to get position of BatchCursor: {:?}", error)
+        })
+    }
+}
+
+impl<KeyType> BatchCursor<u32, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Processes the indices of a collection with `len` elements with `f`, from the
+    /// saved index on.
+    ///
+    /// `proceed` is asked before every index whether the job may go on, e.g.
+    /// because there is enough gas left.
+    ///
+    /// # Errors
+    ///
+    /// If `proceed` interrupts the job, with the index to resume from, which is
+    /// saved for the next call.
+    pub fn run<C, F>(&mut self, len: u32, mut proceed: C, mut f: F) -> Result<(), u32>
+    where
+        C: FnMut() -> bool,
+        F: FnMut(u32),
+    {
+        let start = self.saved().unwrap_or(0);
+        for index in start..len {
+            if !proceed() {
+                self.save(&index);
+                return Err(index)
+            }
+            f(index);
+        }
+        self.finish();
+        Ok(())
+    }
+
+    /// Processes the entries of `mapping` with `f`, from the saved position on.
+    ///
+    /// `proceed` is asked before every entry whether the job may go on, e.g.
+    /// because there is enough gas left.
+    ///
+    /// The saved position refers to the position of a key in the mapping. Removing
+    /// an entry moves other keys to new positions, so removals and, with
+    /// [`KeyOrderIndex`](crate::collections::KeyOrderIndex), insertions between two calls may
+    /// skip or repeat entries.
+    ///
+    /// # Errors
+    ///
+    /// If `proceed` interrupts the job, with the position to resume from, which is
+    /// saved for the next call.
+    pub fn run_over<K, V, MapKey, Order, C, F>(
+        &mut self,
+        mapping: &IterableMapping<K, V, MapKey, Order>,
+        proceed: C,
+        mut f: F,
+    ) -> Result<(), u32>
+    where
+        K: Packed,
+        V: Packed,
+        MapKey: StorageKey,
+        Order: IndexOrder<K>,
+        C: FnMut() -> bool,
+        F: FnMut(K, V),
+    {
+        self.run(mapping.len(), proceed, |index| {
+            if let Some(key) = mapping.key_at(index) {
+                if let Some(value) = mapping.get(&key) {
+                    f(key, value)
+                }
+            }
+        })
+    }
+}
+
+impl<P, KeyType> Storable for BatchCursor<P, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<P, Key, InnerKey> StorableHint<Key> for BatchCursor<P, InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = BatchCursor<P, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<P, KeyType> StorageKey for BatchCursor<P, KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<P, KeyType> StorageLayout for BatchCursor<P, KeyType>
+    where
+        P: StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <P as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+    use ink_prelude::vec::Vec;
+
+    #[test]
+    fn save_and_finish_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut cursor: BatchCursor<u32, ManualKey<123>> = BatchCursor::new();
+            assert!(!cursor.is_running());
+            assert_eq!(cursor.cursor(), Cursor::start());
+
+            cursor.save(&0);
+            assert!(cursor.is_running());
+            assert_eq!(cursor.cursor(), Cursor::at(0));
+
+            cursor.finish();
+            assert!(!cursor.is_running());
+            assert_eq!(cursor.cursor(), Cursor::start());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn run_resumes_from_saved_index() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut cursor: BatchCursor<u32, ManualKey<123>> = BatchCursor::new();
+            let mut processed = Vec::new();
+
+            let mut steps = 0;
+            let result = cursor.run(
+                10,
+                || {
+                    steps += 1;
+                    steps <= 4
+                },
+                |index| processed.push(index),
+            );
+            assert_eq!(result, Err(4));
+            assert!(cursor.is_running());
+            assert_eq!(cursor.cursor(), Cursor::at(4));
+
+            assert_eq!(
+                cursor.run(10, || true, |index| processed.push(index)),
+                Ok(())
+            );
+            assert!(!cursor.is_running());
+            assert_eq!(processed, (0..10).collect::<Vec<_>>());
+
+            // A finished job starts over.
+            processed.clear();
+            assert_eq!(
+                cursor.run(2, || true, |index| processed.push(index)),
+                Ok(())
+            );
+            assert_eq!(processed, [0, 1]);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn run_finishes_job_if_collection_shrunk() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut cursor: BatchCursor<u32, ManualKey<123>> = BatchCursor::new();
+            cursor.save(&5);
+            assert_eq!(cursor.run(3, || false, |_| unreachable!()), Ok(()));
+            assert!(!cursor.is_running());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn run_over_processes_all_entries() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u32, ManualKey<1>> =
+                IterableMapping::new();
+            for key in 0..5 {
+                mapping.insert(key, &(u32::from(key) * 10));
+            }
+            let mut cursor: BatchCursor<u32, ManualKey<123>> = BatchCursor::new();
+            let mut processed = Vec::new();
+
+            let mut steps = 0;
+            let result = cursor.run_over(
+                &mapping,
+                || {
+                    steps += 1;
+                    steps <= 2
+                },
+                |key, value| processed.push((key, value)),
+            );
+            assert_eq!(result, Err(2));
+            assert_eq!(
+                cursor.run_over(
+                    &mapping,
+                    || true,
+                    |key, value| { processed.push((key, value)) }
+                ),
+                Ok(())
+            );
+            assert_eq!(processed, mapping.iter().collect::<Vec<_>>());
+            assert_eq!(processed.len(), 5);
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,425 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A priority queue in contract storage which loads its elements lazily.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this heap doesn't actually "own" any data.
+//! The length is stored under the root key of the heap while every node of the
+//! binary tree is stored under the root key combined with its index.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// A priority queue of elements directly in contract storage.
+///
+/// The greatest element is always at the top of the heap. Pushing and popping an
+/// element only loads the nodes on a single path of the binary tree, i.e. a
+/// logarithmic number of elements, e.g. to settle the highest bid of an auction.
+/// Every node is stored in its own storage cell under the root key combined with
+/// its index.
+///
+/// # Important
+///
+/// The heap requires its own pre-defined storage key where to store its elements.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the heap:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::StorageBinaryHeap;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Auction {
+///     bids: StorageBinaryHeap<(Balance, AccountId)>,
+/// }
+///
+/// impl Auction {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message, payable)]
+///     pub fn bid(&mut self) {
+///         self.bids
+///             .push((self.env().transferred_value(), self.env().caller()));
+///     }
+///
+///     #[ink(message)]
+///     pub fn highest_bid(&self) -> Option<(Balance, AccountId)> {
+///         self.bids.peek()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageBinaryHeap<V: Packed + Ord, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<V, KeyType> Default for StorageBinaryHeap<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> StorageBinaryHeap<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageBinaryHeap`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageBinaryHeap<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageBinaryHeap")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<V, KeyType> StorageBinaryHeap<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<Key, u32>(&KeyType::KEY)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of StorageBinaryHeap: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the greatest element if any.
+    #[inline]
+    pub fn peek(&self) -> Option<V> {
+        self.node(0)
+    }
+
+    /// Pushes the `value` onto the heap.
+    ///
+    /// # Panics
+    ///
+    /// If the heap already contains `u32::MAX` elements.
+    pub fn push(&mut self, value: V) {
+        let mut index = self.len();
+        let len = index
+            .checked_add(1)
+            .expect("cannot push more than u32::MAX elements to StorageBinaryHeap");
+        // Move the parents which are less than `value` down until its position is found.
+        while index > 0 {
+            let parent_index = (index - 1) / 2;
+            let parent = self
+                .node(parent_index)
+                .expect("the parents of a node must be stored");
+            if value <= parent {
+                break
+            }
+            self.set_node(index, &parent);
+            index = parent_index;
+        }
+        self.set_node(index, &value);
+        self.set_len(len);
+    }
+
+    /// Removes the greatest element from the heap and returns it.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<V> {
+        let last = self.len().checked_sub(1)?;
+        let value = self.take_node(last);
+        if last == 0 {
+            ink_env::clear_contract_storage(&KeyType::KEY);
+            return value
+        }
+        let value = value.expect("the last node of a non-empty heap must be stored");
+        let top = self.node(0);
+        // Move the greater children up until the position of the former last
+        // element is found.
+        let mut index = 0;
+        loop {
+            let left = 2 * index + 1;
+            if left >= last {
+                break
+            }
+            let (child_index, child) = self.greater_child(left, last);
+            if value >= child {
+                break
+            }
+            self.set_node(index, &child);
+            index = child_index;
+        }
+        self.set_node(index, &value);
+        self.set_len(last);
+        top
+    }
+
+    /// Removes all elements from the heap.
+    ///
+    /// # Note
+    ///
+    /// This clears the storage cell of every element and hence is as expensive
+    /// as popping all elements.
+    pub fn clear(&mut self) {
+        for index in 0..self.len() {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, index));
+        }
+        ink_env::clear_contract_storage(&KeyType::KEY);
+    }
+
+    /// Returns the greater one of the children starting at index `left` among the
+    /// first `len` nodes together with its index.
+    fn greater_child(&self, left: u32, len: u32) -> (u32, V) {
+        let left_child = self.node(left).expect("the nodes of a heap must be stored");
+        let right = left + 1;
+        if right < len {
+            let right_child = self
+                .node(right)
+                .expect("the nodes of a heap must be stored");
+            if right_child > left_child {
+                return (right, right_child)
+            }
+        }
+        (left, left_child)
+    }
+
+    fn node(&self, index: u32) -> Option<V> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, index)).unwrap_or_else(|error| {
+            panic!("Failed to get value in StorageBinaryHeap: {:?}", error)
+        })
+    }
+
+    fn set_node(&mut self, index: u32, value: &V) {
+        ink_env::set_contract_storage(&(&KeyType::KEY, index), value);
+    }
+
+    fn take_node(&mut self, index: u32) -> Option<V> {
+        ink_env::take_contract_storage(&(&KeyType::KEY, index)).unwrap_or_else(|error| {
+            panic!("Failed to pop value in StorageBinaryHeap: {:?}", error)
+        })
+    }
+
+    /// Stores the number of elements.
+    fn set_len(&mut self, len: u32) {
+        ink_env::set_contract_storage::<Key, u32>(&KeyType::KEY, &len);
+    }
+}
+
+impl<V, KeyType> Storable for StorageBinaryHeap<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageBinaryHeap<V, InnerKey>
+where
+    V: Packed + Ord,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageBinaryHeap<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageBinaryHeap<V, KeyType>
+where
+    V: Packed + Ord,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageBinaryHeap<V, KeyType>
+    where
+        V: Packed + Ord + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn pops_in_descending_order() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut heap: StorageBinaryHeap<u32> = StorageBinaryHeap::new();
+            assert!(heap.is_empty());
+            assert_eq!(heap.peek(), None);
+            assert_eq!(heap.pop(), None);
+
+            let values = [5, 1, 8, 3, 9, 2, 8, 7, 0, 4, 6];
+            for value in values {
+                heap.push(value);
+            }
+            assert_eq!(heap.len(), values.len() as u32);
+            assert_eq!(heap.peek(), Some(9));
+
+            let mut sorted = values.to_vec();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            let popped = core::iter::from_fn(|| heap.pop()).collect::<Vec<_>>();
+            assert_eq!(popped, sorted);
+            assert!(heap.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn interleaved_push_and_pop_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut heap: StorageBinaryHeap<u8, ManualKey<123>> =
+                StorageBinaryHeap::new();
+            heap.push(3);
+            heap.push(1);
+
+            // When
+            assert_eq!(heap.pop(), Some(3));
+            heap.push(2);
+            heap.push(5);
+
+            // Then
+            let mut heap: StorageBinaryHeap<u8, ManualKey<123>> =
+                StorageBinaryHeap::new();
+            assert_eq!(heap.pop(), Some(5));
+            assert_eq!(heap.pop(), Some(2));
+            assert_eq!(heap.pop(), Some(1));
+            assert_eq!(heap.pop(), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn only_one_path_is_loaded() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut heap: StorageBinaryHeap<u32> = StorageBinaryHeap::new();
+            for value in 0..1023 {
+                heap.push(value);
+            }
+            let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+
+            // When
+            assert_eq!(heap.pop(), Some(1022));
+
+            // Then
+            let (reads_after, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+            // The length, the top and two children on each of the 9 levels below it.
+            assert!(reads_after - reads <= 2 + 2 * 9);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut heap: StorageBinaryHeap<u8> = StorageBinaryHeap::new();
+            heap.push(1);
+            heap.push(2);
+
+            // When
+            heap.clear();
+
+            // Then
+            assert!(heap.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
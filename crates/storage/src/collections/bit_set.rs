@@ -0,0 +1,387 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A compact set of bits in contract storage which loads its words lazily.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this set doesn't actually "own" any data.
+//! The bits are packed into words of 256 bits, every word is stored under the root
+//! key of the set combined with its index. Words without any set bit are not stored.
+
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::{
+    marker::PhantomData,
+    ops::Range,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The bits stored in a single storage cell.
+type Word = [u64; 4];
+
+/// The number of bits of a single limb of a [`Word`].
+const LIMB_BITS: u32 = u64::BITS;
+
+/// The number of bits stored in a single storage cell.
+const WORD_BITS: u32 = 4 * LIMB_BITS;
+
+/// A set of bits directly in contract storage.
+///
+/// The bits are packed into words of 256 bits, so that tracking e.g. the claimed
+/// slots of an airdrop takes one storage cell per 256 slots instead of one cell
+/// per slot as a `Mapping<u32, bool>` does. All bits are unset initially.
+///
+/// # Important
+///
+/// The set requires its own pre-defined storage key where to store its words.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the set:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::StorageBitSet;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Airdrop {
+///     claimed: StorageBitSet,
+/// }
+///
+/// impl Airdrop {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     /// Claims the given slot, returns `false` if it was claimed before.
+///     #[ink(message)]
+///     pub fn claim(&mut self, slot: u32) -> bool {
+///         !self.claimed.set(slot)
+///     }
+///
+///     #[ink(message)]
+///     pub fn claimed_in(&self, from: u32, to: u32) -> u32 {
+///         self.claimed.count_ones_in_range(from..to)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageBitSet<KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> KeyType>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<KeyType> Default for StorageBitSet<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<KeyType> StorageBitSet<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageBitSet`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<KeyType> ::core::fmt::Debug for StorageBitSet<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageBitSet")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<KeyType> StorageBitSet<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Returns `true` if the bit at `index` is set.
+    #[inline]
+    pub fn get(&self, index: u32) -> bool {
+        let (word_index, limb, mask) = Self::position(index);
+        matches!(self.word(word_index), Some(word) if word[limb] & mask != 0)
+    }
+
+    /// Sets the bit at `index`.
+    ///
+    /// Returns `true` if the bit was set before.
+    pub fn set(&mut self, index: u32) -> bool {
+        let (word_index, limb, mask) = Self::position(index);
+        let mut word = self.word(word_index).unwrap_or_default();
+        let was_set = word[limb] & mask != 0;
+        if !was_set {
+            word[limb] |= mask;
+            self.set_word(word_index, &word);
+        }
+        was_set
+    }
+
+    /// Unsets the bit at `index`.
+    ///
+    /// Returns `true` if the bit was set before.
+    pub fn clear(&mut self, index: u32) -> bool {
+        let (word_index, limb, mask) = Self::position(index);
+        let mut word = match self.word(word_index) {
+            Some(word) => word,
+            None => return false,
+        };
+        let was_set = word[limb] & mask != 0;
+        if was_set {
+            word[limb] &= !mask;
+            self.set_word(word_index, &word);
+        }
+        was_set
+    }
+
+    /// Returns the number of set bits with an index within `range`.
+    ///
+    /// # Note
+    ///
+    /// This loads every word overlapping with `range`, i.e. one storage cell per
+    /// 256 bits.
+    pub fn count_ones_in_range(&self, range: Range<u32>) -> u32 {
+        if range.is_empty() {
+            return 0
+        }
+        let first_word = range.start / WORD_BITS;
+        let last_word = (range.end - 1) / WORD_BITS;
+        (first_word..=last_word)
+            .filter_map(|word_index| {
+                let word = self.word(word_index)?;
+                // The bounds of `range` within the current word.
+                let offset = word_index * WORD_BITS;
+                let from = range.start.saturating_sub(offset);
+                let to = core::cmp::min(range.end - offset, WORD_BITS);
+                Some(count_ones(&word, from, to))
+            })
+            .sum()
+    }
+
+    /// Returns the index of the word, the index of the limb within the word and
+    /// the mask of the bit within the limb at `index`.
+    fn position(index: u32) -> (u32, usize, u64) {
+        let bit = index % WORD_BITS;
+        (
+            index / WORD_BITS,
+            (bit / LIMB_BITS) as usize,
+            1 << (bit % LIMB_BITS),
+        )
+    }
+
+    fn word(&self, word_index: u32) -> Option<Word> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, word_index)).unwrap_or_else(
+            |error| panic!("Failed to get word in StorageBitSet: {:?}", error),
+        )
+    }
+
+    /// Stores the `word`, words without any set bit are removed from the storage.
+    fn set_word(&mut self, word_index: u32, word: &Word) {
+        if word.iter().all(|limb| *limb == 0) {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, word_index));
+        } else {
+            ink_env::set_contract_storage(&(&KeyType::KEY, word_index), word);
+        }
+    }
+}
+
+/// Returns the number of set bits of the `word` with an index within `from..to`.
+fn count_ones(word: &Word, from: u32, to: u32) -> u32 {
+    (0..)
+        .step_by(LIMB_BITS as usize)
+        .zip(word)
+        .map(|(offset, limb): (u32, &u64)| {
+            let from = from.clamp(offset, offset + LIMB_BITS) - offset;
+            let to = to.clamp(offset, offset + LIMB_BITS) - offset;
+            let mask = match to - from {
+                0 => return 0,
+                LIMB_BITS => u64::MAX,
+                len => ((1 << len) - 1) << from,
+            };
+            (limb & mask).count_ones()
+        })
+        .sum()
+}
+
+impl<KeyType> Storable for StorageBitSet<KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<Key, InnerKey> StorableHint<Key> for StorageBitSet<InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageBitSet<Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<KeyType> StorageKey for StorageBitSet<KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<KeyType> StorageLayout for StorageBitSet<KeyType>
+    where
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <Word as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn set_get_and_clear_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut bits: StorageBitSet = StorageBitSet::new();
+            assert!(!bits.get(0));
+            assert!(!bits.clear(0));
+
+            assert!(!bits.set(0));
+            assert!(!bits.set(255));
+            assert!(!bits.set(256));
+            assert!(!bits.set(u32::MAX));
+            assert!(bits.set(255));
+            assert!(bits.get(0) && bits.get(255) && bits.get(256) && bits.get(u32::MAX));
+            assert!(!bits.get(1) && !bits.get(257));
+
+            assert!(bits.clear(255));
+            assert!(!bits.clear(255));
+            assert!(!bits.get(255));
+            assert!(bits.get(0));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn count_ones_in_range_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut bits: StorageBitSet<ManualKey<123>> = StorageBitSet::new();
+            let indices = [0, 3, 63, 64, 127, 200, 255, 256, 300, 1000, u32::MAX - 1];
+            for index in indices {
+                bits.set(index);
+            }
+            let count = |range: Range<u32>| {
+                indices.iter().filter(|index| range.contains(index)).count() as u32
+            };
+
+            let bits: StorageBitSet<ManualKey<123>> = StorageBitSet::new();
+            for range in [
+                0..0,
+                5..5,
+                0..1,
+                1..63,
+                3..64,
+                63..65,
+                0..256,
+                64..257,
+                200..1000,
+                128..1001,
+                0..1001,
+                u32::MAX - 1..u32::MAX,
+            ] {
+                assert_eq!(bits.count_ones_in_range(range.clone()), count(range));
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn bits_are_packed_into_words() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            let mut bits: StorageBitSet = StorageBitSet::new();
+            for index in 0..512 {
+                bits.set(index);
+            }
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(2)
+            );
+
+            // Words without any set bit are removed.
+            for index in 256..512 {
+                bits.clear(index);
+            }
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(1)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,449 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A byte blob split across multiple storage cells.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), the blob doesn't actually "own" any data.
+//! The length of the payload is stored under the root key of the blob and each
+//! chunk is stored under the root key combined with the index of the chunk.
+
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_prelude::vec::Vec;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// A byte payload of arbitrary size stored in fixed-size chunks in the contract storage.
+///
+/// The value of a single storage cell is limited by the static buffer of the contract.
+/// `StorageBlob` allows to store bigger payloads (Wasm blobs, images, proofs) by splitting
+/// them into chunks of [`StorageBlob::CHUNK_SIZE`] bytes. Reading or writing a range of
+/// the payload only loads the chunks which are touched by that range.
+///
+/// # Important
+///
+/// The blob requires its own pre-defined storage key in order to determine where it stores
+/// its chunks. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can specify a
+/// storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can do this:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{collections::StorageBlob, traits::ManualKey};
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct MyContract {
+///     code: StorageBlob<ManualKey<123>>,
+/// }
+///
+/// impl MyContract {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn upload_part(&mut self, data: Vec<u8>) {
+///         self.code.append(&data);
+///     }
+///
+///     #[ink(message)]
+///     pub fn download_part(&self, offset: u32, len: u32) -> Option<Vec<u8>> {
+///         self.code.read_range(offset, len)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageBlob<KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> KeyType>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<KeyType> Default for StorageBlob<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<KeyType> StorageBlob<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// The maximum amount of bytes stored in a single storage cell.
+    pub const CHUNK_SIZE: u32 = 4096;
+
+    /// Creates a new empty `StorageBlob`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<KeyType> core::fmt::Debug for StorageBlob<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageBlob")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<KeyType> StorageBlob<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Returns the length of the stored payload in bytes.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<Key, u32>(&KeyType::KEY)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of StorageBlob: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the stored payload is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads `len` bytes of the payload starting at `offset`.
+    ///
+    /// Only the chunks overlapping the requested range are loaded.
+    ///
+    /// Returns `None` if the range exceeds the length of the payload.
+    pub fn read_range(&self, offset: u32, len: u32) -> Option<Vec<u8>> {
+        let end = offset.checked_add(len)?;
+        if end > self.len() {
+            return None
+        }
+        let mut result = Vec::with_capacity(len as usize);
+        let mut position = offset;
+        while position < end {
+            let index = position / Self::CHUNK_SIZE;
+            let chunk_start = index * Self::CHUNK_SIZE;
+            let chunk = self.chunk(index);
+            let from = (position - chunk_start) as usize;
+            let to = (core::cmp::min(end - chunk_start, Self::CHUNK_SIZE)) as usize;
+            result.extend_from_slice(&chunk[from..to]);
+            position = chunk_start + to as u32;
+        }
+        Some(result)
+    }
+
+    /// Reads the whole payload.
+    ///
+    /// # Note
+    ///
+    /// This loads every chunk of the blob, prefer [`StorageBlob::read_range`] for
+    /// big payloads.
+    pub fn read(&self) -> Vec<u8> {
+        self.read_range(0, self.len())
+            .expect("the whole payload is always in range; qed")
+    }
+
+    /// Writes `data` into the payload starting at `offset`.
+    ///
+    /// Overwrites the existing bytes in the range and grows the payload if the
+    /// written range ends behind the current end of the payload.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` is greater than the length of the payload, since that would
+    /// leave a gap of undefined bytes.
+    pub fn write_at(&mut self, offset: u32, data: &[u8]) {
+        let len = self.len();
+        assert!(
+            offset <= len,
+            "StorageBlob: write offset {} is out of bounds (length {})",
+            offset,
+            len
+        );
+        let data_len =
+            u32::try_from(data.len()).expect("StorageBlob: data length overflows u32");
+        let end = offset
+            .checked_add(data_len)
+            .expect("StorageBlob: payload length overflows u32");
+        let mut position = offset;
+        let mut written = 0;
+        while position < end {
+            let index = position / Self::CHUNK_SIZE;
+            let chunk_start = index * Self::CHUNK_SIZE;
+            let from = (position - chunk_start) as usize;
+            let to = (core::cmp::min(end - chunk_start, Self::CHUNK_SIZE)) as usize;
+            let count = to - from;
+            // Only load the existing chunk if it is not overwritten completely.
+            let mut chunk = if from == 0 && to as u32 == Self::CHUNK_SIZE {
+                Vec::new()
+            } else {
+                self.chunk(index)
+            };
+            if chunk.len() < to {
+                chunk.resize(to, 0);
+            }
+            chunk[from..to].copy_from_slice(&data[written..written + count]);
+            ink_env::set_contract_storage(&(&KeyType::KEY, index), &chunk);
+            written += count;
+            position += count as u32;
+        }
+        if end > len {
+            ink_env::set_contract_storage(&KeyType::KEY, &end);
+        }
+    }
+
+    /// Appends `data` at the end of the payload.
+    pub fn append(&mut self, data: &[u8]) {
+        let len = self.len();
+        self.write_at(len, data);
+    }
+
+    /// Replaces the whole payload with `data`.
+    pub fn set(&mut self, data: &[u8]) {
+        self.truncate(0);
+        self.write_at(0, data);
+    }
+
+    /// Shortens the payload to `new_len` bytes, removing the chunks that are no longer used.
+    ///
+    /// Has no effect if `new_len` is greater than or equal to the current length.
+    pub fn truncate(&mut self, new_len: u32) {
+        let len = self.len();
+        if new_len >= len {
+            return
+        }
+        let first_unused = Self::chunks_for(new_len);
+        for index in first_unused..Self::chunks_for(len) {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, index));
+        }
+        if new_len % Self::CHUNK_SIZE != 0 {
+            let index = new_len / Self::CHUNK_SIZE;
+            let mut chunk = self.chunk(index);
+            chunk.truncate((new_len % Self::CHUNK_SIZE) as usize);
+            ink_env::set_contract_storage(&(&KeyType::KEY, index), &chunk);
+        }
+        if new_len == 0 {
+            ink_env::clear_contract_storage(&KeyType::KEY);
+        } else {
+            ink_env::set_contract_storage(&KeyType::KEY, &new_len);
+        }
+    }
+
+    /// Removes the whole payload from the contract storage.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Returns the number of chunks required to store `len` bytes.
+    fn chunks_for(len: u32) -> u32 {
+        (len + Self::CHUNK_SIZE - 1) / Self::CHUNK_SIZE
+    }
+
+    /// Loads the chunk at `index`, returning an empty chunk if none is stored.
+    fn chunk(&self, index: u32) -> Vec<u8> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, index))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get chunk of StorageBlob: {:?}", error)
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl<KeyType> Storable for StorageBlob<KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<Key, InnerKey> StorableHint<Key> for StorageBlob<InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageBlob<Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<KeyType> StorageKey for StorageBlob<KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<KeyType> StorageLayout for StorageBlob<KeyType>
+    where
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <Vec<u8> as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    type Blob = StorageBlob<ManualKey<42>>;
+    const CHUNK: u32 = Blob::CHUNK_SIZE;
+
+    fn payload(len: u32) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn empty_blob_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let blob = Blob::new();
+            assert_eq!(blob.len(), 0);
+            assert!(blob.is_empty());
+            assert_eq!(blob.read(), Vec::<u8>::new());
+            assert_eq!(blob.read_range(0, 1), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn append_and_read_across_chunks_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut blob = Blob::new();
+            let data = payload(CHUNK * 2 + 17);
+            blob.append(&data[..100]);
+            blob.append(&data[100..]);
+
+            assert_eq!(blob.len(), CHUNK * 2 + 17);
+            assert_eq!(blob.read(), data);
+            assert_eq!(
+                blob.read_range(CHUNK - 5, 10),
+                Some(data[(CHUNK - 5) as usize..(CHUNK + 5) as usize].to_vec())
+            );
+            assert_eq!(blob.read_range(CHUNK * 2 + 10, 8), None);
+            assert_eq!(blob.read_range(u32::MAX, 2), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn write_at_overwrites_and_grows() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut blob = Blob::new();
+            blob.set(&payload(10));
+            blob.write_at(8, &[0xFF; 4]);
+
+            let mut expected = payload(10);
+            expected.truncate(8);
+            expected.extend_from_slice(&[0xFF; 4]);
+            assert_eq!(blob.len(), 12);
+            assert_eq!(blob.read(), expected);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn write_at_with_gap_panics() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut blob = Blob::new();
+            blob.write_at(1, &[1]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn truncate_and_clear_remove_chunks() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            let mut blob = Blob::new();
+            let data = payload(CHUNK * 3);
+            blob.set(&data);
+            // The length cell plus three chunks.
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(4)
+            );
+
+            blob.truncate(CHUNK + 1);
+            assert_eq!(blob.read(), data[..(CHUNK + 1) as usize].to_vec());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(3)
+            );
+
+            blob.clear();
+            assert!(blob.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
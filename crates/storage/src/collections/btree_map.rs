@@ -0,0 +1,1048 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An ordered map in contract storage backed by a B-tree.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this map doesn't actually "own" any data.
+//! The values are stored under the root key of the map combined with their key,
+//! just like the values of a [`Mapping`](crate::Mapping). The keys are additionally
+//! kept in a B-tree whose nodes are stored under the root key combined with the
+//! node ID.
+
+use super::cursor::{
+    Cursor,
+    Page,
+};
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::{
+    marker::PhantomData,
+    ops::{
+        Bound,
+        RangeBounds,
+    },
+};
+use ink_prelude::{
+    vec,
+    vec::Vec,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Decode,
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage slot of the [`Header`].
+const HEADER: u8 = 0;
+/// The storage slot of the nodes of the B-tree.
+const NODES: u8 = 1;
+/// The storage slot of the values.
+const VALUES: u8 = 2;
+
+/// The minimum degree of the B-tree.
+///
+/// Every node but the root has between `MIN_DEGREE - 1` and `2 * MIN_DEGREE - 1` keys.
+const MIN_DEGREE: usize = 6;
+/// The maximum number of keys of a node.
+const MAX_KEYS: usize = 2 * MIN_DEGREE - 1;
+
+/// The bookkeeping of the B-tree.
+#[derive(Debug, Default, Encode, Decode)]
+struct Header {
+    /// The ID of the root node if the map is not empty.
+    root: Option<u32>,
+    /// The ID of the next allocated node.
+    next_node: u32,
+    /// The number of entries.
+    len: u32,
+}
+
+/// A node of the B-tree.
+///
+/// The node is a leaf if it has no children. Otherwise it has one child more
+/// than keys, where the keys of `children[i]` are less than `keys[i]`.
+#[derive(Debug, Encode, Decode)]
+struct Node<K> {
+    keys: Vec<K>,
+    children: Vec<u32>,
+}
+
+impl<K> Node<K> {
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// An ordered map of key-value pairs directly in contract storage.
+///
+/// In contrast to the [`Mapping`](crate::Mapping) the keys are kept in order, which
+/// allows to iterate over the entries in the order of their keys and to query
+/// ranges of keys, e.g. the price levels of an order book.
+///
+/// Looking up, inserting or removing a value by its key is as cheap as for the
+/// [`Mapping`](crate::Mapping), apart from maintaining the B-tree of keys when a key
+/// is inserted or removed. Every node of the B-tree holds up to 11 keys and is loaded
+/// only once it is reached, hence ordered queries load only the nodes on the path to
+/// the queried keys.
+///
+/// # Important
+///
+/// The map requires its own pre-defined storage key where to store its entries.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the map:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::StorageBTreeMap;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct OrderBook {
+///     /// The total amount offered at every price.
+///     asks: StorageBTreeMap<Balance, Balance>,
+/// }
+///
+/// impl OrderBook {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn ask(&mut self, price: Balance, amount: Balance) {
+///         let total = self.asks.get(&price).unwrap_or(0);
+///         self.asks.insert(price, &(total + amount));
+///     }
+///
+///     #[ink(message)]
+///     pub fn best_ask(&self) -> Option<(Balance, Balance)> {
+///         self.asks.first_key_value()
+///     }
+///
+///     #[ink(message)]
+///     pub fn asks_up_to(&self, max_price: Balance) -> Vec<(Balance, Balance)> {
+///         self.asks.range(..=max_price).collect()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageBTreeMap<K, V: Packed, KeyType: StorageKey = AutoKey> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K, V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<K, V, KeyType> Default for StorageBTreeMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KeyType> StorageBTreeMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageBTreeMap`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K, V, KeyType> ::core::fmt::Debug for StorageBTreeMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageBTreeMap")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<K, V, KeyType> StorageBTreeMap<K, V, KeyType>
+where
+    K: Packed + Ord,
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Insert the given `value` to the contract storage.
+    ///
+    /// Returns the size in bytes of the pre-existing value at the specified key if any.
+    #[inline]
+    pub fn insert<R>(&mut self, key: K, value: &R) -> Option<u32>
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let previous_size =
+            ink_env::set_contract_storage(&(&KeyType::KEY, VALUES, &key), value);
+        if previous_size.is_none() {
+            self.insert_key(key);
+        }
+        previous_size
+    }
+
+    /// Get the `value` at `key` from the contract storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<V> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, VALUES, key)).unwrap_or_else(
+            |error| panic!("Failed to get value in StorageBTreeMap: {:?}", error),
+        )
+    }
+
+    /// Removes the `value` at `key`, returning the previous `value` at `key` from storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn take(&mut self, key: &K) -> Option<V> {
+        let value = self.get(key);
+        if value.is_some() {
+            self.remove(key);
+        }
+        value
+    }
+
+    /// Checks if a value is stored at the given `key` in the contract storage.
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, VALUES, key)).is_some()
+    }
+
+    /// Clears the value at `key` from storage.
+    #[inline]
+    pub fn remove(&mut self, key: &K) {
+        if ink_env::clear_contract_storage(&(&KeyType::KEY, VALUES, key)).is_some() {
+            self.remove_key(key);
+        }
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.header().len
+    }
+
+    /// Returns `true` if there are no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the entry with the smallest key if any.
+    pub fn first_key_value(&self) -> Option<(K, V)> {
+        let mut node = self.node(self.header().root?);
+        while !node.is_leaf() {
+            node = self.node(node.children[0]);
+        }
+        let key = node.keys.into_iter().next()?;
+        self.entry(key)
+    }
+
+    /// Returns the entry with the largest key if any.
+    pub fn last_key_value(&self) -> Option<(K, V)> {
+        let mut node = self.node(self.header().root?);
+        while let Some(&child) = node.children.last() {
+            node = self.node(child);
+        }
+        let key = node.keys.pop()?;
+        self.entry(key)
+    }
+
+    /// Returns an iterator over the entries with keys in `range` in ascending order.
+    ///
+    /// Every node of the B-tree and every value is loaded from storage only once
+    /// the iterator reaches it.
+    pub fn range<R>(&self, range: R) -> impl Iterator<Item = (K, V)> + '_
+    where
+        R: RangeBounds<K> + 'static,
+    {
+        let mut iter = Range {
+            map: self,
+            stack: Vec::new(),
+            range,
+        };
+        let start = iter.range.start_bound();
+        descend(self, &mut iter.stack, self.header().root, start);
+        iter
+    }
+
+    /// Returns an iterator over all entries in ascending order of their keys.
+    ///
+    /// Every node of the B-tree and every value is loaded from storage only once
+    /// the iterator reaches it.
+    pub fn iter(&self) -> impl Iterator<Item = (K, V)> + '_ {
+        self.range(..)
+    }
+
+    /// Returns the page of at most `limit` entries in ascending order of their keys
+    /// from the key of `cursor` on together with the cursor of the next page.
+    ///
+    /// The cursor refers to the key of the first entry of the next page, so
+    /// insertions and removals between fetching two pages don't skip or repeat
+    /// any other entries.
+    pub fn iter_from(&self, cursor: Cursor<K>, limit: u32) -> Page<(K, V), K>
+    where
+        K: 'static,
+    {
+        let start = match cursor.position() {
+            Some(key) => Bound::Included(key),
+            None => Bound::Unbounded,
+        };
+        Page::collect(self.range((start, Bound::Unbounded)), limit, |(key, _)| key)
+    }
+
+    /// Removes all entries.
+    ///
+    /// # Note
+    ///
+    /// This clears the storage cells of every entry and every node of the B-tree
+    /// and hence is as expensive as removing all entries one by one. The gas costs
+    /// grow with the number of entries and may exceed the gas limit of a single call
+    /// for large maps, in which case the entries have to be removed in batches
+    /// across calls.
+    pub fn clear(&mut self) {
+        let mut nodes = self.header().root.into_iter().collect::<Vec<_>>();
+        while let Some(id) = nodes.pop() {
+            let node = self.node(id);
+            for key in &node.keys {
+                ink_env::clear_contract_storage(&(&KeyType::KEY, VALUES, key));
+            }
+            nodes.extend(node.children);
+            self.free_node(id);
+        }
+        ink_env::clear_contract_storage(&(&KeyType::KEY, HEADER));
+    }
+
+    /// Returns the entry of the stored `key`.
+    fn entry(&self, key: K) -> Option<(K, V)> {
+        let value = self.get(&key)?;
+        Some((key, value))
+    }
+
+    fn header(&self) -> Header {
+        ink_env::get_contract_storage(&(&KeyType::KEY, HEADER))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get header of StorageBTreeMap: {:?}", error)
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_header(&mut self, header: &Header) {
+        ink_env::set_contract_storage(&(&KeyType::KEY, HEADER), header);
+    }
+
+    fn node(&self, id: u32) -> Node<K> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, NODES, id))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get node of StorageBTreeMap: {:?}", error)
+            })
+            .expect("nodes referenced by the B-tree must be stored")
+    }
+
+    fn set_node(&mut self, id: u32, node: &Node<K>) {
+        ink_env::set_contract_storage(&(&KeyType::KEY, NODES, id), node);
+    }
+
+    fn free_node(&mut self, id: u32) {
+        ink_env::clear_contract_storage(&(&KeyType::KEY, NODES, id));
+    }
+
+    fn alloc_node(header: &mut Header) -> u32 {
+        let id = header.next_node;
+        header.next_node = id
+            .checked_add(1)
+            .expect("cannot allocate more than u32::MAX nodes in StorageBTreeMap");
+        id
+    }
+
+    /// Stores the `parent` node after one of its children has been merged.
+    ///
+    /// If the root became empty, its only child becomes the new root.
+    fn set_parent(&mut self, header: &mut Header, id: u32, parent: &Node<K>) {
+        if parent.keys.is_empty() && header.root == Some(id) {
+            header.root = parent.children.first().copied();
+            self.free_node(id);
+        } else {
+            self.set_node(id, parent);
+        }
+    }
+
+    /// Inserts `key` which is not yet contained into the B-tree.
+    fn insert_key(&mut self, key: K) {
+        let mut header = self.header();
+        let (mut id, mut node) = match header.root {
+            Some(root_id) => (root_id, self.node(root_id)),
+            None => {
+                let id = Self::alloc_node(&mut header);
+                header.root = Some(id);
+                (
+                    id,
+                    Node {
+                        keys: Vec::new(),
+                        children: Vec::new(),
+                    },
+                )
+            }
+        };
+        if node.keys.len() == MAX_KEYS {
+            // The tree grows in height by splitting the full root.
+            let root_id = Self::alloc_node(&mut header);
+            let mut root = Node {
+                keys: Vec::new(),
+                children: vec![id],
+            };
+            self.split_child(&mut header, &mut root, 0, node);
+            self.set_node(root_id, &root);
+            header.root = Some(root_id);
+            id = root_id;
+            node = root;
+        }
+        // Descend into the leaf the key belongs to, splitting full nodes on the way
+        // so that the parent of a split node always has room for the median key.
+        loop {
+            let index = node.keys.partition_point(|k| k < &key);
+            if node.is_leaf() {
+                node.keys.insert(index, key);
+                self.set_node(id, &node);
+                break
+            }
+            let child_id = node.children[index];
+            let child = self.node(child_id);
+            if child.keys.len() == MAX_KEYS {
+                let (left, right) =
+                    self.split_child(&mut header, &mut node, index, child);
+                self.set_node(id, &node);
+                if key > node.keys[index] {
+                    id = node.children[index + 1];
+                    node = right;
+                } else {
+                    id = child_id;
+                    node = left;
+                }
+            } else {
+                id = child_id;
+                node = child;
+            }
+        }
+        header.len += 1;
+        self.set_header(&header);
+    }
+
+    /// Splits the full child at `index` of `parent` and moves its median key into
+    /// `parent`.
+    ///
+    /// Stores both halves and returns them. The `parent` has to be stored by the caller.
+    fn split_child(
+        &mut self,
+        header: &mut Header,
+        parent: &mut Node<K>,
+        index: usize,
+        mut child: Node<K>,
+    ) -> (Node<K>, Node<K>) {
+        let right_id = Self::alloc_node(header);
+        let right = Node {
+            keys: child.keys.split_off(MIN_DEGREE),
+            children: if child.is_leaf() {
+                Vec::new()
+            } else {
+                child.children.split_off(MIN_DEGREE)
+            },
+        };
+        let median = child.keys.pop().expect("a full node has a median key");
+        self.set_node(parent.children[index], &child);
+        self.set_node(right_id, &right);
+        parent.keys.insert(index, median);
+        parent.children.insert(index + 1, right_id);
+        (child, right)
+    }
+
+    /// Removes the contained `key` from the B-tree.
+    fn remove_key(&mut self, key: &K) {
+        let mut header = self.header();
+        let mut id = header
+            .root
+            .expect("the B-tree of a non-empty map must have a root");
+        let mut node = self.node(id);
+        // Descend to the key making sure every node on the way but the root has
+        // more than the minimum number of keys, so that a key can be removed from it.
+        loop {
+            let found = node.keys.binary_search(key);
+            if node.is_leaf() {
+                let index = found.expect("contained keys must be stored in the B-tree");
+                node.keys.remove(index);
+                self.set_parent(&mut header, id, &node);
+                break
+            }
+            match found {
+                Ok(index) => {
+                    let left_id = node.children[index];
+                    let left = self.node(left_id);
+                    if left.keys.len() >= MIN_DEGREE {
+                        node.keys[index] = self.remove_max(&mut header, left_id, left);
+                        self.set_node(id, &node);
+                        break
+                    }
+                    let right_id = node.children[index + 1];
+                    let right = self.node(right_id);
+                    if right.keys.len() >= MIN_DEGREE {
+                        node.keys[index] = self.remove_min(&mut header, right_id, right);
+                        self.set_node(id, &node);
+                        break
+                    }
+                    let merged = self.merge_children(&mut node, index, left, right);
+                    self.set_parent(&mut header, id, &node);
+                    id = left_id;
+                    node = merged;
+                }
+                Err(index) => {
+                    let (child_id, child) =
+                        self.fill_child(&mut header, id, &mut node, index);
+                    id = child_id;
+                    node = child;
+                }
+            }
+        }
+        header.len -= 1;
+        self.set_header(&header);
+    }
+
+    /// Removes and returns the largest key of the subtree rooted at `node`.
+    fn remove_max(&mut self, header: &mut Header, mut id: u32, mut node: Node<K>) -> K {
+        loop {
+            if node.is_leaf() {
+                let key = node.keys.pop().expect("nodes must not be empty");
+                self.set_node(id, &node);
+                return key
+            }
+            let index = node.children.len() - 1;
+            (id, node) = self.fill_child(header, id, &mut node, index);
+        }
+    }
+
+    /// Removes and returns the smallest key of the subtree rooted at `node`.
+    fn remove_min(&mut self, header: &mut Header, mut id: u32, mut node: Node<K>) -> K {
+        loop {
+            if node.is_leaf() {
+                let key = node.keys.remove(0);
+                self.set_node(id, &node);
+                return key
+            }
+            (id, node) = self.fill_child(header, id, &mut node, 0);
+        }
+    }
+
+    /// Makes sure the child at `index` of `parent` has more than the minimum number
+    /// of keys by moving a key from a sibling or merging it with a sibling.
+    ///
+    /// Returns the ID of the node which now holds the keys of the child and the node.
+    fn fill_child(
+        &mut self,
+        header: &mut Header,
+        parent_id: u32,
+        parent: &mut Node<K>,
+        index: usize,
+    ) -> (u32, Node<K>) {
+        let child_id = parent.children[index];
+        let mut child = self.node(child_id);
+        if child.keys.len() >= MIN_DEGREE {
+            return (child_id, child)
+        }
+        let left = (index > 0).then(|| {
+            let left_id = parent.children[index - 1];
+            (left_id, self.node(left_id))
+        });
+        if let Some((left_id, mut left)) = left {
+            if left.keys.len() >= MIN_DEGREE {
+                let key = left.keys.pop().expect("nodes must not be empty");
+                child
+                    .keys
+                    .insert(0, core::mem::replace(&mut parent.keys[index - 1], key));
+                if let Some(grandchild) = left.children.pop() {
+                    child.children.insert(0, grandchild);
+                }
+                self.set_node(left_id, &left);
+                self.set_node(child_id, &child);
+                self.set_node(parent_id, parent);
+                return (child_id, child)
+            }
+            if index + 1 == parent.children.len() {
+                let merged = self.merge_children(parent, index - 1, left, child);
+                self.set_parent(header, parent_id, parent);
+                return (left_id, merged)
+            }
+        }
+        let right_id = parent.children[index + 1];
+        let mut right = self.node(right_id);
+        if right.keys.len() >= MIN_DEGREE {
+            let key = right.keys.remove(0);
+            child
+                .keys
+                .push(core::mem::replace(&mut parent.keys[index], key));
+            if !right.is_leaf() {
+                child.children.push(right.children.remove(0));
+            }
+            self.set_node(right_id, &right);
+            self.set_node(child_id, &child);
+            self.set_node(parent_id, parent);
+            return (child_id, child)
+        }
+        let merged = self.merge_children(parent, index, child, right);
+        self.set_parent(header, parent_id, parent);
+        (child_id, merged)
+    }
+
+    /// Merges the children at `index` and `index + 1` of `parent` together with
+    /// the key separating them into the child at `index`.
+    ///
+    /// Stores the merged child and returns it. The `parent` has to be stored by
+    /// the caller.
+    fn merge_children(
+        &mut self,
+        parent: &mut Node<K>,
+        index: usize,
+        mut left: Node<K>,
+        right: Node<K>,
+    ) -> Node<K> {
+        left.keys.push(parent.keys.remove(index));
+        left.keys.extend(right.keys);
+        left.children.extend(right.children);
+        let right_id = parent.children.remove(index + 1);
+        self.free_node(right_id);
+        self.set_node(parent.children[index], &left);
+        left
+    }
+}
+
+/// A position within a node of the B-tree during an in-order traversal.
+struct Frame<K> {
+    /// The remaining keys of the node.
+    keys: vec::IntoIter<K>,
+    /// The remaining children of the node.
+    children: vec::IntoIter<u32>,
+}
+
+/// Pushes the path from the node `id` down to the leftmost leaf of keys within
+/// `start` onto the `stack`.
+fn descend<K, V, KeyType>(
+    map: &StorageBTreeMap<K, V, KeyType>,
+    stack: &mut Vec<Frame<K>>,
+    mut id: Option<u32>,
+    start: Bound<&K>,
+) where
+    K: Packed + Ord,
+    V: Packed,
+    KeyType: StorageKey,
+{
+    while let Some(node_id) = id {
+        let node = map.node(node_id);
+        let skip = match start {
+            Bound::Included(start) => node.keys.partition_point(|key| key < start),
+            Bound::Excluded(start) => node.keys.partition_point(|key| key <= start),
+            Bound::Unbounded => 0,
+        };
+        let mut keys = node.keys.into_iter();
+        let mut children = node.children.into_iter();
+        keys.by_ref().take(skip).for_each(drop);
+        children.by_ref().take(skip).for_each(drop);
+        id = children.next();
+        stack.push(Frame { keys, children });
+    }
+}
+
+/// An iterator over a range of entries of a [`StorageBTreeMap`].
+struct Range<'a, K, V: Packed, KeyType: StorageKey, R> {
+    map: &'a StorageBTreeMap<K, V, KeyType>,
+    stack: Vec<Frame<K>>,
+    range: R,
+}
+
+impl<'a, K, V, KeyType, R> Iterator for Range<'a, K, V, KeyType, R>
+where
+    K: Packed + Ord,
+    V: Packed,
+    KeyType: StorageKey,
+    R: RangeBounds<K>,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let key = match frame.keys.next() {
+                Some(key) => key,
+                None => {
+                    self.stack.pop();
+                    continue
+                }
+            };
+            let within_range = match self.range.end_bound() {
+                Bound::Included(end) => &key <= end,
+                Bound::Excluded(end) => &key < end,
+                Bound::Unbounded => true,
+            };
+            if !within_range {
+                self.stack.clear();
+                return None
+            }
+            let child = frame.children.next();
+            descend(self.map, &mut self.stack, child, Bound::Unbounded);
+            return self.map.entry(key)
+        }
+    }
+}
+
+impl<K, V, KeyType> Storable for StorageBTreeMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<K, V, Key, InnerKey> StorableHint<Key> for StorageBTreeMap<K, V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageBTreeMap<K, V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<K, V, KeyType> StorageKey for StorageBTreeMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<K, V, KeyType> StorageLayout for StorageBTreeMap<K, V, KeyType>
+    where
+        K: scale_info::TypeInfo + 'static,
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+    use std::collections::BTreeMap;
+
+    type Map = StorageBTreeMap<u32, u32, ManualKey<123>>;
+
+    /// Returns the keys `0..n` in a pseudo random order.
+    fn shuffled(n: u32) -> Vec<u32> {
+        let mut keys = (0..n).collect::<Vec<_>>();
+        let mut seed = 42u64;
+        for i in (1..keys.len()).rev() {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            keys.swap(i, (seed >> 33) as usize % (i + 1));
+        }
+        keys
+    }
+
+    /// Asserts that the B-tree is balanced, ordered and its nodes are within bounds.
+    ///
+    /// Returns the depth of the subtree.
+    fn assert_valid(map: &Map, id: u32, is_root: bool) -> usize {
+        let node = map.node(id);
+        assert!(node.keys.len() <= MAX_KEYS);
+        if !is_root {
+            assert!(node.keys.len() >= MIN_DEGREE - 1);
+        }
+        assert!(node.keys.windows(2).all(|keys| keys[0] < keys[1]));
+        if node.is_leaf() {
+            return 1
+        }
+        assert_eq!(node.children.len(), node.keys.len() + 1);
+        let depths = node
+            .children
+            .iter()
+            .map(|&child| assert_valid(map, child, false))
+            .collect::<Vec<_>>();
+        assert!(depths.windows(2).all(|depths| depths[0] == depths[1]));
+        depths[0] + 1
+    }
+
+    fn assert_same(map: &Map, expected: &BTreeMap<u32, u32>) {
+        assert_eq!(map.len() as usize, expected.len());
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            expected.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>()
+        );
+        if let Some(root) = map.header().root {
+            assert_valid(map, root, true);
+        }
+    }
+
+    #[test]
+    fn insert_and_get_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut map: StorageBTreeMap<u8, u8> = StorageBTreeMap::new();
+            assert_eq!(map.insert(1, &2), None);
+            assert_eq!(map.insert(1, &3), Some(1));
+            assert_eq!(map.get(&1), Some(3));
+            assert_eq!(map.get(&2), None);
+            assert!(map.contains(&1));
+            assert_eq!(map.len(), 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn ordered_queries_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut map = Map::new();
+            let mut expected = BTreeMap::new();
+            assert_eq!(map.first_key_value(), None);
+            assert_eq!(map.last_key_value(), None);
+
+            // When
+            for key in shuffled(500) {
+                map.insert(key * 2, &key);
+                expected.insert(key * 2, key);
+            }
+
+            // Then
+            assert_same(&map, &expected);
+            assert_eq!(map.first_key_value(), Some((0, 0)));
+            assert_eq!(map.last_key_value(), Some((998, 499)));
+            let collect = |range: (Bound<u32>, Bound<u32>)| {
+                (
+                    map.range(range).collect::<Vec<_>>(),
+                    expected
+                        .range(range)
+                        .map(|(k, v)| (*k, *v))
+                        .collect::<Vec<_>>(),
+                )
+            };
+            for range in [
+                (Bound::Included(100), Bound::Excluded(200)),
+                (Bound::Excluded(101), Bound::Included(201)),
+                (Bound::Excluded(100), Bound::Unbounded),
+                (Bound::Unbounded, Bound::Included(7)),
+                (Bound::Included(999), Bound::Unbounded),
+                (Bound::Included(3), Bound::Excluded(4)),
+            ] {
+                let (actual, expected) = collect(range);
+                assert_eq!(actual, expected);
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_and_take_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut map = Map::new();
+            let mut expected = BTreeMap::new();
+            for key in shuffled(300) {
+                map.insert(key, &key);
+                expected.insert(key, key);
+            }
+
+            // When
+            for key in shuffled(300).into_iter().filter(|key| key % 3 != 0) {
+                if key % 2 == 0 {
+                    assert_eq!(map.take(&key), Some(key));
+                } else {
+                    map.remove(&key);
+                }
+                expected.remove(&key);
+            }
+            map.remove(&1);
+            assert_eq!(map.take(&1), None);
+
+            // Then
+            assert_same(&map, &expected);
+
+            // When
+            for key in shuffled(300) {
+                map.remove(&key);
+            }
+
+            // Then
+            assert!(map.is_empty());
+            assert_eq!(map.iter().next(), None);
+            assert_eq!(map.header().root, None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn removal_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut map = Map::new();
+            for key in 0..100 {
+                map.insert(key, &key);
+            }
+
+            // When
+            for key in 0..100 {
+                map.remove(&key);
+            }
+
+            // Then
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(1)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_removes_all_entries() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut map = Map::new();
+            for key in 0..100 {
+                map.insert(key, &key);
+            }
+
+            // When
+            map.clear();
+
+            // Then
+            assert!(map.is_empty());
+            assert_eq!(map.get(&1), None);
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn range_loads_nodes_lazily() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut map = Map::new();
+            for key in 0..1_000 {
+                map.insert(key, &key);
+            }
+            let reads = || {
+                ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                    &accounts.alice,
+                )
+                .0
+            };
+            let depth = assert_valid(&map, map.header().root.unwrap(), true);
+            let before = reads();
+
+            // When
+            assert_eq!(map.range(500..).next(), Some((500, 500)));
+
+            // Then
+            // The header, the path to the key and its value.
+            assert!(reads() - before <= depth + 2);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_from_resumes_after_removals() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut map = Map::new();
+            for key in 0..50 {
+                map.insert(key, &key);
+            }
+
+            let page = map.iter_from(Cursor::start(), 20);
+            assert_eq!(
+                page.items,
+                (0..20).map(|key| (key, key)).collect::<Vec<_>>()
+            );
+            map.remove(&5);
+            map.remove(&25);
+            let page = map.iter_from(page.next.unwrap(), 20);
+            assert_eq!(page.items.first(), Some(&(20, 20)));
+            assert_eq!(page.items.last(), Some(&(40, 40)));
+            let page = map.iter_from(page.next.unwrap(), 20);
+            assert_eq!(page.items.len(), 9);
+            assert_eq!(page.next, None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
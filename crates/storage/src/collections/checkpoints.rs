@@ -0,0 +1,387 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Historic values per key, e.g. the balances of a governance token per block.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), the checkpoints don't actually "own" any
+//! data. The number of checkpoints of a key is stored under the root key combined
+//! with the key while every checkpoint is stored lazily under the root key combined
+//! with the key and its index.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::{
+    DefaultEnvironment,
+    Environment,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The suffix of the storage keys of the numbers of checkpoints.
+const LENGTH: u8 = 0;
+
+/// The suffix of the storage keys of the checkpoints.
+const CHECKPOINT: u8 = 1;
+
+/// The values `V` of every key `K` recorded at the blocks in which they changed.
+///
+/// Every change of the value of a key is recorded as a checkpoint together with
+/// the current block number, so that the value at any past block can be queried
+/// with [`value_at`](Self::value_at), e.g. the voting power of an account at the
+/// creation of a proposal. A lookup binary searches the checkpoints of the key,
+/// reading only a logarithmic number of them from the contract storage.
+///
+/// # Important
+///
+/// The checkpoints require their own pre-defined storage key where to store the
+/// values. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the checkpoints:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::Checkpoints;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Token {
+///     balances: Checkpoints<AccountId, Balance, Environment>,
+/// }
+///
+/// impl Token {
+///     #[ink(constructor)]
+///     pub fn new(supply: Balance) -> Self {
+///         let mut instance = Self::default();
+///         instance.balances.push(&Self::env().caller(), supply);
+///         instance
+///     }
+///
+///     #[ink(message)]
+///     pub fn transfer(&mut self, to: AccountId, value: Balance) {
+///         let from = self.env().caller();
+///         let from_balance = self.balance_of(from);
+///         assert!(from_balance >= value, "insufficient balance");
+///         self.balances.push(&from, from_balance - value);
+///         self.balances.push(&to, self.balance_of(to) + value);
+///     }
+///
+///     #[ink(message)]
+///     pub fn balance_of(&self, owner: AccountId) -> Balance {
+///         self.balances.latest(&owner).unwrap_or_default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn balance_at(&self, owner: AccountId, block: BlockNumber) -> Balance {
+///         self.balances.value_at(&owner, block).unwrap_or_default()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Checkpoints<
+    K,
+    V: Packed,
+    E: Environment = DefaultEnvironment,
+    KeyType: StorageKey = AutoKey,
+> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K, V, E, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<K, V, E, KeyType> Default for Checkpoints<K, V, E, KeyType>
+where
+    V: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, E, KeyType> Checkpoints<K, V, E, KeyType>
+where
+    V: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Creates new empty `Checkpoints`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K, V, E, KeyType> ::core::fmt::Debug for Checkpoints<K, V, E, KeyType>
+where
+    V: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Checkpoints")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<K, V, E, KeyType> Checkpoints<K, V, E, KeyType>
+where
+    K: Encode,
+    V: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Records `value` as the value of `key` from the current block on.
+    ///
+    /// A checkpoint recorded earlier in the same block is overwritten, so that every
+    /// block has at most one checkpoint per key.
+    pub fn push(&mut self, key: &K, value: V) {
+        let block = ink_env::block_number::<E>();
+        let len = self.len(key);
+        let last = len
+            .checked_sub(1)
+            .and_then(|index| self.checkpoint(key, index));
+        let index = match last {
+            Some((recorded, _)) if recorded == block => len - 1,
+            _ => {
+                ink_env::set_contract_storage(&(&KeyType::KEY, LENGTH, key), &(len + 1));
+                len
+            }
+        };
+        ink_env::set_contract_storage(
+            &(&KeyType::KEY, CHECKPOINT, key, index),
+            &(block, value),
+        );
+    }
+
+    /// Returns the number of checkpoints of `key`.
+    #[inline]
+    pub fn len(&self, key: &K) -> u32 {
+        ink_env::get_contract_storage(&(&KeyType::KEY, LENGTH, key))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of Checkpoints: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if there are no checkpoints of `key`.
+    #[inline]
+    pub fn is_empty(&self, key: &K) -> bool {
+        self.len(key) == 0
+    }
+
+    /// Returns the checkpoint of `key` at `index` as its block number and value.
+    ///
+    /// Returns `None` if `key` has no checkpoint at `index`.
+    #[inline]
+    pub fn checkpoint(&self, key: &K, index: u32) -> Option<(E::BlockNumber, V)> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, CHECKPOINT, key, index))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get checkpoint of Checkpoints: {:?}", error)
+            })
+    }
+
+    /// Returns the current value of `key`.
+    ///
+    /// Returns `None` if there are no checkpoints of `key`.
+    pub fn latest(&self, key: &K) -> Option<V> {
+        match self.len(key) {
+            0 => None,
+            len => self.checkpoint(key, len - 1).map(|(_, value)| value),
+        }
+    }
+
+    /// Returns the value of `key` at the end of `block`.
+    ///
+    /// Returns `None` if the first checkpoint of `key` was recorded after `block`.
+    ///
+    /// # Note
+    ///
+    /// The checkpoints are binary searched, hence at most about `log2(len) + 1` of
+    /// them are read from the contract storage.
+    pub fn value_at(&self, key: &K, block: E::BlockNumber) -> Option<V> {
+        // Find the number of checkpoints recorded at or before `block`.
+        let (mut low, mut high) = (0, self.len(key));
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.checkpoint(key, mid) {
+                Some((recorded, _)) if recorded <= block => low = mid + 1,
+                _ => high = mid,
+            }
+        }
+        match low {
+            0 => None,
+            found => self.checkpoint(key, found - 1).map(|(_, value)| value),
+        }
+    }
+}
+
+impl<K, V, E, KeyType> Storable for Checkpoints<K, V, E, KeyType>
+where
+    V: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<K, V, E, Key, InnerKey> StorableHint<Key> for Checkpoints<K, V, E, InnerKey>
+where
+    V: Packed,
+    E: Environment,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Checkpoints<K, V, E, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<K, V, E, KeyType> StorageKey for Checkpoints<K, V, E, KeyType>
+where
+    V: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        RootLayout,
+        StructLayout,
+    };
+
+    impl<K, V, E, KeyType> StorageLayout for Checkpoints<K, V, E, KeyType>
+    where
+        K: scale_info::TypeInfo + 'static,
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        E: Environment + scale_info::TypeInfo + 'static,
+        E::BlockNumber: StorageLayout,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                StructLayout::new(
+                    "Checkpoints",
+                    [
+                        FieldLayout::new(
+                            "len",
+                            <u32 as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                        FieldLayout::new(
+                            "checkpoint",
+                            <(E::BlockNumber, V) as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                    ],
+                ),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    type Balances = Checkpoints<u8, u128, DefaultEnvironment, ManualKey<123>>;
+
+    #[test]
+    fn push_and_latest_work() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut balances = Balances::new();
+            assert!(balances.is_empty(&1));
+            assert_eq!(balances.latest(&1), None);
+
+            balances.push(&1, 10);
+            // Pushing in the same block overwrites the checkpoint.
+            balances.push(&1, 20);
+            assert_eq!(balances.len(&1), 1);
+            assert_eq!(balances.latest(&1), Some(20));
+
+            ink_env::test::advance_block::<DefaultEnvironment>();
+            balances.push(&1, 30);
+            assert_eq!(balances.len(&1), 2);
+            assert_eq!(balances.checkpoint(&1, 0), Some((0, 20)));
+            assert_eq!(balances.checkpoint(&1, 1), Some((1, 30)));
+            assert_eq!(balances.latest(&1), Some(30));
+            // Other keys are not affected.
+            assert_eq!(balances.latest(&2), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn value_at_finds_past_values() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut balances = Balances::new();
+            // Record a checkpoint at every second block.
+            for value in 1..=9 {
+                ink_env::test::advance_block::<DefaultEnvironment>();
+                ink_env::test::advance_block::<DefaultEnvironment>();
+                balances.push(&1, value);
+            }
+
+            assert_eq!(balances.value_at(&1, 0), None);
+            assert_eq!(balances.value_at(&1, 1), None);
+            for block in 2..=18 {
+                assert_eq!(balances.value_at(&1, block), Some(block as u128 / 2));
+            }
+            assert_eq!(balances.value_at(&1, 100), Some(9));
+            assert_eq!(balances.value_at(&2, 100), None);
+            // The length and the checkpoints.
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(10)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A monotonic counter in contract storage, e.g. for nonces or IDs.
+//!
+//! # Note
+//!
+//! Like the [`Lazy`](crate::Lazy), the counter doesn't actually "own" any data.
+//! The current value is stored under the root key and only written once the counter
+//! is advanced for the first time.
+
+use crate::{
+    traits::{
+        AutoKey,
+        StorableHint,
+        StorageKey,
+    },
+    ArithmeticError,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// A counter handing out increasing `u64` values, e.g. nonces or IDs.
+///
+/// The counter starts at zero and every call to [`next`](Self::next) increments and
+/// persists it before returning the new value, so zero is never handed out and can
+/// be used to mean "none".
+///
+/// # Important
+///
+/// The counter requires its own pre-defined storage key where to store the value.
+/// By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. Libraries which need to
+/// place the counter deterministically, independent of the contract's storage
+/// layout, specify the key using [`ManualKey`](crate::traits::ManualKey), e.g.
+/// `Counter<ManualKey<0x6e6f6e63>>`.
+///
+/// This is an example of how you can use the counter:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     collections::Counter,
+///     traits::ManualKey,
+///     Mapping,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Registry {
+///     next_id: Counter<ManualKey<0x6e657874>>,
+///     owners: Mapping<u64, AccountId>,
+/// }
+///
+/// impl Registry {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn register(&mut self) -> u64 {
+///         let id = self.next_id.next();
+///         self.owners.insert(id, &self.env().caller());
+///         id
+///     }
+///
+///     #[ink(message)]
+///     pub fn registered(&self) -> u64 {
+///         self.next_id.current()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Counter<KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> KeyType>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<KeyType> Default for Counter<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<KeyType> Counter<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new counter, which is zero unless it was advanced before.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<KeyType> ::core::fmt::Debug for Counter<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Counter")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<KeyType> Counter<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Returns the current value, i.e. the value last returned by
+    /// [`next`](Self::next) or zero.
+    #[inline]
+    pub fn current(&self) -> u64 {
+        ink_env::get_contract_storage::<Key, u64>(&KeyType::KEY)
+            .unwrap_or_else(|error| panic!("Failed to get value of Counter: {:?}", error))
+            .unwrap_or(0)
+    }
+
+    /// Increments the counter and returns the new value.
+    ///
+    /// # Panics
+    ///
+    /// If the counter would overflow `u64::MAX`.
+    // The counter is no iterator, since advancing it writes to the storage.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u64 {
+        self.checked_next()
+            .unwrap_or_else(|_| panic!("Counter overflowed"))
+    }
+
+    /// Increments the counter and returns the new value.
+    ///
+    /// # Errors
+    ///
+    /// If the counter would overflow `u64::MAX`. The stored value is not changed then.
+    pub fn checked_next(&mut self) -> Result<u64, ArithmeticError> {
+        let value = self
+            .current()
+            .checked_add(1)
+            .ok_or(ArithmeticError::Overflow)?;
+        ink_env::set_contract_storage(&KeyType::KEY, &value);
+        Ok(value)
+    }
+
+    /// Increments the counter and returns the new value.
+    ///
+    /// Once the counter reached `u64::MAX` it stays there, so the same value is
+    /// returned again.
+    pub fn saturating_next(&mut self) -> u64 {
+        let value = self.current().saturating_add(1);
+        ink_env::set_contract_storage(&KeyType::KEY, &value);
+        value
+    }
+}
+
+impl<KeyType> Storable for Counter<KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<Key, InnerKey> StorableHint<Key> for Counter<InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Counter<Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<KeyType> StorageKey for Counter<KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<KeyType> StorageLayout for Counter<KeyType>
+    where
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <u64 as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn next_increments_and_persists() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut counter: Counter<ManualKey<123>> = Counter::new();
+            assert_eq!(counter.current(), 0);
+
+            assert_eq!(counter.next(), 1);
+            assert_eq!(counter.next(), 2);
+            assert_eq!(counter.current(), 2);
+
+            // Another handle with the same key sees the same value.
+            let mut other: Counter<ManualKey<123>> = Counter::new();
+            assert_eq!(other.next(), 3);
+            assert_eq!(counter.current(), 3);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn overflow_is_handled() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut counter: Counter<ManualKey<123>> = Counter::new();
+            ink_env::set_contract_storage(&123u32, &(u64::MAX - 1));
+
+            assert_eq!(counter.checked_next(), Ok(u64::MAX));
+            assert_eq!(counter.checked_next(), Err(ArithmeticError::Overflow));
+            assert_eq!(counter.current(), u64::MAX);
+            assert_eq!(counter.saturating_next(), u64::MAX);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter overflowed")]
+    fn next_panics_on_overflow() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut counter: Counter<ManualKey<123>> = Counter::new();
+            ink_env::set_contract_storage(&123u32, &u64::MAX);
+            counter.next();
+            Ok(())
+        })
+        .unwrap()
+    }
+}
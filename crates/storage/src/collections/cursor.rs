@@ -0,0 +1,106 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cursors to walk large collections in pages across several calls.
+//!
+//! Iterating over all elements of a large collection in a single call may exceed
+//! the gas limit. The paginated `iter_from` methods of the collections return a
+//! [`Page`] of elements together with the [`Cursor`] of the next page instead.
+//! Both are encodable so that a message can return the page and a caller, e.g. an
+//! off-chain indexer or a batch job, can pass the cursor back to fetch the next one.
+
+use ink_prelude::vec::Vec;
+use scale::{
+    Decode,
+    Encode,
+};
+
+/// The position of a collection to resume a paginated iteration from.
+///
+/// The position `P` depends on the collection, e.g. an index for a
+/// [`StorageVec`](crate::collections::StorageVec) or a key for a
+/// [`StorageBTreeMap`](crate::collections::StorageBTreeMap). It is opaque to the caller, who
+/// either starts with [`Cursor::start`] or continues with the cursor of the
+/// previous [`Page`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Cursor<P = u32>(Option<P>);
+
+impl<P> Cursor<P> {
+    /// Returns the cursor of the first page.
+    pub const fn start() -> Self {
+        Self(None)
+    }
+
+    /// Returns the cursor at `position`.
+    pub(crate) fn at(position: P) -> Self {
+        Self(Some(position))
+    }
+
+    /// Returns the position, `None` for the first page.
+    pub(crate) fn position(self) -> Option<P> {
+        self.0
+    }
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<P> Default for Cursor<P> {
+    fn default() -> Self {
+        Self::start()
+    }
+}
+
+/// A page of elements of a paginated iteration.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Page<T, P = u32> {
+    /// The elements of the page.
+    pub items: Vec<T>,
+    /// The cursor of the next page, `None` if there are no further elements.
+    pub next: Option<Cursor<P>>,
+}
+
+impl<T, P> Page<T, P> {
+    /// Returns the page of the first `limit` elements of `iter` with the cursor of
+    /// the element following them.
+    ///
+    /// This loads one element beyond the page from `iter` to determine whether
+    /// there is a next page.
+    pub(crate) fn collect<I, F>(mut iter: I, limit: u32, position: F) -> Self
+    where
+        I: Iterator<Item = T>,
+        F: FnOnce(T) -> P,
+    {
+        let items = iter.by_ref().take(limit as usize).collect();
+        let next = iter.next().map(|item| Cursor::at(position(item)));
+        Self { items, next }
+    }
+}
+
+impl<T> Page<T> {
+    /// Returns the page of at most `limit` elements from the index of `cursor` on
+    /// of a collection with `len` elements which loads the element at an index
+    /// with `get`.
+    pub(crate) fn from_indices<F>(cursor: Cursor, limit: u32, len: u32, get: F) -> Self
+    where
+        F: FnMut(u32) -> Option<T>,
+    {
+        let start = cursor.position().unwrap_or(0);
+        let end = len.min(start.saturating_add(limit));
+        Self {
+            items: (start..end).filter_map(get).collect(),
+            next: (end < len).then(|| Cursor::at(end)),
+        }
+    }
+}
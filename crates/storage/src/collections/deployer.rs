@@ -0,0 +1,458 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A factory which deploys child contracts from a stored code hash.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), the deployer doesn't actually "own" any data.
+//! The code hash of the children is stored under the root key of the deployer while
+//! the deployed children are stored under the root key combined with their index.
+
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::{
+    call::{
+        build_create,
+        ExecutionInput,
+        FromAccountId,
+    },
+    Environment,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage slot of the number of deployed children.
+const CHILDREN_LEN: u8 = 0;
+/// The storage slot of the deployed children indexed by the order of their deployment.
+const CHILDREN: u8 = 1;
+/// The storage slot of the indices of the deployed children.
+const CHILD_INDICES: u8 = 2;
+
+/// A factory which deploys child contracts from a stored code hash.
+///
+/// Launchpads, DEX pair factories and similar contracts all deploy instances of
+/// the same child contract. The deployer stores the code hash of the child,
+/// instantiates children via [`Deployer::deploy_child`] and keeps track of all
+/// deployed children in an iterable set. Events about deployments are up to the
+/// contract, which declares them along with its other events.
+///
+/// # Important
+///
+/// The deployer requires its own pre-defined storage key in order to determine where
+/// it stores its data. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can specify
+/// a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the deployer:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::{
+///     env::call::{
+///         ExecutionInput,
+///         Selector,
+///     },
+///     storage::collections::Deployer,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct PairFactory {
+///     pairs: Deployer<Environment>,
+/// }
+///
+/// #[ink(event)]
+/// pub struct PairCreated {
+///     #[ink(topic)]
+///     pair: AccountId,
+/// }
+///
+/// impl PairFactory {
+///     #[ink(constructor)]
+///     pub fn new(pair_code_hash: Hash) -> Self {
+///         let mut pairs = Deployer::new();
+///         pairs.set_code_hash(&pair_code_hash);
+///         Self { pairs }
+///     }
+///
+///     #[ink(message)]
+///     pub fn create_pair(&mut self, token_a: AccountId, token_b: AccountId) -> AccountId {
+///         let salt = (token_a, token_b);
+///         let pair = self
+///             .pairs
+///             .deploy_child(
+///                 ExecutionInput::new(Selector::new(ink::selector_bytes!("new")))
+///                     .push_arg(token_a)
+///                     .push_arg(token_b),
+///                 scale::Encode::encode(&salt),
+///                 0,
+///             )
+///             .expect("failed to deploy pair");
+///         self.env().emit_event(PairCreated { pair });
+///         pair
+///     }
+///
+///     #[ink(message)]
+///     pub fn pairs(&self) -> Vec<AccountId> {
+///         self.pairs.children().collect()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Deployer<E: Environment, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (E, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<E, KeyType> Default for Deployer<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, KeyType> Deployer<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Creates a new deployer without a code hash and without children.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<E, KeyType> core::fmt::Debug for Deployer<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Deployer")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<E, KeyType> Deployer<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Returns the code hash the children are deployed from if any.
+    #[inline]
+    pub fn code_hash(&self) -> Option<E::Hash> {
+        ink_env::get_contract_storage::<Key, E::Hash>(&KeyType::KEY).unwrap_or_else(
+            |error| panic!("Failed to get code hash of Deployer: {:?}", error),
+        )
+    }
+
+    /// Sets the code hash the children are deployed from.
+    ///
+    /// Children which have been deployed before are not affected.
+    #[inline]
+    pub fn set_code_hash(&mut self, code_hash: &E::Hash) {
+        ink_env::set_contract_storage::<Key, E::Hash>(&KeyType::KEY, code_hash);
+    }
+
+    /// Returns the number of deployed children.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<_, u32>(&(&KeyType::KEY, CHILDREN_LEN))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of Deployer: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if no children have been deployed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the child deployed at position `index` if any.
+    ///
+    /// Children are ordered by their deployment.
+    #[inline]
+    pub fn child(&self, index: u32) -> Option<E::AccountId> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, CHILDREN, index)).unwrap_or_else(
+            |error| panic!("Failed to get child of Deployer: {:?}", error),
+        )
+    }
+
+    /// Returns an iterator over all deployed children in the order of their deployment.
+    pub fn children(&self) -> impl Iterator<Item = E::AccountId> + '_ {
+        (0..self.len()).filter_map(|index| self.child(index))
+    }
+
+    /// Returns `true` if `account_id` has been deployed by the deployer.
+    #[inline]
+    pub fn is_child(&self, account_id: &E::AccountId) -> bool {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, CHILD_INDICES, account_id))
+            .is_some()
+    }
+
+    /// Records `account_id` as a child of the deployer.
+    ///
+    /// This is done automatically by [`Deployer::deploy_child`] but can be used to
+    /// keep track of children that have been deployed by other means, e.g. before
+    /// the contract started using a deployer.
+    ///
+    /// Returns `false` if `account_id` already is a child of the deployer.
+    pub fn record_child(&mut self, account_id: &E::AccountId) -> bool {
+        if self.is_child(account_id) {
+            return false
+        }
+        let index = self.len();
+        ink_env::set_contract_storage(&(&KeyType::KEY, CHILDREN, index), account_id);
+        ink_env::set_contract_storage(
+            &(&KeyType::KEY, CHILD_INDICES, account_id),
+            &index,
+        );
+        ink_env::set_contract_storage(&(&KeyType::KEY, CHILDREN_LEN), &(index + 1));
+        true
+    }
+
+    /// Deploys a new child from the stored code hash and returns its account ID.
+    ///
+    /// The child is instantiated by calling the constructor given by `exec_input`
+    /// with the transferred `endowment`. The account ID of the child is derived
+    /// from the `salt`. The child is recorded, emitting an event about it is up to
+    /// the caller.
+    ///
+    /// # Errors
+    ///
+    /// - If no code hash has been set.
+    /// - If the instantiation of the child failed.
+    pub fn deploy_child<Args, Salt>(
+        &mut self,
+        exec_input: ExecutionInput<Args>,
+        salt: Salt,
+        endowment: E::Balance,
+    ) -> ink_env::Result<E::AccountId>
+    where
+        Args: scale::Encode,
+        Salt: AsRef<[u8]>,
+    {
+        let code_hash = self.code_hash().ok_or(ink_env::Error::CodeNotFound)?;
+        let params = build_create::<E, Child<E>>()
+            .code_hash(code_hash)
+            .endowment(endowment)
+            .exec_input(exec_input)
+            .salt_bytes(salt)
+            .params();
+        let child = params.instantiate()?.0;
+        self.record_child(&child);
+        Ok(child)
+    }
+}
+
+/// The account ID of a child contract instantiated by the [`Deployer`].
+struct Child<E: Environment>(E::AccountId);
+
+impl<E> FromAccountId<E> for Child<E>
+where
+    E: Environment,
+{
+    fn from_account_id(account_id: E::AccountId) -> Self {
+        Self(account_id)
+    }
+}
+
+impl<E, KeyType> Storable for Deployer<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<E, Key, InnerKey> StorableHint<Key> for Deployer<E, InnerKey>
+where
+    E: Environment,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Deployer<E, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<E, KeyType> StorageKey for Deployer<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        RootLayout,
+        StructLayout,
+    };
+
+    impl<E, KeyType> StorageLayout for Deployer<E, KeyType>
+    where
+        E: Environment + scale_info::TypeInfo + 'static,
+        E::Hash: StorageLayout,
+        E::AccountId: StorageLayout,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                StructLayout::new(
+                    "Deployer",
+                    [
+                        FieldLayout::new(
+                            "code_hash",
+                            <E::Hash as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                        FieldLayout::new(
+                            "len",
+                            <u32 as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                        FieldLayout::new(
+                            "children",
+                            <E::AccountId as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                        FieldLayout::new(
+                            "child_indices",
+                            <u32 as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                    ],
+                ),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+    use ink_env::DefaultEnvironment;
+
+    type Factory = Deployer<DefaultEnvironment, ManualKey<42>>;
+
+    #[test]
+    fn code_hash_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut deployer = Factory::new();
+            assert_eq!(deployer.code_hash(), None);
+
+            deployer.set_code_hash(&[0x01; 32].into());
+            assert_eq!(deployer.code_hash(), Some([0x01; 32].into()));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn record_child_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut deployer = Factory::new();
+            assert!(deployer.is_empty());
+
+            // When
+            assert!(deployer.record_child(&accounts.bob));
+            assert!(deployer.record_child(&accounts.charlie));
+            assert!(!deployer.record_child(&accounts.bob));
+
+            // Then
+            assert_eq!(deployer.len(), 2);
+            assert_eq!(deployer.child(1), Some(accounts.charlie));
+            assert_eq!(deployer.child(2), None);
+            assert!(deployer.is_child(&accounts.bob));
+            assert!(!deployer.is_child(&accounts.django));
+            assert_eq!(
+                deployer.children().collect::<Vec<_>>(),
+                vec![accounts.bob, accounts.charlie]
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn deploy_child_without_code_hash_fails() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut deployer = Factory::new();
+            let exec_input = ExecutionInput::new(ink_env::call::Selector::new([0; 4]));
+
+            assert_eq!(
+                deployer.deploy_child(exec_input, [0u8; 4], 0),
+                Err(ink_env::Error::CodeNotFound)
+            );
+            assert!(deployer.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn layout_describes_children() {
+        use crate::traits::StorageLayout;
+        use ink_metadata::layout::Layout;
+
+        let fields = match <Factory as StorageLayout>::layout(&0) {
+            Layout::Root(root) => {
+                match root.layout() {
+                    Layout::Struct(layout) => {
+                        layout
+                            .fields()
+                            .iter()
+                            .map(|field| *field.name())
+                            .collect::<Vec<_>>()
+                    }
+                    layout => panic!("expected a struct layout, got {:?}", layout),
+                }
+            }
+            layout => panic!("expected a root layout, got {:?}", layout),
+        };
+        assert_eq!(fields, ["code_hash", "len", "children", "child_indices"]);
+    }
+}
@@ -0,0 +1,497 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A double-ended queue in contract storage which loads its elements lazily.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this queue doesn't actually "own" any data.
+//! The indices of the first and past the last element are stored under the root key
+//! of the queue while every element is stored under the root key combined with its
+//! index. The indices wrap around, so that elements can be pushed to the front
+//! without moving the other elements.
+
+use super::cursor::{
+    Cursor,
+    Page,
+};
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Decode,
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The positions of the elements of a [`StorageDeque`].
+#[derive(Debug, Default, Clone, Copy, Encode, Decode)]
+struct Header {
+    /// The index of the first element.
+    head: u32,
+    /// The index past the last element.
+    tail: u32,
+}
+
+impl Header {
+    fn len(&self) -> u32 {
+        self.tail.wrapping_sub(self.head)
+    }
+}
+
+/// A double-ended queue of elements directly in contract storage.
+///
+/// Elements can be pushed and popped at both ends without loading any other
+/// element, e.g. to process pending withdrawals in FIFO order. Every element is
+/// stored in its own storage cell under the root key combined with its index.
+///
+/// # Important
+///
+/// The queue requires its own pre-defined storage key where to store its elements.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the queue:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::StorageDeque;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Withdrawals {
+///     pending: StorageDeque<(AccountId, Balance)>,
+/// }
+///
+/// impl Withdrawals {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn request(&mut self, amount: Balance) {
+///         self.pending.push_back(&(self.env().caller(), amount));
+///     }
+///
+///     #[ink(message)]
+///     pub fn process_next(&mut self) -> Option<(AccountId, Balance)> {
+///         self.pending.pop_front()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageDeque<V: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<V, KeyType> Default for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageDeque`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageDeque")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<V, KeyType> StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.header().len()
+    }
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the `value` to the back of the queue.
+    ///
+    /// # Panics
+    ///
+    /// If the queue already contains `u32::MAX` elements.
+    #[inline]
+    pub fn push_back<R>(&mut self, value: &R)
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let mut header = self.header();
+        Self::ensure_capacity(&header);
+        ink_env::set_contract_storage(&(&KeyType::KEY, header.tail), value);
+        header.tail = header.tail.wrapping_add(1);
+        self.set_header(&header);
+    }
+
+    /// Prepends the `value` to the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// If the queue already contains `u32::MAX` elements.
+    #[inline]
+    pub fn push_front<R>(&mut self, value: &R)
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let mut header = self.header();
+        Self::ensure_capacity(&header);
+        header.head = header.head.wrapping_sub(1);
+        ink_env::set_contract_storage(&(&KeyType::KEY, header.head), value);
+        self.set_header(&header);
+    }
+
+    /// Removes the first element from the queue and returns it.
+    ///
+    /// Returns `None` if the queue is empty.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<V> {
+        let mut header = self.header();
+        if header.len() == 0 {
+            return None
+        }
+        let value = self.take(header.head);
+        header.head = header.head.wrapping_add(1);
+        self.set_header(&header);
+        value
+    }
+
+    /// Removes the last element from the queue and returns it.
+    ///
+    /// Returns `None` if the queue is empty.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<V> {
+        let mut header = self.header();
+        if header.len() == 0 {
+            return None
+        }
+        header.tail = header.tail.wrapping_sub(1);
+        let value = self.take(header.tail);
+        self.set_header(&header);
+        value
+    }
+
+    /// Returns the first element if any.
+    #[inline]
+    pub fn front(&self) -> Option<V> {
+        self.get(0)
+    }
+
+    /// Returns the last element if any.
+    #[inline]
+    pub fn back(&self) -> Option<V> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Returns the element at position `index` counted from the front.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<V> {
+        let header = self.header();
+        if index >= header.len() {
+            return None
+        }
+        ink_env::get_contract_storage(&(&KeyType::KEY, header.head.wrapping_add(index)))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get value in StorageDeque: {:?}", error)
+            })
+    }
+
+    /// Returns an iterator over all elements from front to back.
+    ///
+    /// Every element is loaded from storage only once the iterator reaches it.
+    pub fn iter(&self) -> impl Iterator<Item = V> + '_ {
+        let header = self.header();
+        (0..header.len()).filter_map(move |index| {
+            ink_env::get_contract_storage(&(
+                &KeyType::KEY,
+                header.head.wrapping_add(index),
+            ))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get value in StorageDeque: {:?}", error)
+            })
+        })
+    }
+
+    /// Returns the page of at most `limit` elements from the position of `cursor`
+    /// on together with the cursor of the next page.
+    ///
+    /// The cursor refers to an index relative to the front, so popping elements
+    /// from the front between fetching two pages skips as many elements.
+    pub fn iter_from(&self, cursor: Cursor, limit: u32) -> Page<V> {
+        Page::from_indices(cursor, limit, self.len(), |index| self.get(index))
+    }
+
+    /// Removes all elements from the queue.
+    ///
+    /// # Note
+    ///
+    /// This clears the storage cell of every element and hence is as expensive
+    /// as popping all elements.
+    pub fn clear(&mut self) {
+        let header = self.header();
+        for index in 0..header.len() {
+            ink_env::clear_contract_storage(&(
+                &KeyType::KEY,
+                header.head.wrapping_add(index),
+            ));
+        }
+        ink_env::clear_contract_storage(&KeyType::KEY);
+    }
+
+    /// Takes the element at the storage `index` out of storage.
+    fn take(&mut self, index: u32) -> Option<V> {
+        ink_env::take_contract_storage(&(&KeyType::KEY, index)).unwrap_or_else(|error| {
+            panic!("Failed to pop value in StorageDeque: {:?}", error)
+        })
+    }
+
+    /// Panics if no more elements can be pushed.
+    fn ensure_capacity(header: &Header) {
+        assert!(
+            header.len() < u32::MAX,
+            "cannot push more than u32::MAX elements to StorageDeque"
+        );
+    }
+
+    fn header(&self) -> Header {
+        ink_env::get_contract_storage::<Key, Header>(&KeyType::KEY)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get header of StorageDeque: {:?}", error)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Stores the `header`, clearing it once the queue is empty.
+    fn set_header(&mut self, header: &Header) {
+        if header.len() == 0 {
+            ink_env::clear_contract_storage(&KeyType::KEY);
+        } else {
+            ink_env::set_contract_storage::<Key, Header>(&KeyType::KEY, header);
+        }
+    }
+}
+
+impl<V, KeyType> Storable for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageDeque<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageDeque<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageDeque<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageDeque<V, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn fifo_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut deque: StorageDeque<u8> = StorageDeque::new();
+            assert!(deque.is_empty());
+            assert_eq!(deque.pop_front(), None);
+            assert_eq!(deque.pop_back(), None);
+
+            deque.push_back(&1);
+            deque.push_back(&2);
+            deque.push_back(&3);
+            assert_eq!(deque.len(), 3);
+            assert_eq!(deque.front(), Some(1));
+            assert_eq!(deque.back(), Some(3));
+
+            assert_eq!(deque.pop_front(), Some(1));
+            assert_eq!(deque.pop_front(), Some(2));
+            assert_eq!(deque.pop_front(), Some(3));
+            assert_eq!(deque.pop_front(), None);
+            assert!(deque.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn both_ends_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut deque: StorageDeque<u32, ManualKey<123>> = StorageDeque::new();
+
+            // When
+            // The front wraps around below index zero.
+            deque.push_front(&2);
+            deque.push_front(&1);
+            deque.push_back(&3);
+            deque.push_back(&4);
+
+            // Then
+            let deque: StorageDeque<u32, ManualKey<123>> = StorageDeque::new();
+            assert_eq!(deque.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+            assert_eq!(deque.get(1), Some(2));
+            assert_eq!(deque.get(4), None);
+
+            // When
+            let mut deque = deque;
+            assert_eq!(deque.pop_back(), Some(4));
+            assert_eq!(deque.pop_front(), Some(1));
+
+            // Then
+            assert_eq!(deque.iter().collect::<Vec<_>>(), vec![2, 3]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn only_touched_elements_are_loaded() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut deque: StorageDeque<u8> = StorageDeque::new();
+            for value in 0..10 {
+                deque.push_back(&value);
+            }
+            let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+
+            // When
+            assert_eq!(deque.pop_front(), Some(0));
+
+            // Then
+            let (reads_after, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+            // Only the header is read while the popped element is taken out of storage.
+            assert_eq!(reads_after - reads, 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut deque: StorageDeque<u8> = StorageDeque::new();
+            deque.push_front(&1);
+            deque.push_back(&2);
+
+            // When
+            deque.clear();
+
+            // Then
+            assert!(deque.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,496 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A two-level mapping to contract storage.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this mapping doesn't actually "own" any data.
+//! The root key of the mapping combined with a first key forms the root of the
+//! second level, under which the values and the inserted second keys are stored
+//! combined with the respective second key or index.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage slot of the number of second keys per first key.
+const LEN: u8 = 0;
+/// The storage slot of the second keys indexed by their position.
+const KEYS: u8 = 1;
+/// The storage slot of the values.
+const VALUES: u8 = 2;
+/// The storage slot of the positions of the second keys.
+const INDICES: u8 = 3;
+
+/// A mapping of pairs of keys to values directly into contract storage.
+///
+/// In contrast to a [`Mapping`](crate::Mapping) with tuple keys, the entries are
+/// grouped by their first key, so that all entries of a first key can be enumerated
+/// and removed at once, e.g. all allowances of an owner. This comes at the cost of
+/// two additional storage cells per entry which are written on insertion of a new
+/// pair of keys and on removal.
+///
+/// # Important
+///
+/// The mapping requires its own pre-defined storage key where to store values. By default,
+/// the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey) during compilation.
+/// However, anyone can specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the mapping:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::DoubleMap;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct MyToken {
+///     /// The amount a spender may transfer on behalf of an owner.
+///     allowances: DoubleMap<AccountId, AccountId, Balance>,
+/// }
+///
+/// impl MyToken {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn approve(&mut self, spender: AccountId, value: Balance) {
+///         self.allowances.insert(self.env().caller(), spender, &value);
+///     }
+///
+///     #[ink(message)]
+///     pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+///         self.allowances.get(owner, spender).unwrap_or_default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn revoke_all(&mut self) -> u32 {
+///         self.allowances.remove_prefix(self.env().caller())
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct DoubleMap<K1, K2, V: Packed, KeyType: StorageKey = AutoKey> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K1, K2, V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<K1, K2, V, KeyType> Default for DoubleMap<K1, K2, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K1, K2, V, KeyType> DoubleMap<K1, K2, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `DoubleMap`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K1, K2, V, KeyType> ::core::fmt::Debug for DoubleMap<K1, K2, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("DoubleMap")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<K1, K2, V, KeyType> DoubleMap<K1, K2, V, KeyType>
+where
+    K1: Packed,
+    K2: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Insert the given `value` at the pair of keys to the contract storage.
+    ///
+    /// Returns the size in bytes of the pre-existing value at the specified keys if any.
+    #[inline]
+    pub fn insert<Q1, Q2, R>(&mut self, key1: Q1, key2: Q2, value: &R) -> Option<u32>
+    where
+        Q1: scale::EncodeLike<K1>,
+        Q2: scale::EncodeLike<K2>,
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let previous_size =
+            ink_env::set_contract_storage(&(&KeyType::KEY, VALUES, &key1, &key2), value);
+        if previous_size.is_none() {
+            let index = self.stored_len(&key1);
+            ink_env::set_contract_storage(
+                &(&KeyType::KEY, INDICES, &key1, &key2),
+                &index,
+            );
+            let key2 = <K2 as scale::Decode>::decode(&mut &key2.encode()[..])
+                .unwrap_or_else(|error| {
+                    panic!("Failed to decode key of DoubleMap: {:?}", error)
+                });
+            ink_env::set_contract_storage(&(&KeyType::KEY, KEYS, &key1, index), &key2);
+            ink_env::set_contract_storage(&(&KeyType::KEY, LEN, &key1), &(index + 1));
+        }
+        previous_size
+    }
+
+    /// Get the `value` at the pair of keys from the contract storage.
+    ///
+    /// Returns `None` if no `value` exists at the given keys.
+    #[inline]
+    pub fn get<Q1, Q2>(&self, key1: Q1, key2: Q2) -> Option<V>
+    where
+        Q1: scale::EncodeLike<K1>,
+        Q2: scale::EncodeLike<K2>,
+    {
+        self.value_at(key1, key2)
+    }
+
+    /// Removes the `value` at the pair of keys, returning the previous `value` from
+    /// storage.
+    ///
+    /// Returns `None` if no `value` exists at the given keys.
+    #[inline]
+    pub fn take<Q1, Q2>(&mut self, key1: Q1, key2: Q2) -> Option<V>
+    where
+        Q1: scale::EncodeLike<K1>,
+        Q2: scale::EncodeLike<K2>,
+    {
+        let value = self.value_at(&key1, &key2);
+        if value.is_some() {
+            self.remove(key1, key2);
+        }
+        value
+    }
+
+    /// Checks if a value is stored at the pair of keys in the contract storage.
+    #[inline]
+    pub fn contains<Q1, Q2>(&self, key1: Q1, key2: Q2) -> bool
+    where
+        Q1: scale::EncodeLike<K1>,
+        Q2: scale::EncodeLike<K2>,
+    {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, VALUES, key1, key2)).is_some()
+    }
+
+    /// Clears the value at the pair of keys from storage.
+    #[inline]
+    pub fn remove<Q1, Q2>(&mut self, key1: Q1, key2: Q2)
+    where
+        Q1: scale::EncodeLike<K1>,
+        Q2: scale::EncodeLike<K2>,
+    {
+        if ink_env::clear_contract_storage(&(&KeyType::KEY, VALUES, &key1, &key2))
+            .is_some()
+        {
+            self.remove_key(key1, key2);
+        }
+    }
+
+    /// Returns the number of entries with the first key `key1`.
+    #[inline]
+    pub fn len_of<Q1>(&self, key1: Q1) -> u32
+    where
+        Q1: scale::EncodeLike<K1>,
+    {
+        self.stored_len(key1)
+    }
+
+    /// Returns an iterator over the second keys of all entries with the first key `key1`.
+    ///
+    /// Every key is loaded from storage only once the iterator reaches it. The order
+    /// of the keys is only stable as long as no entries with the first key are removed.
+    pub fn keys_of<'a, Q1>(&'a self, key1: Q1) -> impl Iterator<Item = K2> + 'a
+    where
+        Q1: scale::EncodeLike<K1> + 'a,
+    {
+        (0..self.stored_len(&key1)).filter_map(move |index| self.key_at(&key1, index))
+    }
+
+    /// Returns an iterator over the second keys and values of all entries with the
+    /// first key `key1`.
+    ///
+    /// Every entry is loaded from storage only once the iterator reaches it.
+    pub fn iter_prefix<'a, Q1>(&'a self, key1: Q1) -> impl Iterator<Item = (K2, V)> + 'a
+    where
+        Q1: scale::EncodeLike<K1> + 'a,
+    {
+        (0..self.stored_len(&key1)).filter_map(move |index| {
+            let key2 = self.key_at(&key1, index)?;
+            let value = self.value_at(&key1, &key2)?;
+            Some((key2, value))
+        })
+    }
+
+    /// Clears all entries with the first key `key1` from storage.
+    ///
+    /// Returns the number of removed entries.
+    ///
+    /// # Note
+    ///
+    /// This clears three storage cells for every entry and hence is as expensive
+    /// as removing all entries one by one.
+    pub fn remove_prefix<Q1>(&mut self, key1: Q1) -> u32
+    where
+        Q1: scale::EncodeLike<K1>,
+    {
+        let len = self.stored_len(&key1);
+        for index in 0..len {
+            let key2 = self
+                .key_at(&key1, index)
+                .expect("the keys of a DoubleMap within its length must be stored");
+            ink_env::clear_contract_storage(&(&KeyType::KEY, VALUES, &key1, &key2));
+            ink_env::clear_contract_storage(&(&KeyType::KEY, INDICES, &key1, &key2));
+            ink_env::clear_contract_storage(&(&KeyType::KEY, KEYS, &key1, index));
+        }
+        ink_env::clear_contract_storage(&(&KeyType::KEY, LEN, &key1));
+        len
+    }
+
+    /// Returns the number of entries with the encoded first key `key1`.
+    fn stored_len<Q1>(&self, key1: Q1) -> u32
+    where
+        Q1: Encode,
+    {
+        ink_env::get_contract_storage::<_, u32>(&(&KeyType::KEY, LEN, key1))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of DoubleMap: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the value at the encoded pair of keys if any.
+    fn value_at<Q1, Q2>(&self, key1: Q1, key2: Q2) -> Option<V>
+    where
+        Q1: Encode,
+        Q2: Encode,
+    {
+        ink_env::get_contract_storage(&(&KeyType::KEY, VALUES, key1, key2))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get value in DoubleMap: {:?}", error)
+            })
+    }
+
+    /// Returns the second key at position `index` of the entries with the first key
+    /// `key1` if any.
+    fn key_at<Q1>(&self, key1: Q1, index: u32) -> Option<K2>
+    where
+        Q1: Encode,
+    {
+        ink_env::get_contract_storage(&(&KeyType::KEY, KEYS, key1, index))
+            .unwrap_or_else(|error| panic!("Failed to get key in DoubleMap: {:?}", error))
+    }
+
+    /// Removes `key2` from the tracked keys of `key1` by moving the last key into
+    /// its position.
+    fn remove_key<Q1, Q2>(&mut self, key1: Q1, key2: Q2)
+    where
+        Q1: Encode,
+        Q2: Encode,
+    {
+        let index_key = (&KeyType::KEY, INDICES, &key1, key2);
+        let index = ink_env::get_contract_storage::<_, u32>(&index_key)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get index in DoubleMap: {:?}", error)
+            })
+            .expect("the index of an existing entry must be stored");
+        ink_env::clear_contract_storage(&index_key);
+        let last = self.stored_len(&key1) - 1;
+        if index != last {
+            let last_key = self
+                .key_at(&key1, last)
+                .expect("the last key of a non-empty prefix must be stored");
+            ink_env::set_contract_storage(
+                &(&KeyType::KEY, KEYS, &key1, index),
+                &last_key,
+            );
+            ink_env::set_contract_storage(
+                &(&KeyType::KEY, INDICES, &key1, &last_key),
+                &index,
+            );
+        }
+        ink_env::clear_contract_storage(&(&KeyType::KEY, KEYS, &key1, last));
+        if last == 0 {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, LEN, &key1));
+        } else {
+            ink_env::set_contract_storage(&(&KeyType::KEY, LEN, &key1), &last);
+        }
+    }
+}
+
+impl<K1, K2, V, KeyType> Storable for DoubleMap<K1, K2, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<K1, K2, V, Key, InnerKey> StorableHint<Key> for DoubleMap<K1, K2, V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = DoubleMap<K1, K2, V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<K1, K2, V, KeyType> StorageKey for DoubleMap<K1, K2, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<K1, K2, V, KeyType> StorageLayout for DoubleMap<K1, K2, V, KeyType>
+    where
+        K1: scale_info::TypeInfo + 'static,
+        K2: scale_info::TypeInfo + 'static,
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn insert_and_get_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut map: DoubleMap<u8, u8, u8> = DoubleMap::new();
+            assert_eq!(map.insert(1, 2, &3), None);
+            assert_eq!(map.insert(1, 2, &4), Some(1));
+            assert_eq!(map.get(1, 2), Some(4));
+            assert_eq!(map.get(2, 1), None);
+            assert!(map.contains(1, 2));
+            assert_eq!(map.len_of(1), 1);
+            assert_eq!(map.len_of(2), 0);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_and_take_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut map: DoubleMap<u8, u8, u32, ManualKey<123>> = DoubleMap::new();
+            map.insert(1, 1, &11);
+            map.insert(1, 2, &12);
+            map.insert(1, 3, &13);
+
+            // When
+            map.remove(1, 1);
+            map.remove(1, 4);
+
+            // Then
+            assert_eq!(map.get(1, 1), None);
+            assert_eq!(map.take(1, 3), Some(13));
+            assert_eq!(map.take(1, 3), None);
+            assert_eq!(map.len_of(1), 1);
+            assert_eq!(map.iter_prefix(1).collect::<Vec<_>>(), vec![(2, 12)]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_prefix_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut map: DoubleMap<u8, u8, u32> = DoubleMap::new();
+            for key2 in 0..5 {
+                map.insert(1, key2, &u32::from(key2));
+            }
+            map.insert(2, 0, &20);
+            map.remove(1, 0);
+
+            // When
+            let removed = map.remove_prefix(1);
+
+            // Then
+            assert_eq!(removed, 4);
+            assert_eq!(map.len_of(1), 0);
+            assert_eq!(map.keys_of(1).count(), 0);
+            assert_eq!(map.get(1, 3), None);
+            assert_eq!(map.iter_prefix(2).collect::<Vec<_>>(), vec![(0, 20)]);
+            // Only the value, index, key and length of the remaining entry are left.
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(4)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
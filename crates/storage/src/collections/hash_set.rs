@@ -0,0 +1,510 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sets of values in contract storage.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), these sets don't actually "own" any data.
+//! Every member is stored as an empty value under the root key of the set combined
+//! with the member, just like a [`Mapping<T, ()>`](crate::Mapping). The
+//! [`CountedStorageHashSet`] additionally stores the number of its members under
+//! its root key.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// A set of values directly in contract storage, e.g. an allowlist.
+///
+/// The set behaves like a [`Mapping<T, ()>`](crate::Mapping) with a dedicated API.
+/// Membership can be checked and changed in constant time, but the members
+/// can neither be counted nor enumerated. Use the [`CountedStorageHashSet`] if the
+/// number of members is needed.
+///
+/// # Important
+///
+/// The set requires its own pre-defined storage key where to store its members.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the set:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::StorageHashSet;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Allowlist {
+///     allowed: StorageHashSet<AccountId>,
+/// }
+///
+/// impl Allowlist {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn allow(&mut self, account: AccountId) -> bool {
+///         self.allowed.insert(account)
+///     }
+///
+///     #[ink(message)]
+///     pub fn is_allowed(&self, account: AccountId) -> bool {
+///         self.allowed.contains(account)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageHashSet<T: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (T, KeyType)>,
+}
+
+/// A set of values directly in contract storage which keeps track of the number of
+/// its members.
+///
+/// In contrast to the [`StorageHashSet`] every insertion or removal additionally
+/// reads and writes the number of members.
+///
+/// # Important
+///
+/// The set requires its own pre-defined storage key where to store its members.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CountedStorageHashSet<T: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (T, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<T, KeyType> Default for StorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<T, KeyType> Default for CountedStorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, KeyType> StorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageHashSet`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, KeyType> CountedStorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `CountedStorageHashSet`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, KeyType> ::core::fmt::Debug for StorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageHashSet")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<T, KeyType> ::core::fmt::Debug for CountedStorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("CountedStorageHashSet")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<T, KeyType> StorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    /// Adds the `value` to the set.
+    ///
+    /// Returns `true` if the `value` was not a member before.
+    #[inline]
+    pub fn insert<Q>(&mut self, value: Q) -> bool
+    where
+        Q: scale::EncodeLike<T>,
+    {
+        ink_env::set_contract_storage(&(&KeyType::KEY, value), &()).is_none()
+    }
+
+    /// Removes the `value` from the set.
+    ///
+    /// Returns `true` if the `value` was a member before.
+    #[inline]
+    pub fn remove<Q>(&mut self, value: Q) -> bool
+    where
+        Q: scale::EncodeLike<T>,
+    {
+        ink_env::clear_contract_storage(&(&KeyType::KEY, value)).is_some()
+    }
+
+    /// Checks if the `value` is a member of the set.
+    #[inline]
+    pub fn contains<Q>(&self, value: Q) -> bool
+    where
+        Q: scale::EncodeLike<T>,
+    {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, value)).is_some()
+    }
+}
+
+impl<T, KeyType> CountedStorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    /// Adds the `value` to the set.
+    ///
+    /// Returns `true` if the `value` was not a member before.
+    #[inline]
+    pub fn insert<Q>(&mut self, value: Q) -> bool
+    where
+        Q: scale::EncodeLike<T>,
+    {
+        let inserted =
+            ink_env::set_contract_storage(&(&KeyType::KEY, value), &()).is_none();
+        if inserted {
+            self.set_len(self.len() + 1);
+        }
+        inserted
+    }
+
+    /// Removes the `value` from the set.
+    ///
+    /// Returns `true` if the `value` was a member before.
+    #[inline]
+    pub fn remove<Q>(&mut self, value: Q) -> bool
+    where
+        Q: scale::EncodeLike<T>,
+    {
+        let removed = ink_env::clear_contract_storage(&(&KeyType::KEY, value)).is_some();
+        if removed {
+            self.set_len(self.len() - 1);
+        }
+        removed
+    }
+
+    /// Checks if the `value` is a member of the set.
+    #[inline]
+    pub fn contains<Q>(&self, value: Q) -> bool
+    where
+        Q: scale::EncodeLike<T>,
+    {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, value)).is_some()
+    }
+
+    /// Returns the number of members.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<Key, u32>(&KeyType::KEY)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of CountedStorageHashSet: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the set has no members.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all members from the set in the off-chain storage.
+    ///
+    /// Returns the number of removed members.
+    ///
+    /// # Note
+    ///
+    /// Only available off-chain, e.g. to reset the state between tests, because the
+    /// `contracts` pallet can't clear the storage by prefix, see
+    /// [`ink_env::clear_storage_prefix`]. The gas costs grow with the number of
+    /// members and may exceed the gas limit of a single call for large sets.
+    #[cfg(feature = "std")]
+    pub fn clear_all(&mut self) -> ink_env::Result<u32> {
+        let len = self.len();
+        // The number of members is stored under the root key itself.
+        ink_env::clear_storage_prefix(&KeyType::KEY)?;
+        Ok(len)
+    }
+
+    /// Stores the number of members.
+    fn set_len(&mut self, len: u32) {
+        if len == 0 {
+            ink_env::clear_contract_storage(&KeyType::KEY);
+        } else {
+            ink_env::set_contract_storage::<Key, u32>(&KeyType::KEY, &len);
+        }
+    }
+}
+
+impl<T, KeyType> Storable for StorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<T, KeyType> Storable for CountedStorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<T, Key, InnerKey> StorableHint<Key> for StorageHashSet<T, InnerKey>
+where
+    T: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageHashSet<T, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<T, Key, InnerKey> StorableHint<Key> for CountedStorageHashSet<T, InnerKey>
+where
+    T: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = CountedStorageHashSet<T, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<T, KeyType> StorageKey for StorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+impl<T, KeyType> StorageKey for CountedStorageHashSet<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<T, KeyType> StorageLayout for StorageHashSet<T, KeyType>
+    where
+        T: Packed + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <() as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+
+    impl<T, KeyType> StorageLayout for CountedStorageHashSet<T, KeyType>
+    where
+        T: Packed + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <u32 as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn insert_and_remove_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut set: StorageHashSet<u8> = StorageHashSet::new();
+            assert!(!set.contains(1));
+
+            assert!(set.insert(1));
+            assert!(!set.insert(1));
+            assert!(set.contains(1));
+            assert!(!set.contains(2));
+
+            assert!(set.remove(1));
+            assert!(!set.remove(1));
+            assert!(!set.contains(1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn sets_with_different_keys_are_disjoint() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut voters: StorageHashSet<u32, ManualKey<1>> = StorageHashSet::new();
+            let nonces: StorageHashSet<u32, ManualKey<2>> = StorageHashSet::new();
+
+            // When
+            voters.insert(7);
+
+            // Then
+            let voters: StorageHashSet<u32, ManualKey<1>> = StorageHashSet::new();
+            assert!(voters.contains(7));
+            assert!(!nonces.contains(7));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn counted_set_keeps_track_of_len() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut set: CountedStorageHashSet<u8> = CountedStorageHashSet::new();
+            assert!(set.is_empty());
+
+            // When
+            assert!(set.insert(1));
+            assert!(set.insert(2));
+            assert!(!set.insert(2));
+
+            // Then
+            assert_eq!(set.len(), 2);
+            assert!(set.contains(2));
+
+            // When
+            assert!(set.remove(1));
+            assert!(!set.remove(1));
+            assert!(set.remove(2));
+
+            // Then
+            assert!(set.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn counted_set_clear_all_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut set: CountedStorageHashSet<u8> = CountedStorageHashSet::new();
+            set.insert(1);
+            set.insert(2);
+
+            // When
+            let removed = set.clear_all();
+
+            // Then
+            assert_eq!(removed, Ok(2));
+            assert!(set.is_empty());
+            assert!(!set.contains(1));
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
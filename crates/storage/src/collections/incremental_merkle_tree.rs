@@ -0,0 +1,491 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An append-only Merkle tree of fixed depth in contract storage.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this tree doesn't actually "own" any data.
+//! The number of leaves and the position of the latest root are stored under the
+//! root key of the tree. The rightmost filled subtree of every level and the recent
+//! roots are stored under the root key combined with their level or position.
+//! The leaves themselves are not stored.
+
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::hash::{
+    Blake2x256,
+    Keccak256,
+    Sha2x256,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Decode,
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage slot of the [`Header`].
+const HEADER: u8 = 0;
+/// The storage slot of the rightmost filled subtrees per level.
+const FILLED_SUBTREES: u8 = 1;
+/// The storage slot of the recent roots.
+const ROOTS: u8 = 2;
+
+/// The number of recent roots which are known to
+/// [`StorageIncrementalMerkleTree::is_known_root`].
+const ROOT_HISTORY_SIZE: u32 = 30;
+
+/// The hash function of the nodes of a [`StorageIncrementalMerkleTree`].
+///
+/// Implemented for the built-in [`Keccak256`], [`Sha2x256`] and [`Blake2x256`]
+/// hashes. Zero-knowledge friendly hashes such as Poseidon, whose nodes have to be
+/// elements of the scalar field of the proof system, can be plugged in by
+/// implementing this trait.
+pub trait MerkleHasher {
+    /// The value of an empty leaf.
+    const ZERO_LEAF: [u8; 32] = [0; 32];
+
+    /// Returns the hash of the parent of the `left` and `right` nodes.
+    fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32];
+}
+
+macro_rules! impl_merkle_hasher {
+    ( $($hash:ty),* $(,)? ) => {
+        $(
+            impl MerkleHasher for $hash {
+                fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+                    let mut input = [0; 64];
+                    input[..32].copy_from_slice(left);
+                    input[32..].copy_from_slice(right);
+                    let mut output = [0; 32];
+                    ink_env::hash_bytes::<$hash>(&input, &mut output);
+                    output
+                }
+            }
+        )*
+    };
+}
+impl_merkle_hasher!(Keccak256, Sha2x256, Blake2x256);
+
+/// The number of leaves and the position of the latest root of a
+/// [`StorageIncrementalMerkleTree`].
+#[derive(Debug, Default, Clone, Copy, Encode, Decode)]
+struct Header {
+    /// The number of appended leaves, i.e. the index of the next leaf.
+    next_index: u64,
+    /// The position of the latest root among the recent roots.
+    current_root_index: u32,
+}
+
+/// An append-only Merkle tree of fixed depth directly in contract storage.
+///
+/// This is the incremental Merkle tree used by commitment pools and mixers: leaves
+/// are appended from left to right into a tree of `DEPTH` levels whose missing
+/// leaves are [`MerkleHasher::ZERO_LEAF`]. Only the rightmost filled subtree of
+/// every level is cached, so appending a leaf takes `DEPTH` storage reads and hashes
+/// regardless of the number of leaves. The last
+/// [`ROOT_HISTORY_SIZE`](Self::ROOT_HISTORY_SIZE) roots are kept, so that proofs
+/// against a root remain valid while new leaves are appended.
+///
+/// `DEPTH` must be at least one and at most 32.
+///
+/// # Important
+///
+/// The tree requires its own pre-defined storage key where to store its nodes.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the tree:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::{
+///     env::hash::Keccak256,
+///     storage::collections::StorageIncrementalMerkleTree,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Pool {
+///     commitments: StorageIncrementalMerkleTree<Keccak256, 20>,
+/// }
+///
+/// impl Pool {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn deposit(&mut self, commitment: [u8; 32]) -> Option<u64> {
+///         self.commitments.append(commitment)
+///     }
+///
+///     #[ink(message)]
+///     pub fn is_known_root(&self, root: [u8; 32]) -> bool {
+///         self.commitments.is_known_root(&root)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo),
+    scale_info(skip_type_params(H))
+)]
+pub struct StorageIncrementalMerkleTree<
+    H,
+    const DEPTH: u32,
+    KeyType: StorageKey = AutoKey,
+> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (H, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<H, const DEPTH: u32, KeyType> Default
+    for StorageIncrementalMerkleTree<H, DEPTH, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H, const DEPTH: u32, KeyType> StorageIncrementalMerkleTree<H, DEPTH, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// The number of recent roots which are known to [`Self::is_known_root`].
+    pub const ROOT_HISTORY_SIZE: u32 = ROOT_HISTORY_SIZE;
+
+    /// Creates a new empty `StorageIncrementalMerkleTree`.
+    pub fn new() -> Self {
+        assert!(
+            (1..=32).contains(&DEPTH),
+            "the depth of a StorageIncrementalMerkleTree must be between 1 and 32"
+        );
+        Self {
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns the number of appended leaves.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.header().next_index
+    }
+
+    /// Returns `true` if no leaves have been appended.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of leaves, i.e. `2^DEPTH`.
+    #[inline]
+    pub fn capacity(&self) -> u64 {
+        1 << DEPTH
+    }
+
+    fn header(&self) -> Header {
+        ink_env::get_contract_storage(&(&KeyType::KEY, HEADER))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Failed to get header of StorageIncrementalMerkleTree: {:?}",
+                    error
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    fn stored_root(&self, index: u32) -> Option<[u8; 32]> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, ROOTS, index)).unwrap_or_else(
+            |error| {
+                panic!(
+                    "Failed to get root of StorageIncrementalMerkleTree: {:?}",
+                    error
+                )
+            },
+        )
+    }
+
+    fn filled_subtree(&self, level: u32) -> [u8; 32] {
+        ink_env::get_contract_storage(&(&KeyType::KEY, FILLED_SUBTREES, level))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Failed to get subtree of StorageIncrementalMerkleTree: {:?}",
+                    error
+                )
+            })
+            .expect("the left subtree of a right node has been filled")
+    }
+}
+
+impl<H, const DEPTH: u32, KeyType> StorageIncrementalMerkleTree<H, DEPTH, KeyType>
+where
+    H: MerkleHasher,
+    KeyType: StorageKey,
+{
+    /// Appends the `leaf` and returns its index.
+    ///
+    /// Returns `None` if the tree is full. The tree is left unchanged then.
+    pub fn append(&mut self, leaf: [u8; 32]) -> Option<u64> {
+        let header = self.header();
+        let index = header.next_index;
+        if index >= self.capacity() {
+            return None
+        }
+        let mut node = leaf;
+        let mut zero = H::ZERO_LEAF;
+        for level in 0..DEPTH {
+            node = if (index >> level) & 1 == 0 {
+                ink_env::set_contract_storage(
+                    &(&KeyType::KEY, FILLED_SUBTREES, level),
+                    &node,
+                );
+                H::hash_pair(&node, &zero)
+            } else {
+                H::hash_pair(&self.filled_subtree(level), &node)
+            };
+            zero = H::hash_pair(&zero, &zero);
+        }
+        let current_root_index = (header.current_root_index + 1) % ROOT_HISTORY_SIZE;
+        ink_env::set_contract_storage(&(&KeyType::KEY, ROOTS, current_root_index), &node);
+        ink_env::set_contract_storage(
+            &(&KeyType::KEY, HEADER),
+            &Header {
+                next_index: index + 1,
+                current_root_index,
+            },
+        );
+        Some(index)
+    }
+
+    /// Returns the current root of the tree.
+    ///
+    /// The root of the empty tree is the root of a tree of only zero leaves.
+    pub fn root(&self) -> [u8; 32] {
+        let header = self.header();
+        if header.next_index == 0 {
+            return Self::zero_root()
+        }
+        self.stored_root(header.current_root_index)
+            .expect("the root of a non-empty tree has been stored")
+    }
+
+    /// Returns `true` if `root` is the current root or one of the
+    /// [`ROOT_HISTORY_SIZE`](Self::ROOT_HISTORY_SIZE) most recent roots.
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        let header = self.header();
+        if header.next_index == 0 {
+            return root == &Self::zero_root()
+        }
+        let mut index = header.current_root_index;
+        for _ in 0..ROOT_HISTORY_SIZE {
+            match self.stored_root(index) {
+                Some(stored) if &stored == root => return true,
+                Some(_) => {}
+                // The history has not been filled up to this position yet.
+                None => return false,
+            }
+            index = index.checked_sub(1).unwrap_or(ROOT_HISTORY_SIZE - 1);
+        }
+        false
+    }
+
+    /// Returns the root of a tree of only zero leaves.
+    fn zero_root() -> [u8; 32] {
+        (0..DEPTH).fold(H::ZERO_LEAF, |zero, _| H::hash_pair(&zero, &zero))
+    }
+}
+
+impl<H, const DEPTH: u32, KeyType> ::core::fmt::Debug
+    for StorageIncrementalMerkleTree<H, DEPTH, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageIncrementalMerkleTree")
+            .field("key", &KeyType::KEY)
+            .field("depth", &DEPTH)
+            .finish()
+    }
+}
+
+impl<H, const DEPTH: u32, KeyType> Storable
+    for StorageIncrementalMerkleTree<H, DEPTH, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<H, const DEPTH: u32, Key, InnerKey> StorableHint<Key>
+    for StorageIncrementalMerkleTree<H, DEPTH, InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageIncrementalMerkleTree<H, DEPTH, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<H, const DEPTH: u32, KeyType> StorageKey
+    for StorageIncrementalMerkleTree<H, DEPTH, KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<H, const DEPTH: u32, KeyType> StorageLayout
+        for StorageIncrementalMerkleTree<H, DEPTH, KeyType>
+    where
+        H: 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <[u8; 32] as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    /// A hasher which makes the structure of the tree visible in its nodes.
+    enum SumHasher {}
+
+    impl MerkleHasher for SumHasher {
+        const ZERO_LEAF: [u8; 32] = [1; 32];
+
+        fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+            let mut output = [0; 32];
+            for (byte, (left, right)) in output.iter_mut().zip(left.iter().zip(right)) {
+                *byte = left.wrapping_mul(3).wrapping_add(*right);
+            }
+            output
+        }
+    }
+
+    /// Returns the root of the tree of depth `depth` with `leaves` computed naively.
+    fn naive_root<H: MerkleHasher>(leaves: &[[u8; 32]], depth: u32) -> [u8; 32] {
+        let mut level = leaves.to_vec();
+        level.resize(1 << depth, H::ZERO_LEAF);
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| H::hash_pair(&pair[0], &pair[1]))
+                .collect();
+        }
+        level[0]
+    }
+
+    #[test]
+    fn append_matches_naive_root() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut tree: StorageIncrementalMerkleTree<Keccak256, 3> =
+                StorageIncrementalMerkleTree::new();
+            let mut other: StorageIncrementalMerkleTree<SumHasher, 3, ManualKey<123>> =
+                StorageIncrementalMerkleTree::new();
+            assert!(tree.is_empty());
+            assert_eq!(tree.root(), naive_root::<Keccak256>(&[], 3));
+            assert_eq!(other.root(), naive_root::<SumHasher>(&[], 3));
+
+            let mut leaves = Vec::new();
+            for index in 0..8u8 {
+                let leaf = [index + 2; 32];
+                leaves.push(leaf);
+                assert_eq!(tree.append(leaf), Some(u64::from(index)));
+                assert_eq!(other.append(leaf), Some(u64::from(index)));
+                assert_eq!(tree.root(), naive_root::<Keccak256>(&leaves, 3));
+                assert_eq!(other.root(), naive_root::<SumHasher>(&leaves, 3));
+            }
+            assert_eq!(tree.len(), 8);
+            assert_eq!(tree.capacity(), 8);
+
+            // The tree is full.
+            let root = tree.root();
+            assert_eq!(tree.append([0; 32]), None);
+            assert_eq!(tree.len(), 8);
+            assert_eq!(tree.root(), root);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn recent_roots_are_known() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut tree: StorageIncrementalMerkleTree<Blake2x256, 8> =
+                StorageIncrementalMerkleTree::new();
+            assert!(tree.is_known_root(&tree.root()));
+
+            let mut roots = Vec::new();
+            for index in 0..40u8 {
+                tree.append([index; 32]);
+                roots.push(tree.root());
+            }
+
+            let (outdated, recent) = roots.split_at(40 - ROOT_HISTORY_SIZE as usize);
+            assert!(recent.iter().all(|root| tree.is_known_root(root)));
+            assert!(!outdated.iter().any(|root| tree.is_known_root(root)));
+            assert!(!tree.is_known_root(&[0; 32]));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the depth of a StorageIncrementalMerkleTree must be between 1 and 32"
+    )]
+    fn invalid_depth_is_rejected() {
+        let _: StorageIncrementalMerkleTree<Keccak256, 33> =
+            StorageIncrementalMerkleTree::new();
+    }
+}
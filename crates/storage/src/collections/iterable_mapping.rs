@@ -0,0 +1,752 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A mapping to contract storage which keeps track of its keys.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this mapping doesn't actually "own" any data.
+//! The values and the inserted keys are stored under the root key of the mapping
+//! combined with the respective key or index.
+
+use super::cursor::{
+    Cursor,
+    Page,
+};
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_prelude::vec::Vec;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage slot of the number of keys.
+const LEN: u8 = 0;
+/// The storage slot of the keys indexed by their position.
+const KEYS: u8 = 1;
+/// The storage slot of the values.
+const VALUES: u8 = 2;
+/// The storage slot of the positions of the keys.
+const INDICES: u8 = 3;
+
+mod private {
+    /// Prevents implementations of [`IndexOrder`](super::IndexOrder) outside of this crate.
+    pub trait Sealed {}
+}
+
+/// The order in which an [`IterableMapping`] keeps the keys of its entries.
+///
+/// The order determines the position of the keys in [`IterableMapping::keys`],
+/// [`IterableMapping::iter`] and the pages returned by [`IterableMapping::iter_from`]
+/// and [`IterableMapping::get_page`]. It is implemented by [`UnorderedIndex`],
+/// [`InsertionOrderIndex`] and [`KeyOrderIndex`].
+pub trait IndexOrder<K>: private::Sealed {
+    /// Whether removing a key shifts all following keys by one position instead of
+    /// moving the last key into its position.
+    #[doc(hidden)]
+    const SHIFT_ON_REMOVE: bool;
+
+    /// Returns the position to insert the new `key` at among the `len` existing keys,
+    /// where `key_at` loads the key at a position.
+    #[doc(hidden)]
+    fn insert_position<F>(key: &K, len: u32, key_at: F) -> u32
+    where
+        F: FnMut(u32) -> K;
+}
+
+/// Keeps the keys in insertion order until an entry is removed.
+///
+/// Removing an entry moves the last key into its position, so both insertion and
+/// removal write a constant number of storage cells. This is the default order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct UnorderedIndex;
+
+impl private::Sealed for UnorderedIndex {}
+
+impl<K> IndexOrder<K> for UnorderedIndex {
+    const SHIFT_ON_REMOVE: bool = false;
+
+    fn insert_position<F>(_key: &K, len: u32, _key_at: F) -> u32
+    where
+        F: FnMut(u32) -> K,
+    {
+        len
+    }
+}
+
+/// Keeps the keys in insertion order, also across removals.
+///
+/// Removing an entry shifts all keys inserted after it, which writes two storage
+/// cells per shifted key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct InsertionOrderIndex;
+
+impl private::Sealed for InsertionOrderIndex {}
+
+impl<K> IndexOrder<K> for InsertionOrderIndex {
+    const SHIFT_ON_REMOVE: bool = true;
+
+    fn insert_position<F>(_key: &K, len: u32, _key_at: F) -> u32
+    where
+        F: FnMut(u32) -> K,
+    {
+        len
+    }
+}
+
+/// Keeps the keys sorted in ascending order.
+///
+/// Inserting a new key looks up its position with a binary search over the keys and
+/// shifts all greater keys, as does removing an entry. This writes two storage cells
+/// per shifted key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct KeyOrderIndex;
+
+impl private::Sealed for KeyOrderIndex {}
+
+impl<K> IndexOrder<K> for KeyOrderIndex
+where
+    K: Ord,
+{
+    const SHIFT_ON_REMOVE: bool = true;
+
+    fn insert_position<F>(key: &K, len: u32, mut key_at: F) -> u32
+    where
+        F: FnMut(u32) -> K,
+    {
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if key_at(middle) < *key {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
+
+/// A mapping of key-value pairs directly into contract storage which can be iterated.
+///
+/// In contrast to the [`Mapping`](crate::Mapping) it keeps track of the inserted keys,
+/// so that all entries can be enumerated on-chain, e.g. to pay out an airdrop to all
+/// token holders. This comes at the cost of two additional storage cells per entry
+/// which are written on insertion of a new key and on removal.
+///
+/// The entries are loaded lazily one after the other while iterating. The order of
+/// the keys is chosen with the `Order` type parameter, see [`IndexOrder`]. By default,
+/// removing an entry moves the last key into its position, hence the order of the
+/// keys is only stable as long as no entries are removed. Use [`InsertionOrderIndex`]
+/// or [`KeyOrderIndex`] for a deterministic order, e.g. to serve pages to clients,
+/// at the cost of shifting keys on insertion or removal.
+///
+/// # Important
+///
+/// The mapping requires its own pre-defined storage key where to store values. By default,
+/// the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey) during compilation.
+/// However, anyone can specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the mapping:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::{
+///     Cursor,
+///     IterableMapping,
+///     Page,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Token {
+///     balances: IterableMapping<AccountId, Balance>,
+/// }
+///
+/// impl Token {
+///     #[ink(constructor)]
+///     pub fn new(total_supply: Balance) -> Self {
+///         let mut instance = Self::default();
+///         instance.balances.insert(Self::env().caller(), &total_supply);
+///         instance
+///     }
+///
+///     #[ink(message)]
+///     pub fn holders(&self, cursor: Cursor, limit: u32) -> Page<AccountId> {
+///         let page = self.balances.iter_from(cursor, limit);
+///         Page {
+///             items: page.items.into_iter().map(|(holder, _)| holder).collect(),
+///             next: page.next,
+///         }
+///     }
+///
+///     #[ink(message)]
+///     pub fn number_of_holders(&self) -> u32 {
+///         self.balances.len()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct IterableMapping<
+    K,
+    V: Packed,
+    KeyType: StorageKey = AutoKey,
+    Order = UnorderedIndex,
+> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K, V, KeyType, Order)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<K, V, KeyType, Order> Default for IterableMapping<K, V, KeyType, Order>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KeyType, Order> IterableMapping<K, V, KeyType, Order>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `IterableMapping`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K, V, KeyType, Order> ::core::fmt::Debug for IterableMapping<K, V, KeyType, Order>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("IterableMapping")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<K, V, KeyType, Order> IterableMapping<K, V, KeyType, Order>
+where
+    K: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+    Order: IndexOrder<K>,
+{
+    /// Insert the given `value` to the contract storage.
+    ///
+    /// Returns the size in bytes of the pre-existing value at the specified key if any.
+    #[inline]
+    pub fn insert<Q, R>(&mut self, key: Q, value: &R) -> Option<u32>
+    where
+        Q: scale::EncodeLike<K>,
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let previous_size =
+            ink_env::set_contract_storage(&(&KeyType::KEY, VALUES, &key), value);
+        if previous_size.is_none() {
+            let key = <K as scale::Decode>::decode(&mut &key.encode()[..])
+                .unwrap_or_else(|error| {
+                    panic!("Failed to decode key of IterableMapping: {:?}", error)
+                });
+            let len = self.len();
+            let index =
+                Order::insert_position(&key, len, |index| self.tracked_key(index));
+            for position in (index..len).rev() {
+                self.move_key(position, position + 1);
+            }
+            ink_env::set_contract_storage(&(&KeyType::KEY, INDICES, &key), &index);
+            ink_env::set_contract_storage(&(&KeyType::KEY, KEYS, index), &key);
+            ink_env::set_contract_storage(&(&KeyType::KEY, LEN), &(len + 1));
+        }
+        previous_size
+    }
+
+    /// Get the `value` at `key` from the contract storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn get<Q>(&self, key: Q) -> Option<V>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::get_contract_storage(&(&KeyType::KEY, VALUES, key)).unwrap_or_else(
+            |error| panic!("Failed to get value in IterableMapping: {:?}", error),
+        )
+    }
+
+    /// Removes the `value` at `key`, returning the previous `value` at `key` from storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn take<Q>(&mut self, key: Q) -> Option<V>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        let value = ink_env::take_contract_storage(&(&KeyType::KEY, VALUES, &key))
+            .unwrap_or_else(|error| {
+                panic!("Failed to take value in IterableMapping: {:?}", error)
+            });
+        if value.is_some() {
+            self.remove_key(key);
+        }
+        value
+    }
+
+    /// Get the size of a value stored at `key` in the contract storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn size<Q>(&self, key: Q) -> Option<u32>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, VALUES, key))
+    }
+
+    /// Checks if a value is stored at the given `key` in the contract storage.
+    #[inline]
+    pub fn contains<Q>(&self, key: Q) -> bool
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        self.size(key).is_some()
+    }
+
+    /// Clears the value at `key` from storage.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: Q)
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        if ink_env::clear_contract_storage(&(&KeyType::KEY, VALUES, &key)).is_some() {
+            self.remove_key(key);
+        }
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<_, u32>(&(&KeyType::KEY, LEN))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of IterableMapping: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if there are no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the key at position `index` if any.
+    #[inline]
+    pub fn key_at(&self, index: u32) -> Option<K> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, KEYS, index)).unwrap_or_else(
+            |error| panic!("Failed to get key in IterableMapping: {:?}", error),
+        )
+    }
+
+    /// Returns an iterator over all keys.
+    ///
+    /// Every key is loaded from storage only once the iterator reaches it.
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        (0..self.len()).filter_map(|index| self.key_at(index))
+    }
+
+    /// Returns an iterator over all key-value pairs.
+    ///
+    /// Every entry is loaded from storage only once the iterator reaches it.
+    pub fn iter(&self) -> impl Iterator<Item = (K, V)> + '_ {
+        self.keys().filter_map(|key| {
+            let value = self.get(&key)?;
+            Some((key, value))
+        })
+    }
+
+    /// Returns the page of at most `limit` entries from the position of `cursor`
+    /// on together with the cursor of the next page.
+    ///
+    /// The cursor refers to the position of a key. Removing an entry moves other
+    /// keys to new positions, so removals and, with [`KeyOrderIndex`], insertions
+    /// between fetching two pages may skip or repeat entries.
+    pub fn iter_from(&self, cursor: Cursor, limit: u32) -> Page<(K, V)> {
+        Page::from_indices(cursor, limit, self.len(), |index| {
+            let key = self.key_at(index)?;
+            let value = self.get(&key)?;
+            Some((key, value))
+        })
+    }
+
+    /// Returns at most `limit` entries from position `offset` on, in the order of
+    /// the keys.
+    pub fn get_page(&self, offset: u32, limit: u32) -> Vec<(K, V)> {
+        self.iter_from(Cursor::at(offset), limit).items
+    }
+
+    /// Removes all entries.
+    ///
+    /// # Note
+    ///
+    /// This clears the storage cells of every entry and hence is as expensive as
+    /// removing all entries one by one. The gas costs grow with the number of
+    /// entries and may exceed the gas limit of a single call for large mappings,
+    /// in which case the entries have to be removed in batches across calls.
+    pub fn clear(&mut self) {
+        for index in 0..self.len() {
+            if let Some(key) = self.key_at(index) {
+                ink_env::clear_contract_storage(&(&KeyType::KEY, VALUES, &key));
+                ink_env::clear_contract_storage(&(&KeyType::KEY, INDICES, &key));
+            }
+            ink_env::clear_contract_storage(&(&KeyType::KEY, KEYS, index));
+        }
+        ink_env::clear_contract_storage(&(&KeyType::KEY, LEN));
+    }
+
+    /// Returns the key at position `index`, which must be tracked.
+    fn tracked_key(&self, index: u32) -> K {
+        self.key_at(index)
+            .expect("the keys of all positions below the length must be stored")
+    }
+
+    /// Moves the key at position `from` to position `to`.
+    fn move_key(&mut self, from: u32, to: u32) {
+        let key = self.tracked_key(from);
+        ink_env::set_contract_storage(&(&KeyType::KEY, KEYS, to), &key);
+        ink_env::set_contract_storage(&(&KeyType::KEY, INDICES, &key), &to);
+    }
+
+    /// Removes `key` from the tracked keys by either shifting all following keys or
+    /// moving the last key into its position, depending on the `Order`.
+    fn remove_key<Q>(&mut self, key: Q)
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        let index_key = (&KeyType::KEY, INDICES, key);
+        let index = ink_env::get_contract_storage::<_, u32>(&index_key)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get index in IterableMapping: {:?}", error)
+            })
+            .expect("the index of an existing entry must be stored");
+        ink_env::clear_contract_storage(&index_key);
+        let last = self.len() - 1;
+        if Order::SHIFT_ON_REMOVE {
+            for position in index..last {
+                self.move_key(position + 1, position);
+            }
+        } else if index != last {
+            self.move_key(last, index);
+        }
+        ink_env::clear_contract_storage(&(&KeyType::KEY, KEYS, last));
+        ink_env::set_contract_storage(&(&KeyType::KEY, LEN), &last);
+    }
+}
+
+impl<K, V, KeyType, Order> Storable for IterableMapping<K, V, KeyType, Order>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<K, V, Key, InnerKey, Order> StorableHint<Key>
+    for IterableMapping<K, V, InnerKey, Order>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = IterableMapping<K, V, Key, Order>;
+    type PreferredKey = InnerKey;
+}
+
+impl<K, V, KeyType, Order> StorageKey for IterableMapping<K, V, KeyType, Order>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<K, V, KeyType, Order> StorageLayout for IterableMapping<K, V, KeyType, Order>
+    where
+        K: scale_info::TypeInfo + 'static,
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+        Order: scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn insert_and_get_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            assert_eq!(mapping.insert(1, &2), None);
+            assert_eq!(mapping.insert(1, &3), Some(1));
+            assert_eq!(mapping.get(1), Some(3));
+            assert_eq!(mapping.get(2), None);
+            assert_eq!(mapping.len(), 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: IterableMapping<u8, u8, ManualKey<123>> =
+                IterableMapping::new();
+
+            // When
+            mapping.insert(1, &10);
+            mapping.insert(2, &20);
+            mapping.insert(3, &30);
+
+            // Then
+            let mapping: IterableMapping<u8, u8, ManualKey<123>> = IterableMapping::new();
+            assert_eq!(mapping.keys().collect::<Vec<_>>(), vec![1, 2, 3]);
+            assert_eq!(
+                mapping.iter().collect::<Vec<_>>(),
+                vec![(1, 10), (2, 20), (3, 30)]
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_and_take_untrack_keys() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            for key in 1..=4 {
+                mapping.insert(key, &(key * 10));
+            }
+
+            // When
+            mapping.remove(2);
+            assert_eq!(mapping.take(4), Some(40));
+            mapping.remove(5);
+            assert_eq!(mapping.take(5), None);
+
+            // Then
+            assert_eq!(mapping.len(), 2);
+            assert_eq!(mapping.iter().collect::<Vec<_>>(), vec![(1, 10), (3, 30)]);
+            assert!(!mapping.contains(2));
+            assert_eq!(mapping.key_at(2), None);
+
+            // When
+            mapping.remove(1);
+            mapping.remove(3);
+            mapping.insert(2, &20);
+
+            // Then
+            assert_eq!(mapping.iter().collect::<Vec<_>>(), vec![(2, 20)]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn removal_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            mapping.insert(1, &10);
+            mapping.insert(2, &20);
+
+            // When
+            mapping.remove(1);
+            mapping.remove(2);
+
+            // Then
+            assert!(mapping.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(1)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn insertion_order_index_keeps_order_across_removals() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: IterableMapping<u8, u8, AutoKey, InsertionOrderIndex> =
+                IterableMapping::new();
+            for key in [5, 1, 4, 2, 3] {
+                mapping.insert(key, &(key * 10));
+            }
+
+            // When
+            mapping.remove(1);
+            assert_eq!(mapping.take(2), Some(20));
+            mapping.insert(1, &10);
+
+            // Then
+            assert_eq!(mapping.keys().collect::<Vec<_>>(), vec![5, 4, 3, 1]);
+            assert_eq!(mapping.key_at(4), None);
+
+            // When
+            mapping.remove(5);
+            mapping.insert(4, &41);
+
+            // Then
+            assert_eq!(
+                mapping.iter().collect::<Vec<_>>(),
+                vec![(4, 41), (3, 30), (1, 10)]
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn key_order_index_keeps_keys_sorted() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: IterableMapping<u8, u8, AutoKey, KeyOrderIndex> =
+                IterableMapping::new();
+            for key in [5, 1, 4, 2, 3, 9, 0] {
+                mapping.insert(key, &(key * 10));
+            }
+
+            // When
+            mapping.remove(4);
+            mapping.remove(0);
+            mapping.insert(7, &70);
+
+            // Then
+            assert_eq!(mapping.keys().collect::<Vec<_>>(), vec![1, 2, 3, 5, 7, 9]);
+            assert_eq!(mapping.get(7), Some(70));
+
+            // When
+            mapping.remove(9);
+
+            // Then
+            assert_eq!(mapping.len(), 5);
+            assert_eq!(mapping.key_at(5), None);
+            assert_eq!(mapping.keys().collect::<Vec<_>>(), vec![1, 2, 3, 5, 7]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn get_page_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8, AutoKey, KeyOrderIndex> =
+                IterableMapping::new();
+            for key in [3, 0, 4, 1, 2] {
+                mapping.insert(key, &key);
+            }
+
+            assert_eq!(mapping.get_page(0, 2), vec![(0, 0), (1, 1)]);
+            assert_eq!(mapping.get_page(2, 2), vec![(2, 2), (3, 3)]);
+            assert_eq!(mapping.get_page(4, 2), vec![(4, 4)]);
+            assert_eq!(mapping.get_page(5, 2), vec![]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_removes_all_entries() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            for key in 0..10 {
+                mapping.insert(key, &key);
+            }
+            mapping.remove(3);
+
+            // When
+            mapping.clear();
+
+            // Then
+            assert!(mapping.is_empty());
+            assert_eq!(mapping.get(1), None);
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
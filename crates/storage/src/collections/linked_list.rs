@@ -0,0 +1,596 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A doubly linked list in contract storage which loads its nodes lazily.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this list doesn't actually "own" any data.
+//! The handles of the first and the last node are stored under the root key of the
+//! list while every node is stored under the root key combined with its handle.
+
+use super::cursor::{
+    Cursor,
+    Page,
+};
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Decode,
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// A stable handle to a node of a [`StorageLinkedList`].
+///
+/// The handle stays valid until its node is removed. Handles are never reused,
+/// so a handle of a removed node doesn't refer to any node afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NodeHandle(u32);
+
+/// The ends of a [`StorageLinkedList`].
+#[derive(Debug, Default, Clone, Copy, Encode, Decode)]
+struct Header {
+    /// The handle of the first node.
+    head: Option<u32>,
+    /// The handle of the last node.
+    tail: Option<u32>,
+    /// The number of nodes.
+    len: u32,
+    /// The handle of the next inserted node.
+    next_handle: u32,
+}
+
+/// A node of a [`StorageLinkedList`].
+#[derive(Encode, Decode)]
+struct Node<V> {
+    prev: Option<u32>,
+    next: Option<u32>,
+    value: V,
+}
+
+/// A doubly linked list of elements directly in contract storage.
+///
+/// Inserting and removing an element only loads the element and its neighbours,
+/// no matter where it is located in the list, e.g. to drop a validator from the
+/// middle of a queue. Every element is stored in its own storage cell under the
+/// root key combined with the handle returned on insertion.
+///
+/// # Important
+///
+/// The list requires its own pre-defined storage key where to store its elements.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the list:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     collections::{
+///         NodeHandle,
+///         StorageLinkedList,
+///     },
+///     Mapping,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Validators {
+///     queue: StorageLinkedList<AccountId>,
+///     handles: Mapping<AccountId, NodeHandle>,
+/// }
+///
+/// impl Validators {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn join(&mut self) {
+///         let caller = self.env().caller();
+///         let handle = self.queue.push_back(caller);
+///         self.handles.insert(caller, &handle);
+///     }
+///
+///     #[ink(message)]
+///     pub fn leave(&mut self) {
+///         if let Some(handle) = self.handles.take(self.env().caller()) {
+///             self.queue.remove(handle);
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageLinkedList<V: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<V, KeyType> Default for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageLinkedList`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageLinkedList")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<V, KeyType> StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.header().len
+    }
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the `value` to the back of the list and returns its handle.
+    ///
+    /// # Panics
+    ///
+    /// If `u32::MAX` elements have been inserted into the list.
+    pub fn push_back(&mut self, value: V) -> NodeHandle {
+        let header = self.header();
+        self.link(header, header.tail, None, value)
+    }
+
+    /// Prepends the `value` to the front of the list and returns its handle.
+    ///
+    /// # Panics
+    ///
+    /// If `u32::MAX` elements have been inserted into the list.
+    pub fn push_front(&mut self, value: V) -> NodeHandle {
+        let header = self.header();
+        self.link(header, None, header.head, value)
+    }
+
+    /// Inserts the `value` right after the element of `handle` and returns its
+    /// handle.
+    ///
+    /// Returns `None` if `handle` doesn't refer to any element of the list.
+    ///
+    /// # Panics
+    ///
+    /// If `u32::MAX` elements have been inserted into the list.
+    pub fn insert_after(&mut self, handle: NodeHandle, value: V) -> Option<NodeHandle> {
+        let next = self.node(handle.0)?.next;
+        let header = self.header();
+        Some(self.link(header, Some(handle.0), next, value))
+    }
+
+    /// Removes the element of `handle` from the list and returns it.
+    ///
+    /// Returns `None` if `handle` doesn't refer to any element of the list.
+    pub fn remove(&mut self, handle: NodeHandle) -> Option<V> {
+        let node = self.take_node(handle.0)?;
+        let mut header = self.header();
+        match node.prev {
+            Some(prev) => self.update_node(prev, |prev| prev.next = node.next),
+            None => header.head = node.next,
+        }
+        match node.next {
+            Some(next) => self.update_node(next, |next| next.prev = node.prev),
+            None => header.tail = node.prev,
+        }
+        header.len -= 1;
+        self.set_header(&header);
+        Some(node.value)
+    }
+
+    /// Returns the element of `handle` if any.
+    #[inline]
+    pub fn get(&self, handle: NodeHandle) -> Option<V> {
+        self.node(handle.0).map(|node| node.value)
+    }
+
+    /// Returns the first element together with its handle if any.
+    #[inline]
+    pub fn front(&self) -> Option<(NodeHandle, V)> {
+        let head = self.header().head?;
+        self.get(NodeHandle(head))
+            .map(|value| (NodeHandle(head), value))
+    }
+
+    /// Returns the last element together with its handle if any.
+    #[inline]
+    pub fn back(&self) -> Option<(NodeHandle, V)> {
+        let tail = self.header().tail?;
+        self.get(NodeHandle(tail))
+            .map(|value| (NodeHandle(tail), value))
+    }
+
+    /// Returns an iterator over all elements together with their handles from
+    /// front to back.
+    ///
+    /// Every element is loaded from storage only once the iterator reaches it.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeHandle, V)> + '_ {
+        let mut next = self.header().head;
+        core::iter::from_fn(move || {
+            let handle = next?;
+            let node = self
+                .node(handle)
+                .expect("the nodes of a linked list must be stored");
+            next = node.next;
+            Some((NodeHandle(handle), node.value))
+        })
+    }
+
+    /// Returns the page of at most `limit` elements together with their handles from
+    /// the node of `cursor` on and the cursor of the next page.
+    ///
+    /// The cursor refers to the handle of the first node of the next page. If that
+    /// node is removed before the next page is fetched, the next page is empty.
+    pub fn iter_from(
+        &self,
+        cursor: Cursor<NodeHandle>,
+        limit: u32,
+    ) -> Page<(NodeHandle, V), NodeHandle> {
+        let mut next = match cursor.position() {
+            Some(NodeHandle(handle)) => Some(handle),
+            None => self.header().head,
+        };
+        let iter = core::iter::from_fn(move || {
+            let handle = next?;
+            let node = self.node(handle)?;
+            next = node.next;
+            Some((NodeHandle(handle), node.value))
+        });
+        Page::collect(iter, limit, |(handle, _)| handle)
+    }
+
+    /// Removes all elements from the list.
+    ///
+    /// # Note
+    ///
+    /// This clears the storage cell of every element and hence is as expensive
+    /// as removing all elements.
+    pub fn clear(&mut self) {
+        let mut header = self.header();
+        let mut next = header.head;
+        while let Some(handle) = next {
+            next = self
+                .take_node(handle)
+                .expect("the nodes of a linked list must be stored")
+                .next;
+        }
+        header.head = None;
+        header.tail = None;
+        header.len = 0;
+        self.set_header(&header);
+    }
+
+    /// Stores the `value` in a new node between the nodes `prev` and `next`.
+    fn link(
+        &mut self,
+        mut header: Header,
+        prev: Option<u32>,
+        next: Option<u32>,
+        value: V,
+    ) -> NodeHandle {
+        let handle = header.next_handle;
+        header.next_handle = handle
+            .checked_add(1)
+            .expect("cannot insert more than u32::MAX elements into StorageLinkedList");
+        header.len += 1;
+        self.set_node(handle, &Node { prev, next, value });
+        match prev {
+            Some(prev) => self.update_node(prev, |prev| prev.next = Some(handle)),
+            None => header.head = Some(handle),
+        }
+        match next {
+            Some(next) => self.update_node(next, |next| next.prev = Some(handle)),
+            None => header.tail = Some(handle),
+        }
+        self.set_header(&header);
+        NodeHandle(handle)
+    }
+
+    fn node(&self, handle: u32) -> Option<Node<V>> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, handle)).unwrap_or_else(|error| {
+            panic!("Failed to get value in StorageLinkedList: {:?}", error)
+        })
+    }
+
+    fn set_node(&mut self, handle: u32, node: &Node<V>) {
+        ink_env::set_contract_storage(&(&KeyType::KEY, handle), node);
+    }
+
+    fn take_node(&mut self, handle: u32) -> Option<Node<V>> {
+        ink_env::take_contract_storage(&(&KeyType::KEY, handle)).unwrap_or_else(|error| {
+            panic!("Failed to remove value in StorageLinkedList: {:?}", error)
+        })
+    }
+
+    /// Applies `f` to the stored node of `handle`.
+    fn update_node<F>(&mut self, handle: u32, f: F)
+    where
+        F: FnOnce(&mut Node<V>),
+    {
+        let mut node = self
+            .node(handle)
+            .expect("the neighbours of a node must be stored");
+        f(&mut node);
+        self.set_node(handle, &node);
+    }
+
+    fn header(&self) -> Header {
+        ink_env::get_contract_storage::<Key, Header>(&KeyType::KEY)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get header of StorageLinkedList: {:?}", error)
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_header(&mut self, header: &Header) {
+        ink_env::set_contract_storage::<Key, Header>(&KeyType::KEY, header);
+    }
+}
+
+impl<V, KeyType> Storable for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageLinkedList<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageLinkedList<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageLinkedList<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        LeafLayout,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageLinkedList<V, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+
+    impl StorageLayout for NodeHandle {
+        fn layout(key: &Key) -> Layout {
+            Layout::Leaf(LeafLayout::from_key::<Self>(LayoutKey::from(key)))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    fn values<KeyType: StorageKey>(list: &StorageLinkedList<u8, KeyType>) -> Vec<u8> {
+        list.iter().map(|(_, value)| value).collect()
+    }
+
+    #[test]
+    fn push_and_insert_keep_order() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut list: StorageLinkedList<u8> = StorageLinkedList::new();
+            assert!(list.is_empty());
+            assert_eq!(list.front(), None);
+
+            let two = list.push_back(2);
+            let one = list.push_front(1);
+            let four = list.push_back(4);
+            let three = list.insert_after(two, 3).unwrap();
+            let five = list.insert_after(four, 5).unwrap();
+
+            assert_eq!(values(&list), vec![1, 2, 3, 4, 5]);
+            assert_eq!(list.len(), 5);
+            assert_eq!(list.front(), Some((one, 1)));
+            assert_eq!(list.back(), Some((five, 5)));
+            assert_eq!(list.get(three), Some(3));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_by_handle_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut list: StorageLinkedList<u8, ManualKey<123>> =
+                StorageLinkedList::new();
+            let handles = (0..5)
+                .map(|value| list.push_back(value))
+                .collect::<Vec<_>>();
+
+            // When
+            assert_eq!(list.remove(handles[2]), Some(2));
+            assert_eq!(list.remove(handles[0]), Some(0));
+            assert_eq!(list.remove(handles[4]), Some(4));
+
+            // Then
+            let mut list: StorageLinkedList<u8, ManualKey<123>> =
+                StorageLinkedList::new();
+            assert_eq!(values(&list), vec![1, 3]);
+            assert_eq!(list.remove(handles[2]), None);
+            assert_eq!(list.insert_after(handles[2], 9), None);
+            assert_eq!(list.get(handles[2]), None);
+
+            // Handles are not reused, even once the list was empty.
+            assert_eq!(list.remove(handles[1]), Some(1));
+            assert_eq!(list.remove(handles[3]), Some(3));
+            assert!(list.is_empty());
+            let handle = list.push_back(7);
+            assert!(!handles.contains(&handle));
+            assert_eq!(list.get(handles[0]), None);
+            assert_eq!(values(&list), vec![7]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn removal_only_loads_neighbours() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut list: StorageLinkedList<u32> = StorageLinkedList::new();
+            let handles = (0..100)
+                .map(|value| list.push_back(value))
+                .collect::<Vec<_>>();
+            let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+
+            // When
+            assert_eq!(list.remove(handles[50]), Some(50));
+
+            // Then
+            let (reads_after, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+            // The header and both neighbours, taking the removed node is no read.
+            assert_eq!(reads_after - reads, 3);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            let mut list: StorageLinkedList<u8> = StorageLinkedList::new();
+            let handle = list.push_back(1);
+            list.push_back(2);
+
+            list.clear();
+
+            assert!(list.is_empty());
+            assert_eq!(list.get(handle), None);
+            // Only the header remains to keep the handles unique.
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(1)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_from_pages_through_elements() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut list: StorageLinkedList<u8> = StorageLinkedList::new();
+            let handles = (0..5)
+                .map(|value| list.push_back(value))
+                .collect::<Vec<_>>();
+
+            let page = list.iter_from(Cursor::start(), 3);
+            assert_eq!(
+                page.items,
+                [(handles[0], 0), (handles[1], 1), (handles[2], 2)]
+            );
+            assert_eq!(page.next, Some(Cursor::at(handles[3])));
+            let page = list.iter_from(page.next.unwrap(), 3);
+            assert_eq!(page.items, [(handles[3], 3), (handles[4], 4)]);
+            assert_eq!(page.next, None);
+
+            list.remove(handles[3]);
+            assert!(list.iter_from(Cursor::at(handles[3]), 3).items.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Collections and utilities built on top of [`Lazy`](crate::Lazy) and
+//! [`Mapping`](crate::Mapping).
+//!
+//! Like the [`Mapping`](crate::Mapping), the collections don't load their elements
+//! eagerly but read and write them from and to the contract storage on access.
+
+mod batch_cursor;
+mod binary_heap;
+mod bit_set;
+mod blob;
+mod btree_map;
+mod checkpoints;
+mod counter;
+mod cursor;
+mod deployer;
+mod deque;
+mod double_map;
+mod hash_set;
+mod incremental_merkle_tree;
+mod iterable_mapping;
+mod linked_list;
+mod multi_map;
+mod nonces;
+mod order_statistics_tree;
+mod snapshots;
+mod sparse_merkle_tree;
+mod storage_box;
+mod stream;
+mod string;
+mod timelocked;
+mod vec;
+mod versioned;
+
+#[doc(inline)]
+pub use self::{
+    batch_cursor::BatchCursor,
+    binary_heap::StorageBinaryHeap,
+    bit_set::StorageBitSet,
+    blob::StorageBlob,
+    btree_map::StorageBTreeMap,
+    checkpoints::Checkpoints,
+    counter::Counter,
+    cursor::{
+        Cursor,
+        Page,
+    },
+    deployer::Deployer,
+    deque::StorageDeque,
+    double_map::DoubleMap,
+    hash_set::{
+        CountedStorageHashSet,
+        StorageHashSet,
+    },
+    incremental_merkle_tree::{
+        MerkleHasher,
+        StorageIncrementalMerkleTree,
+    },
+    iterable_mapping::{
+        IndexOrder,
+        InsertionOrderIndex,
+        IterableMapping,
+        KeyOrderIndex,
+        UnorderedIndex,
+    },
+    linked_list::{
+        NodeHandle,
+        StorageLinkedList,
+    },
+    multi_map::MultiMap,
+    nonces::{
+        NonceError,
+        Nonces,
+        SigningPayload,
+    },
+    order_statistics_tree::StorageOrderStatisticsTree,
+    snapshots::Snapshots,
+    sparse_merkle_tree::{
+        MerkleProof,
+        NodeHash,
+        StorageSparseMerkleTree,
+    },
+    storage_box::StorageBox,
+    stream::{
+        Stream,
+        StreamError,
+    },
+    string::StorageString,
+    timelocked::{
+        Activation,
+        Timelocked,
+    },
+    vec::StorageVec,
+    versioned::{
+        Migrate,
+        Versioned,
+    },
+};
@@ -0,0 +1,420 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A mapping of keys to multiple values in contract storage.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this mapping doesn't actually "own" any data.
+//! The number of values of a key is stored under the root key of the mapping
+//! combined with the key while every value is stored under the root key combined
+//! with the key and the index of the value.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage slot of the number of values per key.
+const COUNT: u8 = 0;
+/// The storage slot of the values indexed by their position.
+const VALUES: u8 = 1;
+
+/// A mapping of keys to multiple values directly into contract storage.
+///
+/// Every key holds a list of values which can be enumerated without loading any
+/// other key, e.g. all order ids of an account. The values of a key are indexed
+/// from `0` to [`count`](MultiMap::count) exclusively.
+///
+/// # Important
+///
+/// The mapping requires its own pre-defined storage key where to store values. By default,
+/// the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey) during compilation.
+///
+/// This is an example of how you can use the mapping:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::MultiMap;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct OrderBook {
+///     orders: MultiMap<AccountId, u64>,
+/// }
+///
+/// impl OrderBook {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn place(&mut self, order_id: u64) {
+///         self.orders.insert(self.env().caller(), &order_id);
+///     }
+///
+///     #[ink(message)]
+///     pub fn orders(&self, owner: AccountId) -> Vec<u64> {
+///         self.orders.get_all(owner).collect()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MultiMap<K, V: Packed, KeyType: StorageKey = AutoKey> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K, V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<K, V, KeyType> Default for MultiMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KeyType> MultiMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `MultiMap`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K, V, KeyType> ::core::fmt::Debug for MultiMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("MultiMap")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<K, V, KeyType> MultiMap<K, V, KeyType>
+where
+    K: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Appends the given `value` to the values of `key`.
+    ///
+    /// Returns the index of the inserted value.
+    ///
+    /// # Panics
+    ///
+    /// If `key` already holds `u32::MAX` values.
+    #[inline]
+    pub fn insert<Q, R>(&mut self, key: Q, value: &R) -> u32
+    where
+        Q: scale::EncodeLike<K>,
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let index = self.stored_count(&key);
+        let count = index
+            .checked_add(1)
+            .expect("cannot insert more than u32::MAX values per key into MultiMap");
+        ink_env::set_contract_storage(&(&KeyType::KEY, VALUES, &key, index), value);
+        ink_env::set_contract_storage(&(&KeyType::KEY, COUNT, &key), &count);
+        index
+    }
+
+    /// Returns an iterator over all values of `key` in the order of their indices.
+    ///
+    /// Every value is loaded from storage only once the iterator reaches it.
+    pub fn get_all<'a, Q>(&'a self, key: Q) -> impl Iterator<Item = V> + 'a
+    where
+        Q: scale::EncodeLike<K> + 'a,
+    {
+        (0..self.stored_count(&key)).filter_map(move |index| self.value_at(&key, index))
+    }
+
+    /// Returns the value of `key` at `index` if any.
+    #[inline]
+    pub fn get<Q>(&self, key: Q, index: u32) -> Option<V>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        self.value_at(key, index)
+    }
+
+    /// Removes the value of `key` at `index` and returns it.
+    ///
+    /// Returns `None` if `key` holds no value at `index`.
+    ///
+    /// # Note
+    ///
+    /// The last value of `key` is moved to `index` so that the indices stay
+    /// contiguous. Hence, the index of the last value changes unless it is the
+    /// removed one.
+    pub fn remove<Q>(&mut self, key: Q, index: u32) -> Option<V>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        let count = self.stored_count(&key);
+        if index >= count {
+            return None
+        }
+        let last = count - 1;
+        let value = self.take_at(&key, last);
+        let value = if index == last {
+            value
+        } else {
+            let value = value.expect("the last value of a non-empty key must be stored");
+            let removed = self.take_at(&key, index);
+            ink_env::set_contract_storage(&(&KeyType::KEY, VALUES, &key, index), &value);
+            removed
+        };
+        if last == 0 {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, COUNT, &key));
+        } else {
+            ink_env::set_contract_storage(&(&KeyType::KEY, COUNT, &key), &last);
+        }
+        value
+    }
+
+    /// Returns the number of values of `key`.
+    #[inline]
+    pub fn count<Q>(&self, key: Q) -> u32
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        self.stored_count(key)
+    }
+
+    /// Returns the number of values of the encoded `key`.
+    fn stored_count<Q>(&self, key: Q) -> u32
+    where
+        Q: Encode,
+    {
+        ink_env::get_contract_storage::<_, u32>(&(&KeyType::KEY, COUNT, key))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get count of MultiMap: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the value of the encoded `key` at `index` if any.
+    fn value_at<Q>(&self, key: Q, index: u32) -> Option<V>
+    where
+        Q: Encode,
+    {
+        ink_env::get_contract_storage(&(&KeyType::KEY, VALUES, key, index))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get value in MultiMap: {:?}", error)
+            })
+    }
+
+    /// Takes the value of the encoded `key` at `index` out of storage.
+    fn take_at<Q>(&mut self, key: Q, index: u32) -> Option<V>
+    where
+        Q: Encode,
+    {
+        ink_env::take_contract_storage(&(&KeyType::KEY, VALUES, key, index))
+            .unwrap_or_else(|error| {
+                panic!("Failed to take value in MultiMap: {:?}", error)
+            })
+    }
+}
+
+impl<K, V, KeyType> Storable for MultiMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<K, V, Key, InnerKey> StorableHint<Key> for MultiMap<K, V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = MultiMap<K, V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<K, V, KeyType> StorageKey for MultiMap<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<K, V, KeyType> StorageLayout for MultiMap<K, V, KeyType>
+    where
+        K: scale_info::TypeInfo + 'static,
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn insert_and_get_all_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut map: MultiMap<u8, u64> = MultiMap::new();
+            assert_eq!(map.count(1), 0);
+            assert_eq!(map.get_all(1).next(), None);
+
+            assert_eq!(map.insert(1, &10), 0);
+            assert_eq!(map.insert(1, &11), 1);
+            assert_eq!(map.insert(2, &20), 0);
+
+            assert_eq!(map.count(1), 2);
+            assert_eq!(map.count(2), 1);
+            assert_eq!(map.get_all(1).collect::<Vec<_>>(), vec![10, 11]);
+            assert_eq!(map.get_all(2).collect::<Vec<_>>(), vec![20]);
+            assert_eq!(map.get(1, 1), Some(11));
+            assert_eq!(map.get(1, 2), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_moves_last_value() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut map: MultiMap<u8, u64, ManualKey<123>> = MultiMap::new();
+            for value in 0..4 {
+                map.insert(1, &value);
+            }
+
+            // When
+            assert_eq!(map.remove(1, 1), Some(1));
+            assert_eq!(map.remove(1, 4), None);
+
+            // Then
+            let map: MultiMap<u8, u64, ManualKey<123>> = MultiMap::new();
+            assert_eq!(map.count(1), 3);
+            assert_eq!(map.get_all(1).collect::<Vec<_>>(), vec![0, 3, 2]);
+
+            // When
+            let mut map = map;
+            assert_eq!(map.remove(1, 2), Some(2));
+
+            // Then
+            assert_eq!(map.get_all(1).collect::<Vec<_>>(), vec![0, 3]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn removing_all_values_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut map: MultiMap<u8, u64> = MultiMap::new();
+            map.insert(1, &10);
+            map.insert(1, &11);
+
+            // When
+            assert_eq!(map.remove(1, 0), Some(10));
+            assert_eq!(map.remove(1, 0), Some(11));
+
+            // Then
+            assert_eq!(map.count(1), 0);
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn get_all_loads_values_lazily() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut map: MultiMap<u8, u64> = MultiMap::new();
+            for value in 0..10 {
+                map.insert(1, &value);
+            }
+            let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+
+            // When
+            assert_eq!(map.get_all(1).next(), Some(0));
+
+            // Then
+            let (reads_after, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+            // Only the count and the first value are read.
+            assert_eq!(reads_after - reads, 2);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
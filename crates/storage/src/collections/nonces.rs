@@ -0,0 +1,433 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-account nonces protecting signed payloads, e.g. of meta-transactions,
+//! against replays.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), the nonces don't actually "own" any data.
+//! The nonce of an account is stored under the root key combined with the account
+//! once it was used for the first time.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::{
+    hash::{
+        Blake2x256,
+        CryptoHash,
+    },
+    Environment,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Encode,
+    EncodeLike,
+    Error,
+    Input,
+    Output,
+};
+
+/// Returned if a nonce was rejected by [`Nonces::check_and_increment`].
+///
+/// The stored nonce is left untouched in this case.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NonceError {
+    /// The nonce is not the next nonce of the account, e.g. because the payload was
+    /// already used.
+    Mismatch {
+        /// The next nonce of the account.
+        current: u64,
+    },
+    /// The account used up all nonces.
+    Exhausted,
+}
+
+/// The next nonce to use per account `A`, starting at zero.
+///
+/// A signed payload, e.g. of a meta-transaction, contains the nonce of its signer,
+/// which is consumed by [`check_and_increment`](Self::check_and_increment) when the
+/// payload is executed. Hence every payload can be executed at most once and only
+/// in the order of the nonces. Use a [`SigningPayload`] to bind the payload also to
+/// the chain and the contract.
+///
+/// # Important
+///
+/// The nonces require their own pre-defined storage key where to store the nonces.
+/// By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the nonces:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     collections::{
+///         NonceError,
+///         Nonces,
+///         SigningPayload,
+///     },
+///     Mapping,
+/// };
+///
+/// /// The chain the signatures are valid for.
+/// const CHAIN_ID: u32 = 42;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Relayed {
+///     nonces: Nonces<[u8; 20]>,
+///     values: Mapping<[u8; 20], u32>,
+/// }
+///
+/// impl Relayed {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     /// Sets the value of the Ethereum address of `signer` as signed by it.
+///     #[ink(message)]
+///     pub fn set_for(
+///         &mut self,
+///         signer: [u8; 33],
+///         value: u32,
+///         nonce: u64,
+///         signature: [u8; 65],
+///     ) -> Result<(), NonceError> {
+///         let payload = SigningPayload::new::<Environment>(CHAIN_ID, nonce, value);
+///         assert!(payload.verify_ecdsa(&signature, &signer), "invalid signature");
+///         let mut address = [0; 20];
+///         ink::env::ecdsa_to_eth_address(&signer, &mut address)
+///             .expect("the signer is a valid public key");
+///         self.nonces.check_and_increment(address, nonce)?;
+///         self.values.insert(address, &value);
+///         Ok(())
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Nonces<A, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (A, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<A, KeyType> Default for Nonces<A, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, KeyType> Nonces<A, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates new `Nonces`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<A, KeyType> ::core::fmt::Debug for Nonces<A, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Nonces")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<A, KeyType> Nonces<A, KeyType>
+where
+    A: Packed,
+    KeyType: StorageKey,
+{
+    /// Returns the next nonce of `account`.
+    #[inline]
+    pub fn current<Q>(&self, account: Q) -> u64
+    where
+        Q: EncodeLike<A>,
+    {
+        ink_env::get_contract_storage::<_, u64>(&(&KeyType::KEY, account))
+            .unwrap_or_else(|error| panic!("Failed to get nonce of Nonces: {:?}", error))
+            .unwrap_or(0)
+    }
+
+    /// Consumes the `expected` nonce of `account`.
+    ///
+    /// # Errors
+    ///
+    /// - If `expected` is not the next nonce of `account`.
+    /// - If `account` used up all nonces.
+    pub fn check_and_increment<Q>(
+        &mut self,
+        account: Q,
+        expected: u64,
+    ) -> Result<(), NonceError>
+    where
+        Q: EncodeLike<A> + Copy,
+    {
+        let current = self.current(account);
+        if current != expected {
+            return Err(NonceError::Mismatch { current })
+        }
+        let next = current.checked_add(1).ok_or(NonceError::Exhausted)?;
+        ink_env::set_contract_storage(&(&KeyType::KEY, account), &next);
+        Ok(())
+    }
+}
+
+impl<A, KeyType> Storable for Nonces<A, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<A, Key, InnerKey> StorableHint<Key> for Nonces<A, InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Nonces<A, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<A, KeyType> StorageKey for Nonces<A, KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<A, KeyType> StorageLayout for Nonces<A, KeyType>
+    where
+        A: scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <u64 as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+/// The message an account signs to authorize a `payload`, bound to a chain, a
+/// contract and a nonce of the account.
+///
+/// Binding the payload to the chain and the contract keeps a signature from being
+/// replayed on another chain or against another contract, while the nonce, checked
+/// with [`Nonces::check_and_increment`], keeps it from being replayed against the
+/// same contract.
+#[derive(Debug, Clone, PartialEq, Eq, Encode)]
+pub struct SigningPayload<C, A, T> {
+    /// The identifier of the chain, e.g. its genesis hash.
+    pub chain_id: C,
+    /// The contract executing the payload.
+    pub contract: A,
+    /// The nonce of the signer.
+    pub nonce: u64,
+    /// The payload to execute.
+    pub payload: T,
+}
+
+impl<C, A, T> SigningPayload<C, A, T> {
+    /// Returns the message to sign to authorize `payload` with `nonce` against the
+    /// executing contract on the chain `chain_id`.
+    pub fn new<E>(chain_id: C, nonce: u64, payload: T) -> Self
+    where
+        E: Environment<AccountId = A>,
+    {
+        Self {
+            chain_id,
+            contract: ink_env::account_id::<E>(),
+            nonce,
+            payload,
+        }
+    }
+}
+
+impl<C, A, T> SigningPayload<C, A, T>
+where
+    C: Encode,
+    A: Encode,
+    T: Encode,
+{
+    /// Returns the BLAKE2 256-bit hash of the encoded message, which is signed.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut output = [0; 32];
+        <Blake2x256 as CryptoHash>::hash(&self.encode(), &mut output);
+        output
+    }
+
+    /// Returns `true` if `signature` is an ECDSA signature of the [`hash`](Self::hash)
+    /// of the message by the compressed public key `signer`.
+    pub fn verify_ecdsa(&self, signature: &[u8; 65], signer: &[u8; 33]) -> bool {
+        let mut recovered = [0; 33];
+        ink_env::ecdsa_recover(signature, &self.hash(), &mut recovered).is_ok()
+            && &recovered == signer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+    use ink_env::{
+        test,
+        DefaultEnvironment,
+    };
+    use ink_primitives::AccountId;
+
+    #[test]
+    fn check_and_increment_consumes_nonces() {
+        test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut nonces: Nonces<AccountId, ManualKey<123>> = Nonces::new();
+            assert_eq!(nonces.current(accounts.alice), 0);
+
+            assert_eq!(nonces.check_and_increment(accounts.alice, 0), Ok(()));
+            assert_eq!(nonces.current(accounts.alice), 1);
+            // Replaying the nonce fails, as does skipping one.
+            assert_eq!(
+                nonces.check_and_increment(accounts.alice, 0),
+                Err(NonceError::Mismatch { current: 1 })
+            );
+            assert_eq!(
+                nonces.check_and_increment(accounts.alice, 2),
+                Err(NonceError::Mismatch { current: 1 })
+            );
+            assert_eq!(nonces.check_and_increment(accounts.alice, 1), Ok(()));
+
+            // The nonces of other accounts are independent.
+            assert_eq!(nonces.current(accounts.bob), 0);
+            assert_eq!(nonces.check_and_increment(accounts.bob, 0), Ok(()));
+            assert_eq!(nonces.current(accounts.alice), 2);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn exhausted_nonces_are_rejected() {
+        test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut nonces: Nonces<AccountId, ManualKey<123>> = Nonces::new();
+            ink_env::set_contract_storage(&(123u32, accounts.alice), &u64::MAX);
+            assert_eq!(
+                nonces.check_and_increment(accounts.alice, u64::MAX),
+                Err(NonceError::Exhausted)
+            );
+            assert_eq!(nonces.current(accounts.alice), u64::MAX);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn signing_payload_is_bound_to_context() {
+        test::run_test::<DefaultEnvironment, _>(|accounts| {
+            test::set_callee::<DefaultEnvironment>(accounts.django);
+            let payload = SigningPayload::new::<DefaultEnvironment>(1u32, 0, 42u32);
+            assert_eq!(payload.contract, accounts.django);
+
+            let other_chain = SigningPayload {
+                chain_id: 2u32,
+                ..payload.clone()
+            };
+            let other_nonce = SigningPayload {
+                nonce: 1,
+                ..payload.clone()
+            };
+            let other_contract = SigningPayload {
+                contract: accounts.eve,
+                ..payload.clone()
+            };
+            assert_ne!(payload.hash(), other_chain.hash());
+            assert_ne!(payload.hash(), other_nonce.hash());
+            assert_ne!(payload.hash(), other_contract.hash());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_ecdsa_works() {
+        use secp256k1::{
+            Message,
+            PublicKey,
+            SecretKey,
+            SECP256K1,
+        };
+
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let secret = SecretKey::from_slice(&[0x01; 32]).unwrap();
+            let signer = PublicKey::from_secret_key(SECP256K1, &secret).serialize();
+            let sign = |payload: &SigningPayload<u32, AccountId, u32>| {
+                let message = Message::from_slice(&payload.hash()).unwrap();
+                let (recovery_id, compact) = SECP256K1
+                    .sign_ecdsa_recoverable(&message, &secret)
+                    .serialize_compact();
+                let mut signature = [0; 65];
+                signature[..64].copy_from_slice(&compact);
+                signature[64] = recovery_id.to_i32() as u8;
+                signature
+            };
+
+            let payload = SigningPayload::new::<DefaultEnvironment>(1u32, 0, 42u32);
+            let signature = sign(&payload);
+            assert!(payload.verify_ecdsa(&signature, &signer));
+
+            let replayed = SigningPayload {
+                nonce: 1,
+                ..payload
+            };
+            assert!(!replayed.verify_ecdsa(&signature, &signer));
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,669 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A weighted order-statistics tree in contract storage backed by an AVL tree.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this tree doesn't actually "own" any data.
+//! The link to the root node is stored under the root key of the tree while every
+//! node is stored under the root key combined with its value.
+
+use crate::{
+    traits::{
+        AutoKey,
+        Packed,
+        StorableHint,
+        StorageKey,
+    },
+    ArithmeticError,
+};
+use core::{
+    cmp::Ordering,
+    marker::PhantomData,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Decode,
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage slot of the link to the root node.
+const ROOT: u8 = 0;
+/// The storage slot of the nodes.
+const NODES: u8 = 1;
+
+/// A link from a parent to a child node, summarizing the subtree of the child.
+///
+/// Keeping the summary in the parent allows to balance the tree and to select by
+/// weight without loading the children.
+#[derive(Debug, Encode, Decode)]
+struct Link<V> {
+    /// The value of the child node.
+    value: V,
+    /// The total weight of the subtree.
+    total: u128,
+    /// The height of the subtree.
+    height: u8,
+}
+
+/// A node of the AVL tree, stored under its value.
+#[derive(Debug, Encode, Decode)]
+struct Node<V> {
+    left: Option<Link<V>>,
+    right: Option<Link<V>>,
+    /// The weight of the value of the node.
+    weight: u128,
+}
+
+/// Returns the total weight of the subtree behind `link`.
+fn total<V>(link: &Option<Link<V>>) -> u128 {
+    link.as_ref().map_or(0, |link| link.total)
+}
+
+/// Returns the height of the subtree behind `link`.
+fn height<V>(link: &Option<Link<V>>) -> u8 {
+    link.as_ref().map_or(0, |link| link.height)
+}
+
+/// A multiset of weighted values directly in contract storage, which selects values
+/// by their rank of weight, e.g. the median of the prices reported to an oracle.
+///
+/// Inserting and removing weight of a value as well as selecting a value by a
+/// percentile of the total weight only loads the nodes on the path to the value.
+/// The values are kept in a balanced AVL tree, hence the path has a length of at
+/// most `1.44 * log2(n)` for `n` distinct values, no matter in which order they
+/// were inserted. The weights of equal values are summed up in a single node.
+///
+/// # Important
+///
+/// The tree requires its own pre-defined storage key where to store its nodes.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the tree:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     collections::StorageOrderStatisticsTree,
+///     Mapping,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Oracle {
+///     /// The latest price reported by every reporter.
+///     reports: Mapping<AccountId, Balance>,
+///     /// The number of reports of every price.
+///     prices: StorageOrderStatisticsTree<Balance>,
+/// }
+///
+/// impl Oracle {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn report(&mut self, price: Balance) {
+///         let caller = self.env().caller();
+///         if let Some(previous) = self.reports.get(caller) {
+///             self.prices.remove(&previous, 1).unwrap();
+///         }
+///         self.reports.insert(caller, &price);
+///         self.prices.insert(&price, 1).unwrap();
+///     }
+///
+///     #[ink(message)]
+///     pub fn median(&self) -> Option<Balance> {
+///         self.prices.median()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageOrderStatisticsTree<V: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<V, KeyType> Default for StorageOrderStatisticsTree<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> StorageOrderStatisticsTree<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageOrderStatisticsTree`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageOrderStatisticsTree<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageOrderStatisticsTree")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<V, KeyType> StorageOrderStatisticsTree<V, KeyType>
+where
+    V: Packed + Ord + Clone,
+    KeyType: StorageKey,
+{
+    /// Returns the total weight of all values.
+    #[inline]
+    pub fn total_weight(&self) -> u128 {
+        total(&self.root())
+    }
+
+    /// Returns `true` if the tree holds no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.root().is_none()
+    }
+
+    /// Returns the weight of `value`, `0` if the tree doesn't hold it.
+    pub fn weight(&self, value: &V) -> u128 {
+        self.node(value).map_or(0, |node| node.weight)
+    }
+
+    /// Adds `weight` to the weight of `value`.
+    ///
+    /// # Errors
+    ///
+    /// If the total weight of all values would overflow. The tree is left unchanged
+    /// in this case.
+    pub fn insert(&mut self, value: &V, weight: u128) -> Result<(), ArithmeticError> {
+        if weight == 0 {
+            return Ok(())
+        }
+        let root = self.root();
+        total(&root)
+            .checked_add(weight)
+            .ok_or(ArithmeticError::Overflow)?;
+        let root = self.insert_into(root, value, weight);
+        self.set_root(&Some(root));
+        Ok(())
+    }
+
+    /// Subtracts `weight` from the weight of `value` and removes the value once its
+    /// weight drops to zero.
+    ///
+    /// # Errors
+    ///
+    /// If the weight of `value` is less than `weight`. The tree is left unchanged
+    /// in this case.
+    pub fn remove(&mut self, value: &V, weight: u128) -> Result<(), ArithmeticError> {
+        if weight == 0 {
+            return Ok(())
+        }
+        if self.weight(value) < weight {
+            return Err(ArithmeticError::Underflow)
+        }
+        let root = self.root().expect("a tree holding a value has a root");
+        let root = self.remove_from(root, value, weight);
+        self.set_root(&root);
+        Ok(())
+    }
+
+    /// Returns the value at `percentile` of the total weight, `None` if the tree is
+    /// empty.
+    ///
+    /// This is the least value such that the values up to it hold at least
+    /// `percentile` percent of the total weight, e.g. the lower weighted median for
+    /// a `percentile` of 50, the least value for 0 and the greatest for 100.
+    ///
+    /// # Panics
+    ///
+    /// If `percentile` is greater than 100.
+    pub fn select(&self, percentile: u8) -> Option<V> {
+        assert!(
+            percentile <= 100,
+            "percentile must be at most 100 but is {}",
+            percentile
+        );
+        let total = self.total_weight();
+        let percentile = u128::from(percentile);
+        // Computes `ceil(total * percentile / 100)` without overflowing.
+        let weight = total / 100 * percentile + (total % 100 * percentile + 99) / 100;
+        self.select_by_weight(weight.saturating_sub(1))
+    }
+
+    /// Returns the lower weighted median, `None` if the tree is empty.
+    #[inline]
+    pub fn median(&self) -> Option<V> {
+        self.select(50)
+    }
+
+    /// Returns the value holding the unit of weight at position `rank` when all
+    /// units of weight are lined up in ascending order of their values, `None` if
+    /// `rank` is not less than the total weight.
+    pub fn select_by_weight(&self, mut rank: u128) -> Option<V> {
+        let mut next = self.root();
+        while let Some(link) = next {
+            let node = self.stored_node(&link.value);
+            let left = total(&node.left);
+            if rank < left {
+                next = node.left;
+            } else if rank - left < node.weight {
+                return Some(link.value)
+            } else {
+                rank -= left + node.weight;
+                next = node.right;
+            }
+        }
+        None
+    }
+
+    /// Returns the link to the root node if the tree isn't empty.
+    fn root(&self) -> Option<Link<V>> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, ROOT))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Failed to get root of StorageOrderStatisticsTree: {:?}",
+                    error
+                )
+            })
+            .flatten()
+    }
+
+    /// Stores the link to the root node.
+    fn set_root(&mut self, root: &Option<Link<V>>) {
+        match root {
+            Some(_) => {
+                ink_env::set_contract_storage(&(&KeyType::KEY, ROOT), root);
+            }
+            None => {
+                ink_env::clear_contract_storage(&(&KeyType::KEY, ROOT));
+            }
+        }
+    }
+
+    /// Returns the node of `value` if the tree holds it.
+    fn node(&self, value: &V) -> Option<Node<V>> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, NODES, value)).unwrap_or_else(
+            |error| {
+                panic!(
+                    "Failed to get node of StorageOrderStatisticsTree: {:?}",
+                    error
+                )
+            },
+        )
+    }
+
+    /// Returns the node of `value` which is linked from its parent.
+    fn stored_node(&self, value: &V) -> Node<V> {
+        self.node(value)
+            .expect("the linked nodes of a tree must be stored")
+    }
+
+    /// Stores `node` under `value` and returns the link to it.
+    fn store(&mut self, value: V, node: &Node<V>) -> Link<V> {
+        ink_env::set_contract_storage(&(&KeyType::KEY, NODES, &value), node);
+        Link {
+            value,
+            total: node.weight + total(&node.left) + total(&node.right),
+            height: 1 + height(&node.left).max(height(&node.right)),
+        }
+    }
+
+    /// Adds `weight` to `value` in the subtree behind `link` and returns the link
+    /// to the balanced subtree.
+    fn insert_into(&mut self, link: Option<Link<V>>, value: &V, weight: u128) -> Link<V> {
+        let link = match link {
+            Some(link) => link,
+            None => {
+                let leaf = Node {
+                    left: None,
+                    right: None,
+                    weight,
+                };
+                return self.store(value.clone(), &leaf)
+            }
+        };
+        let mut node = self.stored_node(&link.value);
+        match value.cmp(&link.value) {
+            Ordering::Less => {
+                node.left = Some(self.insert_into(node.left.take(), value, weight));
+            }
+            Ordering::Greater => {
+                node.right = Some(self.insert_into(node.right.take(), value, weight));
+            }
+            Ordering::Equal => node.weight += weight,
+        }
+        self.balance(link.value, node)
+    }
+
+    /// Subtracts `weight` from `value` in the subtree behind `link` and returns the
+    /// link to the balanced subtree if it isn't empty afterwards.
+    ///
+    /// The subtree must hold at least `weight` of `value`.
+    fn remove_from(&mut self, link: Link<V>, value: &V, weight: u128) -> Option<Link<V>> {
+        const HELD: &str = "the subtree holds the removed value";
+        let mut node = self.stored_node(&link.value);
+        match value.cmp(&link.value) {
+            Ordering::Less => {
+                node.left =
+                    self.remove_from(node.left.take().expect(HELD), value, weight);
+            }
+            Ordering::Greater => {
+                node.right =
+                    self.remove_from(node.right.take().expect(HELD), value, weight);
+            }
+            Ordering::Equal if node.weight > weight => node.weight -= weight,
+            Ordering::Equal => {
+                ink_env::clear_contract_storage(&(&KeyType::KEY, NODES, &link.value));
+                return match (node.left, node.right) {
+                    (None, None) => None,
+                    (Some(child), None) | (None, Some(child)) => Some(child),
+                    (left, Some(right)) => {
+                        // The least value of the right subtree takes the place of
+                        // the removed one.
+                        let (right, successor, weight) = self.take_min(right);
+                        let node = Node {
+                            left,
+                            right,
+                            weight,
+                        };
+                        Some(self.balance(successor, node))
+                    }
+                }
+            }
+        }
+        Some(self.balance(link.value, node))
+    }
+
+    /// Removes the least value from the subtree behind `link`.
+    ///
+    /// Returns the link to the balanced subtree if it isn't empty afterwards
+    /// together with the removed value and its weight.
+    fn take_min(&mut self, link: Link<V>) -> (Option<Link<V>>, V, u128) {
+        let mut node = self.stored_node(&link.value);
+        match node.left.take() {
+            Some(left) => {
+                let (left, min, weight) = self.take_min(left);
+                node.left = left;
+                (Some(self.balance(link.value, node)), min, weight)
+            }
+            None => {
+                ink_env::clear_contract_storage(&(&KeyType::KEY, NODES, &link.value));
+                (node.right, link.value, node.weight)
+            }
+        }
+    }
+
+    /// Stores `node` under `value` after restoring the AVL property by rotations
+    /// and returns the link to the balanced subtree.
+    ///
+    /// The heights of the subtrees of `node` may differ by at most two.
+    fn balance(&mut self, value: V, mut node: Node<V>) -> Link<V> {
+        const HIGHER: &str = "the higher subtree is not empty";
+        let (value, node) = if height(&node.left) > height(&node.right) + 1 {
+            let child = node.left.take().expect(HIGHER).value;
+            let mut child_node = self.stored_node(&child);
+            let (child, child_node) =
+                if height(&child_node.left) < height(&child_node.right) {
+                    let grandchild = child_node.right.take().expect(HIGHER).value;
+                    let grandchild_node = self.stored_node(&grandchild);
+                    self.rotate_left(child, child_node, grandchild, grandchild_node)
+                } else {
+                    (child, child_node)
+                };
+            self.rotate_right(value, node, child, child_node)
+        } else if height(&node.right) > height(&node.left) + 1 {
+            let child = node.right.take().expect(HIGHER).value;
+            let mut child_node = self.stored_node(&child);
+            let (child, child_node) =
+                if height(&child_node.right) < height(&child_node.left) {
+                    let grandchild = child_node.left.take().expect(HIGHER).value;
+                    let grandchild_node = self.stored_node(&grandchild);
+                    self.rotate_right(child, child_node, grandchild, grandchild_node)
+                } else {
+                    (child, child_node)
+                };
+            self.rotate_left(value, node, child, child_node)
+        } else {
+            (value, node)
+        };
+        self.store(value, &node)
+    }
+
+    /// Makes `node` the left child of its former right child `pivot`, which has
+    /// already been detached from `node`, and returns the not yet stored `pivot`.
+    fn rotate_left(
+        &mut self,
+        value: V,
+        mut node: Node<V>,
+        pivot: V,
+        mut pivot_node: Node<V>,
+    ) -> (V, Node<V>) {
+        node.right = pivot_node.left.take();
+        pivot_node.left = Some(self.store(value, &node));
+        (pivot, pivot_node)
+    }
+
+    /// Makes `node` the right child of its former left child `pivot`, which has
+    /// already been detached from `node`, and returns the not yet stored `pivot`.
+    fn rotate_right(
+        &mut self,
+        value: V,
+        mut node: Node<V>,
+        pivot: V,
+        mut pivot_node: Node<V>,
+    ) -> (V, Node<V>) {
+        node.left = pivot_node.right.take();
+        pivot_node.right = Some(self.store(value, &node));
+        (pivot, pivot_node)
+    }
+}
+
+impl<V, KeyType> Storable for StorageOrderStatisticsTree<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageOrderStatisticsTree<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageOrderStatisticsTree<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageOrderStatisticsTree<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageOrderStatisticsTree<V, KeyType>
+    where
+        V: Packed + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <u128 as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    type Tree = StorageOrderStatisticsTree<u32, ManualKey<123>>;
+
+    /// Returns the height of the subtree behind `link` after checking that it is
+    /// balanced and its links summarize it correctly.
+    fn check(tree: &Tree, link: &Option<Link<u32>>) -> u8 {
+        let link = match link {
+            Some(link) => link,
+            None => return 0,
+        };
+        let node = tree.stored_node(&link.value);
+        let (left, right) = (check(tree, &node.left), check(tree, &node.right));
+        assert!(left.abs_diff(right) <= 1);
+        assert_eq!(link.height, 1 + left.max(right));
+        assert_eq!(
+            link.total,
+            node.weight + total(&node.left) + total(&node.right)
+        );
+        link.height
+    }
+
+    #[test]
+    fn select_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut tree = Tree::new();
+            assert_eq!(tree.median(), None);
+
+            tree.insert(&30, 1).unwrap();
+            tree.insert(&10, 1).unwrap();
+            tree.insert(&20, 2).unwrap();
+
+            assert_eq!(tree.total_weight(), 4);
+            assert_eq!(tree.weight(&20), 2);
+            assert_eq!(tree.select(0), Some(10));
+            assert_eq!(tree.select(25), Some(10));
+            assert_eq!(tree.select(26), Some(20));
+            assert_eq!(tree.median(), Some(20));
+            assert_eq!(tree.select(75), Some(20));
+            assert_eq!(tree.select(76), Some(30));
+            assert_eq!(tree.select(100), Some(30));
+            assert_eq!(tree.select_by_weight(3), Some(30));
+            assert_eq!(tree.select_by_weight(4), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn stays_balanced_and_matches_a_sorted_vec() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            let mut tree = Tree::new();
+            let mut expected = Vec::new();
+            let mut seed = 42u64;
+            let mut values = Vec::new();
+            for i in 0..300u32 {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                // Ascending values degrade unbalanced trees into lists.
+                let value = if i < 100 {
+                    i
+                } else {
+                    (seed >> 33) as u32 % 200
+                };
+                let weight = u128::from(i % 3 + 1);
+                tree.insert(&value, weight).unwrap();
+                values.push((value, weight));
+                expected.extend(core::iter::repeat(value).take(weight as usize));
+            }
+            expected.sort_unstable();
+            assert!(check(&tree, &tree.root()) <= 12);
+            for (rank, value) in expected.iter().enumerate() {
+                assert_eq!(tree.select_by_weight(rank as u128), Some(*value));
+            }
+
+            for (value, weight) in values.iter().step_by(2) {
+                tree.remove(value, *weight).unwrap();
+                let index = expected.partition_point(|other| other < value);
+                expected.drain(index..index + *weight as usize);
+            }
+            assert!(check(&tree, &tree.root()) <= 12);
+            for (rank, value) in expected.iter().enumerate() {
+                assert_eq!(tree.select_by_weight(rank as u128), Some(*value));
+            }
+
+            for (value, weight) in values.iter().skip(1).step_by(2) {
+                tree.remove(value, *weight).unwrap();
+            }
+            assert!(tree.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn invalid_weight_changes_are_rejected() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut tree = Tree::new();
+            tree.insert(&1, u128::MAX - 1).unwrap();
+
+            assert_eq!(tree.insert(&2, 2), Err(ArithmeticError::Overflow));
+            assert_eq!(tree.remove(&1, u128::MAX), Err(ArithmeticError::Underflow));
+            assert_eq!(tree.remove(&2, 1), Err(ArithmeticError::Underflow));
+            assert_eq!(tree.total_weight(), u128::MAX - 1);
+            assert_eq!(tree.select(100), Some(1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Values of a storage item frozen at numbered snapshots, e.g. per proposal.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), the snapshots don't actually "own" any data.
+//! The ID below which all snapshots are pruned is stored under the root key while
+//! every snapshot is stored under the root key combined with its ID.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The values of a storage item recorded at numbered snapshots.
+///
+/// A snapshot freezes a value under an ID once, e.g. the voting parameters at the
+/// creation of a proposal, so that later changes of the parameters don't affect
+/// running proposals. Only the recorded value is copied, no matter how large the
+/// storage it was derived from is. Snapshots which are no longer needed can be
+/// pruned to free their storage.
+///
+/// # Important
+///
+/// The snapshots require their own pre-defined storage key where to store the
+/// values. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the snapshots:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     collections::Snapshots,
+///     Lazy,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Governance {
+///     quorum: Lazy<u32>,
+///     next_proposal: u32,
+///     quorums: Snapshots<u32>,
+/// }
+///
+/// impl Governance {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn propose(&mut self) -> u32 {
+///         let id = self.next_proposal;
+///         self.next_proposal += 1;
+///         self.quorums.record(id, &self.quorum.get_or_default());
+///         id
+///     }
+///
+///     #[ink(message)]
+///     pub fn quorum_of(&self, proposal: u32) -> Option<u32> {
+///         self.quorums.get(proposal)
+///     }
+///
+///     #[ink(message)]
+///     pub fn finish_until(&mut self, proposal: u32) {
+///         self.quorums.prune_before(proposal);
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Snapshots<T: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (T, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<T, KeyType> Default for Snapshots<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, KeyType> Snapshots<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates new empty `Snapshots`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, KeyType> ::core::fmt::Debug for Snapshots<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Snapshots")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<T, KeyType> Snapshots<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    /// Records `value` at the snapshot `id`.
+    ///
+    /// Returns `false` without changing anything if there already is a snapshot
+    /// `id` or it has been pruned, so a recorded value stays frozen.
+    pub fn record<R>(&mut self, id: u32, value: &R) -> bool
+    where
+        R: Storable + scale::EncodeLike<T>,
+    {
+        if id < self.pruned() || self.contains(id) {
+            return false
+        }
+        ink_env::set_contract_storage(&(&KeyType::KEY, id), value);
+        true
+    }
+
+    /// Returns the value recorded at the snapshot `id`.
+    ///
+    /// Returns `None` if there is no such snapshot.
+    #[inline]
+    pub fn get(&self, id: u32) -> Option<T> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, id)).unwrap_or_else(|error| {
+            panic!("Failed to get snapshot of Snapshots: {:?}", error)
+        })
+    }
+
+    /// Returns `true` if there is a snapshot `id`.
+    #[inline]
+    pub fn contains(&self, id: u32) -> bool {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, id)).is_some()
+    }
+
+    /// Returns the ID below which all snapshots are pruned.
+    #[inline]
+    pub fn pruned(&self) -> u32 {
+        ink_env::get_contract_storage::<Key, u32>(&KeyType::KEY)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get pruned ID of Snapshots: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Removes the snapshot `id`.
+    ///
+    /// In contrast to [`prune_before`](Self::prune_before), the snapshot `id` can be
+    /// recorded again afterwards.
+    #[inline]
+    pub fn remove(&mut self, id: u32) {
+        ink_env::clear_contract_storage(&(&KeyType::KEY, id));
+    }
+
+    /// Removes all snapshots with an ID less than `id`, which can't be recorded
+    /// anymore afterwards.
+    ///
+    /// Returns the number of removed snapshots.
+    ///
+    /// # Note
+    ///
+    /// Every ID since the previous pruning is cleared, hence the gas costs grow with
+    /// the distance between the IDs. Prune regularly, e.g. whenever a proposal is
+    /// finished, to keep the costs of a single call low.
+    pub fn prune_before(&mut self, id: u32) -> u32 {
+        let pruned = self.pruned();
+        if id <= pruned {
+            return 0
+        }
+        let removed = (pruned..id)
+            .filter(|id| ink_env::clear_contract_storage(&(&KeyType::KEY, *id)).is_some())
+            .count();
+        ink_env::set_contract_storage(&KeyType::KEY, &id);
+        removed as u32
+    }
+}
+
+impl<T, KeyType> Storable for Snapshots<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<T, Key, InnerKey> StorableHint<Key> for Snapshots<T, InnerKey>
+where
+    T: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Snapshots<T, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<T, KeyType> StorageKey for Snapshots<T, KeyType>
+where
+    T: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<T, KeyType> StorageLayout for Snapshots<T, KeyType>
+    where
+        T: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <T as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn recorded_values_stay_frozen() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut snapshots: Snapshots<u8, ManualKey<123>> = Snapshots::new();
+
+            assert!(snapshots.record(1, &10));
+            assert!(!snapshots.record(1, &11));
+            assert!(snapshots.record(3, &30));
+
+            assert_eq!(snapshots.get(1), Some(10));
+            assert_eq!(snapshots.get(2), None);
+            assert!(snapshots.contains(3));
+
+            snapshots.remove(1);
+            assert!(!snapshots.contains(1));
+            assert!(snapshots.record(1, &12));
+            assert_eq!(snapshots.get(1), Some(12));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn prune_before_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            let mut snapshots: Snapshots<u8, ManualKey<123>> = Snapshots::new();
+            for id in [0, 2, 3, 5] {
+                snapshots.record(id, &(id as u8));
+            }
+
+            assert_eq!(snapshots.prune_before(3), 2);
+            assert_eq!(snapshots.pruned(), 3);
+            assert_eq!(snapshots.prune_before(2), 0);
+            assert!(!snapshots.record(1, &1));
+            assert_eq!(snapshots.get(3), Some(3));
+
+            assert_eq!(snapshots.prune_before(6), 2);
+            // Only the pruned ID remains.
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(1)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
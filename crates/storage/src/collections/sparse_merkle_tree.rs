@@ -0,0 +1,506 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A sparse Merkle tree in contract storage committing to a set of key-value pairs.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this tree doesn't actually "own" any data.
+//! The root hash is stored under the root key of the tree while every non-empty node
+//! is stored under the root key combined with its depth and the path leading to it.
+//! The values themselves are not stored, only their hashes become part of the tree.
+
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::hash::{
+    Blake2x256,
+    HashOutput,
+};
+use ink_prelude::vec::Vec;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Decode,
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// A hash of a node of a [`StorageSparseMerkleTree`].
+pub type NodeHash = <Blake2x256 as HashOutput>::Type;
+
+/// The storage slot of the root hash.
+const ROOT: u8 = 0;
+/// The storage slot of the nodes below the root.
+const NODES: u8 = 1;
+
+/// The number of bits of the path of a key, i.e. the depth of the leaves.
+const DEPTH: usize = 256;
+
+/// The hash of every empty subtree.
+const EMPTY: NodeHash = [0; 32];
+
+/// A sparse Merkle tree of key-value pairs directly in contract storage.
+///
+/// The tree commits to the values of all keys in a single root hash, which is
+/// updated incrementally on every insertion. A [`MerkleProof`] created with
+/// [`StorageSparseMerkleTree::prove`] shows that a key has a value, or no value at
+/// all, under a root hash, e.g. for bridges which relay the root to another chain.
+///
+/// Every key is hashed into a path of 256 bits from the root to its leaf. The hash
+/// of an empty subtree is zero, so only the nodes on the paths of inserted keys are
+/// stored. Updating a key reads and writes the 256 nodes on its path.
+///
+/// # Important
+///
+/// The tree requires its own pre-defined storage key where to store its nodes.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the tree:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::{
+///     MerkleProof,
+///     StorageSparseMerkleTree,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Commitments {
+///     balances: StorageSparseMerkleTree<AccountId, Balance>,
+/// }
+///
+/// impl Commitments {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn commit(&mut self, value: Balance) -> [u8; 32] {
+///         self.balances.insert(&self.env().caller(), &value);
+///         self.balances.root()
+///     }
+///
+///     #[ink(message)]
+///     pub fn prove(&self, owner: AccountId) -> MerkleProof {
+///         self.balances.prove(&owner)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageSparseMerkleTree<K, V, KeyType: StorageKey = AutoKey> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K, V, KeyType)>,
+}
+
+/// A proof that a key has a value, or no value, under the root of a
+/// [`StorageSparseMerkleTree`].
+///
+/// Only the hashes of the non-empty siblings on the path of the key are contained.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MerkleProof {
+    /// A bit per depth of the path, set if the sibling at that depth is non-empty.
+    non_empty: [u8; 32],
+    /// The hashes of the non-empty siblings from the leaf up to the root.
+    siblings: Vec<NodeHash>,
+}
+
+impl MerkleProof {
+    /// Returns `true` if `key` has the `value` under `root`.
+    ///
+    /// A `value` of `None` checks that the key has no value.
+    pub fn verify<K, V>(&self, root: &NodeHash, key: &K, value: Option<&V>) -> bool
+    where
+        K: Encode,
+        V: Encode,
+    {
+        let path = hash(key);
+        let mut siblings = self.siblings.iter();
+        let mut node = leaf_hash(value);
+        for depth in (1..=DEPTH).rev() {
+            let sibling = if bit(&self.non_empty, depth - 1) {
+                match siblings.next() {
+                    Some(sibling) => *sibling,
+                    None => return false,
+                }
+            } else {
+                EMPTY
+            };
+            node = parent_hash(&path, depth, &node, &sibling);
+        }
+        siblings.next().is_none() && &node == root
+    }
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<K, V, KeyType> Default for StorageSparseMerkleTree<K, V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KeyType> StorageSparseMerkleTree<K, V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageSparseMerkleTree`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns the root hash of the tree, zero if the tree is empty.
+    #[inline]
+    pub fn root(&self) -> NodeHash {
+        ink_env::get_contract_storage(&(&KeyType::KEY, ROOT))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get root of StorageSparseMerkleTree: {:?}", error)
+            })
+            .unwrap_or(EMPTY)
+    }
+
+    /// Returns the hash of the node at `depth` on `path`, zero if it is empty.
+    fn node(&self, path: &NodeHash, depth: usize) -> NodeHash {
+        ink_env::get_contract_storage(&(&KeyType::KEY, NODES, node_key(path, depth)))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get node of StorageSparseMerkleTree: {:?}", error)
+            })
+            .unwrap_or(EMPTY)
+    }
+
+    /// Writes the hash of the node at `depth` on `path` and clears empty nodes.
+    fn set_node(&mut self, path: &NodeHash, depth: usize, node: &NodeHash) {
+        let key = &(&KeyType::KEY, NODES, node_key(path, depth));
+        if node == &EMPTY {
+            ink_env::clear_contract_storage(key);
+        } else {
+            ink_env::set_contract_storage(key, node);
+        }
+    }
+
+    /// Replaces the leaf at `path` and updates all nodes up to the root.
+    fn update(&mut self, path: &NodeHash, leaf: NodeHash) {
+        let mut node = leaf;
+        for depth in (1..=DEPTH).rev() {
+            self.set_node(path, depth, &node);
+            let sibling = self.node(&sibling_path(path, depth), depth);
+            node = parent_hash(path, depth, &node, &sibling);
+        }
+        if node == EMPTY {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, ROOT));
+        } else {
+            ink_env::set_contract_storage(&(&KeyType::KEY, ROOT), &node);
+        }
+    }
+}
+
+impl<K, V, KeyType> StorageSparseMerkleTree<K, V, KeyType>
+where
+    K: Encode,
+    V: Encode,
+    KeyType: StorageKey,
+{
+    /// Sets the `value` of `key` and updates the root hash.
+    #[inline]
+    pub fn insert(&mut self, key: &K, value: &V) {
+        self.update(&hash(key), leaf_hash(Some(value)))
+    }
+
+    /// Removes the value of `key` and updates the root hash.
+    #[inline]
+    pub fn remove(&mut self, key: &K) {
+        self.update(&hash(key), leaf_hash::<V>(None))
+    }
+
+    /// Returns a proof for the current value of `key` under the current root hash.
+    ///
+    /// # Note
+    ///
+    /// This loads the 256 siblings on the path of the key. It is meant to be called
+    /// off-chain, e.g. by a message which is only ever dry-run, rather than within a
+    /// transaction.
+    pub fn prove(&self, key: &K) -> MerkleProof {
+        let path = hash(key);
+        let mut proof = MerkleProof {
+            non_empty: [0; 32],
+            siblings: Vec::new(),
+        };
+        for depth in (1..=DEPTH).rev() {
+            let sibling = self.node(&sibling_path(&path, depth), depth);
+            if sibling != EMPTY {
+                proof.non_empty[(depth - 1) / 8] |= 0x80 >> ((depth - 1) % 8);
+                proof.siblings.push(sibling);
+            }
+        }
+        proof
+    }
+
+    /// Returns `true` if `key` has the `value` under the current root hash.
+    ///
+    /// A `value` of `None` checks that the key has no value.
+    #[inline]
+    pub fn verify(&self, key: &K, value: Option<&V>, proof: &MerkleProof) -> bool {
+        proof.verify(&self.root(), key, value)
+    }
+}
+
+/// Returns the Blake2x256 hash of the encoded `input`.
+fn hash<T: Encode>(input: &T) -> NodeHash {
+    let mut output = EMPTY;
+    ink_env::hash_encoded::<Blake2x256, _>(input, &mut output);
+    output
+}
+
+/// Returns the hash of the leaf with `value`, zero if there is no value.
+///
+/// Leaves and inner nodes are hashed with different prefixes, so that a leaf can't
+/// be passed off as an inner node.
+fn leaf_hash<V: Encode>(value: Option<&V>) -> NodeHash {
+    value.map_or(EMPTY, |value| hash(&(0u8, value)))
+}
+
+/// Returns the hash of the parent of the `node` at `depth` on `path` and its `sibling`.
+fn parent_hash(
+    path: &NodeHash,
+    depth: usize,
+    node: &NodeHash,
+    sibling: &NodeHash,
+) -> NodeHash {
+    match (node == &EMPTY && sibling == &EMPTY, bit(path, depth - 1)) {
+        (true, _) => EMPTY,
+        (false, false) => hash(&(1u8, node, sibling)),
+        (false, true) => hash(&(1u8, sibling, node)),
+    }
+}
+
+/// Returns the bit at `index` of `bytes`, starting at the most significant bit.
+fn bit(bytes: &[u8; 32], index: usize) -> bool {
+    bytes[index / 8] & (0x80 >> (index % 8)) != 0
+}
+
+/// Returns `path` with the bit of the node at `depth` flipped.
+fn sibling_path(path: &NodeHash, depth: usize) -> NodeHash {
+    let mut sibling = *path;
+    sibling[(depth - 1) / 8] ^= 0x80 >> ((depth - 1) % 8);
+    sibling
+}
+
+/// Returns the key of the node at `depth` on `path`, i.e. its depth and the first
+/// `depth` bits of the path.
+fn node_key(path: &NodeHash, depth: usize) -> (u16, NodeHash) {
+    let mut prefix = *path;
+    for (index, byte) in prefix.iter_mut().enumerate() {
+        let bits = depth.saturating_sub(index * 8).min(8);
+        *byte &= !(0xFFu8.checked_shr(bits as u32).unwrap_or(0));
+    }
+    (depth as u16, prefix)
+}
+
+impl<K, V, KeyType> ::core::fmt::Debug for StorageSparseMerkleTree<K, V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageSparseMerkleTree")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<K, V, KeyType> Storable for StorageSparseMerkleTree<K, V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<K, V, Key, InnerKey> StorableHint<Key> for StorageSparseMerkleTree<K, V, InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageSparseMerkleTree<K, V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<K, V, KeyType> StorageKey for StorageSparseMerkleTree<K, V, KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<K, V, KeyType> StorageLayout for StorageSparseMerkleTree<K, V, KeyType>
+    where
+        K: scale_info::TypeInfo + 'static,
+        V: scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <NodeHash as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn node_key_masks_path() {
+        let path = [0xFF; 32];
+        assert_eq!(node_key(&path, 0), (0, [0; 32]));
+        let (depth, prefix) = node_key(&path, 12);
+        assert_eq!(depth, 12);
+        assert_eq!(prefix[..2], [0xFF, 0xF0]);
+        assert_eq!(prefix[2..], [0; 30]);
+        assert_eq!(node_key(&path, 256), (256, path));
+    }
+
+    #[test]
+    fn insert_updates_root() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut tree: StorageSparseMerkleTree<u8, u32> =
+                StorageSparseMerkleTree::new();
+            assert_eq!(tree.root(), EMPTY);
+
+            tree.insert(&1, &10);
+            let root = tree.root();
+            assert_ne!(root, EMPTY);
+            tree.insert(&2, &20);
+            assert_ne!(tree.root(), root);
+
+            // The root only depends on the key-value pairs.
+            let mut other: StorageSparseMerkleTree<u8, u32, ManualKey<123>> =
+                StorageSparseMerkleTree::new();
+            other.insert(&2, &20);
+            other.insert(&1, &10);
+            assert_eq!(other.root(), tree.root());
+
+            tree.remove(&2);
+            assert_eq!(tree.root(), root);
+            tree.remove(&1);
+            assert_eq!(tree.root(), EMPTY);
+
+            // All nodes of removed keys are cleared.
+            other.remove(&1);
+            other.remove(&2);
+            let callee = ink_env::test::callee::<ink_env::DefaultEnvironment>();
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &callee
+                )
+                .unwrap(),
+                0
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn proofs_verify() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut tree: StorageSparseMerkleTree<u8, u32> =
+                StorageSparseMerkleTree::new();
+            for key in 0..8 {
+                tree.insert(&key, &(u32::from(key) * 10));
+            }
+
+            let proof = tree.prove(&3);
+            assert!(tree.verify(&3, Some(&30), &proof));
+            assert!(!tree.verify(&3, Some(&31), &proof));
+            assert!(!tree.verify(&3, None, &proof));
+            assert!(!tree.verify(&4, Some(&30), &proof));
+
+            // Non-membership of a key without a value.
+            let proof = tree.prove(&100);
+            assert!(tree.verify(&100, None, &proof));
+            assert!(!tree.verify(&100, Some(&0), &proof));
+
+            // A proof is bound to the root it was created for.
+            let proof = tree.prove(&3);
+            let root = tree.root();
+            tree.insert(&3, &33);
+            assert!(!tree.verify(&3, Some(&30), &proof));
+            assert!(proof.verify(&root, &3u8, Some(&30u32)));
+            assert!(tree.verify(&3, Some(&33), &tree.prove(&3)));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut tree: StorageSparseMerkleTree<u8, u32> =
+                StorageSparseMerkleTree::new();
+            tree.insert(&1, &10);
+            tree.insert(&2, &20);
+            let proof = tree.prove(&1);
+            assert_eq!(proof.siblings.len(), 1);
+
+            let mut truncated = proof.clone();
+            truncated.siblings.clear();
+            assert!(!tree.verify(&1, Some(&10), &truncated));
+
+            let mut extended = proof.clone();
+            extended.siblings.push([1; 32]);
+            assert!(!tree.verify(&1, Some(&10), &extended));
+
+            let mut modified = proof;
+            modified.siblings[0][0] ^= 1;
+            assert!(!tree.verify(&1, Some(&10), &modified));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
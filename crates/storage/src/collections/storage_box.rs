@@ -0,0 +1,304 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A packed handle to a value stored under its own root key.
+//!
+//! # Note
+//!
+//! The box itself is encoded as zero bytes. The packed fields of the inner value are
+//! stored under the root key of the box, while its non-packed fields derive their
+//! keys from the root key of the box like the fields of the contract storage
+//! struct derive theirs from the root key of the contract.
+
+use crate::traits::{
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use scale::{
+    Decode,
+    Encode,
+};
+
+/// A handle to a value stored under its own root key, e.g. a [`Mapping`](crate::Mapping).
+///
+/// Since the box is packed, it can be used where only packed types are allowed, e.g.
+/// in enum variants, in `Option`s or in packed structs, while the inner value can be
+/// a non-packed type.
+///
+/// # Important
+///
+/// The root key is part of the type and must be specified with a
+/// [`ManualKey`](crate::traits::ManualKey), since packed types are not assigned
+/// automatically calculated keys. Hence all boxes of the same type refer to the
+/// same inner value, e.g. the boxes of a vector.
+///
+/// This is an example of how you can use the box:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     collections::StorageBox,
+///     traits::ManualKey,
+///     Mapping,
+/// };
+///
+/// #[derive(scale::Encode, scale::Decode)]
+/// #[cfg_attr(
+///     feature = "std",
+///     derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+/// )]
+/// pub enum Phase {
+///     Setup,
+///     Voting(StorageBox<Mapping<AccountId, bool>, ManualKey<0x766f7465>>),
+/// }
+///
+/// #[ink(storage)]
+/// pub struct Ballot {
+///     phase: Phase,
+/// }
+///
+/// impl Ballot {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self {
+///             phase: Phase::Setup,
+///         }
+///     }
+///
+///     #[ink(message)]
+///     pub fn open(&mut self) {
+///         self.phase = Phase::Voting(StorageBox::new());
+///     }
+///
+///     #[ink(message)]
+///     pub fn vote(&mut self, approve: bool) {
+///         if let Phase::Voting(votes) = &self.phase {
+///             let mut votes = votes.get_or_default();
+///             votes.insert(self.env().caller(), &approve);
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageBox<T, KeyType: StorageKey> {
+    _marker: PhantomData<fn() -> (T, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<T, KeyType> Default for StorageBox<T, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<T, KeyType> Clone for StorageBox<T, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, KeyType> Copy for StorageBox<T, KeyType> where KeyType: StorageKey {}
+
+impl<T, KeyType> StorageBox<T, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new `StorageBox`.
+    ///
+    /// This does not touch the storage, so a previously stored inner value remains.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, KeyType> ::core::fmt::Debug for StorageBox<T, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageBox")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<T, KeyType> StorageBox<T, KeyType>
+where
+    T: StorableHint<KeyType>,
+    KeyType: StorageKey,
+{
+    /// Reads the inner value from the contract storage, if it exists.
+    ///
+    /// Non-packed fields of the inner value, e.g. a `Mapping`, are not read but
+    /// accessed lazily under their keys derived from the root key of the box.
+    pub fn get(&self) -> Option<T::Type> {
+        ink_env::get_contract_storage::<Key, T::Type>(&KeyType::KEY).unwrap_or_else(
+            |error| panic!("Failed to get value of StorageBox: {:?}", error),
+        )
+    }
+
+    /// Writes the inner value to the contract storage.
+    pub fn set(&mut self, value: &T::Type) {
+        ink_env::set_contract_storage::<Key, T::Type>(&KeyType::KEY, value);
+    }
+
+    /// Removes the packed fields of the inner value from the contract storage.
+    ///
+    /// # Note
+    ///
+    /// The non-packed fields of the inner value are not cleared, since they can't
+    /// be enumerated. Clear them beforehand, e.g. with `Mapping::clear_all`.
+    pub fn clear(&mut self) {
+        ink_env::clear_contract_storage(&KeyType::KEY);
+    }
+}
+
+impl<T, KeyType> StorageBox<T, KeyType>
+where
+    T: StorableHint<KeyType>,
+    T::Type: Default,
+    KeyType: StorageKey,
+{
+    /// Reads the inner value from the contract storage.
+    ///
+    /// Returns the default value if no inner value exists, which is the case for
+    /// types without packed fields like a `Mapping` until it is [`set`](Self::set).
+    pub fn get_or_default(&self) -> T::Type {
+        self.get().unwrap_or_default()
+    }
+}
+
+impl<T, KeyType> Encode for StorageBox<T, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn encode_to<O: scale::Output + ?Sized>(&self, _dest: &mut O) {}
+}
+
+impl<T, KeyType> Decode for StorageBox<T, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn decode<I: scale::Input>(_input: &mut I) -> Result<Self, scale::Error> {
+        Ok(Self::new())
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<T, KeyType> StorageLayout for StorageBox<T, KeyType>
+    where
+        T: StorableHint<KeyType> + scale_info::TypeInfo + 'static,
+        T::Type: StorageLayout,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <T::Type as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        traits::ManualKey,
+        Lazy,
+        Mapping,
+    };
+
+    #[test]
+    fn boxed_mapping_works_inside_option() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            type Boxed = StorageBox<Mapping<u8, u8>, ManualKey<123>>;
+            let boxed: Boxed = StorageBox::new();
+            let encoded = scale::Encode::encode(&Some(boxed));
+            assert_eq!(encoded, vec![1]);
+
+            let mut mapping = boxed.get_or_default();
+            mapping.insert(1, &10);
+
+            let decoded = <Option<Boxed> as Decode>::decode(&mut &encoded[..]).unwrap();
+            assert_eq!(decoded.unwrap().get_or_default().get(1), Some(10));
+            assert_eq!(
+                ink_env::get_contract_storage::<_, u8>(&(123u32, 1u8)),
+                Ok(Some(10))
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn set_get_and_clear_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut boxed: StorageBox<u32, ManualKey<123>> = StorageBox::new();
+            assert_eq!(boxed.get(), None);
+            assert_eq!(boxed.get_or_default(), 0);
+
+            boxed.set(&42);
+            assert_eq!(boxed.get(), Some(42));
+            assert_eq!(StorageBox::<u32, ManualKey<123>>::new().get(), Some(42));
+
+            boxed.clear();
+            assert_eq!(boxed.get(), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn inner_lazy_uses_root_key_of_box() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let boxed: StorageBox<Lazy<u8>, ManualKey<123>> = StorageBox::new();
+            boxed.get_or_default().set(&7);
+            assert_eq!(ink_env::get_contract_storage::<_, u8>(&123u32), Ok(Some(7)));
+            assert_eq!(boxed.get_or_default().get(), Some(7));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
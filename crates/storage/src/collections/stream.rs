@@ -0,0 +1,227 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A payment streamed linearly over time from a deposit, e.g. a salary or a vesting.
+
+use scale::{
+    Decode,
+    Encode,
+};
+
+/// Returned if a withdrawal from a [`Stream`] failed.
+///
+/// The stream is left untouched in this case.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StreamError {
+    /// The amount exceeds the streamed and not yet withdrawn balance.
+    InsufficientBalance,
+}
+
+/// A payment which streams a deposit to its recipient at a constant rate.
+///
+/// The stream captures only the bookkeeping of the payment. Starting at `start`,
+/// the deposit becomes available to the recipient at `rate_per_second` until all
+/// of it is streamed, and the recipient can withdraw the streamed balance at any
+/// time. Moving the funds and checking who calls is left to the contract, which
+/// usually stores the streams in a [`Mapping`](crate::Mapping).
+///
+/// Timestamps are milliseconds like the
+/// [`block_timestamp`](ink_env::block_timestamp) of the
+/// [`DefaultEnvironment`](ink_env::DefaultEnvironment), while the rate is given
+/// per second.
+///
+/// This is an example of how you can use the stream:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     collections::Stream,
+///     Mapping,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Payroll {
+///     streams: Mapping<AccountId, Stream>,
+/// }
+///
+/// impl Payroll {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message, payable)]
+///     pub fn open(&mut self, recipient: AccountId, rate_per_second: Balance) {
+///         let deposit = self.env().transferred_value();
+///         let now = self.env().block_timestamp();
+///         let stream = Stream::new(rate_per_second, now, deposit);
+///         assert!(self.streams.insert(recipient, &stream).is_none());
+///     }
+///
+///     #[ink(message)]
+///     pub fn withdraw(&mut self) -> Balance {
+///         let caller = self.env().caller();
+///         let mut stream = self.streams.get(caller).unwrap();
+///         let amount = stream.withdraw_all(self.env().block_timestamp());
+///         self.streams.insert(caller, &stream);
+///         self.env().transfer(caller, amount).unwrap();
+///         amount
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Stream {
+    /// The amount streamed per second.
+    pub rate_per_second: u128,
+    /// The timestamp at which the streaming starts.
+    pub start: u64,
+    /// The total amount to stream.
+    pub deposit: u128,
+    /// The amount the recipient has withdrawn so far.
+    pub withdrawn: u128,
+}
+
+impl Stream {
+    /// Creates a new stream of `deposit` at `rate_per_second` from `start` on.
+    pub fn new(rate_per_second: u128, start: u64, deposit: u128) -> Self {
+        Self {
+            rate_per_second,
+            start,
+            deposit,
+            withdrawn: 0,
+        }
+    }
+
+    /// Returns the amount streamed until `now`, including the withdrawn amount.
+    pub fn streamed_at(&self, now: u64) -> u128 {
+        let elapsed = u128::from(now.saturating_sub(self.start));
+        self.rate_per_second
+            .checked_mul(elapsed)
+            .map_or(self.deposit, |streamed| streamed / 1000)
+            .min(self.deposit)
+    }
+
+    /// Returns the balance the recipient can withdraw at `now`.
+    pub fn balance_of_at(&self, now: u64) -> u128 {
+        // Saturates for a `now` before the latest withdrawal.
+        self.streamed_at(now).saturating_sub(self.withdrawn)
+    }
+
+    /// Returns the amount of the deposit which is not yet streamed at `now`.
+    pub fn remaining_at(&self, now: u64) -> u128 {
+        self.deposit - self.streamed_at(now)
+    }
+
+    /// Returns `true` if the recipient has withdrawn the whole deposit.
+    pub fn is_depleted(&self) -> bool {
+        self.withdrawn == self.deposit
+    }
+
+    /// Withdraws `amount` of the balance of the recipient at `now`.
+    ///
+    /// # Errors
+    ///
+    /// If `amount` exceeds the balance of the recipient at `now`.
+    pub fn withdraw(&mut self, now: u64, amount: u128) -> Result<(), StreamError> {
+        if amount > self.balance_of_at(now) {
+            return Err(StreamError::InsufficientBalance)
+        }
+        self.withdrawn += amount;
+        Ok(())
+    }
+
+    /// Withdraws the whole balance of the recipient at `now` and returns it.
+    pub fn withdraw_all(&mut self, now: u64) -> u128 {
+        let amount = self.balance_of_at(now);
+        self.withdrawn += amount;
+        amount
+    }
+
+    /// Stops the stream at `now`.
+    ///
+    /// Returns the balance of the recipient and the remaining amount to refund to
+    /// the sender. The stream is depleted afterwards.
+    pub fn cancel(&mut self, now: u64) -> (u128, u128) {
+        let remaining = self.remaining_at(now);
+        self.deposit -= remaining;
+        (self.withdraw_all(now), remaining)
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        LeafLayout,
+    };
+    use ink_primitives::Key;
+
+    impl StorageLayout for Stream {
+        fn layout(key: &Key) -> Layout {
+            Layout::Leaf(LeafLayout::from_key::<Self>(LayoutKey::from(key)))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_linearly_up_to_deposit() {
+        let stream = Stream::new(10, 1_000, 100);
+
+        assert_eq!(stream.streamed_at(0), 0);
+        assert_eq!(stream.streamed_at(1_000), 0);
+        assert_eq!(stream.streamed_at(1_500), 5);
+        assert_eq!(stream.streamed_at(3_000), 20);
+        assert_eq!(stream.remaining_at(3_000), 80);
+        assert_eq!(stream.streamed_at(11_000), 100);
+        assert_eq!(stream.streamed_at(u64::MAX), 100);
+        assert_eq!(Stream::new(u128::MAX, 0, 7).streamed_at(u64::MAX), 7);
+    }
+
+    #[test]
+    fn withdraw_works() {
+        let mut stream = Stream::new(10, 0, 100);
+
+        assert_eq!(stream.withdraw(2_000, 15), Ok(()));
+        assert_eq!(stream.balance_of_at(2_000), 5);
+        assert_eq!(
+            stream.withdraw(2_000, 6),
+            Err(StreamError::InsufficientBalance)
+        );
+        assert_eq!(stream.withdraw_all(5_000), 35);
+        assert_eq!(stream.balance_of_at(5_000), 0);
+        assert_eq!(stream.withdraw_all(20_000), 50);
+        assert!(stream.is_depleted());
+    }
+
+    #[test]
+    fn cancel_settles_both_sides() {
+        let mut stream = Stream::new(10, 0, 100);
+        stream.withdraw(2_000, 10).unwrap();
+
+        assert_eq!(stream.cancel(4_000), (30, 60));
+        assert!(stream.is_depleted());
+        assert_eq!(stream.balance_of_at(10_000), 0);
+    }
+}
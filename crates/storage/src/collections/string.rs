@@ -0,0 +1,279 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A UTF-8 string split across multiple storage cells.
+//!
+//! # Note
+//!
+//! The string is stored as the payload of a [`StorageBlob`] under the same key.
+
+use super::StorageBlob;
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_prelude::string::String;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// A string of arbitrary size stored in fixed-size chunks in the contract storage.
+///
+/// Like the [`StorageBlob`] which stores its UTF-8 encoding, the string may exceed the
+/// limit of a single storage cell, e.g. for metadata URIs, SVG images or the text of
+/// a governance proposal. Reading or appending a part of the string only loads the
+/// chunks touched by that part. Offsets and lengths are given in bytes.
+///
+/// # Important
+///
+/// The string requires its own pre-defined storage key in order to determine where it
+/// stores its chunks. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can specify a
+/// storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the string:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::{
+///     prelude::string::String,
+///     storage::collections::StorageString,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Proposal {
+///     text: StorageString,
+/// }
+///
+/// impl Proposal {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn upload_part(&mut self, part: String) {
+///         self.text.append(&part);
+///     }
+///
+///     #[ink(message)]
+///     pub fn read_part(&self, offset: u32, len: u32) -> Option<String> {
+///         self.text.read_range(offset, len)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageString<KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> KeyType>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<KeyType> Default for StorageString<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<KeyType> StorageString<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageString`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<KeyType> core::fmt::Debug for StorageString<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageString")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<KeyType> StorageString<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Returns the length of the stored string in bytes.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.blob().len()
+    }
+
+    /// Returns `true` if the stored string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.blob().is_empty()
+    }
+
+    /// Reads `len` bytes of the string starting at `offset`.
+    ///
+    /// Only the chunks overlapping the requested range are loaded.
+    ///
+    /// Returns `None` if the range exceeds the length of the string or doesn't start
+    /// and end at character boundaries.
+    pub fn read_range(&self, offset: u32, len: u32) -> Option<String> {
+        // A range within valid UTF-8 is valid UTF-8 itself unless it splits a
+        // character.
+        String::from_utf8(self.blob().read_range(offset, len)?).ok()
+    }
+
+    /// Reads the whole string.
+    ///
+    /// # Note
+    ///
+    /// This loads every chunk of the string, prefer [`StorageString::read_range`]
+    /// for big strings.
+    pub fn read(&self) -> String {
+        String::from_utf8(self.blob().read())
+            .expect("a StorageString only stores valid UTF-8; qed")
+    }
+
+    /// Appends `s` at the end of the string.
+    pub fn append(&mut self, s: &str) {
+        self.blob().append(s.as_bytes());
+    }
+
+    /// Replaces the whole string with `s`.
+    pub fn set(&mut self, s: &str) {
+        self.blob().set(s.as_bytes());
+    }
+
+    /// Removes the whole string from the contract storage.
+    pub fn clear(&mut self) {
+        self.blob().clear();
+    }
+
+    /// Returns the blob storing the UTF-8 encoding of the string.
+    fn blob(&self) -> StorageBlob<KeyType> {
+        StorageBlob::new()
+    }
+}
+
+impl<KeyType> Storable for StorageString<KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<Key, InnerKey> StorableHint<Key> for StorageString<InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageString<Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<KeyType> StorageKey for StorageString<KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::Layout;
+
+    impl<KeyType> StorageLayout for StorageString<KeyType>
+    where
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(key: &Key) -> Layout {
+            <StorageBlob<KeyType> as StorageLayout>::layout(key)
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    type Text = StorageString<ManualKey<42>>;
+
+    #[test]
+    fn append_and_read_across_chunks_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut text = Text::new();
+            assert!(text.is_empty());
+            assert_eq!(text.read(), "");
+
+            // A multi-byte character ends up split across two chunks.
+            let head = "a".repeat(StorageBlob::<ManualKey<42>>::CHUNK_SIZE as usize - 1);
+            text.append(&head);
+            text.append("€uro");
+
+            assert_eq!(text.len(), head.len() as u32 + 6);
+            assert_eq!(text.read(), head + "€uro");
+            let euro = text.len() - 6;
+            assert_eq!(text.read_range(euro, 3), Some(String::from("€")));
+            assert_eq!(text.read_range(euro, 2), None);
+            assert_eq!(text.read_range(euro + 1, 5), None);
+            assert_eq!(text.read_range(euro + 3, 4), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn set_and_clear_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            let mut text = Text::new();
+            text.set("first");
+            text.set("second");
+            assert_eq!(text.read(), "second");
+
+            text.clear();
+            assert!(text.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,482 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A value in contract storage whose changes only become active after a delay.
+//!
+//! # Note
+//!
+//! Like the [`Lazy`](crate::Lazy), the timelocked value doesn't actually "own" any
+//! data. The active value is stored under the root key while the pending value and
+//! its activation are stored under the root key combined with a suffix.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::{
+    DefaultEnvironment,
+    Environment,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The point in time from which on a scheduled value of a [`Timelocked`] is active.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Activation<BlockNumber, Timestamp> {
+    /// Active from the block with the given number on.
+    Block(BlockNumber),
+    /// Active from the first block with at least the given timestamp on.
+    Timestamp(Timestamp),
+}
+
+impl<BlockNumber, Timestamp> Activation<BlockNumber, Timestamp>
+where
+    BlockNumber: PartialOrd,
+    Timestamp: PartialOrd,
+{
+    /// Returns `true` if the current block reached the activation.
+    pub fn is_reached<E>(&self) -> bool
+    where
+        E: Environment<BlockNumber = BlockNumber, Timestamp = Timestamp>,
+    {
+        match self {
+            Self::Block(block) => ink_env::block_number::<E>() >= *block,
+            Self::Timestamp(timestamp) => ink_env::block_timestamp::<E>() >= *timestamp,
+        }
+    }
+}
+
+/// The suffix of the storage key of the pending value.
+const PENDING_VALUE: u8 = 0;
+
+/// The suffix of the storage key of the activation of the pending value.
+const PENDING_ACTIVATION: u8 = 1;
+
+/// A value `T` whose changes only become active after a delay, e.g. the parameters
+/// of a governance contract which users should be able to react to.
+///
+/// A new value is [`schedule`](Self::schedule)d together with the block number or
+/// timestamp from which on it is active. Until then the previous value stays
+/// active and the change can be [`cancel`](Self::cancel)ed. Once the activation is
+/// reached, the pending value transparently replaces the active one.
+///
+/// # Important
+///
+/// The timelocked value requires its own pre-defined storage key where to store
+/// the value. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the timelocked value:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::{
+///     Activation,
+///     Timelocked,
+/// };
+///
+/// /// The number of blocks before a new quorum is active.
+/// const DELAY: BlockNumber = 100;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Governance {
+///     quorum: Timelocked<u32, Environment>,
+/// }
+///
+/// impl Governance {
+///     #[ink(constructor)]
+///     pub fn new(quorum: u32) -> Self {
+///         let mut instance = Self::default();
+///         instance.quorum.set(&quorum);
+///         instance
+///     }
+///
+///     #[ink(message)]
+///     pub fn quorum(&self) -> u32 {
+///         self.quorum.current().unwrap_or_default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn change_quorum(&mut self, quorum: u32) {
+///         let at = self.env().block_number() + DELAY;
+///         self.quorum.schedule(&quorum, Activation::Block(at));
+///     }
+///
+///     #[ink(message)]
+///     pub fn veto(&mut self) -> Option<u32> {
+///         self.quorum.cancel()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Timelocked<
+    T: Packed,
+    E: Environment = DefaultEnvironment,
+    KeyType: StorageKey = AutoKey,
+> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (T, E, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<T, E, KeyType> Default for Timelocked<T, E, KeyType>
+where
+    T: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E, KeyType> Timelocked<T, E, KeyType>
+where
+    T: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Creates a new `Timelocked` without an active or pending value.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, E, KeyType> ::core::fmt::Debug for Timelocked<T, E, KeyType>
+where
+    T: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Timelocked")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<T, E, KeyType> Timelocked<T, E, KeyType>
+where
+    T: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Returns the active value, if any.
+    ///
+    /// A pending value whose activation was reached is promoted to the active value
+    /// first, which is written to the contract storage.
+    pub fn current(&self) -> Option<T> {
+        self.promote();
+        ink_env::get_contract_storage::<Key, T>(&KeyType::KEY).unwrap_or_else(|error| {
+            panic!("Failed to get value of Timelocked: {:?}", error)
+        })
+    }
+
+    /// Returns the pending value and its activation, if any.
+    ///
+    /// A pending value whose activation was reached is promoted first, so that it
+    /// is no longer pending.
+    #[allow(clippy::type_complexity)]
+    pub fn pending(&self) -> Option<(T, Activation<E::BlockNumber, E::Timestamp>)> {
+        self.promote();
+        let at = self.activation()?;
+        Some((self.pending_value(), at))
+    }
+
+    /// Sets the active value immediately, e.g. in the constructor.
+    ///
+    /// A pending value stays scheduled.
+    pub fn set(&mut self, value: &T) {
+        self.promote();
+        ink_env::set_contract_storage::<Key, T>(&KeyType::KEY, value);
+    }
+
+    /// Schedules `value` to become active at `at`.
+    ///
+    /// A pending value which is not active yet is replaced.
+    pub fn schedule(&mut self, value: &T, at: Activation<E::BlockNumber, E::Timestamp>) {
+        self.promote();
+        ink_env::set_contract_storage(&(&KeyType::KEY, PENDING_VALUE), value);
+        ink_env::set_contract_storage(&(&KeyType::KEY, PENDING_ACTIVATION), &at);
+    }
+
+    /// Cancels the pending value which is not active yet.
+    ///
+    /// Returns the cancelled value, if any.
+    pub fn cancel(&mut self) -> Option<T> {
+        self.promote();
+        self.activation()?;
+        let value = self.pending_value();
+        self.clear_pending();
+        Some(value)
+    }
+
+    /// Promotes a pending value whose activation was reached to the active value.
+    fn promote(&self) {
+        match self.activation() {
+            Some(at) if at.is_reached::<E>() => {
+                let value = self.pending_value();
+                ink_env::set_contract_storage::<Key, T>(&KeyType::KEY, &value);
+                self.clear_pending();
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the activation of the pending value, if any.
+    fn activation(&self) -> Option<Activation<E::BlockNumber, E::Timestamp>> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, PENDING_ACTIVATION))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get activation of Timelocked: {:?}", error)
+            })
+    }
+
+    /// Returns the pending value.
+    ///
+    /// # Panics
+    ///
+    /// If there is no pending value.
+    fn pending_value(&self) -> T {
+        ink_env::get_contract_storage(&(&KeyType::KEY, PENDING_VALUE))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get pending value of Timelocked: {:?}", error)
+            })
+            .expect("the pending value is stored together with its activation")
+    }
+
+    fn clear_pending(&self) {
+        ink_env::clear_contract_storage(&(&KeyType::KEY, PENDING_VALUE));
+        ink_env::clear_contract_storage(&(&KeyType::KEY, PENDING_ACTIVATION));
+    }
+}
+
+impl<T, E, KeyType> Storable for Timelocked<T, E, KeyType>
+where
+    T: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<T, E, Key, InnerKey> StorableHint<Key> for Timelocked<T, E, InnerKey>
+where
+    T: Packed,
+    E: Environment,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Timelocked<T, E, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<T, E, KeyType> StorageKey for Timelocked<T, E, KeyType>
+where
+    T: Packed,
+    E: Environment,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Discriminant,
+        EnumLayout,
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        RootLayout,
+        StructLayout,
+    };
+
+    impl<BlockNumber, Timestamp> StorageLayout for Activation<BlockNumber, Timestamp>
+    where
+        BlockNumber: StorageLayout,
+        Timestamp: StorageLayout,
+    {
+        fn layout(key: &Key) -> Layout {
+            Layout::Enum(EnumLayout::new(
+                "Activation",
+                key,
+                [
+                    (
+                        Discriminant::from(0),
+                        StructLayout::new(
+                            "Block",
+                            [FieldLayout::new(
+                                "0",
+                                <BlockNumber as StorageLayout>::layout(key),
+                            )],
+                        ),
+                    ),
+                    (
+                        Discriminant::from(1),
+                        StructLayout::new(
+                            "Timestamp",
+                            [FieldLayout::new(
+                                "0",
+                                <Timestamp as StorageLayout>::layout(key),
+                            )],
+                        ),
+                    ),
+                ],
+            ))
+        }
+    }
+
+    impl<T, E, KeyType> StorageLayout for Timelocked<T, E, KeyType>
+    where
+        T: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        E: Environment + scale_info::TypeInfo + 'static,
+        E::BlockNumber: StorageLayout,
+        E::Timestamp: StorageLayout,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                StructLayout::new(
+                    "Timelocked",
+                    [
+                        FieldLayout::new(
+                            "active",
+                            <Option<T> as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                        FieldLayout::new(
+                            "pending",
+                            <Option<(T, Activation<E::BlockNumber, E::Timestamp>)> as StorageLayout>::layout(
+                                &KeyType::KEY,
+                            ),
+                        ),
+                    ],
+                ),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    type Value = Timelocked<u8, DefaultEnvironment, ManualKey<123>>;
+
+    #[test]
+    fn set_and_current_work() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut value: Value = Timelocked::new();
+            assert_eq!(value.current(), None);
+            assert_eq!(value.pending(), None);
+
+            value.set(&1);
+            assert_eq!(value.current(), Some(1));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn scheduled_value_activates_at_block() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut value: Value = Timelocked::new();
+            value.set(&1);
+            value.schedule(&2, Activation::Block(2));
+            assert_eq!(value.current(), Some(1));
+            assert_eq!(value.pending(), Some((2, Activation::Block(2))));
+
+            ink_env::test::advance_block::<DefaultEnvironment>();
+            assert_eq!(value.current(), Some(1));
+            ink_env::test::advance_block::<DefaultEnvironment>();
+            assert_eq!(value.current(), Some(2));
+            assert_eq!(value.pending(), None);
+
+            // The promoted value can no longer be cancelled.
+            assert_eq!(value.cancel(), None);
+            assert_eq!(value.current(), Some(2));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn scheduled_value_activates_at_timestamp() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut value: Value = Timelocked::new();
+            value.schedule(&1, Activation::Timestamp(1_000));
+            assert_eq!(value.current(), None);
+
+            ink_env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            assert_eq!(value.current(), Some(1));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn cancel_and_reschedule_work() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut value: Value = Timelocked::new();
+            value.set(&1);
+            value.schedule(&2, Activation::Block(1));
+            value.schedule(&3, Activation::Block(1));
+            assert_eq!(value.cancel(), Some(3));
+            assert_eq!(value.cancel(), None);
+
+            ink_env::test::advance_block::<DefaultEnvironment>();
+            assert_eq!(value.current(), Some(1));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn schedule_promotes_reached_value_first() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut value: Value = Timelocked::new();
+            value.schedule(&1, Activation::Block(1));
+            ink_env::test::advance_block::<DefaultEnvironment>();
+
+            value.schedule(&2, Activation::Block(5));
+            assert_eq!(value.current(), Some(1));
+            assert_eq!(value.pending(), Some((2, Activation::Block(5))));
+            Ok(())
+        })
+        .unwrap()
+    }
+}
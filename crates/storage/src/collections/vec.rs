@@ -0,0 +1,471 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A growable vector in contract storage which loads its elements lazily.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), this vector doesn't actually "own" any data.
+//! The length is stored under the root key of the vector while every element is
+//! stored under the root key combined with its index.
+
+use super::cursor::{
+    Cursor,
+    Page,
+};
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// A growable vector of elements directly in contract storage.
+///
+/// In contrast to a `Vec<T>` field, which is encoded into a single storage cell and
+/// thus always loaded and written as a whole, every element is stored in its own
+/// storage cell under the root key combined with its index. Only the elements which
+/// are actually accessed are loaded.
+///
+/// # Important
+///
+/// The vector requires its own pre-defined storage key where to store its elements.
+/// By default, the key is automatically calculated using [`AutoKey`](crate::traits::AutoKey)
+/// during compilation. However, anyone can specify a storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the vector:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::StorageVec;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Ledger {
+///     entries: StorageVec<Balance>,
+/// }
+///
+/// impl Ledger {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn record(&mut self, amount: Balance) -> u32 {
+///         self.entries.push(&amount);
+///         self.entries.len()
+///     }
+///
+///     #[ink(message)]
+///     pub fn entry(&self, index: u32) -> Option<Balance> {
+///         self.entries.get(index)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageVec<V: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<V, KeyType> Default for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, KeyType> StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageVec`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageVec")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<V, KeyType> StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<Key, u32>(&KeyType::KEY)
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of StorageVec: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the `value` to the back of the vector.
+    ///
+    /// # Panics
+    ///
+    /// If the vector already contains `u32::MAX` elements.
+    #[inline]
+    pub fn push<R>(&mut self, value: &R)
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let index = self.len();
+        let len = index
+            .checked_add(1)
+            .expect("cannot push more than u32::MAX elements to StorageVec");
+        ink_env::set_contract_storage(&(&KeyType::KEY, index), value);
+        self.set_len(len);
+    }
+
+    /// Removes the last element from the vector and returns it.
+    ///
+    /// Returns `None` if the vector is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<V> {
+        let index = self.len().checked_sub(1)?;
+        let value = ink_env::take_contract_storage(&(&KeyType::KEY, index))
+            .unwrap_or_else(|error| {
+                panic!("Failed to pop value in StorageVec: {:?}", error)
+            });
+        self.set_len(index);
+        value
+    }
+
+    /// Returns the element at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<V> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, index)).unwrap_or_else(|error| {
+            panic!("Failed to get value in StorageVec: {:?}", error)
+        })
+    }
+
+    /// Replaces the element at `index` with `value`.
+    ///
+    /// Returns the size in bytes of the replaced element.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    #[inline]
+    pub fn set<R>(&mut self, index: u32, value: &R) -> u32
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let len = self.len();
+        assert!(
+            index < len,
+            "index out of bounds: the len is {} but the index is {}",
+            len,
+            index
+        );
+        ink_env::set_contract_storage(&(&KeyType::KEY, index), value)
+            .expect("elements within the bounds of a StorageVec must be stored")
+    }
+
+    /// Returns an iterator over all elements.
+    ///
+    /// Every element is loaded from storage only once the iterator reaches it.
+    pub fn iter(&self) -> impl Iterator<Item = V> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// Returns the page of at most `limit` elements from the position of `cursor`
+    /// on together with the cursor of the next page.
+    ///
+    /// The cursor refers to an index, so pushing elements between fetching two
+    /// pages extends the iteration while popping elements shortens it.
+    pub fn iter_from(&self, cursor: Cursor, limit: u32) -> Page<V> {
+        Page::from_indices(cursor, limit, self.len(), |index| self.get(index))
+    }
+
+    /// Removes all elements from the vector.
+    ///
+    /// # Note
+    ///
+    /// This clears the storage cell of every element and hence is as expensive
+    /// as popping all elements.
+    pub fn clear(&mut self) {
+        for index in 0..self.len() {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, index));
+        }
+        ink_env::clear_contract_storage(&KeyType::KEY);
+    }
+
+    /// Stores the number of elements.
+    fn set_len(&mut self, len: u32) {
+        ink_env::set_contract_storage::<Key, u32>(&KeyType::KEY, &len);
+    }
+}
+
+impl<V, KeyType> Storable for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageVec<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageVec<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageVec<V, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn push_and_pop_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            assert!(vec.is_empty());
+            assert_eq!(vec.pop(), None);
+
+            vec.push(&1);
+            vec.push(&2);
+            assert_eq!(vec.len(), 2);
+            assert_eq!(vec.get(0), Some(1));
+            assert_eq!(vec.get(2), None);
+
+            assert_eq!(vec.pop(), Some(2));
+            assert_eq!(vec.pop(), Some(1));
+            assert_eq!(vec.pop(), None);
+            assert!(vec.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn set_and_iter_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut vec: StorageVec<u32, ManualKey<123>> = StorageVec::new();
+            vec.push(&1);
+            vec.push(&2);
+            vec.push(&3);
+
+            // When
+            assert_eq!(vec.set(1, &20), 4);
+
+            // Then
+            let vec: StorageVec<u32, ManualKey<123>> = StorageVec::new();
+            assert_eq!(vec.iter().collect::<Vec<_>>(), vec![1, 20, 3]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 1 but the index is 1")]
+    fn set_out_of_bounds_panics() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            vec.push(&1);
+            vec.set(1, &2);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn only_touched_elements_are_loaded() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            for value in 0..10 {
+                vec.push(&value);
+            }
+            let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+
+            // When
+            assert_eq!(vec.get(7), Some(7));
+
+            // Then
+            let (reads_after, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+            assert_eq!(reads_after - reads, 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_frees_storage() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            vec.push(&1);
+            vec.push(&2);
+
+            // When
+            vec.clear();
+
+            // Then
+            assert!(vec.is_empty());
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(0)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_from_pages_through_elements() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            for value in 0..5 {
+                vec.push(&value);
+            }
+
+            let page = vec.iter_from(Cursor::start(), 2);
+            assert_eq!(page.items, [0, 1]);
+            let page = vec.iter_from(page.next.unwrap(), 2);
+            assert_eq!(page.items, [2, 3]);
+            let cursor = page.next.unwrap();
+            // The cursor survives the encoding into a message return value.
+            let encoded = scale::Encode::encode(&cursor);
+            let cursor: Cursor = scale::Decode::decode(&mut &encoded[..]).unwrap();
+            let page = vec.iter_from(cursor, 2);
+            assert_eq!(page.items, [4]);
+            assert_eq!(page.next, None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_from_reads_at_most_limit_elements() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            for value in 0..100 {
+                vec.push(&value);
+            }
+            let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+
+            // When
+            let page = vec.iter_from(Cursor::at(10), 3);
+
+            // Then
+            assert_eq!(page.items, [10, 11, 12]);
+            assert_eq!(page.next, Some(Cursor::at(13)));
+            let (reads_after, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&accounts.alice);
+            // The length and the elements of the page are read.
+            assert_eq!(reads_after - reads, 4);
+            assert!(vec.iter_from(Cursor::at(100), 3).items.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,424 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage value tagged with the version of its type, which is migrated on access.
+//!
+//! # Note
+//!
+//! Like the [`Lazy`](crate::Lazy), the versioned value doesn't actually "own" any
+//! data. The version byte followed by the encoded value is stored under the root key.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// Converts a value stored with an older version of a type to the current one.
+///
+/// Implemented by the types stored in a [`Versioned`].
+pub trait Migrate: Sized {
+    /// Decodes a value stored with the older `version` from `input` and converts
+    /// it to the current version.
+    ///
+    /// # Errors
+    ///
+    /// If `version` is unknown or the value can't be decoded.
+    fn migrate<I: Input>(version: u8, input: &mut I) -> Result<Self, Error>;
+}
+
+/// A storage value of type `T` in version `VERSION`, which migrates values stored
+/// with older versions.
+///
+/// Every value is stored together with the version it was written with. When a
+/// value written with an older version is read, it is converted by the
+/// [`Migrate`] implementation of `T` and written back in the current version.
+/// Bump `VERSION` whenever the encoding of `T` changes in an upgrade of the
+/// contract code.
+///
+/// # Important
+///
+/// The versioned value requires its own pre-defined storage key where to store the
+/// value. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the versioned value:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::collections::{
+///     Migrate,
+///     Versioned,
+/// };
+///
+/// #[derive(scale::Decode)]
+/// pub struct ConfigV1 {
+///     fee: u16,
+/// }
+///
+/// #[derive(Default, scale::Encode, scale::Decode)]
+/// #[cfg_attr(
+///     feature = "std",
+///     derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+/// )]
+/// pub struct Config {
+///     fee: u16,
+///     treasury: Option<AccountId>,
+/// }
+///
+/// impl Migrate for Config {
+///     fn migrate<I: scale::Input>(version: u8, input: &mut I) -> Result<Self, scale::Error> {
+///         match version {
+///             1 => {
+///                 let old = <ConfigV1 as scale::Decode>::decode(input)?;
+///                 Ok(Config {
+///                     fee: old.fee,
+///                     treasury: None,
+///                 })
+///             }
+///             _ => Err("unknown version of `Config`".into()),
+///         }
+///     }
+/// }
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Exchange {
+///     config: Versioned<Config, 2>,
+/// }
+///
+/// impl Exchange {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn fee(&self) -> u16 {
+///         self.config.get_or_default().fee
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Versioned<T, const VERSION: u8, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (T, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<T, const VERSION: u8, KeyType> Default for Versioned<T, VERSION, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const VERSION: u8, KeyType> Versioned<T, VERSION, KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `Versioned`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, const VERSION: u8, KeyType> ::core::fmt::Debug for Versioned<T, VERSION, KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Versioned")
+            .field("key", &KeyType::KEY)
+            .field("version", &VERSION)
+            .finish()
+    }
+}
+
+/// A value as read from the storage, together with whether it had to be migrated.
+struct Stored<T, const VERSION: u8> {
+    value: T,
+    migrated: bool,
+}
+
+impl<T, const VERSION: u8> Storable for Stored<T, VERSION>
+where
+    T: Packed + Migrate,
+{
+    fn encode<O: Output + ?Sized>(&self, dest: &mut O) {
+        Tagged::<T, VERSION>(&self.value).encode(dest)
+    }
+
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let version = <u8 as scale::Decode>::decode(input)?;
+        if version > VERSION {
+            return Err("stored version is newer than the current version".into())
+        }
+        let migrated = version < VERSION;
+        let value = if migrated {
+            T::migrate(version, input)?
+        } else {
+            <T as scale::Decode>::decode(input)?
+        };
+        Ok(Self { value, migrated })
+    }
+}
+
+/// A reference to a value to be written together with the version `VERSION`.
+struct Tagged<'a, T, const VERSION: u8>(&'a T);
+
+impl<T, const VERSION: u8> Storable for Tagged<'_, T, VERSION>
+where
+    T: Packed,
+{
+    fn encode<O: Output + ?Sized>(&self, dest: &mut O) {
+        scale::Encode::encode_to(&VERSION, dest);
+        scale::Encode::encode_to(self.0, dest);
+    }
+
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        // Values are only written as `Tagged` and read back as `Stored`.
+        Err("a tagged reference can't be decoded".into())
+    }
+}
+
+impl<T, const VERSION: u8, KeyType> Versioned<T, VERSION, KeyType>
+where
+    T: Packed + Migrate,
+    KeyType: StorageKey,
+{
+    /// Reads the value from the contract storage, if it exists.
+    ///
+    /// A value stored with an older version is migrated and written back in the
+    /// current version.
+    ///
+    /// # Panics
+    ///
+    /// If the value can't be decoded or migrated, e.g. because it was stored with a
+    /// newer version.
+    pub fn get(&self) -> Option<T> {
+        let stored =
+            ink_env::get_contract_storage::<Key, Stored<T, VERSION>>(&KeyType::KEY)
+                .unwrap_or_else(|error| {
+                    panic!("Failed to get value of Versioned: {:?}", error)
+                })?;
+        if stored.migrated {
+            ink_env::set_contract_storage::<Key, Stored<T, VERSION>>(
+                &KeyType::KEY,
+                &stored,
+            );
+        }
+        Some(stored.value)
+    }
+
+    /// Writes the value to the contract storage in the current version.
+    pub fn set(&mut self, value: &T) {
+        ink_env::set_contract_storage(&KeyType::KEY, &Tagged::<T, VERSION>(value));
+    }
+
+    /// Returns the version the value is stored with, without migrating it.
+    ///
+    /// Returns `None` if no value exists.
+    pub fn version(&self) -> Option<u8> {
+        // Only the leading version byte is decoded.
+        ink_env::get_contract_storage::<Key, u8>(&KeyType::KEY).unwrap_or_else(|error| {
+            panic!("Failed to get version of Versioned: {:?}", error)
+        })
+    }
+}
+
+impl<T, const VERSION: u8, KeyType> Versioned<T, VERSION, KeyType>
+where
+    T: Packed + Migrate + Default,
+    KeyType: StorageKey,
+{
+    /// Reads the value from the contract storage, migrating it if necessary.
+    ///
+    /// Returns the default value for the storage type if no value exists.
+    pub fn get_or_default(&self) -> T {
+        self.get().unwrap_or_default()
+    }
+}
+
+impl<T, const VERSION: u8, KeyType> Storable for Versioned<T, VERSION, KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<T, const VERSION: u8, Key, InnerKey> StorableHint<Key>
+    for Versioned<T, VERSION, InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Versioned<T, VERSION, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<T, const VERSION: u8, KeyType> StorageKey for Versioned<T, VERSION, KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        RootLayout,
+        StructLayout,
+    };
+
+    impl<T, const VERSION: u8, KeyType> StorageLayout for Versioned<T, VERSION, KeyType>
+    where
+        T: StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                StructLayout::new(
+                    "Versioned",
+                    [
+                        FieldLayout::new(
+                            "version",
+                            <u8 as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                        FieldLayout::new(
+                            "value",
+                            <T as StorageLayout>::layout(&KeyType::KEY),
+                        ),
+                    ],
+                ),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+    use scale::{
+        Decode,
+        Encode,
+    };
+
+    #[derive(Debug, Default, PartialEq, Eq, Encode, Decode)]
+    struct Config {
+        fee: u16,
+        paused: bool,
+    }
+
+    impl Migrate for Config {
+        fn migrate<I: Input>(version: u8, input: &mut I) -> Result<Self, Error> {
+            match version {
+                1 => {
+                    Ok(Self {
+                        fee: <u8 as Decode>::decode(input)?.into(),
+                        paused: false,
+                    })
+                }
+                _ => Err("unknown version".into()),
+            }
+        }
+    }
+
+    #[test]
+    fn current_version_roundtrips() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut config: Versioned<Config, 2, ManualKey<123>> = Versioned::new();
+            assert_eq!(config.get(), None);
+            assert_eq!(config.version(), None);
+
+            let value = Config {
+                fee: 300,
+                paused: true,
+            };
+            config.set(&value);
+            assert_eq!(config.version(), Some(2));
+            assert_eq!(config.get(), Some(value));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn older_version_is_migrated_and_written_back() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage(&123u32, &(1u8, 42u8));
+            let config: Versioned<Config, 2, ManualKey<123>> = Versioned::new();
+            assert_eq!(config.version(), Some(1));
+
+            let migrated = Config {
+                fee: 42,
+                paused: false,
+            };
+            assert_eq!(config.get(), Some(migrated));
+            assert_eq!(config.version(), Some(2));
+            assert_eq!(
+                ink_env::get_contract_storage::<_, (u8, Config)>(&123u32),
+                Ok(Some((
+                    2,
+                    Config {
+                        fee: 42,
+                        paused: false
+                    }
+                )))
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to get value of Versioned")]
+    fn newer_version_fails() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage(&123u32, &(3u8, 42u16, false));
+            let config: Versioned<Config, 2, ManualKey<123>> = Versioned::new();
+            config.get();
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,425 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of a contract's storage into a JSON value tree.
+//!
+//! Given the storage layout and the type registry from the metadata of a contract
+//! together with a dump of its storage cells (e.g. fetched from a node), the
+//! [`StorageDecoder`] reconstructs the state of the contract as a [`serde_json::Value`].
+//!
+//! # Note
+//!
+//! The keys of the dump are the raw storage keys as used by the contract, i.e. the
+//! SCALE encoded root key of a cell, followed by the SCALE encoded mapping key for
+//! cells of a [`Mapping`](crate::Mapping).
+//!
+//! The layout is decoded as follows:
+//!
+//! - A root layout loads the cell stored under its root key and decodes the cell with
+//!   the nested layout. If there are cells with the root key as a prefix, the root is
+//!   decoded into an object of all those cells, indexed by the hex encoded key suffix.
+//!   The cell stored directly under the root key, if any, has the key `"0x"`.
+//! - Structs are decoded into objects of their fields.
+//! - Enums are decoded into an object with the name of the variant as the only key.
+//! - Arrays are decoded into arrays.
+//! - Leafs are decoded with the type registry and rendered like by
+//!   [`ink_metadata::json`].
+
+use ink_metadata::{
+    json,
+    layout::{
+        ArrayLayout,
+        EnumLayout,
+        Layout,
+        RootLayout,
+        StructLayout,
+    },
+    InkProject,
+};
+use ink_prelude::collections::BTreeMap;
+use scale::Decode;
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+/// An error that can occur while decoding the contract storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The bytes of a cell could not be decoded into the expected type.
+    Scale(scale::Error),
+    /// The value of a leaf could not be rendered with its type from the registry.
+    Type(json::Error),
+    /// The decoded discriminant does not exist in the enum layout.
+    UnknownDiscriminant {
+        /// The name of the enum layout.
+        name: String,
+        /// The decoded discriminant.
+        discriminant: u8,
+    },
+    /// The layout or type can not be decoded by this module.
+    Unsupported(&'static str),
+    /// The cell under the given key was not consumed completely by its layout.
+    TrailingBytes(Vec<u8>),
+}
+
+impl From<scale::Error> for Error {
+    fn from(error: scale::Error) -> Self {
+        Self::Scale(error)
+    }
+}
+
+impl From<json::Error> for Error {
+    fn from(error: json::Error) -> Self {
+        Self::Type(error)
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Scale(error) => write!(f, "failed to decode storage cell: {}", error),
+            Self::Type(error) => write!(f, "failed to decode leaf: {}", error),
+            Self::UnknownDiscriminant { name, discriminant } => {
+                write!(
+                    f,
+                    "unknown discriminant `{}` for enum layout `{}`",
+                    discriminant, name
+                )
+            }
+            Self::Unsupported(what) => write!(f, "decoding {} is not supported", what),
+            Self::TrailingBytes(key) => {
+                write!(f, "cell under key `{}` has trailing bytes", to_hex(key))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Decodes the storage of the contract described by `project` from the given `cells`.
+pub fn decode_contract_storage<I, K, V>(
+    project: &InkProject,
+    cells: I,
+) -> Result<Value, Error>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<Vec<u8>>,
+    V: Into<Vec<u8>>,
+{
+    StorageDecoder::new(project.registry(), cells).decode(project.layout())
+}
+
+/// Decodes storage cells of a contract according to its storage layout.
+pub struct StorageDecoder<'a> {
+    /// The registry with the types referenced by the layout.
+    registry: &'a PortableRegistry,
+    /// The storage cells of the contract, ordered by their keys.
+    cells: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl<'a> StorageDecoder<'a> {
+    /// Creates a new decoder for the given storage `cells`.
+    pub fn new<I, K, V>(registry: &'a PortableRegistry, cells: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Vec<u8>>,
+        V: Into<Vec<u8>>,
+    {
+        Self {
+            registry,
+            cells: cells
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        }
+    }
+
+    /// Decodes the storage described by `layout` into a JSON value tree.
+    pub fn decode(&self, layout: &Layout<PortableForm>) -> Result<Value, Error> {
+        match layout {
+            Layout::Root(root) => self.decode_root(root),
+            // Without a root there is no cell to decode the layout from.
+            _ => Err(Error::Unsupported("layouts without a root")),
+        }
+    }
+
+    /// Decodes a value of the type with id `ty` from `input`.
+    pub fn decode_type(&self, ty: u32, input: &mut &[u8]) -> Result<Value, Error> {
+        json::render_value(self.registry, ty, input).map_err(Into::into)
+    }
+
+    /// Decodes the cells of a root layout.
+    fn decode_root(&self, root: &RootLayout<PortableForm>) -> Result<Value, Error> {
+        let prefix = root.root_key().to_storage_bytes().to_vec();
+        let mut value = None;
+        let mut entries = Map::new();
+        for (key, cell) in self.cells.range(prefix.clone()..) {
+            if !key.starts_with(&prefix) {
+                break
+            }
+            let input = &mut &cell[..];
+            let decoded = self.decode_layout(root.layout(), input)?;
+            if !input.is_empty() {
+                return Err(Error::TrailingBytes(key.clone()))
+            }
+            let suffix = &key[prefix.len()..];
+            if suffix.is_empty() {
+                value = Some(decoded.clone());
+            }
+            entries.insert(to_hex(suffix), decoded);
+        }
+        match (value, entries.len()) {
+            (None, 0) => Ok(Value::Null),
+            (Some(value), 1) => Ok(value),
+            _ => Ok(Value::Object(entries)),
+        }
+    }
+
+    /// Decodes the part of a cell described by the non-root `layout`.
+    fn decode_layout(
+        &self,
+        layout: &Layout<PortableForm>,
+        input: &mut &[u8],
+    ) -> Result<Value, Error> {
+        match layout {
+            Layout::Leaf(leaf) => self.decode_type(leaf.ty().id, input),
+            // The nested root is stored in its own cells, so it doesn't consume any input.
+            Layout::Root(root) => self.decode_root(root),
+            Layout::Hash(_) => Err(Error::Unsupported("hash layouts")),
+            Layout::Array(array) => self.decode_array(array, input),
+            Layout::Struct(layout) => self.decode_struct(layout, input),
+            Layout::Enum(layout) => self.decode_enum(layout, input),
+        }
+    }
+
+    fn decode_array(
+        &self,
+        array: &ArrayLayout<PortableForm>,
+        input: &mut &[u8],
+    ) -> Result<Value, Error> {
+        (0..array.len())
+            .map(|_| self.decode_layout(array.layout(), input))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)
+    }
+
+    fn decode_struct(
+        &self,
+        layout: &StructLayout<PortableForm>,
+        input: &mut &[u8],
+    ) -> Result<Value, Error> {
+        let mut object = Map::new();
+        for field in layout.fields() {
+            object.insert(
+                field.name().clone(),
+                self.decode_layout(field.layout(), input)?,
+            );
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn decode_enum(
+        &self,
+        layout: &EnumLayout<PortableForm>,
+        input: &mut &[u8],
+    ) -> Result<Value, Error> {
+        let discriminant = u8::decode(input)?;
+        let variant = layout
+            .variants()
+            .iter()
+            .find(|(key, _)| key.value() == discriminant as usize)
+            .map(|(_, variant)| variant)
+            .ok_or_else(|| {
+                Error::UnknownDiscriminant {
+                    name: layout.name().clone(),
+                    discriminant,
+                }
+            })?;
+        let mut object = Map::new();
+        object.insert(variant.name().clone(), self.decode_struct(variant, input)?);
+        Ok(Value::Object(object))
+    }
+}
+
+/// Returns the `0x` prefixed hex representation of `bytes`.
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + 2 * bytes.len());
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{:02x}", byte));
+    }
+    hex
+}
+
+#[cfg(test)]
+#[allow(trivial_numeric_casts)]
+mod tests {
+    use super::*;
+    use crate::{
+        traits::{
+            ManualKey,
+            StorageLayout,
+        },
+        Mapping,
+    };
+    use ink_metadata::layout::{
+        FieldLayout,
+        LayoutKey,
+        RootLayout,
+        StructLayout,
+    };
+    use ink_primitives::Key;
+    use scale::Encode;
+    use scale_info::{
+        IntoPortable,
+        Registry,
+    };
+    use serde_json::json;
+
+    #[derive(scale::Encode, scale::Decode, scale_info::TypeInfo)]
+    struct Point {
+        x: u32,
+        y: i64,
+    }
+
+    #[derive(scale::Encode, scale::Decode, scale_info::TypeInfo)]
+    enum Status {
+        Active,
+        Closed(Vec<u8>),
+    }
+
+    /// Returns the layout of a contract with packed fields and a mapping.
+    fn contract_layout() -> (Layout<PortableForm>, PortableRegistry) {
+        let root_key: Key = 0;
+        let layout = Layout::Root(RootLayout::new(
+            LayoutKey::from(&root_key),
+            StructLayout::new(
+                "Contract",
+                [
+                    FieldLayout::new("point", {
+                        ink_metadata::layout::LeafLayout::from_key::<Point>(
+                            LayoutKey::from(&root_key),
+                        )
+                    }),
+                    FieldLayout::new("status", {
+                        ink_metadata::layout::LeafLayout::from_key::<Status>(
+                            LayoutKey::from(&root_key),
+                        )
+                    }),
+                    FieldLayout::new(
+                        "balances",
+                        <Mapping<u8, u128, ManualKey<7>> as StorageLayout>::layout(
+                            &root_key,
+                        ),
+                    ),
+                ],
+            ),
+        ));
+        let mut registry = Registry::new();
+        let layout = layout.into_portable(&mut registry);
+        (layout, registry.into())
+    }
+
+    #[test]
+    fn decodes_packed_fields_and_mappings() {
+        let (layout, registry) = contract_layout();
+        let root = (Point { x: 1, y: -2 }, Status::Closed(vec![0xde, 0xad])).encode();
+        let cells = vec![
+            (0u32.encode(), root),
+            ((7u32, 1u8).encode(), 100u128.encode()),
+            ((7u32, 2u8).encode(), u128::MAX.encode()),
+        ];
+
+        let decoded = StorageDecoder::new(&registry, cells).decode(&layout);
+
+        assert_eq!(
+            decoded,
+            Ok(json!({
+                "point": { "x": 1, "y": -2 },
+                "status": { "Closed": "0xdead" },
+                "balances": {
+                    "0x01": 100,
+                    "0x02": u128::MAX.to_string(),
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn missing_cells_decode_to_null() {
+        let (layout, registry) = contract_layout();
+        let cells = vec![(
+            0u32.encode(),
+            (Point { x: 1, y: 2 }, Status::Active).encode(),
+        )];
+
+        let decoded = StorageDecoder::new(&registry, cells).decode(&layout);
+
+        assert_eq!(
+            decoded,
+            Ok(json!({
+                "point": { "x": 1, "y": 2 },
+                "status": "Active",
+                "balances": null,
+            }))
+        );
+    }
+
+    #[test]
+    fn trailing_bytes_are_reported() {
+        let (layout, registry) = contract_layout();
+        let mut root = (Point { x: 1, y: 2 }, Status::Active).encode();
+        root.push(0);
+        let cells = vec![(0u32.encode(), root)];
+
+        let decoded = StorageDecoder::new(&registry, cells).decode(&layout);
+
+        assert_eq!(decoded, Err(Error::TrailingBytes(0u32.encode())));
+    }
+
+    #[test]
+    fn invalid_variant_is_reported() {
+        let (layout, registry) = contract_layout();
+        let mut root = Point { x: 1, y: 2 }.encode();
+        root.push(5);
+        let cells = vec![(0u32.encode(), root)];
+
+        let decoded = StorageDecoder::new(&registry, cells).decode(&layout);
+
+        assert!(matches!(
+            decoded,
+            Err(Error::Type(json::Error::UnknownVariant { index: 5, .. }))
+        ));
+    }
+
+    #[test]
+    fn oversized_length_is_rejected() {
+        let (layout, registry) = contract_layout();
+        let mut root = Point { x: 1, y: 2 }.encode();
+        // The bytes of `Status::Closed` claim a length exceeding the cell.
+        root.push(1);
+        root.extend(scale::Compact(u32::MAX).encode());
+        root.extend([0xde, 0xad]);
+        let cells = vec![(0u32.encode(), root)];
+
+        let decoded = StorageDecoder::new(&registry, cells).decode(&layout);
+
+        assert!(matches!(decoded, Err(Error::Type(json::Error::Decode(_)))));
+    }
+}
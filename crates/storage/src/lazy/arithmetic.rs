@@ -0,0 +1,91 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checked arithmetic on numeric values in contract storage.
+
+/// Returned if a checked update of a numeric value in storage failed.
+///
+/// The stored value is left untouched in this case.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ArithmeticError {
+    /// The result would be greater than the maximum value of the numeric type.
+    Overflow,
+    /// The result would be less than the minimum value of the numeric type.
+    Underflow,
+}
+
+/// Numeric types whose values in storage can be updated with checked arithmetic.
+///
+/// Implemented for all primitive integer types.
+pub trait CheckedArithmetic: Copy + Default + PartialOrd {
+    /// Computes `self + rhs`, returning `None` if the result is out of range.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Computes `self - rhs`, returning `None` if the result is out of range.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_arithmetic {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl CheckedArithmetic for $ty {
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, rhs)
+                }
+
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+#[rustfmt::skip]
+impl_checked_arithmetic!(
+    u8, u16, u32, u64, u128,
+    i8, i16, i32, i64, i128,
+);
+
+/// Adds `delta` to `value`, classifying an out of range result by the sign of `delta`.
+pub(crate) fn checked_add<V>(value: V, delta: V) -> Result<V, ArithmeticError>
+where
+    V: CheckedArithmetic,
+{
+    value.checked_add(delta).ok_or(
+        if delta < V::default() {
+            ArithmeticError::Underflow
+        } else {
+            ArithmeticError::Overflow
+        },
+    )
+}
+
+/// Subtracts `delta` from `value`, classifying an out of range result by the sign
+/// of `delta`.
+pub(crate) fn checked_sub<V>(value: V, delta: V) -> Result<V, ArithmeticError>
+where
+    V: CheckedArithmetic,
+{
+    value.checked_sub(delta).ok_or(
+        if delta < V::default() {
+            ArithmeticError::Overflow
+        } else {
+            ArithmeticError::Underflow
+        },
+    )
+}
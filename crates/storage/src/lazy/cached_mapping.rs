@@ -0,0 +1,294 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A write-back cache in front of a [`Mapping`] for the duration of a call.
+
+use super::{
+    key_encoder::{
+        Identity,
+        KeyEncoder,
+    },
+    Mapping,
+};
+use crate::traits::{
+    Packed,
+    StorageKey,
+};
+use ink_prelude::collections::BTreeMap;
+use scale::Encode;
+
+/// A cached entry of a [`CachedMapping`].
+struct Entry<V> {
+    /// The value, `None` if there is none in storage or it was removed.
+    value: Option<V>,
+    /// Whether the value differs from storage.
+    dirty: bool,
+}
+
+/// A write-back cache in front of a [`Mapping`].
+///
+/// Every key is loaded from storage at most once and every changed key is written
+/// to storage only once, no matter how often it is read or written in between.
+/// This saves the repeated host calls of contracts touching the same keys many
+/// times during a call, e.g. when settling many transfers between few accounts.
+///
+/// The changed entries are written to storage on [`flush`](Self::flush) or when
+/// the cache is dropped, which usually happens at the end of the scope it was
+/// created in. Reading from the [`Mapping`] bypasses the cache, hence the cache
+/// borrows the mapping mutably for its whole lifetime.
+///
+/// # Note
+///
+/// All cached entries are kept in memory until the cache is dropped, so caching
+/// many distinct keys increases the memory usage of the call.
+///
+/// This is an example of how you can use the cache:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::Mapping;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Token {
+///     balances: Mapping<AccountId, Balance>,
+/// }
+///
+/// impl Token {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn transfer_batch(&mut self, transfers: Vec<(AccountId, Balance)>) {
+///         let caller = self.env().caller();
+///         let mut balances = self.balances.cached();
+///         for (to, value) in transfers {
+///             let from_balance = balances.get(&caller).unwrap_or(0);
+///             assert!(from_balance >= value);
+///             balances.insert(caller, from_balance - value);
+///             let to_balance = balances.get(&to).unwrap_or(0);
+///             balances.insert(to, to_balance + value);
+///         }
+///         // The balance of the caller is written only once when `balances` is
+///         // dropped here.
+///     }
+/// }
+/// # }
+/// ```
+pub struct CachedMapping<'a, K, V, KeyType, Encoder = Identity>
+where
+    K: Ord + Encode,
+    V: Packed,
+    KeyType: StorageKey,
+    Encoder: KeyEncoder,
+{
+    mapping: &'a mut Mapping<K, V, KeyType, Encoder>,
+    cache: BTreeMap<K, Entry<V>>,
+}
+
+impl<'a, K, V, KeyType, Encoder> CachedMapping<'a, K, V, KeyType, Encoder>
+where
+    K: Ord + Encode,
+    V: Packed,
+    KeyType: StorageKey,
+    Encoder: KeyEncoder,
+{
+    /// Creates an empty cache in front of `mapping`.
+    pub fn new(mapping: &'a mut Mapping<K, V, KeyType, Encoder>) -> Self {
+        Self {
+            mapping,
+            cache: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the `value` at `key`.
+    ///
+    /// Loads the value from storage only if `key` is not cached yet.
+    pub fn get(&mut self, key: &K) -> Option<V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.entry(key).value.clone()
+    }
+
+    /// Checks if there is a value at `key`.
+    ///
+    /// Loads the value from storage only if `key` is not cached yet.
+    pub fn contains(&mut self, key: &K) -> bool
+    where
+        K: Clone,
+    {
+        self.entry(key).value.is_some()
+    }
+
+    /// Inserts `value` at `key` into the cache.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(
+            key,
+            Entry {
+                value: Some(value),
+                dirty: true,
+            },
+        );
+    }
+
+    /// Removes the value at `key` from the cache.
+    pub fn remove(&mut self, key: K) {
+        self.cache.insert(
+            key,
+            Entry {
+                value: None,
+                dirty: true,
+            },
+        );
+    }
+
+    /// Writes the changed entries to storage.
+    ///
+    /// The entries stay cached, so reading them afterwards doesn't load them again.
+    pub fn flush(&mut self) {
+        for (key, entry) in self.cache.iter_mut().filter(|(_, entry)| entry.dirty) {
+            match &entry.value {
+                Some(value) => {
+                    ink_env::set_contract_storage(
+                        &Mapping::<K, V, KeyType, Encoder>::storage_key(key),
+                        value,
+                    );
+                }
+                None => {
+                    ink_env::clear_contract_storage(
+                        &Mapping::<K, V, KeyType, Encoder>::storage_key(key),
+                    );
+                }
+            }
+            entry.dirty = false;
+        }
+    }
+
+    /// Returns the cached entry of `key` after loading it from storage if needed.
+    fn entry(&mut self, key: &K) -> &mut Entry<V>
+    where
+        K: Clone,
+    {
+        let mapping = &self.mapping;
+        self.cache.entry(key.clone()).or_insert_with(|| {
+            Entry {
+                value: mapping.get(key),
+                dirty: false,
+            }
+        })
+    }
+}
+
+impl<'a, K, V, KeyType, Encoder> Drop for CachedMapping<'a, K, V, KeyType, Encoder>
+where
+    K: Ord + Encode,
+    V: Packed,
+    KeyType: StorageKey,
+    Encoder: KeyEncoder,
+{
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl<'a, K, V, KeyType, Encoder> ::core::fmt::Debug
+    for CachedMapping<'a, K, V, KeyType, Encoder>
+where
+    K: Ord + Encode,
+    V: Packed,
+    KeyType: StorageKey,
+    Encoder: KeyEncoder,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("CachedMapping")
+            .field("key", &KeyType::KEY)
+            .field("cached", &self.cache.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    fn storage_rw() -> (usize, usize) {
+        let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+        ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+            &accounts.alice,
+        )
+    }
+
+    #[test]
+    fn reads_and_writes_every_key_once() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: Mapping<u8, u32, ManualKey<123>> = Mapping::new();
+            mapping.insert(1, &10);
+            let (reads, writes) = storage_rw();
+
+            // When
+            let mut cached = mapping.cached();
+            for _ in 0..10 {
+                let value = cached.get(&1).unwrap();
+                cached.insert(1, value + 1);
+                assert!(!cached.contains(&2));
+            }
+            cached.insert(3, 30);
+            cached.remove(3);
+            drop(cached);
+
+            // Then
+            assert_eq!(storage_rw(), (reads + 2, writes + 2));
+            assert_eq!(mapping.get(1), Some(20));
+            assert!(!mapping.contains(3));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn flush_writes_only_changed_entries() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: Mapping<u8, u32, ManualKey<123>> = Mapping::new();
+            mapping.insert(1, &10);
+            mapping.insert(2, &20);
+            let mut cached = mapping.cached();
+            assert_eq!(cached.get(&1), Some(10));
+            cached.insert(2, 21);
+            cached.remove(1);
+
+            // When
+            cached.flush();
+            cached.flush();
+            let (reads, writes) = storage_rw();
+            assert_eq!(cached.get(&1), None);
+            drop(cached);
+
+            // Then
+            assert_eq!(storage_rw(), (reads, writes));
+            assert_eq!(mapping.get(1), None);
+            assert_eq!(mapping.get(2), Some(21));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strategies to encode the keys of a [`Mapping`](crate::Mapping) into storage keys.
+//!
+//! The storage key of a value in a mapping is made up of the root key of the mapping
+//! followed by the key of the value as written by the [`KeyEncoder`] of the mapping.
+
+use ink_env::hash::{
+    Blake2x128,
+    HashOutput,
+    Keccak256,
+};
+use scale::{
+    Encode,
+    Output,
+};
+
+/// Writes the keys of a [`Mapping`](crate::Mapping) into its storage keys.
+///
+/// The storage key part of a key is its hash followed by the SCALE encoded key if
+/// [`KeyEncoder::CONCAT`] is set.
+pub trait KeyEncoder {
+    /// The hash of a key.
+    type Hash: AsRef<[u8]>;
+
+    /// Whether the SCALE encoded key follows its hash.
+    const CONCAT: bool;
+
+    /// Returns the hash of the SCALE encoded `key`.
+    ///
+    /// # Note
+    ///
+    /// The hash is computed before the storage is accessed, since the environment
+    /// can't compute hashes while it encodes a storage key.
+    fn hash<K: Encode>(key: &K) -> Self::Hash;
+
+    /// Returns the hashing strategy to describe the storage keys in the metadata,
+    /// `None` if the keys are stored as they are.
+    #[cfg(feature = "std")]
+    fn hashing_strategy() -> Option<ink_metadata::layout::HashingStrategy>;
+}
+
+/// Stores the SCALE encoded keys as they are.
+///
+/// This is the default of the [`Mapping`](crate::Mapping). Since the encodings of
+/// compound keys, e.g. tuples, are just concatenated, the storage key of one key may
+/// be a prefix of the storage key of another key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Identity;
+
+impl KeyEncoder for Identity {
+    type Hash = [u8; 0];
+    const CONCAT: bool = true;
+
+    #[inline]
+    fn hash<K: Encode>(_key: &K) -> Self::Hash {
+        []
+    }
+
+    #[cfg(feature = "std")]
+    fn hashing_strategy() -> Option<ink_metadata::layout::HashingStrategy> {
+        None
+    }
+}
+
+/// Stores the BLAKE-2 hash with an output of 128 bits of the SCALE encoded keys,
+/// followed by the encoded keys themselves.
+///
+/// The hash gives every storage key a fixed size prefix, so that no storage key is a
+/// prefix of another one, while the keys can still be read back from the storage keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Blake2Concat;
+
+impl KeyEncoder for Blake2Concat {
+    type Hash = <Blake2x128 as HashOutput>::Type;
+    const CONCAT: bool = true;
+
+    #[inline]
+    fn hash<K: Encode>(key: &K) -> Self::Hash {
+        let mut hash = Self::Hash::default();
+        ink_env::hash_encoded::<Blake2x128, K>(key, &mut hash);
+        hash
+    }
+
+    #[cfg(feature = "std")]
+    fn hashing_strategy() -> Option<ink_metadata::layout::HashingStrategy> {
+        Some(ink_metadata::layout::HashingStrategy::new(
+            ink_metadata::layout::CryptoHasher::Blake2x128Concat,
+            Default::default(),
+            Default::default(),
+        ))
+    }
+}
+
+/// Stores the KECCAK hash with an output of 256 bits of the SCALE encoded keys.
+///
+/// This allows Solidity-style `keccak256(key)` storage layouts, e.g. to compute the
+/// storage keys of the values off-chain with the tooling of the Ethereum ecosystem.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Keccak;
+
+impl KeyEncoder for Keccak {
+    type Hash = <Keccak256 as HashOutput>::Type;
+    const CONCAT: bool = false;
+
+    #[inline]
+    fn hash<K: Encode>(key: &K) -> Self::Hash {
+        let mut hash = Self::Hash::default();
+        ink_env::hash_encoded::<Keccak256, K>(key, &mut hash);
+        hash
+    }
+
+    #[cfg(feature = "std")]
+    fn hashing_strategy() -> Option<ink_metadata::layout::HashingStrategy> {
+        Some(ink_metadata::layout::HashingStrategy::new(
+            ink_metadata::layout::CryptoHasher::Keccak256,
+            Default::default(),
+            Default::default(),
+        ))
+    }
+}
+
+/// A key of a [`Mapping`](crate::Mapping) together with its hash computed by the key
+/// encoder `E`.
+pub struct EncodedKey<E: KeyEncoder, Q> {
+    key: Q,
+    hash: E::Hash,
+}
+
+impl<E, Q> EncodedKey<E, Q>
+where
+    E: KeyEncoder,
+    Q: Encode,
+{
+    /// Computes the hash of `key` with the key encoder `E`.
+    pub(crate) fn new(key: Q) -> Self {
+        let hash = E::hash(&key);
+        Self { key, hash }
+    }
+}
+
+impl<E, Q> Encode for EncodedKey<E, Q>
+where
+    E: KeyEncoder,
+    Q: Encode,
+{
+    #[inline]
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        dest.write(self.hash.as_ref());
+        if E::CONCAT {
+            self.key.encode_to(dest)
+        }
+    }
+}
+
+impl<E, Q> ::core::fmt::Debug for EncodedKey<E, Q>
+where
+    E: KeyEncoder,
+    Q: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("EncodedKey")
+            .field("key", &self.key)
+            .field("hash", &self.hash.as_ref())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage_key<E: KeyEncoder>(key: (u8, u16)) -> Vec<u8> {
+        EncodedKey::<E, _>::new(key).encode()
+    }
+
+    #[test]
+    fn identity_keeps_encoding() {
+        assert_eq!(storage_key::<Identity>((1, 2)), (1u8, 2u16).encode());
+    }
+
+    #[test]
+    fn hashing_encoders_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let key = (1u8, 2u16);
+            let mut blake2 = [0; 16];
+            ink_env::hash_encoded::<Blake2x128, _>(&key, &mut blake2);
+            let mut keccak = [0; 32];
+            ink_env::hash_encoded::<Keccak256, _>(&key, &mut keccak);
+
+            assert_eq!(
+                storage_key::<Blake2Concat>(key),
+                [&blake2[..], &key.encode()[..]].concat()
+            );
+            assert_eq!(storage_key::<Keccak>(key), keccak.to_vec());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -19,6 +19,19 @@
 //! This mapping doesn't actually "own" any data.
 //! Instead it is just a simple wrapper around the contract storage facilities.
 
+use super::{
+    arithmetic::{
+        self,
+        ArithmeticError,
+        CheckedArithmetic,
+    },
+    key_encoder::{
+        EncodedKey,
+        Identity,
+        KeyEncoder,
+    },
+    CachedMapping,
+};
 use crate::traits::{
     AutoKey,
     Packed,
@@ -80,16 +93,94 @@ use scale::{
 /// ```
 ///
 /// More usage examples can be found [in the ink! examples](https://github.com/paritytech/ink/tree/master/examples).
+///
+/// # Key encoding
+///
+/// The storage key of a value is made up of the root key of the mapping followed by
+/// the key of the value as written by the [`KeyEncoder`] given with the `Encoder` type
+/// parameter. By default, the SCALE encoded key is stored as it is, see [`Identity`].
+/// [`Blake2Concat`](crate::key_encoder::Blake2Concat) prefixes the key with its hash, so that
+/// compound keys never make up ambiguous prefixes, while
+/// [`Keccak`](crate::key_encoder::Keccak) stores only the hash of the key, e.g. for Solidity-style
+/// storage layouts:
+///
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     key_encoder::Keccak,
+///     traits::AutoKey,
+///     Mapping,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct MyContract {
+///     balances: Mapping<AccountId, Balance, AutoKey, Keccak>,
+/// }
+///
+/// impl MyContract {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn balance_of(&self, owner: AccountId) -> Balance {
+///         self.balances.get(owner).unwrap_or_default()
+///     }
+/// }
+/// # }
+/// ```
+///
+/// # Nested mappings
+///
+/// The values of a mapping can be mappings themselves, e.g.
+/// `Mapping<AccountId, Mapping<AccountId, Balance>>`. The inner mapping at a key is
+/// accessed with [`Mapping::nested`]. Its entries are stored under the root key of
+/// the outer mapping combined with the outer key and the inner key, so the inner
+/// mappings of different outer keys never collide. The inner mappings therefore
+/// have no storage key of their own and must use the default [`AutoKey`].
+///
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::Mapping;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct MyToken {
+///     allowances: Mapping<AccountId, Mapping<AccountId, Balance>>,
+/// }
+///
+/// impl MyToken {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn approve(&mut self, spender: AccountId, value: Balance) {
+///         let caller = self.env().caller();
+///         self.allowances.nested(caller).insert(spender, &value);
+///     }
+///
+///     #[ink(message)]
+///     pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+///         self.allowances.nested(owner).get(spender).unwrap_or_default()
+///     }
+/// }
+/// # }
+/// ```
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-pub struct Mapping<K, V: Packed, KeyType: StorageKey = AutoKey> {
+pub struct Mapping<K, V, KeyType: StorageKey = AutoKey, Encoder = Identity> {
     #[allow(clippy::type_complexity)]
-    _marker: PhantomData<fn() -> (K, V, KeyType)>,
+    _marker: PhantomData<fn() -> (K, V, KeyType, Encoder)>,
 }
 
 /// We implement this manually because the derived implementation adds trait bounds.
-impl<K, V, KeyType> Default for Mapping<K, V, KeyType>
+impl<K, V, KeyType, Encoder> Default for Mapping<K, V, KeyType, Encoder>
 where
-    V: Packed,
     KeyType: StorageKey,
 {
     fn default() -> Self {
@@ -99,9 +190,8 @@ where
     }
 }
 
-impl<K, V, KeyType> Mapping<K, V, KeyType>
+impl<K, V, KeyType, Encoder> Mapping<K, V, KeyType, Encoder>
 where
-    V: Packed,
     KeyType: StorageKey,
 {
     /// Creates a new empty `Mapping`.
@@ -110,11 +200,27 @@ where
             _marker: Default::default(),
         }
     }
+
+    /// Removes all values of the mapping, including the values of nested mappings,
+    /// from the off-chain storage.
+    ///
+    /// Returns the number of removed values.
+    ///
+    /// # Note
+    ///
+    /// Only available off-chain, e.g. to reset the state between tests, because the
+    /// `contracts` pallet can't clear the storage by prefix, see
+    /// [`ink_env::clear_storage_prefix`]. The gas costs grow with the number of removed
+    /// values and may exceed the gas limit of a single call for large mappings.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn clear_all(&mut self) -> ink_env::Result<u32> {
+        ink_env::clear_storage_prefix(&KeyType::KEY)
+    }
 }
 
-impl<K, V, KeyType> ::core::fmt::Debug for Mapping<K, V, KeyType>
+impl<K, V, KeyType, Encoder> ::core::fmt::Debug for Mapping<K, V, KeyType, Encoder>
 where
-    V: Packed,
     KeyType: StorageKey,
 {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -124,11 +230,12 @@ where
     }
 }
 
-impl<K, V, KeyType> Mapping<K, V, KeyType>
+impl<K, V, KeyType, Encoder> Mapping<K, V, KeyType, Encoder>
 where
     K: Encode,
     V: Packed,
     KeyType: StorageKey,
+    Encoder: KeyEncoder,
 {
     /// Insert the given `value` to the contract storage.
     ///
@@ -139,7 +246,7 @@ where
         Q: scale::EncodeLike<K>,
         R: Storable + scale::EncodeLike<V>,
     {
-        ink_env::set_contract_storage(&(&KeyType::KEY, key), value)
+        ink_env::set_contract_storage(&Self::storage_key(key), value)
     }
 
     /// Get the `value` at `key` from the contract storage.
@@ -150,7 +257,7 @@ where
     where
         Q: scale::EncodeLike<K>,
     {
-        ink_env::get_contract_storage(&(&KeyType::KEY, key))
+        ink_env::get_contract_storage(&Self::storage_key(key))
             .unwrap_or_else(|error| panic!("Failed to get value in Mapping: {:?}", error))
     }
 
@@ -162,31 +269,35 @@ where
     where
         Q: scale::EncodeLike<K>,
     {
-        ink_env::take_contract_storage(&(&KeyType::KEY, key)).unwrap_or_else(|error| {
+        ink_env::take_contract_storage(&Self::storage_key(key)).unwrap_or_else(|error| {
             panic!("Failed to take value in Mapping: {:?}", error)
         })
     }
 
     /// Get the size of a value stored at `key` in the contract storage.
     ///
+    /// The size is the length of the SCALE encoded value in bytes. It is queried
+    /// from the host without loading or decoding the value, which allows to check
+    /// how expensive loading the value is beforehand.
+    ///
     /// Returns `None` if no `value` exists at the given `key`.
     #[inline]
     pub fn size<Q>(&self, key: Q) -> Option<u32>
     where
         Q: scale::EncodeLike<K>,
     {
-        ink_env::contains_contract_storage(&(&KeyType::KEY, key))
+        ink_env::contains_contract_storage(&Self::storage_key(key))
     }
 
     /// Checks if a value is stored at the given `key` in the contract storage.
     ///
-    /// Returns `None` if no `value` exists at the given `key`.
+    /// Only queries the size of the stored value, so the value is never decoded.
     #[inline]
     pub fn contains<Q>(&self, key: Q) -> bool
     where
         Q: scale::EncodeLike<K>,
     {
-        ink_env::contains_contract_storage(&(&KeyType::KEY, key)).is_some()
+        ink_env::contains_contract_storage(&Self::storage_key(key)).is_some()
     }
 
     /// Clears the value at `key` from storage.
@@ -195,13 +306,179 @@ where
     where
         Q: scale::EncodeLike<K>,
     {
-        ink_env::clear_contract_storage(&(&KeyType::KEY, key));
+        ink_env::clear_contract_storage(&Self::storage_key(key));
+    }
+
+    /// Clears the value at `key` from storage so that its storage deposit is refunded.
+    ///
+    /// Returns the size in bytes of the removed value if any.
+    ///
+    /// # Note
+    ///
+    /// The `contracts` pallet refunds the storage deposit to the origin of the call,
+    /// whoever paid it. The off-chain environment emulates this, see
+    /// `ink_env::test::storage_deposit`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ink::{
+    ///     env::{
+    ///         test,
+    ///         DefaultEnvironment,
+    ///     },
+    ///     storage::Mapping,
+    /// };
+    ///
+    /// test::run_test::<DefaultEnvironment, _>(|accounts| {
+    ///     // Alice creates an entry and pays its storage deposit.
+    ///     let mut balances: Mapping<u8, u32> = Mapping::new();
+    ///     balances.insert(1, &42);
+    ///
+    ///     // Alice cleans up the entry and gets the deposit back.
+    ///     assert_eq!(balances.remove_and_refund(1), Some(4));
+    ///
+    ///     let deposit = test::storage_deposit::<DefaultEnvironment>(&accounts.alice);
+    ///     assert_eq!(deposit.charged, deposit.refunded);
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    #[inline]
+    pub fn remove_and_refund<Q>(&self, key: Q) -> Option<u32>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::clear_contract_storage(&Self::storage_key(key))
+    }
+
+    /// Returns a write-back cache in front of the mapping, which loads and stores
+    /// every key at most once until it is dropped.
+    ///
+    /// See [`CachedMapping`] for details.
+    pub fn cached(&mut self) -> CachedMapping<'_, K, V, KeyType, Encoder>
+    where
+        K: Ord,
+    {
+        CachedMapping::new(self)
+    }
+
+    /// Returns the storage key of the value at `key`.
+    #[inline]
+    pub(crate) fn storage_key<Q>(key: Q) -> (Key, EncodedKey<Encoder, Q>)
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        (KeyType::KEY, EncodedKey::new(key))
     }
 }
 
-impl<K, V, KeyType> Storable for Mapping<K, V, KeyType>
+impl<K, V, KeyType, Encoder> Mapping<K, V, KeyType, Encoder>
+where
+    K: Encode,
+    V: Packed + CheckedArithmetic,
+    KeyType: StorageKey,
+    Encoder: KeyEncoder,
+{
+    /// Adds `delta` to the value at `key` and writes the sum back.
+    ///
+    /// A missing value is treated as the default value, i.e. zero.
+    /// Returns the new value.
+    ///
+    /// # Errors
+    ///
+    /// If the sum is out of the range of `V`. The stored value is not changed then.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ink::{
+    ///     env::{
+    ///         test,
+    ///         DefaultEnvironment,
+    ///     },
+    ///     storage::{
+    ///         ArithmeticError,
+    ///         Mapping,
+    ///     },
+    /// };
+    ///
+    /// test::run_test::<DefaultEnvironment, _>(|_| {
+    ///     let mut balances: Mapping<u8, u8> = Mapping::new();
+    ///     assert_eq!(balances.checked_add(1, 200), Ok(200));
+    ///     assert_eq!(balances.checked_add(1, 100), Err(ArithmeticError::Overflow));
+    ///     assert_eq!(balances.checked_sub(1, 201), Err(ArithmeticError::Underflow));
+    ///     assert_eq!(balances.get(1), Some(200));
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    #[inline]
+    pub fn checked_add<Q>(&mut self, key: Q, delta: V) -> Result<V, ArithmeticError>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        self.update(key, |value| arithmetic::checked_add(value, delta))
+    }
+
+    /// Subtracts `delta` from the value at `key` and writes the difference back.
+    ///
+    /// A missing value is treated as the default value, i.e. zero.
+    /// Returns the new value.
+    ///
+    /// # Errors
+    ///
+    /// If the difference is out of the range of `V`. The stored value is not changed
+    /// then.
+    #[inline]
+    pub fn checked_sub<Q>(&mut self, key: Q, delta: V) -> Result<V, ArithmeticError>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        self.update(key, |value| arithmetic::checked_sub(value, delta))
+    }
+
+    /// Writes back the value at `key` computed by `f` from the stored value, unless
+    /// `f` fails.
+    fn update<Q>(
+        &mut self,
+        key: Q,
+        f: impl FnOnce(V) -> Result<V, ArithmeticError>,
+    ) -> Result<V, ArithmeticError>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        let key = &Self::storage_key(key);
+        let value = ink_env::get_contract_storage(key)
+            .unwrap_or_else(|error| panic!("Failed to get value in Mapping: {:?}", error))
+            .unwrap_or_default();
+        let value = f(value)?;
+        ink_env::set_contract_storage(key, &value);
+        Ok(value)
+    }
+}
+
+impl<K, K2, V, KeyType, Encoder> Mapping<K, Mapping<K2, V>, KeyType, Encoder>
+where
+    K: Encode,
+    KeyType: StorageKey,
+    Encoder: KeyEncoder,
+{
+    /// Returns the inner mapping at `key`.
+    ///
+    /// The inner mapping doesn't need to be inserted first, it is empty until values
+    /// are inserted into it.
+    #[inline]
+    pub fn nested<Q>(&self, key: Q) -> NestedMapping<K2, V, (Key, EncodedKey<Encoder, Q>)>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        NestedMapping::new((KeyType::KEY, EncodedKey::new(key)))
+    }
+}
+
+impl<K, V, KeyType, Encoder> Storable for Mapping<K, V, KeyType, Encoder>
 where
-    V: Packed,
     KeyType: StorageKey,
 {
     #[inline]
@@ -213,44 +490,173 @@ where
     }
 }
 
-impl<K, V, Key, InnerKey> StorableHint<Key> for Mapping<K, V, InnerKey>
+impl<K, V, Key, InnerKey, Encoder> StorableHint<Key> for Mapping<K, V, InnerKey, Encoder>
 where
-    V: Packed,
     Key: StorageKey,
     InnerKey: StorageKey,
 {
-    type Type = Mapping<K, V, Key>;
+    type Type = Mapping<K, V, Key, Encoder>;
     type PreferredKey = InnerKey;
 }
 
-impl<K, V, KeyType> StorageKey for Mapping<K, V, KeyType>
+impl<K, V, KeyType, Encoder> StorageKey for Mapping<K, V, KeyType, Encoder>
 where
-    V: Packed,
     KeyType: StorageKey,
 {
     const KEY: Key = KeyType::KEY;
 }
 
+/// The inner mapping at a key of a nested [`Mapping`].
+///
+/// Returned by [`Mapping::nested`]. The entries are stored under the `prefix` made
+/// up of the root key of the outer mapping and the keys leading to this mapping,
+/// combined with the key of the entry.
+pub struct NestedMapping<K, V, Prefix> {
+    prefix: Prefix,
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V, Prefix> NestedMapping<K, V, Prefix> {
+    /// Creates the inner mapping stored under `prefix`.
+    fn new(prefix: Prefix) -> Self {
+        Self {
+            prefix,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K, V, Prefix> ::core::fmt::Debug for NestedMapping<K, V, Prefix>
+where
+    Prefix: ::core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("NestedMapping")
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl<K, V, Prefix> NestedMapping<K, V, Prefix>
+where
+    K: Encode,
+    V: Packed,
+    Prefix: Encode,
+{
+    /// Insert the given `value` to the contract storage.
+    ///
+    /// Returns the size in bytes of the pre-existing value at the specified key if any.
+    #[inline]
+    pub fn insert<Q, R>(&mut self, key: Q, value: &R) -> Option<u32>
+    where
+        Q: scale::EncodeLike<K>,
+        R: Storable + scale::EncodeLike<V>,
+    {
+        ink_env::set_contract_storage(&(&self.prefix, key), value)
+    }
+
+    /// Get the `value` at `key` from the contract storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn get<Q>(&self, key: Q) -> Option<V>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::get_contract_storage(&(&self.prefix, key))
+            .unwrap_or_else(|error| panic!("Failed to get value in Mapping: {:?}", error))
+    }
+
+    /// Removes the `value` at `key`, returning the previous `value` at `key` from storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn take<Q>(&self, key: Q) -> Option<V>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::take_contract_storage(&(&self.prefix, key)).unwrap_or_else(|error| {
+            panic!("Failed to take value in Mapping: {:?}", error)
+        })
+    }
+
+    /// Get the size of a value stored at `key` in the contract storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn size<Q>(&self, key: Q) -> Option<u32>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::contains_contract_storage(&(&self.prefix, key))
+    }
+
+    /// Checks if a value is stored at the given `key` in the contract storage.
+    #[inline]
+    pub fn contains<Q>(&self, key: Q) -> bool
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::contains_contract_storage(&(&self.prefix, key)).is_some()
+    }
+
+    /// Clears the value at `key` from storage.
+    #[inline]
+    pub fn remove<Q>(&self, key: Q)
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        ink_env::clear_contract_storage(&(&self.prefix, key));
+    }
+}
+
+impl<K, K2, V, Prefix> NestedMapping<K, Mapping<K2, V>, Prefix>
+where
+    K: Encode,
+    Prefix: Encode,
+{
+    /// Returns the inner mapping at `key`.
+    #[inline]
+    pub fn nested<Q>(&self, key: Q) -> NestedMapping<K2, V, (&Prefix, Q)>
+    where
+        Q: scale::EncodeLike<K>,
+    {
+        NestedMapping::new((&self.prefix, key))
+    }
+}
+
 #[cfg(feature = "std")]
 const _: () = {
     use crate::traits::StorageLayout;
     use ink_metadata::layout::{
+        HashLayout,
         Layout,
         LayoutKey,
         RootLayout,
     };
 
-    impl<K, V, KeyType> StorageLayout for Mapping<K, V, KeyType>
+    impl<K, V, KeyType, Encoder> StorageLayout for Mapping<K, V, KeyType, Encoder>
     where
         K: scale_info::TypeInfo + 'static,
-        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        V: StorageLayout + scale_info::TypeInfo + 'static,
         KeyType: StorageKey + scale_info::TypeInfo + 'static,
+        Encoder: KeyEncoder + scale_info::TypeInfo + 'static,
     {
-        fn layout(_: &Key) -> Layout {
-            Layout::Root(RootLayout::new(
-                LayoutKey::from(&KeyType::KEY),
-                <V as StorageLayout>::layout(&KeyType::KEY),
-            ))
+        fn layout(key: &Key) -> Layout {
+            // Only the inner mappings of nested mappings are left with the `AutoKey`,
+            // their entries are stored under the root key of the outer mapping.
+            let root_key = if KeyType::KEY == AutoKey::KEY {
+                *key
+            } else {
+                KeyType::KEY
+            };
+            let layout = <V as StorageLayout>::layout(&root_key);
+            let layout = match Encoder::hashing_strategy() {
+                Some(strategy) => HashLayout::new(root_key, strategy, layout).into(),
+                None => layout,
+            };
+            Layout::Root(RootLayout::new(LayoutKey::from(&root_key), layout))
         }
     }
 };
@@ -321,6 +727,78 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn take_clears_storage_cell() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: Mapping<u8, u8> = Mapping::new();
+            mapping.insert(1, &2);
+            mapping.insert(3, &4);
+            let callee = ink_env::test::callee::<ink_env::DefaultEnvironment>();
+            let used_cells = || {
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &callee,
+                )
+                .unwrap()
+            };
+            assert_eq!(used_cells(), 2);
+
+            // When
+            let taken = mapping.take(1);
+
+            // Then
+            assert_eq!(taken, Some(2));
+            assert!(!mapping.contains(1));
+            assert_eq!(used_cells(), 1);
+            assert_eq!(mapping.take(1), None);
+            assert_eq!(used_cells(), 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn size_returns_encoded_length() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: Mapping<u8, Vec<u8>> = Mapping::new();
+            mapping.insert(1, &vec![0u8; 10]);
+            mapping.insert(2, &vec![0u8; 100]);
+
+            // When
+            let sizes = [mapping.size(1), mapping.size(2), mapping.size(3)];
+
+            // Then
+            // The compact length prefix takes 1 byte below 64 elements and 2 above.
+            assert_eq!(sizes, [Some(11), Some(102), None]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn contains_does_not_decode_value() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mapping: Mapping<u8, Vec<u8>, ManualKey<123>> = Mapping::new();
+            // Not decodable as `Vec<u8>` since its length prefix is incomplete.
+            ink_env::set_contract_storage(&(&123u32, 1u8), &[0xFFu8]);
+
+            // When
+            let contains = mapping.contains(1);
+
+            // Then
+            assert!(contains);
+            assert!(!mapping.contains(2));
+            assert_eq!(mapping.size(1), Some(1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn can_clear_entries() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -341,6 +819,40 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn remove_and_refund_refunds_origin() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut mapping: Mapping<u8, u8> = Mapping::new();
+            mapping.insert(1, &2);
+            let charged = ink_env::test::storage_deposit::<ink_env::DefaultEnvironment>(
+                &accounts.alice,
+            )
+            .charged;
+
+            // When
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            let size = mapping.remove_and_refund(1);
+
+            // Then
+            assert_eq!(size, Some(1));
+            assert_eq!(mapping.get(1), None);
+            assert_eq!(
+                ink_env::test::storage_deposit::<ink_env::DefaultEnvironment>(
+                    &accounts.charlie
+                ),
+                ink_env::test::StorageDeposit {
+                    charged: 0,
+                    refunded: charged,
+                }
+            );
+            assert_eq!(mapping.remove_and_refund(1), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn can_clear_unexistent_entries() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -357,4 +869,205 @@ mod tests {
         })
         .unwrap()
     }
+
+    #[test]
+    fn nested_mappings_do_not_collide() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mapping: Mapping<u8, Mapping<u8, u32>, ManualKey<123>> = Mapping::new();
+
+            // When
+            mapping.nested(1).insert(2, &12);
+            mapping.nested(2).insert(1, &21);
+
+            // Then
+            assert_eq!(mapping.nested(1).get(2), Some(12));
+            assert_eq!(mapping.nested(2).get(1), Some(21));
+            assert_eq!(mapping.nested(1).get(1), None);
+            assert_eq!(mapping.nested(3).get(2), None);
+            assert_eq!(
+                ink_env::get_contract_storage::<_, u32>(&(&123u32, 1u8, 2u8)),
+                Ok(Some(12))
+            );
+
+            assert_eq!(mapping.nested(1).take(2), Some(12));
+            assert!(!mapping.nested(1).contains(2));
+            assert!(mapping.nested(2).contains(1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn deeply_nested_mappings_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mapping: Mapping<u8, Mapping<u8, Mapping<u8, u8>>, ManualKey<123>> =
+                Mapping::new();
+            mapping.nested(1).nested(2).insert(3, &4);
+
+            assert_eq!(mapping.nested(1).nested(2).get(3), Some(4));
+            assert_eq!(mapping.nested(1).nested(3).get(2), None);
+            assert_eq!(mapping.nested(1).nested(2).size(3), Some(1));
+            mapping.nested(1).nested(2).remove(3);
+            assert_eq!(mapping.nested(1).nested(2).get(3), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_all_removes_nested_entries() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // Given
+            let mut mapping: Mapping<u8, Mapping<u8, u8>, ManualKey<123>> =
+                Mapping::new();
+            let mut other: Mapping<u8, u8, ManualKey<124>> = Mapping::new();
+            mapping.nested(1).insert(2, &12);
+            mapping.nested(2).insert(1, &21);
+            other.insert(1, &1);
+
+            // When
+            let removed = mapping.clear_all();
+
+            // Then
+            assert_eq!(removed, Ok(2));
+            assert_eq!(mapping.nested(1).get(2), None);
+            assert_eq!(other.get(1), Some(1));
+            assert_eq!(
+                ink_env::test::count_used_storage_cells::<ink_env::DefaultEnvironment>(
+                    &accounts.alice
+                ),
+                Ok(1)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn nested_layout_uses_outer_root_key() {
+        use crate::traits::StorageLayout;
+        use ink_metadata::layout::{
+            Layout,
+            LayoutKey,
+            LeafLayout,
+            RootLayout,
+        };
+
+        let layout =
+            <Mapping<u8, Mapping<u8, u32>, ManualKey<123>> as StorageLayout>::layout(&0);
+
+        let key = LayoutKey::from(&123);
+        assert_eq!(
+            layout,
+            Layout::Root(RootLayout::new(
+                key,
+                Layout::Root(RootLayout::new(
+                    key,
+                    Layout::Leaf(LeafLayout::from_key::<u32>(key)),
+                )),
+            ))
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut mapping: Mapping<u8, i8> = Mapping::new();
+
+            // When
+            assert_eq!(mapping.checked_sub(1, 100), Ok(-100));
+            assert_eq!(mapping.checked_add(1, -28), Ok(-128));
+            assert_eq!(mapping.checked_add(1, -1), Err(ArithmeticError::Underflow));
+            assert_eq!(mapping.checked_sub(1, 1), Err(ArithmeticError::Underflow));
+            assert_eq!(mapping.checked_sub(2, -128), Err(ArithmeticError::Overflow));
+            assert_eq!(mapping.checked_add(2, 127), Ok(127));
+
+            // Then
+            assert_eq!(mapping.get(1), Some(-128));
+            assert_eq!(mapping.get(2), Some(127));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn key_encoders_store_under_encoded_keys() {
+        use crate::key_encoder::{
+            Blake2Concat,
+            Keccak,
+        };
+        use ink_env::hash::Keccak256;
+
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            // Given
+            let mut identity: Mapping<(u8, u16), u8, ManualKey<1>> = Mapping::new();
+            let mut blake2: Mapping<(u8, u16), u8, ManualKey<2>, Blake2Concat> =
+                Mapping::new();
+            let mut keccak: Mapping<(u8, u16), u8, ManualKey<3>, Keccak> = Mapping::new();
+
+            // When
+            identity.insert((1, 2), &10);
+            blake2.insert((1, 2), &20);
+            keccak.insert((1, 2), &30);
+
+            // Then
+            assert_eq!(identity.get((1, 2)), Some(10));
+            assert_eq!(blake2.get((1, 2)), Some(20));
+            assert_eq!(keccak.take((1, 2)), Some(30));
+            assert!(!keccak.contains((1, 2)));
+            assert_eq!(
+                ink_env::get_contract_storage::<_, u8>(&(1u32, 1u8, 2u16)),
+                Ok(Some(10))
+            );
+            let mut hash = [0; 32];
+            ink_env::hash_encoded::<Keccak256, _>(&(1u8, 2u16), &mut hash);
+            keccak.insert((1, 2), &31);
+            assert_eq!(
+                ink_env::get_contract_storage::<_, u8>(&(3u32, hash)),
+                Ok(Some(31))
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn hashing_key_encoder_is_reflected_in_layout() {
+        use crate::{
+            key_encoder::Keccak,
+            traits::StorageLayout,
+        };
+        use ink_metadata::layout::{
+            CryptoHasher,
+            HashLayout,
+            HashingStrategy,
+            Layout,
+            LayoutKey,
+            LeafLayout,
+            RootLayout,
+        };
+
+        let layout =
+            <Mapping<u8, u32, ManualKey<123>, Keccak> as StorageLayout>::layout(&0);
+
+        let key = LayoutKey::from(&123);
+        assert_eq!(
+            layout,
+            Layout::Root(RootLayout::new(
+                key,
+                HashLayout::new(
+                    key,
+                    HashingStrategy::new(CryptoHasher::Keccak256, Vec::new(), Vec::new()),
+                    Layout::Leaf(LeafLayout::from_key::<u32>(key)),
+                ),
+            ))
+        );
+    }
 }
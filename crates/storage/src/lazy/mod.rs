@@ -18,10 +18,23 @@
 //! These low-level collections are not aware of the elements they manage thus
 //! extra care has to be taken when operating directly on them.
 
+mod arithmetic;
+mod cached_mapping;
+pub mod key_encoder;
 mod mapping;
 
 #[doc(inline)]
-pub use self::mapping::Mapping;
+pub use self::{
+    arithmetic::{
+        ArithmeticError,
+        CheckedArithmetic,
+    },
+    cached_mapping::CachedMapping,
+    mapping::{
+        Mapping,
+        NestedMapping,
+    },
+};
 
 use crate::traits::{
     AutoKey,
@@ -144,6 +157,24 @@ where
     pub fn set(&mut self, value: &V) {
         ink_env::set_contract_storage::<Key, V>(&KeyType::KEY, value);
     }
+
+    /// Reads the `value` from the contract storage.
+    ///
+    /// If no `value` exists, the value returned by `init` is written to the contract
+    /// storage and returned, so `init` is only called on the first access.
+    pub fn get_or_init<F>(&mut self, init: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        match self.get() {
+            Some(value) => value,
+            None => {
+                let value = init();
+                self.set(&value);
+                value
+            }
+        }
+    }
 }
 
 impl<V, KeyType> Lazy<V, KeyType>
@@ -162,6 +193,41 @@ where
     }
 }
 
+impl<V, KeyType> Lazy<V, KeyType>
+where
+    V: Storable + CheckedArithmetic,
+    KeyType: StorageKey,
+{
+    /// Adds `delta` to the stored value and writes the sum back.
+    ///
+    /// A missing value is treated as the default value, i.e. zero.
+    /// Returns the new value.
+    ///
+    /// # Errors
+    ///
+    /// If the sum is out of the range of `V`. The stored value is not changed then.
+    pub fn checked_add(&mut self, delta: V) -> Result<V, ArithmeticError> {
+        let value = arithmetic::checked_add(self.get_or_default(), delta)?;
+        self.set(&value);
+        Ok(value)
+    }
+
+    /// Subtracts `delta` from the stored value and writes the difference back.
+    ///
+    /// A missing value is treated as the default value, i.e. zero.
+    /// Returns the new value.
+    ///
+    /// # Errors
+    ///
+    /// If the difference is out of the range of `V`. The stored value is not changed
+    /// then.
+    pub fn checked_sub(&mut self, delta: V) -> Result<V, ArithmeticError> {
+        let value = arithmetic::checked_sub(self.get_or_default(), delta)?;
+        self.set(&value);
+        Ok(value)
+    }
+}
+
 impl<V, KeyType> Storable for Lazy<V, KeyType>
 where
     KeyType: StorageKey,
@@ -257,6 +323,19 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn get_or_init_persists_initialized_value() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8> = Lazy::new();
+            assert_eq!(storage.get_or_init(|| 7), 7);
+            assert_eq!(storage.get(), Some(7));
+            assert_eq!(storage.get_or_init(|| unreachable!()), 7);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn gets_returns_none_if_no_value_was_set() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -267,4 +346,22 @@ mod tests {
         })
         .unwrap()
     }
+
+    #[test]
+    fn checked_arithmetic_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u128> = Lazy::new();
+            assert_eq!(storage.checked_add(u128::MAX - 1), Ok(u128::MAX - 1));
+            assert_eq!(storage.checked_add(2), Err(ArithmeticError::Overflow));
+            assert_eq!(
+                storage.checked_sub(u128::MAX),
+                Err(ArithmeticError::Underflow)
+            );
+            assert_eq!(storage.checked_sub(u128::MAX - 1), Ok(0));
+            assert_eq!(storage.get(), Some(0));
+
+            Ok(())
+        })
+        .unwrap()
+    }
 }
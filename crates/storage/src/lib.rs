@@ -49,11 +49,21 @@
 
 pub use ink_storage_traits as traits;
 
+pub mod collections;
+#[cfg(feature = "std")]
+pub mod decode;
 #[allow(dead_code)]
 pub(crate) mod lazy;
+pub mod migration;
+pub mod security;
 
 #[doc(inline)]
 pub use self::lazy::{
+    key_encoder,
+    ArithmeticError,
+    CachedMapping,
+    CheckedArithmetic,
     Lazy,
     Mapping,
+    NestedMapping,
 };
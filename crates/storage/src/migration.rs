@@ -0,0 +1,248 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities to migrate the storage of a contract after a code upgrade.
+//!
+//! After [`set_code_hash`](ink_env::set_code_hash) the new code may expect a
+//! different storage layout than the one left behind by the old code. The helpers
+//! of this module read the raw bytes stored under an old key, decode them as the
+//! old type and write the transformed value as the new type.
+//!
+//! Messages flagged with `#[ink(message, migration)]` call [`ensure_first_run`]
+//! before their body, so that they can run only once per code hash.
+
+use ink_env::Environment;
+use ink_prelude::vec::Vec;
+use ink_primitives::{
+    Key,
+    KeyComposer,
+};
+use ink_storage_traits::Storable;
+use scale::{
+    Decode,
+    DecodeAll,
+    Encode,
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage key under which the code hash of the last migration is stored.
+const LAST_MIGRATION_KEY: Key = KeyComposer::from_str("ink::storage::migration");
+
+/// Bytes which are stored as is, without a length prefix.
+struct RawBytes(Vec<u8>);
+
+impl Storable for RawBytes {
+    fn encode<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.0)
+    }
+
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let len = input
+            .remaining_len()?
+            .ok_or("the length of the stored bytes is unknown")?;
+        let mut bytes = ink_prelude::vec![0; len];
+        input.read(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+/// Returns the raw bytes stored under `key`, if any.
+pub fn read_raw<K>(key: &K) -> Option<Vec<u8>>
+where
+    K: Encode,
+{
+    ink_env::get_contract_storage::<K, RawBytes>(key)
+        .unwrap_or_else(|error| panic!("failed to read the raw bytes: {:?}", error))
+        .map(|bytes| bytes.0)
+}
+
+/// Stores `bytes` as is under `key`.
+///
+/// Returns the size in bytes of the pre-existing value at the specified key if any.
+pub fn write_raw<K>(key: &K, bytes: &[u8]) -> Option<u32>
+where
+    K: Encode,
+{
+    ink_env::set_contract_storage::<K, RawBytes>(key, &RawBytes(bytes.to_vec()))
+}
+
+/// Decodes `bytes` as `T`.
+///
+/// # Errors
+///
+/// If `bytes` is not exactly the encoding of a `T`.
+pub fn decode_as<T>(bytes: &[u8]) -> Result<T, Error>
+where
+    T: Decode,
+{
+    T::decode_all(&mut &bytes[..])
+}
+
+/// Migrates the `Old` value stored under `old_key` to a `New` value stored under
+/// `new_key`, transforming it with `f`.
+///
+/// The old key is cleared unless both keys are encoded the same way.
+///
+/// Returns `false` if there is no value stored under `old_key`.
+///
+/// # Errors
+///
+/// If the value stored under `old_key` is not exactly the encoding of an `Old`.
+/// The storage is left untouched in that case.
+pub fn migrate<K1, K2, Old, New, F>(
+    old_key: &K1,
+    new_key: &K2,
+    f: F,
+) -> Result<bool, Error>
+where
+    K1: Encode,
+    K2: Encode,
+    Old: Decode,
+    New: Encode,
+    F: FnOnce(Old) -> New,
+{
+    let bytes = match read_raw(old_key) {
+        Some(bytes) => bytes,
+        None => return Ok(false),
+    };
+    let new = f(decode_as::<Old>(&bytes)?);
+    if old_key.encode() != new_key.encode() {
+        ink_env::clear_contract_storage(old_key);
+    }
+    write_raw(new_key, &new.encode());
+    Ok(true)
+}
+
+/// Ensures that the current code hash has not run a migration yet, and records
+/// that it does now.
+///
+/// # Panics
+///
+/// If a migration already ran for the current code hash.
+pub fn ensure_first_run<E>()
+where
+    E: Environment,
+{
+    let code_hash = ink_env::own_code_hash::<E>()
+        .unwrap_or_else(|error| panic!("failed to query the own code hash: {:?}", error));
+    let last = ink_env::get_contract_storage::<Key, E::Hash>(&LAST_MIGRATION_KEY)
+        .unwrap_or_else(|error| panic!("failed to read the last migration: {:?}", error));
+    if last == Some(code_hash) {
+        panic!("migration already ran for the current code hash")
+    }
+    ink_env::set_contract_storage(&LAST_MIGRATION_KEY, &code_hash);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_env::DefaultEnvironment;
+
+    fn set_own_code_hash(code_hash: [u8; 32]) {
+        let callee = ink_env::test::callee::<DefaultEnvironment>();
+        ink_env::test::set_contract_code_hash::<DefaultEnvironment>(
+            callee,
+            code_hash.into(),
+        );
+    }
+
+    #[test]
+    fn raw_bytes_roundtrip() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            assert_eq!(read_raw(&1u32), None);
+            write_raw(&1u32, &[1, 2, 3]);
+            assert_eq!(read_raw(&1u32), Some(vec![1, 2, 3]));
+            ink_env::set_contract_storage(&2u32, &(7u8, 9u16));
+            assert_eq!(read_raw(&2u32), Some(Encode::encode(&(7u8, 9u16))));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_as_rejects_trailing_bytes() {
+        assert_eq!(decode_as::<u16>(&[1, 0]), Ok(1));
+        assert!(decode_as::<u16>(&[1, 0, 0]).is_err());
+        assert!(decode_as::<u16>(&[1]).is_err());
+    }
+
+    #[test]
+    fn migrate_moves_value() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage(&1u32, &5u8);
+            assert_eq!(migrate(&1u32, &2u32, |old: u8| old as u64 * 2), Ok(true));
+            assert_eq!(read_raw(&1u32), None);
+            assert_eq!(
+                ink_env::get_contract_storage::<u32, u64>(&2u32),
+                Ok(Some(10))
+            );
+            // Nothing left to migrate under the old key.
+            assert_eq!(migrate(&1u32, &2u32, |old: u8| old as u64), Ok(false));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn migrate_in_place() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage(&1u32, &5u8);
+            assert_eq!(migrate(&1u32, &1u32, |old: u8| (old, true)), Ok(true));
+            assert_eq!(
+                ink_env::get_contract_storage::<u32, (u8, bool)>(&1u32),
+                Ok(Some((5, true)))
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn migrate_keeps_storage_on_decode_error() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage(&1u32, &5u32);
+            assert!(migrate(&1u32, &2u32, |old: u8| old).is_err());
+            assert_eq!(read_raw(&1u32), Some(Encode::encode(&5u32)));
+            assert_eq!(read_raw(&2u32), None);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn ensure_first_run_once_per_code_hash() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            set_own_code_hash([1; 32]);
+            ensure_first_run::<DefaultEnvironment>();
+            set_own_code_hash([2; 32]);
+            ensure_first_run::<DefaultEnvironment>();
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "migration already ran for the current code hash")]
+    fn ensure_first_run_twice_fails() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            set_own_code_hash([1; 32]);
+            ensure_first_run::<DefaultEnvironment>();
+            ensure_first_run::<DefaultEnvironment>();
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,747 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Role-based access control for ink! smart contracts.
+//!
+//! An [`AccessControl`] grants roles, identified by 32-byte [`RoleId`]s, to
+//! accounts. Every role has an admin role whose members may grant and revoke it,
+//! which is the [`DEFAULT_ADMIN_ROLE`] unless changed. Messages check the role of
+//! the caller with [`ensure_role!`](crate::ensure_role).
+//!
+//! Changes of the roles emit the standard [`RoleGranted`], [`RoleRevoked`] and
+//! [`RoleAdminChanged`] events, so that clients can track the roles of all
+//! contracts the same way. The events are identified by their signature topic,
+//! e.g. `AccessControl::RoleGranted`, followed by the topics of the role and the
+//! account.
+//!
+//! # Note
+//!
+//! Like the [`Mapping`](crate::Mapping), the access control doesn't actually "own"
+//! any data. The members and the admin of a role are stored under the root key
+//! combined with the role.
+
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::{
+    topics::{
+        state,
+        PrefixedValue,
+        TopicsBuilder,
+        TopicsBuilderBackend,
+    },
+    DefaultEnvironment,
+    Environment,
+    Topics,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The identifier of a role, e.g. the `blake2x256` hash of its name.
+pub type RoleId = [u8; 32];
+
+/// The role which is the admin role of all roles unless changed.
+///
+/// Its members may grant and revoke every role whose admin role was not changed,
+/// including the default admin role itself.
+pub const DEFAULT_ADMIN_ROLE: RoleId = [0; 32];
+
+/// The suffix of the storage keys of the members of a role.
+const MEMBERS: u8 = 0;
+
+/// The suffix of the storage keys of the admin role of a role.
+const ADMINS: u8 = 1;
+
+/// Returned if an account lacks a required role.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[allow(trivial_numeric_casts)]
+pub enum AccessControlError {
+    /// The account is not a member of the role.
+    MissingRole {
+        /// The required role.
+        role: RoleId,
+    },
+}
+
+/// Emitted when `account` was granted `role` by `sender`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleGranted<AccountId> {
+    /// The granted role.
+    pub role: RoleId,
+    /// The account which was granted the role.
+    pub account: AccountId,
+    /// The account which granted the role.
+    pub sender: AccountId,
+}
+
+/// Emitted when `role` was revoked from `account` by `sender`.
+///
+/// `sender` is `account` itself if the role was renounced.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleRevoked<AccountId> {
+    /// The revoked role.
+    pub role: RoleId,
+    /// The account the role was revoked from.
+    pub account: AccountId,
+    /// The account which revoked the role.
+    pub sender: AccountId,
+}
+
+/// Emitted when the admin role of `role` was changed.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoleAdminChanged {
+    /// The role whose admin role was changed.
+    pub role: RoleId,
+    /// The previous admin role.
+    pub previous_admin: RoleId,
+    /// The new admin role.
+    pub new_admin: RoleId,
+}
+
+impl<AccountId> Topics for RoleGranted<AccountId>
+where
+    AccountId: scale::Encode,
+{
+    type RemainingTopics = [state::HasRemainingTopics; 3];
+
+    fn topics<E, B>(
+        &self,
+        builder: TopicsBuilder<state::Uninit, E, B>,
+    ) -> <B as TopicsBuilderBackend<E>>::Output
+    where
+        E: Environment,
+        B: TopicsBuilderBackend<E>,
+    {
+        builder
+            .build::<Self>()
+            .push_topic(&PrefixedValue {
+                prefix: b"",
+                value: b"AccessControl::RoleGranted",
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"AccessControl::RoleGranted::role",
+                value: &self.role,
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"AccessControl::RoleGranted::account",
+                value: &self.account,
+            })
+            .finish()
+    }
+}
+
+impl<AccountId> Topics for RoleRevoked<AccountId>
+where
+    AccountId: scale::Encode,
+{
+    type RemainingTopics = [state::HasRemainingTopics; 3];
+
+    fn topics<E, B>(
+        &self,
+        builder: TopicsBuilder<state::Uninit, E, B>,
+    ) -> <B as TopicsBuilderBackend<E>>::Output
+    where
+        E: Environment,
+        B: TopicsBuilderBackend<E>,
+    {
+        builder
+            .build::<Self>()
+            .push_topic(&PrefixedValue {
+                prefix: b"",
+                value: b"AccessControl::RoleRevoked",
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"AccessControl::RoleRevoked::role",
+                value: &self.role,
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"AccessControl::RoleRevoked::account",
+                value: &self.account,
+            })
+            .finish()
+    }
+}
+
+impl Topics for RoleAdminChanged {
+    type RemainingTopics = [state::HasRemainingTopics; 2];
+
+    fn topics<E, B>(
+        &self,
+        builder: TopicsBuilder<state::Uninit, E, B>,
+    ) -> <B as TopicsBuilderBackend<E>>::Output
+    where
+        E: Environment,
+        B: TopicsBuilderBackend<E>,
+    {
+        builder
+            .build::<Self>()
+            .push_topic(&PrefixedValue {
+                prefix: b"",
+                value: b"AccessControl::RoleAdminChanged",
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"AccessControl::RoleAdminChanged::role",
+                value: &self.role,
+            })
+            .finish()
+    }
+}
+
+/// The roles of the accounts of the environment `E`.
+///
+/// # Important
+///
+/// The access control requires its own pre-defined storage key where to store the
+/// roles. By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the access control:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::{
+///     security::access_control::{
+///         AccessControl,
+///         AccessControlError,
+///         RoleId,
+///         DEFAULT_ADMIN_ROLE,
+///     },
+///     ensure_role,
+///     Mapping,
+/// };
+///
+/// /// The role of the accounts which may mint tokens.
+/// const MINTER: RoleId = ink::blake2x256!("MINTER");
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Token {
+///     roles: AccessControl<Environment>,
+///     balances: Mapping<AccountId, Balance>,
+/// }
+///
+/// impl Token {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         let mut instance = Self::default();
+///         let caller = instance.env().caller();
+///         instance.roles.grant_unchecked(DEFAULT_ADMIN_ROLE, caller);
+///         instance
+///     }
+///
+///     #[ink(message)]
+///     pub fn add_minter(&mut self, account: AccountId) -> Result<(), AccessControlError> {
+///         self.roles.grant(MINTER, account)
+///     }
+///
+///     #[ink(message)]
+///     pub fn mint(&mut self, to: AccountId, amount: Balance) -> Result<(), AccessControlError> {
+///         ensure_role!(self.roles, MINTER);
+///         let balance = self.balances.get(to).unwrap_or_default();
+///         self.balances.insert(to, &(balance + amount));
+///         Ok(())
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AccessControl<
+    E: Environment = DefaultEnvironment,
+    KeyType: StorageKey = AutoKey,
+> {
+    _marker: PhantomData<fn() -> (E, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<E, KeyType> Default for AccessControl<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, KeyType> AccessControl<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Creates a new `AccessControl` without any granted roles.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<E, KeyType> ::core::fmt::Debug for AccessControl<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("AccessControl")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<E, KeyType> AccessControl<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Returns `true` if `account` is a member of `role`.
+    pub fn has_role(&self, role: RoleId, account: &E::AccountId) -> bool {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, MEMBERS, &role, account))
+            .is_some()
+    }
+
+    /// Returns the admin role of `role`, whose members may grant and revoke it.
+    pub fn role_admin(&self, role: RoleId) -> RoleId {
+        ink_env::get_contract_storage(&(&KeyType::KEY, ADMINS, &role))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get admin role of AccessControl: {:?}", error)
+            })
+            .unwrap_or(DEFAULT_ADMIN_ROLE)
+    }
+
+    /// Ensures that `account` is a member of `role`.
+    ///
+    /// # Errors
+    ///
+    /// If `account` is not a member of `role`.
+    pub fn ensure_role(
+        &self,
+        role: RoleId,
+        account: &E::AccountId,
+    ) -> Result<(), AccessControlError> {
+        if !self.has_role(role, account) {
+            return Err(AccessControlError::MissingRole { role })
+        }
+        Ok(())
+    }
+
+    /// Ensures that the caller is a member of `role`.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not a member of `role`.
+    pub fn ensure_caller_role(&self, role: RoleId) -> Result<(), AccessControlError> {
+        self.ensure_role(role, &ink_env::caller::<E>())
+    }
+
+    /// Grants `role` to `account` on behalf of the caller.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not a member of the admin role of `role`.
+    pub fn grant(
+        &mut self,
+        role: RoleId,
+        account: E::AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_caller_role(self.role_admin(role))?;
+        self.grant_unchecked(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from `account` on behalf of the caller.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not a member of the admin role of `role`.
+    pub fn revoke(
+        &mut self,
+        role: RoleId,
+        account: E::AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_caller_role(self.role_admin(role))?;
+        self.revoke_unchecked(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from the caller, e.g. if the caller's account is compromised.
+    ///
+    /// Returns `false` if the caller was not a member of `role`.
+    pub fn renounce(&mut self, role: RoleId) -> bool {
+        self.revoke_unchecked(role, ink_env::caller::<E>())
+    }
+
+    /// Changes the admin role of `role` to `admin` on behalf of the caller.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not a member of the current admin role of `role`.
+    pub fn set_role_admin(
+        &mut self,
+        role: RoleId,
+        admin: RoleId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_caller_role(self.role_admin(role))?;
+        self.set_role_admin_unchecked(role, admin);
+        Ok(())
+    }
+
+    /// Grants `role` to `account` without checking the caller, e.g. to set up the
+    /// initial admin in the constructor.
+    ///
+    /// Returns `false` if `account` was already a member of `role`.
+    pub fn grant_unchecked(&mut self, role: RoleId, account: E::AccountId) -> bool {
+        let granted = ink_env::set_contract_storage(
+            &(&KeyType::KEY, MEMBERS, &role, &account),
+            &(),
+        )
+        .is_none();
+        if granted {
+            ink_env::emit_event::<E, _>(RoleGranted {
+                role,
+                account,
+                sender: ink_env::caller::<E>(),
+            });
+        }
+        granted
+    }
+
+    /// Revokes `role` from `account` without checking the caller.
+    ///
+    /// Returns `false` if `account` was not a member of `role`.
+    pub fn revoke_unchecked(&mut self, role: RoleId, account: E::AccountId) -> bool {
+        let revoked = ink_env::take_contract_storage::<_, ()>(&(
+            &KeyType::KEY,
+            MEMBERS,
+            &role,
+            &account,
+        ))
+        .unwrap_or_else(|error| {
+            panic!("Failed to revoke role of AccessControl: {:?}", error)
+        })
+        .is_some();
+        if revoked {
+            ink_env::emit_event::<E, _>(RoleRevoked {
+                role,
+                account,
+                sender: ink_env::caller::<E>(),
+            });
+        }
+        revoked
+    }
+
+    /// Changes the admin role of `role` to `admin` without checking the caller.
+    pub fn set_role_admin_unchecked(&mut self, role: RoleId, admin: RoleId) {
+        let previous_admin = self.role_admin(role);
+        if admin == DEFAULT_ADMIN_ROLE {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, ADMINS, &role));
+        } else {
+            ink_env::set_contract_storage(&(&KeyType::KEY, ADMINS, &role), &admin);
+        }
+        ink_env::emit_event::<E, _>(RoleAdminChanged {
+            role,
+            previous_admin,
+            new_admin: admin,
+        });
+    }
+}
+
+/// Returns early from the enclosing function with an
+/// [`AccessControlError`](crate::security::access_control::AccessControlError), converted
+/// with `From`, if an account lacks a role.
+///
+/// `ensure_role!(access_control, role)` checks the caller while
+/// `ensure_role!(access_control, role, account)` checks `account`.
+///
+/// # Example
+///
+/// ```
+/// use ink::storage::{
+///     security::access_control::{
+///         AccessControl,
+///         AccessControlError,
+///         RoleId,
+///     },
+///     ensure_role,
+/// };
+///
+/// const PAUSER: RoleId = ink::blake2x256!("PAUSER");
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// enum Error {
+///     Unauthorized,
+/// }
+///
+/// impl From<AccessControlError> for Error {
+///     fn from(_: AccessControlError) -> Self {
+///         Error::Unauthorized
+///     }
+/// }
+///
+/// fn pause(roles: &AccessControl) -> Result<(), Error> {
+///     ensure_role!(roles, PAUSER);
+///     // Pause the contract.
+///     Ok(())
+/// }
+/// # ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+/// #     assert_eq!(pause(&AccessControl::new()), Err(Error::Unauthorized));
+/// #     Ok(())
+/// # }).unwrap();
+/// ```
+#[macro_export]
+macro_rules! ensure_role {
+    ($access_control:expr, $role:expr $(,)?) => {
+        if let ::core::result::Result::Err(error) =
+            $access_control.ensure_caller_role($role)
+        {
+            return ::core::result::Result::Err(::core::convert::From::from(error))
+        }
+    };
+    ($access_control:expr, $role:expr, $account:expr $(,)?) => {
+        if let ::core::result::Result::Err(error) =
+            $access_control.ensure_role($role, &$account)
+        {
+            return ::core::result::Result::Err(::core::convert::From::from(error))
+        }
+    };
+}
+
+impl<E, KeyType> Storable for AccessControl<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<E, Key, InnerKey> StorableHint<Key> for AccessControl<E, InnerKey>
+where
+    E: Environment,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = AccessControl<E, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<E, KeyType> StorageKey for AccessControl<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<E, KeyType> StorageLayout for AccessControl<E, KeyType>
+    where
+        E: Environment + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <RoleId as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+    use ink_env::test::DefaultAccounts;
+    use ink_prelude::vec::Vec;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+
+    type Roles = AccessControl<DefaultEnvironment, ManualKey<123>>;
+
+    const MINTER: RoleId = [1; 32];
+    const MINTER_ADMIN: RoleId = [2; 32];
+
+    fn set_caller(caller: AccountId) {
+        ink_env::test::set_caller::<DefaultEnvironment>(caller);
+    }
+
+    /// Returns roles with Alice as the default admin, which is the caller.
+    fn setup(accounts: &DefaultAccounts<DefaultEnvironment>) -> Roles {
+        set_caller(accounts.alice);
+        let mut roles = Roles::new();
+        roles.grant_unchecked(DEFAULT_ADMIN_ROLE, accounts.alice);
+        roles
+    }
+
+    #[test]
+    fn grant_and_revoke_work() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut roles = setup(&accounts);
+            assert!(!roles.has_role(MINTER, &accounts.bob));
+
+            assert_eq!(roles.grant(MINTER, accounts.bob), Ok(()));
+            assert!(roles.has_role(MINTER, &accounts.bob));
+            assert_eq!(roles.ensure_role(MINTER, &accounts.bob), Ok(()));
+
+            assert_eq!(roles.revoke(MINTER, accounts.bob), Ok(()));
+            assert!(!roles.has_role(MINTER, &accounts.bob));
+            assert_eq!(
+                roles.ensure_role(MINTER, &accounts.bob),
+                Err(AccessControlError::MissingRole { role: MINTER })
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn only_admins_grant_and_revoke() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut roles = setup(&accounts);
+            roles.grant(MINTER, accounts.bob).unwrap();
+
+            set_caller(accounts.bob);
+            let missing = Err(AccessControlError::MissingRole {
+                role: DEFAULT_ADMIN_ROLE,
+            });
+            assert_eq!(roles.grant(MINTER, accounts.charlie), missing);
+            assert_eq!(roles.revoke(MINTER, accounts.bob), missing);
+            assert_eq!(roles.set_role_admin(MINTER, MINTER), missing);
+            assert!(!roles.has_role(MINTER, &accounts.charlie));
+
+            // Members can always renounce their own roles.
+            assert!(roles.renounce(MINTER));
+            assert!(!roles.renounce(MINTER));
+            assert!(!roles.has_role(MINTER, &accounts.bob));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn role_admin_hierarchy_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut roles = setup(&accounts);
+            assert_eq!(roles.role_admin(MINTER), DEFAULT_ADMIN_ROLE);
+            assert_eq!(roles.set_role_admin(MINTER, MINTER_ADMIN), Ok(()));
+            assert_eq!(roles.role_admin(MINTER), MINTER_ADMIN);
+
+            // The default admin no longer administers the minters.
+            assert_eq!(
+                roles.grant(MINTER, accounts.charlie),
+                Err(AccessControlError::MissingRole { role: MINTER_ADMIN })
+            );
+            roles.grant(MINTER_ADMIN, accounts.bob).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(roles.grant(MINTER, accounts.charlie), Ok(()));
+            assert!(roles.has_role(MINTER, &accounts.charlie));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn events_are_emitted_on_changes() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut roles = setup(&accounts);
+            assert!(!roles.grant_unchecked(DEFAULT_ADMIN_ROLE, accounts.alice));
+            roles.grant(MINTER, accounts.bob).unwrap();
+            roles.revoke(MINTER, accounts.bob).unwrap();
+            roles.revoke(MINTER, accounts.bob).unwrap();
+            roles.set_role_admin(MINTER, MINTER_ADMIN).unwrap();
+
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 4);
+            assert_eq!(
+                scale::Decode::decode(&mut &events[1].data[..]),
+                Ok(RoleGranted {
+                    role: MINTER,
+                    account: accounts.bob,
+                    sender: accounts.alice,
+                })
+            );
+            assert_eq!(
+                scale::Decode::decode(&mut &events[2].data[..]),
+                Ok(RoleRevoked {
+                    role: MINTER,
+                    account: accounts.bob,
+                    sender: accounts.alice,
+                })
+            );
+            assert_eq!(
+                scale::Decode::decode(&mut &events[3].data[..]),
+                Ok(RoleAdminChanged {
+                    role: MINTER,
+                    previous_admin: DEFAULT_ADMIN_ROLE,
+                    new_admin: MINTER_ADMIN,
+                })
+            );
+            assert_eq!(events[1].topics.len(), 3);
+            assert_eq!(events[3].topics.len(), 2);
+            // Events of the same kind share their signature topic.
+            assert_eq!(events[0].topics[0], events[1].topics[0]);
+            assert_ne!(events[1].topics[0], events[2].topics[0]);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn ensure_role_macro_works() {
+        fn check(roles: &Roles, account: AccountId) -> Result<(), AccessControlError> {
+            ensure_role!(roles, MINTER);
+            ensure_role!(roles, MINTER, account);
+            Ok(())
+        }
+
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut roles = setup(&accounts);
+            let missing = Err(AccessControlError::MissingRole { role: MINTER });
+            assert_eq!(check(&roles, accounts.bob), missing);
+            roles.grant(MINTER, accounts.alice).unwrap();
+            assert_eq!(check(&roles, accounts.bob), missing);
+            roles.grant(MINTER, accounts.bob).unwrap();
+            assert_eq!(check(&roles, accounts.bob), Ok(()));
+            Ok(())
+        })
+        .unwrap()
+    }
+}
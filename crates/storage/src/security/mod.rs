@@ -0,0 +1,23 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Building blocks guarding the messages of ink! smart contracts.
+//!
+//! They restrict who may call a message, e.g. [`ownable`] and [`access_control`],
+//! and when, e.g. [`pausable`] and [`reentrancy`].
+
+pub mod access_control;
+pub mod ownable;
+pub mod pausable;
+pub mod reentrancy;
@@ -0,0 +1,574 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Single owner access control for ink! smart contracts.
+//!
+//! An [`Ownable`] stores the account owning a contract. Messages restricted to the
+//! owner check the caller with [`Ownable::only_owner`].
+//!
+//! Ownership is transferred in two steps: the owner proposes a new owner with
+//! [`Ownable::transfer_ownership`] which only becomes the owner once it calls
+//! [`Ownable::accept_ownership`]. This prevents transferring the ownership to an
+//! account nobody controls, e.g. due to a typo.
+//!
+//! Changes of the ownership emit the standard [`OwnershipTransferStarted`] and
+//! [`OwnershipTransferred`] events, identified by their signature topic, e.g.
+//! `Ownable::OwnershipTransferred`, followed by the topics of the previous and the
+//! new owner.
+
+use crate::traits::{
+    AutoKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::{
+    topics::{
+        state,
+        PrefixedValue,
+        TopicsBuilder,
+        TopicsBuilderBackend,
+    },
+    DefaultEnvironment,
+    Environment,
+    Topics,
+};
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The suffix of the storage key of the pending owner.
+const PENDING_OWNER: u8 = 0;
+
+/// Returned if the caller is not allowed to change the ownership.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum OwnableError {
+    /// The caller is not the owner.
+    NotOwner,
+    /// The caller is not the pending owner.
+    NotPendingOwner,
+}
+
+/// Emitted when the owner proposed `new_owner` as the next owner.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnershipTransferStarted<AccountId> {
+    /// The current owner.
+    pub previous_owner: AccountId,
+    /// The pending owner which has to accept the ownership.
+    pub new_owner: AccountId,
+}
+
+/// Emitted when the owner changed.
+///
+/// `previous_owner` is `None` when the initial owner was set and `new_owner` is
+/// `None` when the ownership was renounced.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnershipTransferred<AccountId> {
+    /// The previous owner if any.
+    pub previous_owner: Option<AccountId>,
+    /// The new owner if any.
+    pub new_owner: Option<AccountId>,
+}
+
+impl<AccountId> Topics for OwnershipTransferStarted<AccountId>
+where
+    AccountId: scale::Encode,
+{
+    type RemainingTopics = [state::HasRemainingTopics; 3];
+
+    fn topics<E, B>(
+        &self,
+        builder: TopicsBuilder<state::Uninit, E, B>,
+    ) -> <B as TopicsBuilderBackend<E>>::Output
+    where
+        E: Environment,
+        B: TopicsBuilderBackend<E>,
+    {
+        builder
+            .build::<Self>()
+            .push_topic(&PrefixedValue {
+                prefix: b"",
+                value: b"Ownable::OwnershipTransferStarted",
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"Ownable::OwnershipTransferStarted::previous_owner",
+                value: &self.previous_owner,
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"Ownable::OwnershipTransferStarted::new_owner",
+                value: &self.new_owner,
+            })
+            .finish()
+    }
+}
+
+impl<AccountId> Topics for OwnershipTransferred<AccountId>
+where
+    AccountId: scale::Encode,
+{
+    type RemainingTopics = [state::HasRemainingTopics; 3];
+
+    fn topics<E, B>(
+        &self,
+        builder: TopicsBuilder<state::Uninit, E, B>,
+    ) -> <B as TopicsBuilderBackend<E>>::Output
+    where
+        E: Environment,
+        B: TopicsBuilderBackend<E>,
+    {
+        builder
+            .build::<Self>()
+            .push_topic(&PrefixedValue {
+                prefix: b"",
+                value: b"Ownable::OwnershipTransferred",
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"Ownable::OwnershipTransferred::previous_owner",
+                value: &self.previous_owner,
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"Ownable::OwnershipTransferred::new_owner",
+                value: &self.new_owner,
+            })
+            .finish()
+    }
+}
+
+/// The owner of a contract on the environment `E`.
+///
+/// # Important
+///
+/// The ownable requires its own pre-defined storage key where to store the owner.
+/// By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can use the ownable:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::security::ownable::{
+///     Ownable,
+///     OwnableError,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Vault {
+///     ownable: Ownable<Environment>,
+///     fee: Balance,
+/// }
+///
+/// impl Vault {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         let mut instance = Self::default();
+///         let caller = instance.env().caller();
+///         instance.ownable.set_owner_unchecked(caller);
+///         instance
+///     }
+///
+///     #[ink(message)]
+///     pub fn set_fee(&mut self, fee: Balance) -> Result<(), OwnableError> {
+///         self.ownable.only_owner()?;
+///         self.fee = fee;
+///         Ok(())
+///     }
+///
+///     #[ink(message)]
+///     pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+///         self.ownable.transfer_ownership(new_owner)
+///     }
+///
+///     #[ink(message)]
+///     pub fn accept_ownership(&mut self) -> Result<(), OwnableError> {
+///         self.ownable.accept_ownership()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Ownable<E: Environment = DefaultEnvironment, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (E, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<E, KeyType> Default for Ownable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, KeyType> Ownable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Creates a new `Ownable` without an owner.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<E, KeyType> ::core::fmt::Debug for Ownable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Ownable")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<E, KeyType> Ownable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Returns the owner, `None` if the ownership was renounced or never set.
+    pub fn owner(&self) -> Option<E::AccountId> {
+        ink_env::get_contract_storage(&KeyType::KEY)
+            .unwrap_or_else(|error| panic!("Failed to get owner of Ownable: {:?}", error))
+    }
+
+    /// Returns the account proposed as the next owner if any.
+    pub fn pending_owner(&self) -> Option<E::AccountId> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, PENDING_OWNER)).unwrap_or_else(
+            |error| panic!("Failed to get pending owner of Ownable: {:?}", error),
+        )
+    }
+
+    /// Returns `true` if `account` is the owner.
+    pub fn is_owner(&self, account: &E::AccountId) -> bool {
+        self.owner().as_ref() == Some(account)
+    }
+
+    /// Ensures that the caller is the owner.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the owner.
+    pub fn only_owner(&self) -> Result<(), OwnableError> {
+        if !self.is_owner(&ink_env::caller::<E>()) {
+            return Err(OwnableError::NotOwner)
+        }
+        Ok(())
+    }
+
+    /// Proposes `new_owner` as the next owner on behalf of the caller.
+    ///
+    /// The ownership is only transferred once `new_owner` calls
+    /// [`accept_ownership`](Self::accept_ownership). Replaces a previously
+    /// proposed owner.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the owner.
+    pub fn transfer_ownership(
+        &mut self,
+        new_owner: E::AccountId,
+    ) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        ink_env::set_contract_storage(&(&KeyType::KEY, PENDING_OWNER), &new_owner);
+        ink_env::emit_event::<E, _>(OwnershipTransferStarted {
+            previous_owner: ink_env::caller::<E>(),
+            new_owner,
+        });
+        Ok(())
+    }
+
+    /// Makes the caller the owner if it was proposed as the next owner.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the pending owner.
+    pub fn accept_ownership(&mut self) -> Result<(), OwnableError> {
+        let caller = ink_env::caller::<E>();
+        if self.pending_owner().as_ref() != Some(&caller) {
+            return Err(OwnableError::NotPendingOwner)
+        }
+        self.set_owner_unchecked(caller);
+        Ok(())
+    }
+
+    /// Leaves the contract without an owner on behalf of the caller, which disables
+    /// all functionality restricted to the owner for good.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the owner.
+    pub fn renounce_ownership(&mut self) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        let previous_owner = self.owner();
+        ink_env::clear_contract_storage(&KeyType::KEY);
+        ink_env::clear_contract_storage(&(&KeyType::KEY, PENDING_OWNER));
+        ink_env::emit_event::<E, _>(OwnershipTransferred {
+            previous_owner,
+            new_owner: None,
+        });
+        Ok(())
+    }
+
+    /// Makes `owner` the owner without checking the caller, e.g. to set up the
+    /// initial owner in the constructor.
+    ///
+    /// Discards a pending transfer of the ownership.
+    pub fn set_owner_unchecked(&mut self, owner: E::AccountId) {
+        let previous_owner = self.owner();
+        ink_env::set_contract_storage(&KeyType::KEY, &owner);
+        ink_env::clear_contract_storage(&(&KeyType::KEY, PENDING_OWNER));
+        ink_env::emit_event::<E, _>(OwnershipTransferred {
+            previous_owner,
+            new_owner: Some(owner),
+        });
+    }
+}
+
+impl<E, KeyType> Storable for Ownable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<E, Key, InnerKey> StorableHint<Key> for Ownable<E, InnerKey>
+where
+    E: Environment,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Ownable<E, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<E, KeyType> StorageKey for Ownable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        RootLayout,
+        StructLayout,
+    };
+
+    impl<E, KeyType> StorageLayout for Ownable<E, KeyType>
+    where
+        E: Environment + scale_info::TypeInfo + 'static,
+        E::AccountId: StorageLayout,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                StructLayout::new(
+                    "Ownable",
+                    [
+                        FieldLayout::new(
+                            "owner",
+                            <Option<E::AccountId> as StorageLayout>::layout(
+                                &KeyType::KEY,
+                            ),
+                        ),
+                        FieldLayout::new(
+                            "pending_owner",
+                            <Option<E::AccountId> as StorageLayout>::layout(
+                                &KeyType::KEY,
+                            ),
+                        ),
+                    ],
+                ),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+    use ink_env::test::DefaultAccounts;
+    use ink_prelude::vec::Vec;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Owner = Ownable<DefaultEnvironment, ManualKey<123>>;
+
+    fn set_caller(caller: AccountId) {
+        ink_env::test::set_caller::<DefaultEnvironment>(caller);
+    }
+
+    /// Returns an ownable owned by Alice, which is the caller.
+    fn setup(accounts: &DefaultAccounts<DefaultEnvironment>) -> Owner {
+        set_caller(accounts.alice);
+        let mut ownable = Owner::new();
+        ownable.set_owner_unchecked(accounts.alice);
+        ownable
+    }
+
+    #[test]
+    fn only_owner_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            assert_eq!(Owner::new().owner(), None);
+            assert_eq!(Owner::new().only_owner(), Err(OwnableError::NotOwner));
+
+            let ownable = setup(&accounts);
+            assert_eq!(ownable.owner(), Some(accounts.alice));
+            assert!(ownable.is_owner(&accounts.alice));
+            assert_eq!(ownable.only_owner(), Ok(()));
+
+            set_caller(accounts.bob);
+            assert!(!ownable.is_owner(&accounts.bob));
+            assert_eq!(ownable.only_owner(), Err(OwnableError::NotOwner));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn two_step_transfer_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut ownable = setup(&accounts);
+            assert_eq!(ownable.transfer_ownership(accounts.bob), Ok(()));
+            assert_eq!(ownable.pending_owner(), Some(accounts.bob));
+            // The ownership is not transferred until it is accepted.
+            assert_eq!(ownable.owner(), Some(accounts.alice));
+            assert_eq!(
+                ownable.accept_ownership(),
+                Err(OwnableError::NotPendingOwner)
+            );
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                ownable.accept_ownership(),
+                Err(OwnableError::NotPendingOwner)
+            );
+            assert_eq!(
+                ownable.transfer_ownership(accounts.charlie),
+                Err(OwnableError::NotOwner)
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(ownable.accept_ownership(), Ok(()));
+            assert_eq!(ownable.owner(), Some(accounts.bob));
+            assert_eq!(ownable.pending_owner(), None);
+            assert_eq!(ownable.only_owner(), Ok(()));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn renounce_ownership_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut ownable = setup(&accounts);
+            ownable.transfer_ownership(accounts.bob).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(ownable.renounce_ownership(), Err(OwnableError::NotOwner));
+
+            set_caller(accounts.alice);
+            assert_eq!(ownable.renounce_ownership(), Ok(()));
+            assert_eq!(ownable.owner(), None);
+            assert_eq!(ownable.only_owner(), Err(OwnableError::NotOwner));
+
+            // The pending transfer is discarded as well.
+            set_caller(accounts.bob);
+            assert_eq!(
+                ownable.accept_ownership(),
+                Err(OwnableError::NotPendingOwner)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn events_are_emitted_on_changes() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            let mut ownable = setup(&accounts);
+            ownable.transfer_ownership(accounts.bob).unwrap();
+            set_caller(accounts.bob);
+            ownable.accept_ownership().unwrap();
+            ownable.renounce_ownership().unwrap();
+
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 4);
+            assert_eq!(
+                scale::Decode::decode(&mut &events[0].data[..]),
+                Ok(OwnershipTransferred {
+                    previous_owner: None,
+                    new_owner: Some(accounts.alice),
+                })
+            );
+            assert_eq!(
+                scale::Decode::decode(&mut &events[1].data[..]),
+                Ok(OwnershipTransferStarted {
+                    previous_owner: accounts.alice,
+                    new_owner: accounts.bob,
+                })
+            );
+            assert_eq!(
+                scale::Decode::decode(&mut &events[2].data[..]),
+                Ok(OwnershipTransferred {
+                    previous_owner: Some(accounts.alice),
+                    new_owner: Some(accounts.bob),
+                })
+            );
+            assert_eq!(
+                scale::Decode::decode(&mut &events[3].data[..]),
+                Ok(OwnershipTransferred {
+                    previous_owner: Some(accounts.bob),
+                    new_owner: None,
+                })
+            );
+            assert!(events.iter().all(|event| event.topics.len() == 3));
+            // Events of the same kind share their signature topic.
+            assert_eq!(events[0].topics[0], events[2].topics[0]);
+            assert_ne!(events[0].topics[0], events[1].topics[0]);
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,448 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Emergency stop for ink! smart contracts.
+//!
+//! A [`Pausable`] stores whether a contract is paused. Messages that must not run
+//! while the contract is paused check it with [`Pausable::when_not_paused`], or are
+//! flagged with `#[ink(message, when_not_paused)]` which reverts them while the
+//! [`Pausable`] field of the contract is paused.
+//!
+//! Who may pause and unpause the contract is up to the contract, e.g. by combining
+//! the [`Pausable`] with an [`Ownable`](crate::security::ownable::Ownable).
+//!
+//! Pausing and unpausing emit the standard [`Paused`] and [`Unpaused`] events,
+//! identified by their signature topic, e.g. `Pausable::Paused`, followed by the
+//! topic of the account that changed the state.
+
+use crate::traits::{
+    ManualKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_env::{
+    topics::{
+        state,
+        PrefixedValue,
+        TopicsBuilder,
+        TopicsBuilderBackend,
+    },
+    DefaultEnvironment,
+    Environment,
+    Topics,
+};
+use ink_primitives::{
+    Key,
+    KeyComposer,
+};
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The default storage key of a [`Pausable`].
+pub type PausedKey = ManualKey<{ KeyComposer::from_str("ink::storage::pausable") }>;
+
+/// Returned if the contract is not in the state required by the call.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PausableError {
+    /// The contract is paused.
+    Paused,
+    /// The contract is not paused.
+    NotPaused,
+}
+
+/// Emitted when `account` paused the contract.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Paused<AccountId> {
+    /// The account that paused the contract.
+    pub account: AccountId,
+}
+
+/// Emitted when `account` unpaused the contract.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Unpaused<AccountId> {
+    /// The account that unpaused the contract.
+    pub account: AccountId,
+}
+
+impl<AccountId> Topics for Paused<AccountId>
+where
+    AccountId: scale::Encode,
+{
+    type RemainingTopics = [state::HasRemainingTopics; 2];
+
+    fn topics<E, B>(
+        &self,
+        builder: TopicsBuilder<state::Uninit, E, B>,
+    ) -> <B as TopicsBuilderBackend<E>>::Output
+    where
+        E: Environment,
+        B: TopicsBuilderBackend<E>,
+    {
+        builder
+            .build::<Self>()
+            .push_topic(&PrefixedValue {
+                prefix: b"",
+                value: b"Pausable::Paused",
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"Pausable::Paused::account",
+                value: &self.account,
+            })
+            .finish()
+    }
+}
+
+impl<AccountId> Topics for Unpaused<AccountId>
+where
+    AccountId: scale::Encode,
+{
+    type RemainingTopics = [state::HasRemainingTopics; 2];
+
+    fn topics<E, B>(
+        &self,
+        builder: TopicsBuilder<state::Uninit, E, B>,
+    ) -> <B as TopicsBuilderBackend<E>>::Output
+    where
+        E: Environment,
+        B: TopicsBuilderBackend<E>,
+    {
+        builder
+            .build::<Self>()
+            .push_topic(&PrefixedValue {
+                prefix: b"",
+                value: b"Pausable::Unpaused",
+            })
+            .push_topic(&PrefixedValue {
+                prefix: b"Pausable::Unpaused::account",
+                value: &self.account,
+            })
+            .finish()
+    }
+}
+
+/// The paused state of a contract on the environment `E`.
+///
+/// # Important
+///
+/// The pausable requires its own pre-defined storage key where to store the paused
+/// state. Unlike other storage types it uses the [`PausedKey`] by default. However,
+/// anyone can specify another storage key using
+/// [`ManualKey`](crate::traits::ManualKey).
+///
+/// The messages flagged with `#[ink(when_not_paused)]` check the `Pausable` field
+/// of the contract, whatever its key. A contract using them must therefore have
+/// exactly one `Pausable` field. Parts of a contract paused independently by further
+/// `Pausable`s check them with [`Pausable::when_not_paused`] instead.
+///
+/// The paused state is only stored while the contract is paused, so that an
+/// unpaused contract does not occupy any storage.
+///
+/// This is an example of how you can use the pausable:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::security::pausable::{
+///     Pausable,
+///     PausableError,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Vault {
+///     pausable: Pausable<Environment>,
+///     withdrawn: Balance,
+/// }
+///
+/// impl Vault {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message, when_not_paused)]
+///     pub fn withdraw(&mut self, value: Balance) {
+///         self.withdrawn += value;
+///     }
+///
+///     #[ink(message)]
+///     pub fn pause(&mut self) -> Result<(), PausableError> {
+///         // Restrict the caller here, e.g. to the owner of the contract.
+///         self.pausable.pause()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Pausable<E: Environment = DefaultEnvironment, KeyType: StorageKey = PausedKey>
+{
+    _marker: PhantomData<fn() -> (E, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<E, KeyType> Default for Pausable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, KeyType> Pausable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Creates a new `Pausable`, which is not paused unless it was paused before.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<E, KeyType> ::core::fmt::Debug for Pausable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Pausable")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<E, KeyType> Pausable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    /// Returns `true` if the contract is paused.
+    pub fn is_paused(&self) -> bool {
+        ink_env::contains_contract_storage(&KeyType::KEY).is_some()
+    }
+
+    /// Ensures that the contract is not paused.
+    ///
+    /// # Errors
+    ///
+    /// If the contract is paused.
+    pub fn when_not_paused(&self) -> Result<(), PausableError> {
+        if self.is_paused() {
+            return Err(PausableError::Paused)
+        }
+        Ok(())
+    }
+
+    /// Ensures that the contract is paused.
+    ///
+    /// # Errors
+    ///
+    /// If the contract is not paused.
+    pub fn when_paused(&self) -> Result<(), PausableError> {
+        if !self.is_paused() {
+            return Err(PausableError::NotPaused)
+        }
+        Ok(())
+    }
+
+    /// Pauses the contract on behalf of the caller.
+    ///
+    /// # Errors
+    ///
+    /// If the contract is already paused.
+    pub fn pause(&mut self) -> Result<(), PausableError> {
+        self.when_not_paused()?;
+        ink_env::set_contract_storage(&KeyType::KEY, &true);
+        ink_env::emit_event::<E, _>(Paused {
+            account: ink_env::caller::<E>(),
+        });
+        Ok(())
+    }
+
+    /// Unpauses the contract on behalf of the caller.
+    ///
+    /// # Errors
+    ///
+    /// If the contract is not paused.
+    pub fn unpause(&mut self) -> Result<(), PausableError> {
+        self.when_paused()?;
+        ink_env::clear_contract_storage(&KeyType::KEY);
+        ink_env::emit_event::<E, _>(Unpaused {
+            account: ink_env::caller::<E>(),
+        });
+        Ok(())
+    }
+}
+
+impl<E, KeyType> Storable for Pausable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<E, Key, InnerKey> StorableHint<Key> for Pausable<E, InnerKey>
+where
+    E: Environment,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = Pausable<E, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<E, KeyType> StorageKey for Pausable<E, KeyType>
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<E, KeyType> StorageLayout for Pausable<E, KeyType>
+    where
+        E: Environment + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <Option<bool> as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+/// Ensures that the `pausable` is not paused.
+///
+/// Called with the [`Pausable`] field of the contract before the body of the messages
+/// flagged with `#[ink(when_not_paused)]`.
+///
+/// # Panics
+///
+/// If the contract is paused, which reverts the call.
+pub fn ensure_not_paused<E, KeyType>(pausable: &Pausable<E, KeyType>)
+where
+    E: Environment,
+    KeyType: StorageKey,
+{
+    if pausable.is_paused() {
+        panic!(
+            "encountered call of a `when_not_paused` ink! message \
+            while the contract is paused"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_prelude::vec::Vec;
+
+    type Pause = Pausable<DefaultEnvironment, ManualKey<123>>;
+
+    #[test]
+    fn pause_and_unpause_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut pausable = Pause::new();
+            assert!(!pausable.is_paused());
+            assert_eq!(pausable.when_not_paused(), Ok(()));
+            assert_eq!(pausable.when_paused(), Err(PausableError::NotPaused));
+            assert_eq!(pausable.unpause(), Err(PausableError::NotPaused));
+
+            assert_eq!(pausable.pause(), Ok(()));
+            assert!(pausable.is_paused());
+            assert_eq!(pausable.when_not_paused(), Err(PausableError::Paused));
+            assert_eq!(pausable.when_paused(), Ok(()));
+            assert_eq!(pausable.pause(), Err(PausableError::Paused));
+            // Pausables with other keys are independent.
+            assert!(!Pausable::<DefaultEnvironment>::new().is_paused());
+
+            assert_eq!(pausable.unpause(), Ok(()));
+            assert!(!pausable.is_paused());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn events_are_emitted_on_changes() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let mut pausable = Pause::new();
+            pausable.pause().unwrap();
+            pausable.unpause().unwrap();
+
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 2);
+            assert_eq!(
+                scale::Decode::decode(&mut &events[0].data[..]),
+                Ok(Paused {
+                    account: accounts.bob
+                })
+            );
+            assert_eq!(
+                scale::Decode::decode(&mut &events[1].data[..]),
+                Ok(Unpaused {
+                    account: accounts.bob
+                })
+            );
+            assert_eq!(events[0].topics.len(), 2);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "while the contract is paused")]
+    fn ensure_not_paused_checks_given_pausable() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut pausable = Pause::new();
+            Pausable::<DefaultEnvironment>::new().pause().unwrap();
+            ensure_not_paused(&pausable);
+            pausable.pause().unwrap();
+            ensure_not_paused(&pausable);
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protection of ink! smart contracts against reentrant calls.
+//!
+//! A contract calling another contract with `ALLOW_REENTRY` may be called back
+//! before the outer call finished, while its storage is in an intermediate state.
+//! A [`ReentrancyGuard`] records in the storage that a call is in progress, so
+//! that such a callback can be rejected.
+//!
+//! Messages flagged with `#[ink(message, non_reentrant)]` enter a [`NonReentrant`]
+//! guard before their body and exit it afterwards, so that they revert if they are
+//! called again before they returned. All non-reentrant messages of a contract
+//! share the same guard.
+
+use crate::traits::{
+    AutoKey,
+    ManualKey,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::{
+    Key,
+    KeyComposer,
+};
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// The storage key of the guard shared by the messages flagged with
+/// `#[ink(non_reentrant)]`.
+const NON_REENTRANT_KEY: Key = KeyComposer::from_str("ink::storage::reentrancy");
+
+/// Returned if a guarded call is entered while another one is in progress.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ReentrancyError {
+    /// The guard was already entered by a call that did not return yet.
+    ReentrantCall,
+}
+
+/// A flag in the storage recording whether a guarded call is in progress.
+///
+/// # Important
+///
+/// The guard requires its own pre-defined storage key where to store the flag.
+/// By default, the key is automatically calculated using
+/// [`AutoKey`](crate::traits::AutoKey) during compilation. However, anyone can
+/// specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// The flag is only stored while the guard is entered, so that an idle guard does
+/// not occupy any storage.
+///
+/// This is an example of how you can use the guard:
+/// ```rust
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::security::reentrancy::{
+///     ReentrancyError,
+///     ReentrancyGuard,
+/// };
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct Vault {
+///     guard: ReentrancyGuard,
+///     withdrawn: Balance,
+/// }
+///
+/// impl Vault {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     #[ink(message)]
+///     pub fn withdraw(&mut self, value: Balance) -> Result<(), ReentrancyError> {
+///         self.guard.enter()?;
+///         // Calls to other contracts that may call back into this one.
+///         self.withdrawn += value;
+///         self.guard.exit();
+///         Ok(())
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReentrancyGuard<KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> KeyType>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<KeyType> Default for ReentrancyGuard<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<KeyType> ReentrancyGuard<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Creates a new guard which is not entered.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<KeyType> ::core::fmt::Debug for ReentrancyGuard<KeyType>
+where
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("ReentrancyGuard")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<KeyType> ReentrancyGuard<KeyType>
+where
+    KeyType: StorageKey,
+{
+    /// Returns `true` if a guarded call is in progress.
+    pub fn is_entered(&self) -> bool {
+        ink_env::contains_contract_storage(&KeyType::KEY).is_some()
+    }
+
+    /// Records that a guarded call is in progress.
+    ///
+    /// # Errors
+    ///
+    /// If another guarded call is already in progress.
+    pub fn enter(&mut self) -> Result<(), ReentrancyError> {
+        if self.is_entered() {
+            return Err(ReentrancyError::ReentrantCall)
+        }
+        ink_env::set_contract_storage(&KeyType::KEY, &true);
+        Ok(())
+    }
+
+    /// Records that the guarded call in progress returned.
+    pub fn exit(&mut self) {
+        ink_env::clear_contract_storage(&KeyType::KEY);
+    }
+}
+
+impl<KeyType> Storable for ReentrancyGuard<KeyType>
+where
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<O: Output + ?Sized>(&self, _dest: &mut O) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<Key, InnerKey> StorableHint<Key> for ReentrancyGuard<InnerKey>
+where
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = ReentrancyGuard<Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<KeyType> StorageKey for ReentrancyGuard<KeyType>
+where
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<KeyType> StorageLayout for ReentrancyGuard<KeyType>
+    where
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <Option<bool> as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+/// The guard entered by the messages flagged with `#[ink(non_reentrant)]`.
+///
+/// Exits the guard when dropped, so that it is also exited if the message returns
+/// early.
+#[must_use = "the guard is exited as soon as it is dropped"]
+pub struct NonReentrant {
+    guard: ReentrancyGuard<ManualKey<NON_REENTRANT_KEY>>,
+}
+
+impl NonReentrant {
+    /// Enters the guard shared by the messages flagged with `#[ink(non_reentrant)]`.
+    ///
+    /// # Panics
+    ///
+    /// If a non-reentrant message is already in progress, which reverts the call.
+    pub fn enter() -> Self {
+        let mut guard = ReentrancyGuard::new();
+        if guard.enter().is_err() {
+            panic!("encountered reentrant call of a non-reentrant ink! message")
+        }
+        Self { guard }
+    }
+}
+
+impl Drop for NonReentrant {
+    fn drop(&mut self) {
+        self.guard.exit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_env::DefaultEnvironment;
+
+    type Guard = ReentrancyGuard<ManualKey<123>>;
+
+    #[test]
+    fn enter_and_exit_works() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut guard = Guard::new();
+            assert!(!guard.is_entered());
+            assert_eq!(guard.enter(), Ok(()));
+            assert!(guard.is_entered());
+            assert_eq!(guard.enter(), Err(ReentrancyError::ReentrantCall));
+            // Other guards do not share the flag.
+            assert!(!ReentrancyGuard::<ManualKey<124>>::new().is_entered());
+
+            guard.exit();
+            assert!(!guard.is_entered());
+            assert_eq!(guard.enter(), Ok(()));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn non_reentrant_exits_when_dropped() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let non_reentrant = NonReentrant::enter();
+            assert!(non_reentrant.guard.is_entered());
+            drop(non_reentrant);
+            drop(NonReentrant::enter());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "encountered reentrant call of a non-reentrant ink! message"
+    )]
+    fn non_reentrant_rejects_reentrant_call() {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let _outer = NonReentrant::enter();
+            let _inner = NonReentrant::enter();
+            Ok(())
+        })
+        .unwrap()
+    }
+}
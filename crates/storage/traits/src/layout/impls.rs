@@ -38,8 +38,11 @@ use ink_prelude::{
 };
 use ink_primitives::{
     AccountId,
+    BoundedString,
+    BoundedVec,
     Hash,
     Key,
+    MaybeOwned,
 };
 use scale_info::TypeInfo;
 
@@ -172,6 +175,15 @@ where
     }
 }
 
+impl<'a, T> StorageLayout for MaybeOwned<'a, T>
+where
+    T: StorageLayout,
+{
+    fn layout(key: &Key) -> Layout {
+        <T as StorageLayout>::layout(key)
+    }
+}
+
 impl<T> StorageLayout for Option<T>
 where
     T: StorageLayout,
@@ -232,6 +244,32 @@ where
     }
 }
 
+/// Returns the capacity of a bounded collection as recorded in the storage layout.
+fn layout_capacity(capacity: usize) -> u32 {
+    u32::try_from(capacity).unwrap_or(u32::MAX)
+}
+
+impl<T, const N: usize> StorageLayout for BoundedVec<T, N>
+where
+    T: TypeInfo + 'static + Packed,
+{
+    fn layout(key: &Key) -> Layout {
+        Layout::Leaf(LeafLayout::from_key_bounded::<Self>(
+            LayoutKey::from(key),
+            layout_capacity(N),
+        ))
+    }
+}
+
+impl<const N: usize> StorageLayout for BoundedString<N> {
+    fn layout(key: &Key) -> Layout {
+        Layout::Leaf(LeafLayout::from_key_bounded::<Self>(
+            LayoutKey::from(key),
+            layout_capacity(N),
+        ))
+    }
+}
+
 impl<K, V> StorageLayout for BTreeMap<K, V>
 where
     K: TypeInfo + 'static + Packed,
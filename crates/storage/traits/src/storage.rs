@@ -83,6 +83,10 @@ pub trait StorageKey {
 ///
 /// The trait is automatically implemented for [`Packed`] types
 /// via blanket implementation.
+///
+/// Because of this blanket implementation, composite types such as `Option<T>`,
+/// `Result<T, E>`, tuples and arrays implement the trait only if they are [`Packed`].
+/// Non-packed composite types have to be defined with the `#[ink::storage_item]` macro.
 pub trait StorableHint<Key: StorageKey> {
     /// Storable type with storage key inside.
     type Type: Storable;
@@ -0,0 +1,459 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of Groth16 proofs.
+//!
+//! The curve arithmetic and the pairing are provided by the `substrate-bn` crate.
+
+use ink_prelude::vec::Vec;
+use scale::{
+    Decode,
+    Encode,
+};
+use substrate_bn::{
+    arith::U256,
+    pairing_batch,
+    AffineG1,
+    AffineG2,
+    Fq,
+    Fq2,
+    Fr,
+    Group,
+    Gt,
+    G1,
+    G2,
+};
+
+/// A point of `G1` encoded as the big-endian coordinates `x` and `y` of 32 bytes
+/// each.
+///
+/// The identity is encoded as zeros. This is the encoding of the Ethereum
+/// precompiles for the BN254 curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct G1Point(pub [u8; 64]);
+
+/// A point of `G2` encoded as the big-endian coordinates `x` and `y` of 64 bytes
+/// each.
+///
+/// Every coordinate `c0 + c1 * u` is encoded as `c1` followed by `c0`. The identity
+/// is encoded as zeros. This is the encoding of the Ethereum precompiles for the
+/// BN254 curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct G2Point(pub [u8; 128]);
+
+/// An element of the scalar field as big-endian integer less than the group order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Scalar(pub [u8; 32]);
+
+impl From<u128> for Scalar {
+    fn from(value: u128) -> Self {
+        let mut bytes = [0; 32];
+        bytes[16..].copy_from_slice(&value.to_be_bytes());
+        Self(bytes)
+    }
+}
+
+/// A Groth16 proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Proof {
+    /// The point `A` of `G1`.
+    pub a: G1Point,
+    /// The point `B` of `G2`.
+    pub b: G2Point,
+    /// The point `C` of `G1`.
+    pub c: G1Point,
+}
+
+/// The key to verify the Groth16 proofs of a circuit.
+///
+/// The points of the key are only checked when a proof is verified. Prefer the
+/// [`PreparedVerifyingKey`] returned by [`VerifyingKey::prepare`] to verify several
+/// proofs with the same key.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct VerifyingKey {
+    /// The point `alpha` of `G1`.
+    pub alpha: G1Point,
+    /// The point `beta` of `G2`.
+    pub beta: G2Point,
+    /// The point `gamma` of `G2`.
+    pub gamma: G2Point,
+    /// The point `delta` of `G2`.
+    pub delta: G2Point,
+    /// The points to combine the public inputs with, starting with the constant one.
+    pub ic: Vec<G1Point>,
+}
+
+/// A [`VerifyingKey`] whose points have been checked.
+///
+/// It can only be created by [`PreparedVerifyingKey::new`], which checks that all
+/// points are on the curve and that the points of `G2` are in the subgroup. The key
+/// is encoded like the [`VerifyingKey`] and decoding it checks the points again.
+///
+/// # Note
+///
+/// The subgroup checks are comparably expensive, hence every load of the key from
+/// the contract storage consumes a noticeable amount of gas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedVerifyingKey {
+    key: VerifyingKey,
+    points: Points,
+}
+
+/// The reason a proof was not verified.
+#[allow(trivial_numeric_casts)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// A point of `G1` is not on the curve or its coordinates are not canonical.
+    InvalidG1Point,
+    /// A point of `G2` is not in the subgroup or its coordinates are not canonical.
+    InvalidG2Point,
+    /// A public input is not less than the group order.
+    InvalidScalar,
+    /// The number of public inputs doesn't match the verifying key.
+    InvalidPublicInputCount,
+    /// The proof doesn't prove the statement of the public inputs.
+    InvalidProof,
+}
+
+impl VerifyingKey {
+    /// Verifies the `proof` for the `public_inputs`.
+    ///
+    /// # Errors
+    ///
+    /// - If any point of the key or the proof is invalid.
+    /// - If the number of public inputs doesn't match the key.
+    /// - If the proof is not valid for the public inputs.
+    pub fn verify(&self, proof: &Proof, public_inputs: &[Scalar]) -> Result<(), Error> {
+        Points::decode(self)?.verify(proof, public_inputs)
+    }
+
+    /// Checks all points of the key.
+    ///
+    /// # Errors
+    ///
+    /// If any point of the key is invalid.
+    pub fn prepare(&self) -> Result<PreparedVerifyingKey, Error> {
+        PreparedVerifyingKey::new(self.clone())
+    }
+}
+
+impl PreparedVerifyingKey {
+    /// Checks all points of the `key`.
+    ///
+    /// # Errors
+    ///
+    /// If any point of the key is invalid.
+    pub fn new(key: VerifyingKey) -> Result<Self, Error> {
+        let points = Points::decode(&key)?;
+        Ok(Self { key, points })
+    }
+
+    /// Returns the checked key.
+    pub fn key(&self) -> &VerifyingKey {
+        &self.key
+    }
+
+    /// Verifies the `proof` for the `public_inputs`.
+    ///
+    /// # Errors
+    ///
+    /// - If any point of the proof is invalid.
+    /// - If the number of public inputs doesn't match the key.
+    /// - If the proof is not valid for the public inputs.
+    pub fn verify(&self, proof: &Proof, public_inputs: &[Scalar]) -> Result<(), Error> {
+        self.points.verify(proof, public_inputs)
+    }
+}
+
+impl Encode for PreparedVerifyingKey {
+    fn size_hint(&self) -> usize {
+        self.key.size_hint()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        self.key.encode_to(dest)
+    }
+}
+
+impl Decode for PreparedVerifyingKey {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Self::new(VerifyingKey::decode(input)?)
+            .map_err(|_| "Verifying key has invalid points".into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl scale_info::TypeInfo for PreparedVerifyingKey {
+    type Identity = Self;
+
+    fn type_info() -> scale_info::Type {
+        scale_info::Type::builder()
+            .path(scale_info::Path::new(
+                "PreparedVerifyingKey",
+                module_path!(),
+            ))
+            .composite(scale_info::build::Fields::named().field(|field| {
+                field
+                    .ty::<VerifyingKey>()
+                    .name("key")
+                    .type_name("VerifyingKey")
+            }))
+    }
+}
+
+/// The checked points of a verifying key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Points {
+    alpha: G1,
+    beta: G2,
+    gamma: G2,
+    delta: G2,
+    ic: Vec<G1>,
+}
+
+impl Points {
+    fn decode(key: &VerifyingKey) -> Result<Self, Error> {
+        Ok(Self {
+            alpha: decode_g1(&key.alpha)?,
+            beta: decode_g2(&key.beta)?,
+            gamma: decode_g2(&key.gamma)?,
+            delta: decode_g2(&key.delta)?,
+            ic: key.ic.iter().map(decode_g1).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Checks `e(-A, B) * e(alpha, beta) * e(L, gamma) * e(C, delta) = 1` where `L`
+    /// is the combination of the public inputs.
+    fn verify(&self, proof: &Proof, public_inputs: &[Scalar]) -> Result<(), Error> {
+        let (constant, ic) = self
+            .ic
+            .split_first()
+            .filter(|(_, ic)| ic.len() == public_inputs.len())
+            .ok_or(Error::InvalidPublicInputCount)?;
+        let a = decode_g1(&proof.a)?;
+        let b = decode_g2(&proof.b)?;
+        let c = decode_g1(&proof.c)?;
+
+        let mut l = *constant;
+        for (input, point) in public_inputs.iter().zip(ic) {
+            l = l + *point * decode_scalar(input)?;
+        }
+
+        let pairs = [
+            (-a, b),
+            (self.alpha, self.beta),
+            (l, self.gamma),
+            (c, self.delta),
+        ];
+        if pairing_batch(&pairs) == Gt::one() {
+            Ok(())
+        } else {
+            Err(Error::InvalidProof)
+        }
+    }
+}
+
+fn decode_scalar(scalar: &Scalar) -> Result<Fr, Error> {
+    U256::from_slice(&scalar.0)
+        .ok()
+        .and_then(Fr::new)
+        .ok_or(Error::InvalidScalar)
+}
+
+/// Decodes the big-endian field element of the 32 bytes at `index`.
+fn decode_fq(bytes: &[u8], index: usize) -> Option<Fq> {
+    Fq::from_slice(&bytes[index * 32..(index + 1) * 32]).ok()
+}
+
+fn decode_g1(point: &G1Point) -> Result<G1, Error> {
+    if point.0 == [0; 64] {
+        return Ok(G1::zero())
+    }
+    let decode = |index| decode_fq(&point.0, index).ok_or(Error::InvalidG1Point);
+    // All points of the curve are in `G1` since its cofactor is one.
+    AffineG1::new(decode(0)?, decode(1)?)
+        .map(G1::from)
+        .map_err(|_| Error::InvalidG1Point)
+}
+
+fn decode_g2(point: &G2Point) -> Result<G2, Error> {
+    if point.0 == [0; 128] {
+        return Ok(G2::zero())
+    }
+    let decode = |index| decode_fq(&point.0, index).ok_or(Error::InvalidG2Point);
+    let x = Fq2::new(decode(1)?, decode(0)?);
+    let y = Fq2::new(decode(3)?, decode(2)?);
+    // Checks that the point is on the curve and in the subgroup.
+    AffineG2::new(x, y)
+        .map(G2::from)
+        .map_err(|_| Error::InvalidG2Point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex<const N: usize>(hex: &str) -> [u8; N] {
+        let mut bytes = [0; N];
+        for (byte, chunk) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            *byte = u8::from_str_radix(core::str::from_utf8(chunk).unwrap(), 16).unwrap();
+        }
+        bytes
+    }
+
+    /// The key of the test circuit of `risc0-groth16`, exported by `snarkjs`.
+    fn verifying_key() -> VerifyingKey {
+        VerifyingKey {
+            alpha: G1Point(hex("02dc744e59b3d00ffd61de8d61ac18db220976c01634b09b7532aade64ed2dd41d7a91192f8b382f0621d2735ff2c97cc0e68d068ac878cb06ac852e868be639")),
+            beta: G2Point(hex("270dfe92c202d7d10fb7df5caa491f449d1337ce531d910f952a4990417539fa04ce0fb5abe034d3bb5c41ed7d73b21869e82c8f39f62b70bfb0b3eaaefe53530d0f2e77869c1238b563d52d6140d1bd75931e9055550d1578ef3a299dd2042921c483230e95028a8ee06c9b2071aee86b59ff40d3bebea770bcd75d9c3b4e38")),
+            gamma: G2Point(hex("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa")),
+            delta: G2Point(hex("126af0a26cab66c4734a5a944c5771a27897a2988551a72971f2ee0ea5c787ab27fa3d486f6da42bbfe3ac8c3c1ab8bc5e892aab137f6656947caf062b05456b1fae50ae804740bf2349f00eeb7deae32133122692da9265a162e22541ea170a2641641291712c42b63633b978f204808a1537890be38acb44e7db7413491be2")),
+            ic: vec![
+                G1Point(hex("20e6744a9c23e2f3d8e7a5a789cda64794d0ed5a84a1dc7486c8569f185f884d1f4b1bb22bddc4b9d2caeae17a311b499e5f960792067a70034d431f24d02270")),
+                G1Point(hex("1bd5f4e4f770eaf1a2a40773e0bc860485a20115b68d5c0ce7f56a90b9a20ba6220033576e8d9e8579801f969d67e67f84ef8136bb7a8e2d9d4ccf235323cb16")),
+            ],
+        }
+    }
+
+    /// A proof of the test circuit of `risc0-groth16` for the public input `33`,
+    /// exported by `snarkjs`.
+    fn proof() -> Proof {
+        Proof {
+            a: G1Point(hex("2bab42c4ff2336339b486238247bfb19aa74bd53021df26ace515e663aa4b0e7187944728cddc0480b2799d58c522ecdf0337bbd6ad88dd0ed8ecbb5a3e2f2aa")),
+            b: G2Point(hex("1c4f0491e1c9e3e3763894e00de5fdb8516517c42e13ea654e69c8699bedbea7178afa48a33939ce9ba903f425faad8dd8c935d2c319d035e426ba9c9f7db45f154c98da14795bc57d2b050eada9c97ee5b6bffa0291148c6c12ff3e30a2b1f90588d6ed253ace86b59dc19b05ae61554f7bdc08addb88e09f74a8b6e80afe03")),
+            c: G1Point(hex("0777a5cd0062ff9073d34fbf22a9390146295d8bdab0b267092d305f8a86ca8607d32d54eb838df53558a9475d2d4c17d1eb376384ebe272398b0ef6ba41c439")),
+        }
+    }
+
+    fn public_inputs() -> [Scalar; 1] {
+        [Scalar::from(33)]
+    }
+
+    #[test]
+    fn valid_proof_is_verified() {
+        let key = verifying_key();
+        assert_eq!(key.verify(&proof(), &public_inputs()), Ok(()));
+        let prepared = key.prepare().unwrap();
+        assert_eq!(prepared.verify(&proof(), &public_inputs()), Ok(()));
+    }
+
+    #[test]
+    fn proof_for_other_inputs_is_rejected() {
+        let key = verifying_key().prepare().unwrap();
+        let mut inputs = public_inputs();
+        inputs[0] = Scalar::from(34);
+        assert_eq!(key.verify(&proof(), &inputs), Err(Error::InvalidProof));
+
+        let mut proof = proof();
+        core::mem::swap(&mut proof.a, &mut proof.c);
+        assert_eq!(
+            key.verify(&proof, &public_inputs()),
+            Err(Error::InvalidProof)
+        );
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        let key = verifying_key().prepare().unwrap();
+        assert_eq!(
+            key.verify(&proof(), &[]),
+            Err(Error::InvalidPublicInputCount)
+        );
+        assert_eq!(
+            key.verify(&proof(), &[Scalar([0xFF; 32])]),
+            Err(Error::InvalidScalar)
+        );
+
+        let mut proof = proof();
+        proof.a.0[63] ^= 1;
+        assert_eq!(
+            key.verify(&proof, &public_inputs()),
+            Err(Error::InvalidG1Point)
+        );
+    }
+
+    #[test]
+    fn pairing_precompile_vector_is_checked() {
+        // The two pairs of the `jeff1` test vector of the EIP-197 pairing check.
+        let pairs = [
+            (
+                G1Point(hex("1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f593034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41")),
+                G2Point(hex("209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf704bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a416782bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550")),
+            ),
+            (
+                G1Point(hex("111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411")),
+                G2Point(hex("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa")),
+            ),
+        ];
+        let mut decoded = pairs
+            .iter()
+            .map(|(p, q)| Ok((decode_g1(p)?, decode_g2(q)?)))
+            .collect::<Result<Vec<_>, Error>>()
+            .unwrap();
+        assert!(pairing_batch(&decoded) == Gt::one());
+        decoded[0].0 = -decoded[0].0;
+        assert!(pairing_batch(&decoded) != Gt::one());
+        // Points which are not on the curve are rejected.
+        assert_eq!(decode_g1(&G1Point([0x11; 64])), Err(Error::InvalidG1Point));
+    }
+
+    #[test]
+    fn points_outside_of_the_subgroup_are_rejected() {
+        // The point `(1, y)` on the twist is not in `G2`.
+        let point = G2Point(hex("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010d1271953ed9ea0836846e70a1934187998c7f790cb4d7511b7f8da82de048a42869111d5381f072f8e2728fdb825a51aadd70e52c9830e9ab4b871c0531f1bb"));
+        assert_eq!(decode_g2(&point), Err(Error::InvalidG2Point));
+
+        let mut proof = proof();
+        proof.b = point;
+        assert_eq!(
+            verifying_key().verify(&proof, &public_inputs()),
+            Err(Error::InvalidG2Point)
+        );
+        for key in [
+            VerifyingKey {
+                beta: point,
+                ..verifying_key()
+            },
+            VerifyingKey {
+                gamma: point,
+                ..verifying_key()
+            },
+            VerifyingKey {
+                delta: point,
+                ..verifying_key()
+            },
+        ] {
+            assert_eq!(key.prepare(), Err(Error::InvalidG2Point));
+            // Decoding a prepared key checks its points as well.
+            assert!(PreparedVerifyingKey::decode(&mut &key.encode()[..]).is_err());
+        }
+    }
+
+    #[test]
+    fn prepared_key_is_encoded_like_the_key() {
+        let prepared = verifying_key().prepare().unwrap();
+        let encoded = prepared.encode();
+        assert_eq!(encoded, verifying_key().encode());
+        assert_eq!(
+            PreparedVerifyingKey::decode(&mut &encoded[..]),
+            Ok(prepared)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The storage layout of the types which can be stored in the contract storage.
+
+use crate::{
+    G1Point,
+    G2Point,
+    PreparedVerifyingKey,
+    Proof,
+    Scalar,
+    VerifyingKey,
+};
+use ink_metadata::layout::{
+    Layout,
+    LayoutKey,
+    LeafLayout,
+};
+use ink_primitives::Key;
+use ink_storage_traits::StorageLayout;
+
+macro_rules! impl_storage_layout_for_leafs {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl StorageLayout for $ty {
+                fn layout(key: &Key) -> Layout {
+                    Layout::Leaf(LeafLayout::from_key::<Self>(LayoutKey::from(key)))
+                }
+            }
+        )*
+    };
+}
+impl_storage_layout_for_leafs!(
+    G1Point,
+    G2Point,
+    Scalar,
+    Proof,
+    VerifyingKey,
+    PreparedVerifyingKey,
+);
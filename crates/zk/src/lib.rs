@@ -0,0 +1,85 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-knowledge proof verification for ink! smart contracts.
+//!
+//! Provides a verifier of Groth16 proofs over the BN254 curve, also known as
+//! `alt_bn128`, e.g. for private airdrops or rollup verifiers. Points are encoded
+//! like by the Ethereum precompiles, so that the keys and proofs exported by the
+//! common tooling can be used as they are.
+//!
+//! All types can be stored in the contract storage, usually as the
+//! [`PreparedVerifyingKey`] of the circuit whose proofs the contract accepts.
+//!
+//! # Note
+//!
+//! The contracts pallet provides no host functions for curve operations, hence the
+//! whole verification is executed in Wasm and a single verification consumes a
+//! considerable amount of gas.
+//!
+//! # Example
+//!
+//! ```
+//! use ink_zk::{
+//!     Error,
+//!     PreparedVerifyingKey,
+//!     Proof,
+//!     Scalar,
+//! };
+//!
+//! fn claim(key: &PreparedVerifyingKey, proof: &Proof, nullifier: u128) -> Result<(), Error> {
+//!     key.verify(proof, &[Scalar::from(nullifier)])?;
+//!     // Pay out the claim ...
+//!     Ok(())
+//! }
+//! ```
+
+#![doc(
+    html_logo_url = "https://use.ink/img/crate-docs/logo.png",
+    html_favicon_url = "https://use.ink/crate-docs/favicon.png"
+)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(
+    missing_docs,
+    bad_style,
+    bare_trait_objects,
+    improper_ctypes,
+    non_shorthand_field_patterns,
+    no_mangle_generic_items,
+    overflowing_literals,
+    path_statements,
+    patterns_in_fns_without_body,
+    unconditional_recursion,
+    unused_allocation,
+    unused_comparisons,
+    unused_parens,
+    while_true,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_extern_crates
+)]
+
+mod groth16;
+#[cfg(feature = "std")]
+mod layout;
+
+pub use self::groth16::{
+    Error,
+    G1Point,
+    G2Point,
+    PreparedVerifyingKey,
+    Proof,
+    Scalar,
+    VerifyingKey,
+};